@@ -1,12 +1,30 @@
 use bencher::{benchmark_group, benchmark_main, black_box, Bencher};
 use rand::Rng;
 use ta::indicators::{
-    AverageTrueRange, BollingerBands, ChandelierExit, CommodityChannelIndex, EfficiencyRatio,
-    ExponentialMovingAverage, FastStochastic, KeltnerChannel, Maximum, MeanAbsoluteDeviation,
-    Minimum, MoneyFlowIndex, MovingAverageConvergenceDivergence, OnBalanceVolume,
-    PercentagePriceOscillator, RateOfChange, RelativeStrengthIndex, SimpleMovingAverage,
-    SlowStochastic, StandardDeviation, TrueRange, WeightedMovingAverage,
+    AccelerationBands, AcceleratorOscillator, AccumulationDistributionLine, Alligator,
+    AnchoredVwap, AtrTrailingStop, Autocorrelation, AverageTrueRange, AwesomeOscillator,
+    BalanceOfPower, BollingerBands, CenterOfGravity, ChaikinMoneyFlow, ChandeMomentumOscillator,
+    ChandelierExit, ChoppinessIndex, CommodityChannelIndex, ConnorsRsi, CumulativeReturn,
+    DetrendedPriceOscillator, DisparityIndex, DominantCyclePeriod, DonchianChannel, DrawdownStats,
+    EaseOfMovement, EfficiencyRatio, ElderRay, ExponentialMovingAverage, FastStochastic,
+    FibonacciLevels, FisherTransform, ForceIndex, FractalAdaptiveMovingAverage,
+    FractalDimensionIndex, GatorOscillator, GeometricMovingAverage, HarmonicMovingAverage,
+    HistoricalVolatility, InsideOutsideBar, KaufmanAdaptiveMovingAverage, KeltnerChannel,
+    LaguerreFilter, LaguerreRsi, LinearRegression, LogReturn, MarketFacilitationIndex, Maximum,
+    MaximumConst, McGinleyDynamic, MeanAbsoluteDeviation, MedianPrice, MesaAdaptiveMovingAverage,
+    Minimum, MinimumConst, MoneyFlowIndex, MovingAverage, MovingAverageConvergenceDivergence,
+    MovingAverageRibbon, NegativeVolumeIndex, OnBalanceVolume, PercentagePriceOscillator,
+    PercentageVolumeOscillator, PositiveVolumeIndex, PriceVolumeTrend, Qqe, RangeVolatility,
+    RateOfChange, RelativeStrengthIndex, RelativeVigorIndex, RollingEntropy, RollingQuantile,
+    RoofingFilter, SimpleMovingAverage, SimpleMovingAverageConst, SimpleReturn, SlowStochastic,
+    SqueezeMomentum, StandardDeviation, StandardErrorBands, StochasticMomentumIndex, SuperSmoother,
+    SupportResistance, SwingPoints, TillsonT3, TrendIntensityIndex, TriangularMovingAverage,
+    TripleExponentialMovingAverage, Trix, TrueRange, TrueStrengthIndex, TwiggsMoneyFlow,
+    TypicalPrice, UltimateOscillator, VolumeOscillator, VolumeProfile, VolumeRateOfChange,
+    VolumeWeightedAveragePrice, VolumeWeightedMovingAverage, VortexIndicator, WeightedClose,
+    WeightedMovingAverage, WilliamsFractals, ZScore,
 };
+use ta::transforms::{HeikinAshi, RenkoBuilder};
 use ta::{DataItem, Next};
 
 const ITEMS_COUNT: usize = 5_000;
@@ -30,6 +48,10 @@ fn rand_data_item() -> DataItem {
         .unwrap()
 }
 
+type SimpleMovingAverageConst9 = SimpleMovingAverageConst<9>;
+type MaximumConst14 = MaximumConst<14>;
+type MinimumConst14 = MinimumConst<14>;
+
 macro_rules! bench_indicators {
     ($($indicator:ident), *) => {
         $(
@@ -73,5 +95,88 @@ bench_indicators!(
     SlowStochastic,
     StandardDeviation,
     TrueRange,
-    WeightedMovingAverage
+    WeightedMovingAverage,
+    TripleExponentialMovingAverage,
+    KaufmanAdaptiveMovingAverage,
+    VolumeWeightedAveragePrice,
+    AnchoredVwap,
+    DonchianChannel,
+    VortexIndicator,
+    Trix,
+    UltimateOscillator,
+    AwesomeOscillator,
+    AcceleratorOscillator,
+    ChaikinMoneyFlow,
+    AccumulationDistributionLine,
+    ForceIndex,
+    ElderRay,
+    DetrendedPriceOscillator,
+    ChoppinessIndex,
+    ConnorsRsi,
+    TrueStrengthIndex,
+    FisherTransform,
+    HeikinAshi,
+    McGinleyDynamic,
+    FractalAdaptiveMovingAverage,
+    TillsonT3,
+    EaseOfMovement,
+    PriceVolumeTrend,
+    BalanceOfPower,
+    RelativeVigorIndex,
+    Qqe,
+    SqueezeMomentum,
+    LinearRegression,
+    ZScore,
+    RollingQuantile,
+    MesaAdaptiveMovingAverage,
+    DominantCyclePeriod,
+    ChandeMomentumOscillator,
+    StochasticMomentumIndex,
+    WilliamsFractals,
+    AtrTrailingStop,
+    VolumeWeightedMovingAverage,
+    PercentageVolumeOscillator,
+    VolumeOscillator,
+    VolumeRateOfChange,
+    NegativeVolumeIndex,
+    PositiveVolumeIndex,
+    HistoricalVolatility,
+    RangeVolatility,
+    DrawdownStats,
+    SimpleReturn,
+    LogReturn,
+    CumulativeReturn,
+    MovingAverageRibbon,
+    InsideOutsideBar,
+    SwingPoints,
+    SupportResistance,
+    VolumeProfile,
+    RenkoBuilder,
+    Alligator,
+    GatorOscillator,
+    LaguerreFilter,
+    LaguerreRsi,
+    SuperSmoother,
+    RoofingFilter,
+    CenterOfGravity,
+    MovingAverage,
+    DisparityIndex,
+    TrendIntensityIndex,
+    TriangularMovingAverage,
+    GeometricMovingAverage,
+    HarmonicMovingAverage,
+    TypicalPrice,
+    MedianPrice,
+    WeightedClose,
+    TwiggsMoneyFlow,
+    MarketFacilitationIndex,
+    StandardErrorBands,
+    AccelerationBands,
+    Autocorrelation,
+    RollingEntropy,
+    FibonacciLevels,
+    FractalDimensionIndex,
+    SimpleMovingAverageConst9,
+    MaximumConst14,
+    MinimumConst14
 );
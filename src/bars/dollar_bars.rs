@@ -0,0 +1,157 @@
+use core::fmt;
+
+use super::{add_trade, PartialBar};
+use crate::errors::{Result, TaError};
+use crate::{Checkpoint, DataItem, Next, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Dollar bars.
+///
+/// Builds a bar from trades until their total traded value (price × size) reaches _value_.
+/// Compared to [volume bars](struct.VolumeBars.html) the bars adapt to the price level, so a
+/// bar covers about the same amount of money over long periods. Trades are not split, so the
+/// value of a bar can be larger than _value_.
+///
+/// # Parameters
+///
+/// * _value_ - traded value per bar (number greater than 0).
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::DollarBars;
+/// use ta::{Next, Volume};
+///
+/// let mut bars = DollarBars::new(1000.0).unwrap();
+///
+/// assert!(bars.next((10.0, 60.0)).is_none());
+///
+/// let bar = bars.next((20.0, 20.0)).unwrap();
+/// assert_eq!(bar.volume(), 80.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DollarBars {
+    value: f64,
+    partial: Option<PartialBar>,
+}
+
+/// State of [DollarBars](struct.DollarBars.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DollarBarsState {
+    pub value: f64,
+    pub partial: Option<PartialBar>,
+}
+
+impl DollarBars {
+    pub fn new(value: f64) -> Result<Self> {
+        if !value.is_finite() || value <= 0.0 {
+            return Err(TaError::invalid_parameter("value", value, "> 0"));
+        }
+        Ok(Self {
+            value,
+            partial: None,
+        })
+    }
+
+    /// Bar that is being built, if any trades were added since the last completed bar.
+    pub fn partial(&self) -> Option<DataItem> {
+        self.partial.as_ref().map(PartialBar::to_item)
+    }
+}
+
+impl Checkpoint for DollarBars {
+    type State = DollarBarsState;
+
+    fn state(&self) -> Self::State {
+        DollarBarsState {
+            value: self.value,
+            partial: self.partial.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut bars = Self::new(state.value).map_err(|_| TaError::InvalidState)?;
+        // A bar that reached the threshold would have been completed
+        if let Some(bar) = &state.partial {
+            if bar.ticks == 0 || bar.value >= state.value {
+                return Err(TaError::InvalidState);
+            }
+        }
+        bars.partial = state.partial;
+        Ok(bars)
+    }
+}
+
+impl_versioned_serde!(DollarBars, 1);
+
+impl Next<(f64, f64)> for DollarBars {
+    type Output = Option<DataItem>;
+
+    fn next(&mut self, (price, size): (f64, f64)) -> Self::Output {
+        if add_trade(&mut self.partial, price, size).value >= self.value {
+            self.partial.take().map(|bar| bar.to_item())
+        } else {
+            None
+        }
+    }
+}
+
+impl Reset for DollarBars {
+    fn reset(&mut self) {
+        self.partial = None;
+    }
+}
+
+impl fmt::Display for DollarBars {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DOLLARBARS({})", self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Close, Open, Volume};
+
+    #[test]
+    fn test_new() {
+        assert!(DollarBars::new(0.0).is_err());
+        assert!(DollarBars::new(f64::INFINITY).is_err());
+        assert!(DollarBars::new(100.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut bars = DollarBars::new(100.0).unwrap();
+
+        assert!(bars.next((10.0, 5.0)).is_none());
+        assert!(bars.next((20.0, 2.0)).is_none());
+        assert_eq!(bars.partial().unwrap().volume(), 7.0);
+
+        let bar = bars.next((10.0, 1.0)).unwrap();
+        assert_eq!(bar.open(), 10.0);
+        assert_eq!(bar.close(), 10.0);
+        assert_eq!(bar.volume(), 8.0);
+
+        // Same size, higher price
+        assert_eq!(bars.next((50.0, 2.0)).unwrap().volume(), 2.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bars = DollarBars::new(100.0).unwrap();
+
+        bars.next((10.0, 5.0));
+        bars.reset();
+        assert!(bars.partial().is_none());
+        assert!(bars.next((10.0, 5.0)).is_none());
+    }
+
+    #[test]
+    fn test_display() {
+        let bars = DollarBars::new(1000000.0).unwrap();
+        assert_eq!(format!("{}", bars), "DOLLARBARS(1000000)");
+    }
+}
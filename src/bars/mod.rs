@@ -0,0 +1,103 @@
+//! Bar aggregators build OHLCV bars from a stream of trades or from lower timeframe bars.
+//!
+//! Every trade is a `(price, size)` pair. An aggregator collects trades into a bar until its
+//! threshold is reached and then returns the completed bar as a [DataItem](crate::DataItem),
+//! which can be fed into any indicator. The bar that is still being built is available through
+//! `partial()`.
+//!
+//! The [Resampler](struct.Resampler.html) does the same based on time, for trades or bars
+//! with timestamps.
+
+use crate::{Close, DataItem, High, Low, Open, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod dollar_bars;
+pub use self::dollar_bars::{DollarBars, DollarBarsState};
+
+mod range_bars;
+pub use self::range_bars::{RangeBars, RangeBarsState};
+
+mod resampler;
+pub use self::resampler::{Resampler, ResamplerState};
+
+mod tick_bars;
+pub use self::tick_bars::{TickBars, TickBarsState};
+
+mod volume_bars;
+pub use self::volume_bars::{VolumeBars, VolumeBarsState};
+
+/// Bar that is still being built from trades, part of the state of the aggregators, see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialBar {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    /// Sum of price times size of the trades.
+    pub value: f64,
+    /// Number of trades.
+    pub ticks: usize,
+}
+
+impl PartialBar {
+    fn new(price: f64, size: f64) -> Self {
+        Self {
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: size,
+            value: price * size,
+            ticks: 1,
+        }
+    }
+
+    fn from_bar<T: Open + High + Low + Close + Volume>(bar: &T) -> Self {
+        Self {
+            open: bar.open(),
+            high: bar.high(),
+            low: bar.low(),
+            close: bar.close(),
+            volume: bar.volume(),
+            value: bar.close() * bar.volume(),
+            ticks: 1,
+        }
+    }
+
+    fn update(&mut self, price: f64, size: f64) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        self.volume += size;
+        self.value += price * size;
+        self.ticks += 1;
+    }
+
+    fn update_bar<T: High + Low + Close + Volume>(&mut self, bar: &T) {
+        self.high = self.high.max(bar.high());
+        self.low = self.low.min(bar.low());
+        self.close = bar.close();
+        self.volume += bar.volume();
+        self.value += bar.close() * bar.volume();
+        self.ticks += 1;
+    }
+
+    fn to_item(&self) -> DataItem {
+        DataItem::new_unchecked(self.open, self.high, self.low, self.close, self.volume)
+    }
+}
+
+/// Adds a trade to the partial bar, starting a new one when there is none.
+fn add_trade(partial: &mut Option<PartialBar>, price: f64, size: f64) -> &PartialBar {
+    match partial {
+        Some(bar) => {
+            bar.update(price, size);
+            bar
+        }
+        None => partial.insert(PartialBar::new(price, size)),
+    }
+}
@@ -0,0 +1,210 @@
+use core::fmt;
+
+use super::{add_trade, PartialBar};
+use crate::errors::{Result, TaError};
+use crate::{Checkpoint, DataItem, Next, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Range bars.
+///
+/// Builds a bar from trades until the difference between its high and its low reaches
+/// _range_. The trade that reaches the range closes the bar, and the next trade opens a new
+/// one.
+///
+/// Bars never exceed the range. A trade that gaps beyond it closes the bar at the limit, i.e.
+/// its low plus _range_ or its high minus _range_, and opens the next bar at its own price.
+///
+/// # Parameters
+///
+/// * _range_ - price range per bar (number greater than 0).
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::RangeBars;
+/// use ta::{High, Low, Next};
+///
+/// let mut bars = RangeBars::new(1.0).unwrap();
+///
+/// assert!(bars.next((10.0, 5.0)).is_none());
+/// assert!(bars.next((10.5, 5.0)).is_none());
+///
+/// let bar = bars.next((9.5, 5.0)).unwrap();
+/// assert_eq!(bar.high(), 10.5);
+/// assert_eq!(bar.low(), 9.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RangeBars {
+    range: f64,
+    partial: Option<PartialBar>,
+}
+
+/// State of [RangeBars](struct.RangeBars.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeBarsState {
+    pub range: f64,
+    pub partial: Option<PartialBar>,
+}
+
+impl RangeBars {
+    pub fn new(range: f64) -> Result<Self> {
+        if !range.is_finite() || range <= 0.0 {
+            return Err(TaError::invalid_parameter("range", range, "> 0"));
+        }
+        Ok(Self {
+            range,
+            partial: None,
+        })
+    }
+
+    /// Bar that is being built, if any trades were added since the last completed bar.
+    pub fn partial(&self) -> Option<DataItem> {
+        self.partial.as_ref().map(PartialBar::to_item)
+    }
+}
+
+impl Checkpoint for RangeBars {
+    type State = RangeBarsState;
+
+    fn state(&self) -> Self::State {
+        RangeBarsState {
+            range: self.range,
+            partial: self.partial.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut bars = Self::new(state.range).map_err(|_| TaError::InvalidState)?;
+        // A bar that reached the threshold would have been completed
+        if let Some(bar) = &state.partial {
+            if bar.ticks == 0 || bar.high - bar.low >= state.range {
+                return Err(TaError::InvalidState);
+            }
+        }
+        bars.partial = state.partial;
+        Ok(bars)
+    }
+}
+
+impl_versioned_serde!(RangeBars, 1);
+
+impl Next<(f64, f64)> for RangeBars {
+    type Output = Option<DataItem>;
+
+    fn next(&mut self, (price, size): (f64, f64)) -> Self::Output {
+        if let Some(bar) = self.partial.as_mut() {
+            if price - bar.low > self.range || bar.high - price > self.range {
+                let limit = if price > bar.high {
+                    bar.low + self.range
+                } else {
+                    bar.high - self.range
+                };
+                bar.high = bar.high.max(limit);
+                bar.low = bar.low.min(limit);
+                bar.close = limit;
+                let completed = bar.to_item();
+                self.partial = None;
+                add_trade(&mut self.partial, price, size);
+                return Some(completed);
+            }
+        }
+
+        let bar = add_trade(&mut self.partial, price, size);
+        if bar.high - bar.low >= self.range {
+            self.partial.take().map(|bar| bar.to_item())
+        } else {
+            None
+        }
+    }
+}
+
+impl Reset for RangeBars {
+    fn reset(&mut self) {
+        self.partial = None;
+    }
+}
+
+impl fmt::Display for RangeBars {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RANGEBARS({})", self.range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Close, High, Low, Open, Volume};
+
+    #[test]
+    fn test_new() {
+        assert!(RangeBars::new(0.0).is_err());
+        assert!(RangeBars::new(-1.0).is_err());
+        assert!(RangeBars::new(0.25).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut bars = RangeBars::new(2.0).unwrap();
+
+        assert!(bars.next((10.0, 1.0)).is_none());
+        assert!(bars.next((11.0, 1.0)).is_none());
+        assert!(bars.next((9.5, 1.0)).is_none());
+        assert_eq!(bars.partial().unwrap().low(), 9.5);
+
+        let bar = bars.next((11.5, 2.0)).unwrap();
+        assert_eq!(bar.open(), 10.0);
+        assert_eq!(bar.high(), 11.5);
+        assert_eq!(bar.low(), 9.5);
+        assert_eq!(bar.close(), 11.5);
+        assert_eq!(bar.volume(), 5.0);
+
+        assert!(bars.next((11.0, 1.0)).is_none());
+        assert_eq!(bars.partial().unwrap().open(), 11.0);
+    }
+
+    #[test]
+    fn test_next_gap() {
+        let mut bars = RangeBars::new(2.0).unwrap();
+
+        assert!(bars.next((10.0, 1.0)).is_none());
+        assert!(bars.next((11.0, 1.0)).is_none());
+
+        // Gaps up by 3 over the low, the bar is capped at 10 + 2
+        let bar = bars.next((13.0, 2.0)).unwrap();
+        assert_eq!(bar.open(), 10.0);
+        assert_eq!(bar.high(), 12.0);
+        assert_eq!(bar.low(), 10.0);
+        assert_eq!(bar.close(), 12.0);
+        assert_eq!(bar.volume(), 2.0);
+
+        // The gap trade opens the next bar
+        let partial = bars.partial().unwrap();
+        assert_eq!(partial.open(), 13.0);
+        assert_eq!(partial.volume(), 2.0);
+
+        // Gaps down, capped at 13 - 2
+        let bar = bars.next((7.0, 1.0)).unwrap();
+        assert_eq!(bar.high(), 13.0);
+        assert_eq!(bar.low(), 11.0);
+        assert_eq!(bar.close(), 11.0);
+        assert_eq!(bars.partial().unwrap().open(), 7.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bars = RangeBars::new(2.0).unwrap();
+
+        bars.next((10.0, 1.0));
+        bars.reset();
+        assert!(bars.partial().is_none());
+        assert!(bars.next((12.0, 1.0)).is_none());
+    }
+
+    #[test]
+    fn test_display() {
+        let bars = RangeBars::new(0.5).unwrap();
+        assert_eq!(format!("{}", bars), "RANGEBARS(0.5)");
+    }
+}
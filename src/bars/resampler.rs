@@ -0,0 +1,308 @@
+use core::fmt;
+
+use super::{add_trade, PartialBar};
+use crate::errors::{Result, TaError};
+use crate::{Checkpoint, Close, DataItem, High, Low, Next, Open, Reset, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Time-based resampler.
+///
+/// Aggregates timestamped bars or trades into bars of a higher timeframe, e.g. 1 minute bars
+/// into 5 minute, 1 hour or daily bars. Every input is assigned to the period of length
+/// _interval_ that contains its timestamp. Periods start at multiples of _interval_ shifted by
+/// _offset_, which moves the boundaries to the start of a session, e.g. daily bars of a market
+/// opening at 09:30 UTC.
+///
+/// Timestamps, _interval_ and _offset_ can be in any unit (seconds, milliseconds, ...) as long
+/// as it is the same for all of them. A timestamp marks the start of a bar.
+///
+/// A higher timeframe bar is completed when the first input of a later period arrives. It is
+/// then returned together with the timestamp of the start of its period. Periods without any
+/// input produce no bar. Inputs with a timestamp before the current period are added to the
+/// current bar. The bar that is still forming is available through [partial](#method.partial),
+/// and [flush](#method.flush) completes it early, e.g. at the end of the data.
+///
+/// Bars are fed as `(timestamp, &bar)` and trades as `(timestamp, price, size)`.
+///
+/// # Parameters
+///
+/// * _interval_ - length of the higher timeframe (integer greater than 0).
+/// * _offset_ - shift of the period boundaries (integer).
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::Resampler;
+/// use ta::{Close, DataItem, High, Next};
+///
+/// fn bar(close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(close).high(close + 1.0).low(close - 1.0).close(close)
+///         .volume(100.0)
+///         .build().unwrap()
+/// }
+///
+/// // 1 minute bars into 5 minute bars, timestamps in seconds
+/// let mut resampler = Resampler::new(300, 0).unwrap();
+///
+/// assert!(resampler.next((0, &bar(10.0))).is_none());
+/// assert!(resampler.next((60, &bar(12.0))).is_none());
+/// assert!(resampler.next((240, &bar(11.0))).is_none());
+/// assert_eq!(resampler.partial().unwrap().high(), 13.0);
+///
+/// let (start, bar) = resampler.next((300, &bar(14.0))).unwrap();
+/// assert_eq!(start, 0);
+/// assert_eq!(bar.close(), 11.0);
+/// ```
+#[doc(alias = "RESAMPLE")]
+#[derive(Debug, Clone)]
+pub struct Resampler {
+    interval: i64,
+    offset: i64,
+    start: i64,
+    partial: Option<PartialBar>,
+}
+
+/// State of [Resampler](struct.Resampler.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResamplerState {
+    pub interval: i64,
+    /// Offset of the periods, from 0 up to the interval.
+    pub offset: i64,
+    /// Start of the period of the bar that is forming.
+    pub start: i64,
+    pub partial: Option<PartialBar>,
+}
+
+impl Resampler {
+    pub fn new(interval: i64, offset: i64) -> Result<Self> {
+        if interval <= 0 {
+            return Err(TaError::invalid_parameter(
+                "interval",
+                interval as f64,
+                ">= 1",
+            ));
+        }
+        Ok(Self {
+            interval,
+            offset: offset.rem_euclid(interval),
+            start: 0,
+            partial: None,
+        })
+    }
+
+    pub fn interval(&self) -> i64 {
+        self.interval
+    }
+
+    /// Start of the period that contains `timestamp`.
+    pub fn period_start(&self, timestamp: i64) -> i64 {
+        (timestamp - self.offset).div_euclid(self.interval) * self.interval + self.offset
+    }
+
+    /// Bar that is forming, if any inputs were added since the last completed bar.
+    pub fn partial(&self) -> Option<DataItem> {
+        self.partial.as_ref().map(PartialBar::to_item)
+    }
+
+    /// Start of the period of the bar that is forming.
+    pub fn partial_start(&self) -> Option<i64> {
+        self.partial.as_ref().map(|_| self.start)
+    }
+
+    /// Completes the forming bar without waiting for the next period.
+    pub fn flush(&mut self) -> Option<(i64, DataItem)> {
+        self.partial.take().map(|bar| (self.start, bar.to_item()))
+    }
+
+    /// Completes the forming bar if `timestamp` belongs to a later period and moves on to the
+    /// period of `timestamp`.
+    fn advance(&mut self, timestamp: i64) -> Option<(i64, DataItem)> {
+        let start = self.period_start(timestamp);
+        if self.partial.is_some() && start <= self.start {
+            return None;
+        }
+        let completed = self.flush();
+        self.start = start;
+        completed
+    }
+}
+
+impl Checkpoint for Resampler {
+    type State = ResamplerState;
+
+    fn state(&self) -> Self::State {
+        ResamplerState {
+            interval: self.interval,
+            offset: self.offset,
+            start: self.start,
+            partial: self.partial.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut resampler =
+            Self::new(state.interval, state.offset).map_err(|_| TaError::InvalidState)?;
+        if resampler.offset != state.offset
+            || state.start.wrapping_sub(state.offset) % state.interval != 0
+            || state.partial.as_ref().is_some_and(|bar| bar.ticks == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        resampler.start = state.start;
+        resampler.partial = state.partial;
+        Ok(resampler)
+    }
+}
+
+impl_versioned_serde!(Resampler, 1);
+
+impl<T: Open + High + Low + Close + Volume> Next<(i64, &T)> for Resampler {
+    type Output = Option<(i64, DataItem)>;
+
+    fn next(&mut self, (timestamp, bar): (i64, &T)) -> Self::Output {
+        let completed = self.advance(timestamp);
+        match self.partial.as_mut() {
+            Some(partial) => partial.update_bar(bar),
+            None => self.partial = Some(PartialBar::from_bar(bar)),
+        }
+        completed
+    }
+}
+
+impl Next<(i64, f64, f64)> for Resampler {
+    type Output = Option<(i64, DataItem)>;
+
+    fn next(&mut self, (timestamp, price, size): (i64, f64, f64)) -> Self::Output {
+        let completed = self.advance(timestamp);
+        add_trade(&mut self.partial, price, size);
+        completed
+    }
+}
+
+impl Reset for Resampler {
+    fn reset(&mut self) {
+        self.start = 0;
+        self.partial = None;
+    }
+}
+
+impl fmt::Display for Resampler {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RESAMPLE({}, {})", self.interval, self.offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn bar(open: f64, high: f64, low: f64, close: f64) -> Bar {
+        Bar::new()
+            .open(open)
+            .high(high)
+            .low(low)
+            .close(close)
+            .volume(10.0)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Resampler::new(0, 0).is_err());
+        assert!(Resampler::new(-60, 0).is_err());
+        assert!(Resampler::new(60, 0).is_ok());
+    }
+
+    #[test]
+    fn test_period_start() {
+        let resampler = Resampler::new(100, 30).unwrap();
+
+        assert_eq!(resampler.period_start(30), 30);
+        assert_eq!(resampler.period_start(129), 30);
+        assert_eq!(resampler.period_start(130), 130);
+        assert_eq!(resampler.period_start(29), -70);
+        assert_eq!(resampler.period_start(-75), -170);
+    }
+
+    #[test]
+    fn test_next_bars() {
+        let mut resampler = Resampler::new(3, 0).unwrap();
+
+        assert!(resampler.next((0, &bar(10.0, 11.0, 9.0, 10.5))).is_none());
+        assert!(resampler.next((1, &bar(10.5, 13.0, 10.0, 12.0))).is_none());
+        assert!(resampler.next((2, &bar(12.0, 12.5, 8.0, 9.0))).is_none());
+        assert_eq!(resampler.partial_start(), Some(0));
+
+        let (start, item) = resampler.next((3, &bar(9.0, 9.5, 8.5, 9.0))).unwrap();
+        assert_eq!(start, 0);
+        assert_eq!(item.open(), 10.0);
+        assert_eq!(item.high(), 13.0);
+        assert_eq!(item.low(), 8.0);
+        assert_eq!(item.close(), 9.0);
+        assert_eq!(item.volume(), 30.0);
+
+        // Gap: nothing between 4 and 8
+        let (start, item) = resampler.next((9, &bar(7.0, 8.0, 6.0, 7.5))).unwrap();
+        assert_eq!(start, 3);
+        assert_eq!(item.open(), 9.0);
+        assert_eq!(item.volume(), 10.0);
+        assert_eq!(resampler.partial_start(), Some(9));
+
+        // Late input goes into the forming bar
+        assert!(resampler.next((5, &bar(7.5, 8.5, 7.0, 8.0))).is_none());
+        assert_eq!(resampler.partial().unwrap().high(), 8.5);
+
+        let (start, item) = resampler.flush().unwrap();
+        assert_eq!(start, 9);
+        assert_eq!(item.close(), 8.0);
+        assert!(resampler.partial().is_none());
+        assert!(resampler.flush().is_none());
+    }
+
+    #[test]
+    fn test_next_trades() {
+        let mut resampler = Resampler::new(60, 0).unwrap();
+
+        assert!(resampler.next((5, 10.0, 1.0)).is_none());
+        assert!(resampler.next((30, 11.0, 2.0)).is_none());
+
+        let (start, item) = resampler.next((61, 10.5, 1.0)).unwrap();
+        assert_eq!(start, 0);
+        assert_eq!(item.open(), 10.0);
+        assert_eq!(item.high(), 11.0);
+        assert_eq!(item.close(), 11.0);
+        assert_eq!(item.volume(), 3.0);
+    }
+
+    #[test]
+    fn test_offset() {
+        // Daily bars starting at 09:30
+        let day = 24 * 60 * 60;
+        let open = (9 * 60 + 30) * 60;
+        let mut resampler = Resampler::new(day, open).unwrap();
+
+        resampler.next((open, 10.0, 1.0));
+        resampler.next((open + day - 1, 11.0, 1.0));
+        let (start, _) = resampler.next((open + day, 12.0, 1.0)).unwrap();
+        assert_eq!(start, open);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut resampler = Resampler::new(60, 0).unwrap();
+
+        resampler.next((5, 10.0, 1.0));
+        resampler.reset();
+        assert!(resampler.partial().is_none());
+        assert!(resampler.next((65, 10.0, 1.0)).is_none());
+    }
+
+    #[test]
+    fn test_display() {
+        let resampler = Resampler::new(300, 360).unwrap();
+        assert_eq!(format!("{}", resampler), "RESAMPLE(300, 60)");
+    }
+}
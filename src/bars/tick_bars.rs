@@ -0,0 +1,157 @@
+use core::fmt;
+
+use super::{add_trade, PartialBar};
+use crate::errors::{Result, TaError};
+use crate::{Checkpoint, DataItem, Next, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Tick bars.
+///
+/// Builds a bar from every _ticks_ trades, regardless of their size or the time they took.
+///
+/// # Parameters
+///
+/// * _ticks_ - number of trades per bar (integer greater than 0).
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::TickBars;
+/// use ta::{Close, High, Next, Volume};
+///
+/// let mut bars = TickBars::new(3).unwrap();
+///
+/// assert!(bars.next((10.0, 1.0)).is_none());
+/// assert!(bars.next((12.0, 2.0)).is_none());
+/// assert_eq!(bars.partial().unwrap().high(), 12.0);
+///
+/// let bar = bars.next((11.0, 3.0)).unwrap();
+/// assert_eq!(bar.close(), 11.0);
+/// assert_eq!(bar.volume(), 6.0);
+/// assert!(bars.partial().is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct TickBars {
+    ticks: usize,
+    partial: Option<PartialBar>,
+}
+
+/// State of [TickBars](struct.TickBars.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TickBarsState {
+    pub ticks: usize,
+    pub partial: Option<PartialBar>,
+}
+
+impl TickBars {
+    pub fn new(ticks: usize) -> Result<Self> {
+        match ticks {
+            0 => Err(TaError::invalid_parameter("ticks", 0.0, ">= 1")),
+            _ => Ok(Self {
+                ticks,
+                partial: None,
+            }),
+        }
+    }
+
+    /// Bar that is being built, if any trades were added since the last completed bar.
+    pub fn partial(&self) -> Option<DataItem> {
+        self.partial.as_ref().map(PartialBar::to_item)
+    }
+}
+
+impl Checkpoint for TickBars {
+    type State = TickBarsState;
+
+    fn state(&self) -> Self::State {
+        TickBarsState {
+            ticks: self.ticks,
+            partial: self.partial.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut bars = Self::new(state.ticks).map_err(|_| TaError::InvalidState)?;
+        // A bar that reached the threshold would have been completed
+        if let Some(bar) = &state.partial {
+            if bar.ticks == 0 || bar.ticks >= state.ticks {
+                return Err(TaError::InvalidState);
+            }
+        }
+        bars.partial = state.partial;
+        Ok(bars)
+    }
+}
+
+impl_versioned_serde!(TickBars, 1);
+
+impl Next<(f64, f64)> for TickBars {
+    type Output = Option<DataItem>;
+
+    fn next(&mut self, (price, size): (f64, f64)) -> Self::Output {
+        if add_trade(&mut self.partial, price, size).ticks >= self.ticks {
+            self.partial.take().map(|bar| bar.to_item())
+        } else {
+            None
+        }
+    }
+}
+
+impl Reset for TickBars {
+    fn reset(&mut self) {
+        self.partial = None;
+    }
+}
+
+impl fmt::Display for TickBars {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TICKBARS({})", self.ticks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Close, High, Low, Open, Volume};
+
+    #[test]
+    fn test_new() {
+        assert!(TickBars::new(0).is_err());
+        assert!(TickBars::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut bars = TickBars::new(2).unwrap();
+
+        assert!(bars.next((10.0, 1.0)).is_none());
+        let bar = bars.next((9.0, 2.0)).unwrap();
+        assert_eq!(bar.open(), 10.0);
+        assert_eq!(bar.high(), 10.0);
+        assert_eq!(bar.low(), 9.0);
+        assert_eq!(bar.close(), 9.0);
+        assert_eq!(bar.volume(), 3.0);
+
+        assert!(bars.next((11.0, 1.0)).is_none());
+        assert_eq!(bars.partial().unwrap().open(), 11.0);
+        assert_eq!(bars.next((12.0, 1.0)).unwrap().open(), 11.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bars = TickBars::new(2).unwrap();
+
+        bars.next((10.0, 1.0));
+        bars.reset();
+        assert!(bars.partial().is_none());
+        assert!(bars.next((11.0, 1.0)).is_none());
+    }
+
+    #[test]
+    fn test_display() {
+        let bars = TickBars::new(100).unwrap();
+        assert_eq!(format!("{}", bars), "TICKBARS(100)");
+    }
+}
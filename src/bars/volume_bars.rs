@@ -0,0 +1,158 @@
+use core::fmt;
+
+use super::{add_trade, PartialBar};
+use crate::errors::{Result, TaError};
+use crate::{Checkpoint, DataItem, Next, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Volume bars.
+///
+/// Builds a bar from trades until their total size reaches _volume_. Trades are not split, so
+/// the volume of a bar can be larger than _volume_.
+///
+/// # Parameters
+///
+/// * _volume_ - volume per bar (number greater than 0).
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::VolumeBars;
+/// use ta::{Next, Open, Volume};
+///
+/// let mut bars = VolumeBars::new(100.0).unwrap();
+///
+/// assert!(bars.next((10.0, 60.0)).is_none());
+///
+/// let bar = bars.next((11.0, 50.0)).unwrap();
+/// assert_eq!(bar.open(), 10.0);
+/// assert_eq!(bar.volume(), 110.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct VolumeBars {
+    volume: f64,
+    partial: Option<PartialBar>,
+}
+
+/// State of [VolumeBars](struct.VolumeBars.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeBarsState {
+    pub volume: f64,
+    pub partial: Option<PartialBar>,
+}
+
+impl VolumeBars {
+    pub fn new(volume: f64) -> Result<Self> {
+        if !volume.is_finite() || volume <= 0.0 {
+            return Err(TaError::invalid_parameter("volume", volume, "> 0"));
+        }
+        Ok(Self {
+            volume,
+            partial: None,
+        })
+    }
+
+    /// Bar that is being built, if any trades were added since the last completed bar.
+    pub fn partial(&self) -> Option<DataItem> {
+        self.partial.as_ref().map(PartialBar::to_item)
+    }
+}
+
+impl Checkpoint for VolumeBars {
+    type State = VolumeBarsState;
+
+    fn state(&self) -> Self::State {
+        VolumeBarsState {
+            volume: self.volume,
+            partial: self.partial.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut bars = Self::new(state.volume).map_err(|_| TaError::InvalidState)?;
+        // A bar that reached the threshold would have been completed
+        if let Some(bar) = &state.partial {
+            if bar.ticks == 0 || bar.volume >= state.volume {
+                return Err(TaError::InvalidState);
+            }
+        }
+        bars.partial = state.partial;
+        Ok(bars)
+    }
+}
+
+impl_versioned_serde!(VolumeBars, 1);
+
+impl Next<(f64, f64)> for VolumeBars {
+    type Output = Option<DataItem>;
+
+    fn next(&mut self, (price, size): (f64, f64)) -> Self::Output {
+        if add_trade(&mut self.partial, price, size).volume >= self.volume {
+            self.partial.take().map(|bar| bar.to_item())
+        } else {
+            None
+        }
+    }
+}
+
+impl Reset for VolumeBars {
+    fn reset(&mut self) {
+        self.partial = None;
+    }
+}
+
+impl fmt::Display for VolumeBars {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VOLUMEBARS({})", self.volume)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Close, High, Low, Volume};
+
+    #[test]
+    fn test_new() {
+        assert!(VolumeBars::new(0.0).is_err());
+        assert!(VolumeBars::new(f64::NAN).is_err());
+        assert!(VolumeBars::new(10.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut bars = VolumeBars::new(10.0).unwrap();
+
+        assert!(bars.next((10.0, 4.0)).is_none());
+        assert!(bars.next((12.0, 4.0)).is_none());
+        assert_eq!(bars.partial().unwrap().volume(), 8.0);
+
+        let bar = bars.next((9.0, 2.0)).unwrap();
+        assert_eq!(bar.high(), 12.0);
+        assert_eq!(bar.low(), 9.0);
+        assert_eq!(bar.close(), 9.0);
+        assert_eq!(bar.volume(), 10.0);
+        assert!(bars.partial().is_none());
+
+        // A single large trade completes a bar on its own
+        assert_eq!(bars.next((9.5, 25.0)).unwrap().volume(), 25.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bars = VolumeBars::new(10.0).unwrap();
+
+        bars.next((10.0, 5.0));
+        bars.reset();
+        assert!(bars.partial().is_none());
+        assert!(bars.next((11.0, 5.0)).is_none());
+    }
+
+    #[test]
+    fn test_display() {
+        let bars = VolumeBars::new(5000.0).unwrap();
+        assert_eq!(format!("{}", bars), "VOLUMEBARS(5000)");
+    }
+}
@@ -0,0 +1,478 @@
+//! Combinators which compose indicators into new ones, see [Compose].
+
+use core::fmt;
+
+use crate::errors::Result;
+use crate::{Amend, Checkpoint, Current, IsReady, Lookback, Next, Peek, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Composes indicators without writing a struct for every combination.
+///
+/// Implemented for every indicator. The composed indicators implement [Next], [Reset] and
+/// [Period] (as well as [Current], [Peek], [Amend], [IsReady], [Lookback] and `Display`) when the
+/// indicators they are made of do, so they can be composed further.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use ta::indicators::{ExponentialMovingAverage as Ema, RelativeStrengthIndex as Rsi};
+/// use ta::indicators::SimpleMovingAverage as Sma;
+/// use ta::{Compose, Next};
+///
+/// // EMA of RSI
+/// let mut ema_of_rsi = Rsi::new(14).unwrap().then(Ema::new(9).unwrap());
+/// assert_eq!(ema_of_rsi.next(10.0), 50.0);
+///
+/// // Fast and slow SMA of the same input
+/// let mut cross = Sma::new(2).unwrap().zip(Sma::new(3).unwrap());
+/// assert_eq!(cross.next(3.0), (3.0, 3.0));
+/// assert_eq!(cross.next(6.0), (4.5, 4.5));
+/// assert_eq!(cross.next(0.0), (3.0, 3.0));
+///
+/// // Difference of both
+/// let mut spread = Sma::new(2).unwrap()
+///     .zip(Sma::new(3).unwrap())
+///     .map(|(fast, slow)| fast - slow);
+/// spread.next(3.0);
+/// spread.next(6.0);
+/// assert_eq!(spread.next(9.0), 1.5);
+/// # }
+/// ```
+pub trait Compose: Sized {
+    /// Feeds the output of this indicator into `next`.
+    fn then<B>(self, next: B) -> Then<Self, B> {
+        Then::new(self, next)
+    }
+
+    /// Feeds the same input to this indicator and `other`, and returns both outputs.
+    fn zip<B>(self, other: B) -> Zip<Self, B> {
+        Zip::new(self, other)
+    }
+
+    /// Applies `f` to the output of this indicator.
+    fn map<F>(self, f: F) -> Map<Self, F> {
+        Map::new(self, f)
+    }
+}
+
+impl<I: Reset> Compose for I {}
+
+/// Indicator which feeds the output of the first indicator into the second one, see
+/// [Compose::then].
+///
+/// Its period is the sum of both periods minus 1, i.e. the number of inputs the periods of both
+/// indicators span together.
+#[derive(Debug, Clone)]
+pub struct Then<A, B> {
+    first: A,
+    second: B,
+    count: usize,
+}
+
+/// State of [Then], see [Checkpoint].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThenState<A, B> {
+    pub first: A,
+    pub second: B,
+    /// Number of inputs.
+    pub count: usize,
+}
+
+impl<A, B> Then<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            count: 0,
+        }
+    }
+
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<A: Period, B: Period> Period for Then<A, B> {
+    fn period(&self) -> usize {
+        self.first.period() + self.second.period() - 1
+    }
+}
+
+impl<A: Checkpoint, B: Checkpoint> Checkpoint for Then<A, B> {
+    type State = ThenState<A::State, B::State>;
+
+    fn state(&self) -> Self::State {
+        ThenState {
+            first: self.first.state(),
+            second: self.second.state(),
+            count: self.count,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(Self {
+            first: A::from_state(state.first)?,
+            second: B::from_state(state.second)?,
+            count: state.count,
+        })
+    }
+}
+
+impl_versioned_serde!(Then<A, B>, 1);
+
+impl<T, A: Next<T>, B: Next<A::Output>> Next<T> for Then<A, B> {
+    type Output = B::Output;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        self.count += 1;
+        self.second.next(self.first.next(input))
+    }
+}
+
+impl<T, A: Peek<T>, B: Peek<A::Output>> Peek<T> for Then<A, B> {
+    fn peek(&self, input: T) -> B::Output {
+        self.second.peek(self.first.peek(input))
+    }
+}
+
+impl<T, A: Amend<T>, B: Amend<A::Output>> Amend<T> for Then<A, B> {
+    fn amend(&mut self, input: T) -> B::Output {
+        if self.count == 0 {
+            return self.next(input);
+        }
+        self.second.amend(self.first.amend(input))
+    }
+}
+
+impl<A: Reset, B: Reset> Reset for Then<A, B> {
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+        self.count = 0;
+    }
+}
+
+impl<A, B: Current> Current for Then<A, B> {
+    type Output = B::Output;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.second.current()
+    }
+}
+
+impl<A: Lookback, B: Lookback> IsReady for Then<A, B> {
+    fn is_ready(&self) -> bool {
+        self.count >= self.lookback()
+    }
+}
+
+impl<A: Lookback, B: Lookback> Lookback for Then<A, B> {
+    fn lookback(&self) -> usize {
+        self.first.lookback() + self.second.lookback() - 1
+    }
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for Then<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "THEN({}, {})", self.first, self.second)
+    }
+}
+
+/// Indicator which feeds the same input to two indicators and returns both outputs, see
+/// [Compose::zip].
+///
+/// The period is the longer one of both periods.
+#[derive(Debug, Clone)]
+pub struct Zip<A, B> {
+    first: A,
+    second: B,
+}
+
+/// State of [Zip], see [Checkpoint].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZipState<A, B> {
+    pub first: A,
+    pub second: B,
+}
+
+impl<A, B> Zip<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<A: Period, B: Period> Period for Zip<A, B> {
+    fn period(&self) -> usize {
+        self.first.period().max(self.second.period())
+    }
+}
+
+impl<A: Checkpoint, B: Checkpoint> Checkpoint for Zip<A, B> {
+    type State = ZipState<A::State, B::State>;
+
+    fn state(&self) -> Self::State {
+        ZipState {
+            first: self.first.state(),
+            second: self.second.state(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(Self::new(
+            A::from_state(state.first)?,
+            B::from_state(state.second)?,
+        ))
+    }
+}
+
+impl_versioned_serde!(Zip<A, B>, 1);
+
+impl<T: Copy, A: Next<T>, B: Next<T>> Next<T> for Zip<A, B> {
+    type Output = (A::Output, B::Output);
+
+    fn next(&mut self, input: T) -> Self::Output {
+        (self.first.next(input), self.second.next(input))
+    }
+}
+
+impl<T: Copy, A: Peek<T>, B: Peek<T>> Peek<T> for Zip<A, B> {
+    fn peek(&self, input: T) -> (A::Output, B::Output) {
+        (self.first.peek(input), self.second.peek(input))
+    }
+}
+
+impl<T: Copy, A: Amend<T>, B: Amend<T>> Amend<T> for Zip<A, B> {
+    fn amend(&mut self, input: T) -> (A::Output, B::Output) {
+        (self.first.amend(input), self.second.amend(input))
+    }
+}
+
+impl<A: Reset, B: Reset> Reset for Zip<A, B> {
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+    }
+}
+
+impl<A: Current, B: Current> Current for Zip<A, B> {
+    type Output = (A::Output, B::Output);
+
+    fn current(&self) -> Option<Self::Output> {
+        self.first.current().zip(self.second.current())
+    }
+}
+
+impl<A: IsReady, B: IsReady> IsReady for Zip<A, B> {
+    fn is_ready(&self) -> bool {
+        self.first.is_ready() && self.second.is_ready()
+    }
+}
+
+impl<A: Lookback, B: Lookback> Lookback for Zip<A, B> {
+    fn lookback(&self) -> usize {
+        self.first.lookback().max(self.second.lookback())
+    }
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for Zip<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ZIP({}, {})", self.first, self.second)
+    }
+}
+
+/// Indicator which applies a function to the output of another indicator, see [Compose::map].
+#[derive(Clone)]
+pub struct Map<I, F> {
+    indicator: I,
+    f: F,
+}
+
+impl<I, F> Map<I, F> {
+    pub fn new(indicator: I, f: F) -> Self {
+        Self { indicator, f }
+    }
+
+    /// The wrapped indicator.
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+}
+
+impl<I: Period, F> Period for Map<I, F> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<T, I: Next<T>, F: FnMut(I::Output) -> O, O> Next<T> for Map<I, F> {
+    type Output = O;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        (self.f)(self.indicator.next(input))
+    }
+}
+
+impl<T, I: Peek<T>, F: Fn(I::Output) -> O, O> Peek<T> for Map<I, F> {
+    fn peek(&self, input: T) -> O {
+        (self.f)(self.indicator.peek(input))
+    }
+}
+
+impl<T, I: Amend<T>, F: FnMut(I::Output) -> O, O> Amend<T> for Map<I, F> {
+    fn amend(&mut self, input: T) -> O {
+        (self.f)(self.indicator.amend(input))
+    }
+}
+
+impl<I: Reset, F> Reset for Map<I, F> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+    }
+}
+
+/// Applies the function to the current output of the wrapped indicator again, so it is only
+/// available for functions that don't need to mutate their state.
+impl<I: Current, F: Fn(I::Output) -> O, O> Current for Map<I, F> {
+    type Output = O;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.indicator.current().map(&self.f)
+    }
+}
+
+impl<I: IsReady, F> IsReady for Map<I, F> {
+    fn is_ready(&self) -> bool {
+        self.indicator.is_ready()
+    }
+}
+
+impl<I: Lookback, F> Lookback for Map<I, F> {
+    fn lookback(&self) -> usize {
+        self.indicator.lookback()
+    }
+}
+
+impl<I: fmt::Debug, F> fmt::Debug for Map<I, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Map")
+            .field("indicator", &self.indicator)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I: fmt::Display, F> fmt::Display for Map<I, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MAP({})", self.indicator)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        AverageTrueRange as Atr, ExponentialMovingAverage as Ema, Maximum,
+        RelativeStrengthIndex as Rsi, SimpleMovingAverage as Sma,
+    };
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_then() {
+        let mut composed = Sma::new(2).unwrap().then(Ema::new(3).unwrap());
+        let mut sma = Sma::new(2).unwrap();
+        let mut ema = Ema::new(3).unwrap();
+
+        for i in 0..10 {
+            let input = (i * 7 % 11) as f64;
+            assert_eq!(composed.next(input), ema.next(sma.next(input)));
+        }
+        assert_eq!(composed.period(), 4);
+        assert_eq!(composed.lookback(), 4);
+        assert_eq!(format!("{}", composed), "THEN(SMA(2), EMA(3))");
+        assert_eq!(composed.current(), ema.current());
+    }
+
+    #[test]
+    fn test_then_with_bars() {
+        let mut composed = Atr::new(2).unwrap().then(Maximum::new(2).unwrap());
+
+        assert_eq!(composed.next(&Bar::new().high(4).low(2).close(3)), 2.0);
+        assert_eq!(composed.next(&Bar::new().high(4).low(3).close(3)), 2.0);
+        assert_eq!(
+            round(composed.next(&Bar::new().high(6).low(4).close(5))),
+            2.444
+        );
+    }
+
+    #[test]
+    fn test_zip() {
+        let mut composed = Sma::new(2).unwrap().zip(Maximum::new(3).unwrap());
+
+        assert_eq!(composed.next(4.0), (4.0, 4.0));
+        assert_eq!(composed.next(2.0), (3.0, 4.0));
+        assert!(!composed.is_ready());
+        assert_eq!(composed.next(1.0), (1.5, 4.0));
+        assert!(composed.is_ready());
+        assert_eq!(composed.period(), 3);
+        assert_eq!(composed.lookback(), 3);
+        assert_eq!(format!("{}", composed), "ZIP(SMA(2), MAX(3))");
+    }
+
+    #[test]
+    fn test_map() {
+        let mut composed = Rsi::new(3)
+            .unwrap()
+            .map(|rsi| rsi > 70.0)
+            .zip(Sma::new(2).unwrap().map(|sma| sma * 2.0));
+
+        assert_eq!(composed.next(10.0), (false, 20.0));
+        assert_eq!(composed.next(12.0), (true, 22.0));
+        assert_eq!(composed.current(), Some((true, 22.0)));
+        assert_eq!(composed.period(), 3);
+        assert_eq!(format!("{}", composed), "ZIP(MAP(RSI(3)), MAP(SMA(2)))");
+        assert!(format!("{:?}", composed)
+            .starts_with("Zip { first: Map { indicator: RelativeStrengthIndex"));
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut composed = Sma::new(3).unwrap().then(Sma::new(2).unwrap());
+        assert_eq!(composed.lookback(), 4);
+
+        for _ in 0..3 {
+            composed.next(1.0);
+            assert!(!composed.is_ready());
+        }
+        composed.next(1.0);
+        assert!(composed.is_ready());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut composed = Sma::new(2)
+            .unwrap()
+            .then(Ema::new(2).unwrap())
+            .map(|value| value + 1.0);
+
+        let first: Vec<f64> = [3.0, 5.0, 8.0].iter().map(|&x| composed.next(x)).collect();
+        composed.reset();
+        assert!(!composed.is_ready());
+        let second: Vec<f64> = [3.0, 5.0, 8.0].iter().map(|&x| composed.next(x)).collect();
+        assert_eq!(first, second);
+    }
+}
@@ -42,6 +42,17 @@ impl DataItem {
     pub fn builder() -> DataItemBuilder {
         DataItemBuilder::new()
     }
+
+    /// Creates a data item without validation, for bars that are consistent by construction.
+    pub(crate) fn new_unchecked(open: f64, high: f64, low: f64, close: f64, volume: f64) -> Self {
+        Self {
+            open,
+            high,
+            low,
+            close,
+            volume,
+        }
+    }
 }
 
 impl Open for DataItem {
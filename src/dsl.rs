@@ -0,0 +1,585 @@
+//! Parses expressions like `"ema(rsi(close, 14), 9)"` into indicator pipelines.
+//!
+//! Available with the `dsl` feature. An expression is one of:
+//!
+//! * a price source: `close`, `open`, `high`, `low`, `hl2`, `hlc3`, `ohlc4` or `volume`;
+//! * a call of an indicator of the [factory], e.g. `rsi(close, 14)`.
+//!
+//! The first argument of a call is the series the indicator is fed with. It can be an
+//! expression itself, the indicator then gets its values as bars whose open, high, low and close
+//! are the value (and whose volume is the volume of the original bar). Without it the indicator
+//! is fed with the bars, e.g. `atr(14)`. The other arguments are numbers, given in the order of
+//! [factory::parameters], missing ones take the default values.
+//!
+//! Indicators with several outputs need a field, e.g. `bbands(hlc3, 20, 2.0).upper`. Flags are
+//! 1 or 0, outputs which are not numbers (e.g. missing values) are `NaN`.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::{dsl, DataItem, Next};
+//!
+//! let mut pipeline = dsl::parse("ema(rsi(close, 14), 9)").unwrap();
+//! let mut bands = dsl::parse("bbands(hlc3, 20, 2.0).upper").unwrap();
+//!
+//! let bar = DataItem::builder()
+//!     .open(10.0).high(12.0).low(9.0).close(11.0)
+//!     .volume(1000.0)
+//!     .build().unwrap();
+//! pipeline.next(&bar);
+//! assert_eq!(bands.next(&bar), 32.0 / 3.0);
+//!
+//! assert_eq!(pipeline.to_string(), "ema(rsi(close, 14), 9)");
+//! assert!(dsl::parse("bbands(hlc3, 20)").is_err());
+//! assert!(dsl::parse("ema(close, 9, 1)").is_err());
+//! ```
+
+use std::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::factory;
+use crate::indicators::PriceSource;
+use crate::{
+    Close, Current, DataItem, DynIndicator, High, IsReady, Lookback, Low, Next, Open, OutputValue,
+    Reset, Volume,
+};
+
+/// Indicator pipeline parsed from an expression, see the [module](self) documentation.
+///
+/// Fed with bars, returns the value of the expression.
+#[derive(Debug)]
+pub struct Pipeline {
+    root: Node,
+    count: usize,
+    output: Option<f64>,
+}
+
+/// Parses an expression into a [Pipeline].
+///
+/// Returns an error if the expression is malformed, if an indicator or a field is unknown, or if
+/// an indicator can't be created with the given values.
+pub fn parse(expression: &str) -> Result<Pipeline> {
+    let mut parser = Parser {
+        tokens: tokenize(expression)?,
+        position: 0,
+    };
+    let root = match parser.argument()? {
+        Argument::Series(node) => node,
+        Argument::Number(_) => {
+            return Err(TaError::InvalidExpression(
+                "expected a series, not a number",
+            ))
+        }
+    };
+    if parser.position != parser.tokens.len() {
+        return Err(TaError::InvalidExpression(
+            "unexpected token after the expression",
+        ));
+    }
+
+    Ok(Pipeline {
+        root,
+        count: 0,
+        output: None,
+    })
+}
+
+impl<T: Open + High + Low + Close + Volume> Next<&T> for Pipeline {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let bar = DataItem::new_unchecked(
+            input.open(),
+            input.high(),
+            input.low(),
+            input.close(),
+            input.volume(),
+        );
+        self.count += 1;
+        let output = self.root.next(&bar);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Reset for Pipeline {
+    fn reset(&mut self) {
+        self.root.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl Current for Pipeline {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for Pipeline {
+    fn is_ready(&self) -> bool {
+        self.count >= self.lookback()
+    }
+}
+
+impl Lookback for Pipeline {
+    fn lookback(&self) -> usize {
+        self.root.lookback()
+    }
+}
+
+impl fmt::Display for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.root)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Source {
+    Price(PriceSource),
+    Volume,
+}
+
+const SOURCES: [(&str, Source); 8] = [
+    ("close", Source::Price(PriceSource::Close)),
+    ("open", Source::Price(PriceSource::Open)),
+    ("high", Source::Price(PriceSource::High)),
+    ("low", Source::Price(PriceSource::Low)),
+    ("hl2", Source::Price(PriceSource::HL2)),
+    ("hlc3", Source::Price(PriceSource::HLC3)),
+    ("ohlc4", Source::Price(PriceSource::OHLC4)),
+    ("volume", Source::Volume),
+];
+
+#[derive(Debug)]
+enum Node {
+    Source(&'static str, Source),
+    Call {
+        name: String,
+        input: Option<Box<Node>>,
+        params: Vec<f64>,
+        field: Option<String>,
+        indicator: Box<dyn DynIndicator>,
+    },
+}
+
+impl Node {
+    fn call(
+        name: String,
+        input: Option<Node>,
+        params: Vec<f64>,
+        field: Option<String>,
+    ) -> Result<Self> {
+        let names =
+            factory::parameters(&name).ok_or_else(|| TaError::UnknownIndicator(name.clone()))?;
+        if params.len() > names.len() {
+            return Err(TaError::InvalidExpression("too many arguments"));
+        }
+        let named: Vec<(&str, f64)> = names
+            .iter()
+            .zip(params.iter())
+            .map(|(&(param, _), &value)| (param, value))
+            .collect();
+        let mut indicator = factory::create(&name, &named)?;
+
+        // Checks the shape of the output on a probe bar, so a missing or unknown field is
+        // reported here instead of turning every value into NaN.
+        let probe = DataItem::new_unchecked(1.0, 1.0, 1.0, 1.0, 1.0);
+        let output = indicator.next_value(&probe);
+        indicator.reset();
+        match (&output, &field) {
+            (OutputValue::Named(_), None) => {
+                return Err(TaError::InvalidExpression(
+                    "missing field of an indicator with several outputs",
+                ))
+            }
+            (OutputValue::None, Some(_)) => {}
+            (_, Some(field)) if output.get(field).is_none() => {
+                return Err(TaError::InvalidExpression("unknown field"))
+            }
+            _ => {}
+        }
+
+        Ok(Node::Call {
+            name,
+            input: input.map(Box::new),
+            params,
+            field,
+            indicator,
+        })
+    }
+
+    fn next(&mut self, bar: &DataItem) -> f64 {
+        match self {
+            Node::Source(_, Source::Price(source)) => source.price(bar),
+            Node::Source(_, Source::Volume) => bar.volume(),
+            Node::Call {
+                input,
+                field,
+                indicator,
+                ..
+            } => {
+                let output = match input {
+                    Some(input) => {
+                        let value = input.next(bar);
+                        let item =
+                            DataItem::new_unchecked(value, value, value, value, bar.volume());
+                        indicator.next_value(&item)
+                    }
+                    None => indicator.next_value(bar),
+                };
+                match field {
+                    Some(field) => output.get(field).map_or(f64::NAN, number),
+                    None => number(&output),
+                }
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        if let Node::Call {
+            input, indicator, ..
+        } = self
+        {
+            indicator.reset();
+            if let Some(input) = input {
+                input.reset();
+            }
+        }
+    }
+
+    fn lookback(&self) -> usize {
+        match self {
+            Node::Source(..) => 1,
+            Node::Call {
+                input, indicator, ..
+            } => indicator.lookback() + input.as_ref().map_or(1, |input| input.lookback()) - 1,
+        }
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Node::Source(name, _) => write!(f, "{}", name),
+            Node::Call {
+                name,
+                input,
+                params,
+                field,
+                ..
+            } => {
+                write!(f, "{}(", name)?;
+                let mut separator = "";
+                if let Some(input) = input {
+                    write!(f, "{}", input)?;
+                    separator = ", ";
+                }
+                for param in params {
+                    write!(f, "{}{}", separator, param)?;
+                    separator = ", ";
+                }
+                write!(f, ")")?;
+                if let Some(field) = field {
+                    write!(f, ".{}", field)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn number(value: &OutputValue) -> f64 {
+    match value {
+        OutputValue::Scalar(value) => *value,
+        OutputValue::Flag(true) => 1.0,
+        OutputValue::Flag(false) => 0.0,
+        _ => f64::NAN,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    Open,
+    Close,
+    Comma,
+    Dot,
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        i += 1;
+        match c {
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            ',' => tokens.push(Token::Comma),
+            '.' => tokens.push(Token::Dot),
+            c if c.is_whitespace() => {}
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let ident: String = chars[start..i].iter().collect();
+                tokens.push(Token::Ident(ident.to_ascii_lowercase()));
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+                if i + 1 < chars.len() && chars[i] == '.' && chars[i + 1].is_ascii_digit() {
+                    i += 1;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+                let literal: String = chars[start..i].iter().collect();
+                let value = literal.parse::<f64>()?;
+                tokens.push(Token::Number(value));
+            }
+            _ => return Err(TaError::InvalidExpression("unexpected character")),
+        }
+    }
+
+    Ok(tokens)
+}
+
+enum Argument {
+    Series(Node),
+    Number(f64),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn advance(&mut self) -> Result<Token> {
+        let token = self
+            .peek()
+            .cloned()
+            .ok_or(TaError::InvalidExpression("unexpected end"))?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, token: Token) -> Result<()> {
+        match self.advance()? {
+            next if next == token => Ok(()),
+            _ => Err(TaError::InvalidExpression("unexpected token")),
+        }
+    }
+
+    fn argument(&mut self) -> Result<Argument> {
+        let name = match self.advance()? {
+            Token::Number(value) => return Ok(Argument::Number(value)),
+            Token::Ident(name) => name,
+            _ => {
+                return Err(TaError::InvalidExpression(
+                    "expected an indicator, a source or a number",
+                ))
+            }
+        };
+
+        if self.peek() != Some(&Token::Open) {
+            return SOURCES
+                .iter()
+                .find(|(source, _)| *source == name)
+                .map(|&(source, kind)| Argument::Series(Node::Source(source, kind)))
+                .ok_or(TaError::InvalidExpression("unknown source"));
+        }
+
+        self.expect(Token::Open)?;
+        let mut input = None;
+        let mut params = Vec::new();
+        if self.peek() != Some(&Token::Close) {
+            loop {
+                match self.argument()? {
+                    Argument::Series(node) if input.is_none() && params.is_empty() => {
+                        input = Some(node)
+                    }
+                    Argument::Number(value) => params.push(value),
+                    Argument::Series(_) => {
+                        return Err(TaError::InvalidExpression(
+                            "the series must be the first argument",
+                        ))
+                    }
+                }
+                if self.peek() != Some(&Token::Comma) {
+                    break;
+                }
+                self.expect(Token::Comma)?;
+            }
+        }
+        self.expect(Token::Close)?;
+
+        let field = if self.peek() == Some(&Token::Dot) {
+            self.expect(Token::Dot)?;
+            match self.advance()? {
+                Token::Ident(field) => Some(field),
+                _ => return Err(TaError::InvalidExpression("expected a field name")),
+            }
+        } else {
+            None
+        };
+
+        Node::call(name, input, params, field).map(Argument::Series)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        AverageTrueRange, BollingerBands, ExponentialMovingAverage as Ema,
+        RelativeStrengthIndex as Rsi,
+    };
+    use crate::test_helper::*;
+
+    fn bars() -> Vec<Bar> {
+        (0..30)
+            .map(|i| {
+                let close = 10.0 + (i * 7 % 11) as f64;
+                Bar::new()
+                    .open(close - 0.5)
+                    .high(close + 1.0)
+                    .low(close - 1.5)
+                    .close(close)
+                    .volume(100.0 + i as f64)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let pipeline = parse(" EMA( rsi(close,14) , 9 ) ").unwrap();
+        assert_eq!(pipeline.to_string(), "ema(rsi(close, 14), 9)");
+        assert_eq!(pipeline.lookback(), 22);
+
+        assert_eq!(parse("hl2").unwrap().to_string(), "hl2");
+        assert_eq!(parse("atr()").unwrap().to_string(), "atr()");
+        assert_eq!(parse("atr(7)").unwrap().lookback(), 7);
+        assert_eq!(
+            parse("bbands(hlc3, 20, 2.5).upper").unwrap().to_string(),
+            "bbands(hlc3, 20, 2.5).upper"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("").is_err());
+        assert!(parse("14").is_err());
+        assert!(parse("price").is_err());
+        assert!(parse("foo(close)").is_err());
+        assert!(parse("ema(close, 9").is_err());
+        assert!(parse("ema(close, 9))").is_err());
+        assert!(parse("ema(close 9)").is_err());
+        assert!(parse("ema(close, 9,)").is_err());
+        assert!(parse("ema(close, 9, 1)").is_err());
+        assert!(parse("ema(close, 0)").is_err());
+        assert!(parse("ema(9, close)").is_err());
+        assert!(parse("ema(close, high)").is_err());
+        assert!(parse("close.upper").is_err());
+        assert!(parse("ema(close, 9).upper").is_err());
+        assert!(parse("bbands(close, 20)").is_err());
+        assert!(parse("bbands(close, 20).middle").is_err());
+        assert!(parse("ema(close; 9)").is_err());
+        assert!(parse("sma(volume, -1.5)").is_err());
+    }
+
+    #[test]
+    fn test_parse_error_kinds() {
+        assert_eq!(
+            parse("foo(close)").err(),
+            Some(TaError::UnknownIndicator("foo".to_string()))
+        );
+        assert_eq!(
+            parse("ema(close, 9").err(),
+            Some(TaError::InvalidExpression("unexpected end"))
+        );
+        assert_eq!(
+            parse("bbands(close, 20)").err(),
+            Some(TaError::InvalidExpression(
+                "missing field of an indicator with several outputs"
+            ))
+        );
+        assert_eq!(
+            parse("ema(close, 0)").err(),
+            Some(TaError::invalid_parameter("period", 0.0, ">= 1"))
+        );
+    }
+
+    #[test]
+    fn test_next() {
+        let mut pipeline = parse("ema(rsi(close, 14), 9)").unwrap();
+        let mut rsi = Rsi::new(14).unwrap();
+        let mut ema = Ema::new(9).unwrap();
+        for bar in bars() {
+            assert_eq!(pipeline.next(&bar), ema.next(rsi.next(Close::close(&bar))));
+        }
+
+        let mut pipeline = parse("bbands(hl2, 5, 2).lower").unwrap();
+        let mut bb = BollingerBands::new(5, 2.0).unwrap();
+        for bar in bars() {
+            let expected = bb.next((High::high(&bar) + Low::low(&bar)) / 2.0).lower;
+            assert_eq!(pipeline.next(&bar), expected);
+        }
+
+        let mut pipeline = parse("atr(3)").unwrap();
+        let mut atr = AverageTrueRange::new(3).unwrap();
+        for bar in bars() {
+            assert_eq!(pipeline.next(&bar), atr.next(&bar));
+        }
+
+        let mut pipeline = parse("volume").unwrap();
+        assert_eq!(pipeline.next(&Bar::new().volume(42.0)), 42.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut pipeline = parse("sma(ema(close, 3), 2)").unwrap();
+        assert_eq!(pipeline.lookback(), 4);
+
+        for bar in bars().iter().take(3) {
+            pipeline.next(bar);
+            assert!(!pipeline.is_ready());
+        }
+        pipeline.next(&bars()[3]);
+        assert!(pipeline.is_ready());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut pipeline = parse("ema(rsi(close, 3), 2)").unwrap();
+        let first: Vec<f64> = bars().iter().map(|bar| pipeline.next(bar)).collect();
+
+        assert_eq!(pipeline.current(), first.last().copied());
+
+        pipeline.reset();
+        assert!(!pipeline.is_ready());
+        assert_eq!(pipeline.current(), None);
+        let second: Vec<f64> = bars().iter().map(|bar| pipeline.next(bar)).collect();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_dyn_indicator() {
+        let mut pipeline: Box<dyn DynIndicator> = Box::new(parse("sma(close, 2)").unwrap());
+        let bar = DataItem::new_unchecked(1.0, 3.0, 1.0, 2.0, 10.0);
+        assert_eq!(pipeline.next_value(&bar), OutputValue::Scalar(2.0));
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt;
+
+use crate::{DataItem, IsReady, Lookback, Next, Reset};
+
+/// Output of an indicator as a value of a single type.
+///
+/// Every indicator output converts into an `OutputValue`, so indicators with different outputs
+/// can be handled the same way, see [DynIndicator](trait.DynIndicator.html). Output structs
+/// become [Named](#variant.Named) values, with the names of their fields.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::BollingerBandsOutput;
+/// use ta::OutputValue;
+///
+/// let output = BollingerBandsOutput { upper: 6.0, average: 4.0, lower: 2.0 };
+/// let value = OutputValue::from(output);
+///
+/// assert_eq!(value.get("upper").and_then(OutputValue::as_scalar), Some(6.0));
+/// assert_eq!(value.to_string(), "upper: 6, average: 4, lower: 2");
+/// assert_eq!(OutputValue::from(3.5).as_scalar(), Some(3.5));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutputValue {
+    /// No value, e.g. for an event that did not happen on the current bar.
+    None,
+    Scalar(f64),
+    Flag(bool),
+    /// Kind of an event or a state, e.g. `"regular"` for a regular divergence.
+    Label(String),
+    /// Several values of the same kind, e.g. the lines of a moving average ribbon.
+    List(Vec<OutputValue>),
+    /// Named values, in the order of the fields of the output struct.
+    Named(Vec<(&'static str, OutputValue)>),
+}
+
+impl OutputValue {
+    /// The value if it is a scalar.
+    pub fn as_scalar(&self) -> Option<f64> {
+        match self {
+            OutputValue::Scalar(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The value with the given name, if it is a named value.
+    pub fn get(&self, name: &str) -> Option<&OutputValue> {
+        match self {
+            OutputValue::Named(values) => values
+                .iter()
+                .find(|(field, _)| *field == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for OutputValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OutputValue::None => write!(f, "none"),
+            OutputValue::Scalar(value) => write!(f, "{}", value),
+            OutputValue::Flag(value) => write!(f, "{}", value),
+            OutputValue::Label(label) => write!(f, "{}", label),
+            OutputValue::List(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            OutputValue::Named(values) => {
+                for (i, (name, value)) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", name, value)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl From<f64> for OutputValue {
+    fn from(value: f64) -> Self {
+        OutputValue::Scalar(value)
+    }
+}
+
+impl From<usize> for OutputValue {
+    fn from(value: usize) -> Self {
+        OutputValue::Scalar(value as f64)
+    }
+}
+
+impl From<bool> for OutputValue {
+    fn from(value: bool) -> Self {
+        OutputValue::Flag(value)
+    }
+}
+
+impl<T: Into<OutputValue>> From<Option<T>> for OutputValue {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => value.into(),
+            None => OutputValue::None,
+        }
+    }
+}
+
+impl<T: Into<OutputValue>> From<Vec<T>> for OutputValue {
+    fn from(values: Vec<T>) -> Self {
+        OutputValue::List(values.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Indicator that can be used as a trait object.
+///
+/// [Next](trait.Next.html) has an `Output` type that differs from indicator to indicator, so
+/// indicators can't be kept in one collection. `DynIndicator` is implemented for every indicator
+/// that consumes [DataItem](struct.DataItem.html)s, and returns their outputs as
+/// [OutputValue](enum.OutputValue.html)s, so `Box<dyn DynIndicator>` can hold any of them.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{BollingerBands, SimpleMovingAverage};
+/// use ta::{DataItem, DynIndicator, IsReady};
+///
+/// let mut indicators: Vec<Box<dyn DynIndicator>> = vec![
+///     Box::new(SimpleMovingAverage::new(2).unwrap()),
+///     Box::new(BollingerBands::new(2, 2.0).unwrap()),
+/// ];
+///
+/// let bar = DataItem::builder()
+///     .open(10.0).high(10.0).low(10.0).close(10.0)
+///     .volume(100.0)
+///     .build().unwrap();
+///
+/// for indicator in indicators.iter_mut() {
+///     let value = indicator.next_value(&bar);
+///     println!("{} = {}", indicator, value);
+///     assert!(!indicator.is_ready());
+/// }
+/// ```
+pub trait DynIndicator: Reset + IsReady + Lookback + fmt::Debug + fmt::Display {
+    fn next_value(&mut self, input: &DataItem) -> OutputValue;
+}
+
+impl<I, O> DynIndicator for I
+where
+    I: for<'a> Next<&'a DataItem, Output = O> + Reset + IsReady + Lookback,
+    I: fmt::Debug + fmt::Display,
+    O: Into<OutputValue>,
+{
+    fn next_value(&mut self, input: &DataItem) -> OutputValue {
+        self.next(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        BarRelation, InsideOutsideBar, MovingAverageConvergenceDivergence as Macd,
+        MovingAverageRibbon, RibbonSpacing, SimpleMovingAverage as Sma,
+    };
+
+    fn bar(high: f64, low: f64) -> DataItem {
+        DataItem::builder()
+            .open(low)
+            .high(high)
+            .low(low)
+            .close(high)
+            .volume(100.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_output_value() {
+        assert_eq!(OutputValue::from(2.5), OutputValue::Scalar(2.5));
+        assert_eq!(OutputValue::from(3_usize), OutputValue::Scalar(3.0));
+        assert_eq!(OutputValue::from(true), OutputValue::Flag(true));
+        assert_eq!(OutputValue::from(None::<f64>), OutputValue::None);
+        assert_eq!(OutputValue::from(Some(1.5)), OutputValue::Scalar(1.5));
+        assert_eq!(
+            OutputValue::from(vec![1.0, 2.0]),
+            OutputValue::List(vec![OutputValue::Scalar(1.0), OutputValue::Scalar(2.0)])
+        );
+
+        assert_eq!(OutputValue::Flag(true).as_scalar(), None);
+        assert_eq!(OutputValue::Scalar(1.0).get("value"), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let value = OutputValue::Named(vec![
+            ("value", OutputValue::Scalar(1.5)),
+            ("level", OutputValue::None),
+            ("kind", OutputValue::Label("regular".to_string())),
+            ("values", OutputValue::from(vec![1.0, 2.5])),
+        ]);
+        assert_eq!(
+            format!("{}", value),
+            "value: 1.5, level: none, kind: regular, values: [1, 2.5]"
+        );
+    }
+
+    #[test]
+    fn test_dyn_indicator() {
+        let mut indicators: Vec<Box<dyn DynIndicator>> = vec![
+            Box::new(Sma::new(2).unwrap()),
+            Box::new(Macd::new(2, 3, 2).unwrap()),
+            Box::new(MovingAverageRibbon::new(2, 2, 3, RibbonSpacing::Arithmetic).unwrap()),
+            Box::new(InsideOutsideBar::new(2).unwrap()),
+        ];
+
+        for indicator in indicators.iter_mut() {
+            indicator.next_value(&bar(10.0, 5.0));
+        }
+        let values: Vec<OutputValue> = indicators
+            .iter_mut()
+            .map(|indicator| indicator.next_value(&bar(9.0, 6.0)))
+            .collect();
+
+        assert_eq!(values[0], OutputValue::Scalar(9.5));
+        assert!(values[1].get("signal").is_some());
+        assert_eq!(
+            values[2].get("values").map(|values| values.to_string()),
+            Some("[9.333333333333334, 9.5]".to_string())
+        );
+        assert_eq!(values[3], OutputValue::from(BarRelation::Inside));
+
+        for indicator in indicators.iter_mut() {
+            indicator.reset();
+            assert!(!indicator.is_ready() || indicator.lookback() == 1);
+        }
+        assert_eq!(indicators[1].lookback(), 4);
+        assert_eq!(format!("{}", indicators[0]), "SMA(2)");
+    }
+}
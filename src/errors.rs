@@ -1,31 +1,196 @@
-use std::error::Error;
-use std::fmt::{Display, Formatter};
+use core::fmt::{Display, Formatter};
+use core::num::ParseFloatError;
 
-pub type Result<T> = std::result::Result<T, TaError>;
+pub type Result<T> = core::result::Result<T, TaError>;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// Error of the library.
+///
+/// More variants may be added, e.g. the factory's [UnknownIndicator](#variant.UnknownIndicator)
+/// and [UnknownParameter](#variant.UnknownParameter) exist only with the `std` feature, so the
+/// enum is `#[non_exhaustive]` and a `match` on it needs a wildcard arm.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum TaError {
-    InvalidParameter,
+    /// A parameter is out of its valid range, e.g. a period of 0.
+    InvalidParameter {
+        /// Name of the parameter, as in the documentation of the indicator.
+        name: &'static str,
+        /// Offending value of the parameter.
+        value: f64,
+        /// Valid range of the parameter, e.g. `">= 1"`.
+        expected: &'static str,
+    },
+    /// No indicator is known by this name.
+    #[cfg(feature = "std")]
+    UnknownIndicator(String),
+    /// The indicator has no parameter of this name.
+    #[cfg(feature = "std")]
+    UnknownParameter(String),
+    /// An expression is malformed, with a description of what is wrong.
+    InvalidExpression(&'static str),
+    /// A number in an expression can't be parsed.
+    InvalidNumber(ParseFloatError),
     DataItemIncomplete,
     DataItemInvalid,
+    InvalidState,
 }
 
+impl TaError {
+    /// Error for the parameter `name` with a `value` out of the `expected` range.
+    pub fn invalid_parameter(name: &'static str, value: f64, expected: &'static str) -> Self {
+        TaError::InvalidParameter {
+            name,
+            value,
+            expected,
+        }
+    }
+}
+
+// The value of an invalid parameter is compared by its bits, so an error equals itself even for a
+// NaN value and `TaError` stays `Eq`.
+impl PartialEq for TaError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                TaError::InvalidParameter {
+                    name,
+                    value,
+                    expected,
+                },
+                TaError::InvalidParameter {
+                    name: other_name,
+                    value: other_value,
+                    expected: other_expected,
+                },
+            ) => {
+                name == other_name
+                    && value.to_bits() == other_value.to_bits()
+                    && expected == other_expected
+            }
+            #[cfg(feature = "std")]
+            (TaError::UnknownIndicator(name), TaError::UnknownIndicator(other)) => name == other,
+            #[cfg(feature = "std")]
+            (TaError::UnknownParameter(name), TaError::UnknownParameter(other)) => name == other,
+            (TaError::InvalidExpression(reason), TaError::InvalidExpression(other)) => {
+                reason == other
+            }
+            (TaError::InvalidNumber(err), TaError::InvalidNumber(other)) => err == other,
+            (TaError::DataItemIncomplete, TaError::DataItemIncomplete)
+            | (TaError::DataItemInvalid, TaError::DataItemInvalid)
+            | (TaError::InvalidState, TaError::InvalidState) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for TaError {}
+
 impl Display for TaError {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        match *self {
-            TaError::InvalidParameter => write!(f, "invalid parameter"),
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            TaError::InvalidParameter {
+                name,
+                value,
+                expected,
+            } => write!(
+                f,
+                "invalid parameter {} = {}, expected {}",
+                name, value, expected
+            ),
+            #[cfg(feature = "std")]
+            TaError::UnknownIndicator(name) => write!(f, "unknown indicator {:?}", name),
+            #[cfg(feature = "std")]
+            TaError::UnknownParameter(name) => write!(f, "unknown parameter {:?}", name),
+            TaError::InvalidExpression(reason) => write!(f, "invalid expression: {}", reason),
+            TaError::InvalidNumber(_) => write!(f, "invalid number in expression"),
             TaError::DataItemIncomplete => write!(f, "data item is incomplete"),
             TaError::DataItemInvalid => write!(f, "data item is invalid"),
+            TaError::InvalidState => write!(f, "invalid indicator state"),
         }
     }
 }
 
-impl Error for TaError {
-    fn source(&self) -> Option<&(dyn Error + 'static)> {
-        match *self {
-            TaError::InvalidParameter => None,
-            TaError::DataItemIncomplete => None,
-            TaError::DataItemInvalid => None,
+#[cfg(feature = "std")]
+impl std::error::Error for TaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TaError::InvalidNumber(err) => Some(err),
+            TaError::InvalidParameter { .. }
+            | TaError::UnknownIndicator(_)
+            | TaError::UnknownParameter(_)
+            | TaError::InvalidExpression(_)
+            | TaError::DataItemIncomplete
+            | TaError::DataItemInvalid
+            | TaError::InvalidState => None,
         }
     }
 }
+
+impl From<ParseFloatError> for TaError {
+    fn from(err: ParseFloatError) -> Self {
+        TaError::InvalidNumber(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let err = TaError::invalid_parameter("period", 0.0, ">= 1");
+        assert_eq!(
+            err.to_string(),
+            "invalid parameter period = 0, expected >= 1"
+        );
+        assert_eq!(
+            TaError::InvalidExpression("unexpected token").to_string(),
+            "invalid expression: unexpected token"
+        );
+    }
+
+    #[test]
+    fn test_eq() {
+        let nan = TaError::invalid_parameter("multiplier", f64::NAN, "> 0");
+        assert_eq!(nan, nan.clone());
+        assert_ne!(
+            TaError::invalid_parameter("period", 0.0, ">= 1"),
+            TaError::invalid_parameter("period", 1.0, ">= 1")
+        );
+        assert_ne!(
+            TaError::invalid_parameter("period", 0.0, ">= 1"),
+            TaError::InvalidState
+        );
+        assert_eq!(TaError::InvalidState, TaError::InvalidState);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_display_unknown_names() {
+        assert_eq!(
+            TaError::UnknownIndicator("foo".to_string()).to_string(),
+            "unknown indicator \"foo\""
+        );
+        assert_eq!(
+            TaError::UnknownParameter("length".to_string()).to_string(),
+            "unknown parameter \"length\""
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_source() {
+        use std::error::Error;
+
+        let parse_err = "x".parse::<f64>().unwrap_err();
+        let err = TaError::from(parse_err.clone());
+        assert_eq!(err, TaError::InvalidNumber(parse_err.clone()));
+        assert_eq!(
+            err.source().map(|source| source.to_string()),
+            Some(parse_err.to_string())
+        );
+        assert!(TaError::invalid_parameter("k", -1.0, "> 0")
+            .source()
+            .is_none());
+    }
+}
@@ -0,0 +1,488 @@
+//! Creates indicators from their names and parameters, e.g. read from a config file.
+//!
+//! Indicators are created as [DynIndicator]s, named after their common abbreviation in lower
+//! case, e.g. `"rsi"` or `"macd"`. Parameters are given by name, missing ones take the default
+//! value of the indicator. Names of indicators and parameters are not case sensitive.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::factory;
+//!
+//! let rsi = factory::create("rsi", &[("period", 14.0)]).unwrap();
+//! assert_eq!(rsi.to_string(), "RSI(14)");
+//!
+//! // Missing parameters take the default values
+//! let macd = factory::create("MACD", &[("signal_period", 5.0)]).unwrap();
+//! assert_eq!(macd.to_string(), "MACD(12, 26, 5)");
+//!
+//! assert!(factory::create("rsi", &[("length", 14.0)]).is_err());
+//! assert!(factory::create("unknown", &[]).is_err());
+//! ```
+//!
+//! Indicators which consume pairs of series (e.g. [Correlation]) or returns (e.g.
+//! [SortinoRatio]) and wrappers of other indicators (e.g. [Sourced]) are not available.
+//! Parameters which are not numbers, like the kind of moving average of [DisparityIndex], keep
+//! their default values.
+
+use std::ops::Index;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::*;
+use crate::DynIndicator;
+
+type Build = fn(&Params) -> Result<Box<dyn DynIndicator>>;
+
+/// Names of an indicator, its parameters with their default values, and its constructor,
+/// which is called with the values of all parameters in the same order.
+struct Entry {
+    names: &'static [&'static str],
+    params: &'static [(&'static str, f64)],
+    build: Build,
+}
+
+macro_rules! entry {
+    ([$($name:literal),+], [$($param:literal = $default:expr),*], $build:expr) => {
+        Entry {
+            names: &[$($name),+],
+            params: &[$(($param, $default)),*],
+            build: $build,
+        }
+    };
+}
+
+/// Values of the parameters of an entry, in the order of their names.
+struct Params<'a> {
+    names: &'static [(&'static str, f64)],
+    values: &'a [f64],
+}
+
+impl Params<'_> {
+    /// Converts the parameter at `index` into a count or a period.
+    fn int(&self, index: usize) -> Result<usize> {
+        let value = self.values[index];
+        if value >= 0.0 && value.fract() == 0.0 && value <= usize::MAX as f64 {
+            Ok(value as usize)
+        } else {
+            Err(TaError::invalid_parameter(
+                self.names[index].0,
+                value,
+                "a non-negative integer",
+            ))
+        }
+    }
+}
+
+impl Index<usize> for Params<'_> {
+    type Output = f64;
+
+    fn index(&self, index: usize) -> &f64 {
+        &self.values[index]
+    }
+}
+
+#[rustfmt::skip]
+static REGISTRY: &[Entry] = &[
+    // Trend
+    entry!(["ema"], ["period" = 9.0], |p| Ok(Box::new(ExponentialMovingAverage::new(p.int(0)?)?))),
+    entry!(["sma"], ["period" = 9.0], |p| Ok(Box::new(SimpleMovingAverage::new(p.int(0)?)?))),
+    entry!(["wma"], ["period" = 9.0], |p| Ok(Box::new(WeightedMovingAverage::new(p.int(0)?)?))),
+    entry!(["ssma"], ["period" = 9.0], |p| Ok(Box::new(SmoothedSimpleMovingAverage::new(p.int(0)?)?))),
+    entry!(["tema"], ["period" = 9.0], |p| Ok(Box::new(TripleExponentialMovingAverage::new(p.int(0)?)?))),
+    entry!(["kama"], ["er_period" = 10.0, "fast_period" = 2.0, "slow_period" = 30.0],
+        |p| Ok(Box::new(KaufmanAdaptiveMovingAverage::new(p.int(0)?, p.int(1)?, p.int(2)?)?))),
+    entry!(["vwap"], ["period" = 14.0], |p| Ok(Box::new(VolumeWeightedAveragePrice::new(p.int(0)?)?))),
+    entry!(["avwap"], [], |_| Ok(Box::new(AnchoredVwap::new()))),
+    entry!(["md"], ["period" = 14.0, "k" = 0.6], |p| Ok(Box::new(McGinleyDynamic::new(p.int(0)?, p[1])?))),
+    entry!(["frama"], ["period" = 16.0], |p| Ok(Box::new(FractalAdaptiveMovingAverage::new(p.int(0)?)?))),
+    entry!(["t3"], ["period" = 5.0, "volume_factor" = 0.7], |p| Ok(Box::new(TillsonT3::new(p.int(0)?, p[1])?))),
+    entry!(["mama"], ["fast_limit" = 0.5, "slow_limit" = 0.05],
+        |p| Ok(Box::new(MesaAdaptiveMovingAverage::new(p[0], p[1])?))),
+    entry!(["vwma"], ["period" = 20.0], |p| Ok(Box::new(VolumeWeightedMovingAverage::new(p.int(0)?)?))),
+    entry!(["ribbon"], ["count" = 6.0, "shortest" = 10.0, "longest" = 60.0],
+        |p| Ok(Box::new(MovingAverageRibbon::new(p.int(0)?, p.int(1)?, p.int(2)?, RibbonSpacing::Arithmetic)?))),
+    entry!(["alligator"], ["jaw_period" = 13.0, "jaw_shift" = 8.0, "teeth_period" = 8.0,
+                           "teeth_shift" = 5.0, "lips_period" = 5.0, "lips_shift" = 3.0],
+        |p| Ok(Box::new(Alligator::new(p.int(0)?, p.int(1)?, p.int(2)?, p.int(3)?, p.int(4)?, p.int(5)?)?))),
+    entry!(["laguerre"], ["gamma" = 0.8], |p| Ok(Box::new(LaguerreFilter::new(p[0])?))),
+    entry!(["ssf"], ["period" = 10.0], |p| Ok(Box::new(SuperSmoother::new(p.int(0)?)?))),
+    entry!(["tma"], ["period" = 9.0], |p| Ok(Box::new(TriangularMovingAverage::new(p.int(0)?)?))),
+    entry!(["gma"], ["period" = 9.0], |p| Ok(Box::new(GeometricMovingAverage::new(p.int(0)?)?))),
+    entry!(["harmonic"], ["period" = 9.0], |p| Ok(Box::new(HarmonicMovingAverage::new(p.int(0)?)?))),
+    // Oscillators
+    entry!(["rsi"], ["period" = 14.0], |p| Ok(Box::new(RelativeStrengthIndex::new(p.int(0)?)?))),
+    entry!(["fast_stoch"], ["period" = 14.0], |p| Ok(Box::new(FastStochastic::new(p.int(0)?)?))),
+    entry!(["slow_stoch"], ["stochastic_period" = 14.0, "ema_period" = 3.0],
+        |p| Ok(Box::new(SlowStochastic::new(p.int(0)?, p.int(1)?)?))),
+    entry!(["macd"], ["fast_period" = 12.0, "slow_period" = 26.0, "signal_period" = 9.0],
+        |p| Ok(Box::new(MovingAverageConvergenceDivergence::new(p.int(0)?, p.int(1)?, p.int(2)?)?))),
+    entry!(["ppo"], ["fast_period" = 12.0, "slow_period" = 26.0, "signal_period" = 9.0],
+        |p| Ok(Box::new(PercentagePriceOscillator::new(p.int(0)?, p.int(1)?, p.int(2)?)?))),
+    entry!(["cci"], ["period" = 20.0], |p| Ok(Box::new(CommodityChannelIndex::new(p.int(0)?)?))),
+    entry!(["mfi"], ["period" = 14.0], |p| Ok(Box::new(MoneyFlowIndex::new(p.int(0)?)?))),
+    entry!(["vi"], ["period" = 14.0], |p| Ok(Box::new(VortexIndicator::new(p.int(0)?)?))),
+    entry!(["trix"], ["period" = 15.0, "signal_period" = 9.0], |p| Ok(Box::new(Trix::new(p.int(0)?, p.int(1)?)?))),
+    entry!(["uo"], ["short_period" = 7.0, "medium_period" = 14.0, "long_period" = 28.0],
+        |p| Ok(Box::new(UltimateOscillator::new(p.int(0)?, p.int(1)?, p.int(2)?)?))),
+    entry!(["ao"], ["fast_period" = 5.0, "slow_period" = 34.0],
+        |p| Ok(Box::new(AwesomeOscillator::new(p.int(0)?, p.int(1)?)?))),
+    entry!(["ac"], ["fast_period" = 5.0, "slow_period" = 34.0, "signal_period" = 5.0],
+        |p| Ok(Box::new(AcceleratorOscillator::new(p.int(0)?, p.int(1)?, p.int(2)?)?))),
+    entry!(["cmf"], ["period" = 20.0], |p| Ok(Box::new(ChaikinMoneyFlow::new(p.int(0)?)?))),
+    entry!(["fi"], ["period" = 13.0], |p| Ok(Box::new(ForceIndex::new(p.int(0)?)?))),
+    entry!(["eri"], ["period" = 13.0], |p| Ok(Box::new(ElderRay::new(p.int(0)?)?))),
+    entry!(["dpo"], ["period" = 20.0], |p| Ok(Box::new(DetrendedPriceOscillator::new(p.int(0)?)?))),
+    entry!(["crsi"], ["rsi_period" = 3.0, "streak_period" = 2.0, "rank_period" = 100.0],
+        |p| Ok(Box::new(ConnorsRsi::new(p.int(0)?, p.int(1)?, p.int(2)?)?))),
+    entry!(["tsi"], ["long_period" = 25.0, "short_period" = 13.0, "signal_period" = 13.0],
+        |p| Ok(Box::new(TrueStrengthIndex::new(p.int(0)?, p.int(1)?, p.int(2)?)?))),
+    entry!(["ft"], ["period" = 10.0], |p| Ok(Box::new(FisherTransform::new(p.int(0)?)?))),
+    entry!(["eom"], ["period" = 14.0, "divisor" = 100_000_000.0],
+        |p| Ok(Box::new(EaseOfMovement::new(p.int(0)?, p[1])?))),
+    entry!(["bop"], ["period" = 14.0], |p| Ok(Box::new(BalanceOfPower::new(p.int(0)?)?))),
+    entry!(["rvi"], ["period" = 10.0, "signal_period" = 4.0],
+        |p| Ok(Box::new(RelativeVigorIndex::new(p.int(0)?, p.int(1)?)?))),
+    entry!(["qqe"], ["rsi_period" = 14.0, "smoothing_period" = 5.0, "fast_factor" = 2.618, "slow_factor" = 4.236],
+        |p| Ok(Box::new(Qqe::new(p.int(0)?, p.int(1)?, p[2], p[3])?))),
+    entry!(["sqzmom"], ["bb_period" = 20.0, "bb_multiplier" = 2.0, "kc_period" = 20.0, "kc_multiplier" = 1.5],
+        |p| Ok(Box::new(SqueezeMomentum::new(p.int(0)?, p[1], p.int(2)?, p[3])?))),
+    entry!(["cmo"], ["period" = 14.0], |p| Ok(Box::new(ChandeMomentumOscillator::new(p.int(0)?)?))),
+    entry!(["smi"], ["k_period" = 10.0, "smoothing_period" = 3.0, "signal_period" = 3.0],
+        |p| Ok(Box::new(StochasticMomentumIndex::new(p.int(0)?, p.int(1)?, p.int(2)?)?))),
+    entry!(["pvo"], ["fast_period" = 12.0, "slow_period" = 26.0, "signal_period" = 9.0],
+        |p| Ok(Box::new(PercentageVolumeOscillator::new(p.int(0)?, p.int(1)?, p.int(2)?)?))),
+    entry!(["vo"], ["fast_period" = 5.0, "slow_period" = 10.0],
+        |p| Ok(Box::new(VolumeOscillator::new(p.int(0)?, p.int(1)?)?))),
+    entry!(["vroc"], ["period" = 14.0], |p| Ok(Box::new(VolumeRateOfChange::new(p.int(0)?)?))),
+    entry!(["gator"], ["jaw_period" = 13.0, "jaw_shift" = 8.0, "teeth_period" = 8.0,
+                       "teeth_shift" = 5.0, "lips_period" = 5.0, "lips_shift" = 3.0],
+        |p| Ok(Box::new(GatorOscillator::new(p.int(0)?, p.int(1)?, p.int(2)?, p.int(3)?, p.int(4)?, p.int(5)?)?))),
+    entry!(["lrsi"], ["gamma" = 0.5], |p| Ok(Box::new(LaguerreRsi::new(p[0])?))),
+    entry!(["roof"], ["high_pass_period" = 48.0, "smoother_period" = 10.0],
+        |p| Ok(Box::new(RoofingFilter::new(p.int(0)?, p.int(1)?)?))),
+    entry!(["cg"], ["period" = 10.0], |p| Ok(Box::new(CenterOfGravity::new(p.int(0)?)?))),
+    entry!(["disparity"], ["period" = 14.0],
+        |p| Ok(Box::new(DisparityIndex::new(p.int(0)?, MovingAverageType::Simple)?))),
+    entry!(["tii"], ["period" = 60.0, "window" = 30.0],
+        |p| Ok(Box::new(TrendIntensityIndex::new(p.int(0)?, p.int(1)?)?))),
+    entry!(["tmf"], ["period" = 21.0], |p| Ok(Box::new(TwiggsMoneyFlow::new(p.int(0)?)?))),
+    // Other
+    entry!(["sd"], ["period" = 9.0], |p| Ok(Box::new(StandardDeviation::new(p.int(0)?)?))),
+    entry!(["mad"], ["period" = 9.0], |p| Ok(Box::new(MeanAbsoluteDeviation::new(p.int(0)?)?))),
+    entry!(["bb", "bbands"], ["period" = 9.0, "multiplier" = 2.0],
+        |p| Ok(Box::new(BollingerBands::new(p.int(0)?, p[1])?))),
+    entry!(["ce"], ["period" = 22.0, "multiplier" = 3.0], |p| Ok(Box::new(ChandelierExit::new(p.int(0)?, p[1])?))),
+    entry!(["kc"], ["period" = 10.0, "multiplier" = 2.0], |p| Ok(Box::new(KeltnerChannel::new(p.int(0)?, p[1])?))),
+    entry!(["max"], ["period" = 14.0], |p| Ok(Box::new(Maximum::new(p.int(0)?)?))),
+    entry!(["min"], ["period" = 14.0], |p| Ok(Box::new(Minimum::new(p.int(0)?)?))),
+    entry!(["tr"], [], |_| Ok(Box::new(TrueRange::new()))),
+    entry!(["atr"], ["period" = 14.0], |p| Ok(Box::new(AverageTrueRange::new(p.int(0)?)?))),
+    entry!(["er"], ["period" = 14.0], |p| Ok(Box::new(EfficiencyRatio::new(p.int(0)?)?))),
+    entry!(["roc"], ["period" = 9.0], |p| Ok(Box::new(RateOfChange::new(p.int(0)?)?))),
+    entry!(["obv"], [], |_| Ok(Box::new(OnBalanceVolume::new()))),
+    entry!(["dc"], ["period" = 20.0], |p| Ok(Box::new(DonchianChannel::new(p.int(0)?)?))),
+    entry!(["adl"], [], |_| Ok(Box::new(AccumulationDistributionLine::new()))),
+    entry!(["chop"], ["period" = 14.0], |p| Ok(Box::new(ChoppinessIndex::new(p.int(0)?)?))),
+    entry!(["pvt"], [], |_| Ok(Box::new(PriceVolumeTrend::new()))),
+    entry!(["linreg"], ["period" = 14.0], |p| Ok(Box::new(LinearRegression::new(p.int(0)?)?))),
+    entry!(["zs"], ["period" = 20.0], |p| Ok(Box::new(ZScore::new(p.int(0)?)?))),
+    entry!(["quantile"], ["period" = 20.0, "quantile" = 0.5],
+        |p| Ok(Box::new(RollingQuantile::new(p.int(0)?, p[1])?))),
+    entry!(["dcperiod"], [], |_| Ok(Box::new(DominantCyclePeriod::new()))),
+    entry!(["fractals"], ["period" = 2.0], |p| Ok(Box::new(WilliamsFractals::new(p.int(0)?)?))),
+    entry!(["atr_stop"], ["period" = 14.0, "multiplier" = 3.0],
+        |p| Ok(Box::new(AtrTrailingStop::new(p.int(0)?, p[1])?))),
+    entry!(["nvi"], ["signal_period" = 255.0], |p| Ok(Box::new(NegativeVolumeIndex::new(p.int(0)?)?))),
+    entry!(["pvi"], ["signal_period" = 255.0], |p| Ok(Box::new(PositiveVolumeIndex::new(p.int(0)?)?))),
+    entry!(["hv"], ["period" = 20.0, "periods_per_year" = 252.0],
+        |p| Ok(Box::new(HistoricalVolatility::new(p.int(0)?, p[1])?))),
+    entry!(["rvol"], ["period" = 20.0, "periods_per_year" = 252.0],
+        |p| Ok(Box::new(RangeVolatility::new(p.int(0)?, VolatilityEstimator::YangZhang, p[1])?))),
+    entry!(["dd"], ["period" = 756.0, "periods_per_year" = 252.0],
+        |p| Ok(Box::new(DrawdownStats::new(p.int(0)?, p[1])?))),
+    entry!(["ret"], ["lag" = 1.0], |p| Ok(Box::new(SimpleReturn::new(p.int(0)?)?))),
+    entry!(["logret"], ["lag" = 1.0], |p| Ok(Box::new(LogReturn::new(p.int(0)?)?))),
+    entry!(["cumret"], ["base" = 100.0], |p| Ok(Box::new(CumulativeReturn::new(p[0])?))),
+    entry!(["iob"], ["compression" = 2.0], |p| Ok(Box::new(InsideOutsideBar::new(p.int(0)?)?))),
+    entry!(["pivot"], ["left" = 5.0, "right" = 5.0], |p| Ok(Box::new(SwingPoints::new(p.int(0)?, p.int(1)?)?))),
+    entry!(["sr"], ["strength" = 5.0, "width" = 0.005, "max_zones" = 10.0],
+        |p| Ok(Box::new(SupportResistance::new(p.int(0)?, p[1], p.int(2)?)?))),
+    entry!(["vp"], ["period" = 20.0, "bins" = 24.0, "value_area" = 0.7],
+        |p| Ok(Box::new(VolumeProfile::new(p.int(0)?, ProfileBins::Count(p.int(1)?), p[2])?))),
+    entry!(["tp"], [], |_| Ok(Box::new(TypicalPrice::new()))),
+    entry!(["mp"], [], |_| Ok(Box::new(MedianPrice::new()))),
+    entry!(["wc"], [], |_| Ok(Box::new(WeightedClose::new()))),
+    entry!(["bw_mfi"], [], |_| Ok(Box::new(MarketFacilitationIndex::new()))),
+    entry!(["seb"], ["period" = 21.0, "multiplier" = 2.0],
+        |p| Ok(Box::new(StandardErrorBands::new(p.int(0)?, p[1])?))),
+    entry!(["abands"], ["period" = 20.0, "factor" = 4.0],
+        |p| Ok(Box::new(AccelerationBands::new(p.int(0)?, p[1])?))),
+    entry!(["autocorr"], ["period" = 20.0, "lag" = 1.0],
+        |p| Ok(Box::new(Autocorrelation::new(p.int(0)?, p.int(1)?)?))),
+    entry!(["entropy"], ["period" = 20.0, "bins" = 10.0, "range" = 0.05],
+        |p| Ok(Box::new(RollingEntropy::new(p.int(0)?, p.int(1)?, p[2])?))),
+    entry!(["fib"], ["strength" = 5.0], |p| Ok(Box::new(FibonacciLevels::new(p.int(0)?)?))),
+    entry!(["fdi"], ["period" = 30.0], |p| Ok(Box::new(FractalDimensionIndex::new(p.int(0)?)?))),
+];
+
+fn find(name: &str) -> Option<&'static Entry> {
+    REGISTRY.iter().find(|entry| {
+        entry
+            .names
+            .iter()
+            .any(|entry_name| entry_name.eq_ignore_ascii_case(name))
+    })
+}
+
+/// Creates the indicator with the given name and parameters.
+///
+/// Returns an error if the indicator or one of the parameters is unknown, or if the indicator
+/// can't be created with the given values.
+pub fn create(name: &str, params: &[(&str, f64)]) -> Result<Box<dyn DynIndicator>> {
+    let entry = find(name).ok_or_else(|| TaError::UnknownIndicator(name.to_string()))?;
+
+    let mut values: Vec<f64> = entry.params.iter().map(|&(_, default)| default).collect();
+    for &(param, value) in params {
+        let index = entry
+            .params
+            .iter()
+            .position(|(entry_param, _)| entry_param.eq_ignore_ascii_case(param))
+            .ok_or_else(|| TaError::UnknownParameter(param.to_string()))?;
+        values[index] = value;
+    }
+
+    (entry.build)(&Params {
+        names: entry.params,
+        values: &values,
+    })
+}
+
+/// Parameters of the indicator with the given name and their default values, in the order of
+/// the arguments of its constructor.
+pub fn parameters(name: &str) -> Option<&'static [(&'static str, f64)]> {
+    find(name).map(|entry| entry.params)
+}
+
+/// Names of all indicators that can be created.
+pub fn names() -> impl Iterator<Item = &'static str> {
+    REGISTRY
+        .iter()
+        .flat_map(|entry| entry.names.iter().copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DataItem;
+
+    #[test]
+    fn test_create() {
+        let mut sma = create("sma", &[("period", 2.0)]).unwrap();
+        let bar = DataItem::builder()
+            .open(1.0)
+            .high(3.0)
+            .low(1.0)
+            .close(2.0)
+            .volume(10.0)
+            .build()
+            .unwrap();
+
+        assert_eq!(sma.to_string(), "SMA(2)");
+        assert_eq!(sma.next_value(&bar).as_scalar(), Some(2.0));
+        assert_eq!(sma.lookback(), 2);
+
+        let bb = create("BBands", &[("Multiplier", 3.0)]).unwrap();
+        assert_eq!(bb.to_string(), "BB(9, 3)");
+    }
+
+    #[test]
+    fn test_create_errors() {
+        assert_eq!(
+            create("foo", &[]).err(),
+            Some(TaError::UnknownIndicator("foo".to_string()))
+        );
+        assert_eq!(
+            create("sma", &[("length", 2.0)]).err(),
+            Some(TaError::UnknownParameter("length".to_string()))
+        );
+        assert_eq!(
+            create("sma", &[("period", 0.0)]).err(),
+            Some(TaError::invalid_parameter("period", 0.0, ">= 1"))
+        );
+        assert_eq!(
+            create("pivot", &[("right", 2.5)]).err(),
+            Some(TaError::invalid_parameter(
+                "right",
+                2.5,
+                "a non-negative integer"
+            ))
+        );
+        assert!(create("sma", &[("period", -2.0)]).is_err());
+        assert!(create("sma", &[("period", f64::NAN)]).is_err());
+        assert!(create("macd", &[("signal_period", 0.0)]).is_err());
+    }
+
+    #[test]
+    fn test_defaults() {
+        // Every indicator can be created with its default parameters
+        for name in names() {
+            let indicator = create(name, &[]).unwrap();
+            let params = parameters(name).unwrap();
+            assert!(
+                params.iter().all(|&(_, value)| value.is_finite()),
+                "{}",
+                name
+            );
+            assert!(!indicator.to_string().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_default_parameters() {
+        // Without parameters the indicators are the same as their Default
+        macro_rules! assert_default {
+            ($($name:literal => $indicator:ident),* $(,)?) => {
+                $(
+                    assert_eq!(
+                        create($name, &[]).unwrap().to_string(),
+                        $indicator::default().to_string(),
+                        "{}",
+                        $name
+                    );
+                )*
+            };
+        }
+
+        assert_default!(
+            "ema" => ExponentialMovingAverage,
+            "sma" => SimpleMovingAverage,
+            "wma" => WeightedMovingAverage,
+            "ssma" => SmoothedSimpleMovingAverage,
+            "tema" => TripleExponentialMovingAverage,
+            "kama" => KaufmanAdaptiveMovingAverage,
+            "vwap" => VolumeWeightedAveragePrice,
+            "avwap" => AnchoredVwap,
+            "md" => McGinleyDynamic,
+            "frama" => FractalAdaptiveMovingAverage,
+            "t3" => TillsonT3,
+            "mama" => MesaAdaptiveMovingAverage,
+            "vwma" => VolumeWeightedMovingAverage,
+            "ribbon" => MovingAverageRibbon,
+            "alligator" => Alligator,
+            "laguerre" => LaguerreFilter,
+            "ssf" => SuperSmoother,
+            "tma" => TriangularMovingAverage,
+            "gma" => GeometricMovingAverage,
+            "harmonic" => HarmonicMovingAverage,
+            "rsi" => RelativeStrengthIndex,
+            "fast_stoch" => FastStochastic,
+            "slow_stoch" => SlowStochastic,
+            "macd" => MovingAverageConvergenceDivergence,
+            "ppo" => PercentagePriceOscillator,
+            "cci" => CommodityChannelIndex,
+            "mfi" => MoneyFlowIndex,
+            "vi" => VortexIndicator,
+            "trix" => Trix,
+            "uo" => UltimateOscillator,
+            "ao" => AwesomeOscillator,
+            "ac" => AcceleratorOscillator,
+            "cmf" => ChaikinMoneyFlow,
+            "fi" => ForceIndex,
+            "eri" => ElderRay,
+            "dpo" => DetrendedPriceOscillator,
+            "crsi" => ConnorsRsi,
+            "tsi" => TrueStrengthIndex,
+            "ft" => FisherTransform,
+            "eom" => EaseOfMovement,
+            "bop" => BalanceOfPower,
+            "rvi" => RelativeVigorIndex,
+            "qqe" => Qqe,
+            "sqzmom" => SqueezeMomentum,
+            "cmo" => ChandeMomentumOscillator,
+            "smi" => StochasticMomentumIndex,
+            "pvo" => PercentageVolumeOscillator,
+            "vo" => VolumeOscillator,
+            "vroc" => VolumeRateOfChange,
+            "gator" => GatorOscillator,
+            "lrsi" => LaguerreRsi,
+            "roof" => RoofingFilter,
+            "cg" => CenterOfGravity,
+            "disparity" => DisparityIndex,
+            "tii" => TrendIntensityIndex,
+            "tmf" => TwiggsMoneyFlow,
+            "sd" => StandardDeviation,
+            "mad" => MeanAbsoluteDeviation,
+            "bb" => BollingerBands,
+            "ce" => ChandelierExit,
+            "kc" => KeltnerChannel,
+            "max" => Maximum,
+            "min" => Minimum,
+            "tr" => TrueRange,
+            "atr" => AverageTrueRange,
+            "er" => EfficiencyRatio,
+            "roc" => RateOfChange,
+            "obv" => OnBalanceVolume,
+            "dc" => DonchianChannel,
+            "adl" => AccumulationDistributionLine,
+            "chop" => ChoppinessIndex,
+            "pvt" => PriceVolumeTrend,
+            "linreg" => LinearRegression,
+            "zs" => ZScore,
+            "quantile" => RollingQuantile,
+            "dcperiod" => DominantCyclePeriod,
+            "fractals" => WilliamsFractals,
+            "atr_stop" => AtrTrailingStop,
+            "nvi" => NegativeVolumeIndex,
+            "pvi" => PositiveVolumeIndex,
+            "hv" => HistoricalVolatility,
+            "rvol" => RangeVolatility,
+            "dd" => DrawdownStats,
+            "ret" => SimpleReturn,
+            "logret" => LogReturn,
+            "cumret" => CumulativeReturn,
+            "iob" => InsideOutsideBar,
+            "pivot" => SwingPoints,
+            "sr" => SupportResistance,
+            "vp" => VolumeProfile,
+            "tp" => TypicalPrice,
+            "mp" => MedianPrice,
+            "wc" => WeightedClose,
+            "bw_mfi" => MarketFacilitationIndex,
+            "seb" => StandardErrorBands,
+            "abands" => AccelerationBands,
+            "autocorr" => Autocorrelation,
+            "entropy" => RollingEntropy,
+            "fib" => FibonacciLevels,
+            "fdi" => FractalDimensionIndex,
+        );
+    }
+
+    #[test]
+    fn test_parameters() {
+        assert_eq!(
+            parameters("macd"),
+            Some(
+                &[
+                    ("fast_period", 12.0),
+                    ("slow_period", 26.0),
+                    ("signal_period", 9.0)
+                ][..]
+            )
+        );
+        assert_eq!(parameters("obv"), Some(&[][..]));
+        assert_eq!(parameters("foo"), None);
+    }
+
+    #[test]
+    fn test_names() {
+        let names: Vec<&str> = names().collect();
+        for (i, name) in names.iter().enumerate() {
+            assert_eq!(name.to_lowercase(), *name);
+            assert!(
+                !names[i + 1..].contains(name),
+                "{} is registered twice",
+                name
+            );
+        }
+    }
+}
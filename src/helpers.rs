@@ -1,8 +1,367 @@
+use core::fmt;
+
+use crate::Numeric;
+
+#[cfg(feature = "std")]
+use crate::errors::{Result, TaError};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Returns the largest of 3 given numbers.
-pub fn max3(a: f64, b: f64, c: f64) -> f64 {
+pub fn max3<N: Numeric>(a: N, b: N, c: N) -> N {
     a.max(b).max(c)
 }
 
+/// Delays a series by a fixed number of values, e.g. to displace an indicator line forward.
+///
+/// Until _shift_ values have been seen, the first value is returned.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct Delay {
+    index: usize,
+    count: usize,
+    deque: Box<[f64]>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+}
+
+#[cfg(feature = "std")]
+impl Delay {
+    pub fn new(shift: usize) -> Self {
+        Self {
+            index: 0,
+            count: 0,
+            deque: vec![0.0; shift].into_boxed_slice(),
+            saved_value: 0.0,
+            saved_count: 0,
+        }
+    }
+
+    pub fn shift(&self) -> usize {
+        self.deque.len()
+    }
+
+    /// Stores `input` and returns the value from _shift_ calls ago.
+    pub fn next(&mut self, input: f64) -> f64 {
+        let shift = self.deque.len();
+        if shift == 0 {
+            return input;
+        }
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
+
+        let delayed = if self.count == shift {
+            self.deque[self.index]
+        } else {
+            self.count += 1;
+            if self.count == 1 {
+                input
+            } else {
+                self.deque[0]
+            }
+        };
+        self.deque[self.index] = input;
+
+        self.index = if self.index + 1 < shift {
+            self.index + 1
+        } else {
+            0
+        };
+
+        delayed
+    }
+
+    /// Replaces the last input with `input` and returns what `next` would have returned for it.
+    pub fn amend(&mut self, input: f64) -> f64 {
+        let shift = self.deque.len();
+        if shift == 0 || self.count == 0 {
+            return self.next(input);
+        }
+        self.index = (self.index + shift - 1) % shift;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.next(input)
+    }
+
+    /// Returns what `next` would return for `input`, without storing it.
+    pub fn peek(&self, input: f64) -> f64 {
+        let shift = self.deque.len();
+        if shift == 0 {
+            input
+        } else if self.count == shift {
+            self.deque[self.index]
+        } else if self.count == 0 {
+            input
+        } else {
+            self.deque[0]
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+    }
+
+    /// Stored values, the oldest first.
+    pub fn values(&self) -> Vec<f64> {
+        ring_values(&self.deque, self.index, self.count)
+    }
+
+    /// Value which the last input pushed out, to amend it.
+    pub fn evicted(&self) -> Option<f64> {
+        (self.count > 0 && self.saved_count == self.deque.len()).then_some(self.saved_value)
+    }
+
+    /// Delay of `shift` values which has stored `values`, the oldest first, and whose last input
+    /// pushed out `evicted`.
+    pub fn from_values(shift: usize, values: &[f64], evicted: Option<f64>) -> Result<Self> {
+        let (deque, index) = ring_from_values(shift, values)?;
+        if evicted.is_some() && values.len() != shift {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            index,
+            count: values.len(),
+            deque,
+            saved_value: evicted.unwrap_or(0.0),
+            saved_count: match evicted {
+                Some(_) => values.len(),
+                None => values.len().saturating_sub(1),
+            },
+        })
+    }
+}
+
+/// Values of a ring buffer which holds `count` values and stores the next one at `index`, the
+/// oldest first.
+#[cfg(feature = "std")]
+pub(crate) fn ring_values<T: Copy>(deque: &[T], index: usize, count: usize) -> Vec<T> {
+    let len = deque.len();
+    let count = count.min(len);
+    (0..count)
+        .map(|i| deque[(index + len - count + i) % len])
+        .collect()
+}
+
+/// Ring buffer of `len` values which holds `values`, the oldest first, along with the index of
+/// the next value. Fails if there are more values than fit in the buffer.
+#[cfg(feature = "std")]
+pub(crate) fn ring_from_values<T: Copy + Default>(
+    len: usize,
+    values: &[T],
+) -> Result<(Box<[T]>, usize)> {
+    let index = values.len() % len.max(1);
+    Ok((ring_from_values_at(len, values, index)?, index))
+}
+
+/// Ring buffer of `len` values which holds `values`, the oldest first, and stores the next value
+/// at `index`, for indicators which go through the buffer in the order of its slots. Fails if
+/// there are more values than fit in the buffer or if a buffer that is not full doesn't store
+/// the next value right after them.
+#[cfg(feature = "std")]
+pub(crate) fn ring_from_values_at<T: Copy + Default>(
+    len: usize,
+    values: &[T],
+    index: usize,
+) -> Result<Box<[T]>> {
+    let count = values.len();
+    if count > len || (count < len && index != count) || index >= len.max(1) {
+        return Err(TaError::InvalidState);
+    }
+    let mut deque = vec![T::default(); len].into_boxed_slice();
+    for (i, &value) in values.iter().enumerate() {
+        deque[(index + len - count + i) % len] = value;
+    }
+    Ok(deque)
+}
+
+/// Field of an indicator output struct, formatted by `impl_output!`.
+pub(crate) trait OutputField {
+    fn fmt_field(&self, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+impl OutputField for f64 {
+    fn fmt_field(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl OutputField for usize {
+    fn fmt_field(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl OutputField for bool {
+    fn fmt_field(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl<T: fmt::Display> OutputField for Option<T> {
+    fn fmt_field(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Some(value) => write!(f, "{}", value),
+            None => write!(f, "none"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl OutputField for Vec<f64> {
+    fn fmt_field(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Serialized form of an indicator: its [Checkpoint](crate::Checkpoint) state along with the
+/// version of the state.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Versioned<S> {
+    pub version: u32,
+    pub state: S,
+}
+
+/// Implements `Serialize` and `Deserialize` for an indicator as its [Checkpoint](crate::Checkpoint)
+/// state with the given version, instead of deriving them for its fields. A deserialized
+/// indicator is restored with `from_state`, so an inconsistent state, or a state of another
+/// version, is rejected with an error that names the indicator. The version has to be increased
+/// whenever the state of the indicator changes. Generic indicators are given with their type
+/// parameters, e.g. `impl_versioned_serde!(Sourced<I>, 1)`, and are serializable whenever the
+/// state of their parts is.
+macro_rules! impl_versioned_serde {
+    ($indicator:ident, $version:expr) => {
+        impl_versioned_serde!($indicator<>, $version);
+    };
+    ($indicator:ident < $($param:ident),* >, $version:expr) => {
+        #[cfg(feature = "serde")]
+        impl<$($param),*> serde::Serialize for $indicator<$($param),*>
+        where
+            Self: crate::Checkpoint,
+            <Self as crate::Checkpoint>::State: serde::Serialize,
+        {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> core::result::Result<S::Ok, S::Error> {
+                let versioned = crate::helpers::Versioned {
+                    version: $version,
+                    state: crate::Checkpoint::state(self),
+                };
+                serde::Serialize::serialize(&versioned, serializer)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de, $($param),*> serde::Deserialize<'de> for $indicator<$($param),*>
+        where
+            Self: crate::Checkpoint,
+            <Self as crate::Checkpoint>::State: serde::Deserialize<'de>,
+        {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> core::result::Result<Self, D::Error> {
+                use serde::de::Error;
+
+                let versioned: crate::helpers::Versioned<<Self as crate::Checkpoint>::State> =
+                    serde::Deserialize::deserialize(deserializer)?;
+                if versioned.version != $version {
+                    return Err(D::Error::custom(format_args!(
+                        "unsupported version {} of {} state, expected {}",
+                        versioned.version,
+                        stringify!($indicator),
+                        $version
+                    )));
+                }
+                <Self as crate::Checkpoint>::from_state(versioned.state).map_err(|error| {
+                    D::Error::custom(format_args!("{} of {}", error, stringify!($indicator)))
+                })
+            }
+        }
+    };
+}
+
+/// Implements `Next`, `Peek` and `Amend` of every [Numeric](crate::Numeric) type for an indicator
+/// generic over it, through its `next_value`, `peek_value` and `amend_value` methods. A single
+/// implementation generic over the type would overlap with the one for references to bars.
+macro_rules! impl_numeric_inputs {
+    ($indicator:ident) => {
+        impl_numeric_inputs!(@impl $indicator, f64, f64);
+        impl_numeric_inputs!(@impl $indicator, f32, f32);
+        #[cfg(feature = "decimal")]
+        impl_numeric_inputs!(@impl $indicator, rust_decimal::Decimal, rust_decimal::Decimal);
+    };
+    ($indicator:ident => $output:ident) => {
+        impl_numeric_inputs!(@impl $indicator, f64, $output<f64>);
+        impl_numeric_inputs!(@impl $indicator, f32, $output<f32>);
+        #[cfg(feature = "decimal")]
+        impl_numeric_inputs!(@impl $indicator, rust_decimal::Decimal, $output<rust_decimal::Decimal>);
+    };
+    (@impl $indicator:ident, $numeric:ty, $output:ty) => {
+        impl crate::Next<$numeric> for $indicator<$numeric> {
+            type Output = $output;
+
+            fn next(&mut self, input: $numeric) -> Self::Output {
+                self.next_value(input)
+            }
+        }
+
+        impl crate::Peek<$numeric> for $indicator<$numeric> {
+            fn peek(&self, input: $numeric) -> Self::Output {
+                self.peek_value(input)
+            }
+        }
+
+        impl crate::Amend<$numeric> for $indicator<$numeric> {
+            fn amend(&mut self, input: $numeric) -> Self::Output {
+                self.amend_value(input)
+            }
+        }
+    };
+}
+
+/// Implements `Display` for an indicator output struct as a list of `name: value` pairs, e.g.
+/// `upper: 6.5, average: 3.5, lower: 0.5`, and its conversion into a named
+/// [OutputValue](crate::OutputValue).
+macro_rules! impl_output {
+    ($output:ty, $first:ident $(, $field:ident)*) => {
+        impl core::fmt::Display for $output {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                use crate::helpers::OutputField;
+
+                write!(f, concat!(stringify!($first), ": "))?;
+                self.$first.fmt_field(f)?;
+                $(
+                    write!(f, concat!(", ", stringify!($field), ": "))?;
+                    self.$field.fmt_field(f)?;
+                )*
+                Ok(())
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl From<$output> for crate::OutputValue {
+            fn from(output: $output) -> Self {
+                crate::OutputValue::Named(vec![
+                    (stringify!($first), output.$first.into()),
+                    $((stringify!($field), output.$field.into()),)*
+                ])
+            }
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -13,4 +372,92 @@ mod tests {
         assert_eq!(max3(2.0, 3.0, 1.0), 3.0);
         assert_eq!(max3(2.0, 1.0, 3.0), 3.0);
     }
+
+    #[cfg(feature = "std")]
+    struct TestOutput {
+        value: f64,
+        count: usize,
+        flag: bool,
+        level: Option<f64>,
+        values: Vec<f64>,
+    }
+
+    #[cfg(feature = "std")]
+    impl_output!(TestOutput, value, count, flag, level, values);
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_output_display() {
+        let mut output = TestOutput {
+            value: 1.5,
+            count: 3,
+            flag: true,
+            level: Some(2.0),
+            values: vec![1.0, 2.5],
+        };
+        assert_eq!(
+            format!("{}", output),
+            "value: 1.5, count: 3, flag: true, level: 2, values: [1, 2.5]"
+        );
+
+        output.level = None;
+        output.values.clear();
+        assert_eq!(
+            format!("{}", output),
+            "value: 1.5, count: 3, flag: true, level: none, values: []"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_delay() {
+        let mut delay = Delay::new(2);
+        assert_eq!(delay.next(1.0), 1.0);
+        assert_eq!(delay.next(2.0), 1.0);
+        assert_eq!(delay.next(3.0), 1.0);
+        assert_eq!(delay.next(4.0), 2.0);
+        assert_eq!(delay.next(5.0), 3.0);
+
+        delay.reset();
+        assert_eq!(delay.next(6.0), 6.0);
+        assert_eq!(delay.next(7.0), 6.0);
+        assert_eq!(delay.next(8.0), 6.0);
+        assert_eq!(delay.next(9.0), 7.0);
+        assert_eq!(delay.amend(10.0), 7.0);
+        assert_eq!(delay.next(11.0), 8.0);
+        assert_eq!(delay.next(12.0), 10.0);
+
+        let mut delay = Delay::new(0);
+        assert_eq!(delay.next(1.0), 1.0);
+        assert_eq!(delay.next(2.0), 2.0);
+        assert_eq!(delay.amend(3.0), 3.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_delay_values() {
+        let mut delay = Delay::new(3);
+        for input in 1..=4 {
+            delay.next(input as f64);
+        }
+        assert_eq!(delay.values(), vec![2.0, 3.0, 4.0]);
+
+        assert_eq!(delay.evicted(), Some(1.0));
+
+        let mut restored = Delay::from_values(3, &delay.values(), delay.evicted()).unwrap();
+        assert_eq!(restored.amend(6.0), delay.amend(6.0));
+        assert_eq!(restored.next(5.0), delay.next(5.0));
+        assert_eq!(restored.values(), vec![3.0, 6.0, 5.0]);
+
+        let mut restored = Delay::from_values(3, &[1.0], None).unwrap();
+        assert_eq!(restored.next(2.0), 1.0);
+        assert_eq!(restored.values(), vec![1.0, 2.0]);
+
+        assert!(Delay::from_values(2, &[1.0, 2.0, 3.0], None).is_err());
+        assert!(Delay::from_values(2, &[1.0], Some(0.0)).is_err());
+        assert!(Delay::from_values(0, &[], None)
+            .unwrap()
+            .values()
+            .is_empty());
+    }
 }
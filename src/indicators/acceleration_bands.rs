@@ -0,0 +1,322 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{SimpleMovingAverage as Sma, SimpleMovingAverageState};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset,
+    SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Acceleration Bands (ABANDS).
+///
+/// Price Headley's bands around a simple moving average of the close price. Every bar widens
+/// its high and low by a multiple of its relative range, and the widened highs and lows are
+/// averaged into the upper and lower band. A close outside the bands hints at an accelerating
+/// trend.
+///
+/// # Formula
+///
+/// Upper = SMA(High * (1 + _factor_ * (High - Low) / (High + Low)), _period_)
+///
+/// Middle = SMA(Close, _period_)
+///
+/// Lower = SMA(Low * (1 - _factor_ * (High - Low) / (High + Low)), _period_)
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+/// * _factor_ - width factor (number greater than 0). Default is 4.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::AccelerationBands;
+/// use ta::{DataItem, Next};
+///
+/// let mut abands = AccelerationBands::new(2, 4.0).unwrap();
+/// let bar = DataItem::builder()
+///     .open(9.0).high(12.0).low(8.0).close(10.0)
+///     .volume(100.0)
+///     .build().unwrap();
+///
+/// let out = abands.next(&bar);
+/// assert_eq!(out.upper.round(), 22.0);
+/// assert_eq!(out.middle, 10.0);
+/// assert_eq!(out.lower.round(), 2.0);
+/// ```
+#[doc(alias = "ABANDS")]
+#[derive(Debug, Clone)]
+pub struct AccelerationBands {
+    factor: f64,
+    upper: Sma,
+    middle: Sma,
+    lower: Sma,
+    output: Option<AccelerationBandsOutput>,
+}
+
+/// State of [AccelerationBands](struct.AccelerationBands.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccelerationBandsState {
+    pub factor: f64,
+    /// Average of the widened highs.
+    pub upper: SimpleMovingAverageState,
+    /// Average of the closes.
+    pub middle: SimpleMovingAverageState,
+    /// Average of the widened lows.
+    pub lower: SimpleMovingAverageState,
+    pub output: Option<AccelerationBandsOutput>,
+}
+
+/// Output of [AccelerationBands](struct.AccelerationBands.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccelerationBandsOutput {
+    pub upper: f64,
+    pub middle: f64,
+    pub lower: f64,
+}
+
+impl_output!(AccelerationBandsOutput, upper, middle, lower);
+
+impl AccelerationBands {
+    pub fn new(period: usize, factor: f64) -> Result<Self> {
+        if !factor.is_finite() || factor <= 0.0 {
+            return Err(TaError::invalid_parameter("factor", factor, "> 0"));
+        }
+        Ok(Self {
+            factor,
+            upper: Sma::new(period)?,
+            middle: Sma::new(period)?,
+            lower: Sma::new(period)?,
+            output: None,
+        })
+    }
+
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+}
+
+impl Period for AccelerationBands {
+    fn period(&self) -> usize {
+        self.middle.period()
+    }
+}
+
+impl Checkpoint for AccelerationBands {
+    type State = AccelerationBandsState;
+
+    fn state(&self) -> Self::State {
+        AccelerationBandsState {
+            factor: self.factor,
+            upper: self.upper.state(),
+            middle: self.middle.state(),
+            lower: self.lower.state(),
+            output: self.output.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator =
+            Self::new(state.middle.period, state.factor).map_err(|_| TaError::InvalidState)?;
+        let counts =
+            [&state.upper, &state.middle, &state.lower].map(|sma| (sma.period, sma.inputs.len()));
+        if counts[0] != counts[1]
+            || counts[1] != counts[2]
+            || state.output.is_none() != (counts[1].1 == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        indicator.upper = Sma::from_state(state.upper)?;
+        indicator.middle = Sma::from_state(state.middle)?;
+        indicator.lower = Sma::from_state(state.lower)?;
+        indicator.output = state.output;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(AccelerationBands, 1);
+
+impl SetPeriod for AccelerationBands {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period, self.factor)?;
+        Ok(())
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for AccelerationBands {
+    type Output = AccelerationBandsOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let high = input.high();
+        let low = input.low();
+        let sum = high + low;
+        let width = if sum != 0.0 {
+            self.factor * (high - low) / sum
+        } else {
+            0.0
+        };
+
+        let output = Self::Output {
+            upper: self.upper.next(high * (1.0 + width)),
+            middle: self.middle.next(input.close()),
+            lower: self.lower.next(low * (1.0 - width)),
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low + Close> Peek<&T> for AccelerationBands {
+    fn peek(&self, input: &T) -> AccelerationBandsOutput {
+        let high = input.high();
+        let low = input.low();
+        let sum = high + low;
+        let width = if sum != 0.0 {
+            self.factor * (high - low) / sum
+        } else {
+            0.0
+        };
+
+        AccelerationBandsOutput {
+            upper: self.upper.peek(high * (1.0 + width)),
+            middle: self.middle.peek(input.close()),
+            lower: self.lower.peek(low * (1.0 - width)),
+        }
+    }
+}
+
+impl<T: High + Low + Close> Amend<&T> for AccelerationBands {
+    fn amend(&mut self, input: &T) -> AccelerationBandsOutput {
+        let high = input.high();
+        let low = input.low();
+        let sum = high + low;
+        let width = if sum != 0.0 {
+            self.factor * (high - low) / sum
+        } else {
+            0.0
+        };
+
+        let output = AccelerationBandsOutput {
+            upper: self.upper.amend(high * (1.0 + width)),
+            middle: self.middle.amend(input.close()),
+            lower: self.lower.amend(low * (1.0 - width)),
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Reset for AccelerationBands {
+    fn reset(&mut self) {
+        self.upper.reset();
+        self.middle.reset();
+        self.lower.reset();
+        self.output = None;
+    }
+}
+
+impl Current for AccelerationBands {
+    type Output = AccelerationBandsOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for AccelerationBands {
+    fn is_ready(&self) -> bool {
+        self.middle.is_ready()
+    }
+}
+
+impl Lookback for AccelerationBands {
+    fn lookback(&self) -> usize {
+        self.middle.lookback()
+    }
+}
+
+impl Default for AccelerationBands {
+    fn default() -> Self {
+        Self::new(20, 4.0).unwrap()
+    }
+}
+
+impl fmt::Display for AccelerationBands {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ABANDS({}, {})", self.period(), self.factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn bar(high: f64, low: f64, close: f64) -> Bar {
+        Bar::new().high(high).low(low).close(close)
+    }
+
+    fn output(out: AccelerationBandsOutput) -> (f64, f64, f64) {
+        (round(out.upper), round(out.middle), round(out.lower))
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(AccelerationBands::new(0, 4.0).is_err());
+        assert!(AccelerationBands::new(20, 0.0).is_err());
+        assert!(AccelerationBands::new(20, f64::INFINITY).is_err());
+        assert!(AccelerationBands::new(20, 4.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut abands = AccelerationBands::new(2, 4.0).unwrap();
+
+        assert_eq!(
+            output(abands.next(&bar(12.0, 8.0, 10.0))),
+            (21.6, 10.0, 1.6)
+        );
+        assert_eq!(
+            output(abands.next(&bar(11.0, 9.0, 10.5))),
+            (18.5, 10.25, 3.5)
+        );
+        assert_eq!(
+            output(abands.next(&bar(10.0, 10.0, 10.0))),
+            (12.7, 10.25, 7.7)
+        );
+    }
+
+    #[test]
+    fn test_zero_prices() {
+        let mut abands = AccelerationBands::new(2, 4.0).unwrap();
+        assert_eq!(output(abands.next(&bar(0.0, 0.0, 0.0))), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut abands = AccelerationBands::new(2, 4.0).unwrap();
+
+        abands.next(&bar(12.0, 8.0, 10.0));
+        abands.reset();
+        assert_eq!(
+            output(abands.next(&bar(11.0, 9.0, 10.5))),
+            (15.4, 10.5, 5.4)
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        AccelerationBands::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let abands = AccelerationBands::new(20, 4.0).unwrap();
+        assert_eq!(format!("{}", abands), "ABANDS(20, 4)");
+    }
+}
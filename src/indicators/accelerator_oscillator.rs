@@ -0,0 +1,264 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{
+    AwesomeOscillator, AwesomeOscillatorState, SimpleMovingAverage as Sma, SimpleMovingAverageState,
+};
+use crate::{Amend, Checkpoint, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Accelerator Oscillator (AC).
+///
+/// Bill Williams' indicator measuring the acceleration of the
+/// [Awesome Oscillator](struct.AwesomeOscillator.html): the difference between AO and its
+/// simple moving average.
+///
+/// # Formula
+///
+/// AC = AO - SMA(AO, _signal_period_)
+///
+/// # Parameters
+///
+/// * _fast_period_ - period of the fast AO SMA (integer greater than 0). Default is 5.
+/// * _slow_period_ - period of the slow AO SMA (integer greater than _fast_period_). Default is 34.
+/// * _signal_period_ - period of the SMA of AO (integer greater than 0). Default is 5.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::AcceleratorOscillator;
+/// use ta::Next;
+///
+/// let mut ac = AcceleratorOscillator::new(2, 3, 2).unwrap();
+/// assert_eq!(ac.next(2.0), 0.0);
+/// assert_eq!(ac.next(4.0), 0.0);
+/// assert_eq!(ac.next(9.0), 0.75);
+/// ```
+///
+/// # Links
+///
+/// * [Accelerator Oscillator, TradingView](https://www.tradingview.com/support/solutions/43000501837-accelerator-oscillator-ac/)
+///
+#[doc(alias = "AC")]
+#[derive(Debug, Clone)]
+pub struct AcceleratorOscillator {
+    ao: AwesomeOscillator,
+    signal_sma: Sma,
+    count: usize,
+    output: Option<f64>,
+}
+
+/// State of [AcceleratorOscillator](struct.AcceleratorOscillator.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcceleratorOscillatorState {
+    pub ao: AwesomeOscillatorState,
+    pub signal_sma: SimpleMovingAverageState,
+    /// Number of inputs since the awesome oscillator is ready, up to the signal period.
+    pub count: usize,
+    pub output: Option<f64>,
+}
+
+impl AcceleratorOscillator {
+    pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Result<Self> {
+        Ok(Self {
+            ao: AwesomeOscillator::new(fast_period, slow_period)?,
+            signal_sma: Sma::new(signal_period)?,
+            count: 0,
+            output: None,
+        })
+    }
+}
+
+impl Checkpoint for AcceleratorOscillator {
+    type State = AcceleratorOscillatorState;
+
+    fn state(&self) -> Self::State {
+        AcceleratorOscillatorState {
+            ao: self.ao.state(),
+            signal_sma: self.signal_sma.state(),
+            count: self.count,
+            output: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let indicator = Self {
+            ao: AwesomeOscillator::from_state(state.ao)?,
+            signal_sma: Sma::from_state(state.signal_sma)?,
+            count: state.count,
+            output: state.output,
+        };
+        if indicator.count > indicator.signal_sma.period()
+            || (indicator.count > 0 && !indicator.ao.is_ready())
+            || indicator.output.is_some() != indicator.ao.current().is_some()
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(AcceleratorOscillator, 1);
+
+impl Next<f64> for AcceleratorOscillator {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let ao = self.ao.next(input);
+        if self.ao.is_ready() && !self.is_ready() {
+            self.count += 1;
+        }
+        let output = ao - self.signal_sma.next(ao);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low> Next<&T> for AcceleratorOscillator {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next((input.high() + input.low()) / 2.0)
+    }
+}
+
+impl Peek<f64> for AcceleratorOscillator {
+    fn peek(&self, input: f64) -> f64 {
+        let ao = self.ao.peek(input);
+        ao - self.signal_sma.peek(ao)
+    }
+}
+
+impl<T: High + Low> Peek<&T> for AcceleratorOscillator {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek((input.high() + input.low()) / 2.0)
+    }
+}
+
+impl Amend<f64> for AcceleratorOscillator {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let ao = self.ao.amend(input);
+        let output = ao - self.signal_sma.amend(ao);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low> Amend<&T> for AcceleratorOscillator {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend((input.high() + input.low()) / 2.0)
+    }
+}
+
+impl Reset for AcceleratorOscillator {
+    fn reset(&mut self) {
+        self.ao.reset();
+        self.signal_sma.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl Current for AcceleratorOscillator {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for AcceleratorOscillator {
+    fn is_ready(&self) -> bool {
+        self.count == self.signal_sma.period()
+    }
+}
+
+impl Lookback for AcceleratorOscillator {
+    fn lookback(&self) -> usize {
+        self.ao.lookback() + self.signal_sma.period() - 1
+    }
+}
+
+impl Default for AcceleratorOscillator {
+    fn default() -> Self {
+        Self::new(5, 34, 5).unwrap()
+    }
+}
+
+impl fmt::Display for AcceleratorOscillator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "AC({}, {}, {})",
+            self.ao.fast_period(),
+            self.ao.slow_period(),
+            self.signal_sma.period()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(AcceleratorOscillator);
+
+    #[test]
+    fn test_new() {
+        assert!(AcceleratorOscillator::new(0, 34, 5).is_err());
+        assert!(AcceleratorOscillator::new(5, 5, 5).is_err());
+        assert!(AcceleratorOscillator::new(5, 34, 0).is_err());
+        assert!(AcceleratorOscillator::new(1, 2, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut ac = AcceleratorOscillator::new(2, 3, 2).unwrap();
+
+        assert_eq!(ac.next(2.0), 0.0);
+        assert_eq!(ac.next(4.0), 0.0);
+        assert_eq!(ac.next(9.0), 0.75);
+        assert_eq!(round(ac.next(3.0)), -0.417);
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut ac = AcceleratorOscillator::new(2, 3, 2).unwrap();
+
+        assert_eq!(ac.next(&Bar::new().high(3).low(1)), 0.0);
+        assert_eq!(ac.next(&Bar::new().high(5).low(3)), 0.0);
+        assert_eq!(ac.next(&Bar::new().high(10).low(8)), 0.75);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ac = AcceleratorOscillator::new(2, 3, 2).unwrap();
+
+        ac.next(2.0);
+        ac.next(4.0);
+        ac.next(9.0);
+
+        ac.reset();
+        assert_eq!(ac.next(2.0), 0.0);
+        assert_eq!(ac.next(4.0), 0.0);
+        assert_eq!(ac.next(9.0), 0.75);
+    }
+
+    #[test]
+    fn test_default() {
+        AcceleratorOscillator::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let ac = AcceleratorOscillator::new(5, 34, 5).unwrap();
+        assert_eq!(format!("{}", ac), "AC(5, 34, 5)");
+    }
+}
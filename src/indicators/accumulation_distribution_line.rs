@@ -0,0 +1,225 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Reset, Volume,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Accumulation/Distribution Line (ADL).
+///
+/// A cumulative volume based indicator designed to measure the flow of money into and out of
+/// a security. Each bar contributes its volume weighted by the location of the close within
+/// the bar's range.
+///
+/// # Formula
+///
+/// CLV = ((Close - Low) - (High - Close)) / (High - Low)
+///
+/// ADL<sub>t</sub> = ADL<sub>t-1</sub> + CLV * Volume
+///
+/// Where:
+///
+/// * _CLV_ - close location value, 0 for a bar with no range (High = Low)
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::AccumulationDistributionLine;
+/// use ta::{Next, DataItem};
+///
+/// let mut adl = AccumulationDistributionLine::new();
+///
+/// let di1 = DataItem::builder()
+///             .high(10.0).low(6.0).close(9.0).open(7.0)
+///             .volume(1000.0)
+///             .build().unwrap();
+/// let di2 = DataItem::builder()
+///             .high(12.0).low(8.0).close(8.0).open(11.0)
+///             .volume(300.0)
+///             .build().unwrap();
+///
+/// assert_eq!(adl.next(&di1), 500.0);
+/// assert_eq!(adl.next(&di2), 200.0);
+/// ```
+///
+/// # Links
+///
+/// * [Accumulation/distribution index, Wikipedia](https://en.wikipedia.org/wiki/Accumulation/distribution_index)
+///
+#[doc(alias = "ADL")]
+#[derive(Debug, Clone)]
+pub struct AccumulationDistributionLine {
+    adl: f64,
+    output: Option<f64>,
+    // Line before the last input, restored by `amend`
+    saved_adl: f64,
+}
+
+/// State of [AccumulationDistributionLine](struct.AccumulationDistributionLine.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccumulationDistributionLineState {
+    /// Line so far, `None` before the first input.
+    pub adl: Option<f64>,
+    /// Line before the last input, to amend it.
+    pub previous_adl: f64,
+}
+
+impl AccumulationDistributionLine {
+    pub fn new() -> Self {
+        Self {
+            adl: 0.0,
+            output: None,
+            saved_adl: 0.0,
+        }
+    }
+}
+
+impl Checkpoint for AccumulationDistributionLine {
+    type State = AccumulationDistributionLineState;
+
+    fn state(&self) -> Self::State {
+        AccumulationDistributionLineState {
+            adl: self.output,
+            previous_adl: self.saved_adl,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(Self {
+            adl: state.adl.unwrap_or(0.0),
+            output: state.adl,
+            saved_adl: state.previous_adl,
+        })
+    }
+}
+
+impl_versioned_serde!(AccumulationDistributionLine, 2);
+
+impl<T: High + Low + Close + Volume> Next<&T> for AccumulationDistributionLine {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_adl = self.adl;
+        let range = input.high() - input.low();
+        if range > 0.0 {
+            let clv = ((input.close() - input.low()) - (input.high() - input.close())) / range;
+            self.adl += clv * input.volume();
+        }
+        let output = self.adl;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low + Close + Volume> Peek<&T> for AccumulationDistributionLine {
+    fn peek(&self, input: &T) -> f64 {
+        let range = input.high() - input.low();
+        if range > 0.0 {
+            let clv = ((input.close() - input.low()) - (input.high() - input.close())) / range;
+            self.adl + clv * input.volume()
+        } else {
+            self.adl
+        }
+    }
+}
+
+impl<T: High + Low + Close + Volume> Amend<&T> for AccumulationDistributionLine {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.adl = self.saved_adl;
+        self.next(input)
+    }
+}
+
+impl Reset for AccumulationDistributionLine {
+    fn reset(&mut self) {
+        self.adl = 0.0;
+        self.output = None;
+        self.saved_adl = 0.0;
+    }
+}
+
+impl Current for AccumulationDistributionLine {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for AccumulationDistributionLine {
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+impl Lookback for AccumulationDistributionLine {
+    fn lookback(&self) -> usize {
+        1
+    }
+}
+
+impl Default for AccumulationDistributionLine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for AccumulationDistributionLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ADL")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_next_bar() {
+        let mut adl = AccumulationDistributionLine::new();
+
+        let bar1 = Bar::new().high(10).low(6).close(9).volume(1000.0);
+        let bar2 = Bar::new().high(12).low(8).close(8).volume(300.0);
+        let bar3 = Bar::new().high(5).low(5).close(5).volume(700.0);
+        let bar4 = Bar::new().high(6).low(2).close(4).volume(200.0);
+
+        // CLV = 0.5
+        assert_eq!(adl.next(&bar1), 500.0);
+        // CLV = -1
+        assert_eq!(adl.next(&bar2), 200.0);
+        // no range
+        assert_eq!(adl.next(&bar3), 200.0);
+        // CLV = 0
+        assert_eq!(adl.next(&bar4), 200.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut adl = AccumulationDistributionLine::new();
+
+        let bar1 = Bar::new().high(10).low(6).close(9).volume(1000.0);
+        let bar2 = Bar::new().high(12).low(8).close(8).volume(300.0);
+
+        adl.next(&bar1);
+        adl.next(&bar2);
+
+        adl.reset();
+        assert_eq!(adl.next(&bar1), 500.0);
+    }
+
+    #[test]
+    fn test_default() {
+        AccumulationDistributionLine::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let adl = AccumulationDistributionLine::new();
+        assert_eq!(format!("{}", adl), "ADL");
+    }
+}
@@ -0,0 +1,386 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::Delay;
+use crate::indicators::{SmoothedSimpleMovingAverage as Smma, SmoothedSimpleMovingAverageState};
+use crate::{Amend, Checkpoint, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Williams Alligator.
+///
+/// Three smoothed moving averages of the median price, each displaced forward by a number of
+/// bars: the jaw (slowest), the teeth and the lips (fastest). When the lines are intertwined the
+/// alligator sleeps and the market has no trend; when they spread apart in order the alligator
+/// eats and a trend is under way.
+///
+/// Displacing a line forward by _shift_ bars means that the value shown at the current bar is
+/// the one calculated _shift_ bars ago. Until _shift_ bars have been seen, the first value is
+/// used.
+///
+/// # Formula
+///
+/// Jaw<sub>t</sub> = SMMA(Median, _jaw_period_)<sub>t - jaw_shift</sub>
+///
+/// Teeth<sub>t</sub> = SMMA(Median, _teeth_period_)<sub>t - teeth_shift</sub>
+///
+/// Lips<sub>t</sub> = SMMA(Median, _lips_period_)<sub>t - lips_shift</sub>
+///
+/// Where:
+///
+/// * _SMMA_ - [smoothed moving average](type.SmoothedSimpleMovingAverage.html)
+/// * _Median_ - (High + Low) / 2
+///
+/// # Parameters
+///
+/// * _jaw_period_, _jaw_shift_ - default are 13 and 8.
+/// * _teeth_period_, _teeth_shift_ - default are 8 and 5.
+/// * _lips_period_, _lips_shift_ - default are 5 and 3.
+///
+/// Periods are integers greater than 0, shifts are integers.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::Alligator;
+/// use ta::Next;
+///
+/// let mut alligator = Alligator::new(3, 2, 2, 1, 1, 0).unwrap();
+///
+/// alligator.next(2.0);
+/// alligator.next(4.0);
+/// let out = alligator.next(6.0);
+///
+/// assert_eq!(out.jaw, 2.0);
+/// assert_eq!(out.teeth, 3.0);
+/// assert_eq!(out.lips, 6.0);
+/// ```
+#[doc(alias = "ALLIGATOR")]
+#[derive(Debug, Clone)]
+pub struct Alligator {
+    jaw: (Smma, Delay),
+    teeth: (Smma, Delay),
+    lips: (Smma, Delay),
+    count: usize,
+    output: Option<AlligatorOutput>,
+}
+
+/// State of [Alligator](struct.Alligator.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlligatorState {
+    pub jaw: SmoothedSimpleMovingAverageState,
+    pub jaw_shift: usize,
+    /// Values of the jaw waiting to be shown, the oldest first.
+    pub jaw_delayed: Vec<f64>,
+    /// Value of the jaw which the last input pushed out, to amend it.
+    pub jaw_evicted: Option<f64>,
+    pub teeth: SmoothedSimpleMovingAverageState,
+    pub teeth_shift: usize,
+    /// Values of the teeth waiting to be shown, the oldest first.
+    pub teeth_delayed: Vec<f64>,
+    /// Value of the teeth which the last input pushed out, to amend it.
+    pub teeth_evicted: Option<f64>,
+    pub lips: SmoothedSimpleMovingAverageState,
+    pub lips_shift: usize,
+    /// Values of the lips waiting to be shown, the oldest first.
+    pub lips_delayed: Vec<f64>,
+    /// Value of the lips which the last input pushed out, to amend it.
+    pub lips_evicted: Option<f64>,
+    /// Number of inputs, up to the lookback.
+    pub count: usize,
+    pub output: Option<AlligatorOutput>,
+}
+
+/// Output of [Alligator](struct.Alligator.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlligatorOutput {
+    pub jaw: f64,
+    pub teeth: f64,
+    pub lips: f64,
+}
+
+impl_output!(AlligatorOutput, jaw, teeth, lips);
+
+impl From<AlligatorOutput> for (f64, f64, f64) {
+    fn from(alligator: AlligatorOutput) -> Self {
+        (alligator.jaw, alligator.teeth, alligator.lips)
+    }
+}
+
+impl Alligator {
+    pub fn new(
+        jaw_period: usize,
+        jaw_shift: usize,
+        teeth_period: usize,
+        teeth_shift: usize,
+        lips_period: usize,
+        lips_shift: usize,
+    ) -> Result<Self> {
+        for (name, period) in [
+            ("jaw_period", jaw_period),
+            ("teeth_period", teeth_period),
+            ("lips_period", lips_period),
+        ] {
+            if period == 0 {
+                return Err(TaError::invalid_parameter(name, 0.0, ">= 1"));
+            }
+        }
+        Ok(Self {
+            jaw: (Smma::new(jaw_period)?, Delay::new(jaw_shift)),
+            teeth: (Smma::new(teeth_period)?, Delay::new(teeth_shift)),
+            lips: (Smma::new(lips_period)?, Delay::new(lips_shift)),
+            count: 0,
+            output: None,
+        })
+    }
+}
+
+impl Period for Alligator {
+    fn period(&self) -> usize {
+        self.jaw.0.period()
+    }
+}
+
+impl Checkpoint for Alligator {
+    type State = AlligatorState;
+
+    fn state(&self) -> Self::State {
+        AlligatorState {
+            jaw: self.jaw.0.state(),
+            jaw_shift: self.jaw.1.shift(),
+            jaw_delayed: self.jaw.1.values(),
+            jaw_evicted: self.jaw.1.evicted(),
+            teeth: self.teeth.0.state(),
+            teeth_shift: self.teeth.1.shift(),
+            teeth_delayed: self.teeth.1.values(),
+            teeth_evicted: self.teeth.1.evicted(),
+            lips: self.lips.0.state(),
+            lips_shift: self.lips.1.shift(),
+            lips_delayed: self.lips.1.values(),
+            lips_evicted: self.lips.1.evicted(),
+            count: self.count,
+            output: self.output.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let line = |smma, shift, delayed: Vec<f64>, evicted| -> Result<(Smma, Delay)> {
+            Ok((
+                Smma::from_state(smma)?,
+                Delay::from_values(shift, &delayed, evicted)?,
+            ))
+        };
+        let indicator = Self {
+            jaw: line(
+                state.jaw,
+                state.jaw_shift,
+                state.jaw_delayed,
+                state.jaw_evicted,
+            )?,
+            teeth: line(
+                state.teeth,
+                state.teeth_shift,
+                state.teeth_delayed,
+                state.teeth_evicted,
+            )?,
+            lips: line(
+                state.lips,
+                state.lips_shift,
+                state.lips_delayed,
+                state.lips_evicted,
+            )?,
+            count: state.count,
+            output: state.output,
+        };
+        if indicator.count > indicator.lookback()
+            || indicator.output.is_none() != (indicator.count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(Alligator, 2);
+
+impl Next<f64> for Alligator {
+    type Output = AlligatorOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if !self.is_ready() {
+            self.count += 1;
+        }
+        let line = |(smma, delay): &mut (Smma, Delay)| delay.next(smma.next(input));
+        let output = AlligatorOutput {
+            jaw: line(&mut self.jaw),
+            teeth: line(&mut self.teeth),
+            lips: line(&mut self.lips),
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low> Next<&T> for Alligator {
+    type Output = AlligatorOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next((input.high() + input.low()) / 2.0)
+    }
+}
+
+impl Peek<f64> for Alligator {
+    fn peek(&self, input: f64) -> AlligatorOutput {
+        let line = |(smma, delay): &(Smma, Delay)| delay.peek(smma.peek(input));
+        AlligatorOutput {
+            jaw: line(&self.jaw),
+            teeth: line(&self.teeth),
+            lips: line(&self.lips),
+        }
+    }
+}
+
+impl<T: High + Low> Peek<&T> for Alligator {
+    fn peek(&self, input: &T) -> AlligatorOutput {
+        self.peek((input.high() + input.low()) / 2.0)
+    }
+}
+
+impl Amend<f64> for Alligator {
+    fn amend(&mut self, input: f64) -> AlligatorOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let line = |(smma, delay): &mut (Smma, Delay)| delay.amend(smma.amend(input));
+        let output = AlligatorOutput {
+            jaw: line(&mut self.jaw),
+            teeth: line(&mut self.teeth),
+            lips: line(&mut self.lips),
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low> Amend<&T> for Alligator {
+    fn amend(&mut self, input: &T) -> AlligatorOutput {
+        self.amend((input.high() + input.low()) / 2.0)
+    }
+}
+
+impl Reset for Alligator {
+    fn reset(&mut self) {
+        for (smma, delay) in [&mut self.jaw, &mut self.teeth, &mut self.lips] {
+            smma.reset();
+            delay.reset();
+        }
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl Current for Alligator {
+    type Output = AlligatorOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for Alligator {
+    fn is_ready(&self) -> bool {
+        self.count >= self.lookback()
+    }
+}
+
+impl Lookback for Alligator {
+    fn lookback(&self) -> usize {
+        [&self.jaw, &self.teeth, &self.lips]
+            .iter()
+            .map(|(smma, delay)| smma.lookback() + delay.shift())
+            .max()
+            .unwrap_or(1)
+    }
+}
+
+impl Default for Alligator {
+    fn default() -> Self {
+        Self::new(13, 8, 8, 5, 5, 3).unwrap()
+    }
+}
+
+impl fmt::Display for Alligator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ALLIGATOR({}, {}, {}, {}, {}, {})",
+            self.jaw.0.period(),
+            self.jaw.1.shift(),
+            self.teeth.0.period(),
+            self.teeth.1.shift(),
+            self.lips.0.period(),
+            self.lips.1.shift()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Alligator);
+
+    #[test]
+    fn test_new() {
+        assert!(Alligator::new(0, 8, 8, 5, 5, 3).is_err());
+        assert!(Alligator::new(13, 8, 0, 5, 5, 3).is_err());
+        assert!(Alligator::new(13, 8, 8, 5, 0, 3).is_err());
+        assert!(Alligator::new(13, 0, 8, 0, 5, 0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut alligator = Alligator::new(2, 2, 2, 1, 1, 0).unwrap();
+
+        let out = alligator.next(&Bar::new().high(3).low(1));
+        assert_eq!((out.jaw, out.teeth, out.lips), (2.0, 2.0, 2.0));
+
+        let out = alligator.next(&Bar::new().high(5).low(3));
+        assert_eq!((out.jaw, out.teeth, out.lips), (2.0, 2.0, 4.0));
+
+        let out = alligator.next(&Bar::new().high(7).low(5));
+        assert_eq!((out.jaw, out.teeth, out.lips), (2.0, 3.0, 6.0));
+
+        // SMMA(2) of 2, 4, 6, 8 is 2, 3, 4.5, 6.25
+        let out = alligator.next(&Bar::new().high(9).low(7));
+        assert_eq!((out.jaw, out.teeth, out.lips), (3.0, 4.5, 8.0));
+        let out = alligator.next(&Bar::new().high(9).low(7));
+        assert_eq!((out.jaw, out.teeth, out.lips), (4.5, 6.25, 8.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut alligator = Alligator::new(2, 2, 2, 1, 1, 0).unwrap();
+
+        alligator.next(2.0);
+        alligator.next(4.0);
+        alligator.reset();
+
+        let out = alligator.next(10.0);
+        assert_eq!((out.jaw, out.teeth, out.lips), (10.0, 10.0, 10.0));
+    }
+
+    #[test]
+    fn test_default() {
+        Alligator::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let alligator = Alligator::default();
+        assert_eq!(format!("{}", alligator), "ALLIGATOR(13, 8, 8, 5, 5, 3)");
+    }
+}
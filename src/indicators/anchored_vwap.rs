@@ -0,0 +1,298 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Reset, Volume,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Anchored volume weighted average price (AVWAP).
+///
+/// Unlike the rolling [VWAP](struct.VolumeWeightedAveragePrice.html), the anchored VWAP has no
+/// period: it accumulates price and volume from an anchor point until it is re-anchored.
+/// Call [anchor](#method.anchor) to start a new accumulation with the next bar, e.g. on a session
+/// start, a swing low or an earnings date.
+///
+/// # Formula
+///
+/// AVWAP = Σ(TP * Volume) / Σ(Volume)
+///
+/// Where:
+///
+/// * _TP_ - typical price, (High + Low + Close) / 3
+/// * sums are calculated over all bars since the last anchor
+///
+/// If there was no traded volume since the anchor, the typical price of the current bar is returned.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::AnchoredVwap;
+/// use ta::{Next, DataItem};
+///
+/// fn bar(price: f64, volume: f64) -> DataItem {
+///     DataItem::builder()
+///         .high(price).low(price).close(price).open(price)
+///         .volume(volume)
+///         .build().unwrap()
+/// }
+///
+/// let mut avwap = AnchoredVwap::new();
+///
+/// assert_eq!(avwap.next(&bar(10.0, 100.0)), 10.0);
+/// assert_eq!(avwap.next(&bar(13.0, 200.0)), 12.0);
+///
+/// avwap.anchor();
+/// assert_eq!(avwap.next(&bar(7.0, 200.0)), 7.0);
+/// assert_eq!(avwap.next(&bar(10.0, 100.0)), 8.0);
+/// ```
+///
+/// # Links
+///
+/// * [Anchored VWAP, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:anchored_vwap)
+///
+#[doc(alias = "AVWAP")]
+#[derive(Debug, Clone)]
+pub struct AnchoredVwap {
+    count: usize,
+    sum_price_volume: f64,
+    sum_volume: f64,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_count: usize,
+    saved_sum_price_volume: f64,
+    saved_sum_volume: f64,
+}
+
+/// State of [AnchoredVwap](struct.AnchoredVwap.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnchoredVwapState {
+    /// Number of inputs since the anchor.
+    pub count: usize,
+    pub sum_price_volume: f64,
+    pub sum_volume: f64,
+    /// Last output, which is kept when anchoring.
+    pub output: Option<f64>,
+    /// Sums before the last input, to amend it.
+    pub previous_count: usize,
+    pub previous_sum_price_volume: f64,
+    pub previous_sum_volume: f64,
+}
+
+impl AnchoredVwap {
+    pub fn new() -> Self {
+        Self {
+            count: 0,
+            sum_price_volume: 0.0,
+            sum_volume: 0.0,
+            output: None,
+            saved_count: 0,
+            saved_sum_price_volume: 0.0,
+            saved_sum_volume: 0.0,
+        }
+    }
+
+    /// Starts a new accumulation with the next bar.
+    pub fn anchor(&mut self) {
+        self.count = 0;
+        self.sum_price_volume = 0.0;
+        self.sum_volume = 0.0;
+        self.saved_count = 0;
+        self.saved_sum_price_volume = 0.0;
+        self.saved_sum_volume = 0.0;
+    }
+
+    /// Number of bars accumulated since the last anchor.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl Checkpoint for AnchoredVwap {
+    type State = AnchoredVwapState;
+
+    fn state(&self) -> Self::State {
+        AnchoredVwapState {
+            count: self.count,
+            sum_price_volume: self.sum_price_volume,
+            sum_volume: self.sum_volume,
+            output: self.output,
+            previous_count: self.saved_count,
+            previous_sum_price_volume: self.saved_sum_price_volume,
+            previous_sum_volume: self.saved_sum_volume,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.count == 0 && (state.sum_price_volume != 0.0 || state.sum_volume != 0.0)
+            || state.previous_count > state.count
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            count: state.count,
+            sum_price_volume: state.sum_price_volume,
+            sum_volume: state.sum_volume,
+            output: state.output,
+            saved_count: state.previous_count,
+            saved_sum_price_volume: state.previous_sum_price_volume,
+            saved_sum_volume: state.previous_sum_volume,
+        })
+    }
+}
+
+impl_versioned_serde!(AnchoredVwap, 2);
+
+impl<T: High + Low + Close + Volume> Next<&T> for AnchoredVwap {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let typical_price = (input.high() + input.low() + input.close()) / 3.0;
+        let volume = input.volume();
+
+        self.saved_count = self.count;
+        self.saved_sum_price_volume = self.sum_price_volume;
+        self.saved_sum_volume = self.sum_volume;
+        self.count += 1;
+        self.sum_price_volume += typical_price * volume;
+        self.sum_volume += volume;
+
+        let output = if self.sum_volume > 0.0 {
+            self.sum_price_volume / self.sum_volume
+        } else {
+            typical_price
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low + Close + Volume> Peek<&T> for AnchoredVwap {
+    fn peek(&self, input: &T) -> f64 {
+        let typical_price = (input.high() + input.low() + input.close()) / 3.0;
+        let volume = input.volume();
+        let sum_price_volume = self.sum_price_volume + typical_price * volume;
+        let sum_volume = self.sum_volume + volume;
+
+        if sum_volume > 0.0 {
+            sum_price_volume / sum_volume
+        } else {
+            typical_price
+        }
+    }
+}
+
+impl<T: High + Low + Close + Volume> Amend<&T> for AnchoredVwap {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.count = self.saved_count;
+        self.sum_price_volume = self.saved_sum_price_volume;
+        self.sum_volume = self.saved_sum_volume;
+        self.next(input)
+    }
+}
+
+impl Reset for AnchoredVwap {
+    fn reset(&mut self) {
+        self.anchor();
+        self.output = None;
+    }
+}
+
+impl Current for AnchoredVwap {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for AnchoredVwap {
+    fn is_ready(&self) -> bool {
+        self.count > 0
+    }
+}
+
+impl Lookback for AnchoredVwap {
+    fn lookback(&self) -> usize {
+        1
+    }
+}
+
+impl Default for AnchoredVwap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for AnchoredVwap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AVWAP")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_next_bar() {
+        let mut avwap = AnchoredVwap::new();
+
+        let bar1 = Bar::new().high(11).low(9).close(10).volume(100.0);
+        let bar2 = Bar::new().high(14).low(12).close(13).volume(200.0);
+        let bar3 = Bar::new().high(8).low(6).close(7).volume(300.0);
+
+        assert_eq!(avwap.next(&bar1), 10.0);
+        assert_eq!(avwap.next(&bar2), 12.0);
+        assert_eq!(avwap.next(&bar3), 9.5);
+        assert_eq!(avwap.count(), 3);
+    }
+
+    #[test]
+    fn test_anchor() {
+        let mut avwap = AnchoredVwap::new();
+
+        let bar1 = Bar::new().high(11).low(9).close(10).volume(100.0);
+        let bar2 = Bar::new().high(14).low(12).close(13).volume(200.0);
+        let bar3 = Bar::new().high(8).low(6).close(7).volume(0.0);
+
+        avwap.next(&bar1);
+        avwap.next(&bar2);
+
+        avwap.anchor();
+        assert_eq!(avwap.count(), 0);
+
+        // no volume since the anchor
+        assert_eq!(avwap.next(&bar3), 7.0);
+        assert_eq!(avwap.next(&bar2), 13.0);
+        assert_eq!(avwap.count(), 2);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut avwap = AnchoredVwap::new();
+
+        let bar1 = Bar::new().high(11).low(9).close(10).volume(100.0);
+        let bar2 = Bar::new().high(14).low(12).close(13).volume(200.0);
+
+        avwap.next(&bar1);
+        avwap.next(&bar2);
+
+        avwap.reset();
+        assert_eq!(avwap.next(&bar2), 13.0);
+    }
+
+    #[test]
+    fn test_default() {
+        AnchoredVwap::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let avwap = AnchoredVwap::new();
+        assert_eq!(format!("{}", avwap), "AVWAP");
+    }
+}
@@ -0,0 +1,379 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{AverageTrueRange, AverageTrueRangeState};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset,
+    SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// ATR Trailing Stop.
+///
+/// A stop level that trails the close at a distance of a multiple of the
+/// [average true range](type.AverageTrueRange.html). While in a long position the stop can
+/// only move up, while in a short position it can only move down. When the close crosses the
+/// stop, the direction flips and the stop restarts on the other side of the price.
+///
+/// # Formula
+///
+/// Loss = _multiplier_ * ATR(_period_)
+///
+/// * Long, close stays above the stop: Stop = max(Stop<sub>t-1</sub>, Close - Loss)
+/// * Short, close stays below the stop: Stop = min(Stop<sub>t-1</sub>, Close + Loss)
+/// * Close crosses above the stop: Stop = Close - Loss, direction flips to long
+/// * Close crosses below the stop: Stop = Close + Loss, direction flips to short
+///
+/// The very first bar starts a long position.
+///
+/// # Parameters
+///
+/// * _period_ - period of the ATR (integer greater than 0). Default is 14.
+/// * _multiplier_ - ATR multiplier. Default is 3.0.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::AtrTrailingStop;
+/// use ta::{Next, DataItem};
+///
+/// fn bar(high: f64, low: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .high(high).low(low).close(close).open(close)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut ts = AtrTrailingStop::new(2, 1.0).unwrap();
+///
+/// let out = ts.next(&bar(11.0, 9.0, 10.0));
+/// assert_eq!(out.stop, 8.0);
+/// assert!(out.is_long);
+/// assert!(!out.flipped);
+/// ```
+///
+/// # Links
+///
+/// * [Average true range, Wikipedia](https://en.wikipedia.org/wiki/Average_true_range)
+///
+#[doc(alias = "ATR Stop")]
+#[derive(Debug, Clone)]
+pub struct AtrTrailingStop {
+    multiplier: f64,
+    atr: AverageTrueRange,
+    prev: Option<(f64, f64)>,
+    is_long: bool,
+    output: Option<AtrTrailingStopOutput>,
+    // State before the last input, restored by `amend`
+    saved_prev: Option<(f64, f64)>,
+    saved_is_long: bool,
+}
+
+/// State of [AtrTrailingStop](struct.AtrTrailingStop.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtrTrailingStopState {
+    pub multiplier: f64,
+    pub atr: AverageTrueRangeState,
+    /// Last close and stop.
+    pub previous: Option<(f64, f64)>,
+    pub is_long: bool,
+    pub output: Option<AtrTrailingStopOutput>,
+    /// Close, stop and side before the last input, to amend it.
+    pub before_last: Option<(f64, f64)>,
+    pub was_long: bool,
+}
+
+/// Output of [AtrTrailingStop](struct.AtrTrailingStop.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtrTrailingStopOutput {
+    /// Current stop level.
+    pub stop: f64,
+    /// Whether the stop is below the price, protecting a long position.
+    pub is_long: bool,
+    /// Whether the direction changed on this bar.
+    pub flipped: bool,
+}
+
+impl_output!(AtrTrailingStopOutput, stop, is_long, flipped);
+
+impl AtrTrailingStop {
+    pub fn new(period: usize, multiplier: f64) -> Result<Self> {
+        Ok(Self {
+            multiplier,
+            atr: AverageTrueRange::new(period)?,
+            prev: None,
+            is_long: true,
+            output: None,
+            saved_prev: None,
+            saved_is_long: true,
+        })
+    }
+
+    pub fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    // Stop after a close, without updating the indicator
+    fn value(&self, close: f64, atr: f64) -> AtrTrailingStopOutput {
+        let loss = self.multiplier * atr;
+        let mut is_long = self.is_long;
+
+        let stop = match self.prev {
+            None => close - loss,
+            Some((prev_close, prev_stop)) => {
+                if close > prev_stop && prev_close > prev_stop {
+                    prev_stop.max(close - loss)
+                } else if close < prev_stop && prev_close < prev_stop {
+                    prev_stop.min(close + loss)
+                } else if close > prev_stop {
+                    is_long = true;
+                    close - loss
+                } else {
+                    is_long = false;
+                    close + loss
+                }
+            }
+        };
+
+        AtrTrailingStopOutput {
+            stop,
+            is_long,
+            flipped: is_long != self.is_long,
+        }
+    }
+
+    fn calc(&mut self, close: f64, atr: f64) -> AtrTrailingStopOutput {
+        self.saved_prev = self.prev;
+        self.saved_is_long = self.is_long;
+        let output = self.value(close, atr);
+        self.prev = Some((close, output.stop));
+        self.is_long = output.is_long;
+        output
+    }
+}
+
+impl Period for AtrTrailingStop {
+    fn period(&self) -> usize {
+        self.atr.period()
+    }
+}
+
+impl Checkpoint for AtrTrailingStop {
+    type State = AtrTrailingStopState;
+
+    fn state(&self) -> Self::State {
+        AtrTrailingStopState {
+            multiplier: self.multiplier,
+            atr: self.atr.state(),
+            previous: self.prev,
+            is_long: self.is_long,
+            output: self.output.clone(),
+            before_last: self.saved_prev,
+            was_long: self.saved_is_long,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.previous.is_none() != state.output.is_none() {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            multiplier: state.multiplier,
+            atr: AverageTrueRange::from_state(state.atr)?,
+            prev: state.previous,
+            is_long: state.is_long,
+            output: state.output,
+            saved_prev: state.before_last,
+            saved_is_long: state.was_long,
+        })
+    }
+}
+
+impl_versioned_serde!(AtrTrailingStop, 2);
+
+impl SetPeriod for AtrTrailingStop {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period, self.multiplier)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for AtrTrailingStop {
+    type Output = AtrTrailingStopOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let atr = self.atr.next(input);
+        let output = self.calc(input, atr);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for AtrTrailingStop {
+    type Output = AtrTrailingStopOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let atr = self.atr.next(input);
+        let output = self.calc(input.close(), atr);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Peek<f64> for AtrTrailingStop {
+    fn peek(&self, input: f64) -> AtrTrailingStopOutput {
+        self.value(input, self.atr.peek(input))
+    }
+}
+
+impl<T: High + Low + Close> Peek<&T> for AtrTrailingStop {
+    fn peek(&self, input: &T) -> AtrTrailingStopOutput {
+        self.value(input.close(), self.atr.peek(input))
+    }
+}
+
+impl Amend<f64> for AtrTrailingStop {
+    fn amend(&mut self, input: f64) -> AtrTrailingStopOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.prev = self.saved_prev;
+        self.is_long = self.saved_is_long;
+        let atr = self.atr.amend(input);
+        let output = self.calc(input, atr);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low + Close> Amend<&T> for AtrTrailingStop {
+    fn amend(&mut self, input: &T) -> AtrTrailingStopOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.prev = self.saved_prev;
+        self.is_long = self.saved_is_long;
+        let atr = self.atr.amend(input);
+        let output = self.calc(input.close(), atr);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Reset for AtrTrailingStop {
+    fn reset(&mut self) {
+        self.atr.reset();
+        self.prev = None;
+        self.is_long = true;
+        self.output = None;
+        self.saved_prev = None;
+        self.saved_is_long = true;
+    }
+}
+
+impl Current for AtrTrailingStop {
+    type Output = AtrTrailingStopOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for AtrTrailingStop {
+    fn is_ready(&self) -> bool {
+        self.atr.is_ready()
+    }
+}
+
+impl Lookback for AtrTrailingStop {
+    fn lookback(&self) -> usize {
+        self.atr.lookback()
+    }
+}
+
+impl Default for AtrTrailingStop {
+    fn default() -> Self {
+        Self::new(14, 3.0).unwrap()
+    }
+}
+
+impl fmt::Display for AtrTrailingStop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ATRSTOP({}, {})", self.atr.period(), self.multiplier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(AtrTrailingStop);
+
+    fn output(out: AtrTrailingStopOutput) -> (f64, bool, bool) {
+        (round(out.stop), out.is_long, out.flipped)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(AtrTrailingStop::new(0, 3.0).is_err());
+        assert!(AtrTrailingStop::new(1, 3.0).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut ts = AtrTrailingStop::new(2, 1.0).unwrap();
+
+        let bars = [
+            Bar::new().high(11).low(9).close(10),
+            Bar::new().high(13).low(10).close(12),
+            Bar::new().high(14).low(11).close(13),
+            Bar::new().high(12).low(8).close(9),
+            Bar::new().high(10).low(7).close(8),
+            Bar::new().high(14).low(9).close(13),
+        ];
+        let expected = [
+            (8.0, true, false),
+            (9.333, true, false),
+            (10.111, true, false),
+            // close drops below the stop
+            (13.296, false, true),
+            (11.432, false, false),
+            // close rises above the stop
+            (7.856, true, true),
+        ];
+
+        for (bar, expected) in bars.iter().zip(expected.iter()) {
+            assert_eq!(output(ts.next(bar)), *expected);
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ts = AtrTrailingStop::new(2, 1.0).unwrap();
+
+        let bar1 = Bar::new().high(11).low(9).close(10);
+        let bar2 = Bar::new().high(12).low(8).close(9);
+
+        ts.next(&bar1);
+        ts.next(&bar2);
+
+        ts.reset();
+        assert_eq!(output(ts.next(&bar1)), (8.0, true, false));
+    }
+
+    #[test]
+    fn test_default() {
+        AtrTrailingStop::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let ts = AtrTrailingStop::new(14, 3.0).unwrap();
+        assert_eq!(format!("{}", ts), "ATRSTOP(14, 3)");
+    }
+}
@@ -0,0 +1,305 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::Delay;
+use crate::indicators::{Correlation, CorrelationState};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling autocorrelation (AUTOCORR).
+///
+/// [Correlation](struct.Correlation.html) of the input with itself _lag_ values ago, over the
+/// last _period_ pairs. Values close to 1 mean that the series tends to repeat itself after
+/// _lag_ bars, e.g. because of a cycle of that length, values close to -1 mean that it tends to
+/// revert. The indicator returns output in the range of -1..1.
+///
+/// # Formula
+///
+/// AUTOCORR = CORR(x<sub>t</sub>, x<sub>t-lag</sub>, _period_)
+///
+/// 0 is returned until _lag_ + 2 values have been seen, and while the correlation is undefined.
+///
+/// # Parameters
+///
+/// * _period_ - number of pairs in the window (integer greater than 0). Default is 20.
+/// * _lag_ - distance between the correlated values (integer greater than 0). Default is 1.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::Autocorrelation;
+/// use ta::Next;
+///
+/// let mut autocorr = Autocorrelation::new(4, 2).unwrap();
+///
+/// // A cycle of length 2
+/// for &value in &[1.0, 3.0, 1.0, 3.0, 1.0] {
+///     autocorr.next(value);
+/// }
+/// assert_eq!(autocorr.next(3.0), 1.0);
+/// ```
+///
+/// # Links
+///
+/// * [Autocorrelation, Wikipedia](https://en.wikipedia.org/wiki/Autocorrelation)
+///
+#[doc(alias = "AUTOCORR")]
+#[doc(alias = "ACF")]
+#[derive(Debug, Clone)]
+pub struct Autocorrelation {
+    lag: usize,
+    count: usize,
+    delay: Delay,
+    corr: Correlation,
+    output: Option<f64>,
+}
+
+/// State of [Autocorrelation](struct.Autocorrelation.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutocorrelationState {
+    pub lag: usize,
+    /// Number of inputs, up to the lag.
+    pub count: usize,
+    /// Last inputs, the oldest first.
+    pub lagged: Vec<f64>,
+    pub corr: CorrelationState,
+    pub output: Option<f64>,
+    /// Input which the last one pushed out of the lagged inputs, to amend it.
+    pub evicted: Option<f64>,
+}
+
+impl Autocorrelation {
+    pub fn new(period: usize, lag: usize) -> Result<Self> {
+        if lag == 0 {
+            return Err(TaError::invalid_parameter("lag", 0.0, ">= 1"));
+        }
+        Ok(Self {
+            lag,
+            count: 0,
+            delay: Delay::new(lag),
+            corr: Correlation::new(period)?,
+            output: None,
+        })
+    }
+
+    pub fn lag(&self) -> usize {
+        self.lag
+    }
+}
+
+impl Period for Autocorrelation {
+    fn period(&self) -> usize {
+        self.corr.period()
+    }
+}
+
+impl Checkpoint for Autocorrelation {
+    type State = AutocorrelationState;
+
+    fn state(&self) -> Self::State {
+        AutocorrelationState {
+            lag: self.lag,
+            count: self.count,
+            lagged: self.delay.values(),
+            corr: self.corr.state(),
+            output: self.output,
+            evicted: self.delay.evicted(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.lag == 0
+            || state.count > state.lag
+            || state.output.is_none() != (state.count == 0)
+            || (state.count < state.lag && !state.corr.xs.is_empty())
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            lag: state.lag,
+            count: state.count,
+            delay: Delay::from_values(state.lag, &state.lagged, state.evicted)?,
+            corr: Correlation::from_state(state.corr)?,
+            output: state.output,
+        })
+    }
+}
+
+impl_versioned_serde!(Autocorrelation, 2);
+
+impl Next<f64> for Autocorrelation {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let lagged = self.delay.next(input);
+        let output = if self.count < self.lag {
+            self.count += 1;
+            0.0
+        } else {
+            self.corr.next((input, lagged))
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for Autocorrelation {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for Autocorrelation {
+    fn peek(&self, input: f64) -> f64 {
+        if self.count < self.lag {
+            0.0
+        } else {
+            self.corr.peek((input, self.delay.peek(input)))
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for Autocorrelation {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for Autocorrelation {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let lagged = self.delay.amend(input);
+        // The correlation only starts once the lag is filled
+        let output = if self.corr.current().is_some() {
+            self.corr.amend((input, lagged))
+        } else {
+            0.0
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for Autocorrelation {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for Autocorrelation {
+    fn reset(&mut self) {
+        self.count = 0;
+        self.delay.reset();
+        self.corr.reset();
+        self.output = None;
+    }
+}
+
+impl Current for Autocorrelation {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for Autocorrelation {
+    fn is_ready(&self) -> bool {
+        self.corr.is_ready()
+    }
+}
+
+impl Lookback for Autocorrelation {
+    fn lookback(&self) -> usize {
+        self.lag + self.corr.lookback()
+    }
+}
+
+impl Default for Autocorrelation {
+    fn default() -> Self {
+        Self::new(20, 1).unwrap()
+    }
+}
+
+impl fmt::Display for Autocorrelation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AUTOCORR({}, {})", self.corr.period(), self.lag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Autocorrelation);
+
+    #[test]
+    fn test_new() {
+        assert!(Autocorrelation::new(0, 1).is_err());
+        assert!(Autocorrelation::new(20, 0).is_err());
+        assert!(Autocorrelation::new(1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut autocorr = Autocorrelation::new(3, 1).unwrap();
+
+        assert_eq!(autocorr.next(1.0), 0.0);
+        assert_eq!(autocorr.next(2.0), 0.0);
+        assert_eq!(round(autocorr.next(4.0)), 1.0);
+        assert_eq!(round(autocorr.next(3.0)), 0.327);
+        assert_eq!(round(autocorr.next(2.0)), -0.5);
+    }
+
+    #[test]
+    fn test_next_alternating() {
+        let mut autocorr = Autocorrelation::new(4, 1).unwrap();
+
+        for &value in &[1.0, 3.0, 1.0, 3.0, 1.0] {
+            autocorr.next(value);
+        }
+        assert_eq!(round(autocorr.next(3.0)), -1.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut autocorr = Autocorrelation::new(3, 1).unwrap();
+
+        autocorr.next(&Bar::new().close(1));
+        autocorr.next(&Bar::new().close(2));
+        assert_eq!(round(autocorr.next(&Bar::new().close(4))), 1.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut autocorr = Autocorrelation::new(3, 1).unwrap();
+
+        autocorr.next(1.0);
+        autocorr.next(2.0);
+        autocorr.next(4.0);
+
+        autocorr.reset();
+        assert_eq!(autocorr.next(4.0), 0.0);
+        assert_eq!(autocorr.next(2.0), 0.0);
+        assert_eq!(round(autocorr.next(1.0)), 1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        Autocorrelation::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let autocorr = Autocorrelation::new(20, 5).unwrap();
+        assert_eq!(format!("{}", autocorr), "AUTOCORR(20, 5)");
+    }
+}
@@ -1,8 +1,12 @@
-use std::fmt;
+use core::fmt;
 
-use crate::errors::Result;
-use crate::indicators::{ExponentialMovingAverage, TrueRange};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::errors::{Result, TaError};
+use crate::indicators::generic::{ExponentialMovingAverage, TrueRange};
+use crate::indicators::{ExponentialMovingAverageState, TrueRangeState};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Numeric, Peek, Period,
+    Reset, SetPeriod,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -57,58 +61,146 @@ use serde::{Deserialize, Serialize};
 ///     }
 /// }
 #[doc(alias = "ATR")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct AverageTrueRange {
-    true_range: TrueRange,
-    ema: ExponentialMovingAverage,
+pub struct AverageTrueRange<N = f64> {
+    true_range: TrueRange<N>,
+    ema: ExponentialMovingAverage<N>,
+    output: Option<N>,
 }
 
-impl AverageTrueRange {
+/// State of [AverageTrueRange](generic/struct.AverageTrueRange.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AverageTrueRangeState<N = f64> {
+    pub true_range: TrueRangeState<N>,
+    pub ema: ExponentialMovingAverageState<N>,
+}
+
+impl<N: Numeric> AverageTrueRange<N> {
     pub fn new(period: usize) -> Result<Self> {
         Ok(Self {
             true_range: TrueRange::new(),
             ema: ExponentialMovingAverage::new(period)?,
+            output: None,
         })
     }
+
+    fn next_value(&mut self, input: N) -> N {
+        let output = self.ema.next_value(self.true_range.next_value(input));
+        self.output = Some(output);
+        output
+    }
+
+    fn peek_value(&self, input: N) -> N {
+        self.ema.peek_value(self.true_range.peek_value(input))
+    }
+
+    fn amend_value(&mut self, input: N) -> N {
+        let output = self.ema.amend_value(self.true_range.amend_value(input));
+        self.output = Some(output);
+        output
+    }
 }
 
-impl Period for AverageTrueRange {
+impl<N: Numeric> Period for AverageTrueRange<N> {
     fn period(&self) -> usize {
         self.ema.period()
     }
 }
 
-impl Next<f64> for AverageTrueRange {
-    type Output = f64;
+impl<N: Numeric> Checkpoint for AverageTrueRange<N> {
+    type State = AverageTrueRangeState<N>;
 
-    fn next(&mut self, input: f64) -> Self::Output {
-        self.ema.next(self.true_range.next(input))
+    fn state(&self) -> Self::State {
+        AverageTrueRangeState {
+            true_range: self.true_range.state(),
+            ema: self.ema.state(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.true_range.value.is_some() != (state.ema.count > 0) {
+            return Err(TaError::InvalidState);
+        }
+        let ema = ExponentialMovingAverage::from_state(state.ema)?;
+        Ok(Self {
+            true_range: TrueRange::from_state(state.true_range)?,
+            output: ema.current(),
+            ema,
+        })
+    }
+}
+
+impl_versioned_serde!(AverageTrueRange<N>, 1);
+
+impl<N: Numeric> SetPeriod for AverageTrueRange<N> {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        self.ema.set_period(period)
     }
 }
 
-impl<T: High + Low + Close> Next<&T> for AverageTrueRange {
-    type Output = f64;
+impl_numeric_inputs!(AverageTrueRange);
+
+impl<N: Numeric, T: High<N> + Low<N> + Close<N>> Next<&T> for AverageTrueRange<N> {
+    type Output = N;
 
     fn next(&mut self, input: &T) -> Self::Output {
-        self.ema.next(self.true_range.next(input))
+        let output = self.ema.next_value(self.true_range.next(input));
+        self.output = Some(output);
+        output
     }
 }
 
-impl Reset for AverageTrueRange {
+impl<N: Numeric, T: High<N> + Low<N> + Close<N>> Peek<&T> for AverageTrueRange<N> {
+    fn peek(&self, input: &T) -> N {
+        self.ema.peek_value(self.true_range.peek(input))
+    }
+}
+
+impl<N: Numeric, T: High<N> + Low<N> + Close<N>> Amend<&T> for AverageTrueRange<N> {
+    fn amend(&mut self, input: &T) -> N {
+        let output = self.ema.amend_value(self.true_range.amend(input));
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<N: Numeric> Reset for AverageTrueRange<N> {
     fn reset(&mut self) {
         self.true_range.reset();
         self.ema.reset();
+        self.output = None;
     }
 }
 
-impl Default for AverageTrueRange {
+impl<N: Numeric> Current for AverageTrueRange<N> {
+    type Output = N;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<N: Numeric> IsReady for AverageTrueRange<N> {
+    fn is_ready(&self) -> bool {
+        self.ema.is_ready()
+    }
+}
+
+impl<N: Numeric> Lookback for AverageTrueRange<N> {
+    fn lookback(&self) -> usize {
+        self.ema.lookback()
+    }
+}
+
+impl<N: Numeric> Default for AverageTrueRange<N> {
     fn default() -> Self {
         Self::new(14).unwrap()
     }
 }
 
-impl fmt::Display for AverageTrueRange {
+impl<N: Numeric> fmt::Display for AverageTrueRange<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "ATR({})", self.ema.period())
     }
@@ -117,6 +209,7 @@ impl fmt::Display for AverageTrueRange {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::AverageTrueRange;
     use crate::test_helper::*;
 
     test_indicator!(AverageTrueRange);
@@ -139,6 +232,18 @@ mod tests {
         assert_eq!(atr.next(&bar3), 3.375);
     }
 
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_next_decimal() {
+        use crate::indicators::generic::AverageTrueRange;
+        use rust_decimal::Decimal;
+
+        let mut atr = AverageTrueRange::<Decimal>::new(3).unwrap();
+        assert_eq!(atr.next(Decimal::new(1, 1)), Decimal::ZERO);
+        assert_eq!(atr.next(Decimal::new(3, 1)), Decimal::new(1, 1));
+        assert_eq!(atr.next(Decimal::new(2, 1)), Decimal::new(1, 1));
+    }
+
     #[test]
     fn test_reset() {
         let mut atr = AverageTrueRange::new(9).unwrap();
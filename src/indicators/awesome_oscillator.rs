@@ -0,0 +1,258 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{SimpleMovingAverage as Sma, SimpleMovingAverageState};
+use crate::{Amend, Checkpoint, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Awesome Oscillator (AO).
+///
+/// Bill Williams' momentum indicator: the difference between a fast and a slow simple moving
+/// average of the median price.
+///
+/// # Formula
+///
+/// AO = SMA(MP, _fast_period_) - SMA(MP, _slow_period_)
+///
+/// Where:
+///
+/// * _MP_ - median price, (High + Low) / 2
+///
+/// # Parameters
+///
+/// * _fast_period_ - period of the fast SMA (integer greater than 0). Default is 5.
+/// * _slow_period_ - period of the slow SMA (integer greater than _fast_period_). Default is 34.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::AwesomeOscillator;
+/// use ta::Next;
+///
+/// let mut ao = AwesomeOscillator::new(2, 3).unwrap();
+/// assert_eq!(ao.next(2.0), 0.0);
+/// assert_eq!(ao.next(4.0), 0.0);
+/// assert_eq!(ao.next(9.0), 1.5);
+/// ```
+///
+/// # Links
+///
+/// * [Awesome Oscillator, TradingView](https://www.tradingview.com/support/solutions/43000501826-awesome-oscillator-ao/)
+///
+#[doc(alias = "AO")]
+#[derive(Debug, Clone)]
+pub struct AwesomeOscillator {
+    fast_sma: Sma,
+    slow_sma: Sma,
+    output: Option<f64>,
+}
+
+/// State of [AwesomeOscillator](struct.AwesomeOscillator.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AwesomeOscillatorState {
+    pub fast_sma: SimpleMovingAverageState,
+    pub slow_sma: SimpleMovingAverageState,
+    pub output: Option<f64>,
+}
+
+impl AwesomeOscillator {
+    pub fn new(fast_period: usize, slow_period: usize) -> Result<Self> {
+        if slow_period <= fast_period {
+            return Err(TaError::invalid_parameter(
+                "slow_period",
+                slow_period as f64,
+                "> fast_period",
+            ));
+        }
+
+        Ok(Self {
+            fast_sma: Sma::new(fast_period)?,
+            slow_sma: Sma::new(slow_period)?,
+            output: None,
+        })
+    }
+
+    pub fn fast_period(&self) -> usize {
+        self.fast_sma.period()
+    }
+
+    pub fn slow_period(&self) -> usize {
+        self.slow_sma.period()
+    }
+}
+
+impl Checkpoint for AwesomeOscillator {
+    type State = AwesomeOscillatorState;
+
+    fn state(&self) -> Self::State {
+        AwesomeOscillatorState {
+            fast_sma: self.fast_sma.state(),
+            slow_sma: self.slow_sma.state(),
+            output: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(state.fast_sma.period, state.slow_sma.period)
+            .map_err(|_| TaError::InvalidState)?;
+        let count = state.slow_sma.inputs.len();
+        if state.fast_sma.inputs.len() != count.min(state.fast_sma.period)
+            || state.output.is_none() != (count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        indicator.fast_sma = Sma::from_state(state.fast_sma)?;
+        indicator.slow_sma = Sma::from_state(state.slow_sma)?;
+        indicator.output = state.output;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(AwesomeOscillator, 1);
+
+impl Next<f64> for AwesomeOscillator {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let output = self.fast_sma.next(input) - self.slow_sma.next(input);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low> Next<&T> for AwesomeOscillator {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next((input.high() + input.low()) / 2.0)
+    }
+}
+
+impl Peek<f64> for AwesomeOscillator {
+    fn peek(&self, input: f64) -> f64 {
+        self.fast_sma.peek(input) - self.slow_sma.peek(input)
+    }
+}
+
+impl<T: High + Low> Peek<&T> for AwesomeOscillator {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek((input.high() + input.low()) / 2.0)
+    }
+}
+
+impl Amend<f64> for AwesomeOscillator {
+    fn amend(&mut self, input: f64) -> f64 {
+        let output = self.fast_sma.amend(input) - self.slow_sma.amend(input);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low> Amend<&T> for AwesomeOscillator {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend((input.high() + input.low()) / 2.0)
+    }
+}
+
+impl Reset for AwesomeOscillator {
+    fn reset(&mut self) {
+        self.fast_sma.reset();
+        self.slow_sma.reset();
+        self.output = None;
+    }
+}
+
+impl Current for AwesomeOscillator {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for AwesomeOscillator {
+    fn is_ready(&self) -> bool {
+        self.fast_sma.is_ready() && self.slow_sma.is_ready()
+    }
+}
+
+impl Lookback for AwesomeOscillator {
+    fn lookback(&self) -> usize {
+        self.fast_sma.lookback().max(self.slow_sma.lookback())
+    }
+}
+
+impl Default for AwesomeOscillator {
+    fn default() -> Self {
+        Self::new(5, 34).unwrap()
+    }
+}
+
+impl fmt::Display for AwesomeOscillator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AO({}, {})", self.fast_period(), self.slow_period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(AwesomeOscillator);
+
+    #[test]
+    fn test_new() {
+        assert!(AwesomeOscillator::new(0, 34).is_err());
+        assert!(AwesomeOscillator::new(5, 5).is_err());
+        assert!(AwesomeOscillator::new(34, 5).is_err());
+        assert!(AwesomeOscillator::new(1, 2).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut ao = AwesomeOscillator::new(2, 3).unwrap();
+
+        assert_eq!(ao.next(2.0), 0.0);
+        assert_eq!(ao.next(4.0), 0.0);
+        assert_eq!(ao.next(9.0), 1.5);
+        assert_eq!(round(ao.next(3.0)), 0.667);
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut ao = AwesomeOscillator::new(2, 3).unwrap();
+
+        assert_eq!(ao.next(&Bar::new().high(3).low(1)), 0.0);
+        assert_eq!(ao.next(&Bar::new().high(5).low(3)), 0.0);
+        assert_eq!(ao.next(&Bar::new().high(10).low(8)), 1.5);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ao = AwesomeOscillator::new(2, 3).unwrap();
+
+        ao.next(2.0);
+        ao.next(4.0);
+        ao.next(9.0);
+
+        ao.reset();
+        assert_eq!(ao.next(2.0), 0.0);
+        assert_eq!(ao.next(4.0), 0.0);
+        assert_eq!(ao.next(9.0), 1.5);
+    }
+
+    #[test]
+    fn test_default() {
+        AwesomeOscillator::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let ao = AwesomeOscillator::new(5, 34).unwrap();
+        assert_eq!(format!("{}", ao), "AO(5, 34)");
+    }
+}
@@ -0,0 +1,242 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{SimpleMovingAverage as Sma, SimpleMovingAverageState};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Open, Peek, Period,
+    Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Balance of Power (BOP).
+///
+/// Measures the strength of buyers against sellers by relating the change from open to close
+/// to the range of the bar. The oscillator returns output in the range of -1..1.
+///
+/// # Formula
+///
+/// BOP = SMA((Close - Open) / (High - Low), _period_)
+///
+/// When High equals Low the raw value of the bar is 0.
+///
+/// # Parameters
+///
+/// * _period_ - smoothing period (integer greater than 0). Use 1 to get raw values. Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::BalanceOfPower;
+/// use ta::{Next, DataItem};
+///
+/// let mut bop = BalanceOfPower::new(1).unwrap();
+///
+/// let di = DataItem::builder()
+///             .high(12.0).low(8.0).close(11.0).open(9.0)
+///             .volume(1000.0)
+///             .build().unwrap();
+///
+/// assert_eq!(bop.next(&di), 0.5);
+/// ```
+///
+/// # Links
+///
+/// * [Balance of Power, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:balance_of_power)
+///
+#[doc(alias = "BOP")]
+#[derive(Debug, Clone)]
+pub struct BalanceOfPower {
+    sma: Sma,
+    output: Option<f64>,
+}
+
+/// State of [BalanceOfPower](struct.BalanceOfPower.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceOfPowerState {
+    pub sma: SimpleMovingAverageState,
+    pub output: Option<f64>,
+}
+
+impl BalanceOfPower {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            sma: Sma::new(period)?,
+            output: None,
+        })
+    }
+}
+
+impl Period for BalanceOfPower {
+    fn period(&self) -> usize {
+        self.sma.period()
+    }
+}
+
+impl Checkpoint for BalanceOfPower {
+    type State = BalanceOfPowerState;
+
+    fn state(&self) -> Self::State {
+        BalanceOfPowerState {
+            sma: self.sma.state(),
+            output: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_none() != state.sma.inputs.is_empty() {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            sma: Sma::from_state(state.sma)?,
+            output: state.output,
+        })
+    }
+}
+
+impl_versioned_serde!(BalanceOfPower, 1);
+
+impl SetPeriod for BalanceOfPower {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl<T: Open + High + Low + Close> Next<&T> for BalanceOfPower {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let range = input.high() - input.low();
+        let bop = if range > 0.0 {
+            (input.close() - input.open()) / range
+        } else {
+            0.0
+        };
+
+        let output = self.sma.next(bop);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Open + High + Low + Close> Peek<&T> for BalanceOfPower {
+    fn peek(&self, input: &T) -> f64 {
+        let range = input.high() - input.low();
+        let bop = if range > 0.0 {
+            (input.close() - input.open()) / range
+        } else {
+            0.0
+        };
+        self.sma.peek(bop)
+    }
+}
+
+impl<T: Open + High + Low + Close> Amend<&T> for BalanceOfPower {
+    fn amend(&mut self, input: &T) -> f64 {
+        let range = input.high() - input.low();
+        let bop = if range > 0.0 {
+            (input.close() - input.open()) / range
+        } else {
+            0.0
+        };
+
+        let output = self.sma.amend(bop);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Reset for BalanceOfPower {
+    fn reset(&mut self) {
+        self.sma.reset();
+        self.output = None;
+    }
+}
+
+impl Current for BalanceOfPower {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for BalanceOfPower {
+    fn is_ready(&self) -> bool {
+        self.sma.is_ready()
+    }
+}
+
+impl Lookback for BalanceOfPower {
+    fn lookback(&self) -> usize {
+        self.sma.lookback()
+    }
+}
+
+impl Default for BalanceOfPower {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for BalanceOfPower {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BOP({})", self.sma.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(BalanceOfPower::new(0).is_err());
+        assert!(BalanceOfPower::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut bop = BalanceOfPower::new(2).unwrap();
+
+        let bar1 = Bar::new().open(9).high(12).low(8).close(11);
+        let bar2 = Bar::new().open(11).high(11).low(9).close(9);
+        let bar3 = Bar::new().open(10).high(10).low(10).close(10);
+
+        // raw BOP = 0.5
+        assert_eq!(bop.next(&bar1), 0.5);
+        // raw BOP = -1
+        assert_eq!(bop.next(&bar2), -0.25);
+        // high == low, raw BOP = 0
+        assert_eq!(bop.next(&bar3), -0.5);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bop = BalanceOfPower::new(2).unwrap();
+
+        let bar1 = Bar::new().open(9).high(12).low(8).close(11);
+        let bar2 = Bar::new().open(11).high(11).low(9).close(9);
+
+        bop.next(&bar1);
+        bop.next(&bar2);
+
+        bop.reset();
+        assert_eq!(bop.next(&bar2), -1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        BalanceOfPower::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let bop = BalanceOfPower::new(14).unwrap();
+        assert_eq!(format!("{}", bop), "BOP(14)");
+    }
+}
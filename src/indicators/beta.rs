@@ -0,0 +1,402 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{Amend, Checkpoint, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling beta versus a benchmark (BETA).
+///
+/// Measures the sensitivity of an asset to the movements of a benchmark over the last _period_
+/// values. It consumes pairs of returns `(asset, benchmark)`.
+///
+/// # Formula
+///
+/// BETA = Cov(asset, benchmark) / Var(benchmark)
+///
+/// Where the population covariance and variance are calculated over the window. During the first
+/// bars the window contains fewer than _period_ values.
+///
+/// When the benchmark did not change within the window, the variance is 0 and 0 is returned.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::Beta;
+/// use ta::Next;
+///
+/// let mut beta = Beta::new(3).unwrap();
+///
+/// beta.next((2.0, 1.0));
+/// assert_eq!(beta.next((4.0, 2.0)), 2.0);
+/// assert_eq!(beta.covariance(), 0.5);
+/// assert_eq!(beta.variance(), 0.25);
+/// ```
+///
+/// # Links
+///
+/// * [Beta (finance), Wikipedia](https://en.wikipedia.org/wiki/Beta_(finance))
+///
+#[doc(alias = "BETA")]
+#[derive(Debug, Clone)]
+pub struct Beta {
+    period: usize,
+    index: usize,
+    count: usize,
+    sum_asset: f64,
+    sum_benchmark: f64,
+    sum_product: f64,
+    sum_benchmark_sq: f64,
+    assets: Box<[f64]>,
+    benchmarks: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: (f64, f64),
+    saved_count: usize,
+    saved_sums: [f64; 4],
+}
+
+/// State of [Beta](struct.Beta.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BetaState {
+    pub period: usize,
+    /// Asset returns in the window, the oldest first.
+    pub assets: Vec<f64>,
+    /// Benchmark returns in the window, the oldest first.
+    pub benchmarks: Vec<f64>,
+    pub sum_asset: f64,
+    pub sum_benchmark: f64,
+    pub sum_product: f64,
+    pub sum_benchmark_sq: f64,
+    pub output: Option<f64>,
+    /// Returns which the last input pushed out of the window, to amend it.
+    pub evicted: Option<(f64, f64)>,
+    /// Running sums before the last input, to amend it.
+    pub previous_sums: [f64; 4],
+}
+
+impl Beta {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                sum_asset: 0.0,
+                sum_benchmark: 0.0,
+                sum_product: 0.0,
+                sum_benchmark_sq: 0.0,
+                assets: vec![0.0; period].into_boxed_slice(),
+                benchmarks: vec![0.0; period].into_boxed_slice(),
+                output: None,
+                saved_value: (0.0, 0.0),
+                saved_count: 0,
+                saved_sums: [0.0; 4],
+            }),
+        }
+    }
+
+    /// Covariance of the asset and the benchmark in the current window.
+    pub fn covariance(&self) -> f64 {
+        Self::covariance_of(self.count, self.sums())
+    }
+
+    /// Variance of the benchmark in the current window.
+    pub fn variance(&self) -> f64 {
+        Self::variance_of(self.count, self.sums())
+    }
+
+    // Σasset, Σbenchmark, Σasset·benchmark and Σbenchmark² of the window
+    fn sums(&self) -> [f64; 4] {
+        [
+            self.sum_asset,
+            self.sum_benchmark,
+            self.sum_product,
+            self.sum_benchmark_sq,
+        ]
+    }
+
+    // Same as `sums`, after the pair entered the window. The old values are zeros until the
+    // window is full.
+    fn sums_with(&self, (asset, benchmark): (f64, f64)) -> [f64; 4] {
+        let old_asset = self.assets[self.index];
+        let old_benchmark = self.benchmarks[self.index];
+        [
+            self.sum_asset + (asset - old_asset),
+            self.sum_benchmark + (benchmark - old_benchmark),
+            self.sum_product + (asset * benchmark - old_asset * old_benchmark),
+            self.sum_benchmark_sq + (benchmark * benchmark - old_benchmark * old_benchmark),
+        ]
+    }
+
+    fn covariance_of(count: usize, [sum_asset, sum_benchmark, sum_product, _]: [f64; 4]) -> f64 {
+        if count == 0 {
+            return 0.0;
+        }
+        let n = count as f64;
+        (sum_product - sum_asset * sum_benchmark / n) / n
+    }
+
+    fn variance_of(count: usize, [_, sum_benchmark, _, sum_benchmark_sq]: [f64; 4]) -> f64 {
+        if count == 0 {
+            return 0.0;
+        }
+        let n = count as f64;
+        ((sum_benchmark_sq - sum_benchmark * sum_benchmark / n) / n).max(0.0)
+    }
+
+    fn beta(count: usize, sums: [f64; 4]) -> f64 {
+        let variance = Self::variance_of(count, sums);
+        if variance > 0.0 {
+            Self::covariance_of(count, sums) / variance
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Period for Beta {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for Beta {
+    type State = BetaState;
+
+    fn state(&self) -> Self::State {
+        BetaState {
+            period: self.period,
+            assets: ring_values(&self.assets, self.index, self.count),
+            benchmarks: ring_values(&self.benchmarks, self.index, self.count),
+            sum_asset: self.sum_asset,
+            sum_benchmark: self.sum_benchmark,
+            sum_product: self.sum_product,
+            sum_benchmark_sq: self.sum_benchmark_sq,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous_sums: self.saved_sums,
+            output: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.assets.is_empty()
+            || state.benchmarks.len() != state.assets.len()
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let (assets, index) = ring_from_values(state.period, &state.assets)?;
+        let (benchmarks, _) = ring_from_values(state.period, &state.benchmarks)?;
+        indicator.assets = assets;
+        indicator.benchmarks = benchmarks;
+        indicator.index = index;
+        indicator.count = state.assets.len();
+        indicator.sum_asset = state.sum_asset;
+        indicator.sum_benchmark = state.sum_benchmark;
+        indicator.sum_product = state.sum_product;
+        indicator.sum_benchmark_sq = state.sum_benchmark_sq;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or((0.0, 0.0));
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_sums = state.previous_sums;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(Beta, 2);
+
+impl SetPeriod for Beta {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<(f64, f64)> for Beta {
+    type Output = f64;
+
+    fn next(&mut self, (asset, benchmark): (f64, f64)) -> Self::Output {
+        self.saved_value = (self.assets[self.index], self.benchmarks[self.index]);
+        self.saved_count = self.count;
+        self.saved_sums = [
+            self.sum_asset,
+            self.sum_benchmark,
+            self.sum_product,
+            self.sum_benchmark_sq,
+        ];
+        let sums = self.sums_with((asset, benchmark));
+        [
+            self.sum_asset,
+            self.sum_benchmark,
+            self.sum_product,
+            self.sum_benchmark_sq,
+        ] = sums;
+        self.assets[self.index] = asset;
+        self.benchmarks[self.index] = benchmark;
+
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < self.period {
+            self.count += 1;
+        }
+
+        let output = Self::beta(self.count, sums);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Peek<(f64, f64)> for Beta {
+    fn peek(&self, input: (f64, f64)) -> f64 {
+        let count = (self.count + 1).min(self.period);
+        Self::beta(count, self.sums_with(input))
+    }
+}
+
+impl Amend<(f64, f64)> for Beta {
+    fn amend(&mut self, input: (f64, f64)) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        (self.assets[self.index], self.benchmarks[self.index]) = self.saved_value;
+        self.count = self.saved_count;
+        [
+            self.sum_asset,
+            self.sum_benchmark,
+            self.sum_product,
+            self.sum_benchmark_sq,
+        ] = self.saved_sums;
+        self.next(input)
+    }
+}
+
+impl Reset for Beta {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.sum_asset = 0.0;
+        self.sum_benchmark = 0.0;
+        self.sum_product = 0.0;
+        self.sum_benchmark_sq = 0.0;
+        for i in 0..self.period {
+            self.assets[i] = 0.0;
+            self.benchmarks[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = (0.0, 0.0);
+        self.saved_count = 0;
+        self.saved_sums = [0.0; 4];
+    }
+}
+
+impl Current for Beta {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for Beta {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for Beta {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for Beta {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for Beta {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BETA({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(Beta::new(0).is_err());
+        assert!(Beta::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut beta = Beta::new(3).unwrap();
+
+        assert_eq!(beta.next((2.0, 1.0)), 0.0);
+        assert_eq!(beta.next((4.0, 2.0)), 2.0);
+
+        assert_eq!(round(beta.next((-2.0, -2.0))), 1.462);
+        assert_eq!(round(beta.covariance()), 4.222);
+        assert_eq!(round(beta.variance()), 2.889);
+
+        assert_eq!(round(beta.next((6.0, 4.0))), 1.357);
+        assert_eq!(round(beta.covariance()), 8.444);
+        assert_eq!(round(beta.variance()), 6.222);
+    }
+
+    #[test]
+    fn test_next_flat_benchmark() {
+        let mut beta = Beta::new(3).unwrap();
+
+        beta.next((1.0, 1.0));
+        assert_eq!(beta.next((3.0, 1.0)), 0.0);
+        assert_eq!(beta.variance(), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut beta = Beta::new(3).unwrap();
+
+        beta.next((2.0, 1.0));
+        beta.next((4.0, 2.0));
+
+        beta.reset();
+        assert_eq!(beta.covariance(), 0.0);
+        assert_eq!(beta.variance(), 0.0);
+        assert_eq!(beta.next((4.0, 2.0)), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        Beta::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let beta = Beta::new(20).unwrap();
+        assert_eq!(format!("{}", beta), "BETA(20)");
+    }
+}
@@ -1,8 +1,12 @@
-use std::fmt;
+use core::fmt;
 
-use crate::errors::Result;
-use crate::indicators::StandardDeviation as Sd;
-use crate::{Close, Next, Period, Reset};
+use crate::errors::{Result, TaError};
+use crate::indicators::generic::StandardDeviation as Sd;
+use crate::indicators::StandardDeviationState;
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Numeric, Peek, Period, Reset,
+    SetPeriod,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -46,77 +50,183 @@ use serde::{Deserialize, Serialize};
 ///
 /// * [Bollinger Bands, Wikipedia](https://en.wikipedia.org/wiki/Bollinger_Bands)
 #[doc(alias = "BB")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct BollingerBands {
+pub struct BollingerBands<N = f64> {
     period: usize,
     multiplier: f64,
-    sd: Sd,
+    sd: Sd<N>,
+    output: Option<BollingerBandsOutput<N>>,
 }
 
+/// Output of [BollingerBands](generic/struct.BollingerBands.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
-pub struct BollingerBandsOutput {
-    pub average: f64,
-    pub upper: f64,
-    pub lower: f64,
+pub struct BollingerBandsOutput<N = f64> {
+    pub upper: N,
+    pub average: N,
+    pub lower: N,
 }
 
-impl BollingerBands {
+/// State of [BollingerBands](generic/struct.BollingerBands.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BollingerBandsState<N = f64> {
+    pub period: usize,
+    pub multiplier: f64,
+    pub sd: StandardDeviationState<N>,
+}
+
+impl_output!(BollingerBandsOutput, upper, average, lower);
+
+impl<N: Numeric> BollingerBands<N> {
     pub fn new(period: usize, multiplier: f64) -> Result<Self> {
         Ok(Self {
             period,
             multiplier,
             sd: Sd::new(period)?,
+            output: None,
         })
     }
 
     pub fn multiplier(&self) -> f64 {
         self.multiplier
     }
+
+    /// Calculates the output again after the standard deviation was changed.
+    fn update_output(&mut self) {
+        let mean = self.sd.mean();
+        self.output = self.sd.current().map(|sd| self.bands(sd, mean));
+    }
+
+    fn bands(&self, sd: N, mean: N) -> BollingerBandsOutput<N> {
+        let width = sd * N::from_param(self.multiplier);
+        BollingerBandsOutput {
+            average: mean,
+            upper: mean + width,
+            lower: mean - width,
+        }
+    }
+
+    fn next_value(&mut self, input: N) -> BollingerBandsOutput<N> {
+        let sd = self.sd.next_value(input);
+        let output = self.bands(sd, self.sd.mean());
+        self.output = Some(output.clone());
+        output
+    }
+
+    fn peek_value(&self, input: N) -> BollingerBandsOutput<N> {
+        let (sd, mean) = self.sd.peek_with_mean(input);
+        self.bands(sd, mean)
+    }
+
+    fn amend_value(&mut self, input: N) -> BollingerBandsOutput<N> {
+        let sd = self.sd.amend_value(input);
+        let output = self.bands(sd, self.sd.mean());
+        self.output = Some(output.clone());
+        output
+    }
 }
 
-impl Period for BollingerBands {
+impl<N: Numeric> Period for BollingerBands<N> {
     fn period(&self) -> usize {
         self.period
     }
 }
 
-impl Next<f64> for BollingerBands {
-    type Output = BollingerBandsOutput;
+impl<N: Numeric> Checkpoint for BollingerBands<N> {
+    type State = BollingerBandsState<N>;
 
-    fn next(&mut self, input: f64) -> Self::Output {
-        let sd = self.sd.next(input);
-        let mean = self.sd.mean();
+    fn state(&self) -> Self::State {
+        BollingerBandsState {
+            period: self.period,
+            multiplier: self.multiplier,
+            sd: self.sd.state(),
+        }
+    }
 
-        Self::Output {
-            average: mean,
-            upper: mean + sd * self.multiplier,
-            lower: mean - sd * self.multiplier,
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.sd.period != state.period {
+            return Err(TaError::InvalidState);
         }
+        let mut bb = Self {
+            period: state.period,
+            multiplier: state.multiplier,
+            sd: Sd::from_state(state.sd)?,
+            output: None,
+        };
+        bb.update_output();
+        Ok(bb)
+    }
+}
+
+impl_versioned_serde!(BollingerBands<N>, 1);
+
+impl<N: Numeric> SetPeriod for BollingerBands<N> {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        self.sd.set_period(period)?;
+        self.period = period;
+        self.update_output();
+        Ok(())
     }
 }
 
-impl<T: Close> Next<&T> for BollingerBands {
-    type Output = BollingerBandsOutput;
+impl_numeric_inputs!(BollingerBands => BollingerBandsOutput);
+
+impl<N: Numeric, T: Close<N>> Next<&T> for BollingerBands<N> {
+    type Output = BollingerBandsOutput<N>;
 
     fn next(&mut self, input: &T) -> Self::Output {
-        self.next(input.close())
+        self.next_value(input.close())
+    }
+}
+
+impl<N: Numeric, T: Close<N>> Peek<&T> for BollingerBands<N> {
+    fn peek(&self, input: &T) -> BollingerBandsOutput<N> {
+        self.peek_value(input.close())
     }
 }
 
-impl Reset for BollingerBands {
+impl<N: Numeric, T: Close<N>> Amend<&T> for BollingerBands<N> {
+    fn amend(&mut self, input: &T) -> BollingerBandsOutput<N> {
+        self.amend_value(input.close())
+    }
+}
+
+impl<N: Numeric> Reset for BollingerBands<N> {
     fn reset(&mut self) {
         self.sd.reset();
+        self.output = None;
     }
 }
 
-impl Default for BollingerBands {
+impl<N: Numeric> Current for BollingerBands<N> {
+    type Output = BollingerBandsOutput<N>;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl<N: Numeric> IsReady for BollingerBands<N> {
+    fn is_ready(&self) -> bool {
+        self.sd.is_ready()
+    }
+}
+
+impl<N: Numeric> Lookback for BollingerBands<N> {
+    fn lookback(&self) -> usize {
+        self.sd.lookback()
+    }
+}
+
+impl<N: Numeric> Default for BollingerBands<N> {
     fn default() -> Self {
         Self::new(9, 2_f64).unwrap()
     }
 }
 
-impl fmt::Display for BollingerBands {
+impl<N: Numeric> fmt::Display for BollingerBands<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "BB({}, {})", self.period, self.multiplier)
     }
@@ -125,6 +235,7 @@ impl fmt::Display for BollingerBands {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::BollingerBands;
     use crate::test_helper::*;
 
     test_indicator!(BollingerBands);
@@ -161,6 +272,20 @@ mod tests {
         assert_eq!(round(d.lower), -0.395);
     }
 
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_next_decimal() {
+        use crate::indicators::generic::BollingerBands;
+        use rust_decimal::Decimal;
+
+        let mut bb = BollingerBands::<Decimal>::new(2, 2.0).unwrap();
+        bb.next(Decimal::new(1, 1));
+        let output = bb.next(Decimal::new(3, 1));
+        assert_eq!(output.average, Decimal::new(2, 1));
+        assert_eq!(output.upper, Decimal::new(4, 1));
+        assert_eq!(output.lower, Decimal::ZERO);
+    }
+
     #[test]
     fn test_reset() {
         let mut bb = BollingerBands::new(5, 2.0_f64).unwrap();
@@ -197,5 +322,15 @@ mod tests {
     fn test_display() {
         let bb = BollingerBands::new(10, 3.0_f64).unwrap();
         assert_eq!(format!("{}", bb), "BB(10, 3)");
+
+        let output = BollingerBandsOutput {
+            upper: 6.5,
+            average: 3.5,
+            lower: 0.5,
+        };
+        assert_eq!(
+            format!("{}", output),
+            "upper: 6.5, average: 3.5, lower: 0.5"
+        );
     }
 }
@@ -0,0 +1,382 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Ehlers Center of Gravity (CG) oscillator.
+///
+/// Weighs the prices in the window by their position, like the balance point of a beam, and
+/// shifts the result so that it oscillates around zero. It turns with almost no lag, and the
+/// signal line is simply the value of the previous bar: the CG crossing above the signal is a
+/// buy signal, crossing below is a sell signal.
+///
+/// # Formula
+///
+/// CG<sub>t</sub> = -Σ((i + 1) × Price<sub>t-i</sub>) / ΣPrice<sub>t-i</sub> + (n + 1) / 2
+///
+/// Signal<sub>t</sub> = CG<sub>t-1</sub>
+///
+/// Where:
+///
+/// * _i_ goes from 0 to _n_ - 1
+/// * _n_ - _period_, or the number of prices seen so far if it is smaller
+///
+/// The CG is 0 when the sum of the prices is 0. The signal of the first bar is the CG itself.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 10.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::CenterOfGravity;
+/// use ta::Next;
+///
+/// let mut cg = CenterOfGravity::new(3).unwrap();
+///
+/// assert_eq!(cg.next(10.0).cg, 0.0);
+/// let out = cg.next(30.0);
+/// assert_eq!(out.cg, 0.25);
+/// assert_eq!(out.signal, 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [The CG Oscillator, John Ehlers (PDF)](https://www.mesasoftware.com/papers/TheCGOscillator.pdf)
+#[doc(alias = "CG")]
+#[derive(Debug, Clone)]
+pub struct CenterOfGravity {
+    period: usize,
+    index: usize,
+    count: usize,
+    deque: Box<[f64]>,
+    prev: Option<f64>,
+    output: Option<CenterOfGravityOutput>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+    saved_prev: Option<f64>,
+}
+
+/// State of [CenterOfGravity](struct.CenterOfGravity.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CenterOfGravityState {
+    pub period: usize,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<f64>,
+    /// Last output, `None` before the first input.
+    pub output: Option<CenterOfGravityOutput>,
+    /// Input which the last one pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
+    /// Center of gravity before the last one, to amend the last input.
+    pub previous_cg: Option<f64>,
+}
+
+/// Output of [CenterOfGravity](struct.CenterOfGravity.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CenterOfGravityOutput {
+    pub cg: f64,
+    /// CG of the previous bar.
+    pub signal: f64,
+}
+
+impl_output!(CenterOfGravityOutput, cg, signal);
+
+impl From<CenterOfGravityOutput> for (f64, f64) {
+    fn from(cg: CenterOfGravityOutput) -> Self {
+        (cg.cg, cg.signal)
+    }
+}
+
+impl CenterOfGravity {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                deque: vec![0.0; period].into_boxed_slice(),
+                prev: None,
+                output: None,
+                saved_value: 0.0,
+                saved_count: 0,
+                saved_prev: None,
+            }),
+        }
+    }
+}
+
+impl Period for CenterOfGravity {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for CenterOfGravity {
+    type State = CenterOfGravityState;
+
+    fn state(&self) -> Self::State {
+        CenterOfGravityState {
+            period: self.period,
+            inputs: ring_values(&self.deque, self.index, self.count),
+            output: self.output.clone(),
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous_cg: self.saved_prev,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.inputs.is_empty() {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let (deque, index) = ring_from_values(state.period, &state.inputs)?;
+        indicator.deque = deque;
+        indicator.index = index;
+        indicator.count = state.inputs.len();
+        // The signal is the previous center of gravity
+        indicator.prev = state.output.as_ref().map(|output| output.cg);
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_prev = state.previous_cg;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(CenterOfGravity, 2);
+
+impl SetPeriod for CenterOfGravity {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for CenterOfGravity {
+    type Output = CenterOfGravityOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
+        self.saved_prev = self.prev;
+        self.deque[self.index] = input;
+        if self.count < self.period {
+            self.count += 1;
+        }
+
+        let mut numerator = 0.0;
+        let mut denominator = 0.0;
+        for i in 0..self.count {
+            let price = self.deque[(self.index + self.period - i) % self.period];
+            numerator += (i + 1) as f64 * price;
+            denominator += price;
+        }
+
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        let cg = if denominator == 0.0 {
+            0.0
+        } else {
+            -numerator / denominator + (self.count + 1) as f64 / 2.0
+        };
+        let signal = self.prev.unwrap_or(cg);
+        self.prev = Some(cg);
+
+        let output = CenterOfGravityOutput { cg, signal };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for CenterOfGravity {
+    type Output = CenterOfGravityOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for CenterOfGravity {
+    fn peek(&self, input: f64) -> CenterOfGravityOutput {
+        let count = (self.count + 1).min(self.period);
+
+        let mut numerator = 0.0;
+        let mut denominator = 0.0;
+        for i in 0..count {
+            let price = if i == 0 {
+                input
+            } else {
+                self.deque[(self.index + self.period - i) % self.period]
+            };
+            numerator += (i + 1) as f64 * price;
+            denominator += price;
+        }
+
+        let cg = if denominator == 0.0 {
+            0.0
+        } else {
+            -numerator / denominator + (count + 1) as f64 / 2.0
+        };
+
+        CenterOfGravityOutput {
+            cg,
+            signal: self.prev.unwrap_or(cg),
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for CenterOfGravity {
+    fn peek(&self, input: &T) -> CenterOfGravityOutput {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for CenterOfGravity {
+    fn amend(&mut self, input: f64) -> CenterOfGravityOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.prev = self.saved_prev;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for CenterOfGravity {
+    fn amend(&mut self, input: &T) -> CenterOfGravityOutput {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for CenterOfGravity {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+        self.prev = None;
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+        self.saved_prev = None;
+    }
+}
+
+impl Current for CenterOfGravity {
+    type Output = CenterOfGravityOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for CenterOfGravity {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for CenterOfGravity {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for CenterOfGravity {
+    fn default() -> Self {
+        Self::new(10).unwrap()
+    }
+}
+
+impl fmt::Display for CenterOfGravity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CG({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(CenterOfGravity);
+
+    fn next(cg: &mut CenterOfGravity, input: f64) -> (f64, f64) {
+        let out = cg.next(input);
+        (round(out.cg), round(out.signal))
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(CenterOfGravity::new(0).is_err());
+        assert!(CenterOfGravity::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut cg = CenterOfGravity::new(3).unwrap();
+
+        assert_eq!(next(&mut cg, 10.0), (0.0, 0.0));
+        assert_eq!(next(&mut cg, 12.0), (0.045, 0.0));
+        assert_eq!(next(&mut cg, 11.0), (0.03, 0.045));
+        assert_eq!(next(&mut cg, 14.0), (0.054, 0.03));
+        assert_eq!(next(&mut cg, 13.0), (0.053, 0.054));
+
+        let out = cg.next(&Bar::new().close(13));
+        assert_eq!(round(out.signal), 0.053);
+    }
+
+    #[test]
+    fn test_zero_prices() {
+        let mut cg = CenterOfGravity::new(3).unwrap();
+        assert_eq!(next(&mut cg, 0.0), (0.0, 0.0));
+        assert_eq!(next(&mut cg, 0.0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut cg = CenterOfGravity::new(3).unwrap();
+
+        cg.next(10.0);
+        cg.next(12.0);
+        cg.reset();
+        assert_eq!(next(&mut cg, 10.0), (0.0, 0.0));
+        assert_eq!(next(&mut cg, 12.0), (0.045, 0.0));
+    }
+
+    #[test]
+    fn test_default() {
+        CenterOfGravity::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let cg = CenterOfGravity::new(10).unwrap();
+        assert_eq!(format!("{}", cg), "CG(10)");
+    }
+}
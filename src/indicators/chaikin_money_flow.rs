@@ -0,0 +1,368 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset,
+    SetPeriod, Volume,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Chaikin Money Flow (CMF).
+///
+/// Measures the amount of money flow volume over a specific period. The oscillator returns
+/// output in the range of -1..1: positive values indicate buying pressure and negative values
+/// indicate selling pressure.
+///
+/// # Formula
+///
+/// Money Flow Multiplier (MFM) = ((Close - Low) - (High - Close)) / (High - Low)
+///
+/// Money Flow Volume (MFV) = MFM * Volume
+///
+/// CMF = Σ(MFV) / Σ(Volume) over the last _period_ bars
+///
+/// The money flow multiplier of a bar with no range (High = Low) is 0.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::ChaikinMoneyFlow;
+/// use ta::{Next, DataItem};
+///
+/// let mut cmf = ChaikinMoneyFlow::new(3).unwrap();
+/// let di = DataItem::builder()
+///             .high(10.0)
+///             .low(6.0)
+///             .close(9.0)
+///             .open(7.0)
+///             .volume(1000.0)
+///             .build().unwrap();
+///
+/// assert_eq!(cmf.next(&di), 0.5);
+/// ```
+///
+/// # Links
+///
+/// * [Chaikin Money Flow, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:chaikin_money_flow_cmf)
+///
+#[doc(alias = "CMF")]
+#[derive(Debug, Clone)]
+pub struct ChaikinMoneyFlow {
+    period: usize,
+    index: usize,
+    count: usize,
+    sum_money_flow_volume: f64,
+    sum_volume: f64,
+    money_flow_volumes: Box<[f64]>,
+    volumes: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: (f64, f64),
+    saved_count: usize,
+    saved_sum_money_flow_volume: f64,
+    saved_sum_volume: f64,
+}
+
+/// State of [ChaikinMoneyFlow](struct.ChaikinMoneyFlow.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChaikinMoneyFlowState {
+    pub period: usize,
+    /// Money flow volumes in the window, the oldest first.
+    pub money_flow_volumes: Vec<f64>,
+    /// Volumes in the window, the oldest first.
+    pub volumes: Vec<f64>,
+    pub sum_money_flow_volume: f64,
+    pub sum_volume: f64,
+    pub output: Option<f64>,
+    /// Money flow volume and volume which the last input pushed out of the window, to amend it.
+    pub evicted: Option<(f64, f64)>,
+    /// Running sums before the last input, to amend it.
+    pub previous_sum_money_flow_volume: f64,
+    pub previous_sum_volume: f64,
+}
+
+impl ChaikinMoneyFlow {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                sum_money_flow_volume: 0.0,
+                sum_volume: 0.0,
+                money_flow_volumes: vec![0.0; period].into_boxed_slice(),
+                volumes: vec![0.0; period].into_boxed_slice(),
+                output: None,
+                saved_value: (0.0, 0.0),
+                saved_count: 0,
+                saved_sum_money_flow_volume: 0.0,
+                saved_sum_volume: 0.0,
+            }),
+        }
+    }
+}
+
+impl Period for ChaikinMoneyFlow {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for ChaikinMoneyFlow {
+    type State = ChaikinMoneyFlowState;
+
+    fn state(&self) -> Self::State {
+        ChaikinMoneyFlowState {
+            period: self.period,
+            money_flow_volumes: ring_values(&self.money_flow_volumes, self.index, self.count),
+            volumes: ring_values(&self.volumes, self.index, self.count),
+            sum_money_flow_volume: self.sum_money_flow_volume,
+            sum_volume: self.sum_volume,
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous_sum_money_flow_volume: self.saved_sum_money_flow_volume,
+            previous_sum_volume: self.saved_sum_volume,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.money_flow_volumes.is_empty()
+            || state.volumes.len() != state.money_flow_volumes.len()
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let (money_flow_volumes, index) =
+            ring_from_values(state.period, &state.money_flow_volumes)?;
+        let (volumes, _) = ring_from_values(state.period, &state.volumes)?;
+        indicator.money_flow_volumes = money_flow_volumes;
+        indicator.volumes = volumes;
+        indicator.index = index;
+        indicator.count = state.money_flow_volumes.len();
+        indicator.sum_money_flow_volume = state.sum_money_flow_volume;
+        indicator.sum_volume = state.sum_volume;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or((0.0, 0.0));
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_sum_money_flow_volume = state.previous_sum_money_flow_volume;
+        indicator.saved_sum_volume = state.previous_sum_volume;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(ChaikinMoneyFlow, 2);
+
+impl SetPeriod for ChaikinMoneyFlow {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl<T: High + Low + Close + Volume> Next<&T> for ChaikinMoneyFlow {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_value = (
+            self.money_flow_volumes[self.index],
+            self.volumes[self.index],
+        );
+        self.saved_count = self.count;
+        self.saved_sum_money_flow_volume = self.sum_money_flow_volume;
+        self.saved_sum_volume = self.sum_volume;
+        let range = input.high() - input.low();
+        let multiplier = if range > 0.0 {
+            ((input.close() - input.low()) - (input.high() - input.close())) / range
+        } else {
+            0.0
+        };
+        let volume = input.volume();
+        let money_flow_volume = multiplier * volume;
+
+        self.sum_money_flow_volume += money_flow_volume - self.money_flow_volumes[self.index];
+        self.sum_volume += volume - self.volumes[self.index];
+        self.money_flow_volumes[self.index] = money_flow_volume;
+        self.volumes[self.index] = volume;
+
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        self.count = (self.count + 1).min(self.period);
+
+        let output = if self.sum_volume > 0.0 {
+            self.sum_money_flow_volume / self.sum_volume
+        } else {
+            0.0
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low + Close + Volume> Peek<&T> for ChaikinMoneyFlow {
+    fn peek(&self, input: &T) -> f64 {
+        let range = input.high() - input.low();
+        let multiplier = if range > 0.0 {
+            ((input.close() - input.low()) - (input.high() - input.close())) / range
+        } else {
+            0.0
+        };
+        let volume = input.volume();
+        let sum_money_flow_volume = self.sum_money_flow_volume
+            + (multiplier * volume - self.money_flow_volumes[self.index]);
+        let sum_volume = self.sum_volume + (volume - self.volumes[self.index]);
+
+        if sum_volume > 0.0 {
+            sum_money_flow_volume / sum_volume
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<T: High + Low + Close + Volume> Amend<&T> for ChaikinMoneyFlow {
+    fn amend(&mut self, input: &T) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        (
+            self.money_flow_volumes[self.index],
+            self.volumes[self.index],
+        ) = self.saved_value;
+        self.count = self.saved_count;
+        self.sum_money_flow_volume = self.saved_sum_money_flow_volume;
+        self.sum_volume = self.saved_sum_volume;
+        self.next(input)
+    }
+}
+
+impl Reset for ChaikinMoneyFlow {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.sum_money_flow_volume = 0.0;
+        self.sum_volume = 0.0;
+        for i in 0..self.period {
+            self.money_flow_volumes[i] = 0.0;
+            self.volumes[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = (0.0, 0.0);
+        self.saved_count = 0;
+        self.saved_sum_money_flow_volume = 0.0;
+        self.saved_sum_volume = 0.0;
+    }
+}
+
+impl Current for ChaikinMoneyFlow {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for ChaikinMoneyFlow {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for ChaikinMoneyFlow {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for ChaikinMoneyFlow {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for ChaikinMoneyFlow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CMF({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(ChaikinMoneyFlow::new(0).is_err());
+        assert!(ChaikinMoneyFlow::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut cmf = ChaikinMoneyFlow::new(2).unwrap();
+
+        // MFM = 0.5, MFV = 500
+        let bar1 = Bar::new().high(10).low(6).close(9).volume(1000.0);
+        // MFM = -1, MFV = -3000
+        let bar2 = Bar::new().high(12).low(8).close(8).volume(3000.0);
+        // MFM = 0, MFV = 0
+        let bar3 = Bar::new().high(5).low(5).close(5).volume(1000.0);
+
+        assert_eq!(cmf.next(&bar1), 0.5);
+        assert_eq!(cmf.next(&bar2), -0.625);
+        assert_eq!(cmf.next(&bar3), -0.75);
+    }
+
+    #[test]
+    fn test_next_without_volume() {
+        let mut cmf = ChaikinMoneyFlow::new(2).unwrap();
+
+        let bar = Bar::new().high(10).low(6).close(9).volume(0.0);
+        assert_eq!(cmf.next(&bar), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut cmf = ChaikinMoneyFlow::new(2).unwrap();
+
+        let bar1 = Bar::new().high(10).low(6).close(9).volume(1000.0);
+        let bar2 = Bar::new().high(12).low(8).close(8).volume(3000.0);
+
+        cmf.next(&bar1);
+        cmf.next(&bar2);
+
+        cmf.reset();
+        assert_eq!(cmf.next(&bar1), 0.5);
+        assert_eq!(cmf.next(&bar2), -0.625);
+    }
+
+    #[test]
+    fn test_default() {
+        ChaikinMoneyFlow::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let cmf = ChaikinMoneyFlow::new(20).unwrap();
+        assert_eq!(format!("{}", cmf), "CMF(20)");
+    }
+}
@@ -0,0 +1,396 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Chande Momentum Oscillator (CMO).
+///
+/// Developed by Tushar Chande, the oscillator uses the same decomposition into up and down moves
+/// as the [RSI](type.RelativeStrengthIndex.html), but sums them over the window instead of
+/// smoothing them. The oscillator returns output in the range of -100..100.
+///
+/// # Formula
+///
+/// CMO = 100 * (Σup - Σdown) / (Σup + Σdown)
+///
+/// Where:
+///
+/// * _up_ - p<sub>t</sub> - p<sub>t-1</sub> if the value went up, 0 otherwise
+/// * _down_ - p<sub>t-1</sub> - p<sub>t</sub> if the value went down, 0 otherwise
+/// * sums are calculated over the last _period_ bars
+///
+/// The change of the very first bar is 0. When there were no changes in the window, CMO is 0.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::ChandeMomentumOscillator;
+/// use ta::Next;
+///
+/// let mut cmo = ChandeMomentumOscillator::new(3).unwrap();
+/// assert_eq!(cmo.next(10.0), 0.0);
+/// assert_eq!(cmo.next(12.0), 100.0);
+/// assert_eq!(cmo.next(14.0), 100.0);
+/// assert_eq!(cmo.next(13.0), 60.0);
+/// ```
+///
+/// # Links
+///
+/// * [Chande Momentum Oscillator, Investopedia](https://www.investopedia.com/terms/c/chandemomentumoscillator.asp)
+///
+#[doc(alias = "CMO")]
+#[derive(Debug, Clone)]
+pub struct ChandeMomentumOscillator {
+    period: usize,
+    index: usize,
+    count: usize,
+    prev: Option<f64>,
+    sum_up: f64,
+    sum_down: f64,
+    ups: Box<[f64]>,
+    downs: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: (f64, f64),
+    saved_count: usize,
+    saved_prev: Option<f64>,
+    saved_sum_up: f64,
+    saved_sum_down: f64,
+}
+
+/// State of [ChandeMomentumOscillator](struct.ChandeMomentumOscillator.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChandeMomentumOscillatorState {
+    pub period: usize,
+    /// Previous input.
+    pub previous: Option<f64>,
+    /// Gains in the window, the oldest first.
+    pub ups: Vec<f64>,
+    /// Losses in the window, the oldest first.
+    pub downs: Vec<f64>,
+    pub sum_up: f64,
+    pub sum_down: f64,
+    pub output: Option<f64>,
+    /// Gain and loss which the last input pushed out of the window, to amend it.
+    pub evicted: Option<(f64, f64)>,
+    /// Input before the last one and sums before the last input, to amend it.
+    pub before_last: Option<f64>,
+    pub previous_sum_up: f64,
+    pub previous_sum_down: f64,
+}
+
+impl ChandeMomentumOscillator {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                prev: None,
+                sum_up: 0.0,
+                sum_down: 0.0,
+                ups: vec![0.0; period].into_boxed_slice(),
+                downs: vec![0.0; period].into_boxed_slice(),
+                output: None,
+                saved_value: (0.0, 0.0),
+                saved_count: 0,
+                saved_prev: None,
+                saved_sum_up: 0.0,
+                saved_sum_down: 0.0,
+            }),
+        }
+    }
+}
+
+impl Period for ChandeMomentumOscillator {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for ChandeMomentumOscillator {
+    type State = ChandeMomentumOscillatorState;
+
+    fn state(&self) -> Self::State {
+        ChandeMomentumOscillatorState {
+            period: self.period,
+            previous: self.prev,
+            ups: ring_values(&self.ups, self.index, self.count),
+            downs: ring_values(&self.downs, self.index, self.count),
+            sum_up: self.sum_up,
+            sum_down: self.sum_down,
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            before_last: self.saved_prev,
+            previous_sum_up: self.saved_sum_up,
+            previous_sum_down: self.saved_sum_down,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.ups.len() != state.downs.len()
+            || state.previous.is_none() != state.ups.is_empty()
+            || state.output.is_none() != state.ups.is_empty()
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let (ups, index) = ring_from_values(state.period, &state.ups)?;
+        let (downs, _) = ring_from_values(state.period, &state.downs)?;
+        indicator.ups = ups;
+        indicator.downs = downs;
+        indicator.index = index;
+        indicator.count = state.ups.len();
+        indicator.prev = state.previous;
+        indicator.sum_up = state.sum_up;
+        indicator.sum_down = state.sum_down;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or((0.0, 0.0));
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_prev = state.before_last;
+        indicator.saved_sum_up = state.previous_sum_up;
+        indicator.saved_sum_down = state.previous_sum_down;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(ChandeMomentumOscillator, 2);
+
+impl SetPeriod for ChandeMomentumOscillator {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for ChandeMomentumOscillator {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_value = (self.ups[self.index], self.downs[self.index]);
+        self.saved_count = self.count;
+        self.saved_prev = self.prev;
+        self.saved_sum_up = self.sum_up;
+        self.saved_sum_down = self.sum_down;
+        let change = match self.prev {
+            Some(prev) => input - prev,
+            None => 0.0,
+        };
+        self.prev = Some(input);
+
+        let up = change.max(0.0);
+        let down = (-change).max(0.0);
+
+        self.sum_up += up - self.ups[self.index];
+        self.sum_down += down - self.downs[self.index];
+        self.ups[self.index] = up;
+        self.downs[self.index] = down;
+
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        self.count = (self.count + 1).min(self.period);
+
+        let total = self.sum_up + self.sum_down;
+        let output = if total > 0.0 {
+            100.0 * (self.sum_up - self.sum_down) / total
+        } else {
+            0.0
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for ChandeMomentumOscillator {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for ChandeMomentumOscillator {
+    fn peek(&self, input: f64) -> f64 {
+        let change = match self.prev {
+            Some(prev) => input - prev,
+            None => 0.0,
+        };
+
+        let sum_up = self.sum_up + (change.max(0.0) - self.ups[self.index]);
+        let sum_down = self.sum_down + ((-change).max(0.0) - self.downs[self.index]);
+
+        let total = sum_up + sum_down;
+        if total > 0.0 {
+            100.0 * (sum_up - sum_down) / total
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for ChandeMomentumOscillator {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for ChandeMomentumOscillator {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        (self.ups[self.index], self.downs[self.index]) = self.saved_value;
+        self.count = self.saved_count;
+        self.prev = self.saved_prev;
+        self.sum_up = self.saved_sum_up;
+        self.sum_down = self.saved_sum_down;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for ChandeMomentumOscillator {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for ChandeMomentumOscillator {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.prev = None;
+        self.sum_up = 0.0;
+        self.sum_down = 0.0;
+        for i in 0..self.period {
+            self.ups[i] = 0.0;
+            self.downs[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = (0.0, 0.0);
+        self.saved_count = 0;
+        self.saved_prev = None;
+        self.saved_sum_up = 0.0;
+        self.saved_sum_down = 0.0;
+    }
+}
+
+impl Current for ChandeMomentumOscillator {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for ChandeMomentumOscillator {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for ChandeMomentumOscillator {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for ChandeMomentumOscillator {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for ChandeMomentumOscillator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CMO({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(ChandeMomentumOscillator);
+
+    #[test]
+    fn test_new() {
+        assert!(ChandeMomentumOscillator::new(0).is_err());
+        assert!(ChandeMomentumOscillator::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut cmo = ChandeMomentumOscillator::new(3).unwrap();
+
+        assert_eq!(cmo.next(10.0), 0.0);
+        assert_eq!(cmo.next(12.0), 100.0);
+        assert_eq!(round(cmo.next(11.0)), 33.333);
+        assert_eq!(round(cmo.next(14.0)), 66.667);
+        assert_eq!(round(cmo.next(13.0)), 20.0);
+        assert_eq!(round(cmo.next(9.0)), -25.0);
+    }
+
+    #[test]
+    fn test_next_flat() {
+        let mut cmo = ChandeMomentumOscillator::new(2).unwrap();
+
+        assert_eq!(cmo.next(5.0), 0.0);
+        assert_eq!(cmo.next(5.0), 0.0);
+        assert_eq!(cmo.next(5.0), 0.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut cmo = ChandeMomentumOscillator::new(3).unwrap();
+
+        assert_eq!(cmo.next(&Bar::new().close(10)), 0.0);
+        assert_eq!(cmo.next(&Bar::new().close(8)), -100.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut cmo = ChandeMomentumOscillator::new(3).unwrap();
+
+        cmo.next(10.0);
+        cmo.next(12.0);
+
+        cmo.reset();
+        assert_eq!(cmo.next(12.0), 0.0);
+        assert_eq!(cmo.next(10.0), -100.0);
+    }
+
+    #[test]
+    fn test_default() {
+        ChandeMomentumOscillator::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let cmo = ChandeMomentumOscillator::new(14).unwrap();
+        assert_eq!(format!("{}", cmo), "CMO(14)");
+    }
+}
@@ -1,11 +1,16 @@
-use std::fmt;
+use core::fmt;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::errors::Result;
-use crate::indicators::{AverageTrueRange, Maximum, Minimum};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::errors::{Result, TaError};
+use crate::indicators::{
+    AverageTrueRange, AverageTrueRangeState, Maximum, MaximumState, Minimum, MinimumState,
+};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset,
+    SetPeriod,
+};
 
 /// Chandelier Exit (CE).
 ///
@@ -51,13 +56,25 @@ use crate::{Close, High, Low, Next, Period, Reset};
 /// * [Chandelier Exit, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:chandelier_exit)
 ///
 #[doc(alias = "CE")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct ChandelierExit {
     atr: AverageTrueRange,
     min: Minimum,
     max: Maximum,
     multiplier: f64,
+    output: Option<ChandelierExitOutput>,
+}
+
+/// State of [ChandelierExit](struct.ChandelierExit.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChandelierExitState {
+    pub atr: AverageTrueRangeState,
+    pub min: MinimumState,
+    pub max: MaximumState,
+    pub multiplier: f64,
+    pub output: Option<ChandelierExitOutput>,
 }
 
 impl ChandelierExit {
@@ -67,6 +84,7 @@ impl ChandelierExit {
             min: Minimum::new(period)?,
             max: Maximum::new(period)?,
             multiplier,
+            output: None,
         })
     }
 
@@ -75,12 +93,16 @@ impl ChandelierExit {
     }
 }
 
+/// Output of [ChandelierExit](struct.ChandelierExit.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
 pub struct ChandelierExitOutput {
     pub long: f64,
     pub short: f64,
 }
 
+impl_output!(ChandelierExitOutput, long, short);
+
 impl From<ChandelierExitOutput> for (f64, f64) {
     fn from(ce: ChandelierExitOutput) -> Self {
         (ce.long, ce.short)
@@ -93,6 +115,46 @@ impl Period for ChandelierExit {
     }
 }
 
+impl Checkpoint for ChandelierExit {
+    type State = ChandelierExitState;
+
+    fn state(&self) -> Self::State {
+        ChandelierExitState {
+            atr: self.atr.state(),
+            min: self.min.state(),
+            max: self.max.state(),
+            multiplier: self.multiplier,
+            output: self.output.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.min.period != state.atr.ema.period
+            || state.max.period != state.atr.ema.period
+            || state.min.inputs.len() != state.max.inputs.len()
+            || state.output.is_none() != state.max.inputs.is_empty()
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            atr: AverageTrueRange::from_state(state.atr)?,
+            min: Minimum::from_state(state.min)?,
+            max: Maximum::from_state(state.max)?,
+            multiplier: state.multiplier,
+            output: state.output,
+        })
+    }
+}
+
+impl_versioned_serde!(ChandelierExit, 1);
+
+impl SetPeriod for ChandelierExit {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period, self.multiplier)?;
+        Ok(())
+    }
+}
+
 impl<T: Low + High + Close> Next<&T> for ChandelierExit {
     type Output = ChandelierExitOutput;
 
@@ -101,6 +163,21 @@ impl<T: Low + High + Close> Next<&T> for ChandelierExit {
         let min = self.min.next(input);
         let max = self.max.next(input);
 
+        let output = ChandelierExitOutput {
+            long: max - atr,
+            short: min + atr,
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Low + High + Close> Peek<&T> for ChandelierExit {
+    fn peek(&self, input: &T) -> ChandelierExitOutput {
+        let atr = self.atr.peek(input) * self.multiplier;
+        let min = self.min.peek(input);
+        let max = self.max.peek(input);
+
         ChandelierExitOutput {
             long: max - atr,
             short: min + atr,
@@ -108,11 +185,50 @@ impl<T: Low + High + Close> Next<&T> for ChandelierExit {
     }
 }
 
+impl<T: Low + High + Close> Amend<&T> for ChandelierExit {
+    fn amend(&mut self, input: &T) -> ChandelierExitOutput {
+        let atr = self.atr.amend(input) * self.multiplier;
+        let min = self.min.amend(input);
+        let max = self.max.amend(input);
+
+        let output = ChandelierExitOutput {
+            long: max - atr,
+            short: min + atr,
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
 impl Reset for ChandelierExit {
     fn reset(&mut self) {
         self.atr.reset();
         self.min.reset();
         self.max.reset();
+        self.output = None;
+    }
+}
+
+impl Current for ChandelierExit {
+    type Output = ChandelierExitOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for ChandelierExit {
+    fn is_ready(&self) -> bool {
+        self.atr.is_ready() && self.min.is_ready() && self.max.is_ready()
+    }
+}
+
+impl Lookback for ChandelierExit {
+    fn lookback(&self) -> usize {
+        self.atr
+            .lookback()
+            .max(self.min.lookback())
+            .max(self.max.lookback())
     }
 }
 
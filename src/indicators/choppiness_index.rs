@@ -0,0 +1,364 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::indicators::{Maximum, MaximumState, Minimum, MinimumState, TrueRange, TrueRangeState};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset,
+    SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Choppiness Index (CHOP).
+///
+/// Determines whether the market is choppy (trading sideways) or trending.
+/// Values close to 100 indicate a choppy market, values close to 0 indicate a strong trend.
+///
+/// # Formula
+///
+/// CHOP = 100 * log<sub>10</sub>(Σ(TR) / (HH - LL)) / log<sub>10</sub>(_period_)
+///
+/// Where:
+///
+/// * _TR_ - [true range](type.TrueRange.html), i.e. ATR(1), summed over the last _period_ bars
+/// * _HH_ - highest high over the last _period_ bars
+/// * _LL_ - lowest low over the last _period_ bars
+///
+/// If there was no price range at all during the period, 0 is returned.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 1). Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::ChoppinessIndex;
+/// use ta::{Next, DataItem};
+///
+/// fn bar(high: f64, low: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .high(high).low(low).close(close).open(close)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut chop = ChoppinessIndex::new(2).unwrap();
+///
+/// assert_eq!(chop.next(&bar(10.0, 8.0, 9.0)), 0.0);
+/// assert_eq!(chop.next(&bar(12.0, 10.0, 11.0)).round(), 32.0);
+/// ```
+///
+/// # Links
+///
+/// * [Choppiness Index, TradingView](https://www.tradingview.com/support/solutions/43000501980-choppiness-index-chop/)
+///
+#[doc(alias = "CHOP")]
+#[derive(Debug, Clone)]
+pub struct ChoppinessIndex {
+    period: usize,
+    index: usize,
+    count: usize,
+    sum: f64,
+    true_range: TrueRange,
+    maximum: Maximum,
+    minimum: Minimum,
+    deque: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+    saved_sum: f64,
+}
+
+/// State of [ChoppinessIndex](struct.ChoppinessIndex.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChoppinessIndexState {
+    pub period: usize,
+    /// True ranges in the window, the oldest first.
+    pub true_ranges: Vec<f64>,
+    /// Running sum of the true ranges in the window.
+    pub sum: f64,
+    pub true_range: TrueRangeState,
+    pub maximum: MaximumState,
+    pub minimum: MinimumState,
+    pub output: Option<f64>,
+    /// True range which the last input pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
+    /// Running sum before the last input, to amend it.
+    pub previous_sum: f64,
+}
+
+impl ChoppinessIndex {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 | 1 => Err(TaError::invalid_parameter("period", period as f64, ">= 2")),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                sum: 0.0,
+                true_range: TrueRange::new(),
+                maximum: Maximum::new(period)?,
+                minimum: Minimum::new(period)?,
+                deque: vec![0.0; period].into_boxed_slice(),
+                output: None,
+                saved_value: 0.0,
+                saved_count: 0,
+                saved_sum: 0.0,
+            }),
+        }
+    }
+
+    fn update(&mut self, tr: f64, highest: f64, lowest: f64) -> f64 {
+        self.sum += tr - self.deque[self.index];
+        self.deque[self.index] = tr;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        self.count = (self.count + 1).min(self.period);
+
+        let range = highest - lowest;
+        let output = if range > 0.0 {
+            100.0 * (self.sum / range).log10() / (self.period as f64).log10()
+        } else {
+            0.0
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Period for ChoppinessIndex {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for ChoppinessIndex {
+    type State = ChoppinessIndexState;
+
+    fn state(&self) -> Self::State {
+        ChoppinessIndexState {
+            period: self.period,
+            true_ranges: ring_values(&self.deque, self.index, self.count),
+            sum: self.sum,
+            true_range: self.true_range.state(),
+            maximum: self.maximum.state(),
+            minimum: self.minimum.state(),
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous_sum: self.saved_sum,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let count = state.true_ranges.len();
+        if state.maximum.period != state.period
+            || state.minimum.period != state.period
+            || state.maximum.inputs.len() != count
+            || state.minimum.inputs.len() != count
+            || state.output.is_none() != (count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        let (deque, index) = ring_from_values(state.period, &state.true_ranges)?;
+        indicator.deque = deque;
+        indicator.index = index;
+        indicator.count = count;
+        indicator.sum = state.sum;
+        indicator.true_range = TrueRange::from_state(state.true_range)?;
+        indicator.maximum = Maximum::from_state(state.maximum)?;
+        indicator.minimum = Minimum::from_state(state.minimum)?;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_sum = state.previous_sum;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(ChoppinessIndex, 2);
+
+impl SetPeriod for ChoppinessIndex {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for ChoppinessIndex {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
+        self.saved_sum = self.sum;
+        let tr = self.true_range.next(input);
+        let highest = self.maximum.next(input.high());
+        let lowest = self.minimum.next(input.low());
+        self.update(tr, highest, lowest)
+    }
+}
+
+impl<T: High + Low + Close> Peek<&T> for ChoppinessIndex {
+    fn peek(&self, input: &T) -> f64 {
+        let tr = self.true_range.peek(input);
+        let highest = self.maximum.peek(input.high());
+        let lowest = self.minimum.peek(input.low());
+        let sum = self.sum + (tr - self.deque[self.index]);
+
+        let range = highest - lowest;
+        if range > 0.0 {
+            100.0 * (sum / range).log10() / (self.period as f64).log10()
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<T: High + Low + Close> Amend<&T> for ChoppinessIndex {
+    fn amend(&mut self, input: &T) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.sum = self.saved_sum;
+        let tr = self.true_range.amend(input);
+        let highest = self.maximum.amend(input.high());
+        let lowest = self.minimum.amend(input.low());
+        self.update(tr, highest, lowest)
+    }
+}
+
+impl Reset for ChoppinessIndex {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.sum = 0.0;
+        self.true_range.reset();
+        self.maximum.reset();
+        self.minimum.reset();
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+        self.saved_sum = 0.0;
+    }
+}
+
+impl Current for ChoppinessIndex {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for ChoppinessIndex {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for ChoppinessIndex {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for ChoppinessIndex {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for ChoppinessIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CHOP({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(ChoppinessIndex::new(0).is_err());
+        assert!(ChoppinessIndex::new(1).is_err());
+        assert!(ChoppinessIndex::new(2).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut chop = ChoppinessIndex::new(2).unwrap();
+
+        let bar1 = Bar::new().high(10).low(8).close(9);
+        let bar2 = Bar::new().high(12).low(10).close(11);
+        let bar3 = Bar::new().high(12).low(9).close(10);
+        let bar4 = Bar::new().high(11).low(10).close(10.5);
+
+        // TR = 2, range = 2
+        assert_eq!(chop.next(&bar1), 0.0);
+        // TR = 3, sum = 5, range = 4
+        assert_eq!(round(chop.next(&bar2)), 32.193);
+        // TR = 3, sum = 6, range = 3
+        assert_eq!(round(chop.next(&bar3)), 100.0);
+        // TR = 1, sum = 4, range = 3
+        assert_eq!(round(chop.next(&bar4)), 41.504);
+    }
+
+    #[test]
+    fn test_next_flat_bars() {
+        let mut chop = ChoppinessIndex::new(3).unwrap();
+
+        let bar = Bar::new().high(5).low(5).close(5);
+        assert_eq!(chop.next(&bar), 0.0);
+        assert_eq!(chop.next(&bar), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut chop = ChoppinessIndex::new(2).unwrap();
+
+        let bar1 = Bar::new().high(10).low(8).close(9);
+        let bar2 = Bar::new().high(12).low(10).close(11);
+
+        chop.next(&bar1);
+        chop.next(&bar2);
+
+        chop.reset();
+        assert_eq!(chop.next(&bar1), 0.0);
+        assert_eq!(round(chop.next(&bar2)), 32.193);
+    }
+
+    #[test]
+    fn test_default() {
+        ChoppinessIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let chop = ChoppinessIndex::new(14).unwrap();
+        assert_eq!(format!("{}", chop), "CHOP(14)");
+    }
+}
@@ -1,11 +1,17 @@
-use std::fmt;
+use core::fmt;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::errors::Result;
-use crate::indicators::{MeanAbsoluteDeviation, SimpleMovingAverage};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::errors::{Result, TaError};
+use crate::indicators::{
+    MeanAbsoluteDeviation, MeanAbsoluteDeviationState, SimpleMovingAverage,
+    SimpleMovingAverageState,
+};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset,
+    SetPeriod,
+};
 
 /// Commodity Channel Index (CCI)
 ///
@@ -28,11 +34,21 @@ use crate::{Close, High, Low, Next, Period, Reset};
 /// * [Commodity Channel Index, Wikipedia](https://en.wikipedia.org/wiki/Commodity_channel_index)
 /// * [Commodity Channel Index, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:commodity_channel_index_cci)
 ///
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct CommodityChannelIndex {
     sma: SimpleMovingAverage,
     mad: MeanAbsoluteDeviation,
+    output: Option<f64>,
+}
+
+/// State of [CommodityChannelIndex](struct.CommodityChannelIndex.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommodityChannelIndexState {
+    pub sma: SimpleMovingAverageState,
+    pub mad: MeanAbsoluteDeviationState,
+    pub output: Option<f64>,
 }
 
 impl CommodityChannelIndex {
@@ -40,6 +56,7 @@ impl CommodityChannelIndex {
         Ok(Self {
             sma: SimpleMovingAverage::new(period)?,
             mad: MeanAbsoluteDeviation::new(period)?,
+            output: None,
         })
     }
 }
@@ -50,6 +67,41 @@ impl Period for CommodityChannelIndex {
     }
 }
 
+impl Checkpoint for CommodityChannelIndex {
+    type State = CommodityChannelIndexState;
+
+    fn state(&self) -> Self::State {
+        CommodityChannelIndexState {
+            sma: self.sma.state(),
+            mad: self.mad.state(),
+            output: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.sma.period != state.mad.period
+            || state.sma.inputs.len() != state.mad.inputs.len()
+            || state.output.is_none() != state.sma.inputs.is_empty()
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            sma: SimpleMovingAverage::from_state(state.sma)?,
+            mad: MeanAbsoluteDeviation::from_state(state.mad)?,
+            output: state.output,
+        })
+    }
+}
+
+impl_versioned_serde!(CommodityChannelIndex, 1);
+
+impl SetPeriod for CommodityChannelIndex {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
 impl<T: Close + High + Low> Next<&T> for CommodityChannelIndex {
     type Output = f64;
 
@@ -58,11 +110,43 @@ impl<T: Close + High + Low> Next<&T> for CommodityChannelIndex {
         let sma = self.sma.next(tp);
         let mad = self.mad.next(input);
 
+        let output = if mad == 0.0 {
+            0.0
+        } else {
+            (tp - sma) / (mad * 0.015)
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close + High + Low> Peek<&T> for CommodityChannelIndex {
+    fn peek(&self, input: &T) -> f64 {
+        let tp = (input.close() + input.high() + input.low()) / 3.0;
+        let sma = self.sma.peek(tp);
+        let mad = self.mad.peek(input);
+
         if mad == 0.0 {
-            return 0.0;
+            0.0
+        } else {
+            (tp - sma) / (mad * 0.015)
         }
+    }
+}
 
-        (tp - sma) / (mad * 0.015)
+impl<T: Close + High + Low> Amend<&T> for CommodityChannelIndex {
+    fn amend(&mut self, input: &T) -> f64 {
+        let tp = (input.close() + input.high() + input.low()) / 3.0;
+        let sma = self.sma.amend(tp);
+        let mad = self.mad.amend(input);
+
+        let output = if mad == 0.0 {
+            0.0
+        } else {
+            (tp - sma) / (mad * 0.015)
+        };
+        self.output = Some(output);
+        output
     }
 }
 
@@ -70,6 +154,27 @@ impl Reset for CommodityChannelIndex {
     fn reset(&mut self) {
         self.sma.reset();
         self.mad.reset();
+        self.output = None;
+    }
+}
+
+impl Current for CommodityChannelIndex {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for CommodityChannelIndex {
+    fn is_ready(&self) -> bool {
+        self.sma.is_ready() && self.mad.is_ready()
+    }
+}
+
+impl Lookback for CommodityChannelIndex {
+    fn lookback(&self) -> usize {
+        self.sma.lookback().max(self.mad.lookback())
     }
 }
 
@@ -0,0 +1,405 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::indicators::{RelativeStrengthIndex as Rsi, RelativeStrengthIndexState};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Connors RSI (CRSI).
+///
+/// A composite momentum oscillator developed by Larry Connors that averages three components:
+/// a short RSI of the price, an RSI of the up/down streak length and the percent rank of the
+/// latest one-period return.
+///
+/// The oscillator returns output in the range of 0..100.
+///
+/// # Formula
+///
+/// CRSI = (RSI(Close, _rsi_period_) + RSI(Streak, _streak_period_) + PercentRank(ROC(1), _rank_period_)) / 3
+///
+/// Where:
+///
+/// * _Streak_ - number of consecutive up closes (positive) or down closes (negative),
+///   0 if the close did not change
+/// * _PercentRank_ - percentage of the previous _rank_period_ one-period returns that are
+///   lower than the current one, 50 when there is no history yet
+///
+/// # Parameters
+///
+/// * _rsi_period_ - period of the price RSI (integer greater than 0). Default is 3.
+/// * _streak_period_ - period of the streak RSI (integer greater than 0). Default is 2.
+/// * _rank_period_ - look-back of the percent rank (integer greater than 0). Default is 100.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::ConnorsRsi;
+/// use ta::Next;
+///
+/// let mut crsi = ConnorsRsi::new(3, 2, 3).unwrap();
+/// assert_eq!(crsi.next(10.0), 50.0);
+/// assert_eq!(crsi.next(11.0).round(), 83.0);
+/// ```
+///
+/// # Links
+///
+/// * [Connors RSI, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:connorsrsi)
+///
+#[doc(alias = "CRSI")]
+#[derive(Debug, Clone)]
+pub struct ConnorsRsi {
+    rsi_period: usize,
+    streak_period: usize,
+    rsi: Rsi,
+    streak_rsi: Rsi,
+    streak: i64,
+    prev_close: Option<f64>,
+    index: usize,
+    count: usize,
+    returns: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+    saved_prev_close: Option<f64>,
+    saved_streak: i64,
+}
+
+/// State of [ConnorsRsi](struct.ConnorsRsi.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnorsRsiState {
+    pub rsi: RelativeStrengthIndexState,
+    /// RSI of the streak.
+    pub streak_rsi: RelativeStrengthIndexState,
+    pub rank_period: usize,
+    /// Number of rising inputs in a row, negative for falling ones.
+    pub streak: i64,
+    pub previous_close: Option<f64>,
+    /// Returns in the window of the percent rank, the oldest first.
+    pub returns: Vec<f64>,
+    pub output: Option<f64>,
+    /// Return which the last input pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
+    /// Input before the last one and streak before the last input, to amend the last one.
+    pub before_last: Option<f64>,
+    pub previous_streak: i64,
+}
+
+impl ConnorsRsi {
+    pub fn new(rsi_period: usize, streak_period: usize, rank_period: usize) -> Result<Self> {
+        if rank_period == 0 {
+            return Err(TaError::invalid_parameter("rank_period", 0.0, ">= 1"));
+        }
+
+        Ok(Self {
+            rsi_period,
+            streak_period,
+            rsi: Rsi::new(rsi_period)?,
+            streak_rsi: Rsi::new(streak_period)?,
+            streak: 0,
+            prev_close: None,
+            index: 0,
+            count: 0,
+            returns: vec![0.0; rank_period].into_boxed_slice(),
+            output: None,
+            saved_value: 0.0,
+            saved_count: 0,
+            saved_prev_close: None,
+            saved_streak: 0,
+        })
+    }
+
+    // Streak and percent rank of the return after an input, without updating them
+    fn streak_and_rank(&self, input: f64) -> (i64, f64) {
+        let prev = match self.prev_close {
+            Some(prev) => prev,
+            None => return (self.streak, 50.0),
+        };
+        let streak = if input > prev {
+            self.streak.max(0) + 1
+        } else if input < prev {
+            self.streak.min(0) - 1
+        } else {
+            0
+        };
+
+        let ret = (input - prev) / prev * 100.0;
+        let history = &self.returns[..self.count];
+        let rank = if history.is_empty() {
+            50.0
+        } else {
+            let lower = history.iter().filter(|&&r| r < ret).count();
+            lower as f64 / history.len() as f64 * 100.0
+        };
+
+        (streak, rank)
+    }
+
+    fn push_return(&mut self, ret: f64) {
+        self.returns[self.index] = ret;
+        self.index = if self.index + 1 < self.returns.len() {
+            self.index + 1
+        } else {
+            0
+        };
+        self.count = (self.count + 1).min(self.returns.len());
+    }
+
+    // Percent rank of the return, once the input is in the streak and the window
+    fn update(&mut self, input: f64) -> f64 {
+        let (streak, rank) = self.streak_and_rank(input);
+        if let Some(prev) = self.prev_close {
+            self.push_return((input - prev) / prev * 100.0);
+        }
+        self.streak = streak;
+        self.prev_close = Some(input);
+        rank
+    }
+}
+
+impl Checkpoint for ConnorsRsi {
+    type State = ConnorsRsiState;
+
+    fn state(&self) -> Self::State {
+        ConnorsRsiState {
+            rsi: self.rsi.state(),
+            streak_rsi: self.streak_rsi.state(),
+            rank_period: self.returns.len(),
+            streak: self.streak,
+            previous_close: self.prev_close,
+            returns: ring_values(&self.returns, self.index, self.count),
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count == self.returns.len())
+                .then_some(self.saved_value),
+            before_last: self.saved_prev_close,
+            previous_streak: self.saved_streak,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_none() != state.previous_close.is_none()
+            || (state.previous_close.is_none() && !state.returns.is_empty())
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.rsi.period, state.streak_rsi.period, state.rank_period)
+            .map_err(|_| TaError::InvalidState)?;
+        let (returns, index) = ring_from_values(state.rank_period, &state.returns)?;
+        indicator.rsi = Rsi::from_state(state.rsi)?;
+        indicator.streak_rsi = Rsi::from_state(state.streak_rsi)?;
+        indicator.streak = state.streak;
+        indicator.prev_close = state.previous_close;
+        indicator.returns = returns;
+        indicator.index = index;
+        indicator.count = state.returns.len();
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.returns.len() {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_prev_close = state.before_last;
+        indicator.saved_streak = state.previous_streak;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(ConnorsRsi, 2);
+
+impl Next<f64> for ConnorsRsi {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_value = self.returns[self.index];
+        self.saved_count = self.count;
+        self.saved_prev_close = self.prev_close;
+        self.saved_streak = self.streak;
+        let rank = self.update(input);
+        let rsi = self.rsi.next(input);
+        let streak_rsi = self.streak_rsi.next(self.streak as f64);
+
+        let output = (rsi + streak_rsi + rank) / 3.0;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for ConnorsRsi {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for ConnorsRsi {
+    fn peek(&self, input: f64) -> f64 {
+        let (streak, rank) = self.streak_and_rank(input);
+        let rsi = self.rsi.peek(input);
+        let streak_rsi = self.streak_rsi.peek(streak as f64);
+
+        (rsi + streak_rsi + rank) / 3.0
+    }
+}
+
+impl<T: Close> Peek<&T> for ConnorsRsi {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for ConnorsRsi {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        // The first input has no return
+        if self.saved_prev_close.is_some() {
+            let capacity = self.returns.len();
+            self.index = (self.index + capacity - 1) % capacity;
+            self.returns[self.index] = self.saved_value;
+            self.count = self.saved_count;
+        }
+        self.prev_close = self.saved_prev_close;
+        self.streak = self.saved_streak;
+        let rank = self.update(input);
+        let rsi = self.rsi.amend(input);
+        let streak_rsi = self.streak_rsi.amend(self.streak as f64);
+
+        let output = (rsi + streak_rsi + rank) / 3.0;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for ConnorsRsi {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for ConnorsRsi {
+    fn reset(&mut self) {
+        self.rsi.reset();
+        self.streak_rsi.reset();
+        self.streak = 0;
+        self.prev_close = None;
+        self.index = 0;
+        self.count = 0;
+        for i in 0..self.returns.len() {
+            self.returns[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+        self.saved_prev_close = None;
+        self.saved_streak = 0;
+    }
+}
+
+impl Current for ConnorsRsi {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for ConnorsRsi {
+    fn is_ready(&self) -> bool {
+        self.rsi.is_ready() && self.streak_rsi.is_ready() && self.count == self.returns.len()
+    }
+}
+
+impl Lookback for ConnorsRsi {
+    fn lookback(&self) -> usize {
+        // the percent rank starts with the second input
+        let rank = self.returns.len() + 1;
+        rank.max(self.rsi.lookback())
+            .max(self.streak_rsi.lookback())
+    }
+}
+
+impl Default for ConnorsRsi {
+    fn default() -> Self {
+        Self::new(3, 2, 100).unwrap()
+    }
+}
+
+impl fmt::Display for ConnorsRsi {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "CRSI({}, {}, {})",
+            self.rsi_period,
+            self.streak_period,
+            self.returns.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(ConnorsRsi);
+
+    #[test]
+    fn test_new() {
+        assert!(ConnorsRsi::new(0, 2, 100).is_err());
+        assert!(ConnorsRsi::new(3, 0, 100).is_err());
+        assert!(ConnorsRsi::new(3, 2, 0).is_err());
+        assert!(ConnorsRsi::new(1, 1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut crsi = ConnorsRsi::new(3, 2, 3).unwrap();
+
+        assert_eq!(crsi.next(10.0), 50.0);
+        assert_eq!(round(crsi.next(11.0)), 83.333);
+        assert_eq!(round(crsi.next(12.0)), 66.667);
+        assert_eq!(round(crsi.next(11.0)), 25.714);
+        assert_eq!(round(crsi.next(11.0)), 46.101);
+        assert_eq!(round(crsi.next(13.0)), 84.866);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut crsi = ConnorsRsi::new(3, 2, 3).unwrap();
+
+        assert_eq!(crsi.next(&Bar::new().close(10)), 50.0);
+        assert_eq!(round(crsi.next(&Bar::new().close(11))), 83.333);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut crsi = ConnorsRsi::new(3, 2, 3).unwrap();
+
+        crsi.next(10.0);
+        crsi.next(11.0);
+        crsi.next(12.0);
+
+        crsi.reset();
+        assert_eq!(crsi.next(10.0), 50.0);
+        assert_eq!(round(crsi.next(11.0)), 83.333);
+    }
+
+    #[test]
+    fn test_default() {
+        ConnorsRsi::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let crsi = ConnorsRsi::new(3, 2, 100).unwrap();
+        assert_eq!(format!("{}", crsi), "CRSI(3, 2, 100)");
+    }
+}
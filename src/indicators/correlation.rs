@@ -0,0 +1,413 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling Pearson correlation coefficient (CORR).
+///
+/// Measures the linear relationship between two series over the last _period_ values.
+/// The indicator returns output in the range of -1..1.
+///
+/// Unlike most indicators, it consumes pairs of values: `(f64, f64)` or `(&T, &U)`, where
+/// both `T` and `U` implement [Close](crate::Close).
+///
+/// # Formula
+///
+/// CORR = (n * Σxy - Σx * Σy) / √((n * Σx<sup>2</sup> - (Σx)<sup>2</sup>) * (n * Σy<sup>2</sup> - (Σy)<sup>2</sup>))
+///
+/// Where:
+///
+/// * _x_, _y_ - values of the first and the second series
+/// * _n_ - number of pairs in the window, which is less than _period_ during the first bars
+///
+/// When one of the series did not change within the window, the correlation is undefined and 0 is returned.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::Correlation;
+/// use ta::Next;
+///
+/// let mut corr = Correlation::new(3).unwrap();
+///
+/// corr.next((1.0, 2.0));
+/// assert_eq!(corr.next((2.0, 4.0)), 1.0);
+/// corr.next((3.0, 5.0));
+/// corr.next((4.0, 4.0));
+/// assert_eq!((corr.next((5.0, 1.0)) * 1000.0).round(), -961.0);
+/// ```
+///
+/// # Links
+///
+/// * [Pearson correlation coefficient, Wikipedia](https://en.wikipedia.org/wiki/Pearson_correlation_coefficient)
+///
+#[doc(alias = "CORR")]
+#[derive(Debug, Clone)]
+pub struct Correlation {
+    period: usize,
+    index: usize,
+    count: usize,
+    sum_x: f64,
+    sum_y: f64,
+    sum_xy: f64,
+    sum_xx: f64,
+    sum_yy: f64,
+    xs: Box<[f64]>,
+    ys: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: (f64, f64),
+    saved_count: usize,
+    saved_sums: [f64; 5],
+}
+
+/// State of [Correlation](struct.Correlation.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorrelationState {
+    pub period: usize,
+    /// First inputs of the pairs in the window, the oldest first.
+    pub xs: Vec<f64>,
+    /// Second inputs of the pairs in the window, the oldest first.
+    pub ys: Vec<f64>,
+    pub sum_x: f64,
+    pub sum_y: f64,
+    pub sum_xy: f64,
+    pub sum_xx: f64,
+    pub sum_yy: f64,
+    pub output: Option<f64>,
+    /// Pair which the last input pushed out of the window, to amend it.
+    pub evicted: Option<(f64, f64)>,
+    /// Running sums before the last input, to amend it.
+    pub previous_sums: [f64; 5],
+}
+
+impl Correlation {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                sum_x: 0.0,
+                sum_y: 0.0,
+                sum_xy: 0.0,
+                sum_xx: 0.0,
+                sum_yy: 0.0,
+                xs: vec![0.0; period].into_boxed_slice(),
+                ys: vec![0.0; period].into_boxed_slice(),
+                output: None,
+                saved_value: (0.0, 0.0),
+                saved_count: 0,
+                saved_sums: [0.0; 5],
+            }),
+        }
+    }
+
+    // Σx, Σy, Σxy, Σx² and Σy² of the window after the pair entered it. The old values are
+    // zeros until the window is full.
+    fn sums_with(&self, (x, y): (f64, f64)) -> [f64; 5] {
+        let old_x = self.xs[self.index];
+        let old_y = self.ys[self.index];
+        [
+            self.sum_x + (x - old_x),
+            self.sum_y + (y - old_y),
+            self.sum_xy + (x * y - old_x * old_y),
+            self.sum_xx + (x * x - old_x * old_x),
+            self.sum_yy + (y * y - old_y * old_y),
+        ]
+    }
+
+    fn correlation(count: usize, [sum_x, sum_y, sum_xy, sum_xx, sum_yy]: [f64; 5]) -> f64 {
+        let n = count as f64;
+        let variance_x = n * sum_xx - sum_x * sum_x;
+        let variance_y = n * sum_yy - sum_y * sum_y;
+
+        if variance_x > 0.0 && variance_y > 0.0 {
+            let covariance = n * sum_xy - sum_x * sum_y;
+            (covariance / (variance_x * variance_y).sqrt()).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Period for Correlation {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for Correlation {
+    type State = CorrelationState;
+
+    fn state(&self) -> Self::State {
+        CorrelationState {
+            period: self.period,
+            xs: ring_values(&self.xs, self.index, self.count),
+            ys: ring_values(&self.ys, self.index, self.count),
+            sum_x: self.sum_x,
+            sum_y: self.sum_y,
+            sum_xy: self.sum_xy,
+            sum_xx: self.sum_xx,
+            sum_yy: self.sum_yy,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous_sums: self.saved_sums,
+            output: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.xs.is_empty() || state.ys.len() != state.xs.len() {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let (xs, index) = ring_from_values(state.period, &state.xs)?;
+        let (ys, _) = ring_from_values(state.period, &state.ys)?;
+        indicator.xs = xs;
+        indicator.ys = ys;
+        indicator.index = index;
+        indicator.count = state.xs.len();
+        indicator.sum_x = state.sum_x;
+        indicator.sum_y = state.sum_y;
+        indicator.sum_xy = state.sum_xy;
+        indicator.sum_xx = state.sum_xx;
+        indicator.sum_yy = state.sum_yy;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or((0.0, 0.0));
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_sums = state.previous_sums;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(Correlation, 2);
+
+impl SetPeriod for Correlation {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<(f64, f64)> for Correlation {
+    type Output = f64;
+
+    fn next(&mut self, (x, y): (f64, f64)) -> Self::Output {
+        self.saved_value = (self.xs[self.index], self.ys[self.index]);
+        self.saved_count = self.count;
+        self.saved_sums = [
+            self.sum_x,
+            self.sum_y,
+            self.sum_xy,
+            self.sum_xx,
+            self.sum_yy,
+        ];
+        let sums = self.sums_with((x, y));
+        [
+            self.sum_x,
+            self.sum_y,
+            self.sum_xy,
+            self.sum_xx,
+            self.sum_yy,
+        ] = sums;
+        self.xs[self.index] = x;
+        self.ys[self.index] = y;
+
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < self.period {
+            self.count += 1;
+        }
+
+        let output = Self::correlation(self.count, sums);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close, U: Close> Next<(&T, &U)> for Correlation {
+    type Output = f64;
+
+    fn next(&mut self, (x, y): (&T, &U)) -> Self::Output {
+        self.next((x.close(), y.close()))
+    }
+}
+
+impl Peek<(f64, f64)> for Correlation {
+    fn peek(&self, input: (f64, f64)) -> f64 {
+        let count = (self.count + 1).min(self.period);
+        Self::correlation(count, self.sums_with(input))
+    }
+}
+
+impl<T: Close, U: Close> Peek<(&T, &U)> for Correlation {
+    fn peek(&self, (x, y): (&T, &U)) -> f64 {
+        self.peek((x.close(), y.close()))
+    }
+}
+
+impl Amend<(f64, f64)> for Correlation {
+    fn amend(&mut self, input: (f64, f64)) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        (self.xs[self.index], self.ys[self.index]) = self.saved_value;
+        self.count = self.saved_count;
+        [
+            self.sum_x,
+            self.sum_y,
+            self.sum_xy,
+            self.sum_xx,
+            self.sum_yy,
+        ] = self.saved_sums;
+        self.next(input)
+    }
+}
+
+impl<T: Close, U: Close> Amend<(&T, &U)> for Correlation {
+    fn amend(&mut self, (a, b): (&T, &U)) -> f64 {
+        self.amend((a.close(), b.close()))
+    }
+}
+
+impl Reset for Correlation {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.sum_x = 0.0;
+        self.sum_y = 0.0;
+        self.sum_xy = 0.0;
+        self.sum_xx = 0.0;
+        self.sum_yy = 0.0;
+        for i in 0..self.period {
+            self.xs[i] = 0.0;
+            self.ys[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = (0.0, 0.0);
+        self.saved_count = 0;
+        self.saved_sums = [0.0; 5];
+    }
+}
+
+impl Current for Correlation {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for Correlation {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for Correlation {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for Correlation {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for Correlation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CORR({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(Correlation::new(0).is_err());
+        assert!(Correlation::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut corr = Correlation::new(3).unwrap();
+
+        assert_eq!(corr.next((1.0, 2.0)), 0.0);
+        assert_eq!(round(corr.next((2.0, 4.0))), 1.0);
+        assert_eq!(round(corr.next((3.0, 5.0))), 0.982);
+        assert_eq!(round(corr.next((4.0, 4.0))), 0.0);
+        assert_eq!(round(corr.next((5.0, 1.0))), -0.961);
+    }
+
+    #[test]
+    fn test_next_constant_series() {
+        let mut corr = Correlation::new(3).unwrap();
+
+        corr.next((1.0, 5.0));
+        corr.next((2.0, 5.0));
+        assert_eq!(corr.next((3.0, 5.0)), 0.0);
+    }
+
+    #[test]
+    fn test_next_bars() {
+        let mut corr = Correlation::new(3).unwrap();
+
+        let a1 = Bar::new().close(1);
+        let a2 = Bar::new().close(2);
+        let b1 = Bar::new().close(3);
+        let b2 = Bar::new().close(1);
+
+        assert_eq!(corr.next((&a1, &b1)), 0.0);
+        assert_eq!(round(corr.next((&a2, &b2))), -1.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut corr = Correlation::new(3).unwrap();
+
+        corr.next((1.0, 2.0));
+        corr.next((2.0, 4.0));
+
+        corr.reset();
+        assert_eq!(corr.next((2.0, 1.0)), 0.0);
+        assert_eq!(round(corr.next((1.0, 2.0))), -1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        Correlation::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let corr = Correlation::new(20).unwrap();
+        assert_eq!(format!("{}", corr), "CORR(20)");
+    }
+}
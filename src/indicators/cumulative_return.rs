@@ -0,0 +1,319 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Cumulative return, an equity index.
+///
+/// Compounds a stream of per-bar returns into a running equity index which starts at _base_.
+/// When fed with `f64`, the input is a simple return (0.01 means +1%), e.g. the output of
+/// [SimpleReturn](struct.SimpleReturn.html). When fed with bars, the close-to-close returns
+/// are used, so the index follows the close price scaled to start at _base_.
+///
+/// # Formula
+///
+/// E<sub>t</sub> = E<sub>t-1</sub> * (1 + r<sub>t</sub>)
+///
+/// Where:
+///
+/// * _E<sub>t</sub>_ - equity index, E<sub>0</sub> = _base_
+/// * _r<sub>t</sub>_ - return of the current bar
+///
+/// For bars r<sub>t</sub> = Close<sub>t</sub> / Close<sub>t-1</sub> - 1. The return of the first bar,
+/// and of bars following a zero close, is 0.
+///
+/// The highest value of the index is available with [peak](#method.peak).
+///
+/// # Parameters
+///
+/// * _base_ - starting value of the index (must be greater than 0). Default is 100.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::CumulativeReturn;
+/// use ta::Next;
+///
+/// let mut equity = CumulativeReturn::new(100.0).unwrap();
+/// assert_eq!(equity.next(0.25), 125.0);
+/// assert_eq!(equity.next(-0.5), 62.5);
+/// assert_eq!(equity.peak(), 125.0);
+/// ```
+///
+/// # Links
+///
+/// * [Rate of return, Wikipedia](https://en.wikipedia.org/wiki/Rate_of_return)
+///
+#[derive(Debug, Clone)]
+pub struct CumulativeReturn {
+    base: f64,
+    equity: f64,
+    peak: f64,
+    prev_close: Option<f64>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_equity: f64,
+    saved_peak: f64,
+    saved_prev_close: Option<f64>,
+}
+
+/// State of [CumulativeReturn](struct.CumulativeReturn.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CumulativeReturnState {
+    pub base: f64,
+    pub equity: f64,
+    pub peak: f64,
+    /// Previous close, for bars.
+    pub previous_close: Option<f64>,
+    pub output: Option<f64>,
+    /// Equity and peak before the last input, to amend it.
+    pub previous_equity: f64,
+    pub previous_peak: f64,
+    /// Close of the input before the last one, to amend the last one.
+    pub close_before_last: Option<f64>,
+}
+
+impl CumulativeReturn {
+    pub fn new(base: f64) -> Result<Self> {
+        if !base.is_finite() || base <= 0.0 {
+            return Err(TaError::invalid_parameter("base", base, "> 0"));
+        }
+        Ok(Self {
+            base,
+            equity: base,
+            peak: base,
+            prev_close: None,
+            output: None,
+            saved_equity: base,
+            saved_peak: base,
+            saved_prev_close: None,
+        })
+    }
+
+    pub fn base(&self) -> f64 {
+        self.base
+    }
+
+    /// Highest value of the index so far, starting at _base_.
+    pub fn peak(&self) -> f64 {
+        self.peak
+    }
+}
+
+impl Checkpoint for CumulativeReturn {
+    type State = CumulativeReturnState;
+
+    fn state(&self) -> Self::State {
+        CumulativeReturnState {
+            base: self.base,
+            equity: self.equity,
+            peak: self.peak,
+            previous_close: self.prev_close,
+            output: self.output,
+            previous_equity: self.saved_equity,
+            previous_peak: self.saved_peak,
+            close_before_last: self.saved_prev_close,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(state.base).map_err(|_| TaError::InvalidState)?;
+        indicator.equity = state.equity;
+        indicator.peak = state.peak;
+        indicator.prev_close = state.previous_close;
+        indicator.output = state.output;
+        indicator.saved_equity = state.previous_equity;
+        indicator.saved_peak = state.previous_peak;
+        indicator.saved_prev_close = state.close_before_last;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(CumulativeReturn, 2);
+
+impl Next<f64> for CumulativeReturn {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_equity = self.equity;
+        self.saved_peak = self.peak;
+        self.equity *= 1.0 + input;
+        self.peak = self.peak.max(self.equity);
+        let output = self.equity;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for CumulativeReturn {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_prev_close = self.prev_close;
+        let close = input.close();
+        let ret = match self.prev_close {
+            Some(prev_close) if prev_close != 0.0 => close / prev_close - 1.0,
+            _ => 0.0,
+        };
+        self.prev_close = Some(close);
+        self.next(ret)
+    }
+}
+
+impl Peek<f64> for CumulativeReturn {
+    fn peek(&self, input: f64) -> f64 {
+        self.equity * (1.0 + input)
+    }
+}
+
+impl<T: Close> Peek<&T> for CumulativeReturn {
+    fn peek(&self, input: &T) -> f64 {
+        let close = input.close();
+        let ret = match self.prev_close {
+            Some(prev_close) if prev_close != 0.0 => close / prev_close - 1.0,
+            _ => 0.0,
+        };
+        self.peek(ret)
+    }
+}
+
+impl Amend<f64> for CumulativeReturn {
+    fn amend(&mut self, input: f64) -> f64 {
+        self.equity = self.saved_equity;
+        self.peak = self.saved_peak;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for CumulativeReturn {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.prev_close = self.saved_prev_close;
+        self.equity = self.saved_equity;
+        self.peak = self.saved_peak;
+        self.next(input)
+    }
+}
+
+impl Reset for CumulativeReturn {
+    fn reset(&mut self) {
+        self.equity = self.base;
+        self.peak = self.base;
+        self.prev_close = None;
+        self.output = None;
+        self.saved_equity = self.base;
+        self.saved_peak = self.base;
+        self.saved_prev_close = None;
+    }
+}
+
+impl Current for CumulativeReturn {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for CumulativeReturn {
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+impl Lookback for CumulativeReturn {
+    fn lookback(&self) -> usize {
+        1
+    }
+}
+
+impl Default for CumulativeReturn {
+    fn default() -> Self {
+        Self::new(100.0).unwrap()
+    }
+}
+
+impl fmt::Display for CumulativeReturn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CUMRET({})", self.base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(CumulativeReturn);
+
+    #[test]
+    fn test_new() {
+        assert!(CumulativeReturn::new(0.0).is_err());
+        assert!(CumulativeReturn::new(-1.0).is_err());
+        assert!(CumulativeReturn::new(f64::NAN).is_err());
+        assert!(CumulativeReturn::new(1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut equity = CumulativeReturn::new(100.0).unwrap();
+
+        assert_eq!(round(equity.next(0.1)), 110.0);
+        assert_eq!(round(equity.next(-0.5)), 55.0);
+        assert_eq!(round(equity.next(0.2)), 66.0);
+        assert_eq!(round(equity.next(1.0)), 132.0);
+        assert_eq!(round(equity.peak()), 132.0);
+    }
+
+    #[test]
+    fn test_peak() {
+        let mut equity = CumulativeReturn::new(100.0).unwrap();
+        assert_eq!(equity.peak(), 100.0);
+
+        equity.next(-0.1);
+        assert_eq!(equity.peak(), 100.0);
+
+        equity.next(0.5);
+        assert_eq!(round(equity.peak()), 135.0);
+
+        equity.next(-0.2);
+        assert_eq!(round(equity.peak()), 135.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut equity = CumulativeReturn::new(100.0).unwrap();
+
+        assert_eq!(equity.next(&Bar::new().close(20)), 100.0);
+        assert_eq!(round(equity.next(&Bar::new().close(25))), 125.0);
+        assert_eq!(round(equity.next(&Bar::new().close(0))), 0.0);
+        assert_eq!(round(equity.next(&Bar::new().close(10))), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut equity = CumulativeReturn::new(100.0).unwrap();
+
+        equity.next(0.5);
+        equity.next(&Bar::new().close(10));
+
+        equity.reset();
+        assert_eq!(equity.peak(), 100.0);
+        assert_eq!(equity.next(&Bar::new().close(20)), 100.0);
+        assert_eq!(round(equity.next(0.1)), 110.0);
+    }
+
+    #[test]
+    fn test_default() {
+        CumulativeReturn::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let equity = CumulativeReturn::new(1000.0).unwrap();
+        assert_eq!(format!("{}", equity), "CUMRET(1000)");
+    }
+}
@@ -0,0 +1,297 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::Delay;
+use crate::indicators::{SimpleMovingAverage as Sma, SimpleMovingAverageState};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Detrended Price Oscillator (DPO).
+///
+/// Removes the trend from prices by comparing the current price with a displaced simple
+/// moving average, which makes it easier to identify cycles.
+///
+/// # Formula
+///
+/// DPO<sub>t</sub> = Close<sub>t</sub> - SMA<sub>t - shift</sub>
+///
+/// Where:
+///
+/// * _SMA_ - simple moving average of _period_
+/// * _shift_ = _period_ / 2 + 1
+///
+/// Until _shift_ bars have been seen, the oldest available SMA value is used.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::DetrendedPriceOscillator;
+/// use ta::Next;
+///
+/// let mut dpo = DetrendedPriceOscillator::new(2).unwrap();
+/// assert_eq!(dpo.next(1.0), 0.0);
+/// assert_eq!(dpo.next(2.0), 1.0);
+/// assert_eq!(dpo.next(3.0), 2.0);
+/// assert_eq!(dpo.next(4.0), 2.5);
+/// ```
+///
+/// # Links
+///
+/// * [Detrended Price Oscillator, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:detrended_price_osci)
+///
+#[doc(alias = "DPO")]
+#[derive(Debug, Clone)]
+pub struct DetrendedPriceOscillator {
+    period: usize,
+    sma: Sma,
+    delay: Delay,
+    count: usize,
+    output: Option<f64>,
+}
+
+/// State of [DetrendedPriceOscillator](struct.DetrendedPriceOscillator.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetrendedPriceOscillatorState {
+    pub sma: SimpleMovingAverageState,
+    /// Last averages, the oldest first.
+    pub averages: Vec<f64>,
+    /// Number of inputs, up to the lookback.
+    pub count: usize,
+    pub output: Option<f64>,
+    /// Average which the last input pushed out of the averages, to amend it.
+    pub evicted: Option<f64>,
+}
+
+impl DetrendedPriceOscillator {
+    pub fn new(period: usize) -> Result<Self> {
+        let shift = period / 2 + 1;
+        Ok(Self {
+            period,
+            sma: Sma::new(period)?,
+            delay: Delay::new(shift),
+            count: 0,
+            output: None,
+        })
+    }
+}
+
+impl Period for DetrendedPriceOscillator {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for DetrendedPriceOscillator {
+    type State = DetrendedPriceOscillatorState;
+
+    fn state(&self) -> Self::State {
+        DetrendedPriceOscillatorState {
+            sma: self.sma.state(),
+            averages: self.delay.values(),
+            count: self.count,
+            output: self.output,
+            evicted: self.delay.evicted(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let period = state.sma.period;
+        let indicator = Self {
+            period,
+            sma: Sma::from_state(state.sma)?,
+            delay: Delay::from_values(period / 2 + 1, &state.averages, state.evicted)?,
+            count: state.count,
+            output: state.output,
+        };
+        if indicator.count > indicator.lookback()
+            || indicator.output.is_none() != (indicator.count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(DetrendedPriceOscillator, 2);
+
+impl SetPeriod for DetrendedPriceOscillator {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for DetrendedPriceOscillator {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if !self.is_ready() {
+            self.count += 1;
+        }
+        let sma = self.sma.next(input);
+        let output = input - self.delay.next(sma);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for DetrendedPriceOscillator {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for DetrendedPriceOscillator {
+    fn peek(&self, input: f64) -> f64 {
+        input - self.delay.peek(self.sma.peek(input))
+    }
+}
+
+impl<T: Close> Peek<&T> for DetrendedPriceOscillator {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for DetrendedPriceOscillator {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let sma = self.sma.amend(input);
+        let output = input - self.delay.amend(sma);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for DetrendedPriceOscillator {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for DetrendedPriceOscillator {
+    fn reset(&mut self) {
+        self.sma.reset();
+        self.delay.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl Current for DetrendedPriceOscillator {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for DetrendedPriceOscillator {
+    fn is_ready(&self) -> bool {
+        self.count >= self.lookback()
+    }
+}
+
+impl Lookback for DetrendedPriceOscillator {
+    fn lookback(&self) -> usize {
+        self.sma.lookback() + self.delay.shift()
+    }
+}
+
+impl Default for DetrendedPriceOscillator {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for DetrendedPriceOscillator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DPO({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    type Dpo = DetrendedPriceOscillator;
+
+    test_indicator!(Dpo);
+
+    #[test]
+    fn test_new() {
+        assert!(Dpo::new(0).is_err());
+        assert!(Dpo::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut dpo = Dpo::new(2).unwrap();
+
+        assert_eq!(dpo.next(1.0), 0.0);
+        assert_eq!(dpo.next(2.0), 1.0);
+        assert_eq!(dpo.next(3.0), 2.0);
+        assert_eq!(dpo.next(4.0), 2.5);
+        assert_eq!(dpo.next(5.0), 2.5);
+        assert_eq!(dpo.next(1.0), -2.5);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut dpo = Dpo::new(2).unwrap();
+
+        assert_eq!(dpo.next(&Bar::new().close(1)), 0.0);
+        assert_eq!(dpo.next(&Bar::new().close(2)), 1.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut dpo = Dpo::new(2).unwrap();
+
+        dpo.next(1.0);
+        dpo.next(2.0);
+        dpo.next(3.0);
+
+        dpo.reset();
+        assert_eq!(dpo.next(1.0), 0.0);
+        assert_eq!(dpo.next(2.0), 1.0);
+        assert_eq!(dpo.next(3.0), 2.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        // SMA(4) and a shift of 3
+        let mut dpo = Dpo::new(4).unwrap();
+        for _ in 0..6 {
+            dpo.next(1.0);
+            assert!(!dpo.is_ready());
+        }
+        dpo.next(1.0);
+        assert!(dpo.is_ready());
+    }
+
+    #[test]
+    fn test_default() {
+        Dpo::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let dpo = Dpo::new(20).unwrap();
+        assert_eq!(format!("{}", dpo), "DPO(20)");
+    }
+}
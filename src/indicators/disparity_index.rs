@@ -0,0 +1,254 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{MovingAverage, MovingAverageState, MovingAverageType};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Disparity Index.
+///
+/// Percentage distance of the price from its moving average. It oscillates around 0: large
+/// positive values show that price is overextended above its average, large negative values
+/// that it is overextended below it.
+///
+/// # Formula
+///
+/// Disparity = 100 × (Price - MA) / MA
+///
+/// Where:
+///
+/// * _MA_ - [moving average](enum.MovingAverage.html) of type _kind_ and length _period_
+///
+/// The disparity is 0 when the moving average is 0.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 14.
+/// * _kind_ - type of the moving average. Default is `Simple`.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{DisparityIndex, MovingAverageType};
+/// use ta::Next;
+///
+/// let mut disparity = DisparityIndex::new(2, MovingAverageType::Simple).unwrap();
+/// assert_eq!(disparity.next(10.0), 0.0);
+/// assert_eq!(disparity.next(15.0), 20.0);
+/// assert_eq!(disparity.next(11.0), -15.384615384615385);
+/// ```
+#[doc(alias = "DISPARITY")]
+#[derive(Debug, Clone)]
+pub struct DisparityIndex {
+    ma: MovingAverage,
+    output: Option<f64>,
+}
+
+/// State of [DisparityIndex](struct.DisparityIndex.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisparityIndexState {
+    pub ma: MovingAverageState,
+    pub output: Option<f64>,
+}
+
+impl DisparityIndex {
+    pub fn new(period: usize, kind: MovingAverageType) -> Result<Self> {
+        Ok(Self {
+            ma: MovingAverage::new(kind, period)?,
+            output: None,
+        })
+    }
+}
+
+impl Period for DisparityIndex {
+    fn period(&self) -> usize {
+        self.ma.period()
+    }
+}
+
+impl Checkpoint for DisparityIndex {
+    type State = DisparityIndexState;
+
+    fn state(&self) -> Self::State {
+        DisparityIndexState {
+            ma: self.ma.state(),
+            output: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let ma = MovingAverage::from_state(state.ma)?;
+        if state.output.is_some() != ma.current().is_some() {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            ma,
+            output: state.output,
+        })
+    }
+}
+
+impl_versioned_serde!(DisparityIndex, 1);
+
+impl SetPeriod for DisparityIndex {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period, self.ma.kind())?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for DisparityIndex {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let ma = self.ma.next(input);
+        let output = if ma == 0.0 {
+            0.0
+        } else {
+            100.0 * (input - ma) / ma
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for DisparityIndex {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for DisparityIndex {
+    fn peek(&self, input: f64) -> f64 {
+        let ma = self.ma.peek(input);
+        if ma == 0.0 {
+            0.0
+        } else {
+            100.0 * (input - ma) / ma
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for DisparityIndex {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for DisparityIndex {
+    fn amend(&mut self, input: f64) -> f64 {
+        let ma = self.ma.amend(input);
+        let output = if ma == 0.0 {
+            0.0
+        } else {
+            100.0 * (input - ma) / ma
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for DisparityIndex {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for DisparityIndex {
+    fn reset(&mut self) {
+        self.ma.reset();
+        self.output = None;
+    }
+}
+
+impl Current for DisparityIndex {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for DisparityIndex {
+    fn is_ready(&self) -> bool {
+        self.ma.is_ready()
+    }
+}
+
+impl Lookback for DisparityIndex {
+    fn lookback(&self) -> usize {
+        self.ma.lookback()
+    }
+}
+
+impl Default for DisparityIndex {
+    fn default() -> Self {
+        Self::new(14, MovingAverageType::Simple).unwrap()
+    }
+}
+
+impl fmt::Display for DisparityIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DISPARITY({})", self.ma)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(DisparityIndex);
+
+    #[test]
+    fn test_new() {
+        assert!(DisparityIndex::new(0, MovingAverageType::Simple).is_err());
+        assert!(DisparityIndex::new(1, MovingAverageType::Exponential).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut disparity = DisparityIndex::new(3, MovingAverageType::Exponential).unwrap();
+
+        assert_eq!(disparity.next(10.0), 0.0);
+        // EMA is 11
+        assert_eq!(round(disparity.next(12.0)), 9.091);
+        // EMA is 10
+        assert_eq!(disparity.next(&Bar::new().close(9)), -10.0);
+    }
+
+    #[test]
+    fn test_zero_average() {
+        let mut disparity = DisparityIndex::new(2, MovingAverageType::Simple).unwrap();
+        assert_eq!(disparity.next(0.0), 0.0);
+        assert_eq!(disparity.next(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut disparity = DisparityIndex::new(2, MovingAverageType::Simple).unwrap();
+
+        disparity.next(10.0);
+        disparity.next(15.0);
+        disparity.reset();
+        assert_eq!(disparity.next(20.0), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        DisparityIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let disparity = DisparityIndex::new(10, MovingAverageType::Weighted).unwrap();
+        assert_eq!(format!("{}", disparity), "DISPARITY(WMA(10))");
+    }
+}
@@ -0,0 +1,605 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::indicators::{SwingPoint, SwingPoints, SwingPointsState};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, OutputValue, Peek, Reset,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Kind of divergence reported by [DivergenceDetector](struct.DivergenceDetector.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// Price makes a new extreme which the oscillator does not confirm. Hints at a reversal.
+    Regular,
+    /// The oscillator makes a new extreme which the price does not confirm. Hints at a
+    /// continuation of the trend.
+    Hidden,
+}
+
+impl fmt::Display for DivergenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DivergenceKind::Regular => write!(f, "regular"),
+            DivergenceKind::Hidden => write!(f, "hidden"),
+        }
+    }
+}
+
+impl From<DivergenceKind> for OutputValue {
+    fn from(kind: DivergenceKind) -> Self {
+        OutputValue::Label(kind.to_string())
+    }
+}
+
+/// Divergence between price and an oscillator.
+///
+/// Finds [swing lows and swing highs](struct.SwingPoints.html) of the price and compares each of them with the previous swing of the same type. When price and the wrapped
+/// oscillator (e.g. [RSI](type.RelativeStrengthIndex.html)) move in opposite directions between
+/// the two swings, a divergence is reported.
+///
+/// The oscillator is fed with the close price. A swing is confirmed _strength_ bars after it
+/// happened, so the output refers to the bar _bars_ago_ bars before the current one, which is
+/// always equal to _strength_.
+///
+/// # Formula
+///
+/// Comparing swing lows:
+///
+/// * Regular bullish: lower low of price, higher low of the oscillator
+/// * Hidden bullish: higher low of price, lower low of the oscillator
+///
+/// Comparing swing highs:
+///
+/// * Regular bearish: higher high of price, lower high of the oscillator
+/// * Hidden bearish: lower high of price, higher high of the oscillator
+///
+/// Swings more than _max_lookback_ bars apart are not compared.
+///
+/// # Parameters
+///
+/// * _oscillator_ - indicator which consumes `f64` and outputs `f64`
+/// * _strength_ - number of bars on each side of a swing (integer greater than 0). Default is 5.
+/// * _max_lookback_ - maximal number of bars between two compared swings (integer greater than 0).
+///   Default is 60.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{DivergenceDetector, RelativeStrengthIndex};
+/// use ta::Next;
+///
+/// let rsi = RelativeStrengthIndex::new(14).unwrap();
+/// let mut divergence = DivergenceDetector::new(rsi, 5, 60).unwrap();
+///
+/// let out = divergence.next(10.0);
+/// assert_eq!(out.bullish, None);
+/// assert_eq!(out.bearish, None);
+/// assert_eq!(out.bars_ago, 5);
+/// ```
+///
+/// # Links
+///
+/// * [Divergence, Investopedia](https://www.investopedia.com/terms/d/divergence.asp)
+///
+#[doc(alias = "DIV")]
+#[derive(Debug, Clone)]
+pub struct DivergenceDetector<I> {
+    oscillator: I,
+    strength: usize,
+    max_lookback: usize,
+    swings: SwingPoints,
+    index: usize,
+    oscillator_values: Box<[f64]>,
+    last_low: Option<Swing>,
+    last_high: Option<Swing>,
+    output: Option<DivergenceDetectorOutput>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_low: Option<Swing>,
+    saved_high: Option<Swing>,
+}
+
+/// State of [DivergenceDetector](struct.DivergenceDetector.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DivergenceDetectorState<S> {
+    pub oscillator: S,
+    pub max_lookback: usize,
+    pub swings: SwingPointsState,
+    /// Values of the oscillator for the last _strength_ + 1 bars, the oldest first.
+    pub oscillator_values: Vec<f64>,
+    /// Price, oscillator value and index of the last swing low.
+    pub last_low: Option<(f64, f64, usize)>,
+    /// Price, oscillator value and index of the last swing high.
+    pub last_high: Option<(f64, f64, usize)>,
+    pub output: Option<DivergenceDetectorOutput>,
+    /// Oscillator value which the last input pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
+    /// Last swing low before the last input.
+    pub previous_low: Option<(f64, f64, usize)>,
+    /// Last swing high before the last input.
+    pub previous_high: Option<(f64, f64, usize)>,
+}
+
+#[derive(Debug, Clone)]
+struct Swing {
+    price: f64,
+    oscillator: f64,
+    index: usize,
+}
+
+/// Output of [DivergenceDetector](struct.DivergenceDetector.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DivergenceDetectorOutput {
+    /// Current value of the oscillator.
+    pub oscillator: f64,
+    /// Divergence between swing lows, if one was confirmed.
+    pub bullish: Option<DivergenceKind>,
+    /// Divergence between swing highs, if one was confirmed.
+    pub bearish: Option<DivergenceKind>,
+    /// Number of bars between the swing bar and the current bar.
+    pub bars_ago: usize,
+}
+
+impl_output!(
+    DivergenceDetectorOutput,
+    oscillator,
+    bullish,
+    bearish,
+    bars_ago
+);
+
+impl<I> DivergenceDetector<I> {
+    pub fn new(oscillator: I, strength: usize, max_lookback: usize) -> Result<Self> {
+        if max_lookback == 0 {
+            return Err(TaError::invalid_parameter("max_lookback", 0.0, ">= 1"));
+        }
+        Ok(Self {
+            oscillator,
+            strength,
+            max_lookback,
+            swings: SwingPoints::new(strength, strength)?,
+            index: 0,
+            oscillator_values: vec![0.0; strength + 1].into_boxed_slice(),
+            last_low: None,
+            last_high: None,
+            output: None,
+            saved_value: 0.0,
+            saved_low: None,
+            saved_high: None,
+        })
+    }
+
+    fn update(&mut self, high: f64, low: f64, oscillator: f64) -> DivergenceDetectorOutput {
+        let (output, swing_low, swing_high) = self.detect(high, low, oscillator);
+
+        self.saved_value = self.oscillator_values[self.index];
+        self.saved_low.clone_from(&self.last_low);
+        self.saved_high.clone_from(&self.last_high);
+        self.oscillator_values[self.index] = oscillator;
+        self.index = if self.index + 1 < self.oscillator_values.len() {
+            self.index + 1
+        } else {
+            0
+        };
+        self.swings.update(high, low);
+        if swing_low.is_some() {
+            self.last_low = swing_low;
+        }
+        if swing_high.is_some() {
+            self.last_high = swing_high;
+        }
+
+        self.output = Some(output.clone());
+        output
+    }
+
+    // Removes the last bar, which must have been added by `update`
+    fn undo(&mut self) {
+        let size = self.oscillator_values.len();
+        self.index = (self.index + size - 1) % size;
+        self.oscillator_values[self.index] = self.saved_value;
+        self.swings.undo();
+        self.last_low.clone_from(&self.saved_low);
+        self.last_high.clone_from(&self.saved_high);
+    }
+
+    // Output for a new bar and the swing low and swing high it confirms, without adding the bar
+    fn detect(
+        &self,
+        high: f64,
+        low: f64,
+        oscillator: f64,
+    ) -> (DivergenceDetectorOutput, Option<Swing>, Option<Swing>) {
+        let swings = self.swings.detect(high, low);
+        // the new value replaces the one at `self.index`, the value `strength` bars ago is the
+        // one after it
+        let swing_oscillator =
+            self.oscillator_values[(self.index + 1) % self.oscillator_values.len()];
+        let swing = |point: SwingPoint| Swing {
+            price: point.price,
+            oscillator: swing_oscillator,
+            index: point.index,
+        };
+        let swing_low = swings.low.map(swing);
+        let swing_high = swings.high.map(swing);
+
+        let bullish = swing_low.as_ref().and_then(|swing| {
+            self.previous(&self.last_low, swing).and_then(|prev| {
+                if swing.price < prev.price && swing.oscillator > prev.oscillator {
+                    Some(DivergenceKind::Regular)
+                } else if swing.price > prev.price && swing.oscillator < prev.oscillator {
+                    Some(DivergenceKind::Hidden)
+                } else {
+                    None
+                }
+            })
+        });
+
+        let bearish = swing_high.as_ref().and_then(|swing| {
+            self.previous(&self.last_high, swing).and_then(|prev| {
+                if swing.price > prev.price && swing.oscillator < prev.oscillator {
+                    Some(DivergenceKind::Regular)
+                } else if swing.price < prev.price && swing.oscillator > prev.oscillator {
+                    Some(DivergenceKind::Hidden)
+                } else {
+                    None
+                }
+            })
+        });
+
+        let output = DivergenceDetectorOutput {
+            oscillator,
+            bullish,
+            bearish,
+            bars_ago: self.strength,
+        };
+        (output, swing_low, swing_high)
+    }
+
+    fn previous<'a>(&self, last: &'a Option<Swing>, swing: &Swing) -> Option<&'a Swing> {
+        last.as_ref()
+            .filter(|prev| swing.index - prev.index <= self.max_lookback)
+    }
+}
+
+impl<I: Checkpoint> Checkpoint for DivergenceDetector<I> {
+    type State = DivergenceDetectorState<I::State>;
+
+    fn state(&self) -> Self::State {
+        let swings = self.swings.state();
+        let size = self.oscillator_values.len();
+        let count = swings.bar.min(size);
+        let evicted = (swings.bar > size).then_some(self.saved_value);
+        let swing = |swing: &Swing| (swing.price, swing.oscillator, swing.index);
+        DivergenceDetectorState {
+            oscillator: self.oscillator.state(),
+            max_lookback: self.max_lookback,
+            swings,
+            oscillator_values: ring_values(&self.oscillator_values, self.index, count),
+            last_low: self.last_low.as_ref().map(swing),
+            last_high: self.last_high.as_ref().map(swing),
+            output: self.output.clone(),
+            evicted,
+            previous_low: self.saved_low.as_ref().map(swing),
+            previous_high: self.saved_high.as_ref().map(swing),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let strength = state.swings.left;
+        let mut indicator = Self::new(
+            I::from_state(state.oscillator)?,
+            strength,
+            state.max_lookback,
+        )
+        .map_err(|_| TaError::InvalidState)?;
+        let size = indicator.oscillator_values.len();
+        let bar = state.swings.bar;
+        if state.swings.right != strength
+            || state.oscillator_values.len() != bar.min(size)
+            || state.output.is_none() != (bar == 0)
+            || state.evicted.is_some() != (bar > size)
+            || [
+                state.last_low,
+                state.last_high,
+                state.previous_low,
+                state.previous_high,
+            ]
+            .iter()
+            .flatten()
+            .any(|&(_, _, index)| index + strength >= bar)
+        {
+            return Err(TaError::InvalidState);
+        }
+        let (oscillator_values, index) = ring_from_values(size, &state.oscillator_values)?;
+        let swing = |(price, oscillator, index)| Swing {
+            price,
+            oscillator,
+            index,
+        };
+        indicator.swings = SwingPoints::from_state(state.swings)?;
+        indicator.index = index;
+        indicator.oscillator_values = oscillator_values;
+        indicator.last_low = state.last_low.map(swing);
+        indicator.last_high = state.last_high.map(swing);
+        indicator.output = state.output;
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_low = state.previous_low.map(swing);
+        indicator.saved_high = state.previous_high.map(swing);
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(DivergenceDetector<I>, 2);
+
+impl<I: Next<f64, Output = f64>> Next<f64> for DivergenceDetector<I> {
+    type Output = DivergenceDetectorOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let oscillator = self.oscillator.next(input);
+        self.update(input, input, oscillator)
+    }
+}
+
+impl<I: Next<f64, Output = f64>, T: High + Low + Close> Next<&T> for DivergenceDetector<I> {
+    type Output = DivergenceDetectorOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let oscillator = self.oscillator.next(input.close());
+        self.update(input.high(), input.low(), oscillator)
+    }
+}
+
+impl<I: Amend<f64, Output = f64>> Amend<f64> for DivergenceDetector<I> {
+    fn amend(&mut self, input: f64) -> DivergenceDetectorOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let oscillator = self.oscillator.amend(input);
+        self.undo();
+        self.update(input, input, oscillator)
+    }
+}
+
+impl<I: Amend<f64, Output = f64>, T: High + Low + Close> Amend<&T> for DivergenceDetector<I> {
+    fn amend(&mut self, input: &T) -> DivergenceDetectorOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let oscillator = self.oscillator.amend(input.close());
+        self.undo();
+        self.update(input.high(), input.low(), oscillator)
+    }
+}
+
+impl<I: Peek<f64, Output = f64>> Peek<f64> for DivergenceDetector<I> {
+    fn peek(&self, input: f64) -> DivergenceDetectorOutput {
+        let oscillator = self.oscillator.peek(input);
+        self.detect(input, input, oscillator).0
+    }
+}
+
+impl<I: Peek<f64, Output = f64>, T: High + Low + Close> Peek<&T> for DivergenceDetector<I> {
+    fn peek(&self, input: &T) -> DivergenceDetectorOutput {
+        let oscillator = self.oscillator.peek(input.close());
+        self.detect(input.high(), input.low(), oscillator).0
+    }
+}
+
+impl<I: Reset> Reset for DivergenceDetector<I> {
+    fn reset(&mut self) {
+        self.oscillator.reset();
+        self.swings.reset();
+        self.index = 0;
+        for i in 0..self.oscillator_values.len() {
+            self.oscillator_values[i] = 0.0;
+        }
+        self.last_low = None;
+        self.last_high = None;
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_low = None;
+        self.saved_high = None;
+    }
+}
+
+impl<I> Current for DivergenceDetector<I> {
+    type Output = DivergenceDetectorOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl<I: IsReady> IsReady for DivergenceDetector<I> {
+    fn is_ready(&self) -> bool {
+        self.oscillator.is_ready() && self.swings.is_ready()
+    }
+}
+
+impl<I: Lookback> Lookback for DivergenceDetector<I> {
+    fn lookback(&self) -> usize {
+        self.oscillator.lookback().max(self.swings.lookback())
+    }
+}
+
+impl<I: Default> Default for DivergenceDetector<I> {
+    fn default() -> Self {
+        Self::new(I::default(), 5, 60).unwrap()
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for DivergenceDetector<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "DIV({}, {}, {})",
+            self.oscillator, self.strength, self.max_lookback
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::RelativeStrengthIndex as Rsi;
+    use crate::test_helper::*;
+
+    /// Oscillator which replays predefined values.
+    #[derive(Debug, Clone, Default)]
+    struct Replay {
+        values: Vec<f64>,
+        index: usize,
+    }
+
+    impl Replay {
+        fn new(values: &[f64]) -> Self {
+            Self {
+                values: values.to_vec(),
+                index: 0,
+            }
+        }
+    }
+
+    impl Next<f64> for Replay {
+        type Output = f64;
+
+        fn next(&mut self, _input: f64) -> f64 {
+            self.index += 1;
+            self.values[self.index - 1]
+        }
+    }
+
+    impl Reset for Replay {
+        fn reset(&mut self) {
+            self.index = 0;
+        }
+    }
+
+    fn signals(
+        detector: &mut DivergenceDetector<Replay>,
+        prices: &[f64],
+    ) -> Vec<(Option<DivergenceKind>, Option<DivergenceKind>)> {
+        prices
+            .iter()
+            .map(|&price| {
+                let out = detector.next(price);
+                (out.bullish, out.bearish)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(DivergenceDetector::new(Rsi::default(), 0, 60).is_err());
+        assert!(DivergenceDetector::new(Rsi::default(), 1, 0).is_err());
+        assert!(DivergenceDetector::new(Rsi::default(), 1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next_regular_bullish() {
+        let oscillator = Replay::new(&[50.0, 30.0, 40.0, 35.0, 45.0]);
+        let mut detector = DivergenceDetector::new(oscillator, 1, 60).unwrap();
+
+        let out = signals(&mut detector, &[10.0, 8.0, 9.0, 7.0, 8.0]);
+        assert_eq!(out[4], (Some(DivergenceKind::Regular), None));
+        assert!(out[..4].iter().all(|s| *s == (None, None)));
+    }
+
+    #[test]
+    fn test_next_hidden_bullish() {
+        let oscillator = Replay::new(&[50.0, 30.0, 40.0, 25.0, 45.0]);
+        let mut detector = DivergenceDetector::new(oscillator, 1, 60).unwrap();
+
+        let out = signals(&mut detector, &[10.0, 8.0, 10.0, 9.0, 11.0]);
+        assert_eq!(out[4], (Some(DivergenceKind::Hidden), None));
+    }
+
+    #[test]
+    fn test_next_regular_bearish() {
+        let oscillator = Replay::new(&[50.0, 70.0, 60.0, 65.0, 55.0]);
+        let mut detector = DivergenceDetector::new(oscillator, 1, 60).unwrap();
+
+        let out = signals(&mut detector, &[10.0, 12.0, 11.0, 13.0, 12.0]);
+        assert_eq!(out[4], (None, Some(DivergenceKind::Regular)));
+    }
+
+    #[test]
+    fn test_next_hidden_bearish() {
+        let oscillator = Replay::new(&[50.0, 70.0, 60.0, 75.0, 55.0]);
+        let mut detector = DivergenceDetector::new(oscillator, 1, 60).unwrap();
+
+        let out = signals(&mut detector, &[10.0, 12.0, 10.0, 11.0, 9.0]);
+        assert_eq!(out[4], (None, Some(DivergenceKind::Hidden)));
+    }
+
+    #[test]
+    fn test_next_confirmed_trend() {
+        let oscillator = Replay::new(&[50.0, 30.0, 40.0, 25.0, 45.0]);
+        let mut detector = DivergenceDetector::new(oscillator, 1, 60).unwrap();
+
+        // lower low of both price and oscillator
+        let out = signals(&mut detector, &[10.0, 8.0, 9.0, 7.0, 8.0]);
+        assert_eq!(out[4], (None, None));
+    }
+
+    #[test]
+    fn test_next_max_lookback() {
+        let oscillator = Replay::new(&[50.0, 30.0, 40.0, 35.0, 45.0]);
+        let mut detector = DivergenceDetector::new(oscillator, 1, 1).unwrap();
+
+        // the swing lows are 2 bars apart
+        let out = signals(&mut detector, &[10.0, 8.0, 9.0, 7.0, 8.0]);
+        assert_eq!(out[4], (None, None));
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let oscillator = Replay::new(&[50.0, 30.0, 40.0, 35.0, 45.0]);
+        let mut detector = DivergenceDetector::new(oscillator, 1, 60).unwrap();
+
+        let bars = [
+            Bar::new().high(11).low(10).close(10.5),
+            Bar::new().high(10).low(8).close(9),
+            Bar::new().high(10).low(9).close(9.5),
+            Bar::new().high(9).low(7).close(8),
+            Bar::new().high(9).low(8).close(8.5),
+        ];
+        let out: Vec<_> = bars.iter().map(|bar| detector.next(bar)).collect();
+
+        assert_eq!(out[4].bullish, Some(DivergenceKind::Regular));
+        assert_eq!(out[4].oscillator, 45.0);
+        assert_eq!(out[4].bars_ago, 1);
+    }
+
+    #[test]
+    fn test_reset() {
+        let oscillator = Replay::new(&[50.0, 30.0, 40.0, 35.0, 45.0]);
+        let mut detector = DivergenceDetector::new(oscillator, 1, 60).unwrap();
+
+        signals(&mut detector, &[10.0, 8.0, 9.0]);
+        detector.reset();
+
+        let out = signals(&mut detector, &[10.0, 8.0, 9.0, 7.0, 8.0]);
+        assert_eq!(out[2], (None, None));
+        assert_eq!(out[4], (Some(DivergenceKind::Regular), None));
+    }
+
+    #[test]
+    fn test_default() {
+        DivergenceDetector::<Rsi>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let detector = DivergenceDetector::new(Rsi::new(14).unwrap(), 5, 60).unwrap();
+        assert_eq!(format!("{}", detector), "DIV(RSI(14), 5, 60)");
+    }
+}
@@ -0,0 +1,265 @@
+use core::fmt;
+
+use super::hilbert_transform::{HilbertTransform, HilbertTransformState};
+use crate::errors::{Result, TaError};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Hilbert Transform Dominant Cycle Period (DCPERIOD).
+///
+/// Developed by John Ehlers, it estimates the length in bars of the dominant market cycle.
+/// The result can be used to tune the period of other indicators to the current market.
+///
+/// # Formula
+///
+/// The price is smoothed, detrended and split into the in-phase (I) and quadrature (Q)
+/// components with the Hilbert transform. The phase rotation between two bars is measured with
+/// the homodyne discriminator:
+///
+/// Re = I<sub>t</sub> * I<sub>t-1</sub> + Q<sub>t</sub> * Q<sub>t-1</sub>
+///
+/// Im = I<sub>t</sub> * Q<sub>t-1</sub> - Q<sub>t</sub> * I<sub>t-1</sub>
+///
+/// Period = 360 / arctan(Im / Re)
+///
+/// The period is limited to 6..50 bars and to a change of at most 50% per bar, and is then smoothed twice.
+///
+/// During the first 5 bars there is not enough data and 0 is returned. The estimate starts
+/// from 0 as well, so it needs a few dozen bars to settle.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::DominantCyclePeriod;
+/// use ta::Next;
+/// use std::f64::consts::PI;
+///
+/// let mut dc = DominantCyclePeriod::new();
+/// let mut period = 0.0;
+///
+/// // sine wave with a cycle of 20 bars
+/// for i in 0..200 {
+///     period = dc.next(100.0 + 10.0 * (2.0 * PI * i as f64 / 20.0).sin());
+/// }
+///
+/// assert_eq!(period.round(), 20.0);
+/// ```
+///
+/// # Links
+///
+/// * [MESA Adaptive Moving Averages, John Ehlers (PDF)](https://www.mesasoftware.com/papers/MAMA.pdf)
+///
+#[doc(alias = "DCPERIOD")]
+#[doc(alias = "HT_DCPERIOD")]
+#[derive(Debug, Clone)]
+pub struct DominantCyclePeriod {
+    hilbert: HilbertTransform,
+    output: Option<f64>,
+}
+
+/// State of [DominantCyclePeriod](struct.DominantCyclePeriod.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DominantCyclePeriodState {
+    pub hilbert: HilbertTransformState,
+    pub output: Option<f64>,
+}
+
+impl DominantCyclePeriod {
+    pub fn new() -> Self {
+        Self {
+            hilbert: HilbertTransform::new(),
+            output: None,
+        }
+    }
+}
+
+impl Checkpoint for DominantCyclePeriod {
+    type State = DominantCyclePeriodState;
+
+    fn state(&self) -> Self::State {
+        DominantCyclePeriodState {
+            hilbert: self.hilbert.state(),
+            output: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_none() != (state.hilbert.count == 0) {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            hilbert: HilbertTransform::from_state(state.hilbert)?,
+            output: state.output,
+        })
+    }
+}
+
+impl_versioned_serde!(DominantCyclePeriod, 2);
+
+impl Next<f64> for DominantCyclePeriod {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let output = match self.hilbert.next(input) {
+            Some(ht) => ht.smooth_period,
+            None => 0.0,
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for DominantCyclePeriod {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for DominantCyclePeriod {
+    fn peek(&self, input: f64) -> f64 {
+        match self.hilbert.peek(input) {
+            Some(ht) => ht.smooth_period,
+            None => 0.0,
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for DominantCyclePeriod {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for DominantCyclePeriod {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let output = match self.hilbert.amend(input) {
+            Some(ht) => ht.smooth_period,
+            None => 0.0,
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for DominantCyclePeriod {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for DominantCyclePeriod {
+    fn reset(&mut self) {
+        self.hilbert.reset();
+        self.output = None;
+    }
+}
+
+impl Current for DominantCyclePeriod {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for DominantCyclePeriod {
+    fn is_ready(&self) -> bool {
+        self.hilbert.is_ready()
+    }
+}
+
+impl Lookback for DominantCyclePeriod {
+    fn lookback(&self) -> usize {
+        self.hilbert.lookback()
+    }
+}
+
+impl Default for DominantCyclePeriod {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for DominantCyclePeriod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DCPERIOD")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use std::f64::consts::PI;
+
+    test_indicator!(DominantCyclePeriod);
+
+    #[test]
+    fn test_next() {
+        let mut dc = DominantCyclePeriod::new();
+
+        let inputs = [
+            10.0, 12.0, 11.0, 14.0, 13.0, 9.0, 8.0, 12.0, 15.0, 14.0, 11.0, 10.0, 13.0, 16.0,
+        ];
+        let expected = [
+            0.0, 0.0, 0.0, 0.0, 0.0, 0.396, 0.978, 1.622, 2.255, 2.842, 3.369, 3.868, 4.363, 4.872,
+        ];
+
+        for (input, expected) in inputs.iter().zip(expected.iter()) {
+            assert_eq!(round(dc.next(*input)), *expected);
+        }
+    }
+
+    #[test]
+    fn test_next_sine_wave() {
+        let mut dc = DominantCyclePeriod::new();
+        let mut period = 0.0;
+
+        for i in 0..300 {
+            period = dc.next(100.0 + 10.0 * (2.0 * PI * i as f64 / 30.0).sin());
+        }
+
+        assert_eq!(period.round(), 30.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut dc = DominantCyclePeriod::new();
+
+        for x in &[10, 12, 11, 14, 13] {
+            assert_eq!(dc.next(&Bar::new().close(*x)), 0.0);
+        }
+        assert_eq!(round(dc.next(&Bar::new().close(9))), 0.396);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut dc = DominantCyclePeriod::new();
+
+        for x in &[10.0, 12.0, 11.0, 14.0, 13.0, 9.0, 8.0] {
+            dc.next(*x);
+        }
+
+        dc.reset();
+        assert_eq!(dc.next(9.0), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        DominantCyclePeriod::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let dc = DominantCyclePeriod::new();
+        assert_eq!(format!("{}", dc), "DCPERIOD");
+    }
+}
@@ -0,0 +1,304 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{Maximum, MaximumState, Minimum, MinimumState};
+use crate::{
+    Amend, Checkpoint, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Donchian Channel (DC).
+///
+/// A channel formed by the highest high and the lowest low of the last _period_ bars.
+/// The middle line is the average of the two.
+///
+/// # Formula
+///
+/// * _DC<sub>Upper</sub>_ = highest high over _period_
+/// * _DC<sub>Lower</sub>_ = lowest low over _period_
+/// * _DC<sub>Middle</sub>_ = (_DC<sub>Upper</sub>_ + _DC<sub>Lower</sub>_) / 2
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::DonchianChannel;
+/// use ta::Next;
+///
+/// let mut dc = DonchianChannel::new(3).unwrap();
+///
+/// dc.next(2.0);
+/// dc.next(5.0);
+/// let out = dc.next(1.0);
+///
+/// assert_eq!(out.upper, 5.0);
+/// assert_eq!(out.middle, 3.0);
+/// assert_eq!(out.lower, 1.0);
+/// ```
+///
+/// # Links
+///
+/// * [Donchian channel, Wikipedia](https://en.wikipedia.org/wiki/Donchian_channel)
+///
+#[doc(alias = "DC")]
+#[derive(Debug, Clone)]
+pub struct DonchianChannel {
+    period: usize,
+    maximum: Maximum,
+    minimum: Minimum,
+    output: Option<DonchianChannelOutput>,
+}
+
+/// State of [DonchianChannel](struct.DonchianChannel.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DonchianChannelState {
+    pub maximum: MaximumState,
+    pub minimum: MinimumState,
+}
+
+/// Output of [DonchianChannel](struct.DonchianChannel.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DonchianChannelOutput {
+    pub upper: f64,
+    pub middle: f64,
+    pub lower: f64,
+}
+
+impl_output!(DonchianChannelOutput, upper, middle, lower);
+
+impl DonchianChannel {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            period,
+            maximum: Maximum::new(period)?,
+            minimum: Minimum::new(period)?,
+            output: None,
+        })
+    }
+
+    fn output(upper: f64, lower: f64) -> DonchianChannelOutput {
+        DonchianChannelOutput {
+            upper,
+            middle: (upper + lower) / 2.0,
+            lower,
+        }
+    }
+}
+
+impl Period for DonchianChannel {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for DonchianChannel {
+    type State = DonchianChannelState;
+
+    fn state(&self) -> Self::State {
+        DonchianChannelState {
+            maximum: self.maximum.state(),
+            minimum: self.minimum.state(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.maximum.period != state.minimum.period
+            || state.maximum.inputs.len() != state.minimum.inputs.len()
+        {
+            return Err(TaError::InvalidState);
+        }
+        let maximum = Maximum::from_state(state.maximum)?;
+        let minimum = Minimum::from_state(state.minimum)?;
+        Ok(Self {
+            period: maximum.period(),
+            output: maximum
+                .current()
+                .zip(minimum.current())
+                .map(|(upper, lower)| Self::output(upper, lower)),
+            maximum,
+            minimum,
+        })
+    }
+}
+
+impl_versioned_serde!(DonchianChannel, 1);
+
+impl SetPeriod for DonchianChannel {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for DonchianChannel {
+    type Output = DonchianChannelOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let upper = self.maximum.next(input);
+        let lower = self.minimum.next(input);
+
+        let output = Self::output(upper, lower);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low> Next<&T> for DonchianChannel {
+    type Output = DonchianChannelOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let upper = self.maximum.next(input.high());
+        let lower = self.minimum.next(input.low());
+
+        let output = Self::output(upper, lower);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Peek<f64> for DonchianChannel {
+    fn peek(&self, input: f64) -> DonchianChannelOutput {
+        Self::output(self.maximum.peek(input), self.minimum.peek(input))
+    }
+}
+
+impl<T: High + Low> Peek<&T> for DonchianChannel {
+    fn peek(&self, input: &T) -> DonchianChannelOutput {
+        Self::output(
+            self.maximum.peek(input.high()),
+            self.minimum.peek(input.low()),
+        )
+    }
+}
+
+impl Amend<f64> for DonchianChannel {
+    fn amend(&mut self, input: f64) -> DonchianChannelOutput {
+        let upper = self.maximum.amend(input);
+        let lower = self.minimum.amend(input);
+        let output = Self::output(upper, lower);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low> Amend<&T> for DonchianChannel {
+    fn amend(&mut self, input: &T) -> DonchianChannelOutput {
+        let upper = self.maximum.amend(input.high());
+        let lower = self.minimum.amend(input.low());
+        let output = Self::output(upper, lower);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Reset for DonchianChannel {
+    fn reset(&mut self) {
+        self.maximum.reset();
+        self.minimum.reset();
+        self.output = None;
+    }
+}
+
+impl Current for DonchianChannel {
+    type Output = DonchianChannelOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for DonchianChannel {
+    fn is_ready(&self) -> bool {
+        self.maximum.is_ready() && self.minimum.is_ready()
+    }
+}
+
+impl Lookback for DonchianChannel {
+    fn lookback(&self) -> usize {
+        self.maximum.lookback().max(self.minimum.lookback())
+    }
+}
+
+impl Default for DonchianChannel {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for DonchianChannel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DC({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(DonchianChannel);
+
+    #[test]
+    fn test_new() {
+        assert!(DonchianChannel::new(0).is_err());
+        assert!(DonchianChannel::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut dc = DonchianChannel::new(3).unwrap();
+
+        let a = dc.next(2.0);
+        let b = dc.next(5.0);
+        let c = dc.next(1.0);
+        let d = dc.next(3.0);
+
+        assert_eq!((a.upper, a.middle, a.lower), (2.0, 2.0, 2.0));
+        assert_eq!((b.upper, b.middle, b.lower), (5.0, 3.5, 2.0));
+        assert_eq!((c.upper, c.middle, c.lower), (5.0, 3.0, 1.0));
+        assert_eq!((d.upper, d.middle, d.lower), (5.0, 3.0, 1.0));
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut dc = DonchianChannel::new(2).unwrap();
+
+        let a = dc.next(&Bar::new().high(10).low(8));
+        let b = dc.next(&Bar::new().high(12).low(9));
+        let c = dc.next(&Bar::new().high(11).low(7));
+
+        assert_eq!((a.upper, a.middle, a.lower), (10.0, 9.0, 8.0));
+        assert_eq!((b.upper, b.middle, b.lower), (12.0, 10.0, 8.0));
+        assert_eq!((c.upper, c.middle, c.lower), (12.0, 9.5, 7.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut dc = DonchianChannel::new(5).unwrap();
+
+        dc.next(2.0);
+        dc.next(5.0);
+
+        dc.reset();
+        let out = dc.next(3.0);
+        assert_eq!((out.upper, out.middle, out.lower), (3.0, 3.0, 3.0));
+    }
+
+    #[test]
+    fn test_default() {
+        DonchianChannel::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let dc = DonchianChannel::new(20).unwrap();
+        assert_eq!(format!("{}", dc), "DC(20)");
+    }
+}
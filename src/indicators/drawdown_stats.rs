@@ -0,0 +1,510 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Drawdown statistics and rolling Calmar ratio.
+///
+/// Tracks the running peak of an equity curve (or price series), the current and the maximum
+/// drawdown, how long the series has been below its peak, and the Calmar ratio over the last
+/// _period_ bars. Drawdowns are fractions of the peak, e.g. 0.25 means 25% below the peak.
+///
+/// # Formula
+///
+/// Drawdown = 1 - V<sub>t</sub> / Peak<sub>t</sub>
+///
+/// Calmar = ((V<sub>t</sub> / V<sub>t-n+1</sub>)<sup>_periods_per_year_ / (n - 1)</sup> - 1) / MDD<sub>n</sub>
+///
+/// Where:
+///
+/// * _V<sub>t</sub>_ - current value
+/// * _Peak<sub>t</sub>_ - highest value seen so far
+/// * _n_ - number of values in the window, at most _period_
+/// * _MDD<sub>n</sub>_ - maximum drawdown inside the window
+///
+/// Calmar is 0 while there are less than 2 values in the window, or when the window has no drawdown.
+/// Peak, drawdown, maximum drawdown and durations are tracked over the whole series
+/// until [reset](crate::Reset).
+///
+/// # Parameters
+///
+/// * _period_ - number of values used for the Calmar ratio (integer greater than 1).
+///   Default is 756 (3 years of daily bars).
+/// * _periods_per_year_ - number of bars in a year (must be greater than 0). Default is 252.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::DrawdownStats;
+/// use ta::Next;
+///
+/// let mut dd = DrawdownStats::new(4, 4.0).unwrap();
+///
+/// dd.next(100.0);
+/// dd.next(110.0);
+/// let out = dd.next(99.0);
+///
+/// assert_eq!(out.peak, 110.0);
+/// assert_eq!((out.drawdown * 100.0).round(), 10.0);
+/// assert_eq!(out.duration, 1);
+/// ```
+///
+/// # Links
+///
+/// * [Drawdown (economics), Wikipedia](https://en.wikipedia.org/wiki/Drawdown_(economics))
+/// * [Calmar ratio, Wikipedia](https://en.wikipedia.org/wiki/Calmar_ratio)
+///
+#[doc(alias = "Calmar")]
+#[doc(alias = "MDD")]
+#[derive(Debug, Clone)]
+pub struct DrawdownStats {
+    period: usize,
+    periods_per_year: f64,
+    index: usize,
+    count: usize,
+    peak: Option<f64>,
+    max_drawdown: f64,
+    duration: usize,
+    max_duration: usize,
+    deque: Box<[f64]>,
+    output: Option<DrawdownStatsOutput>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+    saved_peak: Option<f64>,
+    saved_max_drawdown: f64,
+    saved_duration: usize,
+    saved_max_duration: usize,
+}
+
+/// State of [DrawdownStats](struct.DrawdownStats.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawdownStatsState {
+    pub period: usize,
+    pub periods_per_year: f64,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<f64>,
+    /// Highest input so far.
+    pub peak: Option<f64>,
+    pub max_drawdown: f64,
+    /// Number of inputs since the peak.
+    pub duration: usize,
+    pub max_duration: usize,
+    pub output: Option<DrawdownStatsOutput>,
+    /// Input which the last one pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
+    /// Peak and drawdown statistics before the last input, to amend it.
+    pub previous_peak: Option<f64>,
+    pub previous_max_drawdown: f64,
+    pub previous_duration: usize,
+    pub previous_max_duration: usize,
+}
+
+impl DrawdownStats {
+    pub fn new(period: usize, periods_per_year: f64) -> Result<Self> {
+        if period < 2 {
+            return Err(TaError::invalid_parameter("period", period as f64, ">= 2"));
+        }
+        if !periods_per_year.is_finite() || periods_per_year <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "periods_per_year",
+                periods_per_year,
+                "> 0",
+            ));
+        }
+        Ok(Self {
+            period,
+            periods_per_year,
+            index: 0,
+            count: 0,
+            peak: None,
+            max_drawdown: 0.0,
+            duration: 0,
+            max_duration: 0,
+            deque: vec![0.0; period].into_boxed_slice(),
+            output: None,
+            saved_value: 0.0,
+            saved_count: 0,
+            saved_peak: None,
+            saved_max_drawdown: 0.0,
+            saved_duration: 0,
+            saved_max_duration: 0,
+        })
+    }
+
+    /// Inputs in the window, the oldest first.
+    fn inputs(&self) -> impl Iterator<Item = f64> + Clone + '_ {
+        let oldest = self.index + self.period - self.count;
+        (0..self.count).map(move |i| self.deque[(oldest + i) % self.period])
+    }
+
+    // Calmar ratio over a window of `count` inputs, the oldest first
+    fn calmar(&self, inputs: impl Iterator<Item = f64> + Clone, count: usize) -> f64 {
+        if count < 2 {
+            return 0.0;
+        }
+
+        let first = inputs.clone().next().unwrap_or_default();
+        let mut last = first;
+        let mut peak = first;
+        let mut max_drawdown = 0.0;
+        for input in inputs {
+            last = input;
+            peak = f64::max(peak, last);
+            max_drawdown = f64::max(max_drawdown, drawdown(last, peak));
+        }
+
+        if max_drawdown <= 0.0 || first <= 0.0 {
+            return 0.0;
+        }
+
+        let years = (count - 1) as f64 / self.periods_per_year;
+        let annual_return = (last / first).powf(1.0 / years) - 1.0;
+        annual_return / max_drawdown
+    }
+}
+
+fn drawdown(value: f64, peak: f64) -> f64 {
+    if peak > 0.0 {
+        1.0 - value / peak
+    } else {
+        0.0
+    }
+}
+
+/// Output of [DrawdownStats](struct.DrawdownStats.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawdownStatsOutput {
+    /// Highest value seen so far.
+    pub peak: f64,
+    /// Current drawdown from the peak.
+    pub drawdown: f64,
+    /// Largest drawdown seen so far.
+    pub max_drawdown: f64,
+    /// Number of bars since the peak, 0 at a new peak.
+    pub duration: usize,
+    /// Longest drawdown duration seen so far.
+    pub max_duration: usize,
+    /// Calmar ratio over the last _period_ bars.
+    pub calmar: f64,
+}
+
+impl_output!(
+    DrawdownStatsOutput,
+    peak,
+    drawdown,
+    max_drawdown,
+    duration,
+    max_duration,
+    calmar
+);
+
+impl Period for DrawdownStats {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for DrawdownStats {
+    type State = DrawdownStatsState;
+
+    fn state(&self) -> Self::State {
+        DrawdownStatsState {
+            period: self.period,
+            periods_per_year: self.periods_per_year,
+            inputs: ring_values(&self.deque, self.index, self.count),
+            peak: self.peak,
+            max_drawdown: self.max_drawdown,
+            duration: self.duration,
+            max_duration: self.max_duration,
+            output: self.output.clone(),
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous_peak: self.saved_peak,
+            previous_max_drawdown: self.saved_max_drawdown,
+            previous_duration: self.saved_duration,
+            previous_max_duration: self.saved_max_duration,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.peak.is_none() != state.inputs.is_empty()
+            || state.output.is_none() != state.inputs.is_empty()
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator =
+            Self::new(state.period, state.periods_per_year).map_err(|_| TaError::InvalidState)?;
+        let (deque, index) = ring_from_values(state.period, &state.inputs)?;
+        indicator.deque = deque;
+        indicator.index = index;
+        indicator.count = state.inputs.len();
+        indicator.peak = state.peak;
+        indicator.max_drawdown = state.max_drawdown;
+        indicator.duration = state.duration;
+        indicator.max_duration = state.max_duration;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_peak = state.previous_peak;
+        indicator.saved_max_drawdown = state.previous_max_drawdown;
+        indicator.saved_duration = state.previous_duration;
+        indicator.saved_max_duration = state.previous_max_duration;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(DrawdownStats, 2);
+
+impl SetPeriod for DrawdownStats {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period, self.periods_per_year)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for DrawdownStats {
+    type Output = DrawdownStatsOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
+        self.saved_peak = self.peak;
+        self.saved_max_drawdown = self.max_drawdown;
+        self.saved_duration = self.duration;
+        self.saved_max_duration = self.max_duration;
+        let peak = match self.peak {
+            Some(peak) if input < peak => {
+                self.duration += 1;
+                peak
+            }
+            _ => {
+                self.duration = 0;
+                input
+            }
+        };
+        self.peak = Some(peak);
+        self.max_duration = self.max_duration.max(self.duration);
+
+        let drawdown = drawdown(input, peak);
+        self.max_drawdown = self.max_drawdown.max(drawdown);
+
+        self.deque[self.index] = input;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < self.period {
+            self.count += 1;
+        }
+
+        let output = DrawdownStatsOutput {
+            peak,
+            drawdown,
+            max_drawdown: self.max_drawdown,
+            duration: self.duration,
+            max_duration: self.max_duration,
+            calmar: self.calmar(self.inputs(), self.count),
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for DrawdownStats {
+    type Output = DrawdownStatsOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for DrawdownStats {
+    fn peek(&self, input: f64) -> DrawdownStatsOutput {
+        let (peak, duration) = match self.peak {
+            Some(peak) if input < peak => (peak, self.duration + 1),
+            _ => (input, 0),
+        };
+        let drawdown = drawdown(input, peak);
+
+        let count = (self.count + 1).min(self.period);
+        let inputs = self.inputs().skip(self.count + 1 - count);
+
+        DrawdownStatsOutput {
+            peak,
+            drawdown,
+            max_drawdown: self.max_drawdown.max(drawdown),
+            duration,
+            max_duration: self.max_duration.max(duration),
+            calmar: self.calmar(inputs.chain(core::iter::once(input)), count),
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for DrawdownStats {
+    fn peek(&self, input: &T) -> DrawdownStatsOutput {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for DrawdownStats {
+    fn amend(&mut self, input: f64) -> DrawdownStatsOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.peak = self.saved_peak;
+        self.max_drawdown = self.saved_max_drawdown;
+        self.duration = self.saved_duration;
+        self.max_duration = self.saved_max_duration;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for DrawdownStats {
+    fn amend(&mut self, input: &T) -> DrawdownStatsOutput {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for DrawdownStats {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.peak = None;
+        self.max_drawdown = 0.0;
+        self.duration = 0;
+        self.max_duration = 0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+        self.saved_peak = None;
+        self.saved_max_drawdown = 0.0;
+        self.saved_duration = 0;
+        self.saved_max_duration = 0;
+    }
+}
+
+impl Current for DrawdownStats {
+    type Output = DrawdownStatsOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for DrawdownStats {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for DrawdownStats {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for DrawdownStats {
+    fn default() -> Self {
+        Self::new(756, 252.0).unwrap()
+    }
+}
+
+impl fmt::Display for DrawdownStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DD({}, {})", self.period, self.periods_per_year)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(DrawdownStats);
+
+    fn summary(out: DrawdownStatsOutput) -> (f64, f64, f64, usize, usize, f64) {
+        let round4 = |x: f64| (x * 10000.0).round() / 10000.0;
+        (
+            out.peak,
+            round4(out.drawdown),
+            round4(out.max_drawdown),
+            out.duration,
+            out.max_duration,
+            round4(out.calmar),
+        )
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(DrawdownStats::new(0, 252.0).is_err());
+        assert!(DrawdownStats::new(1, 252.0).is_err());
+        assert!(DrawdownStats::new(2, 0.0).is_err());
+        assert!(DrawdownStats::new(2, f64::NAN).is_err());
+        assert!(DrawdownStats::new(2, 252.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut dd = DrawdownStats::new(4, 4.0).unwrap();
+
+        assert_eq!(summary(dd.next(100.0)), (100.0, 0.0, 0.0, 0, 0, 0.0));
+        assert_eq!(summary(dd.next(110.0)), (110.0, 0.0, 0.0, 0, 0, 0.0));
+        assert_eq!(summary(dd.next(99.0)), (110.0, 0.1, 0.1, 1, 1, -0.199));
+        assert_eq!(summary(dd.next(104.5)), (110.0, 0.05, 0.1, 2, 2, 0.6045));
+        assert_eq!(summary(dd.next(121.0)), (121.0, 0.0, 0.1, 0, 2, 1.3551));
+        assert_eq!(summary(dd.next(115.0)), (121.0, 0.0496, 0.1, 1, 2, 4.4588));
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut dd = DrawdownStats::new(4, 4.0).unwrap();
+
+        dd.next(&Bar::new().close(100));
+        let out = dd.next(&Bar::new().close(80));
+        assert_eq!(out.peak, 100.0);
+        assert_eq!(round(out.drawdown), 0.2);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut dd = DrawdownStats::new(4, 4.0).unwrap();
+
+        dd.next(100.0);
+        dd.next(50.0);
+
+        dd.reset();
+        assert_eq!(summary(dd.next(40.0)), (40.0, 0.0, 0.0, 0, 0, 0.0));
+    }
+
+    #[test]
+    fn test_default() {
+        DrawdownStats::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let dd = DrawdownStats::new(756, 252.0).unwrap();
+        assert_eq!(format!("{}", dd), "DD(756, 252)");
+    }
+}
@@ -0,0 +1,303 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{SimpleMovingAverage as Sma, SimpleMovingAverageState};
+use crate::{
+    Amend, Checkpoint, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset, SetPeriod,
+    Volume,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Ease of Movement (EOM).
+///
+/// Developed by Richard Arms, EOM relates price change to volume. High positive values
+/// indicate that prices are moving up on low volume.
+///
+/// # Formula
+///
+/// Distance = (High<sub>t</sub> + Low<sub>t</sub>) / 2 - (High<sub>t-1</sub> + Low<sub>t-1</sub>) / 2
+///
+/// Box ratio = (Volume / _divisor_) / (High - Low)
+///
+/// EOM = SMA(Distance / Box ratio, _period_)
+///
+/// The distance of the very first bar is 0, since there is no previous bar.
+/// When the box ratio is 0 (no price range or no volume), the raw value of the bar is 0.
+///
+/// # Parameters
+///
+/// * _period_ - period of the SMA (integer greater than 0). Default is 14.
+/// * _divisor_ - volume divisor to keep the scale readable (number greater than 0). Default is 100000000.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::EaseOfMovement;
+/// use ta::{Next, DataItem};
+///
+/// let mut eom = EaseOfMovement::new(2, 1000.0).unwrap();
+///
+/// let di1 = DataItem::builder()
+///             .high(10.0).low(8.0).close(9.0).open(9.0)
+///             .volume(1000.0)
+///             .build().unwrap();
+/// let di2 = DataItem::builder()
+///             .high(12.0).low(9.0).close(11.0).open(10.0)
+///             .volume(2000.0)
+///             .build().unwrap();
+///
+/// assert_eq!(eom.next(&di1), 0.0);
+/// assert_eq!(eom.next(&di2), 1.125);
+/// ```
+///
+/// # Links
+///
+/// * [Ease of movement, Wikipedia](https://en.wikipedia.org/wiki/Ease_of_movement)
+///
+#[doc(alias = "EOM")]
+#[doc(alias = "EMV")]
+#[derive(Debug, Clone)]
+pub struct EaseOfMovement {
+    divisor: f64,
+    sma: Sma,
+    prev_mid: Option<f64>,
+    output: Option<f64>,
+    // Midpoint before the last input, restored by `amend`
+    saved_prev_mid: Option<f64>,
+}
+
+/// State of [EaseOfMovement](struct.EaseOfMovement.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EaseOfMovementState {
+    pub divisor: f64,
+    pub sma: SimpleMovingAverageState,
+    /// Midpoint of the last input.
+    pub previous_mid: Option<f64>,
+    pub output: Option<f64>,
+    /// Midpoint of the input before the last one, to amend the last one.
+    pub mid_before_last: Option<f64>,
+}
+
+impl EaseOfMovement {
+    pub fn new(period: usize, divisor: f64) -> Result<Self> {
+        if !divisor.is_finite() || divisor <= 0.0 {
+            return Err(TaError::invalid_parameter("divisor", divisor, "> 0"));
+        }
+
+        Ok(Self {
+            divisor,
+            sma: Sma::new(period)?,
+            prev_mid: None,
+            output: None,
+            saved_prev_mid: None,
+        })
+    }
+
+    pub fn divisor(&self) -> f64 {
+        self.divisor
+    }
+
+    // Ease of movement of a bar, before the smoothing
+    fn emv<T: High + Low + Volume>(&self, input: &T) -> f64 {
+        let mid = (input.high() + input.low()) / 2.0;
+        let distance = match self.prev_mid {
+            Some(prev_mid) => mid - prev_mid,
+            None => 0.0,
+        };
+
+        let box_ratio = (input.volume() / self.divisor) / (input.high() - input.low());
+        if box_ratio > 0.0 && box_ratio.is_finite() {
+            distance / box_ratio
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Period for EaseOfMovement {
+    fn period(&self) -> usize {
+        self.sma.period()
+    }
+}
+
+impl Checkpoint for EaseOfMovement {
+    type State = EaseOfMovementState;
+
+    fn state(&self) -> Self::State {
+        EaseOfMovementState {
+            divisor: self.divisor,
+            sma: self.sma.state(),
+            previous_mid: self.prev_mid,
+            output: self.output,
+            mid_before_last: self.saved_prev_mid,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator =
+            Self::new(state.sma.period, state.divisor).map_err(|_| TaError::InvalidState)?;
+        if state.previous_mid.is_none() != state.output.is_none()
+            || state.output.is_none() != state.sma.inputs.is_empty()
+        {
+            return Err(TaError::InvalidState);
+        }
+        indicator.sma = Sma::from_state(state.sma)?;
+        indicator.prev_mid = state.previous_mid;
+        indicator.output = state.output;
+        indicator.saved_prev_mid = state.mid_before_last;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(EaseOfMovement, 2);
+
+impl SetPeriod for EaseOfMovement {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period, self.divisor)?;
+        Ok(())
+    }
+}
+
+impl<T: High + Low + Volume> Next<&T> for EaseOfMovement {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_prev_mid = self.prev_mid;
+        let emv = self.emv(input);
+        self.prev_mid = Some((input.high() + input.low()) / 2.0);
+
+        let output = self.sma.next(emv);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low + Volume> Peek<&T> for EaseOfMovement {
+    fn peek(&self, input: &T) -> f64 {
+        self.sma.peek(self.emv(input))
+    }
+}
+
+impl<T: High + Low + Volume> Amend<&T> for EaseOfMovement {
+    fn amend(&mut self, input: &T) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.prev_mid = self.saved_prev_mid;
+        let emv = self.emv(input);
+        self.prev_mid = Some((input.high() + input.low()) / 2.0);
+        let output = self.sma.amend(emv);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Reset for EaseOfMovement {
+    fn reset(&mut self) {
+        self.sma.reset();
+        self.prev_mid = None;
+        self.output = None;
+        self.saved_prev_mid = None;
+    }
+}
+
+impl Current for EaseOfMovement {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for EaseOfMovement {
+    fn is_ready(&self) -> bool {
+        self.sma.is_ready()
+    }
+}
+
+impl Lookback for EaseOfMovement {
+    fn lookback(&self) -> usize {
+        self.sma.lookback()
+    }
+}
+
+impl Default for EaseOfMovement {
+    fn default() -> Self {
+        Self::new(14, 100_000_000.0).unwrap()
+    }
+}
+
+impl fmt::Display for EaseOfMovement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "EOM({})", self.sma.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(EaseOfMovement::new(0, 1.0).is_err());
+        assert!(EaseOfMovement::new(14, 0.0).is_err());
+        assert!(EaseOfMovement::new(14, -1.0).is_err());
+        assert!(EaseOfMovement::new(14, f64::NAN).is_err());
+        assert!(EaseOfMovement::new(1, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut eom = EaseOfMovement::new(2, 1000.0).unwrap();
+
+        let bar1 = Bar::new().high(10).low(8).volume(1000.0);
+        let bar2 = Bar::new().high(12).low(9).volume(2000.0);
+        let bar3 = Bar::new().high(11).low(7).volume(1000.0);
+
+        assert_eq!(eom.next(&bar1), 0.0);
+        // distance = 1.5, box ratio = 2 / 3
+        assert_eq!(eom.next(&bar2), 1.125);
+        // distance = -1.5, box ratio = 1 / 4
+        assert_eq!(eom.next(&bar3), -1.875);
+    }
+
+    #[test]
+    fn test_next_degenerate_bars() {
+        let mut eom = EaseOfMovement::new(1, 1000.0).unwrap();
+
+        eom.next(&Bar::new().high(10).low(8).volume(1000.0));
+        // no price range
+        assert_eq!(eom.next(&Bar::new().high(12).low(12).volume(1000.0)), 0.0);
+        // no volume
+        assert_eq!(eom.next(&Bar::new().high(14).low(12).volume(0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut eom = EaseOfMovement::new(2, 1000.0).unwrap();
+
+        let bar1 = Bar::new().high(10).low(8).volume(1000.0);
+        let bar2 = Bar::new().high(12).low(9).volume(2000.0);
+
+        eom.next(&bar1);
+        eom.next(&bar2);
+
+        eom.reset();
+        assert_eq!(eom.next(&bar2), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        EaseOfMovement::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let eom = EaseOfMovement::new(14, 1.0).unwrap();
+        assert_eq!(format!("{}", eom), "EOM(14)");
+    }
+}
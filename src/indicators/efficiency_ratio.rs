@@ -1,7 +1,10 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::traits::{Close, Next, Period, Reset};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::traits::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -30,24 +33,44 @@ use serde::{Deserialize, Serialize};
 /// ```
 
 #[doc(alias = "ER")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct EfficiencyRatio {
     period: usize,
     index: usize,
     count: usize,
     deque: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+}
+
+/// State of [EfficiencyRatio](struct.EfficiencyRatio.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EfficiencyRatioState {
+    pub period: usize,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<f64>,
+    /// Last output, `None` before the first input.
+    pub output: Option<f64>,
+    /// Input which the last one pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
 }
 
 impl EfficiencyRatio {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
             _ => Ok(Self {
                 period,
                 index: 0,
                 count: 0,
                 deque: vec![0.0; period].into_boxed_slice(),
+                output: None,
+                saved_value: 0.0,
+                saved_count: 0,
             }),
         }
     }
@@ -59,10 +82,56 @@ impl Period for EfficiencyRatio {
     }
 }
 
+impl Checkpoint for EfficiencyRatio {
+    type State = EfficiencyRatioState;
+
+    fn state(&self) -> Self::State {
+        EfficiencyRatioState {
+            period: self.period,
+            inputs: ring_values(&self.deque, self.index, self.count),
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.inputs.is_empty() {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let (deque, index) = ring_from_values(state.period, &state.inputs)?;
+        indicator.deque = deque;
+        indicator.index = index;
+        indicator.count = state.inputs.len();
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(EfficiencyRatio, 2);
+
+impl SetPeriod for EfficiencyRatio {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
 impl Next<f64> for EfficiencyRatio {
     type Output = f64;
 
     fn next(&mut self, input: f64) -> f64 {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
         let first = if self.count >= self.period {
             self.deque[self.index]
         } else {
@@ -88,7 +157,9 @@ impl Next<f64> for EfficiencyRatio {
             previous = *n;
         }
 
-        (first - input).abs() / volatility
+        let output = (first - input).abs() / volatility;
+        self.output = Some(output);
+        output
     }
 }
 
@@ -100,6 +171,60 @@ impl<T: Close> Next<&T> for EfficiencyRatio {
     }
 }
 
+impl Peek<f64> for EfficiencyRatio {
+    fn peek(&self, input: f64) -> f64 {
+        let (first, count) = if self.count >= self.period {
+            (self.deque[self.index], self.count)
+        } else {
+            (self.deque[0], self.count + 1)
+        };
+        let index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        // Same window as `next`, with the input in the slot it would take
+        let mut volatility = 0.0;
+        let mut previous = first;
+        for i in (index..count).chain(0..index) {
+            let n = if i == self.index {
+                input
+            } else {
+                self.deque[i]
+            };
+            volatility += (previous - n).abs();
+            previous = n;
+        }
+
+        (first - input).abs() / volatility
+    }
+}
+
+impl<T: Close> Peek<&T> for EfficiencyRatio {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for EfficiencyRatio {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for EfficiencyRatio {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
 impl Reset for EfficiencyRatio {
     fn reset(&mut self) {
         self.index = 0;
@@ -107,6 +232,29 @@ impl Reset for EfficiencyRatio {
         for i in 0..self.period {
             self.deque[i] = 0.0;
         }
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+    }
+}
+
+impl Current for EfficiencyRatio {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for EfficiencyRatio {
+    fn is_ready(&self) -> bool {
+        self.count >= self.period
+    }
+}
+
+impl Lookback for EfficiencyRatio {
+    fn lookback(&self) -> usize {
+        self.period
     }
 }
 
@@ -0,0 +1,257 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{ExponentialMovingAverage as Ema, ExponentialMovingAverageState};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset,
+    SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Elder Ray Index (ERI).
+///
+/// Developed by Alexander Elder, it measures the amount of buying and selling pressure
+/// by comparing the highs and lows of a bar against an EMA of the closing prices.
+///
+/// # Formula
+///
+/// Bull Power = High - EMA(Close, _period_)
+///
+/// Bear Power = Low - EMA(Close, _period_)
+///
+/// # Parameters
+///
+/// * _period_ - period of the EMA (integer greater than 0). Default is 13.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::ElderRay;
+/// use ta::{Next, DataItem};
+///
+/// let mut eri = ElderRay::new(3).unwrap();
+///
+/// let di = DataItem::builder()
+///             .high(12.0).low(8.0).close(11.0).open(9.0)
+///             .volume(1000.0)
+///             .build().unwrap();
+///
+/// let out = eri.next(&di);
+/// assert_eq!(out.bull_power, 1.0);
+/// assert_eq!(out.bear_power, -3.0);
+/// ```
+///
+/// # Links
+///
+/// * [Elder-Ray Index, Investopedia](https://www.investopedia.com/terms/e/elderray.asp)
+///
+#[doc(alias = "ERI")]
+#[derive(Debug, Clone)]
+pub struct ElderRay {
+    ema: Ema,
+    output: Option<ElderRayOutput>,
+}
+
+/// State of [ElderRay](struct.ElderRay.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElderRayState {
+    pub ema: ExponentialMovingAverageState,
+    pub output: Option<ElderRayOutput>,
+}
+
+/// Output of [ElderRay](struct.ElderRay.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElderRayOutput {
+    pub bull_power: f64,
+    pub bear_power: f64,
+}
+
+impl_output!(ElderRayOutput, bull_power, bear_power);
+
+impl From<ElderRayOutput> for (f64, f64) {
+    fn from(er: ElderRayOutput) -> Self {
+        (er.bull_power, er.bear_power)
+    }
+}
+
+impl ElderRay {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            ema: Ema::new(period)?,
+            output: None,
+        })
+    }
+}
+
+impl Period for ElderRay {
+    fn period(&self) -> usize {
+        self.ema.period()
+    }
+}
+
+impl Checkpoint for ElderRay {
+    type State = ElderRayState;
+
+    fn state(&self) -> Self::State {
+        ElderRayState {
+            ema: self.ema.state(),
+            output: self.output.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_none() != (state.ema.count == 0) {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            ema: Ema::from_state(state.ema)?,
+            output: state.output,
+        })
+    }
+}
+
+impl_versioned_serde!(ElderRay, 1);
+
+impl SetPeriod for ElderRay {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for ElderRay {
+    type Output = ElderRayOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let ema = self.ema.next(input.close());
+
+        let output = ElderRayOutput {
+            bull_power: input.high() - ema,
+            bear_power: input.low() - ema,
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low + Close> Peek<&T> for ElderRay {
+    fn peek(&self, input: &T) -> ElderRayOutput {
+        let ema = self.ema.peek(input.close());
+
+        ElderRayOutput {
+            bull_power: input.high() - ema,
+            bear_power: input.low() - ema,
+        }
+    }
+}
+
+impl<T: High + Low + Close> Amend<&T> for ElderRay {
+    fn amend(&mut self, input: &T) -> ElderRayOutput {
+        let ema = self.ema.amend(input.close());
+        let output = ElderRayOutput {
+            bull_power: input.high() - ema,
+            bear_power: input.low() - ema,
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Reset for ElderRay {
+    fn reset(&mut self) {
+        self.ema.reset();
+        self.output = None;
+    }
+}
+
+impl Current for ElderRay {
+    type Output = ElderRayOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for ElderRay {
+    fn is_ready(&self) -> bool {
+        self.ema.is_ready()
+    }
+}
+
+impl Lookback for ElderRay {
+    fn lookback(&self) -> usize {
+        self.ema.lookback()
+    }
+}
+
+impl Default for ElderRay {
+    fn default() -> Self {
+        Self::new(13).unwrap()
+    }
+}
+
+impl fmt::Display for ElderRay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ERI({})", self.ema.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn powers(out: ElderRayOutput) -> (f64, f64) {
+        out.into()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(ElderRay::new(0).is_err());
+        assert!(ElderRay::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut eri = ElderRay::new(3).unwrap();
+
+        let bar1 = Bar::new().high(12).low(8).close(11);
+        let bar2 = Bar::new().high(14).low(12).close(13);
+        let bar3 = Bar::new().high(11).low(9).close(9);
+
+        // EMA = 11
+        assert_eq!(powers(eri.next(&bar1)), (1.0, -3.0));
+        // EMA = 12
+        assert_eq!(powers(eri.next(&bar2)), (2.0, 0.0));
+        // EMA = 10.5
+        assert_eq!(powers(eri.next(&bar3)), (0.5, -1.5));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut eri = ElderRay::new(3).unwrap();
+
+        let bar1 = Bar::new().high(12).low(8).close(11);
+        let bar2 = Bar::new().high(14).low(12).close(13);
+
+        eri.next(&bar1);
+        eri.next(&bar2);
+
+        eri.reset();
+        assert_eq!(powers(eri.next(&bar2)), (1.0, -1.0));
+    }
+
+    #[test]
+    fn test_default() {
+        ElderRay::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let eri = ElderRay::new(13).unwrap();
+        assert_eq!(format!("{}", eri), "ERI(13)");
+    }
+}
@@ -1,7 +1,10 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Numeric, Peek, Period, Reset,
+    SetPeriod,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -53,71 +56,187 @@ use serde::{Deserialize, Serialize};
 ///
 
 #[doc(alias = "EMA")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct ExponentialMovingAverage {
+pub struct ExponentialMovingAverage<N = f64> {
     period: usize,
-    k: f64,
-    current: f64,
-    is_new: bool,
+    k: N,
+    current: N,
+    count: usize,
+    output: Option<N>,
+    // State before the last input, restored by `amend`
+    saved_current: N,
+    saved_count: usize,
+}
+
+/// State of [ExponentialMovingAverage](generic/struct.ExponentialMovingAverage.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExponentialMovingAverageState<N = f64> {
+    pub period: usize,
+    /// Number of inputs, up to the period.
+    pub count: usize,
+    pub current: N,
+    /// Value before the last input, to amend it.
+    pub previous: N,
 }
 
-impl ExponentialMovingAverage {
+impl<N: Numeric> ExponentialMovingAverage<N> {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
             _ => Ok(Self {
                 period,
-                k: 2.0 / (period + 1) as f64,
-                current: 0.0,
-                is_new: true,
+                k: N::from_count(2) / N::from_count(period + 1),
+                current: N::zero(),
+                count: 0,
+                output: None,
+                saved_current: N::zero(),
+                saved_count: 0,
             }),
         }
     }
+
+    pub(super) fn next_value(&mut self, input: N) -> N {
+        self.saved_current = self.current;
+        self.saved_count = self.count;
+        if self.count == 0 {
+            self.current = input;
+        } else {
+            self.current = self.k * input + (N::one() - self.k) * self.current;
+        }
+        self.count = (self.count + 1).min(self.period);
+        let output = self.current;
+        self.output = Some(output);
+        output
+    }
+
+    pub(super) fn peek_value(&self, input: N) -> N {
+        if self.count == 0 {
+            input
+        } else {
+            self.k * input + (N::one() - self.k) * self.current
+        }
+    }
+
+    pub(super) fn amend_value(&mut self, input: N) -> N {
+        if self.output.is_none() {
+            return self.next_value(input);
+        }
+        self.current = self.saved_current;
+        self.count = self.saved_count;
+        self.next_value(input)
+    }
 }
 
-impl Period for ExponentialMovingAverage {
+impl<N: Numeric> Period for ExponentialMovingAverage<N> {
     fn period(&self) -> usize {
         self.period
     }
 }
 
-impl Next<f64> for ExponentialMovingAverage {
-    type Output = f64;
+impl<N: Numeric> Checkpoint for ExponentialMovingAverage<N> {
+    type State = ExponentialMovingAverageState<N>;
 
-    fn next(&mut self, input: f64) -> Self::Output {
-        if self.is_new {
-            self.is_new = false;
-            self.current = input;
-        } else {
-            self.current = self.k * input + (1.0 - self.k) * self.current;
+    fn state(&self) -> Self::State {
+        ExponentialMovingAverageState {
+            period: self.period,
+            count: self.count,
+            current: self.current,
+            previous: self.saved_current,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.period == 0 || state.count > state.period {
+            return Err(TaError::InvalidState);
         }
-        self.current
+        let mut ema = Self::new(state.period)?;
+        ema.count = state.count;
+        ema.current = state.current;
+        ema.output = (state.count > 0).then_some(state.current);
+        // Amending feeds the last input again after the previous one, or as the first input
+        ema.saved_current = state.previous;
+        ema.saved_count = state.count.saturating_sub(1);
+        Ok(ema)
     }
 }
 
-impl<T: Close> Next<&T> for ExponentialMovingAverage {
-    type Output = f64;
+impl_versioned_serde!(ExponentialMovingAverage<N>, 1);
+
+impl<N: Numeric> SetPeriod for ExponentialMovingAverage<N> {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        match period {
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
+            _ => {
+                self.period = period;
+                self.k = N::from_count(2) / N::from_count(period + 1);
+                self.count = self.count.min(period);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl_numeric_inputs!(ExponentialMovingAverage);
+
+impl<N: Numeric, T: Close<N>> Next<&T> for ExponentialMovingAverage<N> {
+    type Output = N;
 
     fn next(&mut self, input: &T) -> Self::Output {
-        self.next(input.close())
+        self.next_value(input.close())
+    }
+}
+
+impl<N: Numeric, T: Close<N>> Peek<&T> for ExponentialMovingAverage<N> {
+    fn peek(&self, input: &T) -> N {
+        self.peek_value(input.close())
     }
 }
 
-impl Reset for ExponentialMovingAverage {
+impl<N: Numeric, T: Close<N>> Amend<&T> for ExponentialMovingAverage<N> {
+    fn amend(&mut self, input: &T) -> N {
+        self.amend_value(input.close())
+    }
+}
+
+impl<N: Numeric> Reset for ExponentialMovingAverage<N> {
     fn reset(&mut self) {
-        self.current = 0.0;
-        self.is_new = true;
+        self.current = N::zero();
+        self.count = 0;
+        self.output = None;
+        self.saved_current = N::zero();
+        self.saved_count = 0;
+    }
+}
+
+impl<N: Numeric> Current for ExponentialMovingAverage<N> {
+    type Output = N;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
     }
 }
 
-impl Default for ExponentialMovingAverage {
+impl<N: Numeric> IsReady for ExponentialMovingAverage<N> {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl<N: Numeric> Lookback for ExponentialMovingAverage<N> {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl<N: Numeric> Default for ExponentialMovingAverage<N> {
     fn default() -> Self {
         Self::new(9).unwrap()
     }
 }
 
-impl fmt::Display for ExponentialMovingAverage {
+impl<N: Numeric> fmt::Display for ExponentialMovingAverage<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "EMA({})", self.period)
     }
@@ -126,7 +245,9 @@ impl fmt::Display for ExponentialMovingAverage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::ExponentialMovingAverage;
     use crate::test_helper::*;
+    use crate::TryNext;
 
     test_indicator!(ExponentialMovingAverage);
 
@@ -152,6 +273,18 @@ mod tests {
         assert_eq!(ema.next(&bar2), 3.5);
     }
 
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_next_decimal() {
+        use crate::indicators::generic::ExponentialMovingAverage;
+        use rust_decimal::Decimal;
+
+        let mut ema = ExponentialMovingAverage::<Decimal>::new(3).unwrap();
+        assert_eq!(ema.next(Decimal::new(1, 1)), Decimal::new(1, 1));
+        assert_eq!(ema.next(Decimal::new(2, 1)), Decimal::new(15, 2));
+        assert_eq!(ema.next(Decimal::new(4, 1)), Decimal::new(275, 3));
+    }
+
     #[test]
     fn test_reset() {
         let mut ema = ExponentialMovingAverage::new(5).unwrap();
@@ -166,6 +299,33 @@ mod tests {
         assert_eq!(ema.next(4.0), 4.0);
     }
 
+    #[test]
+    fn test_set_period() {
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        ema.next(2.0);
+        assert_eq!(ema.next(4.0), 3.0);
+
+        ema.set_period(1).unwrap();
+        assert_eq!(ema.period(), 1);
+        assert!(ema.is_ready());
+        assert_eq!(ema.next(8.0), 8.0);
+
+        assert!(ema.set_period(0).is_err());
+        assert_eq!(ema.period(), 1);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        assert_eq!(ema.try_next(2.0), None);
+        assert_eq!(ema.try_next(5.0), None);
+        assert_eq!(ema.try_next(1.0), Some(2.25));
+        assert!(ema.is_ready());
+
+        ema.reset();
+        assert!(!ema.is_ready());
+    }
+
     #[test]
     fn test_default() {
         ExponentialMovingAverage::default();
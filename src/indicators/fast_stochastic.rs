@@ -1,8 +1,11 @@
-use std::fmt;
+use core::fmt;
 
-use crate::errors::Result;
-use crate::indicators::{Maximum, Minimum};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::errors::{Result, TaError};
+use crate::indicators::{Maximum, MaximumState, Minimum, MinimumState};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset,
+    SetPeriod,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -40,12 +43,24 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(stoch.next(35.0), 75.0);
 /// assert_eq!(stoch.next(15.0), 0.0);
 /// ```
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct FastStochastic {
     period: usize,
     minimum: Minimum,
     maximum: Maximum,
+    output: Option<f64>,
+}
+
+/// State of [FastStochastic](struct.FastStochastic.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FastStochasticState {
+    pub period: usize,
+    pub minimum: MinimumState,
+    pub maximum: MaximumState,
+    /// Value of the last input.
+    pub value: Option<f64>,
 }
 
 impl FastStochastic {
@@ -54,6 +69,7 @@ impl FastStochastic {
             period,
             minimum: Minimum::new(period)?,
             maximum: Maximum::new(period)?,
+            output: None,
         })
     }
 }
@@ -64,6 +80,44 @@ impl Period for FastStochastic {
     }
 }
 
+impl Checkpoint for FastStochastic {
+    type State = FastStochasticState;
+
+    fn state(&self) -> Self::State {
+        FastStochasticState {
+            period: self.period,
+            minimum: self.minimum.state(),
+            maximum: self.maximum.state(),
+            value: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.minimum.period != state.period
+            || state.maximum.period != state.period
+            || state.minimum.inputs.len() != state.maximum.inputs.len()
+            || state.value.is_some() == state.minimum.inputs.is_empty()
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            period: state.period,
+            minimum: Minimum::from_state(state.minimum)?,
+            maximum: Maximum::from_state(state.maximum)?,
+            output: state.value,
+        })
+    }
+}
+
+impl_versioned_serde!(FastStochastic, 1);
+
+impl SetPeriod for FastStochastic {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
 impl Next<f64> for FastStochastic {
     type Output = f64;
 
@@ -71,13 +125,15 @@ impl Next<f64> for FastStochastic {
         let min = self.minimum.next(input);
         let max = self.maximum.next(input);
 
-        if min == max {
+        let output = if min == max {
             // When only 1 input was given, than min and max are the same,
             // therefore it makes sense to return 50
             50.0
         } else {
             (input - min) / (max - min) * 100.0
-        }
+        };
+        self.output = Some(output);
+        output
     }
 }
 
@@ -89,19 +145,98 @@ impl<T: High + Low + Close> Next<&T> for FastStochastic {
         let lowest = self.minimum.next(input.low());
         let close = input.close();
 
-        if highest == lowest {
+        let output = if highest == lowest {
             // To avoid division by zero, return 50.0
             50.0
         } else {
             (close - lowest) / (highest - lowest) * 100.0
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Peek<f64> for FastStochastic {
+    fn peek(&self, input: f64) -> f64 {
+        let min = self.minimum.peek(input);
+        let max = self.maximum.peek(input);
+
+        if min == max {
+            50.0
+        } else {
+            (input - min) / (max - min) * 100.0
         }
     }
 }
 
+impl<T: High + Low + Close> Peek<&T> for FastStochastic {
+    fn peek(&self, input: &T) -> f64 {
+        let highest = self.maximum.peek(input.high());
+        let lowest = self.minimum.peek(input.low());
+
+        if highest == lowest {
+            50.0
+        } else {
+            (input.close() - lowest) / (highest - lowest) * 100.0
+        }
+    }
+}
+
+impl Amend<f64> for FastStochastic {
+    fn amend(&mut self, input: f64) -> f64 {
+        let min = self.minimum.amend(input);
+        let max = self.maximum.amend(input);
+
+        let output = if min == max {
+            50.0
+        } else {
+            (input - min) / (max - min) * 100.0
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low + Close> Amend<&T> for FastStochastic {
+    fn amend(&mut self, input: &T) -> f64 {
+        let highest = self.maximum.amend(input.high());
+        let lowest = self.minimum.amend(input.low());
+
+        let output = if highest == lowest {
+            50.0
+        } else {
+            (input.close() - lowest) / (highest - lowest) * 100.0
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
 impl Reset for FastStochastic {
     fn reset(&mut self) {
         self.minimum.reset();
         self.maximum.reset();
+        self.output = None;
+    }
+}
+
+impl Current for FastStochastic {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for FastStochastic {
+    fn is_ready(&self) -> bool {
+        self.maximum.is_ready() && self.minimum.is_ready()
+    }
+}
+
+impl Lookback for FastStochastic {
+    fn lookback(&self) -> usize {
+        self.maximum.lookback().max(self.minimum.lookback())
     }
 }
 
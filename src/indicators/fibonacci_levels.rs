@@ -0,0 +1,532 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{SwingPoint, SwingPoints, SwingPointsOutput, SwingPointsState};
+use crate::{
+    Amend, Checkpoint, Current, High, IsReady, Lookback, Low, Next, OutputValue, Peek, Period,
+    Reset,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Fibonacci retracement and extension levels.
+///
+/// Follows the most recent swing leg, which runs from the older to the newer of the last
+/// [swing high and swing low](struct.SwingPoints.html), and places Fibonacci levels on it:
+///
+/// * retracement levels lie between the start and the end of the leg, measured back from the
+///   end. They are where a pullback against the leg is expected to stop;
+/// * extension levels lie beyond the end of the leg, measured from its start. They are targets
+///   for the continuation of the leg.
+///
+/// On each bar the current levels are checked and every level within the range of the bar is
+/// reported as touched, unless the previous bar touched it already.
+///
+/// Swings are confirmed _strength_ bars after they happened, so a new leg starts being used
+/// _strength_ bars after its end.
+///
+/// # Formula
+///
+/// Retracement = End - _ratio_ * (End - Start)
+///
+/// Extension = Start + _ratio_ * (End - Start)
+///
+/// # Parameters
+///
+/// * _strength_ - number of bars on each side of a swing point (integer greater than 0).
+///   Default is 5.
+///
+/// The ratios are set with [retracements](#method.retracements) and
+/// [extensions](#method.extensions). By default they are 0.236, 0.382, 0.5, 0.618 and 0.786 for
+/// retracements and 1.272 and 1.618 for extensions.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{FibonacciLevels, FibonacciKind};
+/// use ta::{Next, DataItem};
+///
+/// fn bar(high: f64, low: f64) -> DataItem {
+///     DataItem::builder()
+///         .high(high).low(low).close(low).open(low)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut fib = FibonacciLevels::new(1).unwrap()
+///     .retracements(&[0.5]).unwrap()
+///     .extensions(&[]).unwrap();
+///
+/// fib.next(&bar(12.0, 10.0));
+/// fib.next(&bar(11.0, 8.0));
+/// fib.next(&bar(13.0, 9.0));
+/// // Leg from the swing low at 8 to the swing high at 13
+/// fib.next(&bar(12.5, 11.0));
+///
+/// let touches = fib.next(&bar(11.0, 10.0));
+/// assert_eq!(touches[0].kind, FibonacciKind::Retracement);
+/// assert_eq!(touches[0].price, 10.5);
+/// ```
+///
+/// # Links
+///
+/// * [Fibonacci retracement, Wikipedia](https://en.wikipedia.org/wiki/Fibonacci_retracement)
+///
+#[doc(alias = "FIB")]
+#[derive(Debug, Clone)]
+pub struct FibonacciLevels {
+    swings: SwingPoints,
+    retracements: Vec<f64>,
+    extensions: Vec<f64>,
+    high: Option<SwingPoint>,
+    low: Option<SwingPoint>,
+    prev: Option<(f64, f64)>,
+    output: Option<Vec<FibonacciLevel>>,
+    // State before the last input, restored by `amend`
+    saved_high: Option<SwingPoint>,
+    saved_low: Option<SwingPoint>,
+    saved_prev: Option<(f64, f64)>,
+}
+
+/// State of [FibonacciLevels](struct.FibonacciLevels.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FibonacciLevelsState {
+    pub swings: SwingPointsState,
+    pub retracements: Vec<f64>,
+    pub extensions: Vec<f64>,
+    /// Last swing high.
+    pub high: Option<SwingPoint>,
+    /// Last swing low.
+    pub low: Option<SwingPoint>,
+    /// High and low of the last input.
+    pub previous: Option<(f64, f64)>,
+    pub output: Option<Vec<FibonacciLevel>>,
+    /// Swings before the last input and high and low of the input before it, to amend it.
+    pub previous_high: Option<SwingPoint>,
+    pub previous_low: Option<SwingPoint>,
+    pub before_last: Option<(f64, f64)>,
+}
+
+/// Kind of a [Fibonacci level](struct.FibonacciLevels.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FibonacciKind {
+    Retracement,
+    Extension,
+}
+
+impl fmt::Display for FibonacciKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FibonacciKind::Retracement => write!(f, "retracement"),
+            FibonacciKind::Extension => write!(f, "extension"),
+        }
+    }
+}
+
+/// Output of [FibonacciLevels](struct.FibonacciLevels.html), one for every level that price
+/// touched.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FibonacciLevel {
+    pub kind: FibonacciKind,
+    pub ratio: f64,
+    pub price: f64,
+}
+
+impl fmt::Display for FibonacciLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} at {}", self.kind, self.ratio, self.price)
+    }
+}
+
+impl From<FibonacciLevel> for OutputValue {
+    fn from(level: FibonacciLevel) -> Self {
+        OutputValue::Named(vec![
+            ("kind", OutputValue::Label(level.kind.to_string())),
+            ("ratio", level.ratio.into()),
+            ("price", level.price.into()),
+        ])
+    }
+}
+
+impl FibonacciLevels {
+    pub fn new(strength: usize) -> Result<Self> {
+        Ok(Self {
+            swings: SwingPoints::new(strength, strength)?,
+            retracements: vec![0.236, 0.382, 0.5, 0.618, 0.786],
+            extensions: vec![1.272, 1.618],
+            high: None,
+            low: None,
+            prev: None,
+            output: None,
+            saved_high: None,
+            saved_low: None,
+            saved_prev: None,
+        })
+    }
+
+    /// Replaces the retracement ratios. Fails when a ratio is not a number greater than 0.
+    pub fn retracements(mut self, ratios: &[f64]) -> Result<Self> {
+        self.retracements = check_ratios(ratios)?;
+        Ok(self)
+    }
+
+    /// Replaces the extension ratios. Fails when a ratio is not a number greater than 0.
+    pub fn extensions(mut self, ratios: &[f64]) -> Result<Self> {
+        self.extensions = check_ratios(ratios)?;
+        Ok(self)
+    }
+
+    /// Start and end of the current swing leg.
+    pub fn leg(&self) -> Option<(SwingPoint, SwingPoint)> {
+        Self::leg_between(self.high, self.low)
+    }
+
+    fn leg_between(
+        high: Option<SwingPoint>,
+        low: Option<SwingPoint>,
+    ) -> Option<(SwingPoint, SwingPoint)> {
+        match (high, low) {
+            (Some(high), Some(low)) if high.index < low.index => Some((high, low)),
+            (Some(high), Some(low)) => Some((low, high)),
+            _ => None,
+        }
+    }
+
+    /// Levels of the current swing leg, retracements first.
+    pub fn levels(&self) -> Vec<FibonacciLevel> {
+        self.levels_on(self.leg())
+    }
+
+    fn levels_on(&self, leg: Option<(SwingPoint, SwingPoint)>) -> Vec<FibonacciLevel> {
+        let (start, end) = match leg {
+            Some((start, end)) => (start.price, end.price),
+            None => return Vec::new(),
+        };
+        let length = end - start;
+
+        let retracements = self.retracements.iter().map(|&ratio| FibonacciLevel {
+            kind: FibonacciKind::Retracement,
+            ratio,
+            price: end - ratio * length,
+        });
+        let extensions = self.extensions.iter().map(|&ratio| FibonacciLevel {
+            kind: FibonacciKind::Extension,
+            ratio,
+            price: start + ratio * length,
+        });
+        retracements.chain(extensions).collect()
+    }
+
+    // Levels of the leg that a bar touches and the previous bar did not
+    fn touched(
+        &self,
+        (high, low): (f64, f64),
+        leg: Option<(SwingPoint, SwingPoint)>,
+    ) -> Vec<FibonacciLevel> {
+        self.levels_on(leg)
+            .into_iter()
+            .filter(|level| {
+                let touched = |(high, low): (f64, f64)| low <= level.price && level.price <= high;
+                touched((high, low)) && !self.prev.is_some_and(touched)
+            })
+            .collect()
+    }
+
+    fn update(&mut self, swing: SwingPointsOutput, (high, low): (f64, f64)) -> Vec<FibonacciLevel> {
+        if swing.high.is_some() {
+            self.high = swing.high;
+        }
+        if swing.low.is_some() {
+            self.low = swing.low;
+        }
+
+        let output = self.touched((high, low), self.leg());
+        self.prev = Some((high, low));
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+fn check_ratios(ratios: &[f64]) -> Result<Vec<f64>> {
+    match ratios.iter().find(|r| !(r.is_finite() && **r > 0.0)) {
+        Some(&ratio) => Err(TaError::invalid_parameter("ratios", ratio, "> 0")),
+        None => Ok(ratios.to_vec()),
+    }
+}
+
+impl Period for FibonacciLevels {
+    fn period(&self) -> usize {
+        self.swings.period()
+    }
+}
+
+impl Checkpoint for FibonacciLevels {
+    type State = FibonacciLevelsState;
+
+    fn state(&self) -> Self::State {
+        FibonacciLevelsState {
+            swings: self.swings.state(),
+            retracements: self.retracements.clone(),
+            extensions: self.extensions.clone(),
+            high: self.high,
+            low: self.low,
+            previous: self.prev,
+            output: self.output.clone(),
+            previous_high: self.saved_high,
+            previous_low: self.saved_low,
+            before_last: self.saved_prev,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.swings.left != state.swings.right
+            || state.previous.is_none() != state.output.is_none()
+            || state.output.is_none() != (state.swings.bar == 0)
+            || [state.high, state.low]
+                .iter()
+                .flatten()
+                .any(|swing| swing.index >= state.swings.bar)
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            swings: SwingPoints::from_state(state.swings)?,
+            retracements: check_ratios(&state.retracements).map_err(|_| TaError::InvalidState)?,
+            extensions: check_ratios(&state.extensions).map_err(|_| TaError::InvalidState)?,
+            high: state.high,
+            low: state.low,
+            prev: state.previous,
+            output: state.output,
+            saved_high: state.previous_high,
+            saved_low: state.previous_low,
+            saved_prev: state.before_last,
+        })
+    }
+}
+
+impl_versioned_serde!(FibonacciLevels, 2);
+
+impl<T: High + Low> Next<&T> for FibonacciLevels {
+    type Output = Vec<FibonacciLevel>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let (high, low) = (input.high(), input.low());
+        self.saved_high = self.high;
+        self.saved_low = self.low;
+        self.saved_prev = self.prev;
+
+        let swing = self.swings.update(high, low);
+        self.update(swing, (high, low))
+    }
+}
+
+impl<T: High + Low> Peek<&T> for FibonacciLevels {
+    fn peek(&self, input: &T) -> Vec<FibonacciLevel> {
+        let (high, low) = (input.high(), input.low());
+
+        let swing = self.swings.detect(high, low);
+        let leg = Self::leg_between(swing.high.or(self.high), swing.low.or(self.low));
+        self.touched((high, low), leg)
+    }
+}
+
+impl<T: High + Low> Amend<&T> for FibonacciLevels {
+    fn amend(&mut self, input: &T) -> Vec<FibonacciLevel> {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let (high, low) = (input.high(), input.low());
+        self.high = self.saved_high;
+        self.low = self.saved_low;
+        self.prev = self.saved_prev;
+
+        let swing = self.swings.amend_update(high, low);
+        self.update(swing, (high, low))
+    }
+}
+
+impl Reset for FibonacciLevels {
+    fn reset(&mut self) {
+        self.swings.reset();
+        self.high = None;
+        self.low = None;
+        self.prev = None;
+        self.output = None;
+        self.saved_high = None;
+        self.saved_low = None;
+        self.saved_prev = None;
+    }
+}
+
+impl Current for FibonacciLevels {
+    type Output = Vec<FibonacciLevel>;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for FibonacciLevels {
+    fn is_ready(&self) -> bool {
+        self.swings.is_ready()
+    }
+}
+
+impl Lookback for FibonacciLevels {
+    fn lookback(&self) -> usize {
+        self.swings.lookback()
+    }
+}
+
+impl Default for FibonacciLevels {
+    fn default() -> Self {
+        Self::new(5).unwrap()
+    }
+}
+
+impl fmt::Display for FibonacciLevels {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FIB({})", self.swings.left())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn bar(high: f64, low: f64) -> Bar {
+        Bar::new().high(high).low(low)
+    }
+
+    fn prices(levels: Vec<FibonacciLevel>) -> Vec<f64> {
+        levels.into_iter().map(|level| round(level.price)).collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(FibonacciLevels::new(0).is_err());
+        assert!(FibonacciLevels::new(1).is_ok());
+
+        let fib = FibonacciLevels::new(1).unwrap();
+        assert!(fib.clone().retracements(&[0.5, 0.0]).is_err());
+        assert!(fib.clone().extensions(&[f64::NAN]).is_err());
+        assert!(fib.retracements(&[]).is_ok());
+    }
+
+    #[test]
+    fn test_levels() {
+        let mut fib = FibonacciLevels::new(1).unwrap();
+
+        fib.next(&bar(10.0, 8.0));
+        fib.next(&bar(12.0, 9.0));
+        fib.next(&bar(11.0, 7.0));
+        assert_eq!(fib.leg(), None);
+        assert!(fib.levels().is_empty());
+
+        // Down leg from the swing high at 12 to the swing low at 7
+        fib.next(&bar(13.0, 9.0));
+        let (start, end) = fib.leg().unwrap();
+        assert_eq!((start.price, start.index), (12.0, 1));
+        assert_eq!((end.price, end.index), (7.0, 2));
+        assert_eq!(
+            prices(fib.levels()),
+            vec![8.18, 8.91, 9.5, 10.09, 10.93, 5.64, 3.91]
+        );
+
+        // Up leg from the swing low at 7 to the swing high at 13
+        fib.next(&bar(10.0, 8.5));
+        let (start, end) = fib.leg().unwrap();
+        assert_eq!((start.price, end.price), (7.0, 13.0));
+        assert_eq!(
+            prices(fib.levels()),
+            vec![11.584, 10.708, 10.0, 9.292, 8.284, 14.632, 16.708]
+        );
+    }
+
+    #[test]
+    fn test_touches() {
+        let mut fib = FibonacciLevels::new(1).unwrap();
+
+        fib.next(&bar(10.0, 8.0));
+        fib.next(&bar(12.0, 9.0));
+        fib.next(&bar(11.0, 7.0));
+        fib.next(&bar(13.0, 9.0));
+        // 10 and 9.292 were already touched by the previous bar
+        assert!(fib.next(&bar(10.0, 8.5)).is_empty());
+
+        let touches = fib.next(&bar(9.5, 8.0));
+        assert_eq!(touches.len(), 1);
+        assert_eq!(touches[0].kind, FibonacciKind::Retracement);
+        assert_eq!(touches[0].ratio, 0.786);
+        assert_eq!(round(touches[0].price), 8.284);
+
+        let touches: Vec<_> = fib
+            .next(&bar(15.0, 7.9))
+            .into_iter()
+            .map(|level| (level.kind, level.ratio))
+            .collect();
+        assert_eq!(
+            touches,
+            vec![
+                (FibonacciKind::Retracement, 0.236),
+                (FibonacciKind::Retracement, 0.382),
+                (FibonacciKind::Retracement, 0.5),
+                (FibonacciKind::Extension, 1.272),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_ratios() {
+        let mut fib = FibonacciLevels::new(1)
+            .unwrap()
+            .retracements(&[0.5])
+            .unwrap()
+            .extensions(&[2.0])
+            .unwrap();
+
+        fib.next(&bar(10.0, 8.0));
+        fib.next(&bar(12.0, 9.0));
+        fib.next(&bar(11.0, 7.0));
+        fib.next(&bar(13.0, 9.0));
+        assert_eq!(prices(fib.levels()), vec![9.5, 2.0]);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut fib = FibonacciLevels::new(1).unwrap();
+
+        fib.next(&bar(10.0, 8.0));
+        fib.next(&bar(12.0, 9.0));
+        fib.next(&bar(11.0, 7.0));
+        fib.next(&bar(13.0, 9.0));
+        assert!(fib.leg().is_some());
+
+        fib.reset();
+        assert_eq!(fib.leg(), None);
+        assert!(fib.next(&bar(10.0, 8.0)).is_empty());
+    }
+
+    #[test]
+    fn test_default() {
+        FibonacciLevels::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let fib = FibonacciLevels::new(3).unwrap();
+        assert_eq!(format!("{}", fib), "FIB(3)");
+
+        let level = FibonacciLevel {
+            kind: FibonacciKind::Extension,
+            ratio: 1.618,
+            price: 16.5,
+        };
+        assert_eq!(format!("{}", level), "extension 1.618 at 16.5");
+    }
+}
@@ -0,0 +1,376 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{Maximum, MaximumState, Minimum, MinimumState};
+use crate::{
+    Amend, Checkpoint, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Fisher Transform (FT).
+///
+/// Developed by John Ehlers, the Fisher transform converts prices into a Gaussian normal
+/// distribution, which makes turning points sharper and easier to spot.
+///
+/// # Formula
+///
+/// Value<sub>t</sub> = 0.66 * ((MP - Min) / (Max - Min) - 0.5) + 0.67 * Value<sub>t-1</sub>
+///
+/// Fisher<sub>t</sub> = 0.5 * ln((1 + Value<sub>t</sub>) / (1 - Value<sub>t</sub>)) + 0.5 * Fisher<sub>t-1</sub>
+///
+/// Trigger<sub>t</sub> = Fisher<sub>t-1</sub>
+///
+/// Where:
+///
+/// * _MP_ - median price, (High + Low) / 2
+/// * _Max_, _Min_ - highest and lowest median price over the last _period_ bars
+///
+/// _Value_ is clamped to -0.999..0.999 to keep the logarithm finite. When there is no price
+/// range the normalized price is 0.5, i.e. the middle of the range.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 10.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::FisherTransform;
+/// use ta::Next;
+///
+/// let mut ft = FisherTransform::new(3).unwrap();
+///
+/// let out = ft.next(10.0);
+/// assert_eq!(out.fisher, 0.0);
+///
+/// let out = ft.next(12.0);
+/// assert_eq!((out.fisher * 1000.0).round(), 343.0);
+/// assert_eq!(out.trigger, 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [Fisher Transform, Investopedia](https://www.investopedia.com/terms/f/fisher-transform.asp)
+///
+#[doc(alias = "FT")]
+#[derive(Debug, Clone)]
+pub struct FisherTransform {
+    period: usize,
+    maximum: Maximum,
+    minimum: Minimum,
+    value: f64,
+    fisher: f64,
+    output: Option<FisherTransformOutput>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_fisher: f64,
+}
+
+/// State of [FisherTransform](struct.FisherTransform.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FisherTransformState {
+    pub maximum: MaximumState,
+    pub minimum: MinimumState,
+    /// Smoothed position of the input in its range, between -1 and 1.
+    pub value: f64,
+    pub fisher: f64,
+    pub output: Option<FisherTransformOutput>,
+    /// Value and transform before the last input, to amend it.
+    pub previous_value: f64,
+    pub previous_fisher: f64,
+}
+
+/// Output of [FisherTransform](struct.FisherTransform.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FisherTransformOutput {
+    pub fisher: f64,
+    pub trigger: f64,
+}
+
+impl_output!(FisherTransformOutput, fisher, trigger);
+
+impl From<FisherTransformOutput> for (f64, f64) {
+    fn from(o: FisherTransformOutput) -> Self {
+        (o.fisher, o.trigger)
+    }
+}
+
+const MAX_VALUE: f64 = 0.999;
+
+impl FisherTransform {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            period,
+            maximum: Maximum::new(period)?,
+            minimum: Minimum::new(period)?,
+            value: 0.0,
+            fisher: 0.0,
+            output: None,
+            saved_value: 0.0,
+            saved_fisher: 0.0,
+        })
+    }
+
+    fn update(&mut self, input: f64, max: f64, min: f64) -> FisherTransformOutput {
+        self.saved_value = self.value;
+        self.saved_fisher = self.fisher;
+
+        let normalized = if max > min {
+            (input - min) / (max - min)
+        } else {
+            0.5
+        };
+
+        self.value = (0.66 * (normalized - 0.5) + 0.67 * self.value).clamp(-MAX_VALUE, MAX_VALUE);
+
+        let trigger = self.fisher;
+        self.fisher = 0.5 * ((1.0 + self.value) / (1.0 - self.value)).ln() + 0.5 * self.fisher;
+
+        let output = FisherTransformOutput {
+            fisher: self.fisher,
+            trigger,
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Period for FisherTransform {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for FisherTransform {
+    type State = FisherTransformState;
+
+    fn state(&self) -> Self::State {
+        FisherTransformState {
+            maximum: self.maximum.state(),
+            minimum: self.minimum.state(),
+            value: self.value,
+            fisher: self.fisher,
+            output: self.output.clone(),
+            previous_value: self.saved_value,
+            previous_fisher: self.saved_fisher,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.maximum.period != state.minimum.period
+            || state.maximum.inputs.len() != state.minimum.inputs.len()
+            || state.output.is_none() != state.maximum.inputs.is_empty()
+            || state.value.is_nan()
+            || state.value.abs() > MAX_VALUE
+            || state.previous_value.is_nan()
+            || state.previous_value.abs() > MAX_VALUE
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            period: state.maximum.period,
+            maximum: Maximum::from_state(state.maximum)?,
+            minimum: Minimum::from_state(state.minimum)?,
+            value: state.value,
+            fisher: state.fisher,
+            output: state.output,
+            saved_value: state.previous_value,
+            saved_fisher: state.previous_fisher,
+        })
+    }
+}
+
+impl_versioned_serde!(FisherTransform, 2);
+
+impl SetPeriod for FisherTransform {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for FisherTransform {
+    type Output = FisherTransformOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let max = self.maximum.next(input);
+        let min = self.minimum.next(input);
+        self.update(input, max, min)
+    }
+}
+
+impl<T: High + Low> Next<&T> for FisherTransform {
+    type Output = FisherTransformOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next((input.high() + input.low()) / 2.0)
+    }
+}
+
+impl Peek<f64> for FisherTransform {
+    fn peek(&self, input: f64) -> FisherTransformOutput {
+        let max = self.maximum.peek(input);
+        let min = self.minimum.peek(input);
+
+        let normalized = if max > min {
+            (input - min) / (max - min)
+        } else {
+            0.5
+        };
+        let value = (0.66 * (normalized - 0.5) + 0.67 * self.value).clamp(-MAX_VALUE, MAX_VALUE);
+
+        FisherTransformOutput {
+            fisher: 0.5 * ((1.0 + value) / (1.0 - value)).ln() + 0.5 * self.fisher,
+            trigger: self.fisher,
+        }
+    }
+}
+
+impl<T: High + Low> Peek<&T> for FisherTransform {
+    fn peek(&self, input: &T) -> FisherTransformOutput {
+        self.peek((input.high() + input.low()) / 2.0)
+    }
+}
+
+impl Amend<f64> for FisherTransform {
+    fn amend(&mut self, input: f64) -> FisherTransformOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.value = self.saved_value;
+        self.fisher = self.saved_fisher;
+        let max = self.maximum.amend(input);
+        let min = self.minimum.amend(input);
+        self.update(input, max, min)
+    }
+}
+
+impl<T: High + Low> Amend<&T> for FisherTransform {
+    fn amend(&mut self, input: &T) -> FisherTransformOutput {
+        self.amend((input.high() + input.low()) / 2.0)
+    }
+}
+
+impl Reset for FisherTransform {
+    fn reset(&mut self) {
+        self.maximum.reset();
+        self.minimum.reset();
+        self.value = 0.0;
+        self.fisher = 0.0;
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_fisher = 0.0;
+    }
+}
+
+impl Current for FisherTransform {
+    type Output = FisherTransformOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for FisherTransform {
+    fn is_ready(&self) -> bool {
+        self.maximum.is_ready() && self.minimum.is_ready()
+    }
+}
+
+impl Lookback for FisherTransform {
+    fn lookback(&self) -> usize {
+        self.maximum.lookback().max(self.minimum.lookback())
+    }
+}
+
+impl Default for FisherTransform {
+    fn default() -> Self {
+        Self::new(10).unwrap()
+    }
+}
+
+impl fmt::Display for FisherTransform {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FT({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(FisherTransform);
+
+    fn round(nums: (f64, f64)) -> (f64, f64) {
+        let n0 = (nums.0 * 1000.0).round() / 1000.0;
+        let n1 = (nums.1 * 1000.0).round() / 1000.0;
+        (n0, n1)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(FisherTransform::new(0).is_err());
+        assert!(FisherTransform::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut ft = FisherTransform::new(3).unwrap();
+
+        assert_eq!(round(ft.next(10.0).into()), (0.0, 0.0));
+        assert_eq!(round(ft.next(12.0).into()), (0.343, 0.0));
+        assert_eq!(round(ft.next(11.0).into()), (0.396, 0.343));
+        assert_eq!(round(ft.next(14.0).into()), (0.719, 0.396));
+        assert_eq!(round(ft.next(9.0).into()), (0.35, 0.719));
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut ft = FisherTransform::new(3).unwrap();
+
+        // median prices are 9 and 10.5
+        let bar1 = Bar::new().high(10).low(8);
+        let bar2 = Bar::new().high(12).low(9);
+
+        assert_eq!(round(ft.next(&bar1).into()), (0.0, 0.0));
+        assert_eq!(round(ft.next(&bar2).into()), (0.343, 0.0));
+    }
+
+    #[test]
+    fn test_next_clamped() {
+        let mut ft = FisherTransform::new(2).unwrap();
+
+        for i in 0..100 {
+            let out = ft.next(i as f64);
+            assert!(out.fisher.is_finite());
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ft = FisherTransform::new(3).unwrap();
+
+        ft.next(10.0);
+        ft.next(12.0);
+
+        ft.reset();
+        assert_eq!(round(ft.next(10.0).into()), (0.0, 0.0));
+        assert_eq!(round(ft.next(12.0).into()), (0.343, 0.0));
+    }
+
+    #[test]
+    fn test_default() {
+        FisherTransform::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let ft = FisherTransform::new(10).unwrap();
+        assert_eq!(format!("{}", ft), "FT(10)");
+    }
+}
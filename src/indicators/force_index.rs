@@ -0,0 +1,263 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{ExponentialMovingAverage as Ema, ExponentialMovingAverageState};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+    Volume,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Elder's Force Index (FI).
+///
+/// Uses price and volume to assess the power behind a price move.
+///
+/// # Formula
+///
+/// FI = EMA((Close<sub>t</sub> - Close<sub>t-1</sub>) * Volume<sub>t</sub>, _period_)
+///
+/// The raw force of the very first bar is 0, since there is no previous close.
+///
+/// # Parameters
+///
+/// * _period_ - period of the EMA (integer greater than 0). Default is 13.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::ForceIndex;
+/// use ta::{Next, DataItem};
+///
+/// let mut fi = ForceIndex::new(3).unwrap();
+///
+/// let di1 = DataItem::builder()
+///             .high(10.0).low(6.0).close(9.0).open(7.0)
+///             .volume(1000.0)
+///             .build().unwrap();
+/// let di2 = DataItem::builder()
+///             .high(12.0).low(8.0).close(11.0).open(9.0)
+///             .volume(300.0)
+///             .build().unwrap();
+///
+/// assert_eq!(fi.next(&di1), 0.0);
+/// assert_eq!(fi.next(&di2), 300.0);
+/// ```
+///
+/// # Links
+///
+/// * [Force index, Wikipedia](https://en.wikipedia.org/wiki/Force_index)
+///
+#[doc(alias = "FI")]
+#[derive(Debug, Clone)]
+pub struct ForceIndex {
+    ema: Ema,
+    prev_close: Option<f64>,
+    output: Option<f64>,
+    // Close before the last input, restored by `amend`
+    saved_prev_close: Option<f64>,
+}
+
+/// State of [ForceIndex](struct.ForceIndex.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForceIndexState {
+    pub ema: ExponentialMovingAverageState,
+    pub previous_close: Option<f64>,
+    pub output: Option<f64>,
+    /// Close of the input before the last one, to amend the last one.
+    pub close_before_last: Option<f64>,
+}
+
+impl ForceIndex {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            ema: Ema::new(period)?,
+            prev_close: None,
+            output: None,
+            saved_prev_close: None,
+        })
+    }
+}
+
+impl Period for ForceIndex {
+    fn period(&self) -> usize {
+        self.ema.period()
+    }
+}
+
+impl Checkpoint for ForceIndex {
+    type State = ForceIndexState;
+
+    fn state(&self) -> Self::State {
+        ForceIndexState {
+            ema: self.ema.state(),
+            previous_close: self.prev_close,
+            output: self.output,
+            close_before_last: self.saved_prev_close,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.previous_close.is_none() != state.output.is_none()
+            || state.output.is_none() != (state.ema.count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            ema: Ema::from_state(state.ema)?,
+            prev_close: state.previous_close,
+            output: state.output,
+            saved_prev_close: state.close_before_last,
+        })
+    }
+}
+
+impl_versioned_serde!(ForceIndex, 2);
+
+impl SetPeriod for ForceIndex {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl<T: Close + Volume> Next<&T> for ForceIndex {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_prev_close = self.prev_close;
+        let force = match self.prev_close {
+            Some(prev_close) => (input.close() - prev_close) * input.volume(),
+            None => 0.0,
+        };
+        self.prev_close = Some(input.close());
+
+        let output = self.ema.next(force);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close + Volume> Peek<&T> for ForceIndex {
+    fn peek(&self, input: &T) -> f64 {
+        let force = match self.prev_close {
+            Some(prev_close) => (input.close() - prev_close) * input.volume(),
+            None => 0.0,
+        };
+        self.ema.peek(force)
+    }
+}
+
+impl<T: Close + Volume> Amend<&T> for ForceIndex {
+    fn amend(&mut self, input: &T) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let force = match self.saved_prev_close {
+            Some(prev_close) => (input.close() - prev_close) * input.volume(),
+            None => 0.0,
+        };
+        self.prev_close = Some(input.close());
+        let output = self.ema.amend(force);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Reset for ForceIndex {
+    fn reset(&mut self) {
+        self.ema.reset();
+        self.prev_close = None;
+        self.output = None;
+        self.saved_prev_close = None;
+    }
+}
+
+impl Current for ForceIndex {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for ForceIndex {
+    fn is_ready(&self) -> bool {
+        self.ema.is_ready()
+    }
+}
+
+impl Lookback for ForceIndex {
+    fn lookback(&self) -> usize {
+        self.ema.lookback()
+    }
+}
+
+impl Default for ForceIndex {
+    fn default() -> Self {
+        Self::new(13).unwrap()
+    }
+}
+
+impl fmt::Display for ForceIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FI({})", self.ema.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(ForceIndex::new(0).is_err());
+        assert!(ForceIndex::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut fi = ForceIndex::new(3).unwrap();
+
+        let bar1 = Bar::new().close(9).volume(1000.0);
+        let bar2 = Bar::new().close(11).volume(300.0);
+        let bar3 = Bar::new().close(10).volume(1000.0);
+        let bar4 = Bar::new().close(10).volume(500.0);
+
+        assert_eq!(fi.next(&bar1), 0.0);
+        // raw force = 600
+        assert_eq!(fi.next(&bar2), 300.0);
+        // raw force = -1000
+        assert_eq!(fi.next(&bar3), -350.0);
+        // raw force = 0
+        assert_eq!(fi.next(&bar4), -175.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut fi = ForceIndex::new(3).unwrap();
+
+        let bar1 = Bar::new().close(9).volume(1000.0);
+        let bar2 = Bar::new().close(11).volume(300.0);
+
+        fi.next(&bar1);
+        fi.next(&bar2);
+
+        fi.reset();
+        assert_eq!(fi.next(&bar2), 0.0);
+        assert_eq!(fi.next(&bar1), -1000.0);
+    }
+
+    #[test]
+    fn test_default() {
+        ForceIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let fi = ForceIndex::new(13).unwrap();
+        assert_eq!(format!("{}", fi), "FI(13)");
+    }
+}
@@ -0,0 +1,423 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset,
+    SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Fractal Adaptive Moving Average (FRAMA).
+///
+/// Developed by John Ehlers, FRAMA estimates the fractal dimension of the recent price range
+/// and uses it to adapt the smoothing constant of an exponential moving average: it follows
+/// prices closely in strong trends and flattens out in congestion.
+///
+/// # Formula
+///
+/// N<sub>1</sub> = (max(High) - min(Low)) / (n / 2) over the newer half of the window
+///
+/// N<sub>2</sub> = (max(High) - min(Low)) / (n / 2) over the older half of the window
+///
+/// N<sub>3</sub> = (max(High) - min(Low)) / n over the whole window
+///
+/// D = (ln(N<sub>1</sub> + N<sub>2</sub>) - ln(N<sub>3</sub>)) / ln(2)
+///
+/// α = exp(-4.6 * (D - 1)), limited to 0.01..1
+///
+/// FRAMA<sub>t</sub> = α * Close<sub>t</sub> + (1 - α) * FRAMA<sub>t-1</sub>
+///
+/// Where _n_ is the period. Until the window holds _n_ bars the dimension can not be
+/// estimated, so FRAMA follows the close price. The same happens when there was no price
+/// range in the window (α = 1).
+///
+/// When a single `f64` is passed, it is used as high, low and close.
+///
+/// # Parameters
+///
+/// * _period_ - size of the window (even integer greater than 0). Default is 16.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::FractalAdaptiveMovingAverage;
+/// use ta::Next;
+///
+/// let mut frama = FractalAdaptiveMovingAverage::new(4).unwrap();
+///
+/// assert_eq!(frama.next(10.0), 10.0);
+/// assert_eq!(frama.next(12.0), 12.0);
+/// assert_eq!(frama.next(11.0), 11.0);
+/// assert_eq!((frama.next(14.0) * 1000.0).round() / 1000.0, 11.682);
+/// ```
+///
+/// # Links
+///
+/// * [Fractal Adaptive Moving Average, John Ehlers (PDF)](https://www.mesasoftware.com/papers/FRAMA.pdf)
+///
+#[doc(alias = "FRAMA")]
+#[derive(Debug, Clone)]
+pub struct FractalAdaptiveMovingAverage {
+    period: usize,
+    index: usize,
+    count: usize,
+    current: f64,
+    highs: Box<[f64]>,
+    lows: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: (f64, f64),
+    saved_count: usize,
+    saved_current: f64,
+}
+
+/// State of [FractalAdaptiveMovingAverage](struct.FractalAdaptiveMovingAverage.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FractalAdaptiveMovingAverageState {
+    pub period: usize,
+    /// Highs in the window, the oldest first.
+    pub highs: Vec<f64>,
+    /// Lows in the window, the oldest first.
+    pub lows: Vec<f64>,
+    /// Last output, `None` before the first input.
+    pub current: Option<f64>,
+    /// High and low which the last input pushed out of the window, to amend it.
+    pub evicted: Option<(f64, f64)>,
+    /// Value before the last input, to amend it.
+    pub previous: f64,
+}
+
+impl FractalAdaptiveMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        if period == 0 || period % 2 == 1 {
+            return Err(TaError::invalid_parameter(
+                "period",
+                period as f64,
+                "even and >= 2",
+            ));
+        }
+
+        Ok(Self {
+            period,
+            index: 0,
+            count: 0,
+            current: 0.0,
+            highs: vec![0.0; period].into_boxed_slice(),
+            lows: vec![0.0; period].into_boxed_slice(),
+            output: None,
+            saved_value: (0.0, 0.0),
+            saved_count: 0,
+            saved_current: 0.0,
+        })
+    }
+
+    // Range of highs and lows over bars with ages in `from..to`, where age 0 is the bar
+    // `high` and `low` belong to and the older bars are in the buffers.
+    fn range(&self, (high, low): (f64, f64), from: usize, to: usize) -> f64 {
+        let (mut max, mut min) = if from == 0 {
+            (high, low)
+        } else {
+            (f64::NEG_INFINITY, f64::INFINITY)
+        };
+
+        for age in from.max(1)..to {
+            let i = (self.index + self.period - age) % self.period;
+            max = max.max(self.highs[i]);
+            min = min.min(self.lows[i]);
+        }
+
+        max - min
+    }
+
+    // Value of the average after a bar, without updating it
+    fn value(&self, high: f64, low: f64, close: f64) -> f64 {
+        if self.count + 1 < self.period {
+            return close;
+        }
+
+        let half = self.period / 2;
+        let bar = (high, low);
+        let n1 = self.range(bar, 0, half) / half as f64;
+        let n2 = self.range(bar, half, self.period) / half as f64;
+        let n3 = self.range(bar, 0, self.period) / self.period as f64;
+
+        let alpha = if n3 > 0.0 {
+            let dimension = ((n1 + n2).ln() - n3.ln()) / 2f64.ln();
+            (-4.6 * (dimension - 1.0)).exp().clamp(0.01, 1.0)
+        } else {
+            1.0
+        };
+
+        alpha * close + (1.0 - alpha) * self.current
+    }
+
+    fn calc(&mut self, high: f64, low: f64, close: f64) -> f64 {
+        self.saved_value = (self.highs[self.index], self.lows[self.index]);
+        self.saved_count = self.count;
+        self.saved_current = self.current;
+        self.current = self.value(high, low, close);
+
+        self.highs[self.index] = high;
+        self.lows[self.index] = low;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        if self.count < self.period {
+            self.count += 1;
+        }
+
+        self.current
+    }
+
+    // Goes back to the state before the last input
+    fn restore(&mut self) {
+        self.index = (self.index + self.period - 1) % self.period;
+        (self.highs[self.index], self.lows[self.index]) = self.saved_value;
+        self.count = self.saved_count;
+        self.current = self.saved_current;
+    }
+}
+
+impl Period for FractalAdaptiveMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for FractalAdaptiveMovingAverage {
+    type State = FractalAdaptiveMovingAverageState;
+
+    fn state(&self) -> Self::State {
+        FractalAdaptiveMovingAverageState {
+            period: self.period,
+            highs: ring_values(&self.highs, self.index, self.count),
+            lows: ring_values(&self.lows, self.index, self.count),
+            current: self.output,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous: self.saved_current,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.highs.len() != state.lows.len()
+            || state.current.is_some() == state.highs.is_empty()
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let (highs, index) = ring_from_values(state.period, &state.highs)?;
+        let (lows, _) = ring_from_values(state.period, &state.lows)?;
+        indicator.highs = highs;
+        indicator.lows = lows;
+        indicator.index = index;
+        indicator.count = state.highs.len();
+        indicator.current = state.current.unwrap_or(0.0);
+        indicator.output = state.current;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or((0.0, 0.0));
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_current = state.previous;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(FractalAdaptiveMovingAverage, 2);
+
+impl SetPeriod for FractalAdaptiveMovingAverage {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for FractalAdaptiveMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let output = self.calc(input, input, input);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for FractalAdaptiveMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let output = self.calc(input.high(), input.low(), input.close());
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Peek<f64> for FractalAdaptiveMovingAverage {
+    fn peek(&self, input: f64) -> f64 {
+        self.value(input, input, input)
+    }
+}
+
+impl<T: High + Low + Close> Peek<&T> for FractalAdaptiveMovingAverage {
+    fn peek(&self, input: &T) -> f64 {
+        self.value(input.high(), input.low(), input.close())
+    }
+}
+
+impl Amend<f64> for FractalAdaptiveMovingAverage {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.restore();
+        self.next(input)
+    }
+}
+
+impl<T: High + Low + Close> Amend<&T> for FractalAdaptiveMovingAverage {
+    fn amend(&mut self, input: &T) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.restore();
+        self.next(input)
+    }
+}
+
+impl Reset for FractalAdaptiveMovingAverage {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.current = 0.0;
+        for i in 0..self.period {
+            self.highs[i] = 0.0;
+            self.lows[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = (0.0, 0.0);
+        self.saved_count = 0;
+        self.saved_current = 0.0;
+    }
+}
+
+impl Current for FractalAdaptiveMovingAverage {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for FractalAdaptiveMovingAverage {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for FractalAdaptiveMovingAverage {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for FractalAdaptiveMovingAverage {
+    fn default() -> Self {
+        Self::new(16).unwrap()
+    }
+}
+
+impl fmt::Display for FractalAdaptiveMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FRAMA({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(FractalAdaptiveMovingAverage);
+
+    #[test]
+    fn test_new() {
+        assert!(FractalAdaptiveMovingAverage::new(0).is_err());
+        assert!(FractalAdaptiveMovingAverage::new(1).is_err());
+        assert!(FractalAdaptiveMovingAverage::new(15).is_err());
+        assert!(FractalAdaptiveMovingAverage::new(2).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut frama = FractalAdaptiveMovingAverage::new(4).unwrap();
+
+        assert_eq!(frama.next(10.0), 10.0);
+        assert_eq!(frama.next(12.0), 12.0);
+        assert_eq!(frama.next(11.0), 11.0);
+        assert_eq!(round(frama.next(14.0)), 11.682);
+        assert_eq!(round(frama.next(9.0)), 10.882);
+        assert_eq!(round(frama.next(13.0)), 11.109);
+        assert_eq!(round(frama.next(12.0)), 11.375);
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut frama = FractalAdaptiveMovingAverage::new(4).unwrap();
+
+        let bar1 = Bar::new().high(11).low(9).close(10);
+        let bar2 = Bar::new().high(13).low(10).close(12);
+        let bar3 = Bar::new().high(12).low(10).close(11);
+        let bar4 = Bar::new().high(15).low(12).close(14);
+        let bar5 = Bar::new().high(10).low(8).close(9);
+
+        assert_eq!(frama.next(&bar1), 10.0);
+        assert_eq!(frama.next(&bar2), 12.0);
+        assert_eq!(frama.next(&bar3), 11.0);
+        assert_eq!(round(frama.next(&bar4)), 11.203);
+        assert_eq!(round(frama.next(&bar5)), 10.997);
+    }
+
+    #[test]
+    fn test_next_flat() {
+        let mut frama = FractalAdaptiveMovingAverage::new(2).unwrap();
+
+        assert_eq!(frama.next(5.0), 5.0);
+        assert_eq!(frama.next(5.0), 5.0);
+        assert_eq!(frama.next(5.0), 5.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut frama = FractalAdaptiveMovingAverage::new(4).unwrap();
+
+        for x in &[10.0, 12.0, 11.0, 14.0] {
+            frama.next(*x);
+        }
+
+        frama.reset();
+        assert_eq!(frama.next(9.0), 9.0);
+        assert_eq!(frama.next(13.0), 13.0);
+    }
+
+    #[test]
+    fn test_default() {
+        FractalAdaptiveMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let frama = FractalAdaptiveMovingAverage::new(16).unwrap();
+        assert_eq!(format!("{}", frama), "FRAMA(16)");
+    }
+}
@@ -0,0 +1,367 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Fractal Dimension Index (FDI).
+///
+/// Estimates the fractal dimension of the price path over the last _period_ values with
+/// Sevcik's method: the path is scaled into a unit square and its length is measured. A
+/// smooth, trending path has a dimension close to 1, a random walk one close to 1.5 and a
+/// choppy, mean reverting path one close to 2. Unlike the
+/// [FRAMA](struct.FractalAdaptiveMovingAverage.html) estimate, it only needs the close price.
+///
+/// # Formula
+///
+/// y<sub>i</sub> = (Close<sub>i</sub> - min(Close)) / (max(Close) - min(Close))
+///
+/// L = Σ √((y<sub>i</sub> - y<sub>i-1</sub>)<sup>2</sup> + 1 / (n - 1)<sup>2</sup>)
+///
+/// FDI = 1 + (ln(L) + ln(2)) / ln(2 * (n - 1))
+///
+/// Where _n_ is the period and min and max are taken over the window. The result is limited
+/// to 1..2. Until the window is full the dimension can not be estimated and 1.5 is returned.
+/// When the price did not change within the window, the path is a straight line and 1 is
+/// returned.
+///
+/// # Parameters
+///
+/// * _period_ - size of the window (integer greater than 1). Default is 30.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::FractalDimensionIndex;
+/// use ta::Next;
+///
+/// let mut fdi = FractalDimensionIndex::new(5).unwrap();
+///
+/// for &value in &[1.0, 2.0, 3.0, 4.0] {
+///     assert_eq!(fdi.next(value), 1.5);
+/// }
+/// assert_eq!((fdi.next(3.0) * 1000.0).round() / 1000.0, 1.579);
+/// ```
+///
+/// # Links
+///
+/// * [Fractal dimension, Wikipedia](https://en.wikipedia.org/wiki/Fractal_dimension)
+///
+#[doc(alias = "FDI")]
+#[derive(Debug, Clone)]
+pub struct FractalDimensionIndex {
+    period: usize,
+    index: usize,
+    count: usize,
+    deque: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+}
+
+/// State of [FractalDimensionIndex](struct.FractalDimensionIndex.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FractalDimensionIndexState {
+    pub period: usize,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<f64>,
+    /// Last output, `None` before the first input.
+    pub output: Option<f64>,
+    /// Input which the last one pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
+}
+
+impl FractalDimensionIndex {
+    pub fn new(period: usize) -> Result<Self> {
+        if period < 2 {
+            return Err(TaError::invalid_parameter("period", period as f64, ">= 2"));
+        }
+        Ok(Self {
+            period,
+            index: 0,
+            count: 0,
+            deque: vec![0.0; period].into_boxed_slice(),
+            output: None,
+            saved_value: 0.0,
+            saved_count: 0,
+        })
+    }
+
+    // Dimension of a full window of values, the oldest first
+    fn dimension(&self, values: impl Iterator<Item = f64> + Clone) -> f64 {
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        for value in values.clone() {
+            min = min.min(value);
+            max = max.max(value);
+        }
+        let range = max - min;
+        if range <= 0.0 {
+            return 1.0;
+        }
+
+        let steps = (self.period - 1) as f64;
+        let dx = 1.0 / (steps * steps);
+        let mut length = 0.0;
+        let mut values = values;
+        let mut prev = values.next().unwrap_or_default();
+        for value in values {
+            let dy = (value - prev) / range;
+            length += (dy * dy + dx).sqrt();
+            prev = value;
+        }
+
+        (1.0 + (length.ln() + 2f64.ln()) / (2.0 * steps).ln()).clamp(1.0, 2.0)
+    }
+}
+
+impl Period for FractalDimensionIndex {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for FractalDimensionIndex {
+    type State = FractalDimensionIndexState;
+
+    fn state(&self) -> Self::State {
+        FractalDimensionIndexState {
+            period: self.period,
+            inputs: ring_values(&self.deque, self.index, self.count),
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.inputs.is_empty() {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let (deque, index) = ring_from_values(state.period, &state.inputs)?;
+        indicator.deque = deque;
+        indicator.index = index;
+        indicator.count = state.inputs.len();
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(FractalDimensionIndex, 2);
+
+impl SetPeriod for FractalDimensionIndex {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for FractalDimensionIndex {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
+        self.deque[self.index] = input;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        self.count = (self.count + 1).min(self.period);
+
+        let output = if self.count < self.period {
+            1.5
+        } else {
+            // the oldest value is at `self.index`
+            self.dimension((0..self.period).map(|i| self.deque[(self.index + i) % self.period]))
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for FractalDimensionIndex {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for FractalDimensionIndex {
+    fn peek(&self, input: f64) -> f64 {
+        if self.count + 1 < self.period {
+            return 1.5;
+        }
+        // the oldest value at `self.index` leaves the window
+        let older = (1..self.period).map(|i| self.deque[(self.index + i) % self.period]);
+        self.dimension(older.chain(core::iter::once(input)))
+    }
+}
+
+impl<T: Close> Peek<&T> for FractalDimensionIndex {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for FractalDimensionIndex {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for FractalDimensionIndex {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for FractalDimensionIndex {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+    }
+}
+
+impl Current for FractalDimensionIndex {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for FractalDimensionIndex {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for FractalDimensionIndex {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for FractalDimensionIndex {
+    fn default() -> Self {
+        Self::new(30).unwrap()
+    }
+}
+
+impl fmt::Display for FractalDimensionIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FDI({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(FractalDimensionIndex);
+
+    #[test]
+    fn test_new() {
+        assert!(FractalDimensionIndex::new(0).is_err());
+        assert!(FractalDimensionIndex::new(1).is_err());
+        assert!(FractalDimensionIndex::new(2).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut fdi = FractalDimensionIndex::new(5).unwrap();
+
+        for &value in &[1.0, 2.0, 3.0, 4.0] {
+            assert_eq!(fdi.next(value), 1.5);
+        }
+        assert_eq!(round(fdi.next(3.0)), 1.579);
+        assert_eq!(round(fdi.next(5.0)), 1.657);
+    }
+
+    #[test]
+    fn test_next_extremes() {
+        let mut fdi = FractalDimensionIndex::new(5).unwrap();
+
+        // Straight line
+        for i in 0..5 {
+            fdi.next(i as f64);
+        }
+        assert_eq!(round(fdi.next(5.0)), 1.5);
+
+        // Zigzag
+        for &value in &[1.0, 5.0, 1.0, 5.0] {
+            fdi.next(value);
+        }
+        assert_eq!(fdi.next(1.0), 2.0);
+
+        // Flat
+        for _ in 0..5 {
+            fdi.next(3.0);
+        }
+        assert_eq!(fdi.next(3.0), 1.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut fdi = FractalDimensionIndex::new(5).unwrap();
+
+        for &close in &[1.0, 2.0, 3.0, 4.0] {
+            fdi.next(&Bar::new().close(close));
+        }
+        assert_eq!(round(fdi.next(&Bar::new().close(3))), 1.579);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut fdi = FractalDimensionIndex::new(3).unwrap();
+
+        fdi.next(1.0);
+        fdi.next(2.0);
+        fdi.next(3.0);
+
+        fdi.reset();
+        assert_eq!(fdi.next(1.0), 1.5);
+        assert_eq!(fdi.next(1.0), 1.5);
+        assert_eq!(fdi.next(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        FractalDimensionIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let fdi = FractalDimensionIndex::new(30).unwrap();
+        assert_eq!(format!("{}", fdi), "FDI(30)");
+    }
+}
@@ -0,0 +1,264 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{Alligator, AlligatorState};
+use crate::{Amend, Checkpoint, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Gator Oscillator.
+///
+/// Histogram of the distances between the lines of the [Alligator](struct.Alligator.html). The
+/// upper bars show the distance between the jaw and the teeth, the lower bars the distance
+/// between the teeth and the lips, drawn below zero. Growing bars on both sides mean the
+/// alligator is eating (a trend), shrinking bars mean it is falling asleep.
+///
+/// # Formula
+///
+/// Upper = |Jaw - Teeth|
+///
+/// Lower = -|Teeth - Lips|
+///
+/// # Parameters
+///
+/// Same as for the [Alligator](struct.Alligator.html). Default are 13, 8, 8, 5, 5, 3.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::GatorOscillator;
+/// use ta::Next;
+///
+/// let mut gator = GatorOscillator::new(3, 2, 2, 1, 1, 0).unwrap();
+///
+/// gator.next(2.0);
+/// gator.next(4.0);
+/// let out = gator.next(6.0);
+///
+/// assert_eq!(out.upper, 1.0);
+/// assert_eq!(out.lower, -3.0);
+/// ```
+#[doc(alias = "GATOR")]
+#[derive(Debug, Clone, Default)]
+pub struct GatorOscillator {
+    alligator: Alligator,
+    output: Option<GatorOscillatorOutput>,
+}
+
+/// State of [GatorOscillator](struct.GatorOscillator.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GatorOscillatorState {
+    pub alligator: AlligatorState,
+    pub output: Option<GatorOscillatorOutput>,
+}
+
+/// Output of [GatorOscillator](struct.GatorOscillator.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GatorOscillatorOutput {
+    pub upper: f64,
+    pub lower: f64,
+}
+
+impl_output!(GatorOscillatorOutput, upper, lower);
+
+impl From<GatorOscillatorOutput> for (f64, f64) {
+    fn from(gator: GatorOscillatorOutput) -> Self {
+        (gator.upper, gator.lower)
+    }
+}
+
+impl GatorOscillator {
+    pub fn new(
+        jaw_period: usize,
+        jaw_shift: usize,
+        teeth_period: usize,
+        teeth_shift: usize,
+        lips_period: usize,
+        lips_shift: usize,
+    ) -> Result<Self> {
+        Ok(Self {
+            alligator: Alligator::new(
+                jaw_period,
+                jaw_shift,
+                teeth_period,
+                teeth_shift,
+                lips_period,
+                lips_shift,
+            )?,
+            output: None,
+        })
+    }
+}
+
+impl Period for GatorOscillator {
+    fn period(&self) -> usize {
+        self.alligator.period()
+    }
+}
+
+impl Checkpoint for GatorOscillator {
+    type State = GatorOscillatorState;
+
+    fn state(&self) -> Self::State {
+        GatorOscillatorState {
+            alligator: self.alligator.state(),
+            output: self.output.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_none() != state.alligator.output.is_none() {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            alligator: Alligator::from_state(state.alligator)?,
+            output: state.output,
+        })
+    }
+}
+
+impl_versioned_serde!(GatorOscillator, 2);
+
+impl Next<f64> for GatorOscillator {
+    type Output = GatorOscillatorOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let lines = self.alligator.next(input);
+        let output = GatorOscillatorOutput {
+            upper: (lines.jaw - lines.teeth).abs(),
+            lower: -(lines.teeth - lines.lips).abs(),
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low> Next<&T> for GatorOscillator {
+    type Output = GatorOscillatorOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next((input.high() + input.low()) / 2.0)
+    }
+}
+
+impl Peek<f64> for GatorOscillator {
+    fn peek(&self, input: f64) -> GatorOscillatorOutput {
+        let lines = self.alligator.peek(input);
+        GatorOscillatorOutput {
+            upper: (lines.jaw - lines.teeth).abs(),
+            lower: -(lines.teeth - lines.lips).abs(),
+        }
+    }
+}
+
+impl<T: High + Low> Peek<&T> for GatorOscillator {
+    fn peek(&self, input: &T) -> GatorOscillatorOutput {
+        self.peek((input.high() + input.low()) / 2.0)
+    }
+}
+
+impl Amend<f64> for GatorOscillator {
+    fn amend(&mut self, input: f64) -> GatorOscillatorOutput {
+        let lines = self.alligator.amend(input);
+        let output = GatorOscillatorOutput {
+            upper: (lines.jaw - lines.teeth).abs(),
+            lower: -(lines.teeth - lines.lips).abs(),
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low> Amend<&T> for GatorOscillator {
+    fn amend(&mut self, input: &T) -> GatorOscillatorOutput {
+        self.amend((input.high() + input.low()) / 2.0)
+    }
+}
+
+impl Reset for GatorOscillator {
+    fn reset(&mut self) {
+        self.alligator.reset();
+        self.output = None;
+    }
+}
+
+impl Current for GatorOscillator {
+    type Output = GatorOscillatorOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for GatorOscillator {
+    fn is_ready(&self) -> bool {
+        self.alligator.is_ready()
+    }
+}
+
+impl Lookback for GatorOscillator {
+    fn lookback(&self) -> usize {
+        self.alligator.lookback()
+    }
+}
+
+impl fmt::Display for GatorOscillator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let alligator = self.alligator.to_string();
+        write!(f, "GATOR{}", alligator.trim_start_matches("ALLIGATOR"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(GatorOscillator);
+
+    #[test]
+    fn test_new() {
+        assert!(GatorOscillator::new(0, 8, 8, 5, 5, 3).is_err());
+        assert!(GatorOscillator::new(13, 8, 8, 5, 5, 3).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut gator = GatorOscillator::new(2, 2, 2, 1, 1, 0).unwrap();
+
+        let out = gator.next(&Bar::new().high(3).low(1));
+        assert_eq!((out.upper, out.lower), (0.0, 0.0));
+        let out = gator.next(&Bar::new().high(5).low(3));
+        assert_eq!((out.upper, out.lower), (0.0, -2.0));
+        let out = gator.next(&Bar::new().high(7).low(5));
+        assert_eq!((out.upper, out.lower), (1.0, -3.0));
+        let out = gator.next(&Bar::new().high(9).low(7));
+        assert_eq!((out.upper, out.lower), (1.5, -3.5));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut gator = GatorOscillator::new(2, 2, 2, 1, 1, 0).unwrap();
+
+        gator.next(2.0);
+        gator.next(4.0);
+        gator.reset();
+
+        let out = gator.next(10.0);
+        assert_eq!((out.upper, out.lower), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_default() {
+        GatorOscillator::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let gator = GatorOscillator::default();
+        assert_eq!(format!("{}", gator), "GATOR(13, 8, 8, 5, 5, 3)");
+    }
+}
@@ -0,0 +1,21 @@
+//! Indicators generic over their [value type](../../trait.Numeric.html).
+//!
+//! [indicators](../index.html) exports each of them for `f64` under the same name.
+
+pub use super::average_true_range::AverageTrueRange;
+#[cfg(feature = "std")]
+pub use super::bollinger_bands::BollingerBands;
+pub use super::exponential_moving_average::ExponentialMovingAverage;
+#[cfg(feature = "std")]
+pub use super::maximum::Maximum;
+#[cfg(feature = "std")]
+pub use super::minimum::Minimum;
+pub use super::relative_strength_index::RelativeStrengthIndex;
+#[cfg(feature = "std")]
+pub use super::simple_moving_average::SimpleMovingAverage;
+pub use super::smoothed_simple_moving_average::SmoothedSimpleMovingAverage;
+#[cfg(feature = "std")]
+pub use super::standard_deviation::StandardDeviation;
+pub use super::true_range::TrueRange;
+#[cfg(feature = "std")]
+pub use super::weighted_moving_average::WeightedMovingAverage;
@@ -0,0 +1,325 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Geometric moving average (GMA).
+///
+/// The geometric mean of the last _period_ values. It is the right average for quantities that
+/// compound, such as growth factors (1 + return): the GMA of the growth factors is the
+/// constant growth factor that gives the same total growth.
+///
+/// The window is kept as a rolling sum of logarithms. Inputs must be greater than 0.
+///
+/// # Formula
+///
+/// GMA<sub>t</sub> = (p<sub>t</sub> × p<sub>t-1</sub> × ... × p<sub>t-n+1</sub>)<sup>1/n</sup>
+/// = exp(Σln(p<sub>t-i</sub>) / n)
+///
+/// Where:
+///
+/// * _n_ - _period_, or the number of values seen so far if it is smaller
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::GeometricMovingAverage;
+/// use ta::Next;
+///
+/// let mut gma = GeometricMovingAverage::new(2).unwrap();
+/// assert_eq!(gma.next(2.0).round(), 2.0);
+/// assert_eq!(gma.next(8.0).round(), 4.0);
+/// assert_eq!(gma.next(18.0).round(), 12.0);
+/// ```
+///
+/// # Links
+///
+/// * [Geometric mean, Wikipedia](https://en.wikipedia.org/wiki/Geometric_mean)
+///
+#[doc(alias = "GMA")]
+#[derive(Debug, Clone)]
+pub struct GeometricMovingAverage {
+    period: usize,
+    index: usize,
+    count: usize,
+    log_sum: f64,
+    deque: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+    saved_log_sum: f64,
+}
+
+/// State of [GeometricMovingAverage](struct.GeometricMovingAverage.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeometricMovingAverageState {
+    pub period: usize,
+    /// Logarithms of the inputs in the window, the oldest first.
+    pub logs: Vec<f64>,
+    /// Running sum of the logarithms.
+    pub log_sum: f64,
+    /// Logarithm which the last input pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
+    /// Running sum before the last input, to amend it.
+    pub previous_log_sum: f64,
+}
+
+impl GeometricMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                log_sum: 0.0,
+                deque: vec![0.0; period].into_boxed_slice(),
+                output: None,
+                saved_value: 0.0,
+                saved_count: 0,
+                saved_log_sum: 0.0,
+            }),
+        }
+    }
+}
+
+impl Period for GeometricMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for GeometricMovingAverage {
+    type State = GeometricMovingAverageState;
+
+    fn state(&self) -> Self::State {
+        GeometricMovingAverageState {
+            period: self.period,
+            logs: ring_values(&self.deque, self.index, self.count),
+            log_sum: self.log_sum,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous_log_sum: self.saved_log_sum,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.logs.is_empty() && state.log_sum != 0.0 {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let (deque, index) = ring_from_values(state.period, &state.logs)?;
+        let (count, sum) = (state.logs.len(), state.log_sum);
+        indicator.deque = deque;
+        indicator.index = index;
+        indicator.count = count;
+        indicator.log_sum = sum;
+        indicator.output = (count > 0).then(|| (sum / count as f64).exp());
+        if state.evicted.is_some() && count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            Some(_) => count,
+            None => count.saturating_sub(1),
+        };
+        indicator.saved_log_sum = state.previous_log_sum;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(GeometricMovingAverage, 2);
+
+impl SetPeriod for GeometricMovingAverage {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for GeometricMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
+        self.saved_log_sum = self.log_sum;
+        let log = input.ln();
+        let old_log = self.deque[self.index];
+        self.deque[self.index] = log;
+
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        if self.count < self.period {
+            self.count += 1;
+            self.log_sum += log;
+        } else {
+            self.log_sum += log - old_log;
+        }
+
+        let output = (self.log_sum / self.count as f64).exp();
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for GeometricMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for GeometricMovingAverage {
+    fn peek(&self, input: f64) -> f64 {
+        let log = input.ln();
+        if self.count < self.period {
+            ((self.log_sum + log) / (self.count + 1) as f64).exp()
+        } else {
+            ((self.log_sum + (log - self.deque[self.index])) / self.count as f64).exp()
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for GeometricMovingAverage {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for GeometricMovingAverage {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.log_sum = self.saved_log_sum;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for GeometricMovingAverage {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for GeometricMovingAverage {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.log_sum = 0.0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+        self.saved_log_sum = 0.0;
+    }
+}
+
+impl Current for GeometricMovingAverage {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for GeometricMovingAverage {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for GeometricMovingAverage {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for GeometricMovingAverage {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for GeometricMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GMA({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(GeometricMovingAverage);
+
+    #[test]
+    fn test_new() {
+        assert!(GeometricMovingAverage::new(0).is_err());
+        assert!(GeometricMovingAverage::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut gma = GeometricMovingAverage::new(3).unwrap();
+
+        assert_eq!(round(gma.next(1.0)), 1.0);
+        assert_eq!(round(gma.next(4.0)), 2.0);
+        assert_eq!(round(gma.next(16.0)), 4.0);
+        assert_eq!(round(gma.next(&Bar::new().close(2))), 5.04);
+        assert_eq!(round(gma.next(&Bar::new().close(0.5))), 2.52);
+    }
+
+    #[test]
+    fn test_growth_factors() {
+        // +10% and -10% do not cancel out
+        let mut gma = GeometricMovingAverage::new(2).unwrap();
+        gma.next(1.1);
+        assert_eq!(round(gma.next(0.9)), 0.995);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut gma = GeometricMovingAverage::new(3).unwrap();
+
+        gma.next(4.0);
+        gma.next(16.0);
+        gma.reset();
+        assert_eq!(round(gma.next(9.0)), 9.0);
+    }
+
+    #[test]
+    fn test_default() {
+        GeometricMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let gma = GeometricMovingAverage::new(5).unwrap();
+        assert_eq!(format!("{}", gma), "GMA(5)");
+    }
+}
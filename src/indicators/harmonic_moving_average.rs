@@ -0,0 +1,325 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Harmonic moving average.
+///
+/// The harmonic mean of the last _period_ values. It is the right average for rates and
+/// ratios, such as P/E ratios or prices of a fixed amount of money invested at regular
+/// intervals: the HMA of the prices is the average price paid.
+///
+/// The window is kept as a rolling sum of reciprocals. Inputs must not be 0.
+///
+/// # Formula
+///
+/// HMA<sub>t</sub> = n / Σ(1 / p<sub>t-i</sub>)
+///
+/// Where:
+///
+/// * _n_ - _period_, or the number of values seen so far if it is smaller
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::HarmonicMovingAverage;
+/// use ta::Next;
+///
+/// let mut hma = HarmonicMovingAverage::new(2).unwrap();
+/// assert_eq!(hma.next(2.0), 2.0);
+/// assert_eq!(hma.next(6.0).round(), 3.0);
+/// assert_eq!(hma.next(3.0).round(), 4.0);
+/// ```
+///
+/// # Links
+///
+/// * [Harmonic mean, Wikipedia](https://en.wikipedia.org/wiki/Harmonic_mean)
+///
+#[doc(alias = "HARMONIC")]
+#[derive(Debug, Clone)]
+pub struct HarmonicMovingAverage {
+    period: usize,
+    index: usize,
+    count: usize,
+    reciprocal_sum: f64,
+    deque: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+    saved_reciprocal_sum: f64,
+}
+
+/// State of [HarmonicMovingAverage](struct.HarmonicMovingAverage.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HarmonicMovingAverageState {
+    pub period: usize,
+    /// Reciprocals of the inputs in the window, the oldest first.
+    pub reciprocals: Vec<f64>,
+    /// Running sum of the reciprocals.
+    pub reciprocal_sum: f64,
+    /// Reciprocal which the last input pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
+    /// Running sum before the last input, to amend it.
+    pub previous_reciprocal_sum: f64,
+}
+
+impl HarmonicMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                reciprocal_sum: 0.0,
+                deque: vec![0.0; period].into_boxed_slice(),
+                output: None,
+                saved_value: 0.0,
+                saved_count: 0,
+                saved_reciprocal_sum: 0.0,
+            }),
+        }
+    }
+}
+
+impl Period for HarmonicMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for HarmonicMovingAverage {
+    type State = HarmonicMovingAverageState;
+
+    fn state(&self) -> Self::State {
+        HarmonicMovingAverageState {
+            period: self.period,
+            reciprocals: ring_values(&self.deque, self.index, self.count),
+            reciprocal_sum: self.reciprocal_sum,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous_reciprocal_sum: self.saved_reciprocal_sum,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.reciprocals.is_empty() && state.reciprocal_sum != 0.0 {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let (deque, index) = ring_from_values(state.period, &state.reciprocals)?;
+        let (count, sum) = (state.reciprocals.len(), state.reciprocal_sum);
+        indicator.deque = deque;
+        indicator.index = index;
+        indicator.count = count;
+        indicator.reciprocal_sum = sum;
+        indicator.output = (count > 0).then(|| count as f64 / sum);
+        if state.evicted.is_some() && count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            Some(_) => count,
+            None => count.saturating_sub(1),
+        };
+        indicator.saved_reciprocal_sum = state.previous_reciprocal_sum;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(HarmonicMovingAverage, 2);
+
+impl SetPeriod for HarmonicMovingAverage {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for HarmonicMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
+        self.saved_reciprocal_sum = self.reciprocal_sum;
+        let reciprocal = 1.0 / input;
+        let old_reciprocal = self.deque[self.index];
+        self.deque[self.index] = reciprocal;
+
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        if self.count < self.period {
+            self.count += 1;
+            self.reciprocal_sum += reciprocal;
+        } else {
+            self.reciprocal_sum += reciprocal - old_reciprocal;
+        }
+
+        let output = self.count as f64 / self.reciprocal_sum;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for HarmonicMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for HarmonicMovingAverage {
+    fn peek(&self, input: f64) -> f64 {
+        let reciprocal = 1.0 / input;
+        if self.count < self.period {
+            (self.count + 1) as f64 / (self.reciprocal_sum + reciprocal)
+        } else {
+            self.count as f64 / (self.reciprocal_sum + (reciprocal - self.deque[self.index]))
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for HarmonicMovingAverage {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for HarmonicMovingAverage {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.reciprocal_sum = self.saved_reciprocal_sum;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for HarmonicMovingAverage {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for HarmonicMovingAverage {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.reciprocal_sum = 0.0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+        self.saved_reciprocal_sum = 0.0;
+    }
+}
+
+impl Current for HarmonicMovingAverage {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for HarmonicMovingAverage {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for HarmonicMovingAverage {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for HarmonicMovingAverage {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for HarmonicMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HARMONIC({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(HarmonicMovingAverage);
+
+    #[test]
+    fn test_new() {
+        assert!(HarmonicMovingAverage::new(0).is_err());
+        assert!(HarmonicMovingAverage::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut hma = HarmonicMovingAverage::new(3).unwrap();
+
+        assert_eq!(round(hma.next(1.0)), 1.0);
+        assert_eq!(round(hma.next(2.0)), 1.333);
+        assert_eq!(round(hma.next(4.0)), 1.714);
+        assert_eq!(round(hma.next(&Bar::new().close(4))), 3.0);
+        assert_eq!(round(hma.next(&Bar::new().close(8))), 4.8);
+    }
+
+    #[test]
+    fn test_average_price() {
+        // Investing 120 at prices 10, 20 and 40 buys 12 + 6 + 3 = 21 units
+        let mut hma = HarmonicMovingAverage::new(3).unwrap();
+        hma.next(10.0);
+        hma.next(20.0);
+        assert_eq!(round(hma.next(40.0)), round(360.0 / 21.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut hma = HarmonicMovingAverage::new(3).unwrap();
+
+        hma.next(4.0);
+        hma.next(16.0);
+        hma.reset();
+        assert_eq!(hma.next(9.0), 9.0);
+    }
+
+    #[test]
+    fn test_default() {
+        HarmonicMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let hma = HarmonicMovingAverage::new(5).unwrap();
+        assert_eq!(format!("{}", hma), "HARMONIC(5)");
+    }
+}
@@ -0,0 +1,305 @@
+use crate::errors::{Result, TaError};
+use crate::Checkpoint;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Number of bars before the transform produces the first output.
+const WARMUP: usize = 5;
+
+/// Number of bars until the smoothed price, detrender and I1/Q1 histories are filled.
+const READY: usize = WARMUP + 3 * 6 + 1;
+
+/// Ehlers' Hilbert transform pipeline, shared by the cycle based indicators.
+///
+/// It smooths the price, removes the trend and splits the result into the in-phase (I1) and
+/// quadrature (Q1) components, from which the dominant cycle period is measured with the
+/// homodyne discriminator.
+#[derive(Debug, Clone)]
+pub(crate) struct HilbertTransform {
+    count: usize,
+    // histories hold the newest value at index 0
+    prices: [f64; 4],
+    smooth: [f64; 7],
+    detrender: [f64; 7],
+    i1: [f64; 7],
+    q1: [f64; 7],
+    i2: f64,
+    q2: f64,
+    re: f64,
+    im: f64,
+    period: f64,
+    smooth_period: f64,
+    // State before the last price, restored by `amend`
+    saved_count: usize,
+    saved_evicted: [f64; 5],
+    saved_scalars: [f64; 6],
+}
+
+/// State of the Hilbert transform of [DominantCyclePeriod](struct.DominantCyclePeriod.html) and
+/// [MesaAdaptiveMovingAverage](struct.MesaAdaptiveMovingAverage.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+///
+/// The histories hold the newest value first.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HilbertTransformState {
+    /// Number of prices, up to the number needed to fill the histories.
+    pub count: usize,
+    pub prices: [f64; 4],
+    pub smooth: [f64; 7],
+    pub detrender: [f64; 7],
+    pub i1: [f64; 7],
+    pub q1: [f64; 7],
+    pub i2: f64,
+    pub q2: f64,
+    pub re: f64,
+    pub im: f64,
+    pub period: f64,
+    pub smooth_period: f64,
+    /// Count before the last price, to amend it.
+    pub previous_count: usize,
+    /// Oldest values which the last price pushed out of the prices, smooth, detrender, i1 and
+    /// q1 histories, to amend it.
+    pub evicted: [f64; 5],
+    /// I2, Q2, Re, Im, period and smooth period before the last price, to amend it.
+    pub previous: [f64; 6],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct HilbertTransformOutput {
+    pub in_phase: f64,
+    pub quadrature: f64,
+    pub period: f64,
+    pub smooth_period: f64,
+}
+
+fn push<const N: usize>(history: &mut [f64; N], value: f64) {
+    history.rotate_right(1);
+    history[0] = value;
+}
+
+// Undoes `push`, putting back the oldest value
+fn pop<const N: usize>(history: &mut [f64; N], oldest: f64) {
+    history.rotate_left(1);
+    history[N - 1] = oldest;
+}
+
+fn hilbert(history: &[f64; 7], adjustment: f64) -> f64 {
+    (0.0962 * history[0] + 0.5769 * history[2] - 0.5769 * history[4] - 0.0962 * history[6])
+        * adjustment
+}
+
+impl HilbertTransform {
+    pub fn new() -> Self {
+        Self {
+            count: 0,
+            prices: [0.0; 4],
+            smooth: [0.0; 7],
+            detrender: [0.0; 7],
+            i1: [0.0; 7],
+            q1: [0.0; 7],
+            i2: 0.0,
+            q2: 0.0,
+            re: 0.0,
+            im: 0.0,
+            period: 0.0,
+            smooth_period: 0.0,
+            saved_count: 0,
+            saved_evicted: [0.0; 5],
+            saved_scalars: [0.0; 6],
+        }
+    }
+
+    /// Returns `None` until enough prices are available.
+    pub fn next(&mut self, price: f64) -> Option<HilbertTransformOutput> {
+        self.saved_count = self.count;
+        self.saved_evicted = [
+            self.prices[3],
+            self.smooth[6],
+            self.detrender[6],
+            self.i1[6],
+            self.q1[6],
+        ];
+        self.saved_scalars = [
+            self.i2,
+            self.q2,
+            self.re,
+            self.im,
+            self.period,
+            self.smooth_period,
+        ];
+        push(&mut self.prices, price);
+        if self.count < READY {
+            self.count += 1;
+        }
+        if self.count <= WARMUP {
+            return None;
+        }
+
+        let p = &self.prices;
+        push(
+            &mut self.smooth,
+            (4.0 * p[0] + 3.0 * p[1] + 2.0 * p[2] + p[3]) / 10.0,
+        );
+
+        let adjustment = 0.075 * self.period + 0.54;
+        push(&mut self.detrender, hilbert(&self.smooth, adjustment));
+
+        let i1 = self.detrender[3];
+        let q1 = hilbert(&self.detrender, adjustment);
+        push(&mut self.i1, i1);
+        push(&mut self.q1, q1);
+
+        // advance the phase of I1 and Q1 by 90 degrees
+        let ji = hilbert(&self.i1, adjustment);
+        let jq = hilbert(&self.q1, adjustment);
+
+        let i2 = 0.2 * (i1 - jq) + 0.8 * self.i2;
+        let q2 = 0.2 * (q1 + ji) + 0.8 * self.q2;
+
+        // homodyne discriminator
+        let re = i2 * self.i2 + q2 * self.q2;
+        let im = i2 * self.q2 - q2 * self.i2;
+        self.i2 = i2;
+        self.q2 = q2;
+        self.re = 0.2 * re + 0.8 * self.re;
+        self.im = 0.2 * im + 0.8 * self.im;
+
+        let prev_period = self.period;
+        let mut period = prev_period;
+        if self.im != 0.0 && self.re != 0.0 {
+            period = 360.0 / (self.im / self.re).atan().to_degrees();
+        }
+        period = period
+            .min(1.5 * prev_period)
+            .max(0.67 * prev_period)
+            .clamp(6.0, 50.0);
+        self.period = 0.2 * period + 0.8 * prev_period;
+        self.smooth_period = 0.33 * self.period + 0.67 * self.smooth_period;
+
+        Some(HilbertTransformOutput {
+            in_phase: i1,
+            quadrature: q1,
+            period: self.period,
+            smooth_period: self.smooth_period,
+        })
+    }
+
+    /// Replaces the last price, see [Amend](../trait.Amend.html).
+    pub fn amend(&mut self, price: f64) -> Option<HilbertTransformOutput> {
+        let [price_evicted, smooth, detrender, i1, q1] = self.saved_evicted;
+        pop(&mut self.prices, price_evicted);
+        // The histories after the prices only move past the warmup
+        if self.count > WARMUP {
+            pop(&mut self.smooth, smooth);
+            pop(&mut self.detrender, detrender);
+            pop(&mut self.i1, i1);
+            pop(&mut self.q1, q1);
+        }
+        [
+            self.i2,
+            self.q2,
+            self.re,
+            self.im,
+            self.period,
+            self.smooth_period,
+        ] = self.saved_scalars;
+        self.count = self.saved_count;
+        self.next(price)
+    }
+
+    /// Output of [next](#method.next) for the price without changing the transform.
+    pub fn peek(&self, price: f64) -> Option<HilbertTransformOutput> {
+        self.clone().next(price)
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.count == READY
+    }
+
+    pub fn lookback(&self) -> usize {
+        READY
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Checkpoint for HilbertTransform {
+    type State = HilbertTransformState;
+
+    fn state(&self) -> Self::State {
+        HilbertTransformState {
+            count: self.count,
+            prices: self.prices,
+            smooth: self.smooth,
+            detrender: self.detrender,
+            i1: self.i1,
+            q1: self.q1,
+            i2: self.i2,
+            q2: self.q2,
+            re: self.re,
+            im: self.im,
+            period: self.period,
+            smooth_period: self.smooth_period,
+            previous_count: self.saved_count,
+            evicted: self.saved_evicted,
+            previous: self.saved_scalars,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.count > READY || state.previous_count > state.count {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            count: state.count,
+            prices: state.prices,
+            smooth: state.smooth,
+            detrender: state.detrender,
+            i1: state.i1,
+            q1: state.q1,
+            i2: state.i2,
+            q2: state.q2,
+            re: state.re,
+            im: state.im,
+            period: state.period,
+            smooth_period: state.smooth_period,
+            saved_count: state.previous_count,
+            saved_evicted: state.evicted,
+            saved_scalars: state.previous,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_warmup() {
+        let mut ht = HilbertTransform::new();
+
+        for _ in 0..WARMUP {
+            assert_eq!(ht.next(10.0), None);
+        }
+        assert!(ht.next(10.0).is_some());
+    }
+
+    #[test]
+    fn test_sine_wave_period() {
+        for cycle in &[20.0, 30.0] {
+            let mut ht = HilbertTransform::new();
+            let mut out = None;
+
+            for i in 0..300 {
+                out = ht.next(100.0 + 10.0 * (2.0 * PI * i as f64 / cycle).sin());
+            }
+
+            let period = out.unwrap().smooth_period;
+            assert!((period - cycle).abs() < 0.5, "{} != {}", period, cycle);
+        }
+    }
+}
@@ -0,0 +1,332 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{StandardDeviation as Sd, StandardDeviationState};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Historical volatility (HV).
+///
+/// Annualized close-to-close volatility: the [standard deviation](type.StandardDeviation.html)
+/// of logarithmic returns over the last _period_ bars, scaled to a yearly value.
+/// The result is a fraction, e.g. 0.25 means 25% annualized volatility.
+///
+/// # Formula
+///
+/// r<sub>t</sub> = ln(p<sub>t</sub> / p<sub>t-1</sub>)
+///
+/// HV = SD(r, _period_) * √_periods_per_year_
+///
+/// The first value is 0, since there is no return yet. Non-positive prices produce a
+/// return of 0.
+///
+/// # Parameters
+///
+/// * _period_ - number of returns in the window (integer greater than 0). Default is 20.
+/// * _periods_per_year_ - number of bars in a year, e.g. 252 for daily stock data or
+///   365 * 24 for hourly crypto data (must be greater than 0). Default is 252.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::HistoricalVolatility;
+/// use ta::Next;
+///
+/// let mut hv = HistoricalVolatility::new(3, 1.0).unwrap();
+/// assert_eq!(hv.next(100.0), 0.0);
+/// assert_eq!(hv.next(110.0), 0.0);
+/// assert_eq!((hv.next(99.0) * 10000.0).round(), 1003.0);
+/// ```
+///
+/// # Links
+///
+/// * [Volatility (finance), Wikipedia](https://en.wikipedia.org/wiki/Volatility_(finance))
+///
+#[doc(alias = "HV")]
+#[derive(Debug, Clone)]
+pub struct HistoricalVolatility {
+    periods_per_year: f64,
+    annualization: f64,
+    sd: Sd,
+    prev: Option<f64>,
+    output: Option<f64>,
+    // Input before the last one, restored by `amend`
+    saved_prev: Option<f64>,
+}
+
+/// State of [HistoricalVolatility](struct.HistoricalVolatility.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoricalVolatilityState {
+    pub periods_per_year: f64,
+    /// Standard deviation of the log returns.
+    pub sd: StandardDeviationState,
+    /// Last input.
+    pub previous: Option<f64>,
+    pub output: Option<f64>,
+    /// Input before the last one, to amend the last one.
+    pub before_last: Option<f64>,
+}
+
+impl HistoricalVolatility {
+    pub fn new(period: usize, periods_per_year: f64) -> Result<Self> {
+        if !periods_per_year.is_finite() || periods_per_year <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "periods_per_year",
+                periods_per_year,
+                "> 0",
+            ));
+        }
+        Ok(Self {
+            periods_per_year,
+            annualization: periods_per_year.sqrt(),
+            sd: Sd::new(period)?,
+            prev: None,
+            output: None,
+            saved_prev: None,
+        })
+    }
+
+    pub fn periods_per_year(&self) -> f64 {
+        self.periods_per_year
+    }
+}
+
+impl Period for HistoricalVolatility {
+    fn period(&self) -> usize {
+        self.sd.period()
+    }
+}
+
+impl Checkpoint for HistoricalVolatility {
+    type State = HistoricalVolatilityState;
+
+    fn state(&self) -> Self::State {
+        HistoricalVolatilityState {
+            periods_per_year: self.periods_per_year,
+            sd: self.sd.state(),
+            previous: self.prev,
+            output: self.output,
+            before_last: self.saved_prev,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(state.sd.period, state.periods_per_year)
+            .map_err(|_| TaError::InvalidState)?;
+        if state.previous.is_none() != state.output.is_none()
+            || (state.previous.is_none() && !state.sd.inputs.is_empty())
+        {
+            return Err(TaError::InvalidState);
+        }
+        indicator.sd = Sd::from_state(state.sd)?;
+        indicator.prev = state.previous;
+        indicator.output = state.output;
+        indicator.saved_prev = state.before_last;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(HistoricalVolatility, 2);
+
+impl SetPeriod for HistoricalVolatility {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period, self.periods_per_year)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for HistoricalVolatility {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_prev = self.prev;
+        let output = match self.prev.replace(input) {
+            Some(prev) => {
+                let log_return = if prev > 0.0 && input > 0.0 {
+                    (input / prev).ln()
+                } else {
+                    0.0
+                };
+                self.sd.next(log_return) * self.annualization
+            }
+            None => 0.0,
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for HistoricalVolatility {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for HistoricalVolatility {
+    fn peek(&self, input: f64) -> f64 {
+        match self.prev {
+            Some(prev) => {
+                let log_return = if prev > 0.0 && input > 0.0 {
+                    (input / prev).ln()
+                } else {
+                    0.0
+                };
+                self.sd.peek(log_return) * self.annualization
+            }
+            None => 0.0,
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for HistoricalVolatility {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for HistoricalVolatility {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.prev = Some(input);
+        let output = match self.saved_prev {
+            Some(prev) => {
+                let log_return = if prev > 0.0 && input > 0.0 {
+                    (input / prev).ln()
+                } else {
+                    0.0
+                };
+                self.sd.amend(log_return) * self.annualization
+            }
+            None => 0.0,
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for HistoricalVolatility {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for HistoricalVolatility {
+    fn reset(&mut self) {
+        self.sd.reset();
+        self.prev = None;
+        self.output = None;
+        self.saved_prev = None;
+    }
+}
+
+impl Current for HistoricalVolatility {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for HistoricalVolatility {
+    fn is_ready(&self) -> bool {
+        self.sd.is_ready()
+    }
+}
+
+impl Lookback for HistoricalVolatility {
+    fn lookback(&self) -> usize {
+        // the first return needs two prices
+        self.sd.lookback() + 1
+    }
+}
+
+impl Default for HistoricalVolatility {
+    fn default() -> Self {
+        Self::new(20, 252.0).unwrap()
+    }
+}
+
+impl fmt::Display for HistoricalVolatility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HV({}, {})", self.sd.period(), self.periods_per_year)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(HistoricalVolatility);
+
+    #[test]
+    fn test_new() {
+        assert!(HistoricalVolatility::new(0, 252.0).is_err());
+        assert!(HistoricalVolatility::new(3, 0.0).is_err());
+        assert!(HistoricalVolatility::new(3, -1.0).is_err());
+        assert!(HistoricalVolatility::new(3, f64::NAN).is_err());
+        assert!(HistoricalVolatility::new(1, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut hv = HistoricalVolatility::new(3, 252.0).unwrap();
+
+        assert_eq!(hv.next(100.0), 0.0);
+        assert_eq!(hv.next(110.0), 0.0);
+        assert_eq!(round(hv.next(99.0)), 1.593);
+        assert_eq!(round(hv.next(99.0)), 1.301);
+        assert_eq!(round(hv.next(108.9)), 1.301);
+    }
+
+    #[test]
+    fn test_next_non_positive_price() {
+        let mut hv = HistoricalVolatility::new(3, 1.0).unwrap();
+
+        assert_eq!(hv.next(0.0), 0.0);
+        assert_eq!(hv.next(10.0), 0.0);
+        assert_eq!(hv.next(-5.0), 0.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut hv = HistoricalVolatility::new(3, 252.0).unwrap();
+
+        assert_eq!(hv.next(&Bar::new().close(100)), 0.0);
+        assert_eq!(hv.next(&Bar::new().close(110)), 0.0);
+        assert_eq!(round(hv.next(&Bar::new().close(99))), 1.593);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut hv = HistoricalVolatility::new(3, 252.0).unwrap();
+
+        hv.next(100.0);
+        hv.next(110.0);
+        hv.next(99.0);
+
+        hv.reset();
+        assert_eq!(hv.next(110.0), 0.0);
+        assert_eq!(hv.next(110.0), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        HistoricalVolatility::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let hv = HistoricalVolatility::new(20, 252.0).unwrap();
+        assert_eq!(format!("{}", hv), "HV(20, 252)");
+    }
+}
@@ -0,0 +1,324 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{
+    Amend, Checkpoint, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Relation of a bar to the previous one, as reported by
+/// [InsideOutsideBar](struct.InsideOutsideBar.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarRelation {
+    /// Neither inside nor outside, or the very first bar.
+    Normal,
+    /// High and low are within the range of the previous bar.
+    Inside,
+    /// High and low are beyond the range of the previous bar.
+    Outside,
+    /// Inside bar which is part of a series of consecutive inside bars.
+    /// Holds the number of inside bars in the series.
+    Compression(usize),
+}
+
+/// Converts into a named value with the `relation` (`"normal"`, `"inside"`, `"outside"` or
+/// `"compression"`) and the number of inside bars of a `compression`, which is 0 otherwise.
+#[cfg(feature = "std")]
+impl From<BarRelation> for crate::OutputValue {
+    fn from(relation: BarRelation) -> Self {
+        let (label, inside_bars) = match relation {
+            BarRelation::Normal => ("normal", 0),
+            BarRelation::Inside => ("inside", 0),
+            BarRelation::Outside => ("outside", 0),
+            BarRelation::Compression(count) => ("compression", count),
+        };
+        crate::OutputValue::Named(vec![
+            ("relation", crate::OutputValue::Label(label.to_string())),
+            ("compression", inside_bars.into()),
+        ])
+    }
+}
+
+/// Inside and outside bars (IOB).
+///
+/// Compares the range of each bar with the range of the previous bar. An inside bar shows
+/// consolidation, several inside bars in a row show a volatility compression which often
+/// precedes a breakout. An outside bar shows expansion.
+///
+/// # Formula
+///
+/// * Inside: High < High<sub>t-1</sub> and Low > Low<sub>t-1</sub>
+/// * Outside: High > High<sub>t-1</sub> and Low < Low<sub>t-1</sub>
+/// * Compression: inside bar which is at least the _compression_ consecutive inside bar
+///
+/// # Parameters
+///
+/// * _compression_ - number of consecutive inside bars reported as a compression
+///   (integer greater than 1). Default is 2.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{BarRelation, InsideOutsideBar};
+/// use ta::{DataItem, Next};
+///
+/// fn bar(high: f64, low: f64) -> DataItem {
+///     DataItem::builder()
+///         .high(high).low(low).close(low).open(low)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut iob = InsideOutsideBar::new(2).unwrap();
+///
+/// assert_eq!(iob.next(&bar(10.0, 5.0)), BarRelation::Normal);
+/// assert_eq!(iob.next(&bar(9.0, 6.0)), BarRelation::Inside);
+/// assert_eq!(iob.next(&bar(8.0, 7.0)), BarRelation::Compression(2));
+/// assert_eq!(iob.next(&bar(11.0, 4.0)), BarRelation::Outside);
+/// ```
+#[doc(alias = "IOB")]
+#[derive(Debug, Clone)]
+pub struct InsideOutsideBar {
+    compression: usize,
+    inside_count: usize,
+    prev: Option<(f64, f64)>,
+    output: Option<BarRelation>,
+    // State before the last input, restored by `amend`
+    saved_inside_count: usize,
+    saved_prev: Option<(f64, f64)>,
+}
+
+/// State of [InsideOutsideBar](struct.InsideOutsideBar.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct InsideOutsideBarState {
+    pub compression: usize,
+    /// Number of inside bars in a row.
+    pub inside_count: usize,
+    /// High and low of the previous bar.
+    pub previous: Option<(f64, f64)>,
+    pub output: Option<BarRelation>,
+    /// Inside bar count and bar before the last input, to amend it.
+    pub previous_inside_count: usize,
+    pub before_last: Option<(f64, f64)>,
+}
+
+impl InsideOutsideBar {
+    pub fn new(compression: usize) -> Result<Self> {
+        if compression < 2 {
+            return Err(TaError::invalid_parameter(
+                "compression",
+                compression as f64,
+                ">= 2",
+            ));
+        }
+        Ok(Self {
+            compression,
+            inside_count: 0,
+            prev: None,
+            output: None,
+            saved_inside_count: 0,
+            saved_prev: None,
+        })
+    }
+
+    // Relation of a bar to the previous one, with the number of inside bars in a row it makes
+    fn relation(&self, high: f64, low: f64) -> (BarRelation, usize) {
+        match self.prev {
+            Some((prev_high, prev_low)) if high < prev_high && low > prev_low => {
+                let inside_count = self.inside_count + 1;
+                if inside_count >= self.compression {
+                    (BarRelation::Compression(inside_count), inside_count)
+                } else {
+                    (BarRelation::Inside, inside_count)
+                }
+            }
+            Some((prev_high, prev_low)) if high > prev_high && low < prev_low => {
+                (BarRelation::Outside, 0)
+            }
+            _ => (BarRelation::Normal, 0),
+        }
+    }
+}
+
+impl Period for InsideOutsideBar {
+    fn period(&self) -> usize {
+        self.compression
+    }
+}
+
+impl Checkpoint for InsideOutsideBar {
+    type State = InsideOutsideBarState;
+
+    fn state(&self) -> Self::State {
+        InsideOutsideBarState {
+            compression: self.compression,
+            inside_count: self.inside_count,
+            previous: self.prev,
+            output: self.output,
+            previous_inside_count: self.saved_inside_count,
+            before_last: self.saved_prev,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.previous.is_none() && state.inside_count > 0
+            || state.before_last.is_none() && state.previous_inside_count > 0
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.compression).map_err(|_| TaError::InvalidState)?;
+        indicator.inside_count = state.inside_count;
+        indicator.prev = state.previous;
+        indicator.output = state.output;
+        indicator.saved_inside_count = state.previous_inside_count;
+        indicator.saved_prev = state.before_last;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(InsideOutsideBar, 2);
+
+impl SetPeriod for InsideOutsideBar {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl<T: High + Low> Next<&T> for InsideOutsideBar {
+    type Output = BarRelation;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_inside_count = self.inside_count;
+        self.saved_prev = self.prev;
+        let (relation, inside_count) = self.relation(input.high(), input.low());
+        self.inside_count = inside_count;
+        self.prev = Some((input.high(), input.low()));
+        self.output = Some(relation);
+        relation
+    }
+}
+
+impl<T: High + Low> Peek<&T> for InsideOutsideBar {
+    fn peek(&self, input: &T) -> BarRelation {
+        self.relation(input.high(), input.low()).0
+    }
+}
+
+impl<T: High + Low> Amend<&T> for InsideOutsideBar {
+    fn amend(&mut self, input: &T) -> BarRelation {
+        self.inside_count = self.saved_inside_count;
+        self.prev = self.saved_prev;
+        self.next(input)
+    }
+}
+
+impl Reset for InsideOutsideBar {
+    fn reset(&mut self) {
+        self.inside_count = 0;
+        self.prev = None;
+        self.output = None;
+        self.saved_inside_count = 0;
+        self.saved_prev = None;
+    }
+}
+
+impl Current for InsideOutsideBar {
+    type Output = BarRelation;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for InsideOutsideBar {
+    fn is_ready(&self) -> bool {
+        self.prev.is_some()
+    }
+}
+
+impl Lookback for InsideOutsideBar {
+    fn lookback(&self) -> usize {
+        1
+    }
+}
+
+impl Default for InsideOutsideBar {
+    fn default() -> Self {
+        Self::new(2).unwrap()
+    }
+}
+
+impl fmt::Display for InsideOutsideBar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "IOB({})", self.compression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn bar(high: f64, low: f64) -> Bar {
+        Bar::new().high(high).low(low)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(InsideOutsideBar::new(0).is_err());
+        assert!(InsideOutsideBar::new(1).is_err());
+        assert!(InsideOutsideBar::new(2).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut iob = InsideOutsideBar::new(3).unwrap();
+
+        assert_eq!(iob.next(&bar(10.0, 5.0)), BarRelation::Normal);
+        assert_eq!(iob.next(&bar(9.0, 6.0)), BarRelation::Inside);
+        assert_eq!(iob.next(&bar(8.5, 6.5)), BarRelation::Inside);
+        assert_eq!(iob.next(&bar(8.0, 7.0)), BarRelation::Compression(3));
+        assert_eq!(iob.next(&bar(7.9, 7.1)), BarRelation::Compression(4));
+        assert_eq!(iob.next(&bar(11.0, 4.0)), BarRelation::Outside);
+        // higher high, higher low
+        assert_eq!(iob.next(&bar(12.0, 5.0)), BarRelation::Normal);
+        assert_eq!(iob.next(&bar(11.0, 6.0)), BarRelation::Inside);
+    }
+
+    #[test]
+    fn test_next_equal_range() {
+        let mut iob = InsideOutsideBar::new(2).unwrap();
+
+        iob.next(&bar(10.0, 5.0));
+        assert_eq!(iob.next(&bar(10.0, 5.0)), BarRelation::Normal);
+        assert_eq!(iob.next(&bar(10.0, 6.0)), BarRelation::Normal);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut iob = InsideOutsideBar::new(2).unwrap();
+
+        iob.next(&bar(10.0, 5.0));
+        iob.next(&bar(9.0, 6.0));
+
+        iob.reset();
+        assert_eq!(iob.next(&bar(8.0, 7.0)), BarRelation::Normal);
+        assert_eq!(iob.next(&bar(7.5, 7.2)), BarRelation::Inside);
+    }
+
+    #[test]
+    fn test_default() {
+        InsideOutsideBar::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let iob = InsideOutsideBar::new(3).unwrap();
+        assert_eq!(format!("{}", iob), "IOB(3)");
+    }
+}
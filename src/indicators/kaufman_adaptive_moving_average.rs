@@ -0,0 +1,355 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{EfficiencyRatio, EfficiencyRatioState};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Kaufman's Adaptive Moving Average (KAMA).
+///
+/// A moving average designed to account for market noise or volatility. It uses the
+/// [Efficiency Ratio](struct.EfficiencyRatio.html) to move the smoothing constant between a fast
+/// and a slow EMA constant: it follows prices closely when the market is trending and flattens
+/// out when the market is choppy.
+///
+/// # Formula
+///
+/// SC = (ER * (fast<sub>sc</sub> - slow<sub>sc</sub>) + slow<sub>sc</sub>)<sup>2</sup>
+///
+/// KAMA<sub>t</sub> = KAMA<sub>t-1</sub> + SC * (p<sub>t</sub> - KAMA<sub>t-1</sub>)
+///
+/// Where:
+///
+/// * _ER_ - efficiency ratio over _er_period_
+/// * _fast<sub>sc</sub>_ = 2 / (fast_period + 1)
+/// * _slow<sub>sc</sub>_ = 2 / (slow_period + 1)
+/// * _p<sub>t</sub>_ - input value at a time period _t_
+///
+/// The first value of KAMA is the first input value.
+///
+/// # Parameters
+///
+/// * _er_period_ - number of periods for the efficiency ratio (integer greater than 0). Default is 10.
+/// * _fast_period_ - period of the fastest EMA constant (integer greater than 0). Default is 2.
+/// * _slow_period_ - period of the slowest EMA constant (integer greater than _fast_period_). Default is 30.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::KaufmanAdaptiveMovingAverage;
+/// use ta::Next;
+///
+/// let mut kama = KaufmanAdaptiveMovingAverage::new(3, 2, 30).unwrap();
+/// assert_eq!(kama.next(3.0), 3.0);
+/// assert_eq!((kama.next(5.0) * 1000.0).round() / 1000.0, 3.889);
+/// ```
+///
+/// # Links
+///
+/// * [Kaufman's Adaptive Moving Average, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:kaufman_s_adaptive_moving_average)
+///
+#[doc(alias = "KAMA")]
+#[derive(Debug, Clone)]
+pub struct KaufmanAdaptiveMovingAverage {
+    fast_period: usize,
+    slow_period: usize,
+    fast_sc: f64,
+    slow_sc: f64,
+    er: EfficiencyRatio,
+    current: f64,
+    is_new: bool,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_current: f64,
+    saved_is_new: bool,
+}
+
+/// State of [KaufmanAdaptiveMovingAverage](struct.KaufmanAdaptiveMovingAverage.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct KaufmanAdaptiveMovingAverageState {
+    pub er: EfficiencyRatioState,
+    pub fast_period: usize,
+    pub slow_period: usize,
+    pub current: f64,
+    pub output: Option<f64>,
+    /// Value before the last input, to amend it.
+    pub previous: Option<f64>,
+}
+
+impl KaufmanAdaptiveMovingAverage {
+    pub fn new(er_period: usize, fast_period: usize, slow_period: usize) -> Result<Self> {
+        if fast_period == 0 {
+            return Err(TaError::invalid_parameter("fast_period", 0.0, ">= 1"));
+        }
+        if slow_period <= fast_period {
+            return Err(TaError::invalid_parameter(
+                "slow_period",
+                slow_period as f64,
+                "> fast_period",
+            ));
+        }
+
+        Ok(Self {
+            fast_period,
+            slow_period,
+            fast_sc: 2.0 / (fast_period + 1) as f64,
+            slow_sc: 2.0 / (slow_period + 1) as f64,
+            er: EfficiencyRatio::new(er_period)?,
+            current: 0.0,
+            is_new: true,
+            output: None,
+            saved_current: 0.0,
+            saved_is_new: true,
+        })
+    }
+
+    pub fn fast_period(&self) -> usize {
+        self.fast_period
+    }
+
+    pub fn slow_period(&self) -> usize {
+        self.slow_period
+    }
+
+    fn update(&mut self, input: f64, er: f64) -> f64 {
+        self.saved_current = self.current;
+        self.saved_is_new = self.is_new;
+
+        if self.is_new {
+            self.is_new = false;
+            self.current = input;
+        } else {
+            // ER is undefined (0 / 0) when prices did not move at all
+            let er = if er.is_nan() { 0.0 } else { er };
+            let sc = (er * (self.fast_sc - self.slow_sc) + self.slow_sc).powi(2);
+            self.current += sc * (input - self.current);
+        }
+
+        let output = self.current;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Period for KaufmanAdaptiveMovingAverage {
+    fn period(&self) -> usize {
+        self.er.period()
+    }
+}
+
+impl Checkpoint for KaufmanAdaptiveMovingAverage {
+    type State = KaufmanAdaptiveMovingAverageState;
+
+    fn state(&self) -> Self::State {
+        KaufmanAdaptiveMovingAverageState {
+            er: self.er.state(),
+            fast_period: self.fast_period,
+            slow_period: self.slow_period,
+            current: self.current,
+            output: self.output,
+            previous: (!self.saved_is_new).then_some(self.saved_current),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(state.er.period, state.fast_period, state.slow_period)
+            .map_err(|_| TaError::InvalidState)?;
+        if state.output.is_none() != state.er.output.is_none() {
+            return Err(TaError::InvalidState);
+        }
+        indicator.er = EfficiencyRatio::from_state(state.er)?;
+        indicator.current = state.current;
+        indicator.is_new = state.output.is_none();
+        indicator.output = state.output;
+        indicator.saved_current = state.previous.unwrap_or(0.0);
+        indicator.saved_is_new = state.previous.is_none();
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(KaufmanAdaptiveMovingAverage, 2);
+
+impl Next<f64> for KaufmanAdaptiveMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let er = self.er.next(input);
+        self.update(input, er)
+    }
+}
+
+impl<T: Close> Next<&T> for KaufmanAdaptiveMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for KaufmanAdaptiveMovingAverage {
+    fn peek(&self, input: f64) -> f64 {
+        let er = self.er.peek(input);
+
+        if self.is_new {
+            input
+        } else {
+            let er = if er.is_nan() { 0.0 } else { er };
+            let sc = (er * (self.fast_sc - self.slow_sc) + self.slow_sc).powi(2);
+            self.current + sc * (input - self.current)
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for KaufmanAdaptiveMovingAverage {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for KaufmanAdaptiveMovingAverage {
+    fn amend(&mut self, input: f64) -> f64 {
+        let er = self.er.amend(input);
+        self.current = self.saved_current;
+        self.is_new = self.saved_is_new;
+        self.update(input, er)
+    }
+}
+
+impl<T: Close> Amend<&T> for KaufmanAdaptiveMovingAverage {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for KaufmanAdaptiveMovingAverage {
+    fn reset(&mut self) {
+        self.er.reset();
+        self.current = 0.0;
+        self.is_new = true;
+        self.output = None;
+        self.saved_current = 0.0;
+        self.saved_is_new = true;
+    }
+}
+
+impl Current for KaufmanAdaptiveMovingAverage {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for KaufmanAdaptiveMovingAverage {
+    fn is_ready(&self) -> bool {
+        self.er.is_ready()
+    }
+}
+
+impl Lookback for KaufmanAdaptiveMovingAverage {
+    fn lookback(&self) -> usize {
+        self.er.lookback()
+    }
+}
+
+impl Default for KaufmanAdaptiveMovingAverage {
+    fn default() -> Self {
+        Self::new(10, 2, 30).unwrap()
+    }
+}
+
+impl fmt::Display for KaufmanAdaptiveMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "KAMA({}, {}, {})",
+            self.er.period(),
+            self.fast_period,
+            self.slow_period
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    type Kama = KaufmanAdaptiveMovingAverage;
+
+    test_indicator!(Kama);
+
+    #[test]
+    fn test_new() {
+        assert!(Kama::new(0, 2, 30).is_err());
+        assert!(Kama::new(10, 0, 30).is_err());
+        assert!(Kama::new(10, 30, 30).is_err());
+        assert!(Kama::new(10, 30, 2).is_err());
+        assert!(Kama::new(1, 1, 2).is_ok());
+        assert!(Kama::new(10, 2, 30).is_ok());
+
+        assert_eq!(
+            Kama::new(10, 30, 2).err(),
+            Some(TaError::invalid_parameter(
+                "slow_period",
+                2.0,
+                "> fast_period"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_next() {
+        let mut kama = Kama::new(3, 2, 30).unwrap();
+
+        assert_eq!(kama.next(3.0), 3.0);
+        assert_eq!(round(kama.next(5.0)), 3.889);
+        assert_eq!(round(kama.next(2.0)), 3.824);
+        assert_eq!(round(kama.next(3.0)), 3.821);
+        assert_eq!(round(kama.next(1.0)), 3.208);
+    }
+
+    #[test]
+    fn test_next_flat_prices() {
+        let mut kama = Kama::new(3, 2, 30).unwrap();
+
+        assert_eq!(kama.next(4.0), 4.0);
+        assert_eq!(kama.next(4.0), 4.0);
+        assert_eq!(kama.next(4.0), 4.0);
+        assert_eq!(kama.next(4.0), 4.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut kama = Kama::new(3, 2, 30).unwrap();
+
+        assert_eq!(kama.next(&Bar::new().close(3)), 3.0);
+        assert_eq!(round(kama.next(&Bar::new().close(5))), 3.889);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut kama = Kama::new(3, 2, 30).unwrap();
+
+        kama.next(3.0);
+        kama.next(5.0);
+
+        kama.reset();
+        assert_eq!(kama.next(3.0), 3.0);
+        assert_eq!(round(kama.next(5.0)), 3.889);
+    }
+
+    #[test]
+    fn test_default() {
+        Kama::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let kama = Kama::new(10, 2, 30).unwrap();
+        assert_eq!(format!("{}", kama), "KAMA(10, 2, 30)");
+    }
+}
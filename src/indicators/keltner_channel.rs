@@ -1,8 +1,14 @@
-use std::fmt;
-
-use crate::errors::Result;
-use crate::indicators::{AverageTrueRange, ExponentialMovingAverage};
-use crate::{Close, High, Low, Next, Period, Reset};
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{
+    AverageTrueRange, AverageTrueRangeState, ExponentialMovingAverage,
+    ExponentialMovingAverageState,
+};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset,
+    SetPeriod,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -47,22 +53,37 @@ use serde::{Deserialize, Serialize};
 ///
 /// * [Keltner channel, Wikipedia](https://en.wikipedia.org/wiki/Keltner_channel)
 #[doc(alias = "KC")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct KeltnerChannel {
     period: usize,
     multiplier: f64,
     atr: AverageTrueRange,
     ema: ExponentialMovingAverage,
+    output: Option<KeltnerChannelOutput>,
 }
 
+/// State of [KeltnerChannel](struct.KeltnerChannel.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeltnerChannelState {
+    pub multiplier: f64,
+    pub atr: AverageTrueRangeState,
+    pub ema: ExponentialMovingAverageState,
+    pub output: Option<KeltnerChannelOutput>,
+}
+
+/// Output of [KeltnerChannel](struct.KeltnerChannel.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
 pub struct KeltnerChannelOutput {
-    pub average: f64,
     pub upper: f64,
+    pub average: f64,
     pub lower: f64,
 }
 
+impl_output!(KeltnerChannelOutput, upper, average, lower);
+
 impl KeltnerChannel {
     pub fn new(period: usize, multiplier: f64) -> Result<Self> {
         Ok(Self {
@@ -70,6 +91,7 @@ impl KeltnerChannel {
             multiplier,
             atr: AverageTrueRange::new(period)?,
             ema: ExponentialMovingAverage::new(period)?,
+            output: None,
         })
     }
 
@@ -84,6 +106,44 @@ impl Period for KeltnerChannel {
     }
 }
 
+impl Checkpoint for KeltnerChannel {
+    type State = KeltnerChannelState;
+
+    fn state(&self) -> Self::State {
+        KeltnerChannelState {
+            multiplier: self.multiplier,
+            atr: self.atr.state(),
+            ema: self.ema.state(),
+            output: self.output.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.atr.ema.period != state.ema.period
+            || state.atr.ema.count != state.ema.count
+            || state.output.is_none() != (state.ema.count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            period: state.ema.period,
+            multiplier: state.multiplier,
+            atr: AverageTrueRange::from_state(state.atr)?,
+            ema: ExponentialMovingAverage::from_state(state.ema)?,
+            output: state.output,
+        })
+    }
+}
+
+impl_versioned_serde!(KeltnerChannel, 1);
+
+impl SetPeriod for KeltnerChannel {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period, self.multiplier)?;
+        Ok(())
+    }
+}
+
 impl Next<f64> for KeltnerChannel {
     type Output = KeltnerChannelOutput;
 
@@ -91,11 +151,13 @@ impl Next<f64> for KeltnerChannel {
         let atr = self.atr.next(input);
         let average = self.ema.next(input);
 
-        Self::Output {
+        let output = Self::Output {
             average,
             upper: average + atr * self.multiplier,
             lower: average - atr * self.multiplier,
-        }
+        };
+        self.output = Some(output.clone());
+        output
     }
 }
 
@@ -108,7 +170,37 @@ impl<T: Close + High + Low> Next<&T> for KeltnerChannel {
         let average = self.ema.next(typical_price);
         let atr = self.atr.next(input);
 
-        Self::Output {
+        let output = Self::Output {
+            average,
+            upper: average + atr * self.multiplier,
+            lower: average - atr * self.multiplier,
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Peek<f64> for KeltnerChannel {
+    fn peek(&self, input: f64) -> KeltnerChannelOutput {
+        let atr = self.atr.peek(input);
+        let average = self.ema.peek(input);
+
+        KeltnerChannelOutput {
+            average,
+            upper: average + atr * self.multiplier,
+            lower: average - atr * self.multiplier,
+        }
+    }
+}
+
+impl<T: Close + High + Low> Peek<&T> for KeltnerChannel {
+    fn peek(&self, input: &T) -> KeltnerChannelOutput {
+        let typical_price = (input.close() + input.high() + input.low()) / 3.0;
+
+        let average = self.ema.peek(typical_price);
+        let atr = self.atr.peek(input);
+
+        KeltnerChannelOutput {
             average,
             upper: average + atr * self.multiplier,
             lower: average - atr * self.multiplier,
@@ -116,10 +208,60 @@ impl<T: Close + High + Low> Next<&T> for KeltnerChannel {
     }
 }
 
+impl Amend<f64> for KeltnerChannel {
+    fn amend(&mut self, input: f64) -> KeltnerChannelOutput {
+        let atr = self.atr.amend(input);
+        let average = self.ema.amend(input);
+        let output = KeltnerChannelOutput {
+            average,
+            upper: average + atr * self.multiplier,
+            lower: average - atr * self.multiplier,
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Close + High + Low> Amend<&T> for KeltnerChannel {
+    fn amend(&mut self, input: &T) -> KeltnerChannelOutput {
+        let typical_price = (input.close() + input.high() + input.low()) / 3.0;
+        let average = self.ema.amend(typical_price);
+        let atr = self.atr.amend(input);
+        let output = KeltnerChannelOutput {
+            average,
+            upper: average + atr * self.multiplier,
+            lower: average - atr * self.multiplier,
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
 impl Reset for KeltnerChannel {
     fn reset(&mut self) {
         self.atr.reset();
         self.ema.reset();
+        self.output = None;
+    }
+}
+
+impl Current for KeltnerChannel {
+    type Output = KeltnerChannelOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for KeltnerChannel {
+    fn is_ready(&self) -> bool {
+        self.ema.is_ready() && self.atr.is_ready()
+    }
+}
+
+impl Lookback for KeltnerChannel {
+    fn lookback(&self) -> usize {
+        self.ema.lookback().max(self.atr.lookback())
     }
 }
 
@@ -0,0 +1,271 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Laguerre Filter.
+///
+/// John Ehlers' smoother built from a four element Laguerre polynomial. It reacts faster than
+/// a moving average of similar smoothness. Instead of a period, the amount of smoothing is set
+/// by _gamma_: 0 gives almost no smoothing and values close to 1 give heavy smoothing.
+///
+/// # Formula
+///
+/// L0<sub>t</sub> = (1 - γ) × Price<sub>t</sub> + γ × L0<sub>t-1</sub>
+///
+/// L1<sub>t</sub> = -γ × L0<sub>t</sub> + L0<sub>t-1</sub> + γ × L1<sub>t-1</sub>
+///
+/// L2<sub>t</sub> = -γ × L1<sub>t</sub> + L1<sub>t-1</sub> + γ × L2<sub>t-1</sub>
+///
+/// L3<sub>t</sub> = -γ × L2<sub>t</sub> + L2<sub>t-1</sub> + γ × L3<sub>t-1</sub>
+///
+/// Filter<sub>t</sub> = (L0<sub>t</sub> + 2 × L1<sub>t</sub> + 2 × L2<sub>t</sub> +
+/// L3<sub>t</sub>) / 6
+///
+/// All four elements start at the first price.
+///
+/// # Parameters
+///
+/// * _gamma_ - damping factor (number in range [0, 1)). Default is 0.8.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::LaguerreFilter;
+/// use ta::Next;
+///
+/// let mut filter = LaguerreFilter::new(0.5).unwrap();
+/// assert_eq!(filter.next(10.0), 10.0);
+/// assert_eq!(filter.next(12.0), 10.0625);
+/// assert_eq!(filter.next(11.0), 10.15625);
+/// ```
+///
+/// # Links
+///
+/// * [Time Warp - Without Space Travel, John Ehlers (PDF)](https://www.mesasoftware.com/papers/TimeWarp.pdf)
+#[doc(alias = "LAGUERRE")]
+#[derive(Debug, Clone)]
+pub struct LaguerreFilter {
+    gamma: f64,
+    elements: Option<[f64; 4]>,
+    output: Option<f64>,
+    // Elements before the last input, restored by `amend`
+    saved_elements: Option<[f64; 4]>,
+}
+
+/// State of [LaguerreFilter](struct.LaguerreFilter.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaguerreFilterState {
+    pub gamma: f64,
+    /// Filter elements, `None` before the first input.
+    pub elements: Option<[f64; 4]>,
+    /// Elements before the last input, to amend it.
+    pub previous: Option<[f64; 4]>,
+}
+
+impl LaguerreFilter {
+    pub fn new(gamma: f64) -> Result<Self> {
+        if !(0.0..1.0).contains(&gamma) {
+            return Err(TaError::invalid_parameter("gamma", gamma, "0 <= gamma < 1"));
+        }
+        Ok(Self {
+            gamma,
+            elements: None,
+            output: None,
+            saved_elements: None,
+        })
+    }
+
+    pub fn gamma(&self) -> f64 {
+        self.gamma
+    }
+
+    /// Calculates the four Laguerre elements for the next price, without updating them.
+    pub(super) fn elements(&self, input: f64) -> [f64; 4] {
+        let g = self.gamma;
+        let [p0, p1, p2, p3] = self.elements.unwrap_or([input; 4]);
+
+        let l0 = (1.0 - g) * input + g * p0;
+        let l1 = -g * l0 + p0 + g * p1;
+        let l2 = -g * l1 + p1 + g * p2;
+        let l3 = -g * l2 + p2 + g * p3;
+
+        [l0, l1, l2, l3]
+    }
+
+    /// Updates the four Laguerre elements with the next price and returns them.
+    pub(super) fn update(&mut self, input: f64) -> [f64; 4] {
+        self.saved_elements = self.elements;
+        let elements = self.elements(input);
+        self.elements = Some(elements);
+        elements
+    }
+
+    // Goes back to the elements before the last input
+    pub(super) fn restore(&mut self) {
+        self.elements = self.saved_elements;
+    }
+}
+
+impl Checkpoint for LaguerreFilter {
+    type State = LaguerreFilterState;
+
+    fn state(&self) -> Self::State {
+        LaguerreFilterState {
+            gamma: self.gamma,
+            elements: self.elements,
+            previous: self.saved_elements,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(state.gamma).map_err(|_| TaError::InvalidState)?;
+        indicator.elements = state.elements;
+        indicator.saved_elements = state.previous;
+        indicator.output = state
+            .elements
+            .map(|[l0, l1, l2, l3]| (l0 + 2.0 * l1 + 2.0 * l2 + l3) / 6.0);
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(LaguerreFilter, 2);
+
+impl Next<f64> for LaguerreFilter {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let [l0, l1, l2, l3] = self.update(input);
+        let output = (l0 + 2.0 * l1 + 2.0 * l2 + l3) / 6.0;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for LaguerreFilter {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for LaguerreFilter {
+    fn peek(&self, input: f64) -> f64 {
+        let [l0, l1, l2, l3] = self.elements(input);
+        (l0 + 2.0 * l1 + 2.0 * l2 + l3) / 6.0
+    }
+}
+
+impl<T: Close> Peek<&T> for LaguerreFilter {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for LaguerreFilter {
+    fn amend(&mut self, input: f64) -> f64 {
+        self.restore();
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for LaguerreFilter {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for LaguerreFilter {
+    fn reset(&mut self) {
+        self.elements = None;
+        self.output = None;
+        self.saved_elements = None;
+    }
+}
+
+impl Current for LaguerreFilter {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for LaguerreFilter {
+    fn is_ready(&self) -> bool {
+        self.elements.is_some()
+    }
+}
+
+impl Lookback for LaguerreFilter {
+    fn lookback(&self) -> usize {
+        1
+    }
+}
+
+impl Default for LaguerreFilter {
+    fn default() -> Self {
+        Self::new(0.8).unwrap()
+    }
+}
+
+impl fmt::Display for LaguerreFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LAGUERRE({})", self.gamma)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(LaguerreFilter);
+
+    #[test]
+    fn test_new() {
+        assert!(LaguerreFilter::new(-0.1).is_err());
+        assert!(LaguerreFilter::new(1.0).is_err());
+        assert!(LaguerreFilter::new(f64::NAN).is_err());
+        assert!(LaguerreFilter::new(0.0).is_ok());
+        assert!(LaguerreFilter::new(0.99).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut filter = LaguerreFilter::new(0.8).unwrap();
+
+        assert_eq!(round(filter.next(10.0)), 10.0);
+        assert_eq!(round(filter.next(12.0)), 10.011);
+        assert_eq!(round(filter.next(11.0)), 10.032);
+        assert_eq!(round(filter.next(14.0)), 10.067);
+        assert_eq!(round(filter.next(&Bar::new().close(13))), 10.119);
+        assert_eq!(round(filter.next(&Bar::new().close(15))), 10.184);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut filter = LaguerreFilter::new(0.5).unwrap();
+
+        filter.next(10.0);
+        filter.next(12.0);
+        filter.reset();
+        assert_eq!(filter.next(20.0), 20.0);
+    }
+
+    #[test]
+    fn test_default() {
+        LaguerreFilter::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let filter = LaguerreFilter::new(0.7).unwrap();
+        assert_eq!(format!("{}", filter), "LAGUERRE(0.7)");
+    }
+}
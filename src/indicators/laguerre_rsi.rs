@@ -0,0 +1,268 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::indicators::{LaguerreFilter, LaguerreFilterState};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Laguerre RSI.
+///
+/// John Ehlers' RSI calculated from the four elements of a
+/// [Laguerre filter](struct.LaguerreFilter.html) instead of from price changes over a period.
+/// It needs very little data and is much smoother than a regular RSI of similar lag. The
+/// amount of smoothing is set by _gamma_. Like the [RSI](type.RelativeStrengthIndex.html), it
+/// ranges from 0 to 100.
+///
+/// # Formula
+///
+/// CU = Σ max(L<sub>i</sub> - L<sub>i+1</sub>, 0)
+///
+/// CD = Σ max(L<sub>i+1</sub> - L<sub>i</sub>, 0)
+///
+/// LRSI = 100 × CU / (CU + CD)
+///
+/// Where:
+///
+/// * _L<sub>i</sub>_ - elements L0 to L3 of the Laguerre filter, summed for _i_ from 0 to 2
+///
+/// When CU + CD is 0, the previous value is repeated, starting at 50.
+///
+/// # Parameters
+///
+/// * _gamma_ - damping factor (number in range [0, 1)). Default is 0.5.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::LaguerreRsi;
+/// use ta::Next;
+///
+/// let mut lrsi = LaguerreRsi::new(0.5).unwrap();
+/// assert_eq!(lrsi.next(10.0), 50.0);
+/// assert_eq!(lrsi.next(12.0).round(), 71.0);
+/// assert_eq!(lrsi.next(15.0), 80.0);
+/// ```
+///
+/// # Links
+///
+/// * [Time Warp - Without Space Travel, John Ehlers (PDF)](https://www.mesasoftware.com/papers/TimeWarp.pdf)
+#[doc(alias = "LRSI")]
+#[derive(Debug, Clone)]
+pub struct LaguerreRsi {
+    filter: LaguerreFilter,
+    current: f64,
+    output: Option<f64>,
+    // Value before the last input, restored by `amend`
+    saved_current: f64,
+}
+
+/// State of [LaguerreRsi](struct.LaguerreRsi.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaguerreRsiState {
+    pub filter: LaguerreFilterState,
+    /// Last RSI, which is kept while the filter is flat.
+    pub current: f64,
+    /// Value before the last input, to amend it.
+    pub previous: f64,
+}
+
+impl LaguerreRsi {
+    pub fn new(gamma: f64) -> Result<Self> {
+        Ok(Self {
+            filter: LaguerreFilter::new(gamma)?,
+            current: 50.0,
+            output: None,
+            saved_current: 50.0,
+        })
+    }
+
+    // Share of the rises between the Laguerre elements, the last value when they are all equal
+    fn value(&self, elements: [f64; 4]) -> f64 {
+        let (mut up, mut down) = (0.0, 0.0);
+        for pair in elements.windows(2) {
+            let diff = pair[0] - pair[1];
+            if diff > 0.0 {
+                up += diff;
+            } else {
+                down -= diff;
+            }
+        }
+
+        if up + down > 0.0 {
+            100.0 * up / (up + down)
+        } else {
+            self.current
+        }
+    }
+}
+
+impl Checkpoint for LaguerreRsi {
+    type State = LaguerreRsiState;
+
+    fn state(&self) -> Self::State {
+        LaguerreRsiState {
+            filter: self.filter.state(),
+            current: self.current,
+            previous: self.saved_current,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(Self {
+            output: state.filter.elements.map(|_| state.current),
+            filter: LaguerreFilter::from_state(state.filter)?,
+            current: state.current,
+            saved_current: state.previous,
+        })
+    }
+}
+
+impl_versioned_serde!(LaguerreRsi, 2);
+
+impl Next<f64> for LaguerreRsi {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_current = self.current;
+        let elements = self.filter.update(input);
+        self.current = self.value(elements);
+        let output = self.current;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for LaguerreRsi {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for LaguerreRsi {
+    fn peek(&self, input: f64) -> f64 {
+        self.value(self.filter.elements(input))
+    }
+}
+
+impl<T: Close> Peek<&T> for LaguerreRsi {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for LaguerreRsi {
+    fn amend(&mut self, input: f64) -> f64 {
+        self.filter.restore();
+        self.current = self.saved_current;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for LaguerreRsi {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for LaguerreRsi {
+    fn reset(&mut self) {
+        self.filter.reset();
+        self.current = 50.0;
+        self.output = None;
+        self.saved_current = 50.0;
+    }
+}
+
+impl Current for LaguerreRsi {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for LaguerreRsi {
+    fn is_ready(&self) -> bool {
+        self.filter.is_ready()
+    }
+}
+
+impl Lookback for LaguerreRsi {
+    fn lookback(&self) -> usize {
+        self.filter.lookback()
+    }
+}
+
+impl Default for LaguerreRsi {
+    fn default() -> Self {
+        Self::new(0.5).unwrap()
+    }
+}
+
+impl fmt::Display for LaguerreRsi {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LRSI({})", self.filter.gamma())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(LaguerreRsi);
+
+    #[test]
+    fn test_new() {
+        assert!(LaguerreRsi::new(-0.5).is_err());
+        assert!(LaguerreRsi::new(1.5).is_err());
+        assert!(LaguerreRsi::new(f64::INFINITY).is_err());
+        assert!(LaguerreRsi::new(0.5).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut lrsi = LaguerreRsi::new(0.5).unwrap();
+
+        assert_eq!(lrsi.next(10.0), 50.0);
+        assert_eq!(round(lrsi.next(12.0)), 71.429);
+        assert_eq!(round(lrsi.next(11.0)), 61.538);
+        assert_eq!(round(lrsi.next(14.0)), 94.118);
+        assert_eq!(round(lrsi.next(&Bar::new().close(13))), 80.0);
+        assert_eq!(round(lrsi.next(&Bar::new().close(15))), 100.0);
+    }
+
+    #[test]
+    fn test_flat() {
+        let mut lrsi = LaguerreRsi::new(0.5).unwrap();
+
+        for _ in 0..5 {
+            assert_eq!(lrsi.next(10.0), 50.0);
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut lrsi = LaguerreRsi::new(0.5).unwrap();
+
+        lrsi.next(10.0);
+        lrsi.next(12.0);
+        lrsi.reset();
+        assert_eq!(lrsi.next(20.0), 50.0);
+    }
+
+    #[test]
+    fn test_default() {
+        LaguerreRsi::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let lrsi = LaguerreRsi::new(0.6).unwrap();
+        assert_eq!(format!("{}", lrsi), "LRSI(0.6)");
+    }
+}
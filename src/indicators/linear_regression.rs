@@ -0,0 +1,499 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling linear regression (LINREG).
+///
+/// Fits a least squares line through the last _period_ values and returns its parameters.
+/// The sums are maintained incrementally, so every update takes constant time regardless of
+/// the period.
+///
+/// # Formula
+///
+/// Slope = (n * Σxy - Σx * Σy) / (n * Σx<sup>2</sup> - (Σx)<sup>2</sup>)
+///
+/// Intercept = (Σy - Slope * Σx) / n
+///
+/// Forecast = Intercept + Slope * n
+///
+/// R<sup>2</sup> = (n * Σxy - Σx * Σy)<sup>2</sup> / ((n * Σx<sup>2</sup> - (Σx)<sup>2</sup>) * (n * Σy<sup>2</sup> - (Σy)<sup>2</sup>))
+///
+/// Where:
+///
+/// * _y_ - input values in the window
+/// * _x_ - position of the value in the window, 0 for the oldest and n - 1 for the newest value
+/// * _n_ - number of values in the window, which is less than _period_ during the first bars
+///
+/// _Forecast_ is the value of the line at the next bar. The value of the line at the current
+/// bar, also known as least squares moving average (LSMA), is _Forecast_ - _Slope_.
+///
+/// With a single value the slope is 0. When all values are equal the line fits them
+/// perfectly and R<sup>2</sup> is 1.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::LinearRegression;
+/// use ta::Next;
+///
+/// let mut lr = LinearRegression::new(3).unwrap();
+///
+/// lr.next(2.0);
+/// lr.next(4.0);
+/// let out = lr.next(6.0);
+///
+/// assert_eq!(out.slope, 2.0);
+/// assert_eq!(out.intercept, 2.0);
+/// assert_eq!(out.forecast, 8.0);
+/// assert_eq!(out.r_squared, 1.0);
+/// ```
+///
+/// # Links
+///
+/// * [Simple linear regression, Wikipedia](https://en.wikipedia.org/wiki/Simple_linear_regression)
+///
+#[doc(alias = "LINREG")]
+#[doc(alias = "LSMA")]
+#[derive(Debug, Clone)]
+pub struct LinearRegression {
+    period: usize,
+    index: usize,
+    count: usize,
+    sum_y: f64,
+    sum_xy: f64,
+    sum_yy: f64,
+    deque: Box<[f64]>,
+    output: Option<LinearRegressionOutput>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+    saved_sum_y: f64,
+    saved_sum_xy: f64,
+    saved_sum_yy: f64,
+}
+
+/// State of [LinearRegression](struct.LinearRegression.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearRegressionState {
+    pub period: usize,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<f64>,
+    pub sum_y: f64,
+    /// Running sum of the inputs weighted by their position in the window.
+    pub sum_xy: f64,
+    pub sum_yy: f64,
+    pub output: Option<LinearRegressionOutput>,
+    /// Input which the last one pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
+    /// Running sums before the last input, to amend it.
+    pub previous_sum_y: f64,
+    pub previous_sum_xy: f64,
+    pub previous_sum_yy: f64,
+}
+
+/// Output of [LinearRegression](struct.LinearRegression.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearRegressionOutput {
+    pub slope: f64,
+    pub intercept: f64,
+    pub forecast: f64,
+    pub r_squared: f64,
+}
+
+impl_output!(
+    LinearRegressionOutput,
+    slope,
+    intercept,
+    forecast,
+    r_squared
+);
+
+// Count, Σy, Σxy and Σy² of the values in the window
+type Sums = (usize, f64, f64, f64);
+
+impl LinearRegression {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                sum_y: 0.0,
+                sum_xy: 0.0,
+                sum_yy: 0.0,
+                deque: vec![0.0; period].into_boxed_slice(),
+                output: None,
+                saved_value: 0.0,
+                saved_count: 0,
+                saved_sum_y: 0.0,
+                saved_sum_xy: 0.0,
+                saved_sum_yy: 0.0,
+            }),
+        }
+    }
+
+    /// Standard error of the regression: the standard deviation of the values in the window
+    /// around the fitted line. It is 0 while there are 2 values or less in the window.
+    ///
+    /// SE = sqrt(Σ(y - ŷ)<sup>2</sup> / (n - 2))
+    pub fn standard_error(&self) -> f64 {
+        Self::error(self.sums())
+    }
+
+    /// Standard error of the regression if `input` was the next input.
+    pub(super) fn peek_standard_error(&self, input: f64) -> f64 {
+        Self::error(self.sums_with(input))
+    }
+
+    /// Running sums of the window.
+    fn sums(&self) -> Sums {
+        (self.count, self.sum_y, self.sum_xy, self.sum_yy)
+    }
+
+    /// Same as `sums`, after `input` entered the window.
+    fn sums_with(&self, input: f64) -> Sums {
+        let (mut count, mut sum_y, mut sum_xy, mut sum_yy) = self.sums();
+        if count < self.period {
+            sum_xy += count as f64 * input;
+            count += 1;
+        } else {
+            // every value moves one position back, the oldest one leaves the window
+            let old_val = self.deque[self.index];
+            sum_xy += -(sum_y - old_val) + (self.period - 1) as f64 * input;
+            sum_y -= old_val;
+            sum_yy -= old_val * old_val;
+        }
+        sum_y += input;
+        sum_yy += input * input;
+
+        (count, sum_y, sum_xy, sum_yy)
+    }
+
+    /// Returns n, Σx, and n times the (co)variances of x and y.
+    fn moments((count, sum_y, sum_xy, sum_yy): Sums) -> (f64, f64, f64, f64, f64) {
+        let n = count as f64;
+        let sum_x = n * (n - 1.0) / 2.0;
+        let sum_xx = (n - 1.0) * n * (2.0 * n - 1.0) / 6.0;
+
+        let covariance = n * sum_xy - sum_x * sum_y;
+        let variance_x = n * sum_xx - sum_x * sum_x;
+        let variance_y = n * sum_yy - sum_y * sum_y;
+
+        (n, sum_x, covariance, variance_x, variance_y)
+    }
+
+    fn error(sums: Sums) -> f64 {
+        if sums.0 <= 2 {
+            return 0.0;
+        }
+
+        let (n, _, covariance, variance_x, variance_y) = Self::moments(sums);
+        let sse = (variance_y - covariance * covariance / variance_x) / n;
+        (sse.max(0.0) / (n - 2.0)).sqrt()
+    }
+
+    fn regression(sums: Sums) -> LinearRegressionOutput {
+        let (n, sum_x, covariance, variance_x, variance_y) = Self::moments(sums);
+
+        let slope = if variance_x > 0.0 {
+            covariance / variance_x
+        } else {
+            0.0
+        };
+        let intercept = (sums.1 - slope * sum_x) / n;
+        let r_squared = if variance_x > 0.0 && variance_y > 0.0 {
+            (covariance * covariance / (variance_x * variance_y)).min(1.0)
+        } else {
+            1.0
+        };
+
+        LinearRegressionOutput {
+            slope,
+            intercept,
+            forecast: intercept + slope * n,
+            r_squared,
+        }
+    }
+}
+
+impl Period for LinearRegression {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for LinearRegression {
+    type State = LinearRegressionState;
+
+    fn state(&self) -> Self::State {
+        LinearRegressionState {
+            period: self.period,
+            inputs: ring_values(&self.deque, self.index, self.count),
+            sum_y: self.sum_y,
+            sum_xy: self.sum_xy,
+            sum_yy: self.sum_yy,
+            output: self.output.clone(),
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous_sum_y: self.saved_sum_y,
+            previous_sum_xy: self.saved_sum_xy,
+            previous_sum_yy: self.saved_sum_yy,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.inputs.is_empty() {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let (deque, index) = ring_from_values(state.period, &state.inputs)?;
+        indicator.deque = deque;
+        indicator.index = index;
+        indicator.count = state.inputs.len();
+        indicator.sum_y = state.sum_y;
+        indicator.sum_xy = state.sum_xy;
+        indicator.sum_yy = state.sum_yy;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_sum_y = state.previous_sum_y;
+        indicator.saved_sum_xy = state.previous_sum_xy;
+        indicator.saved_sum_yy = state.previous_sum_yy;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(LinearRegression, 2);
+
+impl SetPeriod for LinearRegression {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for LinearRegression {
+    type Output = LinearRegressionOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
+        self.saved_sum_y = self.sum_y;
+        self.saved_sum_xy = self.sum_xy;
+        self.saved_sum_yy = self.sum_yy;
+        let sums = self.sums_with(input);
+        (self.count, self.sum_y, self.sum_xy, self.sum_yy) = sums;
+
+        self.deque[self.index] = input;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        let output = Self::regression(sums);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for LinearRegression {
+    type Output = LinearRegressionOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for LinearRegression {
+    fn peek(&self, input: f64) -> LinearRegressionOutput {
+        Self::regression(self.sums_with(input))
+    }
+}
+
+impl<T: Close> Peek<&T> for LinearRegression {
+    fn peek(&self, input: &T) -> LinearRegressionOutput {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for LinearRegression {
+    fn amend(&mut self, input: f64) -> LinearRegressionOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.sum_y = self.saved_sum_y;
+        self.sum_xy = self.saved_sum_xy;
+        self.sum_yy = self.saved_sum_yy;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for LinearRegression {
+    fn amend(&mut self, input: &T) -> LinearRegressionOutput {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for LinearRegression {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.sum_y = 0.0;
+        self.sum_xy = 0.0;
+        self.sum_yy = 0.0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+        self.saved_sum_y = 0.0;
+        self.saved_sum_xy = 0.0;
+        self.saved_sum_yy = 0.0;
+    }
+}
+
+impl Current for LinearRegression {
+    type Output = LinearRegressionOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for LinearRegression {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for LinearRegression {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for LinearRegression {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for LinearRegression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LINREG({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(LinearRegression);
+
+    fn output(out: LinearRegressionOutput) -> (f64, f64, f64, f64) {
+        (
+            round(out.slope),
+            round(out.intercept),
+            round(out.forecast),
+            round(out.r_squared),
+        )
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(LinearRegression::new(0).is_err());
+        assert!(LinearRegression::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut lr = LinearRegression::new(3).unwrap();
+
+        assert_eq!(output(lr.next(2.0)), (0.0, 2.0, 2.0, 1.0));
+        assert_eq!(output(lr.next(5.0)), (3.0, 2.0, 8.0, 1.0));
+        assert_eq!(output(lr.next(1.0)), (-0.5, 3.167, 1.667, 0.058));
+        assert_eq!(output(lr.next(6.25)), (0.625, 3.458, 5.333, 0.052));
+        assert_eq!(output(lr.next(4.0)), (1.5, 2.25, 6.75, 0.324));
+    }
+
+    #[test]
+    fn test_standard_error() {
+        let mut lr = LinearRegression::new(3).unwrap();
+
+        lr.next(2.0);
+        lr.next(5.0);
+        assert_eq!(lr.standard_error(), 0.0);
+        lr.next(1.0);
+        assert_eq!(round(lr.standard_error()), 2.858);
+        lr.next(2.0);
+        lr.next(3.0);
+        assert_eq!(round(lr.standard_error()), 0.0);
+    }
+
+    #[test]
+    fn test_next_flat() {
+        let mut lr = LinearRegression::new(2).unwrap();
+
+        lr.next(3.0);
+        lr.next(3.0);
+        assert_eq!(output(lr.next(3.0)), (0.0, 3.0, 3.0, 1.0));
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut lr = LinearRegression::new(3).unwrap();
+
+        assert_eq!(output(lr.next(&Bar::new().close(2))), (0.0, 2.0, 2.0, 1.0));
+        assert_eq!(output(lr.next(&Bar::new().close(5))), (3.0, 2.0, 8.0, 1.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut lr = LinearRegression::new(3).unwrap();
+
+        lr.next(2.0);
+        lr.next(5.0);
+        lr.next(1.0);
+
+        lr.reset();
+        assert_eq!(output(lr.next(2.0)), (0.0, 2.0, 2.0, 1.0));
+        assert_eq!(output(lr.next(5.0)), (3.0, 2.0, 8.0, 1.0));
+    }
+
+    #[test]
+    fn test_default() {
+        LinearRegression::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let lr = LinearRegression::new(14).unwrap();
+        assert_eq!(format!("{}", lr), "LINREG(14)");
+    }
+}
@@ -0,0 +1,320 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Logarithmic return.
+///
+/// Natural logarithm of the ratio between the value and the value _lag_ bars ago.
+/// Unlike [simple returns](struct.SimpleReturn.html), log returns add up over time.
+///
+/// # Formula
+///
+/// R = ln(p<sub>t</sub> / p<sub>t-n</sub>)
+///
+/// Where _n_ is the _lag_.
+///
+/// The return is 0 until _lag_ previous values are available, and when the ratio is not positive.
+///
+/// # Parameters
+///
+/// * _lag_ - number of bars (integer greater than 0). Default is 1.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::LogReturn;
+/// use ta::Next;
+///
+/// let mut ret = LogReturn::new(1).unwrap();
+/// assert_eq!(ret.next(10.0), 0.0);
+/// assert_eq!((ret.next(11.0) * 10000.0).round(), 953.0);
+/// ```
+///
+/// # Links
+///
+/// * [Rate of return, Wikipedia](https://en.wikipedia.org/wiki/Rate_of_return#Logarithmic_or_continuously_compounded_return)
+///
+#[derive(Debug, Clone)]
+pub struct LogReturn {
+    lag: usize,
+    index: usize,
+    count: usize,
+    deque: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+}
+
+/// State of [LogReturn](struct.LogReturn.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogReturnState {
+    pub lag: usize,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<f64>,
+    /// Last output, `None` before the first input.
+    pub output: Option<f64>,
+    /// Input which the last one pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
+}
+
+impl LogReturn {
+    pub fn new(lag: usize) -> Result<Self> {
+        match lag {
+            0 => Err(TaError::invalid_parameter("lag", 0.0, ">= 1")),
+            _ => Ok(Self {
+                lag,
+                index: 0,
+                count: 0,
+                deque: vec![0.0; lag].into_boxed_slice(),
+                output: None,
+                saved_value: 0.0,
+                saved_count: 0,
+            }),
+        }
+    }
+}
+
+impl Period for LogReturn {
+    fn period(&self) -> usize {
+        self.lag
+    }
+}
+
+impl Checkpoint for LogReturn {
+    type State = LogReturnState;
+
+    fn state(&self) -> Self::State {
+        LogReturnState {
+            lag: self.lag,
+            inputs: ring_values(&self.deque, self.index, self.count),
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count >= self.lag).then_some(self.saved_value),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.inputs.is_empty()
+            || (state.evicted.is_some() && state.inputs.len() != state.lag)
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.lag).map_err(|_| TaError::InvalidState)?;
+        let (deque, index) = ring_from_values(state.lag, &state.inputs)?;
+        indicator.deque = deque;
+        indicator.index = index;
+        indicator.count = state.inputs.len();
+        indicator.output = state.output;
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(LogReturn, 2);
+
+impl SetPeriod for LogReturn {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for LogReturn {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
+        let previous = self.deque[self.index];
+        self.deque[self.index] = input;
+
+        self.index = if self.index + 1 < self.lag {
+            self.index + 1
+        } else {
+            0
+        };
+
+        self.count = (self.count + 1).min(self.lag + 1);
+        let ratio = input / previous;
+        let output = if self.count > self.lag && ratio > 0.0 && ratio.is_finite() {
+            ratio.ln()
+        } else {
+            0.0
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for LogReturn {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for LogReturn {
+    fn peek(&self, input: f64) -> f64 {
+        let ratio = input / self.deque[self.index];
+        if self.count + 1 > self.lag && ratio > 0.0 && ratio.is_finite() {
+            ratio.ln()
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for LogReturn {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for LogReturn {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.lag - 1) % self.lag;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for LogReturn {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for LogReturn {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        for i in 0..self.lag {
+            self.deque[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+    }
+}
+
+impl Current for LogReturn {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for LogReturn {
+    fn is_ready(&self) -> bool {
+        self.count > self.lag
+    }
+}
+
+impl Lookback for LogReturn {
+    fn lookback(&self) -> usize {
+        self.lag + 1
+    }
+}
+
+impl Default for LogReturn {
+    fn default() -> Self {
+        Self::new(1).unwrap()
+    }
+}
+
+impl fmt::Display for LogReturn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LOGRET({})", self.lag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(LogReturn);
+
+    #[test]
+    fn test_new() {
+        assert!(LogReturn::new(0).is_err());
+        assert!(LogReturn::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut ret = LogReturn::new(1).unwrap();
+
+        assert_eq!(ret.next(10.0), 0.0);
+        assert_eq!(round(ret.next(11.0)), 0.095);
+        assert_eq!(round(ret.next(10.0)), -0.095);
+        assert_eq!(ret.next(10.0), 0.0);
+    }
+
+    #[test]
+    fn test_next_with_lag() {
+        let mut ret = LogReturn::new(2).unwrap();
+
+        assert_eq!(ret.next(10.0), 0.0);
+        assert_eq!(ret.next(11.0), 0.0);
+        assert_eq!(round(ret.next(12.0)), 0.182);
+        assert_eq!(round(ret.next(8.8)), -0.223);
+    }
+
+    #[test]
+    fn test_next_non_positive() {
+        let mut ret = LogReturn::new(1).unwrap();
+
+        ret.next(0.0);
+        assert_eq!(ret.next(5.0), 0.0);
+        assert_eq!(ret.next(-5.0), 0.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut ret = LogReturn::new(1).unwrap();
+
+        assert_eq!(ret.next(&Bar::new().close(10)), 0.0);
+        assert_eq!(round(ret.next(&Bar::new().close(11))), 0.095);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ret = LogReturn::new(1).unwrap();
+
+        ret.next(10.0);
+        ret.next(11.0);
+
+        ret.reset();
+        assert_eq!(ret.next(11.0), 0.0);
+        assert_eq!(round(ret.next(10.0)), -0.095);
+    }
+
+    #[test]
+    fn test_default() {
+        LogReturn::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let ret = LogReturn::new(5).unwrap();
+        assert_eq!(format!("{}", ret), "LOGRET(5)");
+    }
+}
@@ -0,0 +1,296 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::{Amend, Checkpoint, Current, High, IsReady, Lookback, Low, Next, Peek, Reset, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Market Facilitation Index (BW MFI).
+///
+/// Bill Williams' measure of how much the price moves per unit of volume. Every bar is also
+/// classified by comparing its MFI and volume with those of the previous bar:
+///
+/// | MFI  | Volume | State |
+/// |------|--------|-------|
+/// | up   | up     | [Green](enum.FacilitationState.html#variant.Green) |
+/// | down | down   | [Fade](enum.FacilitationState.html#variant.Fade) |
+/// | up   | down   | [Fake](enum.FacilitationState.html#variant.Fake) |
+/// | down | up     | [Squat](enum.FacilitationState.html#variant.Squat) |
+///
+/// A value that did not increase counts as down. The first bar has no state.
+///
+/// # Formula
+///
+/// MFI = (High - Low) / Volume
+///
+/// MFI is 0 for a bar without volume.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{FacilitationState, MarketFacilitationIndex};
+/// use ta::{DataItem, Next};
+///
+/// fn bar(high: f64, low: f64, volume: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(low).high(high).low(low).close(high)
+///         .volume(volume)
+///         .build().unwrap()
+/// }
+///
+/// let mut mfi = MarketFacilitationIndex::new();
+///
+/// let output = mfi.next(&bar(10.0, 8.0, 100.0));
+/// assert_eq!(output.value, 0.02);
+/// assert_eq!(output.state, None);
+///
+/// let output = mfi.next(&bar(13.0, 9.0, 50.0));
+/// assert_eq!(output.value, 0.08);
+/// assert_eq!(output.state, Some(FacilitationState::Fake));
+/// ```
+///
+/// # Links
+///
+/// * [Market Facilitation Index, Wikipedia](https://en.wikipedia.org/wiki/Market_facilitation_index)
+///
+#[doc(alias = "BW MFI")]
+#[derive(Debug, Clone, Default)]
+pub struct MarketFacilitationIndex {
+    prev: Option<(f64, f64)>,
+    output: Option<MarketFacilitationIndexOutput>,
+    // Index and volume before the last input, restored by `amend`
+    saved_prev: Option<(f64, f64)>,
+}
+
+/// State of [MarketFacilitationIndex](struct.MarketFacilitationIndex.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketFacilitationIndexState {
+    /// Value and volume of the previous bar.
+    pub previous: Option<(f64, f64)>,
+    pub output: Option<MarketFacilitationIndexOutput>,
+    /// Index and volume of the bar before the last one, to amend the last one.
+    pub before_last: Option<(f64, f64)>,
+}
+
+/// Market state of a bar, see [MarketFacilitationIndex](struct.MarketFacilitationIndex.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FacilitationState {
+    /// MFI and volume up: the market is moving and new players are entering.
+    Green,
+    /// MFI and volume down: the market has lost interest.
+    Fade,
+    /// MFI up, volume down: the move is not backed by volume.
+    Fake,
+    /// MFI down, volume up: a battle between buyers and sellers, often before a breakout.
+    Squat,
+}
+
+impl fmt::Display for FacilitationState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FacilitationState::Green => write!(f, "green"),
+            FacilitationState::Fade => write!(f, "fade"),
+            FacilitationState::Fake => write!(f, "fake"),
+            FacilitationState::Squat => write!(f, "squat"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<FacilitationState> for crate::OutputValue {
+    fn from(state: FacilitationState) -> Self {
+        crate::OutputValue::Label(state.to_string())
+    }
+}
+
+/// Output of [MarketFacilitationIndex](struct.MarketFacilitationIndex.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketFacilitationIndexOutput {
+    pub value: f64,
+    pub state: Option<FacilitationState>,
+}
+
+impl_output!(MarketFacilitationIndexOutput, value, state);
+
+impl MarketFacilitationIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Index of a bar and its state against the previous bar
+    fn value<T: High + Low + Volume>(&self, input: &T) -> MarketFacilitationIndexOutput {
+        let volume = input.volume();
+        let value = if volume > 0.0 {
+            (input.high() - input.low()) / volume
+        } else {
+            0.0
+        };
+
+        let state = self.prev.map(|(prev_value, prev_volume)| {
+            match (value > prev_value, volume > prev_volume) {
+                (true, true) => FacilitationState::Green,
+                (false, false) => FacilitationState::Fade,
+                (true, false) => FacilitationState::Fake,
+                (false, true) => FacilitationState::Squat,
+            }
+        });
+
+        MarketFacilitationIndexOutput { value, state }
+    }
+}
+
+impl Checkpoint for MarketFacilitationIndex {
+    type State = MarketFacilitationIndexState;
+
+    fn state(&self) -> Self::State {
+        MarketFacilitationIndexState {
+            previous: self.prev,
+            output: self.output.clone(),
+            before_last: self.saved_prev,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(Self {
+            prev: state.previous,
+            output: state.output,
+            saved_prev: state.before_last,
+        })
+    }
+}
+
+impl_versioned_serde!(MarketFacilitationIndex, 2);
+
+impl<T: High + Low + Volume> Next<&T> for MarketFacilitationIndex {
+    type Output = MarketFacilitationIndexOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_prev = self.prev;
+        let output = self.value(input);
+        self.prev = Some((output.value, input.volume()));
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low + Volume> Peek<&T> for MarketFacilitationIndex {
+    fn peek(&self, input: &T) -> MarketFacilitationIndexOutput {
+        self.value(input)
+    }
+}
+
+impl<T: High + Low + Volume> Amend<&T> for MarketFacilitationIndex {
+    fn amend(&mut self, input: &T) -> MarketFacilitationIndexOutput {
+        self.prev = self.saved_prev;
+        self.next(input)
+    }
+}
+
+impl Reset for MarketFacilitationIndex {
+    fn reset(&mut self) {
+        self.prev = None;
+        self.output = None;
+        self.saved_prev = None;
+    }
+}
+
+impl Current for MarketFacilitationIndex {
+    type Output = MarketFacilitationIndexOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for MarketFacilitationIndex {
+    fn is_ready(&self) -> bool {
+        self.prev.is_some()
+    }
+}
+
+impl Lookback for MarketFacilitationIndex {
+    fn lookback(&self) -> usize {
+        1
+    }
+}
+
+impl fmt::Display for MarketFacilitationIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BW_MFI")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn bar(high: f64, low: f64, volume: f64) -> Bar {
+        Bar::new().high(high).low(low).volume(volume)
+    }
+
+    #[test]
+    fn test_next() {
+        let mut mfi = MarketFacilitationIndex::new();
+
+        let output = mfi.next(&bar(10.0, 8.0, 100.0));
+        assert_eq!(output.value, 0.02);
+        assert_eq!(output.state, None);
+
+        let output = mfi.next(&bar(12.0, 8.0, 150.0));
+        assert_eq!(round(output.value), 0.027);
+        assert_eq!(output.state, Some(FacilitationState::Green));
+
+        let output = mfi.next(&bar(12.0, 11.0, 100.0));
+        assert_eq!(output.value, 0.01);
+        assert_eq!(output.state, Some(FacilitationState::Fade));
+
+        let output = mfi.next(&bar(13.0, 10.0, 50.0));
+        assert_eq!(output.value, 0.06);
+        assert_eq!(output.state, Some(FacilitationState::Fake));
+
+        let output = mfi.next(&bar(12.0, 11.5, 200.0));
+        assert_eq!(output.value, 0.0025);
+        assert_eq!(output.state, Some(FacilitationState::Squat));
+
+        // Unchanged counts as down
+        let output = mfi.next(&bar(12.0, 11.5, 200.0));
+        assert_eq!(output.state, Some(FacilitationState::Fade));
+    }
+
+    #[test]
+    fn test_no_volume() {
+        let mut mfi = MarketFacilitationIndex::new();
+        assert_eq!(mfi.next(&bar(10.0, 8.0, 0.0)).value, 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut mfi = MarketFacilitationIndex::new();
+
+        mfi.next(&bar(10.0, 8.0, 100.0));
+        mfi.reset();
+        assert_eq!(mfi.next(&bar(13.0, 9.0, 50.0)).state, None);
+    }
+
+    #[test]
+    fn test_default() {
+        MarketFacilitationIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let mfi = MarketFacilitationIndex::new();
+        assert_eq!(format!("{}", mfi), "BW_MFI");
+
+        let output = MarketFacilitationIndexOutput {
+            value: 0.02,
+            state: Some(FacilitationState::Squat),
+        };
+        assert_eq!(format!("{}", output), "value: 0.02, state: squat");
+    }
+}
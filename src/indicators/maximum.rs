@@ -1,7 +1,10 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{High, Next, Period, Reset};
+use crate::{
+    Amend, Checkpoint, Current, High, IsReady, Lookback, Next, Numeric, Peek, Period, Reset,
+    SetPeriod,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -24,30 +27,55 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(max.next(4.0), 5.0);
 /// assert_eq!(max.next(8.0), 8.0);
 /// ```
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct Maximum {
+pub struct Maximum<N = f64> {
     period: usize,
     max_index: usize,
     cur_index: usize,
-    deque: Box<[f64]>,
+    count: usize,
+    deque: Box<[N]>,
+    output: Option<N>,
+    // State before the last input, restored by `amend`
+    saved_max_index: usize,
+    saved_count: usize,
+    saved_value: N,
+}
+
+/// State of [Maximum](generic/struct.Maximum.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaximumState<N = f64> {
+    pub period: usize,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<N>,
 }
 
-impl Maximum {
+impl<N: Numeric> Maximum<N> {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
             _ => Ok(Self {
                 period,
                 max_index: 0,
                 cur_index: 0,
-                deque: vec![f64::NEG_INFINITY; period].into_boxed_slice(),
+                count: 0,
+                deque: vec![N::lowest(); period].into_boxed_slice(),
+                output: None,
+                saved_max_index: 0,
+                saved_count: 0,
+                saved_value: N::lowest(),
             }),
         }
     }
 
+    /// Inputs in the window, the oldest first.
+    fn inputs(&self) -> impl Iterator<Item = N> + '_ {
+        let oldest = self.cur_index + self.period - self.count;
+        (0..self.count).map(move |i| self.deque[(oldest + i) % self.period])
+    }
+
     fn find_max_index(&self) -> usize {
-        let mut max = f64::NEG_INFINITY;
+        let mut max = N::lowest();
         let mut index: usize = 0;
 
         for (i, &val) in self.deque.iter().enumerate() {
@@ -59,18 +87,11 @@ impl Maximum {
 
         index
     }
-}
-
-impl Period for Maximum {
-    fn period(&self) -> usize {
-        self.period
-    }
-}
 
-impl Next<f64> for Maximum {
-    type Output = f64;
-
-    fn next(&mut self, input: f64) -> Self::Output {
+    fn next_value(&mut self, input: N) -> N {
+        self.saved_max_index = self.max_index;
+        self.saved_count = self.count;
+        self.saved_value = self.deque[self.cur_index];
         self.deque[self.cur_index] = input;
 
         if input > self.deque[self.max_index] {
@@ -84,34 +105,142 @@ impl Next<f64> for Maximum {
         } else {
             0
         };
+        self.count = (self.count + 1).min(self.period);
+
+        let output = self.deque[self.max_index];
+        self.output = Some(output);
+        output
+    }
+
+    fn peek_value(&self, input: N) -> N {
+        if self.max_index != self.cur_index {
+            input.max(self.deque[self.max_index])
+        } else {
+            self.deque
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != self.cur_index)
+                .fold(input, |max, (_, &value)| max.max(value))
+        }
+    }
+
+    fn amend_value(&mut self, input: N) -> N {
+        if self.output.is_none() {
+            return self.next_value(input);
+        }
+        self.cur_index = (self.cur_index + self.period - 1) % self.period;
+        self.deque[self.cur_index] = self.saved_value;
+        self.max_index = self.saved_max_index;
+        self.count = self.saved_count;
+        self.next_value(input)
+    }
+}
 
-        self.deque[self.max_index]
+impl<N: Numeric> Period for Maximum<N> {
+    fn period(&self) -> usize {
+        self.period
     }
 }
 
-impl<T: High> Next<&T> for Maximum {
-    type Output = f64;
+impl<N: Numeric> Checkpoint for Maximum<N> {
+    type State = MaximumState<N>;
+
+    fn state(&self) -> Self::State {
+        MaximumState {
+            period: self.period,
+            inputs: self.inputs().collect(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.period == 0 || state.inputs.len() > state.period {
+            return Err(TaError::InvalidState);
+        }
+        // Replay the inputs, so the last one can be amended
+        let mut indicator = Self::new(state.period)?;
+        for input in state.inputs {
+            indicator.next_value(input);
+        }
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(Maximum<N>, 1);
+
+impl<N: Numeric> SetPeriod for Maximum<N> {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        // Replay the latest inputs that fit in the new window
+        let mut resized = Self::new(period)?;
+        for input in self.inputs().skip(self.count.saturating_sub(period)) {
+            resized.next_value(input);
+        }
+        *self = resized;
+        Ok(())
+    }
+}
+
+impl_numeric_inputs!(Maximum);
+
+impl<N: Numeric, T: High<N>> Next<&T> for Maximum<N> {
+    type Output = N;
 
     fn next(&mut self, input: &T) -> Self::Output {
-        self.next(input.high())
+        self.next_value(input.high())
     }
 }
 
-impl Reset for Maximum {
+impl<N: Numeric, T: High<N>> Peek<&T> for Maximum<N> {
+    fn peek(&self, input: &T) -> N {
+        self.peek_value(input.high())
+    }
+}
+
+impl<N: Numeric, T: High<N>> Amend<&T> for Maximum<N> {
+    fn amend(&mut self, input: &T) -> N {
+        self.amend_value(input.high())
+    }
+}
+
+impl<N: Numeric> Reset for Maximum<N> {
     fn reset(&mut self) {
         for i in 0..self.period {
-            self.deque[i] = f64::NEG_INFINITY;
+            self.deque[i] = N::lowest();
         }
+        self.count = 0;
+        self.output = None;
+        self.saved_max_index = 0;
+        self.saved_count = 0;
+        self.saved_value = N::lowest();
     }
 }
 
-impl Default for Maximum {
+impl<N: Numeric> Current for Maximum<N> {
+    type Output = N;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<N: Numeric> IsReady for Maximum<N> {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl<N: Numeric> Lookback for Maximum<N> {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl<N: Numeric> Default for Maximum<N> {
     fn default() -> Self {
         Self::new(14).unwrap()
     }
 }
 
-impl fmt::Display for Maximum {
+impl<N: Numeric> fmt::Display for Maximum<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "MAX({})", self.period)
     }
@@ -120,6 +249,7 @@ impl fmt::Display for Maximum {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::Maximum;
     use crate::test_helper::*;
 
     test_indicator!(Maximum);
@@ -170,6 +300,18 @@ mod tests {
         assert_eq!(max.next(4.0), 4.0);
     }
 
+    #[test]
+    fn test_is_ready() {
+        let mut max = Maximum::new(2).unwrap();
+        max.next(1.0);
+        assert!(!max.is_ready());
+        max.next(2.0);
+        assert!(max.is_ready());
+
+        max.reset();
+        assert!(!max.is_ready());
+    }
+
     #[test]
     fn test_default() {
         Maximum::default();
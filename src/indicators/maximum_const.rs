@@ -0,0 +1,319 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Amend, Checkpoint, Current, High, IsReady, Lookback, Next, Peek, Period, Reset};
+
+/// Returns the highest value in a time frame known at compile time.
+///
+/// Same as [Maximum](crate::indicators::Maximum), but the inputs are kept in an array of _N_
+/// values instead of a heap allocated buffer, so it needs no allocation and is available
+/// without the `std` feature. Unlike the other indicators it can't be serialized.
+///
+/// # Parameters
+///
+/// * _N_ - size of the time frame (integer greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::MaximumConst;
+/// use ta::Next;
+///
+/// let mut max = MaximumConst::<3>::new().unwrap();
+/// assert_eq!(max.next(7.0), 7.0);
+/// assert_eq!(max.next(5.0), 7.0);
+/// assert_eq!(max.next(4.0), 7.0);
+/// assert_eq!(max.next(4.0), 5.0);
+/// assert_eq!(max.next(8.0), 8.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaximumConst<const N: usize> {
+    max_index: usize,
+    cur_index: usize,
+    count: usize,
+    deque: [f64; N],
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_max_index: usize,
+    saved_count: usize,
+    saved_value: f64,
+}
+
+/// State of [MaximumConst](struct.MaximumConst.html), see [Checkpoint](../trait.Checkpoint.html).
+/// Kept in an array as well, so it needs no allocation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaximumConstState<const N: usize> {
+    /// Number of inputs in the window, up to _N_.
+    pub count: usize,
+    /// Inputs in the window, the oldest first, followed by zeros.
+    pub inputs: [f64; N],
+}
+
+impl<const N: usize> MaximumConst<N> {
+    pub fn new() -> Result<Self> {
+        match N {
+            0 => Err(TaError::invalid_parameter("N", 0.0, ">= 1")),
+            _ => Ok(Self {
+                max_index: 0,
+                cur_index: 0,
+                count: 0,
+                deque: [f64::NEG_INFINITY; N],
+                output: None,
+                saved_max_index: 0,
+                saved_count: 0,
+                saved_value: f64::NEG_INFINITY,
+            }),
+        }
+    }
+
+    fn find_max_index(&self) -> usize {
+        let mut max = f64::NEG_INFINITY;
+        let mut index: usize = 0;
+
+        for (i, &val) in self.deque.iter().enumerate() {
+            if val > max {
+                max = val;
+                index = i;
+            }
+        }
+
+        index
+    }
+}
+
+impl<const N: usize> Period for MaximumConst<N> {
+    fn period(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Checkpoint for MaximumConst<N> {
+    type State = MaximumConstState<N>;
+
+    fn state(&self) -> Self::State {
+        let mut inputs = [0.0; N];
+        let oldest = self.cur_index + N - self.count;
+        for (i, input) in inputs.iter_mut().take(self.count).enumerate() {
+            *input = self.deque[(oldest + i) % N];
+        }
+        MaximumConstState {
+            count: self.count,
+            inputs,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.count > N {
+            return Err(TaError::InvalidState);
+        }
+        // Replay the inputs, so the last one can be amended
+        let mut indicator = Self::new()?;
+        for &input in &state.inputs[..state.count] {
+            indicator.next(input);
+        }
+        Ok(indicator)
+    }
+}
+
+impl<const N: usize> Next<f64> for MaximumConst<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_max_index = self.max_index;
+        self.saved_count = self.count;
+        self.saved_value = self.deque[self.cur_index];
+        self.deque[self.cur_index] = input;
+
+        if input > self.deque[self.max_index] {
+            self.max_index = self.cur_index;
+        } else if self.max_index == self.cur_index {
+            self.max_index = self.find_max_index();
+        }
+
+        self.cur_index = if self.cur_index + 1 < N {
+            self.cur_index + 1
+        } else {
+            0
+        };
+        self.count = (self.count + 1).min(N);
+
+        let output = self.deque[self.max_index];
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<const N: usize, T: High> Next<&T> for MaximumConst<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.high())
+    }
+}
+
+impl<const N: usize> Peek<f64> for MaximumConst<N> {
+    fn peek(&self, input: f64) -> f64 {
+        if self.max_index != self.cur_index {
+            input.max(self.deque[self.max_index])
+        } else {
+            self.deque
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != self.cur_index)
+                .fold(input, |max, (_, &value)| max.max(value))
+        }
+    }
+}
+
+impl<const N: usize, T: High> Peek<&T> for MaximumConst<N> {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.high())
+    }
+}
+
+impl<const N: usize> Amend<f64> for MaximumConst<N> {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.cur_index = (self.cur_index + N - 1) % N;
+        self.deque[self.cur_index] = self.saved_value;
+        self.max_index = self.saved_max_index;
+        self.count = self.saved_count;
+        self.next(input)
+    }
+}
+
+impl<const N: usize, T: High> Amend<&T> for MaximumConst<N> {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.high())
+    }
+}
+
+impl<const N: usize> Reset for MaximumConst<N> {
+    fn reset(&mut self) {
+        self.max_index = 0;
+        self.cur_index = 0;
+        self.count = 0;
+        self.deque = [f64::NEG_INFINITY; N];
+        self.output = None;
+        self.saved_max_index = 0;
+        self.saved_count = 0;
+        self.saved_value = f64::NEG_INFINITY;
+    }
+}
+
+impl<const N: usize> Current for MaximumConst<N> {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<const N: usize> IsReady for MaximumConst<N> {
+    fn is_ready(&self) -> bool {
+        self.count == N
+    }
+}
+
+impl<const N: usize> Lookback for MaximumConst<N> {
+    fn lookback(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Default for MaximumConst<N> {
+    fn default() -> Self {
+        Self::new().unwrap()
+    }
+}
+
+impl<const N: usize> fmt::Display for MaximumConst<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MAX({})", N)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    type MaximumConst14 = MaximumConst<14>;
+
+    test_indicator!(MaximumConst14);
+
+    #[test]
+    fn test_new() {
+        assert!(MaximumConst::<0>::new().is_err());
+        assert!(MaximumConst::<1>::new().is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut max = MaximumConst::<3>::new().unwrap();
+
+        assert_eq!(max.next(4.0), 4.0);
+        assert_eq!(max.next(1.2), 4.0);
+        assert_eq!(max.next(5.0), 5.0);
+        assert_eq!(max.next(3.0), 5.0);
+        assert_eq!(max.next(4.0), 5.0);
+        assert_eq!(max.next(0.0), 4.0);
+        assert_eq!(max.next(-1.0), 4.0);
+        assert_eq!(max.next(-2.0), 0.0);
+        assert_eq!(max.next(-1.5), -1.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_same_as_dynamic() {
+        use crate::indicators::Maximum;
+
+        let mut max = MaximumConst::<5>::new().unwrap();
+        let mut dynamic = Maximum::new(5).unwrap();
+
+        for i in 0..20 {
+            let input = (i * 7 % 11) as f64;
+            assert_eq!(max.next(input), dynamic.next(input));
+            assert_eq!(max.is_ready(), dynamic.is_ready());
+        }
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        fn bar(high: f64) -> Bar {
+            Bar::new().high(high)
+        }
+
+        let mut max = MaximumConst::<2>::new().unwrap();
+
+        assert_eq!(max.next(&bar(1.1)), 1.1);
+        assert_eq!(max.next(&bar(4.0)), 4.0);
+        assert_eq!(max.next(&bar(3.5)), 4.0);
+        assert_eq!(max.next(&bar(2.0)), 3.5);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut max = MaximumConst::<100>::new().unwrap();
+        assert_eq!(max.next(4.0), 4.0);
+        assert_eq!(max.next(10.0), 10.0);
+        assert_eq!(max.next(4.0), 10.0);
+
+        max.reset();
+        assert!(!max.is_ready());
+        assert_eq!(max.next(4.0), 4.0);
+    }
+
+    #[test]
+    fn test_default() {
+        MaximumConst::<14>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let indicator = MaximumConst::<7>::new().unwrap();
+        assert_eq!(format!("{}", indicator), "MAX(7)");
+    }
+}
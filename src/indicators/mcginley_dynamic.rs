@@ -0,0 +1,302 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// McGinley Dynamic (MD).
+///
+/// A moving average developed by John R. McGinley that adjusts its speed to the market:
+/// it speeds up when prices fall and slows down when prices rise, which makes it hug
+/// prices more closely than an EMA of the same period.
+///
+/// # Formula
+///
+/// MD<sub>t</sub> = MD<sub>t-1</sub> + (p<sub>t</sub> - MD<sub>t-1</sub>) / (k * n * (p<sub>t</sub> / MD<sub>t-1</sub>)<sup>4</sup>)
+///
+/// Where:
+///
+/// * _p<sub>t</sub>_ - input value at a time period _t_
+/// * _n_ - period
+/// * _k_ - constant factor
+///
+/// The first value of MD is the first input value. When the previous value is 0 the formula
+/// is undefined and MD restarts from the input value.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 14.
+/// * _k_ - constant factor (number greater than 0). Default is 0.6.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::McGinleyDynamic;
+/// use ta::Next;
+///
+/// let mut md = McGinleyDynamic::new(3, 0.6).unwrap();
+/// assert_eq!(md.next(10.0), 10.0);
+/// assert_eq!((md.next(12.0) * 1000.0).round() / 1000.0, 10.536);
+/// ```
+///
+/// # Links
+///
+/// * [McGinley Dynamic, Investopedia](https://www.investopedia.com/articles/forex/09/mcginley-dynamic-indicator.asp)
+///
+#[doc(alias = "MD")]
+#[derive(Debug, Clone)]
+pub struct McGinleyDynamic {
+    period: usize,
+    k: f64,
+    current: f64,
+    is_new: bool,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_current: f64,
+    saved_is_new: bool,
+}
+
+/// State of [McGinleyDynamic](struct.McGinleyDynamic.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct McGinleyDynamicState {
+    pub period: usize,
+    pub k: f64,
+    /// Last output, `None` before the first input.
+    pub current: Option<f64>,
+    /// Value before the last input, to amend it.
+    pub previous: Option<f64>,
+}
+
+impl McGinleyDynamic {
+    pub fn new(period: usize, k: f64) -> Result<Self> {
+        if period == 0 {
+            return Err(TaError::invalid_parameter("period", 0.0, ">= 1"));
+        }
+        if !k.is_finite() || k <= 0.0 {
+            return Err(TaError::invalid_parameter("k", k, "> 0"));
+        }
+
+        Ok(Self {
+            period,
+            k,
+            current: 0.0,
+            is_new: true,
+            output: None,
+            saved_current: 0.0,
+            saved_is_new: true,
+        })
+    }
+
+    pub fn k(&self) -> f64 {
+        self.k
+    }
+}
+
+impl Period for McGinleyDynamic {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for McGinleyDynamic {
+    type State = McGinleyDynamicState;
+
+    fn state(&self) -> Self::State {
+        McGinleyDynamicState {
+            period: self.period,
+            k: self.k,
+            current: self.output,
+            previous: (!self.saved_is_new).then_some(self.saved_current),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(state.period, state.k).map_err(|_| TaError::InvalidState)?;
+        indicator.current = state.current.unwrap_or(0.0);
+        indicator.is_new = state.current.is_none();
+        indicator.output = state.current;
+        indicator.saved_current = state.previous.unwrap_or(0.0);
+        indicator.saved_is_new = state.previous.is_none();
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(McGinleyDynamic, 2);
+
+impl SetPeriod for McGinleyDynamic {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period, self.k)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for McGinleyDynamic {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_current = self.current;
+        self.saved_is_new = self.is_new;
+        if self.is_new || self.current == 0.0 {
+            self.is_new = false;
+            self.current = input;
+        } else {
+            let ratio = input / self.current;
+            self.current += (input - self.current) / (self.k * self.period as f64 * ratio.powi(4));
+        }
+
+        let output = self.current;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for McGinleyDynamic {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for McGinleyDynamic {
+    fn peek(&self, input: f64) -> f64 {
+        if self.is_new || self.current == 0.0 {
+            input
+        } else {
+            let ratio = input / self.current;
+            self.current + (input - self.current) / (self.k * self.period as f64 * ratio.powi(4))
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for McGinleyDynamic {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for McGinleyDynamic {
+    fn amend(&mut self, input: f64) -> f64 {
+        self.current = self.saved_current;
+        self.is_new = self.saved_is_new;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for McGinleyDynamic {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for McGinleyDynamic {
+    fn reset(&mut self) {
+        self.current = 0.0;
+        self.is_new = true;
+        self.output = None;
+        self.saved_current = 0.0;
+        self.saved_is_new = true;
+    }
+}
+
+impl Current for McGinleyDynamic {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for McGinleyDynamic {
+    fn is_ready(&self) -> bool {
+        !self.is_new
+    }
+}
+
+impl Lookback for McGinleyDynamic {
+    fn lookback(&self) -> usize {
+        1
+    }
+}
+
+impl Default for McGinleyDynamic {
+    fn default() -> Self {
+        Self::new(14, 0.6).unwrap()
+    }
+}
+
+impl fmt::Display for McGinleyDynamic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MD({}, {})", self.period, self.k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(McGinleyDynamic);
+
+    #[test]
+    fn test_new() {
+        assert!(McGinleyDynamic::new(0, 0.6).is_err());
+        assert!(McGinleyDynamic::new(14, 0.0).is_err());
+        assert!(McGinleyDynamic::new(14, -0.6).is_err());
+        assert!(McGinleyDynamic::new(14, f64::NAN).is_err());
+        assert!(McGinleyDynamic::new(1, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut md = McGinleyDynamic::new(3, 0.6).unwrap();
+
+        assert_eq!(md.next(10.0), 10.0);
+        assert_eq!(round(md.next(12.0)), 10.536);
+        assert_eq!(round(md.next(11.0)), 10.753);
+        assert_eq!(round(md.next(14.0)), 11.381);
+    }
+
+    #[test]
+    fn test_next_from_zero() {
+        let mut md = McGinleyDynamic::new(3, 0.6).unwrap();
+
+        assert_eq!(md.next(0.0), 0.0);
+        assert_eq!(md.next(5.0), 5.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut md = McGinleyDynamic::new(3, 0.6).unwrap();
+
+        assert_eq!(md.next(&Bar::new().close(10)), 10.0);
+        assert_eq!(round(md.next(&Bar::new().close(12))), 10.536);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut md = McGinleyDynamic::new(3, 0.6).unwrap();
+
+        md.next(10.0);
+        md.next(12.0);
+
+        md.reset();
+        assert_eq!(md.next(12.0), 12.0);
+    }
+
+    #[test]
+    fn test_default() {
+        McGinleyDynamic::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let md = McGinleyDynamic::new(14, 0.6).unwrap();
+        assert_eq!(format!("{}", md), "MD(14, 0.6)");
+    }
+}
@@ -1,10 +1,13 @@
-use std::fmt;
+use core::fmt;
 
+use crate::helpers::{ring_from_values_at, ring_values};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
 
 /// Mean Absolute Deviation (MAD)
 ///
@@ -27,7 +30,6 @@ use crate::{Close, Next, Period, Reset};
 ///
 /// * [Mean Absolute Deviation, Wikipedia](https://en.wikipedia.org/wiki/Mean_absolute_deviation)
 ///
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct MeanAbsoluteDeviation {
     period: usize,
@@ -35,18 +37,48 @@ pub struct MeanAbsoluteDeviation {
     count: usize,
     sum: f64,
     deque: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+    saved_sum: f64,
+}
+
+/// State of [MeanAbsoluteDeviation](struct.MeanAbsoluteDeviation.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeanAbsoluteDeviationState {
+    pub period: usize,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<f64>,
+    /// Slot of the next input in the window, as the deviations are summed over the
+    /// slots in their order.
+    pub index: usize,
+    /// Running sum of the inputs in the window.
+    pub sum: f64,
+    /// Last output, `None` before the first input.
+    pub output: Option<f64>,
+    /// Input which the last one pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
+    /// Running sum before the last input, to amend it.
+    pub previous_sum: f64,
 }
 
 impl MeanAbsoluteDeviation {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
             _ => Ok(Self {
                 period,
                 index: 0,
                 count: 0,
                 sum: 0.0,
                 deque: vec![0.0; period].into_boxed_slice(),
+                output: None,
+                saved_value: 0.0,
+                saved_count: 0,
+                saved_sum: 0.0,
             }),
         }
     }
@@ -58,10 +90,61 @@ impl Period for MeanAbsoluteDeviation {
     }
 }
 
+impl Checkpoint for MeanAbsoluteDeviation {
+    type State = MeanAbsoluteDeviationState;
+
+    fn state(&self) -> Self::State {
+        MeanAbsoluteDeviationState {
+            period: self.period,
+            inputs: ring_values(&self.deque, self.index, self.count),
+            index: self.index,
+            sum: self.sum,
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous_sum: self.saved_sum,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.inputs.is_empty() {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        indicator.deque = ring_from_values_at(state.period, &state.inputs, state.index)?;
+        indicator.index = state.index;
+        indicator.count = state.inputs.len();
+        indicator.sum = state.sum;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_sum = state.previous_sum;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(MeanAbsoluteDeviation, 2);
+
+impl SetPeriod for MeanAbsoluteDeviation {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
 impl Next<f64> for MeanAbsoluteDeviation {
     type Output = f64;
 
     fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
+        self.saved_sum = self.sum;
         self.sum = if self.count < self.period {
             self.count = self.count + 1;
             self.sum + input
@@ -82,7 +165,9 @@ impl Next<f64> for MeanAbsoluteDeviation {
         for value in &self.deque[..self.count] {
             mad += (value - mean).abs();
         }
-        mad / self.count as f64
+        let output = mad / self.count as f64;
+        self.output = Some(output);
+        output
     }
 }
 
@@ -94,6 +179,49 @@ impl<T: Close> Next<&T> for MeanAbsoluteDeviation {
     }
 }
 
+impl Peek<f64> for MeanAbsoluteDeviation {
+    fn peek(&self, input: f64) -> f64 {
+        let (count, sum) = if self.count < self.period {
+            (self.count + 1, self.sum + input)
+        } else {
+            (self.count, self.sum + input - self.deque[self.index])
+        };
+        let mean = sum / count as f64;
+
+        let mut mad = 0.0;
+        for (i, value) in self.deque[..count].iter().enumerate() {
+            let value = if i == self.index { input } else { *value };
+            mad += (value - mean).abs();
+        }
+        mad / count as f64
+    }
+}
+
+impl<T: Close> Peek<&T> for MeanAbsoluteDeviation {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for MeanAbsoluteDeviation {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.sum = self.saved_sum;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for MeanAbsoluteDeviation {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
 impl Reset for MeanAbsoluteDeviation {
     fn reset(&mut self) {
         self.index = 0;
@@ -102,6 +230,30 @@ impl Reset for MeanAbsoluteDeviation {
         for i in 0..self.period {
             self.deque[i] = 0.0;
         }
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+        self.saved_sum = 0.0;
+    }
+}
+
+impl Current for MeanAbsoluteDeviation {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for MeanAbsoluteDeviation {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for MeanAbsoluteDeviation {
+    fn lookback(&self) -> usize {
+        self.period
     }
 }
 
@@ -0,0 +1,148 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::{Amend, Checkpoint, Current, High, IsReady, Lookback, Low, Next, Peek, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Median price (MP).
+///
+/// Turns a bar into a single price: the middle of the high-low range. It can be used as the input
+/// of indicators that take a single price, instead of the close.
+///
+/// # Formula
+///
+/// MP = (High + Low) / 2
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::MedianPrice;
+/// use ta::{DataItem, Next};
+///
+/// let mut median = MedianPrice::new();
+///
+/// let bar = DataItem::builder()
+///     .open(7.0).high(12.0).low(6.0).close(7.0)
+///     .volume(1000.0)
+///     .build().unwrap();
+///
+/// assert_eq!(median.next(&bar), 9.0);
+/// ```
+#[doc(alias = "MP")]
+#[derive(Debug, Clone, Default)]
+pub struct MedianPrice {
+    output: Option<f64>,
+}
+
+/// State of [MedianPrice](struct.MedianPrice.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MedianPriceState {
+    /// Last output, `None` before the first input.
+    pub output: Option<f64>,
+}
+
+impl MedianPrice {
+    pub fn new() -> Self {
+        Self { output: None }
+    }
+}
+
+impl Checkpoint for MedianPrice {
+    type State = MedianPriceState;
+
+    fn state(&self) -> Self::State {
+        MedianPriceState {
+            output: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(Self {
+            output: state.output,
+        })
+    }
+}
+
+impl_versioned_serde!(MedianPrice, 1);
+
+impl<T: High + Low> Next<&T> for MedianPrice {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let output = (input.high() + input.low()) / 2.0;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low> Peek<&T> for MedianPrice {
+    fn peek(&self, input: &T) -> f64 {
+        (input.high() + input.low()) / 2.0
+    }
+}
+
+impl<T: High + Low> Amend<&T> for MedianPrice {
+    fn amend(&mut self, input: &T) -> f64 {
+        // The output only depends on the input
+        self.next(input)
+    }
+}
+
+impl Reset for MedianPrice {
+    fn reset(&mut self) {
+        self.output = None;
+    }
+}
+
+impl Current for MedianPrice {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for MedianPrice {
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+impl Lookback for MedianPrice {
+    fn lookback(&self) -> usize {
+        1
+    }
+}
+
+impl fmt::Display for MedianPrice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MP")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_next() {
+        let mut mp = MedianPrice::new();
+
+        assert_eq!(mp.next(&Bar::new().high(10).low(4).close(9)), 7.0);
+        assert_eq!(mp.next(&Bar::new().high(12).low(7).close(7)), 9.5);
+    }
+
+    #[test]
+    fn test_default() {
+        MedianPrice::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let mp = MedianPrice::new();
+        assert_eq!(format!("{}", mp), "MP");
+    }
+}
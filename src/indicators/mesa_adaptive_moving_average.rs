@@ -0,0 +1,392 @@
+use core::fmt;
+
+use super::hilbert_transform::{HilbertTransform, HilbertTransformOutput, HilbertTransformState};
+use crate::errors::{Result, TaError};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// MESA Adaptive Moving Average (MAMA) and Following Adaptive Moving Average (FAMA).
+///
+/// Developed by John Ehlers, MAMA adapts to the rate of change of the phase measured by the
+/// Hilbert transform: the smoothing constant is large when the phase changes quickly and small
+/// when it changes slowly. FAMA applies the same adaptation at half the rate to MAMA, and the
+/// crossings of both lines are used as signals.
+///
+/// # Formula
+///
+/// Phase = arctan(Q1 / I1)
+///
+/// ΔPhase = max(Phase<sub>t-1</sub> - Phase<sub>t</sub>, 1)
+///
+/// α = max(_fast_limit_ / ΔPhase, _slow_limit_)
+///
+/// MAMA<sub>t</sub> = α * p<sub>t</sub> + (1 - α) * MAMA<sub>t-1</sub>
+///
+/// FAMA<sub>t</sub> = α / 2 * MAMA<sub>t</sub> + (1 - α / 2) * FAMA<sub>t-1</sub>
+///
+/// Where:
+///
+/// * _I1_, _Q1_ - in-phase and quadrature components of the Hilbert transform of the price
+/// * _Phase_ - in degrees
+///
+/// During the first 5 bars the Hilbert transform has not enough data yet, and both MAMA and FAMA
+/// are equal to the input value.
+///
+/// # Parameters
+///
+/// * _fast_limit_ - maximum smoothing constant (number in range 0..1). Default is 0.5.
+/// * _slow_limit_ - minimum smoothing constant (number in range 0.._fast_limit_). Default is 0.05.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::MesaAdaptiveMovingAverage;
+/// use ta::Next;
+///
+/// let mut mama = MesaAdaptiveMovingAverage::new(0.5, 0.05).unwrap();
+///
+/// for x in &[10.0, 12.0, 11.0, 14.0, 13.0] {
+///     let out = mama.next(*x);
+///     assert_eq!(out.mama, *x);
+/// }
+///
+/// let out = mama.next(9.0);
+/// assert_eq!(out.mama, 11.0);
+/// assert_eq!(out.fama, 12.5);
+/// ```
+///
+/// # Links
+///
+/// * [MESA Adaptive Moving Averages, John Ehlers (PDF)](https://www.mesasoftware.com/papers/MAMA.pdf)
+///
+#[doc(alias = "MAMA")]
+#[doc(alias = "FAMA")]
+#[derive(Debug, Clone)]
+pub struct MesaAdaptiveMovingAverage {
+    fast_limit: f64,
+    slow_limit: f64,
+    hilbert: HilbertTransform,
+    phase: f64,
+    mama: f64,
+    fama: f64,
+    output: Option<MesaAdaptiveMovingAverageOutput>,
+    // State before the last input, restored by `amend`
+    saved: (f64, f64, f64),
+}
+
+/// State of [MesaAdaptiveMovingAverage](struct.MesaAdaptiveMovingAverage.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MesaAdaptiveMovingAverageState {
+    pub fast_limit: f64,
+    pub slow_limit: f64,
+    pub hilbert: HilbertTransformState,
+    /// Phase of the last cycle, in degrees.
+    pub phase: f64,
+    pub mama: f64,
+    pub fama: f64,
+    /// Phase, MAMA and FAMA before the last input, to amend it.
+    pub previous: (f64, f64, f64),
+}
+
+/// Output of [MesaAdaptiveMovingAverage](struct.MesaAdaptiveMovingAverage.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MesaAdaptiveMovingAverageOutput {
+    pub mama: f64,
+    pub fama: f64,
+}
+
+impl_output!(MesaAdaptiveMovingAverageOutput, mama, fama);
+
+impl From<MesaAdaptiveMovingAverageOutput> for (f64, f64) {
+    fn from(o: MesaAdaptiveMovingAverageOutput) -> Self {
+        (o.mama, o.fama)
+    }
+}
+
+impl MesaAdaptiveMovingAverage {
+    pub fn new(fast_limit: f64, slow_limit: f64) -> Result<Self> {
+        if !(fast_limit > 0.0 && fast_limit <= 1.0) {
+            return Err(TaError::invalid_parameter(
+                "fast_limit",
+                fast_limit,
+                "0 < fast_limit <= 1",
+            ));
+        }
+        if !(slow_limit > 0.0 && slow_limit <= fast_limit) {
+            return Err(TaError::invalid_parameter(
+                "slow_limit",
+                slow_limit,
+                "0 < slow_limit <= fast_limit",
+            ));
+        }
+
+        Ok(Self {
+            fast_limit,
+            slow_limit,
+            hilbert: HilbertTransform::new(),
+            phase: 0.0,
+            mama: 0.0,
+            fama: 0.0,
+            output: None,
+            saved: (0.0, 0.0, 0.0),
+        })
+    }
+
+    pub fn fast_limit(&self) -> f64 {
+        self.fast_limit
+    }
+
+    pub fn slow_limit(&self) -> f64 {
+        self.slow_limit
+    }
+
+    // Phase, MAMA and FAMA after the input and its Hilbert transform
+    fn averages(&self, input: f64, ht: Option<HilbertTransformOutput>) -> (f64, f64, f64) {
+        let ht = match ht {
+            Some(ht) => ht,
+            None => return (self.phase, input, input),
+        };
+
+        let mut phase = self.phase;
+        if ht.in_phase != 0.0 {
+            phase = (ht.quadrature / ht.in_phase).atan().to_degrees();
+        }
+        let delta_phase = (self.phase - phase).max(1.0);
+        let alpha = (self.fast_limit / delta_phase).max(self.slow_limit);
+
+        let mama = alpha * input + (1.0 - alpha) * self.mama;
+        let fama = 0.5 * alpha * mama + (1.0 - 0.5 * alpha) * self.fama;
+        (phase, mama, fama)
+    }
+}
+
+impl Checkpoint for MesaAdaptiveMovingAverage {
+    type State = MesaAdaptiveMovingAverageState;
+
+    fn state(&self) -> Self::State {
+        MesaAdaptiveMovingAverageState {
+            fast_limit: self.fast_limit,
+            slow_limit: self.slow_limit,
+            hilbert: self.hilbert.state(),
+            phase: self.phase,
+            mama: self.mama,
+            fama: self.fama,
+            previous: self.saved,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator =
+            Self::new(state.fast_limit, state.slow_limit).map_err(|_| TaError::InvalidState)?;
+        let started = state.hilbert.count > 0;
+        indicator.hilbert = HilbertTransform::from_state(state.hilbert)?;
+        indicator.phase = state.phase;
+        indicator.mama = state.mama;
+        indicator.fama = state.fama;
+        indicator.saved = state.previous;
+        if started {
+            indicator.output = Some(MesaAdaptiveMovingAverageOutput {
+                mama: state.mama,
+                fama: state.fama,
+            });
+        }
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(MesaAdaptiveMovingAverage, 2);
+
+impl Next<f64> for MesaAdaptiveMovingAverage {
+    type Output = MesaAdaptiveMovingAverageOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved = (self.phase, self.mama, self.fama);
+        let ht = self.hilbert.next(input);
+        (self.phase, self.mama, self.fama) = self.averages(input, ht);
+
+        let output = MesaAdaptiveMovingAverageOutput {
+            mama: self.mama,
+            fama: self.fama,
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for MesaAdaptiveMovingAverage {
+    type Output = MesaAdaptiveMovingAverageOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for MesaAdaptiveMovingAverage {
+    fn peek(&self, input: f64) -> MesaAdaptiveMovingAverageOutput {
+        let (_, mama, fama) = self.averages(input, self.hilbert.peek(input));
+        MesaAdaptiveMovingAverageOutput { mama, fama }
+    }
+}
+
+impl<T: Close> Peek<&T> for MesaAdaptiveMovingAverage {
+    fn peek(&self, input: &T) -> MesaAdaptiveMovingAverageOutput {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for MesaAdaptiveMovingAverage {
+    fn amend(&mut self, input: f64) -> MesaAdaptiveMovingAverageOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        (self.phase, self.mama, self.fama) = self.saved;
+        let ht = self.hilbert.amend(input);
+        (self.phase, self.mama, self.fama) = self.averages(input, ht);
+
+        let output = MesaAdaptiveMovingAverageOutput {
+            mama: self.mama,
+            fama: self.fama,
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for MesaAdaptiveMovingAverage {
+    fn amend(&mut self, input: &T) -> MesaAdaptiveMovingAverageOutput {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for MesaAdaptiveMovingAverage {
+    fn reset(&mut self) {
+        self.hilbert.reset();
+        self.phase = 0.0;
+        self.mama = 0.0;
+        self.fama = 0.0;
+        self.output = None;
+        self.saved = (0.0, 0.0, 0.0);
+    }
+}
+
+impl Current for MesaAdaptiveMovingAverage {
+    type Output = MesaAdaptiveMovingAverageOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for MesaAdaptiveMovingAverage {
+    fn is_ready(&self) -> bool {
+        self.hilbert.is_ready()
+    }
+}
+
+impl Lookback for MesaAdaptiveMovingAverage {
+    fn lookback(&self) -> usize {
+        self.hilbert.lookback()
+    }
+}
+
+impl Default for MesaAdaptiveMovingAverage {
+    fn default() -> Self {
+        Self::new(0.5, 0.05).unwrap()
+    }
+}
+
+impl fmt::Display for MesaAdaptiveMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MAMA({}, {})", self.fast_limit, self.slow_limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(MesaAdaptiveMovingAverage);
+
+    fn round(nums: (f64, f64)) -> (f64, f64) {
+        let n0 = (nums.0 * 1000.0).round() / 1000.0;
+        let n1 = (nums.1 * 1000.0).round() / 1000.0;
+        (n0, n1)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(MesaAdaptiveMovingAverage::new(0.0, 0.0).is_err());
+        assert!(MesaAdaptiveMovingAverage::new(1.5, 0.05).is_err());
+        assert!(MesaAdaptiveMovingAverage::new(0.5, 0.0).is_err());
+        assert!(MesaAdaptiveMovingAverage::new(0.5, 0.6).is_err());
+        assert!(MesaAdaptiveMovingAverage::new(f64::NAN, 0.05).is_err());
+        assert!(MesaAdaptiveMovingAverage::new(1.0, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut mama = MesaAdaptiveMovingAverage::new(0.5, 0.05).unwrap();
+
+        let inputs = [
+            10.0, 12.0, 11.0, 14.0, 13.0, 9.0, 8.0, 12.0, 15.0, 14.0, 11.0, 10.0, 13.0, 16.0,
+        ];
+        let expected = [
+            (10.0, 10.0),
+            (12.0, 12.0),
+            (11.0, 11.0),
+            (14.0, 14.0),
+            (13.0, 13.0),
+            (11.0, 12.5),
+            (9.5, 11.75),
+            (10.75, 11.5),
+            (12.875, 11.844),
+            (13.438, 12.242),
+            (13.316, 12.269),
+            (13.15, 12.291),
+            (13.142, 12.312),
+            (14.571, 12.877),
+        ];
+
+        for (input, expected) in inputs.iter().zip(expected.iter()) {
+            assert_eq!(round(mama.next(*input).into()), *expected);
+        }
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut mama = MesaAdaptiveMovingAverage::new(0.5, 0.05).unwrap();
+
+        for x in &[10, 12, 11, 14, 13] {
+            mama.next(&Bar::new().close(*x));
+        }
+        assert_eq!(round(mama.next(&Bar::new().close(9)).into()), (11.0, 12.5));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut mama = MesaAdaptiveMovingAverage::new(0.5, 0.05).unwrap();
+
+        for x in &[10.0, 12.0, 11.0, 14.0, 13.0, 9.0, 8.0] {
+            mama.next(*x);
+        }
+
+        mama.reset();
+        assert_eq!(round(mama.next(5.0).into()), (5.0, 5.0));
+    }
+
+    #[test]
+    fn test_default() {
+        MesaAdaptiveMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let mama = MesaAdaptiveMovingAverage::new(0.5, 0.05).unwrap();
+        assert_eq!(format!("{}", mama), "MAMA(0.5, 0.05)");
+    }
+}
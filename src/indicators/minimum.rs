@@ -1,7 +1,10 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{Low, Next, Period, Reset};
+use crate::{
+    Amend, Checkpoint, Current, IsReady, Lookback, Low, Next, Numeric, Peek, Period, Reset,
+    SetPeriod,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -23,30 +26,55 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(min.next(12.0), 10.0);
 /// assert_eq!(min.next(13.0), 11.0);
 /// ```
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct Minimum {
+pub struct Minimum<N = f64> {
     period: usize,
     min_index: usize,
     cur_index: usize,
-    deque: Box<[f64]>,
+    count: usize,
+    deque: Box<[N]>,
+    output: Option<N>,
+    // State before the last input, restored by `amend`
+    saved_min_index: usize,
+    saved_count: usize,
+    saved_value: N,
+}
+
+/// State of [Minimum](generic/struct.Minimum.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinimumState<N = f64> {
+    pub period: usize,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<N>,
 }
 
-impl Minimum {
+impl<N: Numeric> Minimum<N> {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
             _ => Ok(Self {
                 period,
                 min_index: 0,
                 cur_index: 0,
-                deque: vec![f64::INFINITY; period].into_boxed_slice(),
+                count: 0,
+                deque: vec![N::highest(); period].into_boxed_slice(),
+                output: None,
+                saved_min_index: 0,
+                saved_count: 0,
+                saved_value: N::highest(),
             }),
         }
     }
 
+    /// Inputs in the window, the oldest first.
+    fn inputs(&self) -> impl Iterator<Item = N> + '_ {
+        let oldest = self.cur_index + self.period - self.count;
+        (0..self.count).map(move |i| self.deque[(oldest + i) % self.period])
+    }
+
     fn find_min_index(&self) -> usize {
-        let mut min = f64::INFINITY;
+        let mut min = N::highest();
         let mut index: usize = 0;
 
         for (i, &val) in self.deque.iter().enumerate() {
@@ -58,18 +86,11 @@ impl Minimum {
 
         index
     }
-}
 
-impl Period for Minimum {
-    fn period(&self) -> usize {
-        self.period
-    }
-}
-
-impl Next<f64> for Minimum {
-    type Output = f64;
-
-    fn next(&mut self, input: f64) -> Self::Output {
+    fn next_value(&mut self, input: N) -> N {
+        self.saved_min_index = self.min_index;
+        self.saved_count = self.count;
+        self.saved_value = self.deque[self.cur_index];
         self.deque[self.cur_index] = input;
 
         if input < self.deque[self.min_index] {
@@ -83,34 +104,142 @@ impl Next<f64> for Minimum {
         } else {
             0
         };
+        self.count = (self.count + 1).min(self.period);
 
-        self.deque[self.min_index]
+        let output = self.deque[self.min_index];
+        self.output = Some(output);
+        output
+    }
+
+    fn peek_value(&self, input: N) -> N {
+        if self.min_index != self.cur_index {
+            input.min(self.deque[self.min_index])
+        } else {
+            self.deque
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != self.cur_index)
+                .fold(input, |min, (_, &value)| min.min(value))
+        }
+    }
+
+    fn amend_value(&mut self, input: N) -> N {
+        if self.output.is_none() {
+            return self.next_value(input);
+        }
+        self.cur_index = (self.cur_index + self.period - 1) % self.period;
+        self.deque[self.cur_index] = self.saved_value;
+        self.min_index = self.saved_min_index;
+        self.count = self.saved_count;
+        self.next_value(input)
+    }
+}
+
+impl<N: Numeric> Period for Minimum<N> {
+    fn period(&self) -> usize {
+        self.period
     }
 }
 
-impl<T: Low> Next<&T> for Minimum {
-    type Output = f64;
+impl<N: Numeric> Checkpoint for Minimum<N> {
+    type State = MinimumState<N>;
+
+    fn state(&self) -> Self::State {
+        MinimumState {
+            period: self.period,
+            inputs: self.inputs().collect(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.period == 0 || state.inputs.len() > state.period {
+            return Err(TaError::InvalidState);
+        }
+        // Replay the inputs, so the last one can be amended
+        let mut indicator = Self::new(state.period)?;
+        for input in state.inputs {
+            indicator.next_value(input);
+        }
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(Minimum<N>, 1);
+
+impl<N: Numeric> SetPeriod for Minimum<N> {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        // Replay the latest inputs that fit in the new window
+        let mut resized = Self::new(period)?;
+        for input in self.inputs().skip(self.count.saturating_sub(period)) {
+            resized.next_value(input);
+        }
+        *self = resized;
+        Ok(())
+    }
+}
+
+impl_numeric_inputs!(Minimum);
+
+impl<N: Numeric, T: Low<N>> Next<&T> for Minimum<N> {
+    type Output = N;
 
     fn next(&mut self, input: &T) -> Self::Output {
-        self.next(input.low())
+        self.next_value(input.low())
+    }
+}
+
+impl<N: Numeric, T: Low<N>> Peek<&T> for Minimum<N> {
+    fn peek(&self, input: &T) -> N {
+        self.peek_value(input.low())
     }
 }
 
-impl Reset for Minimum {
+impl<N: Numeric, T: Low<N>> Amend<&T> for Minimum<N> {
+    fn amend(&mut self, input: &T) -> N {
+        self.amend_value(input.low())
+    }
+}
+
+impl<N: Numeric> Reset for Minimum<N> {
     fn reset(&mut self) {
         for i in 0..self.period {
-            self.deque[i] = f64::INFINITY;
+            self.deque[i] = N::highest();
         }
+        self.count = 0;
+        self.output = None;
+        self.saved_min_index = 0;
+        self.saved_count = 0;
+        self.saved_value = N::highest();
+    }
+}
+
+impl<N: Numeric> Current for Minimum<N> {
+    type Output = N;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<N: Numeric> IsReady for Minimum<N> {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl<N: Numeric> Lookback for Minimum<N> {
+    fn lookback(&self) -> usize {
+        self.period
     }
 }
 
-impl Default for Minimum {
+impl<N: Numeric> Default for Minimum<N> {
     fn default() -> Self {
         Self::new(14).unwrap()
     }
 }
 
-impl fmt::Display for Minimum {
+impl<N: Numeric> fmt::Display for Minimum<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "MIN({})", self.period)
     }
@@ -119,6 +248,7 @@ impl fmt::Display for Minimum {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::Minimum;
     use crate::test_helper::*;
 
     test_indicator!(Minimum);
@@ -0,0 +1,318 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Amend, Checkpoint, Current, IsReady, Lookback, Low, Next, Peek, Period, Reset};
+
+/// Returns the lowest value in a time frame known at compile time.
+///
+/// Same as [Minimum](crate::indicators::Minimum), but the inputs are kept in an array of _N_
+/// values instead of a heap allocated buffer, so it needs no allocation and is available
+/// without the `std` feature. Unlike the other indicators it can't be serialized.
+///
+/// # Parameters
+///
+/// * _N_ - size of the time frame (integer greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::MinimumConst;
+/// use ta::Next;
+///
+/// let mut min = MinimumConst::<3>::new().unwrap();
+/// assert_eq!(min.next(10.0), 10.0);
+/// assert_eq!(min.next(11.0), 10.0);
+/// assert_eq!(min.next(12.0), 10.0);
+/// assert_eq!(min.next(13.0), 11.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MinimumConst<const N: usize> {
+    min_index: usize,
+    cur_index: usize,
+    count: usize,
+    deque: [f64; N],
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_min_index: usize,
+    saved_count: usize,
+    saved_value: f64,
+}
+
+/// State of [MinimumConst](struct.MinimumConst.html), see [Checkpoint](../trait.Checkpoint.html).
+/// Kept in an array as well, so it needs no allocation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinimumConstState<const N: usize> {
+    /// Number of inputs in the window, up to _N_.
+    pub count: usize,
+    /// Inputs in the window, the oldest first, followed by zeros.
+    pub inputs: [f64; N],
+}
+
+impl<const N: usize> MinimumConst<N> {
+    pub fn new() -> Result<Self> {
+        match N {
+            0 => Err(TaError::invalid_parameter("N", 0.0, ">= 1")),
+            _ => Ok(Self {
+                min_index: 0,
+                cur_index: 0,
+                count: 0,
+                deque: [f64::INFINITY; N],
+                output: None,
+                saved_min_index: 0,
+                saved_count: 0,
+                saved_value: f64::INFINITY,
+            }),
+        }
+    }
+
+    fn find_min_index(&self) -> usize {
+        let mut min = f64::INFINITY;
+        let mut index: usize = 0;
+
+        for (i, &val) in self.deque.iter().enumerate() {
+            if val < min {
+                min = val;
+                index = i;
+            }
+        }
+
+        index
+    }
+}
+
+impl<const N: usize> Period for MinimumConst<N> {
+    fn period(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Checkpoint for MinimumConst<N> {
+    type State = MinimumConstState<N>;
+
+    fn state(&self) -> Self::State {
+        let mut inputs = [0.0; N];
+        let oldest = self.cur_index + N - self.count;
+        for (i, input) in inputs.iter_mut().take(self.count).enumerate() {
+            *input = self.deque[(oldest + i) % N];
+        }
+        MinimumConstState {
+            count: self.count,
+            inputs,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.count > N {
+            return Err(TaError::InvalidState);
+        }
+        // Replay the inputs, so the last one can be amended
+        let mut indicator = Self::new()?;
+        for &input in &state.inputs[..state.count] {
+            indicator.next(input);
+        }
+        Ok(indicator)
+    }
+}
+
+impl<const N: usize> Next<f64> for MinimumConst<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_min_index = self.min_index;
+        self.saved_count = self.count;
+        self.saved_value = self.deque[self.cur_index];
+        self.deque[self.cur_index] = input;
+
+        if input < self.deque[self.min_index] {
+            self.min_index = self.cur_index;
+        } else if self.min_index == self.cur_index {
+            self.min_index = self.find_min_index();
+        }
+
+        self.cur_index = if self.cur_index + 1 < N {
+            self.cur_index + 1
+        } else {
+            0
+        };
+        self.count = (self.count + 1).min(N);
+
+        let output = self.deque[self.min_index];
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<const N: usize, T: Low> Next<&T> for MinimumConst<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.low())
+    }
+}
+
+impl<const N: usize> Peek<f64> for MinimumConst<N> {
+    fn peek(&self, input: f64) -> f64 {
+        if self.min_index != self.cur_index {
+            input.min(self.deque[self.min_index])
+        } else {
+            self.deque
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != self.cur_index)
+                .fold(input, |min, (_, &value)| min.min(value))
+        }
+    }
+}
+
+impl<const N: usize, T: Low> Peek<&T> for MinimumConst<N> {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.low())
+    }
+}
+
+impl<const N: usize> Amend<f64> for MinimumConst<N> {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.cur_index = (self.cur_index + N - 1) % N;
+        self.deque[self.cur_index] = self.saved_value;
+        self.min_index = self.saved_min_index;
+        self.count = self.saved_count;
+        self.next(input)
+    }
+}
+
+impl<const N: usize, T: Low> Amend<&T> for MinimumConst<N> {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.low())
+    }
+}
+
+impl<const N: usize> Reset for MinimumConst<N> {
+    fn reset(&mut self) {
+        self.min_index = 0;
+        self.cur_index = 0;
+        self.count = 0;
+        self.deque = [f64::INFINITY; N];
+        self.output = None;
+        self.saved_min_index = 0;
+        self.saved_count = 0;
+        self.saved_value = f64::INFINITY;
+    }
+}
+
+impl<const N: usize> Current for MinimumConst<N> {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<const N: usize> IsReady for MinimumConst<N> {
+    fn is_ready(&self) -> bool {
+        self.count == N
+    }
+}
+
+impl<const N: usize> Lookback for MinimumConst<N> {
+    fn lookback(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Default for MinimumConst<N> {
+    fn default() -> Self {
+        Self::new().unwrap()
+    }
+}
+
+impl<const N: usize> fmt::Display for MinimumConst<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MIN({})", N)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    type MinimumConst14 = MinimumConst<14>;
+
+    test_indicator!(MinimumConst14);
+
+    #[test]
+    fn test_new() {
+        assert!(MinimumConst::<0>::new().is_err());
+        assert!(MinimumConst::<1>::new().is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut min = MinimumConst::<3>::new().unwrap();
+
+        assert_eq!(min.next(4.0), 4.0);
+        assert_eq!(min.next(1.2), 1.2);
+        assert_eq!(min.next(5.0), 1.2);
+        assert_eq!(min.next(3.0), 1.2);
+        assert_eq!(min.next(4.0), 3.0);
+        assert_eq!(min.next(6.0), 3.0);
+        assert_eq!(min.next(7.0), 4.0);
+        assert_eq!(min.next(8.0), 6.0);
+        assert_eq!(min.next(-9.0), -9.0);
+        assert_eq!(min.next(0.0), -9.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_same_as_dynamic() {
+        use crate::indicators::Minimum;
+
+        let mut min = MinimumConst::<5>::new().unwrap();
+        let mut dynamic = Minimum::new(5).unwrap();
+
+        for i in 0..20 {
+            let input = (i * 7 % 11) as f64;
+            assert_eq!(min.next(input), dynamic.next(input));
+            assert_eq!(min.is_ready(), dynamic.is_ready());
+        }
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        fn bar(low: f64) -> Bar {
+            Bar::new().low(low)
+        }
+
+        let mut min = MinimumConst::<2>::new().unwrap();
+
+        assert_eq!(min.next(&bar(4.0)), 4.0);
+        assert_eq!(min.next(&bar(1.2)), 1.2);
+        assert_eq!(min.next(&bar(3.5)), 1.2);
+        assert_eq!(min.next(&bar(2.0)), 2.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut min = MinimumConst::<100>::new().unwrap();
+        assert_eq!(min.next(5.0), 5.0);
+        assert_eq!(min.next(7.0), 5.0);
+
+        min.reset();
+        assert!(!min.is_ready());
+        assert_eq!(min.next(7.0), 7.0);
+    }
+
+    #[test]
+    fn test_default() {
+        MinimumConst::<14>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let indicator = MinimumConst::<7>::new().unwrap();
+        assert_eq!(format!("{}", indicator), "MIN(7)");
+    }
+}
@@ -1,72 +1,585 @@
+pub mod generic;
+
 mod exponential_moving_average;
-pub use self::exponential_moving_average::ExponentialMovingAverage;
+pub use self::exponential_moving_average::ExponentialMovingAverageState;
+/// [Exponential moving average](generic/struct.ExponentialMovingAverage.html) of `f64` values.
+pub type ExponentialMovingAverage = generic::ExponentialMovingAverage<f64>;
 
+#[cfg(feature = "std")]
 mod weighted_moving_average;
-pub use self::weighted_moving_average::WeightedMovingAverage;
+#[cfg(feature = "std")]
+pub use self::weighted_moving_average::WeightedMovingAverageState;
+/// [Weighted moving average](generic/struct.WeightedMovingAverage.html) of `f64` values.
+#[cfg(feature = "std")]
+pub type WeightedMovingAverage = generic::WeightedMovingAverage<f64>;
 
+#[cfg(feature = "std")]
 mod simple_moving_average;
-pub use self::simple_moving_average::SimpleMovingAverage;
+#[cfg(feature = "std")]
+pub use self::simple_moving_average::SimpleMovingAverageState;
+/// [Simple moving average](generic/struct.SimpleMovingAverage.html) of `f64` values.
+#[cfg(feature = "std")]
+pub type SimpleMovingAverage = generic::SimpleMovingAverage<f64>;
+
+mod simple_moving_average_const;
+pub use self::simple_moving_average_const::{
+    SimpleMovingAverageConst, SimpleMovingAverageConstState,
+};
 
+#[cfg(feature = "std")]
 mod standard_deviation;
-pub use self::standard_deviation::StandardDeviation;
+#[cfg(feature = "std")]
+pub use self::standard_deviation::StandardDeviationState;
+/// [Standard deviation](generic/struct.StandardDeviation.html) of `f64` values.
+#[cfg(feature = "std")]
+pub type StandardDeviation = generic::StandardDeviation<f64>;
 
+#[cfg(feature = "std")]
 mod mean_absolute_deviation;
-pub use self::mean_absolute_deviation::MeanAbsoluteDeviation;
+#[cfg(feature = "std")]
+pub use self::mean_absolute_deviation::{MeanAbsoluteDeviation, MeanAbsoluteDeviationState};
 
 mod relative_strength_index;
-pub use self::relative_strength_index::RelativeStrengthIndex;
+pub use self::relative_strength_index::RelativeStrengthIndexState;
+/// [RSI](generic/struct.RelativeStrengthIndex.html) of `f64` values.
+pub type RelativeStrengthIndex = generic::RelativeStrengthIndex<f64>;
 
+#[cfg(feature = "std")]
 mod minimum;
-pub use self::minimum::Minimum;
+#[cfg(feature = "std")]
+pub use self::minimum::MinimumState;
+/// [Minimum](generic/struct.Minimum.html) of `f64` values.
+#[cfg(feature = "std")]
+pub type Minimum = generic::Minimum<f64>;
+
+mod minimum_const;
+pub use self::minimum_const::{MinimumConst, MinimumConstState};
 
+#[cfg(feature = "std")]
 mod maximum;
-pub use self::maximum::Maximum;
+#[cfg(feature = "std")]
+pub use self::maximum::MaximumState;
+/// [Maximum](generic/struct.Maximum.html) of `f64` values.
+#[cfg(feature = "std")]
+pub type Maximum = generic::Maximum<f64>;
 
+mod maximum_const;
+pub use self::maximum_const::{MaximumConst, MaximumConstState};
+
+#[cfg(feature = "std")]
 mod fast_stochastic;
-pub use self::fast_stochastic::FastStochastic;
+#[cfg(feature = "std")]
+pub use self::fast_stochastic::{FastStochastic, FastStochasticState};
 
+#[cfg(feature = "std")]
 mod slow_stochastic;
-pub use self::slow_stochastic::SlowStochastic;
+#[cfg(feature = "std")]
+pub use self::slow_stochastic::{SlowStochastic, SlowStochasticState};
 
 mod true_range;
-pub use self::true_range::TrueRange;
+pub use self::true_range::TrueRangeState;
+/// [True range](generic/struct.TrueRange.html) of `f64` values.
+pub type TrueRange = generic::TrueRange<f64>;
 
 mod average_true_range;
-pub use self::average_true_range::AverageTrueRange;
+pub use self::average_true_range::AverageTrueRangeState;
+/// [Average true range](generic/struct.AverageTrueRange.html) of `f64` values.
+pub type AverageTrueRange = generic::AverageTrueRange<f64>;
 
 mod moving_average_convergence_divergence;
 pub use self::moving_average_convergence_divergence::{
     MovingAverageConvergenceDivergence, MovingAverageConvergenceDivergenceOutput,
+    MovingAverageConvergenceDivergenceState,
 };
 
 mod percentage_price_oscillator;
 pub use self::percentage_price_oscillator::{
-    PercentagePriceOscillator, PercentagePriceOscillatorOutput,
+    PercentagePriceOscillator, PercentagePriceOscillatorOutput, PercentagePriceOscillatorState,
 };
 
+#[cfg(feature = "std")]
 mod commodity_channel_index;
-pub use self::commodity_channel_index::CommodityChannelIndex;
+#[cfg(feature = "std")]
+pub use self::commodity_channel_index::{CommodityChannelIndex, CommodityChannelIndexState};
 
+#[cfg(feature = "std")]
 mod efficiency_ratio;
-pub use self::efficiency_ratio::EfficiencyRatio;
+#[cfg(feature = "std")]
+pub use self::efficiency_ratio::{EfficiencyRatio, EfficiencyRatioState};
 
+#[cfg(feature = "std")]
 mod bollinger_bands;
-pub use self::bollinger_bands::{BollingerBands, BollingerBandsOutput};
+#[cfg(feature = "std")]
+pub use self::bollinger_bands::{BollingerBandsOutput, BollingerBandsState};
+/// [Bollinger Bands](generic/struct.BollingerBands.html) of `f64` values.
+#[cfg(feature = "std")]
+pub type BollingerBands = generic::BollingerBands<f64>;
 
+#[cfg(feature = "std")]
 mod chandelier_exit;
-pub use self::chandelier_exit::{ChandelierExit, ChandelierExitOutput};
+#[cfg(feature = "std")]
+pub use self::chandelier_exit::{ChandelierExit, ChandelierExitOutput, ChandelierExitState};
 
 mod keltner_channel;
-pub use self::keltner_channel::{KeltnerChannel, KeltnerChannelOutput};
+pub use self::keltner_channel::{KeltnerChannel, KeltnerChannelOutput, KeltnerChannelState};
 
+#[cfg(feature = "std")]
 mod rate_of_change;
-pub use self::rate_of_change::RateOfChange;
+#[cfg(feature = "std")]
+pub use self::rate_of_change::{RateOfChange, RateOfChangeState};
 
+#[cfg(feature = "std")]
 mod money_flow_index;
-pub use self::money_flow_index::MoneyFlowIndex;
+#[cfg(feature = "std")]
+pub use self::money_flow_index::{MoneyFlowIndex, MoneyFlowIndexState};
 
 mod on_balance_volume;
-pub use self::on_balance_volume::OnBalanceVolume;
+pub use self::on_balance_volume::{OnBalanceVolume, OnBalanceVolumeState};
 
 mod smoothed_simple_moving_average;
-pub use self::smoothed_simple_moving_average::SmoothedSimpleMovingAverage;
\ No newline at end of file
+pub use self::smoothed_simple_moving_average::SmoothedSimpleMovingAverageState;
+/// [Smoothed simple moving average](generic/struct.SmoothedSimpleMovingAverage.html) of `f64` values.
+pub type SmoothedSimpleMovingAverage = generic::SmoothedSimpleMovingAverage<f64>;
+
+mod triple_exponential_moving_average;
+pub use self::triple_exponential_moving_average::{
+    TripleExponentialMovingAverage, TripleExponentialMovingAverageState,
+};
+
+#[cfg(feature = "std")]
+mod kaufman_adaptive_moving_average;
+#[cfg(feature = "std")]
+pub use self::kaufman_adaptive_moving_average::{
+    KaufmanAdaptiveMovingAverage, KaufmanAdaptiveMovingAverageState,
+};
+
+#[cfg(feature = "std")]
+mod volume_weighted_average_price;
+#[cfg(feature = "std")]
+pub use self::volume_weighted_average_price::{
+    VolumeWeightedAveragePrice, VolumeWeightedAveragePriceState,
+};
+
+mod anchored_vwap;
+pub use self::anchored_vwap::{AnchoredVwap, AnchoredVwapState};
+
+#[cfg(feature = "std")]
+mod donchian_channel;
+#[cfg(feature = "std")]
+pub use self::donchian_channel::{DonchianChannel, DonchianChannelOutput, DonchianChannelState};
+
+#[cfg(feature = "std")]
+mod vortex_indicator;
+#[cfg(feature = "std")]
+pub use self::vortex_indicator::{VortexIndicator, VortexIndicatorOutput, VortexIndicatorState};
+
+mod trix;
+pub use self::trix::{Trix, TrixOutput, TrixState};
+
+#[cfg(feature = "std")]
+mod ultimate_oscillator;
+#[cfg(feature = "std")]
+pub use self::ultimate_oscillator::{UltimateOscillator, UltimateOscillatorState};
+
+#[cfg(feature = "std")]
+mod awesome_oscillator;
+#[cfg(feature = "std")]
+pub use self::awesome_oscillator::{AwesomeOscillator, AwesomeOscillatorState};
+
+#[cfg(feature = "std")]
+mod accelerator_oscillator;
+#[cfg(feature = "std")]
+pub use self::accelerator_oscillator::{AcceleratorOscillator, AcceleratorOscillatorState};
+
+#[cfg(feature = "std")]
+mod chaikin_money_flow;
+#[cfg(feature = "std")]
+pub use self::chaikin_money_flow::{ChaikinMoneyFlow, ChaikinMoneyFlowState};
+
+mod accumulation_distribution_line;
+pub use self::accumulation_distribution_line::{
+    AccumulationDistributionLine, AccumulationDistributionLineState,
+};
+
+mod force_index;
+pub use self::force_index::{ForceIndex, ForceIndexState};
+
+mod elder_ray;
+pub use self::elder_ray::{ElderRay, ElderRayOutput, ElderRayState};
+
+#[cfg(feature = "std")]
+mod detrended_price_oscillator;
+#[cfg(feature = "std")]
+pub use self::detrended_price_oscillator::{
+    DetrendedPriceOscillator, DetrendedPriceOscillatorState,
+};
+
+#[cfg(feature = "std")]
+mod choppiness_index;
+#[cfg(feature = "std")]
+pub use self::choppiness_index::{ChoppinessIndex, ChoppinessIndexState};
+
+#[cfg(feature = "std")]
+mod connors_rsi;
+#[cfg(feature = "std")]
+pub use self::connors_rsi::{ConnorsRsi, ConnorsRsiState};
+
+mod true_strength_index;
+pub use self::true_strength_index::{
+    TrueStrengthIndex, TrueStrengthIndexOutput, TrueStrengthIndexState,
+};
+
+#[cfg(feature = "std")]
+mod fisher_transform;
+#[cfg(feature = "std")]
+pub use self::fisher_transform::{FisherTransform, FisherTransformOutput, FisherTransformState};
+
+#[cfg(feature = "std")]
+mod mcginley_dynamic;
+#[cfg(feature = "std")]
+pub use self::mcginley_dynamic::{McGinleyDynamic, McGinleyDynamicState};
+
+#[cfg(feature = "std")]
+mod fractal_adaptive_moving_average;
+#[cfg(feature = "std")]
+pub use self::fractal_adaptive_moving_average::{
+    FractalAdaptiveMovingAverage, FractalAdaptiveMovingAverageState,
+};
+
+#[cfg(feature = "std")]
+mod tillson_t3;
+#[cfg(feature = "std")]
+pub use self::tillson_t3::{TillsonT3, TillsonT3State};
+
+#[cfg(feature = "std")]
+mod ease_of_movement;
+#[cfg(feature = "std")]
+pub use self::ease_of_movement::{EaseOfMovement, EaseOfMovementState};
+
+mod price_volume_trend;
+pub use self::price_volume_trend::{PriceVolumeTrend, PriceVolumeTrendState};
+
+#[cfg(feature = "std")]
+mod balance_of_power;
+#[cfg(feature = "std")]
+pub use self::balance_of_power::{BalanceOfPower, BalanceOfPowerState};
+
+#[cfg(feature = "std")]
+mod relative_vigor_index;
+#[cfg(feature = "std")]
+pub use self::relative_vigor_index::{
+    RelativeVigorIndex, RelativeVigorIndexOutput, RelativeVigorIndexState,
+};
+
+#[cfg(feature = "std")]
+mod qqe;
+#[cfg(feature = "std")]
+pub use self::qqe::{Qqe, QqeOutput, QqeState};
+
+#[cfg(feature = "std")]
+mod squeeze_momentum;
+#[cfg(feature = "std")]
+pub use self::squeeze_momentum::{SqueezeMomentum, SqueezeMomentumOutput, SqueezeMomentumState};
+
+#[cfg(feature = "std")]
+mod linear_regression;
+#[cfg(feature = "std")]
+pub use self::linear_regression::{
+    LinearRegression, LinearRegressionOutput, LinearRegressionState,
+};
+
+#[cfg(feature = "std")]
+mod correlation;
+#[cfg(feature = "std")]
+pub use self::correlation::{Correlation, CorrelationState};
+
+#[cfg(feature = "std")]
+mod beta;
+#[cfg(feature = "std")]
+pub use self::beta::{Beta, BetaState};
+
+#[cfg(feature = "std")]
+mod z_score;
+#[cfg(feature = "std")]
+pub use self::z_score::{ZScore, ZScoreState};
+
+#[cfg(feature = "std")]
+mod rolling_quantile;
+#[cfg(feature = "std")]
+pub use self::rolling_quantile::{RollingQuantile, RollingQuantileState};
+
+#[cfg(feature = "std")]
+mod hilbert_transform;
+#[cfg(feature = "std")]
+pub use self::hilbert_transform::HilbertTransformState;
+
+#[cfg(feature = "std")]
+mod mesa_adaptive_moving_average;
+#[cfg(feature = "std")]
+pub use self::mesa_adaptive_moving_average::{
+    MesaAdaptiveMovingAverage, MesaAdaptiveMovingAverageOutput, MesaAdaptiveMovingAverageState,
+};
+
+#[cfg(feature = "std")]
+mod dominant_cycle_period;
+#[cfg(feature = "std")]
+pub use self::dominant_cycle_period::{DominantCyclePeriod, DominantCyclePeriodState};
+
+#[cfg(feature = "std")]
+mod chande_momentum_oscillator;
+#[cfg(feature = "std")]
+pub use self::chande_momentum_oscillator::{
+    ChandeMomentumOscillator, ChandeMomentumOscillatorState,
+};
+
+#[cfg(feature = "std")]
+mod stochastic_momentum_index;
+#[cfg(feature = "std")]
+pub use self::stochastic_momentum_index::{
+    StochasticMomentumIndex, StochasticMomentumIndexOutput, StochasticMomentumIndexState,
+};
+
+#[cfg(feature = "std")]
+mod williams_fractals;
+#[cfg(feature = "std")]
+pub use self::williams_fractals::{
+    WilliamsFractals, WilliamsFractalsOutput, WilliamsFractalsState,
+};
+
+mod atr_trailing_stop;
+pub use self::atr_trailing_stop::{AtrTrailingStop, AtrTrailingStopOutput, AtrTrailingStopState};
+
+#[cfg(feature = "std")]
+mod volume_weighted_moving_average;
+#[cfg(feature = "std")]
+pub use self::volume_weighted_moving_average::{
+    VolumeWeightedMovingAverage, VolumeWeightedMovingAverageState,
+};
+
+mod percentage_volume_oscillator;
+pub use self::percentage_volume_oscillator::{
+    PercentageVolumeOscillator, PercentageVolumeOscillatorOutput, PercentageVolumeOscillatorState,
+};
+
+mod volume_oscillator;
+pub use self::volume_oscillator::{VolumeOscillator, VolumeOscillatorState};
+
+#[cfg(feature = "std")]
+mod volume_rate_of_change;
+#[cfg(feature = "std")]
+pub use self::volume_rate_of_change::{VolumeRateOfChange, VolumeRateOfChangeState};
+
+mod negative_volume_index;
+pub use self::negative_volume_index::{
+    NegativeVolumeIndex, NegativeVolumeIndexOutput, NegativeVolumeIndexState,
+};
+
+mod positive_volume_index;
+pub use self::positive_volume_index::{
+    PositiveVolumeIndex, PositiveVolumeIndexOutput, PositiveVolumeIndexState,
+};
+
+#[cfg(feature = "std")]
+mod historical_volatility;
+#[cfg(feature = "std")]
+pub use self::historical_volatility::{HistoricalVolatility, HistoricalVolatilityState};
+
+#[cfg(feature = "std")]
+mod range_volatility;
+#[cfg(feature = "std")]
+pub use self::range_volatility::{RangeVolatility, RangeVolatilityState, VolatilityEstimator};
+
+#[cfg(feature = "std")]
+mod sortino_ratio;
+#[cfg(feature = "std")]
+pub use self::sortino_ratio::{SortinoRatio, SortinoRatioState};
+
+#[cfg(feature = "std")]
+mod drawdown_stats;
+#[cfg(feature = "std")]
+pub use self::drawdown_stats::{DrawdownStats, DrawdownStatsOutput, DrawdownStatsState};
+
+#[cfg(feature = "std")]
+mod value_at_risk;
+#[cfg(feature = "std")]
+pub use self::value_at_risk::{ValueAtRisk, ValueAtRiskOutput, ValueAtRiskState, VarMethod};
+
+#[cfg(feature = "std")]
+mod simple_return;
+#[cfg(feature = "std")]
+pub use self::simple_return::{SimpleReturn, SimpleReturnState};
+
+#[cfg(feature = "std")]
+mod log_return;
+#[cfg(feature = "std")]
+pub use self::log_return::{LogReturn, LogReturnState};
+
+mod cumulative_return;
+pub use self::cumulative_return::{CumulativeReturn, CumulativeReturnState};
+
+#[cfg(feature = "std")]
+mod moving_average_ribbon;
+#[cfg(feature = "std")]
+pub use self::moving_average_ribbon::{
+    MovingAverageRibbon, MovingAverageRibbonOutput, MovingAverageRibbonState, RibbonSpacing,
+};
+
+#[cfg(feature = "std")]
+mod divergence_detector;
+#[cfg(feature = "std")]
+pub use self::divergence_detector::{
+    DivergenceDetector, DivergenceDetectorOutput, DivergenceDetectorState, DivergenceKind,
+};
+
+mod inside_outside_bar;
+pub use self::inside_outside_bar::{BarRelation, InsideOutsideBar, InsideOutsideBarState};
+
+#[cfg(feature = "std")]
+mod swing_points;
+#[cfg(feature = "std")]
+pub use self::swing_points::{SwingPoint, SwingPoints, SwingPointsOutput, SwingPointsState};
+
+#[cfg(feature = "std")]
+mod support_resistance;
+#[cfg(feature = "std")]
+pub use self::support_resistance::{
+    SupportResistance, SupportResistanceState, Zone, ZoneEvent, ZoneEventKind,
+};
+
+#[cfg(feature = "std")]
+mod volume_profile;
+#[cfg(feature = "std")]
+pub use self::volume_profile::{
+    ProfileBin, ProfileBins, VolumeProfile, VolumeProfileOutput, VolumeProfileState,
+};
+
+mod multi_timeframe;
+pub use self::multi_timeframe::{MultiTimeframe, MultiTimeframeState};
+
+#[cfg(feature = "std")]
+mod session_reset;
+#[cfg(feature = "std")]
+pub use self::session_reset::{Session, SessionReset, SessionResetState, Weekday};
+
+#[cfg(feature = "std")]
+mod alligator;
+#[cfg(feature = "std")]
+pub use self::alligator::{Alligator, AlligatorOutput, AlligatorState};
+
+#[cfg(feature = "std")]
+mod gator_oscillator;
+#[cfg(feature = "std")]
+pub use self::gator_oscillator::{GatorOscillator, GatorOscillatorOutput, GatorOscillatorState};
+
+mod laguerre_filter;
+pub use self::laguerre_filter::{LaguerreFilter, LaguerreFilterState};
+
+mod laguerre_rsi;
+pub use self::laguerre_rsi::{LaguerreRsi, LaguerreRsiState};
+
+#[cfg(feature = "std")]
+mod super_smoother;
+#[cfg(feature = "std")]
+pub use self::super_smoother::{SuperSmoother, SuperSmootherState};
+
+#[cfg(feature = "std")]
+mod roofing_filter;
+#[cfg(feature = "std")]
+pub use self::roofing_filter::{RoofingFilter, RoofingFilterState};
+
+#[cfg(feature = "std")]
+mod center_of_gravity;
+#[cfg(feature = "std")]
+pub use self::center_of_gravity::{CenterOfGravity, CenterOfGravityOutput, CenterOfGravityState};
+
+#[cfg(feature = "std")]
+mod moving_average;
+#[cfg(feature = "std")]
+pub use self::moving_average::{MovingAverage, MovingAverageState, MovingAverageType};
+
+#[cfg(feature = "std")]
+mod disparity_index;
+#[cfg(feature = "std")]
+pub use self::disparity_index::{DisparityIndex, DisparityIndexState};
+
+#[cfg(feature = "std")]
+mod trend_intensity_index;
+#[cfg(feature = "std")]
+pub use self::trend_intensity_index::{TrendIntensityIndex, TrendIntensityIndexState};
+
+#[cfg(feature = "std")]
+mod triangular_moving_average;
+#[cfg(feature = "std")]
+pub use self::triangular_moving_average::{TriangularMovingAverage, TriangularMovingAverageState};
+
+mod price_source;
+pub use self::price_source::{PriceSource, Sourced, SourcedState};
+
+#[cfg(feature = "std")]
+mod geometric_moving_average;
+#[cfg(feature = "std")]
+pub use self::geometric_moving_average::{GeometricMovingAverage, GeometricMovingAverageState};
+
+#[cfg(feature = "std")]
+mod harmonic_moving_average;
+#[cfg(feature = "std")]
+pub use self::harmonic_moving_average::{HarmonicMovingAverage, HarmonicMovingAverageState};
+
+mod typical_price;
+pub use self::typical_price::{TypicalPrice, TypicalPriceState};
+
+mod median_price;
+pub use self::median_price::{MedianPrice, MedianPriceState};
+
+mod weighted_close;
+pub use self::weighted_close::{WeightedClose, WeightedCloseState};
+
+mod twiggs_money_flow;
+pub use self::twiggs_money_flow::{TwiggsMoneyFlow, TwiggsMoneyFlowState};
+
+mod market_facilitation_index;
+pub use self::market_facilitation_index::{
+    FacilitationState, MarketFacilitationIndex, MarketFacilitationIndexOutput,
+    MarketFacilitationIndexState,
+};
+
+#[cfg(feature = "std")]
+mod standard_error_bands;
+#[cfg(feature = "std")]
+pub use self::standard_error_bands::{
+    StandardErrorBands, StandardErrorBandsOutput, StandardErrorBandsState,
+};
+
+#[cfg(feature = "std")]
+mod acceleration_bands;
+#[cfg(feature = "std")]
+pub use self::acceleration_bands::{
+    AccelerationBands, AccelerationBandsOutput, AccelerationBandsState,
+};
+
+#[cfg(feature = "std")]
+mod autocorrelation;
+#[cfg(feature = "std")]
+pub use self::autocorrelation::{Autocorrelation, AutocorrelationState};
+
+#[cfg(feature = "std")]
+mod rolling_entropy;
+#[cfg(feature = "std")]
+pub use self::rolling_entropy::{RollingEntropy, RollingEntropyState};
+
+#[cfg(feature = "std")]
+mod spread_z_score;
+#[cfg(feature = "std")]
+pub use self::spread_z_score::{SpreadZScore, SpreadZScoreOutput, SpreadZScoreState};
+
+#[cfg(feature = "std")]
+mod fibonacci_levels;
+#[cfg(feature = "std")]
+pub use self::fibonacci_levels::{
+    FibonacciKind, FibonacciLevel, FibonacciLevels, FibonacciLevelsState,
+};
+
+#[cfg(feature = "std")]
+mod fractal_dimension_index;
+#[cfg(feature = "std")]
+pub use self::fractal_dimension_index::{FractalDimensionIndex, FractalDimensionIndexState};
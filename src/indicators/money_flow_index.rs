@@ -1,8 +1,12 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{Close, High, Low, Next, Period, Reset, Volume};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset,
+    SetPeriod, Volume,
+};
 
+use crate::helpers::{ring_from_values, ring_values};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -54,7 +58,6 @@ use serde::{Deserialize, Serialize};
 /// * [Money Flow Index, stockcharts](https://stockcharts.com/school/doku.php?id=chart_school:technical_indicators:money_flow_index_mfi)
 
 #[doc(alias = "MFI")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct MoneyFlowIndex {
     period: usize,
@@ -64,12 +67,41 @@ pub struct MoneyFlowIndex {
     total_positive_money_flow: f64,
     total_negative_money_flow: f64,
     deque: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+    saved_typical_price: f64,
+    saved_positive_money_flow: f64,
+    saved_negative_money_flow: f64,
+}
+
+/// State of [MoneyFlowIndex](struct.MoneyFlowIndex.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoneyFlowIndexState {
+    pub period: usize,
+    /// Money flows in the window, the oldest first, negative for a falling typical price
+    /// and 0 for the first input.
+    pub flows: Vec<f64>,
+    pub previous_typical_price: Option<f64>,
+    pub total_positive_money_flow: f64,
+    pub total_negative_money_flow: f64,
+    pub output: Option<f64>,
+    /// Money flow which the last input pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
+    /// Typical price of the input before the last one, to amend the last one.
+    pub typical_price_before_last: f64,
+    /// Totals before the last input, to amend it.
+    pub previous_total_positive_money_flow: f64,
+    pub previous_total_negative_money_flow: f64,
 }
 
 impl MoneyFlowIndex {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
             _ => Ok(Self {
                 period,
                 index: 0,
@@ -78,6 +110,12 @@ impl MoneyFlowIndex {
                 total_positive_money_flow: 0.0,
                 total_negative_money_flow: 0.0,
                 deque: vec![0.0; period].into_boxed_slice(),
+                output: None,
+                saved_value: 0.0,
+                saved_count: 0,
+                saved_typical_price: 0.0,
+                saved_positive_money_flow: 0.0,
+                saved_negative_money_flow: 0.0,
             }),
         }
     }
@@ -89,10 +127,74 @@ impl Period for MoneyFlowIndex {
     }
 }
 
+impl Checkpoint for MoneyFlowIndex {
+    type State = MoneyFlowIndexState;
+
+    fn state(&self) -> Self::State {
+        MoneyFlowIndexState {
+            period: self.period,
+            flows: ring_values(&self.deque, (self.index + 1) % self.period, self.count),
+            previous_typical_price: (self.count > 0).then_some(self.previous_typical_price),
+            total_positive_money_flow: self.total_positive_money_flow,
+            total_negative_money_flow: self.total_negative_money_flow,
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            typical_price_before_last: self.saved_typical_price,
+            previous_total_positive_money_flow: self.saved_positive_money_flow,
+            previous_total_negative_money_flow: self.saved_negative_money_flow,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.previous_typical_price.is_none() != state.flows.is_empty()
+            || state.output.is_none() != state.flows.is_empty()
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        // The index points at the last flow rather than at the next one
+        let (deque, next) = ring_from_values(state.period, &state.flows)?;
+        indicator.deque = deque;
+        indicator.index = (next + state.period - 1) % state.period;
+        indicator.count = state.flows.len();
+        indicator.previous_typical_price = state.previous_typical_price.unwrap_or(0.0);
+        indicator.total_positive_money_flow = state.total_positive_money_flow;
+        indicator.total_negative_money_flow = state.total_negative_money_flow;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_typical_price = state.typical_price_before_last;
+        indicator.saved_positive_money_flow = state.previous_total_positive_money_flow;
+        indicator.saved_negative_money_flow = state.previous_total_negative_money_flow;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(MoneyFlowIndex, 2);
+
+impl SetPeriod for MoneyFlowIndex {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
 impl<T: High + Low + Close + Volume> Next<&T> for MoneyFlowIndex {
     type Output = f64;
 
     fn next(&mut self, input: &T) -> f64 {
+        self.saved_value = self.deque[(self.index + 1) % self.period];
+        self.saved_count = self.count;
+        self.saved_typical_price = self.previous_typical_price;
+        self.saved_positive_money_flow = self.total_positive_money_flow;
+        self.saved_negative_money_flow = self.total_negative_money_flow;
         let tp = (input.close() + input.high() + input.low()) / 3.0;
 
         self.index = if self.index + 1 < self.period {
@@ -105,6 +207,7 @@ impl<T: High + Low + Close + Volume> Next<&T> for MoneyFlowIndex {
             self.count = self.count + 1;
             if self.count == 1 {
                 self.previous_typical_price = tp;
+                self.output = Some(50.0);
                 return 50.0;
             }
         } else {
@@ -129,9 +232,44 @@ impl<T: High + Low + Close + Volume> Next<&T> for MoneyFlowIndex {
         }
         self.previous_typical_price = tp;
 
-        self.total_positive_money_flow
+        let output = self.total_positive_money_flow
             / (self.total_positive_money_flow + self.total_negative_money_flow)
-            * 100.0
+            * 100.0;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low + Close + Volume> Peek<&T> for MoneyFlowIndex {
+    fn peek(&self, input: &T) -> f64 {
+        if self.count == 0 {
+            return 50.0;
+        }
+        let tp = (input.close() + input.high() + input.low()) / 3.0;
+
+        let mut positive = self.total_positive_money_flow;
+        let mut negative = self.total_negative_money_flow;
+        if self.count >= self.period {
+            let index = if self.index + 1 < self.period {
+                self.index + 1
+            } else {
+                0
+            };
+            let popped = self.deque[index];
+            if popped.is_sign_positive() {
+                positive -= popped;
+            } else {
+                negative += popped;
+            }
+        }
+
+        if tp > self.previous_typical_price {
+            positive += tp * input.volume();
+        } else if tp < self.previous_typical_price {
+            negative += tp * input.volume();
+        }
+
+        positive / (positive + negative) * 100.0
     }
 }
 
@@ -147,6 +285,21 @@ impl fmt::Display for MoneyFlowIndex {
     }
 }
 
+impl<T: High + Low + Close + Volume> Amend<&T> for MoneyFlowIndex {
+    fn amend(&mut self, input: &T) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.deque[self.index] = self.saved_value;
+        self.index = (self.index + self.period - 1) % self.period;
+        self.count = self.saved_count;
+        self.previous_typical_price = self.saved_typical_price;
+        self.total_positive_money_flow = self.saved_positive_money_flow;
+        self.total_negative_money_flow = self.saved_negative_money_flow;
+        self.next(input)
+    }
+}
+
 impl Reset for MoneyFlowIndex {
     fn reset(&mut self) {
         self.index = 0;
@@ -157,6 +310,32 @@ impl Reset for MoneyFlowIndex {
         for i in 0..self.period {
             self.deque[i] = 0.0;
         }
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+        self.saved_typical_price = 0.0;
+        self.saved_positive_money_flow = 0.0;
+        self.saved_negative_money_flow = 0.0;
+    }
+}
+
+impl Current for MoneyFlowIndex {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for MoneyFlowIndex {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for MoneyFlowIndex {
+    fn lookback(&self) -> usize {
+        self.period
     }
 }
 
@@ -0,0 +1,314 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::indicators::{
+    ExponentialMovingAverage as Ema, ExponentialMovingAverageState, SimpleMovingAverage as Sma,
+    SimpleMovingAverageState, SmoothedSimpleMovingAverage as Smma,
+    SmoothedSimpleMovingAverageState, WeightedMovingAverage as Wma, WeightedMovingAverageState,
+};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Type of a [MovingAverage](enum.MovingAverage.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovingAverageType {
+    Simple,
+    Exponential,
+    Weighted,
+    Smoothed,
+}
+
+/// Moving average of a type chosen at runtime.
+///
+/// Lets indicators and users switch between the
+/// [simple](type.SimpleMovingAverage.html), [exponential](type.ExponentialMovingAverage.html),
+/// [weighted](type.WeightedMovingAverage.html) and
+/// [smoothed](type.SmoothedSimpleMovingAverage.html) moving averages with a
+/// [MovingAverageType](enum.MovingAverageType.html) parameter.
+///
+/// # Parameters
+///
+/// * _kind_ - type of the moving average. Default is `Simple`.
+/// * _period_ - number of periods (integer greater than 0). Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{MovingAverage, MovingAverageType};
+/// use ta::Next;
+///
+/// let mut ma = MovingAverage::new(MovingAverageType::Weighted, 3).unwrap();
+/// assert_eq!(ma.next(3.0), 3.0);
+/// assert_eq!(ma.next(6.0), 5.0);
+/// assert_eq!(ma.to_string(), "WMA(3)");
+/// ```
+#[doc(alias = "MA")]
+#[derive(Debug, Clone)]
+pub enum MovingAverage {
+    Simple(Sma),
+    Exponential(Ema),
+    Weighted(Wma),
+    Smoothed(Smma),
+}
+
+/// State of a [MovingAverage](enum.MovingAverage.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum MovingAverageState {
+    Simple(SimpleMovingAverageState),
+    Exponential(ExponentialMovingAverageState),
+    Weighted(WeightedMovingAverageState),
+    Smoothed(SmoothedSimpleMovingAverageState),
+}
+
+impl MovingAverage {
+    pub fn new(kind: MovingAverageType, period: usize) -> Result<Self> {
+        Ok(match kind {
+            MovingAverageType::Simple => Self::Simple(Sma::new(period)?),
+            MovingAverageType::Exponential => Self::Exponential(Ema::new(period)?),
+            MovingAverageType::Weighted => Self::Weighted(Wma::new(period)?),
+            MovingAverageType::Smoothed => Self::Smoothed(Smma::new(period)?),
+        })
+    }
+
+    pub fn kind(&self) -> MovingAverageType {
+        match self {
+            Self::Simple(_) => MovingAverageType::Simple,
+            Self::Exponential(_) => MovingAverageType::Exponential,
+            Self::Weighted(_) => MovingAverageType::Weighted,
+            Self::Smoothed(_) => MovingAverageType::Smoothed,
+        }
+    }
+}
+
+impl Period for MovingAverage {
+    fn period(&self) -> usize {
+        match self {
+            Self::Simple(ma) => ma.period(),
+            Self::Exponential(ma) => ma.period(),
+            Self::Weighted(ma) => ma.period(),
+            Self::Smoothed(ma) => ma.period(),
+        }
+    }
+}
+
+impl Checkpoint for MovingAverage {
+    type State = MovingAverageState;
+
+    fn state(&self) -> Self::State {
+        match self {
+            Self::Simple(ma) => MovingAverageState::Simple(ma.state()),
+            Self::Exponential(ma) => MovingAverageState::Exponential(ma.state()),
+            Self::Weighted(ma) => MovingAverageState::Weighted(ma.state()),
+            Self::Smoothed(ma) => MovingAverageState::Smoothed(ma.state()),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(match state {
+            MovingAverageState::Simple(state) => Self::Simple(Sma::from_state(state)?),
+            MovingAverageState::Exponential(state) => Self::Exponential(Ema::from_state(state)?),
+            MovingAverageState::Weighted(state) => Self::Weighted(Wma::from_state(state)?),
+            MovingAverageState::Smoothed(state) => Self::Smoothed(Smma::from_state(state)?),
+        })
+    }
+}
+
+impl_versioned_serde!(MovingAverage, 1);
+
+impl SetPeriod for MovingAverage {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        match self {
+            Self::Simple(ma) => ma.set_period(period),
+            Self::Exponential(ma) => ma.set_period(period),
+            Self::Weighted(ma) => ma.set_period(period),
+            Self::Smoothed(ma) => ma.set_period(period),
+        }
+    }
+}
+
+impl Next<f64> for MovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        match self {
+            Self::Simple(ma) => ma.next(input),
+            Self::Exponential(ma) => ma.next(input),
+            Self::Weighted(ma) => ma.next(input),
+            Self::Smoothed(ma) => ma.next(input),
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for MovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for MovingAverage {
+    fn peek(&self, input: f64) -> f64 {
+        match self {
+            Self::Simple(ma) => ma.peek(input),
+            Self::Exponential(ma) => ma.peek(input),
+            Self::Weighted(ma) => ma.peek(input),
+            Self::Smoothed(ma) => ma.peek(input),
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for MovingAverage {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for MovingAverage {
+    fn amend(&mut self, input: f64) -> f64 {
+        match self {
+            Self::Simple(ma) => ma.amend(input),
+            Self::Exponential(ma) => ma.amend(input),
+            Self::Weighted(ma) => ma.amend(input),
+            Self::Smoothed(ma) => ma.amend(input),
+        }
+    }
+}
+
+impl<T: Close> Amend<&T> for MovingAverage {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for MovingAverage {
+    fn reset(&mut self) {
+        match self {
+            Self::Simple(ma) => ma.reset(),
+            Self::Exponential(ma) => ma.reset(),
+            Self::Weighted(ma) => ma.reset(),
+            Self::Smoothed(ma) => ma.reset(),
+        }
+    }
+}
+
+impl Current for MovingAverage {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        match self {
+            Self::Simple(ma) => ma.current(),
+            Self::Exponential(ma) => ma.current(),
+            Self::Weighted(ma) => ma.current(),
+            Self::Smoothed(ma) => ma.current(),
+        }
+    }
+}
+
+impl IsReady for MovingAverage {
+    fn is_ready(&self) -> bool {
+        match self {
+            Self::Simple(ma) => ma.is_ready(),
+            Self::Exponential(ma) => ma.is_ready(),
+            Self::Weighted(ma) => ma.is_ready(),
+            Self::Smoothed(ma) => ma.is_ready(),
+        }
+    }
+}
+
+impl Lookback for MovingAverage {
+    fn lookback(&self) -> usize {
+        match self {
+            Self::Simple(ma) => ma.lookback(),
+            Self::Exponential(ma) => ma.lookback(),
+            Self::Weighted(ma) => ma.lookback(),
+            Self::Smoothed(ma) => ma.lookback(),
+        }
+    }
+}
+
+impl Default for MovingAverage {
+    fn default() -> Self {
+        Self::new(MovingAverageType::Simple, 9).unwrap()
+    }
+}
+
+impl fmt::Display for MovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Simple(ma) => ma.fmt(f),
+            Self::Exponential(ma) => ma.fmt(f),
+            Self::Weighted(ma) => ma.fmt(f),
+            Self::Smoothed(ma) => ma.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(MovingAverage);
+
+    const KINDS: [MovingAverageType; 4] = [
+        MovingAverageType::Simple,
+        MovingAverageType::Exponential,
+        MovingAverageType::Weighted,
+        MovingAverageType::Smoothed,
+    ];
+
+    #[test]
+    fn test_new() {
+        for kind in KINDS {
+            assert!(MovingAverage::new(kind, 0).is_err());
+            let ma = MovingAverage::new(kind, 5).unwrap();
+            assert_eq!(ma.kind(), kind);
+            assert_eq!(ma.period(), 5);
+        }
+    }
+
+    #[test]
+    fn test_next() {
+        let inputs = [2.0, 4.0, 6.0, 8.0];
+        for kind in KINDS {
+            let mut ma = MovingAverage::new(kind, 3).unwrap();
+            let mut expected: Box<dyn Next<f64, Output = f64>> = match kind {
+                MovingAverageType::Simple => Box::new(Sma::new(3).unwrap()),
+                MovingAverageType::Exponential => Box::new(Ema::new(3).unwrap()),
+                MovingAverageType::Weighted => Box::new(Wma::new(3).unwrap()),
+                MovingAverageType::Smoothed => Box::new(Smma::new(3).unwrap()),
+            };
+            for input in inputs {
+                assert_eq!(ma.next(input), expected.next(input));
+            }
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ma = MovingAverage::new(MovingAverageType::Exponential, 3).unwrap();
+
+        ma.next(2.0);
+        ma.next(&Bar::new().close(4));
+        ma.reset();
+        assert_eq!(ma.next(10.0), 10.0);
+    }
+
+    #[test]
+    fn test_default() {
+        MovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let ma = MovingAverage::new(MovingAverageType::Exponential, 7).unwrap();
+        assert_eq!(format!("{}", ma), "EMA(7)");
+    }
+}
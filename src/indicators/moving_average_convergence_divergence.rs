@@ -1,8 +1,8 @@
-use std::fmt;
+use core::fmt;
 
-use crate::errors::Result;
-use crate::indicators::ExponentialMovingAverage as Ema;
-use crate::{Close, Next, Period, Reset};
+use crate::errors::{Result, TaError};
+use crate::indicators::{ExponentialMovingAverage as Ema, ExponentialMovingAverageState};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -51,12 +51,13 @@ use serde::{Deserialize, Serialize};
 /// }
 /// ```
 #[doc(alias = "MACD")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct MovingAverageConvergenceDivergence {
     fast_ema: Ema,
     slow_ema: Ema,
     signal_ema: Ema,
+    count: usize,
+    output: Option<MovingAverageConvergenceDivergenceOutput>,
 }
 
 impl MovingAverageConvergenceDivergence {
@@ -65,10 +66,14 @@ impl MovingAverageConvergenceDivergence {
             fast_ema: Ema::new(fast_period)?,
             slow_ema: Ema::new(slow_period)?,
             signal_ema: Ema::new(signal_period)?,
+            count: 0,
+            output: None,
         })
     }
 }
 
+/// Output of [MovingAverageConvergenceDivergence](struct.MovingAverageConvergenceDivergence.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
 pub struct MovingAverageConvergenceDivergenceOutput {
     pub macd: f64,
@@ -76,12 +81,79 @@ pub struct MovingAverageConvergenceDivergenceOutput {
     pub histogram: f64,
 }
 
+/// State of [MovingAverageConvergenceDivergence](struct.MovingAverageConvergenceDivergence.html),
+/// see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovingAverageConvergenceDivergenceState {
+    pub fast: ExponentialMovingAverageState,
+    pub slow: ExponentialMovingAverageState,
+    pub signal: ExponentialMovingAverageState,
+    /// Number of inputs since both the fast and the slow EMA are ready, up to the signal period.
+    pub count: usize,
+}
+
+impl_output!(
+    MovingAverageConvergenceDivergenceOutput,
+    macd,
+    signal,
+    histogram
+);
+
 impl From<MovingAverageConvergenceDivergenceOutput> for (f64, f64, f64) {
     fn from(mo: MovingAverageConvergenceDivergenceOutput) -> Self {
         (mo.macd, mo.signal, mo.histogram)
     }
 }
 
+impl Checkpoint for MovingAverageConvergenceDivergence {
+    type State = MovingAverageConvergenceDivergenceState;
+
+    fn state(&self) -> Self::State {
+        MovingAverageConvergenceDivergenceState {
+            fast: self.fast_ema.state(),
+            slow: self.slow_ema.state(),
+            signal: self.signal_ema.state(),
+            count: self.count,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let has_inputs = state.signal.count > 0;
+        if (state.fast.count > 0) != has_inputs
+            || (state.slow.count > 0) != has_inputs
+            || state.count > state.signal.count
+        {
+            return Err(TaError::InvalidState);
+        }
+        let fast_ema = Ema::from_state(state.fast)?;
+        let slow_ema = Ema::from_state(state.slow)?;
+        let signal_ema = Ema::from_state(state.signal)?;
+        let output = fast_ema
+            .current()
+            .zip(slow_ema.current())
+            .zip(signal_ema.current())
+            .map(|((fast, slow), signal)| {
+                let macd = fast - slow;
+                MovingAverageConvergenceDivergenceOutput {
+                    macd,
+                    signal,
+                    histogram: macd - signal,
+                }
+            });
+
+        Ok(Self {
+            fast_ema,
+            slow_ema,
+            signal_ema,
+            count: state.count,
+            output,
+        })
+    }
+}
+
+impl_versioned_serde!(MovingAverageConvergenceDivergence, 1);
+
 impl Next<f64> for MovingAverageConvergenceDivergence {
     type Output = MovingAverageConvergenceDivergenceOutput;
 
@@ -90,14 +162,19 @@ impl Next<f64> for MovingAverageConvergenceDivergence {
         let slow_val = self.slow_ema.next(input);
 
         let macd = fast_val - slow_val;
+        if self.fast_ema.is_ready() && self.slow_ema.is_ready() && !self.is_ready() {
+            self.count += 1;
+        }
         let signal = self.signal_ema.next(macd);
         let histogram = macd - signal;
 
-        MovingAverageConvergenceDivergenceOutput {
+        let output = MovingAverageConvergenceDivergenceOutput {
             macd,
             signal,
             histogram,
-        }
+        };
+        self.output = Some(output.clone());
+        output
     }
 }
 
@@ -109,11 +186,77 @@ impl<T: Close> Next<&T> for MovingAverageConvergenceDivergence {
     }
 }
 
+impl Peek<f64> for MovingAverageConvergenceDivergence {
+    fn peek(&self, input: f64) -> MovingAverageConvergenceDivergenceOutput {
+        let macd = self.fast_ema.peek(input) - self.slow_ema.peek(input);
+        let signal = self.signal_ema.peek(macd);
+
+        MovingAverageConvergenceDivergenceOutput {
+            macd,
+            signal,
+            histogram: macd - signal,
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for MovingAverageConvergenceDivergence {
+    fn peek(&self, input: &T) -> MovingAverageConvergenceDivergenceOutput {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for MovingAverageConvergenceDivergence {
+    fn amend(&mut self, input: f64) -> MovingAverageConvergenceDivergenceOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let macd = self.fast_ema.amend(input) - self.slow_ema.amend(input);
+        let signal = self.signal_ema.amend(macd);
+
+        let output = MovingAverageConvergenceDivergenceOutput {
+            macd,
+            signal,
+            histogram: macd - signal,
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for MovingAverageConvergenceDivergence {
+    fn amend(&mut self, input: &T) -> MovingAverageConvergenceDivergenceOutput {
+        self.amend(input.close())
+    }
+}
+
 impl Reset for MovingAverageConvergenceDivergence {
     fn reset(&mut self) {
         self.fast_ema.reset();
         self.slow_ema.reset();
         self.signal_ema.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl Current for MovingAverageConvergenceDivergence {
+    type Output = MovingAverageConvergenceDivergenceOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for MovingAverageConvergenceDivergence {
+    fn is_ready(&self) -> bool {
+        self.count == self.signal_ema.period()
+    }
+}
+
+impl Lookback for MovingAverageConvergenceDivergence {
+    fn lookback(&self) -> usize {
+        let macd = self.fast_ema.lookback().max(self.slow_ema.lookback());
+        macd + self.signal_ema.period() - 1
     }
 }
 
@@ -183,6 +326,27 @@ mod tests {
         assert_eq!(round(macd.next(3.0).into()), (0.21, 0.09, 0.13));
     }
 
+    #[test]
+    fn test_is_ready() {
+        // the slow EMA is ready after 3 values and the signal needs 2 more MACD values
+        let mut macd = Macd::new(2, 3, 2).unwrap();
+        for input in [1.0, 2.0, 3.0] {
+            macd.next(input);
+            assert!(!macd.is_ready());
+        }
+        macd.next(4.0);
+        assert!(macd.is_ready());
+
+        macd.reset();
+        assert!(!macd.is_ready());
+    }
+
+    #[test]
+    fn test_lookback() {
+        assert_eq!(Macd::new(2, 3, 2).unwrap().lookback(), 4);
+        assert_eq!(Macd::default().lookback(), 34);
+    }
+
     #[test]
     fn test_default() {
         Macd::default();
@@ -0,0 +1,420 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{ExponentialMovingAverage as Ema, ExponentialMovingAverageState};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Spacing of the periods in a [MovingAverageRibbon](struct.MovingAverageRibbon.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RibbonSpacing {
+    /// Periods differ by a constant step, e.g. 10, 20, 30, 40.
+    Arithmetic,
+    /// Periods differ by a constant ratio, e.g. 5, 10, 20, 40.
+    Geometric,
+}
+
+/// Moving average ribbon.
+///
+/// A set of [EMAs](type.ExponentialMovingAverage.html) with periods spread between
+/// _shortest_ and _longest_. Besides the values of all averages it reports the width of the
+/// ribbon and whether the averages are stacked in trend order.
+///
+/// # Formula
+///
+/// Periods, for i = 0.._count_ - 1:
+///
+/// * Arithmetic: round(_shortest_ + i * (_longest_ - _shortest_) / (_count_ - 1))
+/// * Geometric: round(_shortest_ * (_longest_ / _shortest_)<sup>i / (_count_ - 1)</sup>)
+///
+/// Width = max(EMA) - min(EMA)
+///
+/// The ribbon is stacked bullish when every EMA is strictly above the EMA with the next longer
+/// period, and stacked bearish when every EMA is strictly below it.
+///
+/// # Parameters
+///
+/// * _count_ - number of moving averages (integer greater than 1). Default is 6.
+/// * _shortest_ - period of the fastest average (integer greater than 0). Default is 10.
+/// * _longest_ - period of the slowest average. Default is 60.
+/// * _spacing_ - [RibbonSpacing](enum.RibbonSpacing.html). Default is arithmetic.
+///
+/// The resulting periods must be strictly increasing, otherwise an error is returned.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{MovingAverageRibbon, RibbonSpacing};
+/// use ta::Next;
+///
+/// let mut ribbon = MovingAverageRibbon::new(3, 2, 8, RibbonSpacing::Geometric).unwrap();
+/// assert_eq!(ribbon.periods(), vec![2, 4, 8]);
+///
+/// ribbon.next(10.0);
+/// ribbon.next(11.0);
+/// let out = ribbon.next(12.0);
+///
+/// assert_eq!(out.values.len(), 3);
+/// assert!(out.bullish);
+/// assert!(!out.bearish);
+/// ```
+#[doc(alias = "RIBBON")]
+#[derive(Debug, Clone)]
+pub struct MovingAverageRibbon {
+    shortest: usize,
+    longest: usize,
+    spacing: RibbonSpacing,
+    emas: Vec<Ema>,
+    output: Option<MovingAverageRibbonOutput>,
+}
+
+/// State of [MovingAverageRibbon](struct.MovingAverageRibbon.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovingAverageRibbonState {
+    pub shortest: usize,
+    pub longest: usize,
+    pub spacing: RibbonSpacing,
+    /// Averages of the lines, the shortest first.
+    pub emas: Vec<ExponentialMovingAverageState>,
+    pub output: Option<MovingAverageRibbonOutput>,
+}
+
+impl MovingAverageRibbon {
+    pub fn new(
+        count: usize,
+        shortest: usize,
+        longest: usize,
+        spacing: RibbonSpacing,
+    ) -> Result<Self> {
+        if count < 2 {
+            return Err(TaError::invalid_parameter("count", count as f64, ">= 2"));
+        }
+        if shortest == 0 {
+            return Err(TaError::invalid_parameter("shortest", 0.0, ">= 1"));
+        }
+        if longest <= shortest {
+            return Err(TaError::invalid_parameter(
+                "longest",
+                longest as f64,
+                "> shortest",
+            ));
+        }
+
+        let steps = (count - 1) as f64;
+        let (first, last) = (shortest as f64, longest as f64);
+        let periods: Vec<usize> = (0..count)
+            .map(|i| {
+                let i = i as f64;
+                let period = match spacing {
+                    RibbonSpacing::Arithmetic => first + i * (last - first) / steps,
+                    RibbonSpacing::Geometric => first * (last / first).powf(i / steps),
+                };
+                period.round() as usize
+            })
+            .collect();
+
+        // Too many lines for the range of periods round to the same period
+        if periods.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(TaError::invalid_parameter(
+                "count",
+                count as f64,
+                "distinct periods",
+            ));
+        }
+
+        Ok(Self {
+            shortest,
+            longest,
+            spacing,
+            emas: periods
+                .into_iter()
+                .map(Ema::new)
+                .collect::<Result<Vec<_>>>()?,
+            output: None,
+        })
+    }
+
+    /// Periods of the averages, from the fastest to the slowest.
+    pub fn periods(&self) -> Vec<usize> {
+        self.emas.iter().map(|ema| ema.period()).collect()
+    }
+
+    fn output(values: Vec<f64>) -> MovingAverageRibbonOutput {
+        let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+        let bullish = values.windows(2).all(|w| w[0] > w[1]);
+        let bearish = values.windows(2).all(|w| w[0] < w[1]);
+
+        MovingAverageRibbonOutput {
+            values,
+            width: max - min,
+            bullish,
+            bearish,
+        }
+    }
+}
+
+/// Output of [MovingAverageRibbon](struct.MovingAverageRibbon.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovingAverageRibbonOutput {
+    /// Values of the averages, from the fastest to the slowest.
+    pub values: Vec<f64>,
+    /// Distance between the highest and the lowest average.
+    pub width: f64,
+    /// Every average is above the next slower one.
+    pub bullish: bool,
+    /// Every average is below the next slower one.
+    pub bearish: bool,
+}
+
+impl_output!(MovingAverageRibbonOutput, values, width, bullish, bearish);
+
+impl Period for MovingAverageRibbon {
+    fn period(&self) -> usize {
+        self.longest
+    }
+}
+
+impl Checkpoint for MovingAverageRibbon {
+    type State = MovingAverageRibbonState;
+
+    fn state(&self) -> Self::State {
+        MovingAverageRibbonState {
+            shortest: self.shortest,
+            longest: self.longest,
+            spacing: self.spacing,
+            emas: self.emas.iter().map(Ema::state).collect(),
+            output: self.output.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(
+            state.emas.len(),
+            state.shortest,
+            state.longest,
+            state.spacing,
+        )
+        .map_err(|_| TaError::InvalidState)?;
+        if indicator.periods() != state.emas.iter().map(|ema| ema.period).collect::<Vec<_>>()
+            || state
+                .emas
+                .iter()
+                .any(|ema| (ema.count == 0) != state.output.is_none())
+        {
+            return Err(TaError::InvalidState);
+        }
+        indicator.emas = state
+            .emas
+            .into_iter()
+            .map(Ema::from_state)
+            .collect::<Result<Vec<_>>>()?;
+        indicator.output = state.output;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(MovingAverageRibbon, 1);
+
+impl Next<f64> for MovingAverageRibbon {
+    type Output = MovingAverageRibbonOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let values = self.emas.iter_mut().map(|ema| ema.next(input)).collect();
+        let output = Self::output(values);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for MovingAverageRibbon {
+    type Output = MovingAverageRibbonOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for MovingAverageRibbon {
+    fn peek(&self, input: f64) -> MovingAverageRibbonOutput {
+        Self::output(self.emas.iter().map(|ema| ema.peek(input)).collect())
+    }
+}
+
+impl<T: Close> Peek<&T> for MovingAverageRibbon {
+    fn peek(&self, input: &T) -> MovingAverageRibbonOutput {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for MovingAverageRibbon {
+    fn amend(&mut self, input: f64) -> MovingAverageRibbonOutput {
+        let values = self.emas.iter_mut().map(|ema| ema.amend(input)).collect();
+        let output = Self::output(values);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for MovingAverageRibbon {
+    fn amend(&mut self, input: &T) -> MovingAverageRibbonOutput {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for MovingAverageRibbon {
+    fn reset(&mut self) {
+        for ema in self.emas.iter_mut() {
+            ema.reset();
+        }
+        self.output = None;
+    }
+}
+
+impl Current for MovingAverageRibbon {
+    type Output = MovingAverageRibbonOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for MovingAverageRibbon {
+    fn is_ready(&self) -> bool {
+        self.emas.iter().all(|ema| ema.is_ready())
+    }
+}
+
+impl Lookback for MovingAverageRibbon {
+    fn lookback(&self) -> usize {
+        self.emas.iter().map(Lookback::lookback).max().unwrap_or(1)
+    }
+}
+
+impl Default for MovingAverageRibbon {
+    fn default() -> Self {
+        Self::new(6, 10, 60, RibbonSpacing::Arithmetic).unwrap()
+    }
+}
+
+impl fmt::Display for MovingAverageRibbon {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "RIBBON({}, {}, {}, {:?})",
+            self.emas.len(),
+            self.shortest,
+            self.longest,
+            self.spacing
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(MovingAverageRibbon);
+
+    fn rounded(out: &MovingAverageRibbonOutput) -> Vec<f64> {
+        out.values.iter().map(|v| round(*v)).collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let s = RibbonSpacing::Arithmetic;
+        assert!(MovingAverageRibbon::new(1, 10, 60, s).is_err());
+        assert!(MovingAverageRibbon::new(3, 0, 60, s).is_err());
+        assert!(MovingAverageRibbon::new(3, 10, 10, s).is_err());
+        assert!(MovingAverageRibbon::new(3, 10, 5, s).is_err());
+        // not enough distinct periods between 2 and 3
+        assert!(MovingAverageRibbon::new(4, 2, 3, s).is_err());
+        assert!(MovingAverageRibbon::new(2, 2, 3, s).is_ok());
+    }
+
+    #[test]
+    fn test_periods() {
+        let arithmetic = MovingAverageRibbon::new(5, 3, 48, RibbonSpacing::Arithmetic).unwrap();
+        assert_eq!(arithmetic.periods(), vec![3, 14, 26, 37, 48]);
+
+        let geometric = MovingAverageRibbon::new(5, 3, 48, RibbonSpacing::Geometric).unwrap();
+        assert_eq!(geometric.periods(), vec![3, 6, 12, 24, 48]);
+    }
+
+    #[test]
+    fn test_next() {
+        let mut ribbon = MovingAverageRibbon::new(3, 2, 8, RibbonSpacing::Geometric).unwrap();
+
+        let out = ribbon.next(10.0);
+        assert_eq!(rounded(&out), vec![10.0, 10.0, 10.0]);
+        assert_eq!(out.width, 0.0);
+        assert!(!out.bullish);
+        assert!(!out.bearish);
+
+        let out = ribbon.next(11.0);
+        assert_eq!(rounded(&out), vec![10.667, 10.4, 10.222]);
+        assert_eq!(round(out.width), 0.444);
+        assert!(out.bullish);
+
+        ribbon.next(12.0);
+        let out = ribbon.next(13.0);
+        assert_eq!(rounded(&out), vec![12.519, 11.824, 11.147]);
+        assert_eq!(round(out.width), 1.372);
+        assert!(out.bullish);
+
+        let out = ribbon.next(11.0);
+        assert_eq!(rounded(&out), vec![11.506, 11.494, 11.114]);
+        assert!(out.bullish);
+
+        let out = ribbon.next(5.0);
+        assert!(!out.bullish);
+    }
+
+    #[test]
+    fn test_next_bearish() {
+        let mut ribbon = MovingAverageRibbon::new(3, 2, 8, RibbonSpacing::Geometric).unwrap();
+
+        ribbon.next(10.0);
+        let out = ribbon.next(9.0);
+        assert!(out.bearish);
+        assert!(!out.bullish);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut ribbon = MovingAverageRibbon::new(3, 2, 8, RibbonSpacing::Geometric).unwrap();
+
+        ribbon.next(&Bar::new().close(10));
+        let out = ribbon.next(&Bar::new().close(11));
+        assert_eq!(rounded(&out), vec![10.667, 10.4, 10.222]);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ribbon = MovingAverageRibbon::new(3, 2, 8, RibbonSpacing::Geometric).unwrap();
+
+        ribbon.next(10.0);
+        ribbon.next(11.0);
+
+        ribbon.reset();
+        assert_eq!(rounded(&ribbon.next(11.0)), vec![11.0, 11.0, 11.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        let ribbon = MovingAverageRibbon::default();
+        assert_eq!(ribbon.periods(), vec![10, 20, 30, 40, 50, 60]);
+    }
+
+    #[test]
+    fn test_display() {
+        let ribbon = MovingAverageRibbon::new(4, 5, 40, RibbonSpacing::Geometric).unwrap();
+        assert_eq!(format!("{}", ribbon), "RIBBON(4, 5, 40, Geometric)");
+    }
+}
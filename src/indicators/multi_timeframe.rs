@@ -0,0 +1,294 @@
+use core::fmt;
+
+use crate::bars::{Resampler, ResamplerState};
+use crate::errors::Result;
+use crate::{
+    Checkpoint, Close, Current, DataItem, High, IsReady, Low, Next, Open, Peek, Reset, Volume,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Multi-timeframe indicator.
+///
+/// Runs an indicator on a higher timeframe while being fed lower timeframe bars, e.g. a 1 hour
+/// EMA fed with 1 minute bars. The lower timeframe bars go into a
+/// [Resampler](crate::bars::Resampler), and the wrapped indicator is only updated when a higher
+/// timeframe bar is completed. Every update returns the latest value of the wrapped indicator,
+/// which is `None` until the first higher timeframe bar is completed.
+///
+/// Inputs are `(timestamp, &bar)` pairs, as for the resampler. Since a higher timeframe bar is
+/// completed by the first bar of the next period, the value changes on that bar.
+///
+/// [provisional](#method.provisional) gives the value the indicator would have if the forming
+/// higher timeframe bar was completed now, without changing the state of the indicator.
+///
+/// It doesn't implement [Lookback](crate::Lookback), as the number of lower timeframe bars that
+/// make up a higher timeframe bar depends on their timestamps, not on the configuration. The
+/// lookback of the wrapped indicator, in higher timeframe bars, is given by
+/// `indicator().lookback()`.
+///
+/// # Parameters
+///
+/// * _indicator_ - indicator to run on the higher timeframe bars.
+/// * _resampler_ - resampler that builds the higher timeframe bars.
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::Resampler;
+/// use ta::indicators::{ExponentialMovingAverage, MultiTimeframe};
+/// use ta::{DataItem, Next};
+///
+/// fn bar(close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(close).high(close).low(close).close(close)
+///         .volume(100.0)
+///         .build().unwrap()
+/// }
+///
+/// // EMA of 5 minute closes, fed with 1 minute bars
+/// let ema = ExponentialMovingAverage::new(3).unwrap();
+/// let mut mtf = MultiTimeframe::new(ema, Resampler::new(300, 0).unwrap());
+///
+/// assert_eq!(mtf.next((0, &bar(10.0))), None);
+/// assert_eq!(mtf.next((240, &bar(12.0))), None);
+/// assert_eq!(mtf.next((300, &bar(14.0))), Some(12.0));
+/// assert_eq!(mtf.next((360, &bar(13.0))), Some(12.0));
+/// assert_eq!(mtf.provisional(), Some(12.5));
+/// ```
+#[doc(alias = "MTF")]
+#[derive(Debug, Clone)]
+pub struct MultiTimeframe<I, O = f64> {
+    indicator: I,
+    resampler: Resampler,
+    value: Option<O>,
+}
+
+/// State of [MultiTimeframe](struct.MultiTimeframe.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiTimeframeState<S, O = f64> {
+    pub indicator: S,
+    pub resampler: ResamplerState,
+    /// Value of the indicator for the last completed bar.
+    pub value: Option<O>,
+}
+
+impl<I, O> MultiTimeframe<I, O> {
+    pub fn new(indicator: I, resampler: Resampler) -> Self {
+        Self {
+            indicator,
+            resampler,
+            value: None,
+        }
+    }
+
+    /// The wrapped indicator.
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+
+    pub fn resampler(&self) -> &Resampler {
+        &self.resampler
+    }
+
+    /// Latest value of the wrapped indicator.
+    pub fn value(&self) -> Option<&O> {
+        self.value.as_ref()
+    }
+}
+
+impl<I, O> MultiTimeframe<I, O>
+where
+    I: for<'a> Next<&'a DataItem, Output = O> + Clone,
+    O: Clone,
+{
+    /// Value of the wrapped indicator if the forming higher timeframe bar was completed now.
+    pub fn provisional(&self) -> Option<O> {
+        match self.resampler.partial() {
+            Some(bar) => Some(self.indicator.clone().next(&bar)),
+            None => self.value.clone(),
+        }
+    }
+}
+
+impl<I: Checkpoint, O: Clone> Checkpoint for MultiTimeframe<I, O> {
+    type State = MultiTimeframeState<I::State, O>;
+
+    fn state(&self) -> Self::State {
+        MultiTimeframeState {
+            indicator: self.indicator.state(),
+            resampler: self.resampler.state(),
+            value: self.value.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(Self {
+            indicator: I::from_state(state.indicator)?,
+            resampler: Resampler::from_state(state.resampler)?,
+            value: state.value,
+        })
+    }
+}
+
+impl_versioned_serde!(MultiTimeframe<I, O>, 1);
+
+impl<I, O, T> Next<(i64, &T)> for MultiTimeframe<I, O>
+where
+    I: for<'a> Next<&'a DataItem, Output = O>,
+    O: Clone,
+    T: Open + High + Low + Close + Volume,
+{
+    type Output = Option<O>;
+
+    fn next(&mut self, input: (i64, &T)) -> Self::Output {
+        if let Some((_, bar)) = self.resampler.next(input) {
+            self.value = Some(self.indicator.next(&bar));
+        }
+        self.value.clone()
+    }
+}
+
+impl<I, O, T> Peek<(i64, &T)> for MultiTimeframe<I, O>
+where
+    I: for<'a> Peek<&'a DataItem, Output = O>,
+    O: Clone,
+    T: Open + High + Low + Close + Volume,
+{
+    fn peek(&self, (timestamp, _): (i64, &T)) -> Self::Output {
+        // the input only completes the forming bar, if it belongs to a later period
+        let completed = self
+            .resampler
+            .partial_start()
+            .is_some_and(|start| self.resampler.period_start(timestamp) > start);
+
+        match self.resampler.partial() {
+            Some(bar) if completed => Some(self.indicator.peek(&bar)),
+            _ => self.value.clone(),
+        }
+    }
+}
+
+impl<I: Reset, O> Reset for MultiTimeframe<I, O> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.resampler.reset();
+        self.value = None;
+    }
+}
+
+impl<I, O: Clone> Current for MultiTimeframe<I, O> {
+    type Output = O;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.value.clone()
+    }
+}
+
+impl<I: IsReady, O> IsReady for MultiTimeframe<I, O> {
+    fn is_ready(&self) -> bool {
+        self.value.is_some() && self.indicator.is_ready()
+    }
+}
+
+impl<I: fmt::Display, O> fmt::Display for MultiTimeframe<I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MTF({}, {})", self.indicator, self.resampler.interval())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::indicators::{FastStochastic, SimpleMovingAverage as Sma};
+    use crate::test_helper::*;
+
+    fn bar(close: f64) -> Bar {
+        Bar::new().open(close).high(close).low(close).close(close)
+    }
+
+    #[test]
+    fn test_next() {
+        let sma = Sma::new(2).unwrap();
+        let mut mtf = MultiTimeframe::new(sma, Resampler::new(3, 0).unwrap());
+
+        assert_eq!(mtf.next((0, &bar(1.0))), None);
+        assert_eq!(mtf.next((1, &bar(2.0))), None);
+        assert_eq!(mtf.next((2, &bar(4.0))), None);
+        assert_eq!(mtf.value(), None);
+
+        // Closes of the higher timeframe bars are 4 and 7
+        assert_eq!(mtf.next((3, &bar(6.0))), Some(4.0));
+        assert_eq!(mtf.next((4, &bar(5.0))), Some(4.0));
+        assert_eq!(mtf.next((5, &bar(7.0))), Some(4.0));
+        assert_eq!(mtf.next((6, &bar(9.0))), Some(5.5));
+        assert_eq!(mtf.value(), Some(&5.5));
+    }
+
+    #[test]
+    fn test_provisional() {
+        let sma = Sma::new(2).unwrap();
+        let mut mtf = MultiTimeframe::new(sma, Resampler::new(3, 0).unwrap());
+
+        assert_eq!(mtf.provisional(), None);
+
+        mtf.next((0, &bar(4.0)));
+        assert_eq!(mtf.provisional(), Some(4.0));
+
+        mtf.next((3, &bar(8.0)));
+        assert_eq!(mtf.provisional(), Some(6.0));
+        mtf.next((4, &bar(10.0)));
+        assert_eq!(mtf.provisional(), Some(7.0));
+
+        // The wrapped indicator is not changed
+        assert_eq!(mtf.next((6, &bar(1.0))), Some(7.0));
+    }
+
+    #[test]
+    fn test_bars() {
+        // FastStochastic needs the high and low of the whole higher timeframe bar
+        let stoch = FastStochastic::new(1).unwrap();
+        let mut mtf = MultiTimeframe::new(stoch, Resampler::new(2, 0).unwrap());
+
+        mtf.next((0, &Bar::new().high(10).low(8).close(9)));
+        mtf.next((1, &Bar::new().high(12).low(9).close(11)));
+        let value = mtf.next((2, &bar(11.0))).unwrap();
+        assert_eq!(round(value), 75.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let sma = Sma::new(2).unwrap();
+        let mut mtf = MultiTimeframe::new(sma, Resampler::new(3, 0).unwrap());
+
+        mtf.next((0, &bar(4.0)));
+        mtf.next((3, &bar(8.0)));
+        assert!(mtf.value().is_some());
+
+        mtf.reset();
+        assert_eq!(mtf.value(), None);
+        assert_eq!(mtf.next((6, &bar(2.0))), None);
+        assert_eq!(mtf.next((9, &bar(2.0))), Some(2.0));
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let sma = Sma::new(2).unwrap();
+        let mut mtf = MultiTimeframe::new(sma, Resampler::new(3, 0).unwrap());
+
+        mtf.next((0, &bar(4.0)));
+        mtf.next((3, &bar(8.0)));
+        assert!(!mtf.is_ready());
+        mtf.next((6, &bar(2.0)));
+        assert!(mtf.is_ready());
+    }
+
+    #[test]
+    fn test_display() {
+        let sma = Sma::new(9).unwrap();
+        let mtf: MultiTimeframe<Sma> = MultiTimeframe::new(sma, Resampler::new(3600, 0).unwrap());
+        assert_eq!(format!("{}", mtf), "MTF(SMA(9), 3600)");
+    }
+}
@@ -0,0 +1,350 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{ExponentialMovingAverage as Ema, ExponentialMovingAverageState};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+    Volume,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Negative Volume Index (NVI).
+///
+/// A cumulative indicator which changes only on bars where volume decreased compared to
+/// the previous bar. The idea is that "smart money" trades on quiet days, so price moves
+/// on low volume show what informed traders are doing.
+/// See also [PositiveVolumeIndex](struct.PositiveVolumeIndex.html).
+///
+/// # Formula
+///
+/// If Volume<sub>t</sub> < Volume<sub>t-1</sub>:
+///
+/// NVI<sub>t</sub> = NVI<sub>t-1</sub> * Close<sub>t</sub> / Close<sub>t-1</sub>
+///
+/// Otherwise NVI<sub>t</sub> = NVI<sub>t-1</sub>.
+///
+/// The index starts at 1000. Bars following a zero close do not change NVI.
+///
+/// Signal = EMA<sub>signal_period</sub>(NVI)
+///
+/// # Parameters
+///
+/// * _signal_period_ - period of the signal EMA (integer greater than 0). Default is 255.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::NegativeVolumeIndex;
+/// use ta::{Next, DataItem};
+///
+/// let mut nvi = NegativeVolumeIndex::new(255).unwrap();
+///
+/// let di1 = DataItem::builder()
+///             .high(11.0).low(9.0).close(10.0).open(10.0)
+///             .volume(1000.0)
+///             .build().unwrap();
+/// let di2 = DataItem::builder()
+///             .high(12.0).low(10.0).close(11.0).open(10.0)
+///             .volume(500.0)
+///             .build().unwrap();
+///
+/// assert_eq!(nvi.next(&di1).nvi, 1000.0);
+/// assert_eq!(nvi.next(&di2).nvi.round(), 1100.0);
+/// ```
+///
+/// # Links
+///
+/// * [Negative volume index, Wikipedia](https://en.wikipedia.org/wiki/Negative_volume_index)
+///
+#[doc(alias = "NVI")]
+#[derive(Debug, Clone)]
+pub struct NegativeVolumeIndex {
+    nvi: f64,
+    prev_close: Option<f64>,
+    prev_volume: f64,
+    signal_ema: Ema,
+    output: Option<NegativeVolumeIndexOutput>,
+    // State before the last input, restored by `amend`
+    saved_nvi: f64,
+    saved_prev_close: Option<f64>,
+    saved_prev_volume: f64,
+}
+
+/// State of [NegativeVolumeIndex](struct.NegativeVolumeIndex.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct NegativeVolumeIndexState {
+    pub nvi: f64,
+    pub previous_close: Option<f64>,
+    pub previous_volume: f64,
+    pub signal_ema: ExponentialMovingAverageState,
+    pub output: Option<NegativeVolumeIndexOutput>,
+    /// Index before the last input, to amend it.
+    pub previous_nvi: f64,
+    /// Close and volume of the input before the last one, to amend the last one.
+    pub close_before_last: Option<f64>,
+    pub volume_before_last: f64,
+}
+
+impl NegativeVolumeIndex {
+    pub fn new(signal_period: usize) -> Result<Self> {
+        Ok(Self {
+            nvi: 1000.0,
+            prev_close: None,
+            prev_volume: 0.0,
+            signal_ema: Ema::new(signal_period)?,
+            output: None,
+            saved_nvi: 1000.0,
+            saved_prev_close: None,
+            saved_prev_volume: 0.0,
+        })
+    }
+
+    fn update<T: Close + Volume>(&mut self, input: &T) {
+        if let Some(prev_close) = self.prev_close {
+            if input.volume() < self.prev_volume && prev_close != 0.0 {
+                self.nvi *= input.close() / prev_close;
+            }
+        }
+        self.prev_close = Some(input.close());
+        self.prev_volume = input.volume();
+    }
+}
+
+/// Output of [NegativeVolumeIndex](struct.NegativeVolumeIndex.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct NegativeVolumeIndexOutput {
+    pub nvi: f64,
+    pub signal: f64,
+}
+
+impl_output!(NegativeVolumeIndexOutput, nvi, signal);
+
+impl From<NegativeVolumeIndexOutput> for (f64, f64) {
+    fn from(output: NegativeVolumeIndexOutput) -> Self {
+        (output.nvi, output.signal)
+    }
+}
+
+impl Period for NegativeVolumeIndex {
+    fn period(&self) -> usize {
+        self.signal_ema.period()
+    }
+}
+
+impl Checkpoint for NegativeVolumeIndex {
+    type State = NegativeVolumeIndexState;
+
+    fn state(&self) -> Self::State {
+        NegativeVolumeIndexState {
+            nvi: self.nvi,
+            previous_close: self.prev_close,
+            previous_volume: self.prev_volume,
+            signal_ema: self.signal_ema.state(),
+            output: self.output.clone(),
+            previous_nvi: self.saved_nvi,
+            close_before_last: self.saved_prev_close,
+            volume_before_last: self.saved_prev_volume,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.previous_close.is_none() != state.output.is_none()
+            || state.output.is_none() != (state.signal_ema.count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            nvi: state.nvi,
+            prev_close: state.previous_close,
+            prev_volume: state.previous_volume,
+            signal_ema: Ema::from_state(state.signal_ema)?,
+            output: state.output,
+            saved_nvi: state.previous_nvi,
+            saved_prev_close: state.close_before_last,
+            saved_prev_volume: state.volume_before_last,
+        })
+    }
+}
+
+impl_versioned_serde!(NegativeVolumeIndex, 2);
+
+impl SetPeriod for NegativeVolumeIndex {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl<T: Close + Volume> Next<&T> for NegativeVolumeIndex {
+    type Output = NegativeVolumeIndexOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_nvi = self.nvi;
+        self.saved_prev_close = self.prev_close;
+        self.saved_prev_volume = self.prev_volume;
+        self.update(input);
+
+        let output = NegativeVolumeIndexOutput {
+            nvi: self.nvi,
+            signal: self.signal_ema.next(self.nvi),
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Close + Volume> Peek<&T> for NegativeVolumeIndex {
+    fn peek(&self, input: &T) -> NegativeVolumeIndexOutput {
+        let nvi = match self.prev_close {
+            Some(prev_close) if input.volume() < self.prev_volume && prev_close != 0.0 => {
+                self.nvi * (input.close() / prev_close)
+            }
+            _ => self.nvi,
+        };
+
+        NegativeVolumeIndexOutput {
+            nvi,
+            signal: self.signal_ema.peek(nvi),
+        }
+    }
+}
+
+impl<T: Close + Volume> Amend<&T> for NegativeVolumeIndex {
+    fn amend(&mut self, input: &T) -> NegativeVolumeIndexOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.nvi = self.saved_nvi;
+        self.prev_close = self.saved_prev_close;
+        self.prev_volume = self.saved_prev_volume;
+        self.update(input);
+
+        let output = NegativeVolumeIndexOutput {
+            nvi: self.nvi,
+            signal: self.signal_ema.amend(self.nvi),
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Reset for NegativeVolumeIndex {
+    fn reset(&mut self) {
+        self.nvi = 1000.0;
+        self.prev_close = None;
+        self.prev_volume = 0.0;
+        self.signal_ema.reset();
+        self.output = None;
+        self.saved_nvi = 1000.0;
+        self.saved_prev_close = None;
+        self.saved_prev_volume = 0.0;
+    }
+}
+
+impl Current for NegativeVolumeIndex {
+    type Output = NegativeVolumeIndexOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for NegativeVolumeIndex {
+    fn is_ready(&self) -> bool {
+        self.signal_ema.is_ready()
+    }
+}
+
+impl Lookback for NegativeVolumeIndex {
+    fn lookback(&self) -> usize {
+        self.signal_ema.lookback()
+    }
+}
+
+impl Default for NegativeVolumeIndex {
+    fn default() -> Self {
+        Self::new(255).unwrap()
+    }
+}
+
+impl fmt::Display for NegativeVolumeIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "NVI({})", self.signal_ema.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn round(nums: (f64, f64)) -> (f64, f64) {
+        let n0 = (nums.0 * 1000.0).round() / 1000.0;
+        let n1 = (nums.1 * 1000.0).round() / 1000.0;
+        (n0, n1)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(NegativeVolumeIndex::new(0).is_err());
+        assert!(NegativeVolumeIndex::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut nvi = NegativeVolumeIndex::new(3).unwrap();
+
+        let bar1 = Bar::new().close(10).volume(1000.0);
+        let bar2 = Bar::new().close(11).volume(500.0);
+        let bar3 = Bar::new().close(8.8).volume(1000.0);
+        let bar4 = Bar::new().close(8.8).volume(800.0);
+        let bar5 = Bar::new().close(9.68).volume(600.0);
+
+        assert_eq!(round(nvi.next(&bar1).into()), (1000.0, 1000.0));
+        // volume decreased, +10%
+        assert_eq!(round(nvi.next(&bar2).into()), (1100.0, 1050.0));
+        // volume increased, unchanged
+        assert_eq!(round(nvi.next(&bar3).into()), (1100.0, 1075.0));
+        // volume decreased, price unchanged
+        assert_eq!(round(nvi.next(&bar4).into()), (1100.0, 1087.5));
+        // volume decreased, +10%
+        assert_eq!(round(nvi.next(&bar5).into()), (1210.0, 1148.75));
+    }
+
+    #[test]
+    fn test_next_after_zero_close() {
+        let mut nvi = NegativeVolumeIndex::new(3).unwrap();
+
+        nvi.next(&Bar::new().close(0).volume(1000.0));
+        assert_eq!(nvi.next(&Bar::new().close(5).volume(500.0)).nvi, 1000.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut nvi = NegativeVolumeIndex::new(3).unwrap();
+
+        let bar1 = Bar::new().close(10).volume(1000.0);
+        let bar2 = Bar::new().close(11).volume(500.0);
+
+        nvi.next(&bar1);
+        nvi.next(&bar2);
+
+        nvi.reset();
+        assert_eq!(round(nvi.next(&bar2).into()), (1000.0, 1000.0));
+    }
+
+    #[test]
+    fn test_default() {
+        NegativeVolumeIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let nvi = NegativeVolumeIndex::new(255).unwrap();
+        assert_eq!(format!("{}", nvi), "NVI(255)");
+    }
+}
@@ -1,6 +1,7 @@
-use std::fmt;
+use core::fmt;
 
-use crate::{Close, Next, Reset, Volume};
+use crate::errors::{Result, TaError};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Reset, Volume};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -59,11 +60,29 @@ use serde::{Deserialize, Serialize};
 /// * [On Balance Volume, stockcharts](https://stockcharts.com/school/doku.php?id=chart_school:technical_indicators:on_balance_volume_obv)
 
 #[doc(alias = "OBV")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct OnBalanceVolume {
     obv: f64,
     prev_close: f64,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_obv: f64,
+    saved_prev_close: f64,
+}
+
+/// State of [OnBalanceVolume](struct.OnBalanceVolume.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnBalanceVolumeState {
+    /// On balance volume, `None` before the first input.
+    pub value: Option<f64>,
+    /// Close of the last input.
+    pub close: f64,
+    /// On balance volume before the last input, to amend it.
+    pub previous_value: f64,
+    /// Close of the input before the last one, to amend the last one.
+    pub previous_close: f64,
 }
 
 impl OnBalanceVolume {
@@ -71,6 +90,9 @@ impl OnBalanceVolume {
         Self {
             obv: 0.0,
             prev_close: 0.0,
+            output: None,
+            saved_obv: 0.0,
+            saved_prev_close: 0.0,
         }
     }
 }
@@ -79,16 +101,48 @@ impl<T: Close + Volume> Next<&T> for OnBalanceVolume {
     type Output = f64;
 
     fn next(&mut self, input: &T) -> f64 {
+        self.saved_obv = self.obv;
+        self.saved_prev_close = self.prev_close;
         if input.close() > self.prev_close {
             self.obv = self.obv + input.volume();
         } else if input.close() < self.prev_close {
             self.obv = self.obv - input.volume();
         }
         self.prev_close = input.close();
-        self.obv
+        let output = self.obv;
+        self.output = Some(output);
+        output
     }
 }
 
+impl Checkpoint for OnBalanceVolume {
+    type State = OnBalanceVolumeState;
+
+    fn state(&self) -> Self::State {
+        OnBalanceVolumeState {
+            value: self.output,
+            close: self.prev_close,
+            previous_value: self.saved_obv,
+            previous_close: self.saved_prev_close,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.value.is_none() && state.close != 0.0 {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            obv: state.value.unwrap_or(0.0),
+            prev_close: state.close,
+            output: state.value,
+            saved_obv: state.previous_value,
+            saved_prev_close: state.previous_close,
+        })
+    }
+}
+
+impl_versioned_serde!(OnBalanceVolume, 1);
+
 impl Default for OnBalanceVolume {
     fn default() -> Self {
         Self::new()
@@ -101,10 +155,56 @@ impl fmt::Display for OnBalanceVolume {
     }
 }
 
+impl<T: Close + Volume> Peek<&T> for OnBalanceVolume {
+    fn peek(&self, input: &T) -> f64 {
+        if input.close() > self.prev_close {
+            self.obv + input.volume()
+        } else if input.close() < self.prev_close {
+            self.obv - input.volume()
+        } else {
+            self.obv
+        }
+    }
+}
+
+impl<T: Close + Volume> Amend<&T> for OnBalanceVolume {
+    fn amend(&mut self, input: &T) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.obv = self.saved_obv;
+        self.prev_close = self.saved_prev_close;
+        self.next(input)
+    }
+}
+
 impl Reset for OnBalanceVolume {
     fn reset(&mut self) {
         self.obv = 0.0;
         self.prev_close = 0.0;
+        self.output = None;
+        self.saved_obv = 0.0;
+        self.saved_prev_close = 0.0;
+    }
+}
+
+impl Current for OnBalanceVolume {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for OnBalanceVolume {
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+impl Lookback for OnBalanceVolume {
+    fn lookback(&self) -> usize {
+        1
     }
 }
 
@@ -1,8 +1,8 @@
-use std::fmt;
+use core::fmt;
 
-use crate::errors::Result;
-use crate::indicators::ExponentialMovingAverage as Ema;
-use crate::{Close, Next, Period, Reset};
+use crate::errors::{Result, TaError};
+use crate::indicators::{ExponentialMovingAverage as Ema, ExponentialMovingAverageState};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -51,12 +51,26 @@ use serde::{Deserialize, Serialize};
 /// }
 /// ```
 #[doc(alias = "PPO")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct PercentagePriceOscillator {
     fast_ema: Ema,
     slow_ema: Ema,
     signal_ema: Ema,
+    count: usize,
+    output: Option<PercentagePriceOscillatorOutput>,
+}
+
+/// State of [PercentagePriceOscillator](struct.PercentagePriceOscillator.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PercentagePriceOscillatorState {
+    pub fast_ema: ExponentialMovingAverageState,
+    pub slow_ema: ExponentialMovingAverageState,
+    pub signal_ema: ExponentialMovingAverageState,
+    /// Number of inputs since both averages are ready, up to the signal period.
+    pub count: usize,
+    pub output: Option<PercentagePriceOscillatorOutput>,
 }
 
 impl PercentagePriceOscillator {
@@ -65,10 +79,14 @@ impl PercentagePriceOscillator {
             fast_ema: Ema::new(fast_period)?,
             slow_ema: Ema::new(slow_period)?,
             signal_ema: Ema::new(signal_period)?,
+            count: 0,
+            output: None,
         })
     }
 }
 
+/// Output of [PercentagePriceOscillator](struct.PercentagePriceOscillator.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
 pub struct PercentagePriceOscillatorOutput {
     pub ppo: f64,
@@ -76,12 +94,48 @@ pub struct PercentagePriceOscillatorOutput {
     pub histogram: f64,
 }
 
+impl_output!(PercentagePriceOscillatorOutput, ppo, signal, histogram);
+
 impl From<PercentagePriceOscillatorOutput> for (f64, f64, f64) {
     fn from(po: PercentagePriceOscillatorOutput) -> Self {
         (po.ppo, po.signal, po.histogram)
     }
 }
 
+impl Checkpoint for PercentagePriceOscillator {
+    type State = PercentagePriceOscillatorState;
+
+    fn state(&self) -> Self::State {
+        PercentagePriceOscillatorState {
+            fast_ema: self.fast_ema.state(),
+            slow_ema: self.slow_ema.state(),
+            signal_ema: self.signal_ema.state(),
+            count: self.count,
+            output: self.output.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let indicator = Self {
+            fast_ema: Ema::from_state(state.fast_ema)?,
+            slow_ema: Ema::from_state(state.slow_ema)?,
+            signal_ema: Ema::from_state(state.signal_ema)?,
+            count: state.count,
+            output: state.output,
+        };
+        if indicator.count > indicator.signal_ema.period()
+            || (indicator.count > 0
+                && !(indicator.fast_ema.is_ready() && indicator.slow_ema.is_ready()))
+            || indicator.output.is_some() != indicator.signal_ema.current().is_some()
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(PercentagePriceOscillator, 1);
+
 impl Next<f64> for PercentagePriceOscillator {
     type Output = PercentagePriceOscillatorOutput;
 
@@ -90,14 +144,19 @@ impl Next<f64> for PercentagePriceOscillator {
         let slow_val = self.slow_ema.next(input);
 
         let ppo = (fast_val - slow_val) / slow_val * 100.0;
+        if self.fast_ema.is_ready() && self.slow_ema.is_ready() && !self.is_ready() {
+            self.count += 1;
+        }
         let signal = self.signal_ema.next(ppo);
         let histogram = ppo - signal;
 
-        PercentagePriceOscillatorOutput {
+        let output = PercentagePriceOscillatorOutput {
             ppo,
             signal,
             histogram,
-        }
+        };
+        self.output = Some(output.clone());
+        output
     }
 }
 
@@ -109,11 +168,82 @@ impl<T: Close> Next<&T> for PercentagePriceOscillator {
     }
 }
 
+impl Peek<f64> for PercentagePriceOscillator {
+    fn peek(&self, input: f64) -> PercentagePriceOscillatorOutput {
+        let fast_val = self.fast_ema.peek(input);
+        let slow_val = self.slow_ema.peek(input);
+
+        let ppo = (fast_val - slow_val) / slow_val * 100.0;
+        let signal = self.signal_ema.peek(ppo);
+
+        PercentagePriceOscillatorOutput {
+            ppo,
+            signal,
+            histogram: ppo - signal,
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for PercentagePriceOscillator {
+    fn peek(&self, input: &T) -> PercentagePriceOscillatorOutput {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for PercentagePriceOscillator {
+    fn amend(&mut self, input: f64) -> PercentagePriceOscillatorOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let fast_val = self.fast_ema.amend(input);
+        let slow_val = self.slow_ema.amend(input);
+        let ppo = (fast_val - slow_val) / slow_val * 100.0;
+        let signal = self.signal_ema.amend(ppo);
+
+        let output = PercentagePriceOscillatorOutput {
+            ppo,
+            signal,
+            histogram: ppo - signal,
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for PercentagePriceOscillator {
+    fn amend(&mut self, input: &T) -> PercentagePriceOscillatorOutput {
+        self.amend(input.close())
+    }
+}
+
 impl Reset for PercentagePriceOscillator {
     fn reset(&mut self) {
         self.fast_ema.reset();
         self.slow_ema.reset();
         self.signal_ema.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl Current for PercentagePriceOscillator {
+    type Output = PercentagePriceOscillatorOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for PercentagePriceOscillator {
+    fn is_ready(&self) -> bool {
+        self.count == self.signal_ema.period()
+    }
+}
+
+impl Lookback for PercentagePriceOscillator {
+    fn lookback(&self) -> usize {
+        let ppo = self.fast_ema.lookback().max(self.slow_ema.lookback());
+        ppo + self.signal_ema.period() - 1
     }
 }
 
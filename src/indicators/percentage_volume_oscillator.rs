@@ -0,0 +1,368 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{ExponentialMovingAverage as Ema, ExponentialMovingAverageState};
+use crate::{Amend, Checkpoint, Current, IsReady, Lookback, Next, Peek, Period, Reset, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Percentage Volume Oscillator (PVO).
+///
+/// The PVO is the [PPO](struct.PercentagePriceOscillator.html) applied to volume
+/// instead of price. It is a collection of three time series:
+///
+/// * The PVO series proper
+/// * The "signal" or "average" series
+/// * The "divergence" series which is the difference between the two
+///
+/// The PVO series is the difference between a "fast" (short period) exponential
+/// moving average (EMA) and a "slow" (longer period) EMA of the volume, expressed
+/// as a percentage of the slow EMA. The signal series is an EMA of the PVO series itself.
+///
+/// # Formula
+///
+/// PVO = (EMA<sub>fast</sub>(Volume) - EMA<sub>slow</sub>(Volume)) / EMA<sub>slow</sub>(Volume) * 100
+///
+/// Signal = EMA<sub>signal</sub>(PVO)
+///
+/// Histogram = PVO - Signal
+///
+/// If the slow EMA is zero (no volume has been traded yet), PVO is 0.
+///
+/// # Parameters
+///
+/// * _fast_period_ - period for the fast EMA. Default is 12.
+/// * _slow_period_ - period for the slow EMA. Default is 26.
+/// * _signal_period_ - period for the signal EMA. Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::PercentageVolumeOscillator as Pvo;
+/// use ta::Next;
+///
+/// let mut pvo = Pvo::new(2, 4, 3).unwrap();
+///
+/// assert_eq!(round(pvo.next(100.0).into()), (0.0, 0.0, 0.0));
+/// assert_eq!(round(pvo.next(300.0).into()), (29.63, 14.81, 14.81));
+/// assert_eq!(round(pvo.next(200.0).into()), (12.29, 13.55, -1.26));
+///
+/// fn round(nums: (f64, f64, f64)) -> (f64, f64, f64) {
+///     let n0 = (nums.0 * 100.0).round() / 100.0;
+///     let n1 = (nums.1 * 100.0).round() / 100.0;
+///     let n2 = (nums.2 * 100.0).round() / 100.0;
+///     (n0, n1, n2)
+/// }
+/// ```
+///
+/// # Links
+///
+/// * [Percentage Volume Oscillator, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:percentage_volume_oscillator_pvo)
+///
+#[doc(alias = "PVO")]
+#[derive(Debug, Clone)]
+pub struct PercentageVolumeOscillator {
+    fast_ema: Ema,
+    slow_ema: Ema,
+    signal_ema: Ema,
+    count: usize,
+    output: Option<PercentageVolumeOscillatorOutput>,
+}
+
+/// State of [PercentageVolumeOscillator](struct.PercentageVolumeOscillator.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PercentageVolumeOscillatorState {
+    pub fast_ema: ExponentialMovingAverageState,
+    pub slow_ema: ExponentialMovingAverageState,
+    pub signal_ema: ExponentialMovingAverageState,
+    /// Number of inputs since both averages are ready, up to the signal period.
+    pub count: usize,
+    pub output: Option<PercentageVolumeOscillatorOutput>,
+}
+
+impl PercentageVolumeOscillator {
+    pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Result<Self> {
+        Ok(PercentageVolumeOscillator {
+            fast_ema: Ema::new(fast_period)?,
+            slow_ema: Ema::new(slow_period)?,
+            signal_ema: Ema::new(signal_period)?,
+            count: 0,
+            output: None,
+        })
+    }
+}
+
+/// Output of [PercentageVolumeOscillator](struct.PercentageVolumeOscillator.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PercentageVolumeOscillatorOutput {
+    pub pvo: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+impl_output!(PercentageVolumeOscillatorOutput, pvo, signal, histogram);
+
+impl From<PercentageVolumeOscillatorOutput> for (f64, f64, f64) {
+    fn from(po: PercentageVolumeOscillatorOutput) -> Self {
+        (po.pvo, po.signal, po.histogram)
+    }
+}
+
+impl Checkpoint for PercentageVolumeOscillator {
+    type State = PercentageVolumeOscillatorState;
+
+    fn state(&self) -> Self::State {
+        PercentageVolumeOscillatorState {
+            fast_ema: self.fast_ema.state(),
+            slow_ema: self.slow_ema.state(),
+            signal_ema: self.signal_ema.state(),
+            count: self.count,
+            output: self.output.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let indicator = Self {
+            fast_ema: Ema::from_state(state.fast_ema)?,
+            slow_ema: Ema::from_state(state.slow_ema)?,
+            signal_ema: Ema::from_state(state.signal_ema)?,
+            count: state.count,
+            output: state.output,
+        };
+        if indicator.count > indicator.signal_ema.period()
+            || (indicator.count > 0
+                && !(indicator.fast_ema.is_ready() && indicator.slow_ema.is_ready()))
+            || indicator.output.is_some() != indicator.signal_ema.current().is_some()
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(PercentageVolumeOscillator, 1);
+
+impl Next<f64> for PercentageVolumeOscillator {
+    type Output = PercentageVolumeOscillatorOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let fast_val = self.fast_ema.next(input);
+        let slow_val = self.slow_ema.next(input);
+
+        let pvo = if slow_val != 0.0 {
+            (fast_val - slow_val) / slow_val * 100.0
+        } else {
+            0.0
+        };
+        if self.fast_ema.is_ready() && self.slow_ema.is_ready() && !self.is_ready() {
+            self.count += 1;
+        }
+        let signal = self.signal_ema.next(pvo);
+        let histogram = pvo - signal;
+
+        let output = PercentageVolumeOscillatorOutput {
+            pvo,
+            signal,
+            histogram,
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Volume> Next<&T> for PercentageVolumeOscillator {
+    type Output = PercentageVolumeOscillatorOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.volume())
+    }
+}
+
+impl Peek<f64> for PercentageVolumeOscillator {
+    fn peek(&self, input: f64) -> PercentageVolumeOscillatorOutput {
+        let fast_val = self.fast_ema.peek(input);
+        let slow_val = self.slow_ema.peek(input);
+
+        let pvo = if slow_val != 0.0 {
+            (fast_val - slow_val) / slow_val * 100.0
+        } else {
+            0.0
+        };
+        let signal = self.signal_ema.peek(pvo);
+
+        PercentageVolumeOscillatorOutput {
+            pvo,
+            signal,
+            histogram: pvo - signal,
+        }
+    }
+}
+
+impl<T: Volume> Peek<&T> for PercentageVolumeOscillator {
+    fn peek(&self, input: &T) -> PercentageVolumeOscillatorOutput {
+        self.peek(input.volume())
+    }
+}
+
+impl Amend<f64> for PercentageVolumeOscillator {
+    fn amend(&mut self, input: f64) -> PercentageVolumeOscillatorOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let fast_val = self.fast_ema.amend(input);
+        let slow_val = self.slow_ema.amend(input);
+        let pvo = if slow_val != 0.0 {
+            (fast_val - slow_val) / slow_val * 100.0
+        } else {
+            0.0
+        };
+        let signal = self.signal_ema.amend(pvo);
+
+        let output = PercentageVolumeOscillatorOutput {
+            pvo,
+            signal,
+            histogram: pvo - signal,
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Volume> Amend<&T> for PercentageVolumeOscillator {
+    fn amend(&mut self, input: &T) -> PercentageVolumeOscillatorOutput {
+        self.amend(input.volume())
+    }
+}
+
+impl Reset for PercentageVolumeOscillator {
+    fn reset(&mut self) {
+        self.fast_ema.reset();
+        self.slow_ema.reset();
+        self.signal_ema.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl Current for PercentageVolumeOscillator {
+    type Output = PercentageVolumeOscillatorOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for PercentageVolumeOscillator {
+    fn is_ready(&self) -> bool {
+        self.count == self.signal_ema.period()
+    }
+}
+
+impl Lookback for PercentageVolumeOscillator {
+    fn lookback(&self) -> usize {
+        let pvo = self.fast_ema.lookback().max(self.slow_ema.lookback());
+        pvo + self.signal_ema.period() - 1
+    }
+}
+
+impl Default for PercentageVolumeOscillator {
+    fn default() -> Self {
+        Self::new(12, 26, 9).unwrap()
+    }
+}
+
+impl fmt::Display for PercentageVolumeOscillator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "PVO({}, {}, {})",
+            self.fast_ema.period(),
+            self.slow_ema.period(),
+            self.signal_ema.period()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    type Pvo = PercentageVolumeOscillator;
+
+    test_indicator!(Pvo);
+
+    fn round(nums: (f64, f64, f64)) -> (f64, f64, f64) {
+        let n0 = (nums.0 * 100.0).round() / 100.0;
+        let n1 = (nums.1 * 100.0).round() / 100.0;
+        let n2 = (nums.2 * 100.0).round() / 100.0;
+        (n0, n1, n2)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Pvo::new(0, 1, 1).is_err());
+        assert!(Pvo::new(1, 0, 1).is_err());
+        assert!(Pvo::new(1, 1, 0).is_err());
+        assert!(Pvo::new(1, 1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut pvo = Pvo::new(2, 4, 3).unwrap();
+
+        assert_eq!(round(pvo.next(100.0).into()), (0.0, 0.0, 0.0));
+        assert_eq!(round(pvo.next(300.0).into()), (29.63, 14.81, 14.81));
+        assert_eq!(round(pvo.next(200.0).into()), (12.29, 13.55, -1.26));
+        assert_eq!(round(pvo.next(500.0).into()), (29.06, 21.31, 7.75));
+        assert_eq!(round(pvo.next(400.0).into()), (15.4, 18.36, -2.95));
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut pvo = Pvo::new(2, 4, 3).unwrap();
+
+        assert_eq!(
+            round(pvo.next(&Bar::new().volume(100.0)).into()),
+            (0.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            round(pvo.next(&Bar::new().volume(300.0)).into()),
+            (29.63, 14.81, 14.81)
+        );
+    }
+
+    #[test]
+    fn test_next_zero_volume() {
+        let mut pvo = Pvo::new(2, 4, 3).unwrap();
+
+        assert_eq!(round(pvo.next(0.0).into()), (0.0, 0.0, 0.0));
+        assert_eq!(round(pvo.next(0.0).into()), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut pvo = Pvo::new(2, 4, 3).unwrap();
+
+        assert_eq!(round(pvo.next(100.0).into()), (0.0, 0.0, 0.0));
+        assert_eq!(round(pvo.next(300.0).into()), (29.63, 14.81, 14.81));
+
+        pvo.reset();
+
+        assert_eq!(round(pvo.next(100.0).into()), (0.0, 0.0, 0.0));
+        assert_eq!(round(pvo.next(300.0).into()), (29.63, 14.81, 14.81));
+    }
+
+    #[test]
+    fn test_default() {
+        Pvo::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let indicator = Pvo::new(13, 30, 10).unwrap();
+        assert_eq!(format!("{}", indicator), "PVO(13, 30, 10)");
+    }
+}
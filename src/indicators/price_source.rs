@@ -0,0 +1,261 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Open, Peek, Period,
+    Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Price of a bar that an indicator is fed with.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PriceSource {
+    #[default]
+    Close,
+    Open,
+    High,
+    Low,
+    /// Median price, (High + Low) / 2.
+    HL2,
+    /// Typical price, (High + Low + Close) / 3.
+    HLC3,
+    /// Average price, (Open + High + Low + Close) / 4.
+    OHLC4,
+}
+
+impl PriceSource {
+    /// Price of `bar`.
+    pub fn price<T: Open + High + Low + Close>(&self, bar: &T) -> f64 {
+        match self {
+            Self::Close => bar.close(),
+            Self::Open => bar.open(),
+            Self::High => bar.high(),
+            Self::Low => bar.low(),
+            Self::HL2 => (bar.high() + bar.low()) / 2.0,
+            Self::HLC3 => (bar.high() + bar.low() + bar.close()) / 3.0,
+            Self::OHLC4 => (bar.open() + bar.high() + bar.low() + bar.close()) / 4.0,
+        }
+    }
+}
+
+/// Feeds an indicator with a price other than the close.
+///
+/// Indicators that take a single price use the close of a bar. This wrapper lets them use any
+/// [PriceSource](enum.PriceSource.html) instead, e.g. the typical price, without converting the
+/// bars first. Plain `f64` inputs are passed through unchanged.
+///
+/// # Parameters
+///
+/// * _indicator_ - indicator to feed.
+/// * _source_ - price to take from each bar. Default is `Close`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use ta::indicators::{PriceSource, SimpleMovingAverage, Sourced};
+/// use ta::{DataItem, Next};
+///
+/// let sma = SimpleMovingAverage::new(2).unwrap();
+/// let mut sma = Sourced::new(sma, PriceSource::HLC3);
+///
+/// let bar = DataItem::builder()
+///     .open(10.0).high(12.0).low(6.0).close(9.0)
+///     .volume(1000.0)
+///     .build().unwrap();
+///
+/// assert_eq!(sma.next(&bar), 9.0);
+/// assert_eq!(sma.next(11.0), 10.0);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sourced<I> {
+    indicator: I,
+    source: PriceSource,
+}
+
+/// State of [Sourced](struct.Sourced.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourcedState<S> {
+    pub indicator: S,
+    pub source: PriceSource,
+}
+
+impl<I> Sourced<I> {
+    pub fn new(indicator: I, source: PriceSource) -> Self {
+        Self { indicator, source }
+    }
+
+    /// The wrapped indicator.
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+
+    pub fn source(&self) -> PriceSource {
+        self.source
+    }
+}
+
+impl<I: Period> Period for Sourced<I> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<I: SetPeriod> SetPeriod for Sourced<I> {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        self.indicator.set_period(period)
+    }
+}
+
+impl<I: Checkpoint> Checkpoint for Sourced<I> {
+    type State = SourcedState<I::State>;
+
+    fn state(&self) -> Self::State {
+        SourcedState {
+            indicator: self.indicator.state(),
+            source: self.source,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(Self::new(I::from_state(state.indicator)?, state.source))
+    }
+}
+
+impl_versioned_serde!(Sourced<I>, 1);
+
+impl<I: Next<f64>> Next<f64> for Sourced<I> {
+    type Output = I::Output;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.indicator.next(input)
+    }
+}
+
+impl<I: Next<f64>, T: Open + High + Low + Close> Next<&T> for Sourced<I> {
+    type Output = I::Output;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.indicator.next(self.source.price(input))
+    }
+}
+
+impl<I: Peek<f64>> Peek<f64> for Sourced<I> {
+    fn peek(&self, input: f64) -> I::Output {
+        self.indicator.peek(input)
+    }
+}
+
+impl<I: Peek<f64>, T: Open + High + Low + Close> Peek<&T> for Sourced<I> {
+    fn peek(&self, input: &T) -> I::Output {
+        self.indicator.peek(self.source.price(input))
+    }
+}
+
+impl<I: Amend<f64>> Amend<f64> for Sourced<I> {
+    fn amend(&mut self, input: f64) -> I::Output {
+        self.indicator.amend(input)
+    }
+}
+
+impl<I: Amend<f64>, T: Open + High + Low + Close> Amend<&T> for Sourced<I> {
+    fn amend(&mut self, input: &T) -> I::Output {
+        self.indicator.amend(self.source.price(input))
+    }
+}
+
+impl<I: Reset> Reset for Sourced<I> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+    }
+}
+
+impl<I: Current> Current for Sourced<I> {
+    type Output = I::Output;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.indicator.current()
+    }
+}
+
+impl<I: IsReady> IsReady for Sourced<I> {
+    fn is_ready(&self) -> bool {
+        self.indicator.is_ready()
+    }
+}
+
+impl<I: Lookback> Lookback for Sourced<I> {
+    fn lookback(&self) -> usize {
+        self.indicator.lookback()
+    }
+}
+
+impl<I: Default> Default for Sourced<I> {
+    fn default() -> Self {
+        Self::new(I::default(), PriceSource::default())
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for Sourced<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SOURCED({}, {:?})", self.indicator, self.source)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::indicators::SimpleMovingAverage as Sma;
+    use crate::test_helper::*;
+
+    fn bar() -> Bar {
+        Bar::new().open(9).high(12).low(6).close(11)
+    }
+
+    #[test]
+    fn test_price() {
+        let bar = bar();
+        assert_eq!(PriceSource::Close.price(&bar), 11.0);
+        assert_eq!(PriceSource::Open.price(&bar), 9.0);
+        assert_eq!(PriceSource::High.price(&bar), 12.0);
+        assert_eq!(PriceSource::Low.price(&bar), 6.0);
+        assert_eq!(PriceSource::HL2.price(&bar), 9.0);
+        assert_eq!(PriceSource::HLC3.price(&bar), 29.0 / 3.0);
+        assert_eq!(PriceSource::OHLC4.price(&bar), 9.5);
+    }
+
+    #[test]
+    fn test_next() {
+        let mut sma = Sourced::new(Sma::new(2).unwrap(), PriceSource::High);
+
+        assert_eq!(sma.next(&bar()), 12.0);
+        assert_eq!(sma.next(&bar().high(14)), 13.0);
+        assert_eq!(sma.next(8.0), 11.0);
+        assert_eq!(sma.period(), 2);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sma = Sourced::new(Sma::new(2).unwrap(), PriceSource::Low);
+
+        sma.next(&bar());
+        sma.reset();
+        assert_eq!(sma.next(&bar().low(4)), 4.0);
+    }
+
+    #[test]
+    fn test_default() {
+        let sma = Sourced::<Sma>::default();
+        assert_eq!(sma.source(), PriceSource::Close);
+    }
+
+    #[test]
+    fn test_display() {
+        let sma = Sourced::new(Sma::new(9).unwrap(), PriceSource::OHLC4);
+        assert_eq!(format!("{}", sma), "SOURCED(SMA(9), OHLC4)");
+    }
+}
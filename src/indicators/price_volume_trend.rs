@@ -0,0 +1,245 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Reset, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Price Volume Trend (PVT).
+///
+/// A cumulative volume based indicator similar to [OBV](struct.OnBalanceVolume.html), but instead
+/// of adding or subtracting the whole volume it adds volume in proportion to the relative
+/// price change.
+///
+/// # Formula
+///
+/// PVT<sub>t</sub> = PVT<sub>t-1</sub> + Volume<sub>t</sub> * (Close<sub>t</sub> - Close<sub>t-1</sub>) / Close<sub>t-1</sub>
+///
+/// The value of the very first bar is 0, since there is no previous close.
+/// Bars following a zero close do not change PVT.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::PriceVolumeTrend;
+/// use ta::{Next, DataItem};
+///
+/// let mut pvt = PriceVolumeTrend::new();
+///
+/// let di1 = DataItem::builder()
+///             .high(11.0).low(9.0).close(10.0).open(10.0)
+///             .volume(1000.0)
+///             .build().unwrap();
+/// let di2 = DataItem::builder()
+///             .high(12.0).low(10.0).close(11.0).open(10.0)
+///             .volume(500.0)
+///             .build().unwrap();
+///
+/// assert_eq!(pvt.next(&di1), 0.0);
+/// assert_eq!(pvt.next(&di2), 50.0);
+/// ```
+///
+/// # Links
+///
+/// * [Volume-price trend, Wikipedia](https://en.wikipedia.org/wiki/Volume%E2%80%93price_trend)
+///
+#[doc(alias = "PVT")]
+#[derive(Debug, Clone)]
+pub struct PriceVolumeTrend {
+    pvt: f64,
+    prev_close: Option<f64>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_pvt: f64,
+    saved_prev_close: Option<f64>,
+}
+
+/// State of [PriceVolumeTrend](struct.PriceVolumeTrend.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceVolumeTrendState {
+    pub pvt: f64,
+    pub previous_close: Option<f64>,
+    /// Trend before the last input, to amend it.
+    pub previous_pvt: f64,
+    /// Close of the input before the last one, to amend the last one.
+    pub close_before_last: Option<f64>,
+}
+
+impl PriceVolumeTrend {
+    pub fn new() -> Self {
+        Self {
+            pvt: 0.0,
+            prev_close: None,
+            output: None,
+            saved_pvt: 0.0,
+            saved_prev_close: None,
+        }
+    }
+}
+
+impl Checkpoint for PriceVolumeTrend {
+    type State = PriceVolumeTrendState;
+
+    fn state(&self) -> Self::State {
+        PriceVolumeTrendState {
+            pvt: self.pvt,
+            previous_close: self.prev_close,
+            previous_pvt: self.saved_pvt,
+            close_before_last: self.saved_prev_close,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.previous_close.is_none() && (state.pvt != 0.0 || state.close_before_last.is_some())
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            pvt: state.pvt,
+            prev_close: state.previous_close,
+            output: state.previous_close.map(|_| state.pvt),
+            saved_pvt: state.previous_pvt,
+            saved_prev_close: state.close_before_last,
+        })
+    }
+}
+
+impl_versioned_serde!(PriceVolumeTrend, 2);
+
+impl<T: Close + Volume> Next<&T> for PriceVolumeTrend {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_pvt = self.pvt;
+        self.saved_prev_close = self.prev_close;
+        if let Some(prev_close) = self.prev_close {
+            if prev_close != 0.0 {
+                self.pvt += input.volume() * (input.close() - prev_close) / prev_close;
+            }
+        }
+        self.prev_close = Some(input.close());
+        let output = self.pvt;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close + Volume> Peek<&T> for PriceVolumeTrend {
+    fn peek(&self, input: &T) -> f64 {
+        match self.prev_close {
+            Some(prev_close) if prev_close != 0.0 => {
+                self.pvt + input.volume() * (input.close() - prev_close) / prev_close
+            }
+            _ => self.pvt,
+        }
+    }
+}
+
+impl<T: Close + Volume> Amend<&T> for PriceVolumeTrend {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.pvt = self.saved_pvt;
+        self.prev_close = self.saved_prev_close;
+        self.next(input)
+    }
+}
+
+impl Reset for PriceVolumeTrend {
+    fn reset(&mut self) {
+        self.pvt = 0.0;
+        self.prev_close = None;
+        self.output = None;
+        self.saved_pvt = 0.0;
+        self.saved_prev_close = None;
+    }
+}
+
+impl Current for PriceVolumeTrend {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for PriceVolumeTrend {
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+impl Lookback for PriceVolumeTrend {
+    fn lookback(&self) -> usize {
+        1
+    }
+}
+
+impl Default for PriceVolumeTrend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for PriceVolumeTrend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PVT")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_next_bar() {
+        let mut pvt = PriceVolumeTrend::new();
+
+        let bar1 = Bar::new().close(10).volume(1000.0);
+        let bar2 = Bar::new().close(11).volume(500.0);
+        let bar3 = Bar::new().close(8.8).volume(1000.0);
+        let bar4 = Bar::new().close(8.8).volume(3000.0);
+
+        assert_eq!(pvt.next(&bar1), 0.0);
+        // +10%
+        assert_eq!(round(pvt.next(&bar2)), 50.0);
+        // -20%
+        assert_eq!(round(pvt.next(&bar3)), -150.0);
+        // unchanged
+        assert_eq!(round(pvt.next(&bar4)), -150.0);
+    }
+
+    #[test]
+    fn test_next_after_zero_close() {
+        let mut pvt = PriceVolumeTrend::new();
+
+        pvt.next(&Bar::new().close(0).volume(1000.0));
+        assert_eq!(pvt.next(&Bar::new().close(5).volume(1000.0)), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut pvt = PriceVolumeTrend::new();
+
+        let bar1 = Bar::new().close(10).volume(1000.0);
+        let bar2 = Bar::new().close(11).volume(500.0);
+
+        pvt.next(&bar1);
+        pvt.next(&bar2);
+
+        pvt.reset();
+        assert_eq!(pvt.next(&bar2), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        PriceVolumeTrend::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let pvt = PriceVolumeTrend::new();
+        assert_eq!(format!("{}", pvt), "PVT");
+    }
+}
@@ -0,0 +1,557 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{
+    ExponentialMovingAverage as Ema, ExponentialMovingAverageState, RelativeStrengthIndex as Rsi,
+    RelativeStrengthIndexState,
+};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Quantitative Qualitative Estimation (QQE).
+///
+/// A smoothed [RSI](type.RelativeStrengthIndex.html) combined with two trailing lines whose
+/// distance is based on the volatility of the smoothed RSI, in the same way an ATR trailing
+/// stop follows the price. Crosses of the smoothed RSI and the trailing lines are used as signals.
+///
+/// # Formula
+///
+/// RSI<sub>MA</sub> = EMA(RSI(_rsi_period_), _smoothing_period_)
+///
+/// DAR = EMA(EMA(|RSI<sub>MA, t</sub> - RSI<sub>MA, t-1</sub>|, 2 * _rsi_period_ - 1), 2 * _rsi_period_ - 1)
+///
+/// Long band = RSI<sub>MA</sub> - _factor_ * DAR, which only rises while RSI<sub>MA</sub> stays above it
+///
+/// Short band = RSI<sub>MA</sub> + _factor_ * DAR, which only falls while RSI<sub>MA</sub> stays below it
+///
+/// The trailing line follows the long band until RSI<sub>MA</sub> drops below it, and the
+/// short band until RSI<sub>MA</sub> rises above it. The EMA over 2 * _rsi_period_ - 1 bars is
+/// the equivalent of Wilder's smoothing over _rsi_period_ bars.
+///
+/// The fast and the slow lines are trailing lines calculated with _fast_factor_ and _slow_factor_.
+///
+/// # Parameters
+///
+/// * _rsi_period_ - period of the RSI (integer greater than 0). Default is 14.
+/// * _smoothing_period_ - period of the RSI smoothing (integer greater than 0). Default is 5.
+/// * _fast_factor_ - band width multiplier of the fast line (number greater than 0). Default is 2.618.
+/// * _slow_factor_ - band width multiplier of the slow line (number greater than 0). Default is 4.236.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::Qqe;
+/// use ta::Next;
+///
+/// let mut qqe = Qqe::new(3, 2, 2.618, 4.236).unwrap();
+///
+/// let out = qqe.next(10.0);
+/// assert_eq!(out.rsi_ma, 50.0);
+///
+/// let out = qqe.next(12.0);
+/// assert_eq!(out.rsi_ma.round(), 83.0);
+/// assert_eq!(out.fast_line.round(), 74.0);
+/// assert_eq!(out.slow_line.round(), 68.0);
+/// ```
+///
+#[doc(alias = "QQE")]
+#[derive(Debug, Clone)]
+pub struct Qqe {
+    rsi: Rsi,
+    rsi_ema: Ema,
+    tr_ema: Ema,
+    dar_ema: Ema,
+    prev_rsi_ma: Option<f64>,
+    fast: TrailingLine,
+    slow: TrailingLine,
+    count: usize,
+    output: Option<QqeOutput>,
+    // State before the last input, restored by `amend`
+    saved_count: usize,
+    saved_prev_rsi_ma: Option<f64>,
+    saved_fast: TrailingLine,
+    saved_slow: TrailingLine,
+}
+
+/// State of [Qqe](struct.Qqe.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct QqeState {
+    pub rsi: RelativeStrengthIndexState,
+    /// Smoothing of the RSI.
+    pub rsi_ema: ExponentialMovingAverageState,
+    /// First smoothing of the changes of the smoothed RSI.
+    pub tr_ema: ExponentialMovingAverageState,
+    /// Second smoothing of the changes of the smoothed RSI.
+    pub dar_ema: ExponentialMovingAverageState,
+    /// Last smoothed RSI.
+    pub previous_rsi_ma: Option<f64>,
+    pub fast_factor: f64,
+    /// Long and short band of the fast line.
+    pub fast_bands: (f64, f64),
+    /// Whether the fast line follows the long band.
+    pub fast_bullish: bool,
+    pub slow_factor: f64,
+    /// Long and short band of the slow line.
+    pub slow_bands: (f64, f64),
+    /// Whether the slow line follows the long band.
+    pub slow_bullish: bool,
+    /// Number of inputs, up to the lookback.
+    pub count: usize,
+    pub output: Option<QqeOutput>,
+    /// Count, smoothed RSI and lines before the last input, to amend it.
+    pub previous_count: usize,
+    pub rsi_ma_before_last: Option<f64>,
+    pub previous_fast_bands: (f64, f64),
+    pub previous_fast_bullish: bool,
+    pub previous_slow_bands: (f64, f64),
+    pub previous_slow_bullish: bool,
+}
+
+/// Output of [Qqe](struct.Qqe.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct QqeOutput {
+    pub rsi_ma: f64,
+    pub fast_line: f64,
+    pub slow_line: f64,
+}
+
+impl_output!(QqeOutput, rsi_ma, fast_line, slow_line);
+
+impl From<QqeOutput> for (f64, f64, f64) {
+    fn from(o: QqeOutput) -> Self {
+        (o.rsi_ma, o.fast_line, o.slow_line)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TrailingLine {
+    factor: f64,
+    long_band: f64,
+    short_band: f64,
+    bullish: bool,
+    is_new: bool,
+}
+
+impl TrailingLine {
+    fn new(factor: f64) -> Self {
+        Self {
+            factor,
+            long_band: 0.0,
+            short_band: 0.0,
+            bullish: true,
+            is_new: true,
+        }
+    }
+
+    fn next(&mut self, prev_rsi_ma: f64, rsi_ma: f64, dar: f64) -> f64 {
+        let long_band = rsi_ma - self.factor * dar;
+        let short_band = rsi_ma + self.factor * dar;
+
+        if self.is_new {
+            self.is_new = false;
+            self.long_band = long_band;
+            self.short_band = short_band;
+        } else {
+            let (prev_long, prev_short) = (self.long_band, self.short_band);
+
+            self.long_band = if prev_rsi_ma > prev_long && rsi_ma > prev_long {
+                prev_long.max(long_band)
+            } else {
+                long_band
+            };
+            self.short_band = if prev_rsi_ma < prev_short && rsi_ma < prev_short {
+                prev_short.min(short_band)
+            } else {
+                short_band
+            };
+
+            if rsi_ma > prev_short {
+                self.bullish = true;
+            } else if rsi_ma < prev_long {
+                self.bullish = false;
+            }
+        }
+
+        if self.bullish {
+            self.long_band
+        } else {
+            self.short_band
+        }
+    }
+
+    fn peek(&self, prev_rsi_ma: f64, rsi_ma: f64, dar: f64) -> f64 {
+        self.clone().next(prev_rsi_ma, rsi_ma, dar)
+    }
+
+    fn reset(&mut self) {
+        self.long_band = 0.0;
+        self.short_band = 0.0;
+        self.bullish = true;
+        self.is_new = true;
+    }
+}
+
+impl Qqe {
+    pub fn new(
+        rsi_period: usize,
+        smoothing_period: usize,
+        fast_factor: f64,
+        slow_factor: f64,
+    ) -> Result<Self> {
+        if rsi_period == 0 {
+            return Err(TaError::invalid_parameter("rsi_period", 0.0, ">= 1"));
+        }
+        for (name, factor) in [("fast_factor", fast_factor), ("slow_factor", slow_factor)] {
+            if !factor.is_finite() || factor <= 0.0 {
+                return Err(TaError::invalid_parameter(name, factor, "> 0"));
+            }
+        }
+
+        Ok(Self {
+            rsi: Rsi::new(rsi_period)?,
+            rsi_ema: Ema::new(smoothing_period)?,
+            tr_ema: Ema::new(2 * rsi_period - 1)?,
+            dar_ema: Ema::new(2 * rsi_period - 1)?,
+            prev_rsi_ma: None,
+            fast: TrailingLine::new(fast_factor),
+            slow: TrailingLine::new(slow_factor),
+            count: 0,
+            output: None,
+            saved_count: 0,
+            saved_prev_rsi_ma: None,
+            saved_fast: TrailingLine::new(fast_factor),
+            saved_slow: TrailingLine::new(slow_factor),
+        })
+    }
+
+    fn update(&mut self, prev_rsi_ma: f64, rsi_ma: f64, dar: f64) -> QqeOutput {
+        let output = QqeOutput {
+            rsi_ma,
+            fast_line: self.fast.next(prev_rsi_ma, rsi_ma, dar),
+            slow_line: self.slow.next(prev_rsi_ma, rsi_ma, dar),
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Period for Qqe {
+    fn period(&self) -> usize {
+        self.rsi.period()
+    }
+}
+
+impl Checkpoint for Qqe {
+    type State = QqeState;
+
+    fn state(&self) -> Self::State {
+        QqeState {
+            rsi: self.rsi.state(),
+            rsi_ema: self.rsi_ema.state(),
+            tr_ema: self.tr_ema.state(),
+            dar_ema: self.dar_ema.state(),
+            previous_rsi_ma: self.prev_rsi_ma,
+            fast_factor: self.fast.factor,
+            fast_bands: (self.fast.long_band, self.fast.short_band),
+            fast_bullish: self.fast.bullish,
+            slow_factor: self.slow.factor,
+            slow_bands: (self.slow.long_band, self.slow.short_band),
+            slow_bullish: self.slow.bullish,
+            count: self.count,
+            output: self.output.clone(),
+            previous_count: self.saved_count,
+            rsi_ma_before_last: self.saved_prev_rsi_ma,
+            previous_fast_bands: (self.saved_fast.long_band, self.saved_fast.short_band),
+            previous_fast_bullish: self.saved_fast.bullish,
+            previous_slow_bands: (self.saved_slow.long_band, self.saved_slow.short_band),
+            previous_slow_bullish: self.saved_slow.bullish,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(
+            state.rsi.period,
+            state.rsi_ema.period,
+            state.fast_factor,
+            state.slow_factor,
+        )
+        .map_err(|_| TaError::InvalidState)?;
+        if state.tr_ema.period != indicator.tr_ema.period()
+            || state.dar_ema.period != indicator.dar_ema.period()
+            || state.previous_rsi_ma.is_none() != state.output.is_none()
+            || state.count > indicator.lookback()
+            || state.output.is_none() != (state.count == 0)
+            || state.previous_count > state.count
+            || state.rsi_ma_before_last.is_none() != (state.previous_count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        indicator.rsi = Rsi::from_state(state.rsi)?;
+        indicator.rsi_ema = Ema::from_state(state.rsi_ema)?;
+        indicator.tr_ema = Ema::from_state(state.tr_ema)?;
+        indicator.dar_ema = Ema::from_state(state.dar_ema)?;
+        indicator.prev_rsi_ma = state.previous_rsi_ma;
+        for (line, (long_band, short_band), bullish) in [
+            (&mut indicator.fast, state.fast_bands, state.fast_bullish),
+            (&mut indicator.slow, state.slow_bands, state.slow_bullish),
+        ] {
+            line.long_band = long_band;
+            line.short_band = short_band;
+            line.bullish = bullish;
+            line.is_new = state.output.is_none();
+        }
+        for (line, (long_band, short_band), bullish) in [
+            (
+                &mut indicator.saved_fast,
+                state.previous_fast_bands,
+                state.previous_fast_bullish,
+            ),
+            (
+                &mut indicator.saved_slow,
+                state.previous_slow_bands,
+                state.previous_slow_bullish,
+            ),
+        ] {
+            line.long_band = long_band;
+            line.short_band = short_band;
+            line.bullish = bullish;
+            line.is_new = state.previous_count == 0;
+        }
+        indicator.saved_count = state.previous_count;
+        indicator.saved_prev_rsi_ma = state.rsi_ma_before_last;
+        indicator.count = state.count;
+        indicator.output = state.output;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(Qqe, 2);
+
+impl Next<f64> for Qqe {
+    type Output = QqeOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_count = self.count;
+        self.saved_prev_rsi_ma = self.prev_rsi_ma;
+        self.saved_fast = self.fast.clone();
+        self.saved_slow = self.slow.clone();
+        if !self.is_ready() {
+            self.count += 1;
+        }
+        let rsi_ma = self.rsi_ema.next(self.rsi.next(input));
+        let prev_rsi_ma = self.prev_rsi_ma.unwrap_or(rsi_ma);
+        self.prev_rsi_ma = Some(rsi_ma);
+
+        let dar = self
+            .dar_ema
+            .next(self.tr_ema.next((rsi_ma - prev_rsi_ma).abs()));
+        self.update(prev_rsi_ma, rsi_ma, dar)
+    }
+}
+
+impl<T: Close> Next<&T> for Qqe {
+    type Output = QqeOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for Qqe {
+    fn peek(&self, input: f64) -> QqeOutput {
+        let rsi_ma = self.rsi_ema.peek(self.rsi.peek(input));
+        let prev_rsi_ma = self.prev_rsi_ma.unwrap_or(rsi_ma);
+
+        let dar = self
+            .dar_ema
+            .peek(self.tr_ema.peek((rsi_ma - prev_rsi_ma).abs()));
+
+        QqeOutput {
+            rsi_ma,
+            fast_line: self.fast.peek(prev_rsi_ma, rsi_ma, dar),
+            slow_line: self.slow.peek(prev_rsi_ma, rsi_ma, dar),
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for Qqe {
+    fn peek(&self, input: &T) -> QqeOutput {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for Qqe {
+    fn amend(&mut self, input: f64) -> QqeOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.count = self.saved_count;
+        self.fast = self.saved_fast.clone();
+        self.slow = self.saved_slow.clone();
+        if !self.is_ready() {
+            self.count += 1;
+        }
+        let rsi_ma = self.rsi_ema.amend(self.rsi.amend(input));
+        let prev_rsi_ma = self.saved_prev_rsi_ma.unwrap_or(rsi_ma);
+        self.prev_rsi_ma = Some(rsi_ma);
+
+        let dar = self
+            .dar_ema
+            .amend(self.tr_ema.amend((rsi_ma - prev_rsi_ma).abs()));
+        self.update(prev_rsi_ma, rsi_ma, dar)
+    }
+}
+
+impl<T: Close> Amend<&T> for Qqe {
+    fn amend(&mut self, input: &T) -> QqeOutput {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for Qqe {
+    fn reset(&mut self) {
+        self.rsi.reset();
+        self.rsi_ema.reset();
+        self.tr_ema.reset();
+        self.dar_ema.reset();
+        self.prev_rsi_ma = None;
+        self.fast.reset();
+        self.slow.reset();
+        self.count = 0;
+        self.output = None;
+        self.saved_count = 0;
+        self.saved_prev_rsi_ma = None;
+        self.saved_fast.reset();
+        self.saved_slow.reset();
+    }
+}
+
+impl Current for Qqe {
+    type Output = QqeOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for Qqe {
+    fn is_ready(&self) -> bool {
+        self.count >= self.lookback()
+    }
+}
+
+impl Lookback for Qqe {
+    fn lookback(&self) -> usize {
+        let rsi_ma = self.rsi.lookback() + self.rsi_ema.period() - 1;
+        // one more value for the first change of the smoothed RSI
+        rsi_ma + 1 + self.tr_ema.period() - 1 + self.dar_ema.period() - 1
+    }
+}
+
+impl Default for Qqe {
+    fn default() -> Self {
+        Self::new(14, 5, 2.618, 4.236).unwrap()
+    }
+}
+
+impl fmt::Display for Qqe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "QQE({}, {}, {}, {})",
+            self.rsi.period(),
+            self.rsi_ema.period(),
+            self.fast.factor,
+            self.slow.factor
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Qqe);
+
+    fn round(nums: (f64, f64, f64)) -> (f64, f64, f64) {
+        let n0 = (nums.0 * 1000.0).round() / 1000.0;
+        let n1 = (nums.1 * 1000.0).round() / 1000.0;
+        let n2 = (nums.2 * 1000.0).round() / 1000.0;
+        (n0, n1, n2)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Qqe::new(0, 5, 2.618, 4.236).is_err());
+        assert!(Qqe::new(14, 0, 2.618, 4.236).is_err());
+        assert!(Qqe::new(14, 5, 0.0, 4.236).is_err());
+        assert!(Qqe::new(14, 5, 2.618, -1.0).is_err());
+        assert!(Qqe::new(14, 5, f64::NAN, 4.236).is_err());
+        assert!(Qqe::new(1, 1, 1.0, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut qqe = Qqe::new(3, 2, 2.618, 4.236).unwrap();
+
+        let inputs = [10.0, 12.0, 11.0, 14.0, 13.0, 9.0, 8.0, 12.0];
+        let expected = [
+            (50.0, 50.0, 50.0),
+            (83.333, 73.637, 67.644),
+            // fast line flips to the short band
+            (72.222, 88.383, 67.644),
+            (81.852, 88.383, 67.644),
+            (71.728, 88.383, 67.644),
+            // slow line flips to the short band
+            (42.547, 72.104, 90.371),
+            (29.488, 62.704, 83.233),
+            (51.718, 62.704, 83.233),
+        ];
+
+        for (input, expected) in inputs.iter().zip(expected.iter()) {
+            assert_eq!(round(qqe.next(*input).into()), *expected);
+        }
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut qqe = Qqe::new(3, 2, 2.618, 4.236).unwrap();
+
+        assert_eq!(
+            round(qqe.next(&Bar::new().close(10)).into()),
+            (50.0, 50.0, 50.0)
+        );
+        assert_eq!(
+            round(qqe.next(&Bar::new().close(12)).into()),
+            (83.333, 73.637, 67.644)
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut qqe = Qqe::new(3, 2, 2.618, 4.236).unwrap();
+
+        qqe.next(10.0);
+        qqe.next(12.0);
+        qqe.next(11.0);
+
+        qqe.reset();
+        assert_eq!(round(qqe.next(10.0).into()), (50.0, 50.0, 50.0));
+        assert_eq!(round(qqe.next(12.0).into()), (83.333, 73.637, 67.644));
+    }
+
+    #[test]
+    fn test_default() {
+        Qqe::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let qqe = Qqe::new(14, 5, 2.618, 4.236).unwrap();
+        assert_eq!(format!("{}", qqe), "QQE(14, 5, 2.618, 4.236)");
+    }
+}
@@ -0,0 +1,573 @@
+use core::f64::consts::LN_2;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Open, Peek, Period,
+    Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Estimator used by [RangeVolatility](struct.RangeVolatility.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolatilityEstimator {
+    /// High-low range only. Assumes no drift and no opening jumps.
+    Parkinson,
+    /// High-low range and open-close move. Assumes no drift and no opening jumps.
+    GarmanKlass,
+    /// Unbiased in the presence of drift, but ignores opening jumps.
+    RogersSatchell,
+    /// Combines overnight, open-close and Rogers-Satchell variances.
+    /// Handles both drift and opening jumps.
+    YangZhang,
+}
+
+/// Range based volatility (RVOL).
+///
+/// Annualized volatility estimated from open, high, low and close prices over the last
+/// _period_ bars. Range based estimators use more information than close-to-close
+/// [historical volatility](struct.HistoricalVolatility.html), so they need fewer bars
+/// for the same accuracy. The result is a fraction, e.g. 0.25 means 25% annualized volatility.
+///
+/// # Formula
+///
+/// Per bar terms:
+///
+/// * Parkinson: ln(H/L)<sup>2</sup> / (4 * ln(2))
+/// * Garman-Klass: 0.5 * ln(H/L)<sup>2</sup> - (2 * ln(2) - 1) * ln(C/O)<sup>2</sup>
+/// * Rogers-Satchell: ln(H/C) * ln(H/O) + ln(L/C) * ln(L/O)
+///
+/// For these estimators the variance σ<sup>2</sup> is the average of the terms over the window.
+///
+/// Yang-Zhang: σ<sup>2</sup> = σ<sub>o</sub><sup>2</sup> + k * σ<sub>c</sub><sup>2</sup> + (1 - k) * σ<sub>rs</sub><sup>2</sup>
+///
+/// Where:
+///
+/// * σ<sub>o</sub><sup>2</sup> - sample variance of overnight returns ln(O<sub>t</sub> / C<sub>t-1</sub>)
+/// * σ<sub>c</sub><sup>2</sup> - sample variance of open to close returns ln(C<sub>t</sub> / O<sub>t</sub>)
+/// * σ<sub>rs</sub><sup>2</sup> - Rogers-Satchell variance
+/// * k = 0.34 / (1.34 + (n + 1) / (n - 1))
+///
+/// The overnight return of the very first bar is 0. With a single bar in the window,
+/// Yang-Zhang falls back to the Rogers-Satchell variance.
+///
+/// RVOL = √(σ<sup>2</sup> * _periods_per_year_)
+///
+/// Bars with non-positive prices contribute 0 to all terms.
+///
+/// # Parameters
+///
+/// * _period_ - number of bars (integer greater than 0). Default is 20.
+/// * _estimator_ - [VolatilityEstimator](enum.VolatilityEstimator.html). Default is Yang-Zhang.
+/// * _periods_per_year_ - number of bars in a year (must be greater than 0). Default is 252.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{RangeVolatility, VolatilityEstimator};
+/// use ta::{Next, DataItem};
+///
+/// let mut rvol = RangeVolatility::new(3, VolatilityEstimator::Parkinson, 1.0).unwrap();
+///
+/// let di = DataItem::builder()
+///             .open(10.0).high(11.0).low(9.0).close(10.5)
+///             .volume(1000.0)
+///             .build().unwrap();
+///
+/// assert_eq!((rvol.next(&di) * 10000.0).round(), 1205.0);
+/// ```
+///
+/// # Links
+///
+/// * [Volatility (finance), Wikipedia](https://en.wikipedia.org/wiki/Volatility_(finance))
+///
+#[doc(alias = "RVOL")]
+#[derive(Debug, Clone)]
+pub struct RangeVolatility {
+    period: usize,
+    estimator: VolatilityEstimator,
+    periods_per_year: f64,
+    index: usize,
+    count: usize,
+    prev_close: Option<f64>,
+    sum_range: f64,
+    sum_overnight: f64,
+    sum_overnight_sq: f64,
+    sum_open_close: f64,
+    sum_open_close_sq: f64,
+    ranges: Box<[f64]>,
+    overnights: Box<[f64]>,
+    open_closes: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: (f64, f64, f64),
+    saved_count: usize,
+    saved_prev_close: Option<f64>,
+    saved_sums: [f64; 5],
+}
+
+/// State of [RangeVolatility](struct.RangeVolatility.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeVolatilityState {
+    pub period: usize,
+    pub estimator: VolatilityEstimator,
+    pub periods_per_year: f64,
+    /// Range terms of the bars in the window, the oldest first.
+    pub ranges: Vec<f64>,
+    /// Overnight log returns of the bars in the window, the oldest first.
+    pub overnights: Vec<f64>,
+    /// Open to close log returns of the bars in the window, the oldest first.
+    pub open_closes: Vec<f64>,
+    pub sum_range: f64,
+    pub sum_overnight: f64,
+    pub sum_overnight_sq: f64,
+    pub sum_open_close: f64,
+    pub sum_open_close_sq: f64,
+    pub previous_close: Option<f64>,
+    pub output: Option<f64>,
+    /// Terms of the bar which the last input pushed out of the window, to amend it.
+    pub evicted: Option<(f64, f64, f64)>,
+    /// Close of the bar before the last one and sums before the last input, to amend it.
+    pub close_before_last: Option<f64>,
+    pub previous_sums: [f64; 5],
+}
+
+impl RangeVolatility {
+    pub fn new(
+        period: usize,
+        estimator: VolatilityEstimator,
+        periods_per_year: f64,
+    ) -> Result<Self> {
+        if period == 0 {
+            return Err(TaError::invalid_parameter("period", 0.0, ">= 1"));
+        }
+        if !periods_per_year.is_finite() || periods_per_year <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "periods_per_year",
+                periods_per_year,
+                "> 0",
+            ));
+        }
+        Ok(Self {
+            period,
+            estimator,
+            periods_per_year,
+            index: 0,
+            count: 0,
+            prev_close: None,
+            sum_range: 0.0,
+            sum_overnight: 0.0,
+            sum_overnight_sq: 0.0,
+            sum_open_close: 0.0,
+            sum_open_close_sq: 0.0,
+            ranges: vec![0.0; period].into_boxed_slice(),
+            overnights: vec![0.0; period].into_boxed_slice(),
+            open_closes: vec![0.0; period].into_boxed_slice(),
+            output: None,
+            saved_value: (0.0, 0.0, 0.0),
+            saved_count: 0,
+            saved_prev_close: None,
+            saved_sums: [0.0; 5],
+        })
+    }
+
+    pub fn estimator(&self) -> VolatilityEstimator {
+        self.estimator
+    }
+
+    fn range_term(&self, open: f64, high: f64, low: f64, close: f64) -> f64 {
+        match self.estimator {
+            VolatilityEstimator::Parkinson => {
+                let hl = (high / low).ln();
+                hl * hl / (4.0 * LN_2)
+            }
+            VolatilityEstimator::GarmanKlass => {
+                let hl = (high / low).ln();
+                let co = (close / open).ln();
+                0.5 * hl * hl - (2.0 * LN_2 - 1.0) * co * co
+            }
+            VolatilityEstimator::RogersSatchell | VolatilityEstimator::YangZhang => {
+                (high / close).ln() * (high / open).ln() + (low / close).ln() * (low / open).ln()
+            }
+        }
+    }
+
+    // Range term, overnight return and open to close return of a bar
+    fn terms<T: Open + High + Low + Close>(&self, input: &T) -> (f64, f64, f64) {
+        let (open, high, low, close) = (input.open(), input.high(), input.low(), input.close());
+
+        if open > 0.0 && high > 0.0 && low > 0.0 && close > 0.0 {
+            let overnight = match self.prev_close {
+                Some(prev_close) if prev_close > 0.0 => (open / prev_close).ln(),
+                _ => 0.0,
+            };
+            (
+                self.range_term(open, high, low, close),
+                overnight,
+                (close / open).ln(),
+            )
+        } else {
+            (0.0, 0.0, 0.0)
+        }
+    }
+
+    // Running sums once the terms of a new bar replace the oldest ones
+    fn sums_with(&self, (range, overnight, open_close): (f64, f64, f64)) -> [f64; 5] {
+        let old_overnight = self.overnights[self.index];
+        let old_open_close = self.open_closes[self.index];
+        [
+            self.sum_range + (range - self.ranges[self.index]),
+            self.sum_overnight + (overnight - old_overnight),
+            self.sum_overnight_sq + (overnight * overnight - old_overnight * old_overnight),
+            self.sum_open_close + (open_close - old_open_close),
+            self.sum_open_close_sq + (open_close * open_close - old_open_close * old_open_close),
+        ]
+    }
+
+    fn variance(&self, count: usize, sums: [f64; 5]) -> f64 {
+        let [sum_range, sum_overnight, sum_overnight_sq, sum_open_close, sum_open_close_sq] = sums;
+        let n = count as f64;
+        let range_variance = sum_range / n;
+
+        if self.estimator != VolatilityEstimator::YangZhang || count < 2 {
+            return range_variance;
+        }
+
+        let sample_variance =
+            |sum: f64, sum_sq: f64| ((sum_sq - sum * sum / n) / (n - 1.0)).max(0.0);
+        let k = 0.34 / (1.34 + (n + 1.0) / (n - 1.0));
+
+        sample_variance(sum_overnight, sum_overnight_sq)
+            + k * sample_variance(sum_open_close, sum_open_close_sq)
+            + (1.0 - k) * range_variance
+    }
+
+    fn volatility(&self, count: usize, sums: [f64; 5]) -> f64 {
+        (self.variance(count, sums).max(0.0) * self.periods_per_year).sqrt()
+    }
+}
+
+impl Period for RangeVolatility {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for RangeVolatility {
+    type State = RangeVolatilityState;
+
+    fn state(&self) -> Self::State {
+        RangeVolatilityState {
+            period: self.period,
+            estimator: self.estimator,
+            periods_per_year: self.periods_per_year,
+            ranges: ring_values(&self.ranges, self.index, self.count),
+            overnights: ring_values(&self.overnights, self.index, self.count),
+            open_closes: ring_values(&self.open_closes, self.index, self.count),
+            sum_range: self.sum_range,
+            sum_overnight: self.sum_overnight,
+            sum_overnight_sq: self.sum_overnight_sq,
+            sum_open_close: self.sum_open_close,
+            sum_open_close_sq: self.sum_open_close_sq,
+            previous_close: self.prev_close,
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            close_before_last: self.saved_prev_close,
+            previous_sums: self.saved_sums,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.ranges.is_empty()
+            || state.overnights.len() != state.ranges.len()
+            || state.open_closes.len() != state.ranges.len()
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period, state.estimator, state.periods_per_year)
+            .map_err(|_| TaError::InvalidState)?;
+        let (ranges, index) = ring_from_values(state.period, &state.ranges)?;
+        let (overnights, _) = ring_from_values(state.period, &state.overnights)?;
+        let (open_closes, _) = ring_from_values(state.period, &state.open_closes)?;
+        indicator.ranges = ranges;
+        indicator.overnights = overnights;
+        indicator.open_closes = open_closes;
+        indicator.index = index;
+        indicator.count = state.ranges.len();
+        indicator.sum_range = state.sum_range;
+        indicator.sum_overnight = state.sum_overnight;
+        indicator.sum_overnight_sq = state.sum_overnight_sq;
+        indicator.sum_open_close = state.sum_open_close;
+        indicator.sum_open_close_sq = state.sum_open_close_sq;
+        indicator.prev_close = state.previous_close;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or((0.0, 0.0, 0.0));
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_prev_close = state.close_before_last;
+        indicator.saved_sums = state.previous_sums;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(RangeVolatility, 2);
+
+impl SetPeriod for RangeVolatility {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period, self.estimator, self.periods_per_year)?;
+        Ok(())
+    }
+}
+
+impl<T: Open + High + Low + Close> Next<&T> for RangeVolatility {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_value = (
+            self.ranges[self.index],
+            self.overnights[self.index],
+            self.open_closes[self.index],
+        );
+        self.saved_count = self.count;
+        self.saved_prev_close = self.prev_close;
+        self.saved_sums = [
+            self.sum_range,
+            self.sum_overnight,
+            self.sum_overnight_sq,
+            self.sum_open_close,
+            self.sum_open_close_sq,
+        ];
+        let terms = self.terms(input);
+        let sums = self.sums_with(terms);
+        self.prev_close = Some(input.close());
+
+        let (range, overnight, open_close) = terms;
+        [
+            self.sum_range,
+            self.sum_overnight,
+            self.sum_overnight_sq,
+            self.sum_open_close,
+            self.sum_open_close_sq,
+        ] = sums;
+        self.ranges[self.index] = range;
+        self.overnights[self.index] = overnight;
+        self.open_closes[self.index] = open_close;
+
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < self.period {
+            self.count += 1;
+        }
+
+        let output = self.volatility(self.count, sums);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Open + High + Low + Close> Peek<&T> for RangeVolatility {
+    fn peek(&self, input: &T) -> f64 {
+        let count = (self.count + 1).min(self.period);
+        self.volatility(count, self.sums_with(self.terms(input)))
+    }
+}
+
+impl<T: Open + High + Low + Close> Amend<&T> for RangeVolatility {
+    fn amend(&mut self, input: &T) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        (
+            self.ranges[self.index],
+            self.overnights[self.index],
+            self.open_closes[self.index],
+        ) = self.saved_value;
+        self.count = self.saved_count;
+        self.prev_close = self.saved_prev_close;
+        [
+            self.sum_range,
+            self.sum_overnight,
+            self.sum_overnight_sq,
+            self.sum_open_close,
+            self.sum_open_close_sq,
+        ] = self.saved_sums;
+        self.next(input)
+    }
+}
+
+impl Reset for RangeVolatility {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.prev_close = None;
+        self.sum_range = 0.0;
+        self.sum_overnight = 0.0;
+        self.sum_overnight_sq = 0.0;
+        self.sum_open_close = 0.0;
+        self.sum_open_close_sq = 0.0;
+        for i in 0..self.period {
+            self.ranges[i] = 0.0;
+            self.overnights[i] = 0.0;
+            self.open_closes[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = (0.0, 0.0, 0.0);
+        self.saved_count = 0;
+        self.saved_prev_close = None;
+        self.saved_sums = [0.0; 5];
+    }
+}
+
+impl Current for RangeVolatility {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for RangeVolatility {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for RangeVolatility {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for RangeVolatility {
+    fn default() -> Self {
+        Self::new(20, VolatilityEstimator::YangZhang, 252.0).unwrap()
+    }
+}
+
+impl fmt::Display for RangeVolatility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "RVOL({}, {:?}, {})",
+            self.period, self.estimator, self.periods_per_year
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn bars() -> Vec<Bar> {
+        vec![
+            Bar::new().open(10).high(11).low(9).close(10.5),
+            Bar::new().open(10.5).high(12).low(10).close(11.5),
+            Bar::new().open(11.4).high(11.8).low(10.6).close(11),
+            Bar::new().open(11).high(11.5).low(10).close(10.2),
+        ]
+    }
+
+    fn run(estimator: VolatilityEstimator) -> Vec<f64> {
+        let mut rvol = RangeVolatility::new(3, estimator, 1.0).unwrap();
+        bars()
+            .iter()
+            .map(|bar| (rvol.next(bar) * 10000.0).round() / 10000.0)
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let e = VolatilityEstimator::Parkinson;
+        assert!(RangeVolatility::new(0, e, 252.0).is_err());
+        assert!(RangeVolatility::new(3, e, 0.0).is_err());
+        assert!(RangeVolatility::new(3, e, f64::INFINITY).is_err());
+        assert!(RangeVolatility::new(1, e, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next_parkinson() {
+        assert_eq!(
+            run(VolatilityEstimator::Parkinson),
+            vec![0.1205, 0.1151, 0.1011, 0.0879]
+        );
+    }
+
+    #[test]
+    fn test_next_garman_klass() {
+        assert_eq!(
+            run(VolatilityEstimator::GarmanKlass),
+            vec![0.1386, 0.1277, 0.1124, 0.0935]
+        );
+    }
+
+    #[test]
+    fn test_next_rogers_satchell() {
+        assert_eq!(
+            run(VolatilityEstimator::RogersSatchell),
+            vec![0.1438, 0.1288, 0.113, 0.091]
+        );
+    }
+
+    #[test]
+    fn test_next_yang_zhang() {
+        assert_eq!(
+            run(VolatilityEstimator::YangZhang),
+            vec![0.1438, 0.1239, 0.1092, 0.0907]
+        );
+    }
+
+    #[test]
+    fn test_next_annualized() {
+        let mut rvol = RangeVolatility::new(3, VolatilityEstimator::Parkinson, 252.0).unwrap();
+        let bar = Bar::new().open(10).high(11).low(9).close(10.5);
+
+        assert_eq!(round(rvol.next(&bar)), 1.913);
+    }
+
+    #[test]
+    fn test_next_non_positive_price() {
+        let mut rvol = RangeVolatility::new(3, VolatilityEstimator::YangZhang, 1.0).unwrap();
+
+        assert_eq!(rvol.next(&Bar::new().open(0).high(1).low(0).close(1)), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rvol = RangeVolatility::new(3, VolatilityEstimator::YangZhang, 1.0).unwrap();
+
+        for bar in bars().iter() {
+            rvol.next(bar);
+        }
+
+        rvol.reset();
+        let first = (rvol.next(&bars()[0]) * 10000.0).round() / 10000.0;
+        assert_eq!(first, 0.1438);
+    }
+
+    #[test]
+    fn test_default() {
+        RangeVolatility::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let rvol = RangeVolatility::new(20, VolatilityEstimator::Parkinson, 252.0).unwrap();
+        assert_eq!(format!("{}", rvol), "RVOL(20, Parkinson, 252)");
+    }
+}
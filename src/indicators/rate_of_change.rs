@@ -1,7 +1,10 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::traits::{Close, Next, Period, Reset};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::traits::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -39,24 +42,43 @@ use serde::{Deserialize, Serialize};
 /// * [Rate of Change, Wikipedia](https://en.wikipedia.org/wiki/Momentum_(technical_analysis))
 ///
 #[doc(alias = "ROC")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct RateOfChange {
     period: usize,
     index: usize,
     count: usize,
     deque: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+}
+
+/// State of [RateOfChange](struct.RateOfChange.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateOfChangeState {
+    pub period: usize,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<f64>,
+    /// Last output, `None` before the first input.
+    pub output: Option<f64>,
+    /// Input which the last one pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
 }
 
 impl RateOfChange {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
             _ => Ok(Self {
                 period,
                 index: 0,
                 count: 0,
                 deque: vec![0.0; period].into_boxed_slice(),
+                output: None,
+                saved_value: 0.0,
+                saved_count: 0,
             }),
         }
     }
@@ -68,10 +90,56 @@ impl Period for RateOfChange {
     }
 }
 
+impl Checkpoint for RateOfChange {
+    type State = RateOfChangeState;
+
+    fn state(&self) -> Self::State {
+        RateOfChangeState {
+            period: self.period,
+            inputs: ring_values(&self.deque, self.index, self.count),
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count >= self.period)
+                .then_some(self.saved_value),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.inputs.is_empty()
+            || (state.evicted.is_some() && state.inputs.len() != state.period)
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let (deque, index) = ring_from_values(state.period, &state.inputs)?;
+        indicator.deque = deque;
+        indicator.index = index;
+        indicator.count = state.inputs.len();
+        indicator.output = state.output;
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            // A full window takes the oldest input from the next slot
+            Some(_) => indicator.count + 1,
+            None => indicator.count.saturating_sub(1),
+        };
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(RateOfChange, 2);
+
+impl SetPeriod for RateOfChange {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
 impl Next<f64> for RateOfChange {
     type Output = f64;
 
     fn next(&mut self, input: f64) -> f64 {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
         let previous = if self.count > self.period {
             self.deque[self.index]
         } else {
@@ -90,7 +158,9 @@ impl Next<f64> for RateOfChange {
             0
         };
 
-        (input - previous) / previous * 100.0
+        let output = (input - previous) / previous * 100.0;
+        self.output = Some(output);
+        output
     }
 }
 
@@ -102,6 +172,26 @@ impl<T: Close> Next<&T> for RateOfChange {
     }
 }
 
+impl Peek<f64> for RateOfChange {
+    fn peek(&self, input: f64) -> f64 {
+        let previous = if self.count > self.period {
+            self.deque[self.index]
+        } else if self.count == 0 {
+            input
+        } else {
+            self.deque[0]
+        };
+
+        (input - previous) / previous * 100.0
+    }
+}
+
+impl<T: Close> Peek<&T> for RateOfChange {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
 impl Default for RateOfChange {
     fn default() -> Self {
         Self::new(9).unwrap()
@@ -114,6 +204,24 @@ impl fmt::Display for RateOfChange {
     }
 }
 
+impl Amend<f64> for RateOfChange {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for RateOfChange {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
 impl Reset for RateOfChange {
     fn reset(&mut self) {
         self.index = 0;
@@ -121,6 +229,29 @@ impl Reset for RateOfChange {
         for i in 0..self.period {
             self.deque[i] = 0.0;
         }
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+    }
+}
+
+impl Current for RateOfChange {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for RateOfChange {
+    fn is_ready(&self) -> bool {
+        self.count > self.period
+    }
+}
+
+impl Lookback for RateOfChange {
+    fn lookback(&self) -> usize {
+        self.period + 1
     }
 }
 
@@ -128,6 +259,7 @@ impl Reset for RateOfChange {
 mod tests {
     use super::*;
     use crate::test_helper::*;
+    use crate::TryNext;
 
     test_indicator!(RateOfChange);
 
@@ -176,4 +308,12 @@ mod tests {
         assert_eq!(round(roc.next(10.4)), 4.0);
         assert_eq!(round(roc.next(10.57)), 5.7);
     }
+
+    #[test]
+    fn test_is_ready() {
+        let mut roc = RateOfChange::new(2).unwrap();
+        assert_eq!(roc.try_next(10.0), None);
+        assert_eq!(roc.try_next(11.0), None);
+        assert_eq!(roc.try_next(12.0).map(round), Some(20.0));
+    }
 }
@@ -1,9 +1,12 @@
-use std::fmt;
+use core::fmt;
 
-use crate::errors::Result;
-use crate::indicators::SimpleMovingAverage as Sma;
-use crate::indicators::SmoothedSimpleMovingAverage as Ssma;
-use crate::{Close, Next, Period, Reset};
+use crate::errors::{Result, TaError};
+use crate::indicators::generic::SmoothedSimpleMovingAverage as Ssma;
+use crate::indicators::SmoothedSimpleMovingAverageState;
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Numeric, Peek, Period, Reset,
+    SetPeriod,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -70,54 +73,69 @@ use serde::{Deserialize, Serialize};
 /// * [RSI (Investopedia)](http://www.investopedia.com/terms/r/rsi.asp)
 ///
 #[doc(alias = "RSI")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct RelativeStrengthIndex {
+pub struct RelativeStrengthIndex<N = f64> {
     period: usize,
-    up_ma_indicator: Ssma,
-    down_ma_indicator: Ssma,
-    prev_val: f64,
+    up_ma_indicator: Ssma<N>,
+    down_ma_indicator: Ssma<N>,
+    prev_val: N,
     is_new: bool,
+    output: Option<N>,
+    // Previous value of the last input, restored by `amend`, `None` if it was the first input
+    saved_prev_val: Option<N>,
+}
+
+/// State of [RelativeStrengthIndex](generic/struct.RelativeStrengthIndex.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelativeStrengthIndexState<N = f64> {
+    pub period: usize,
+    /// Average of the gains.
+    pub up: SmoothedSimpleMovingAverageState<N>,
+    /// Average of the losses.
+    pub down: SmoothedSimpleMovingAverageState<N>,
+    /// Last input, `None` before the first one.
+    pub last: Option<N>,
+    /// Input before the last one, to amend the last one.
+    pub previous: Option<N>,
 }
 
-impl RelativeStrengthIndex {
+impl<N: Numeric> RelativeStrengthIndex<N> {
     pub fn new(period: usize) -> Result<Self> {
         Ok(Self {
             period,
             up_ma_indicator: Ssma::new(period)?,
             down_ma_indicator: Ssma::new(period)?,
-            prev_val: 0.0,
+            prev_val: N::zero(),
             is_new: true,
+            output: None,
+            saved_prev_val: None,
         })
     }
-}
-
-impl Period for RelativeStrengthIndex {
-    fn period(&self) -> usize {
-        self.period
-    }
-}
 
-impl Next<f64> for RelativeStrengthIndex {
-    type Output = f64;
-
-    fn next(&mut self, input: f64) -> Self::Output {
+    fn next_value(&mut self, input: N) -> N {
+        self.saved_prev_val = if self.is_new {
+            None
+        } else {
+            Some(self.prev_val)
+        };
         let (up_ma, down_ma) = match self.is_new {
             true => {
                 self.is_new = false;
                 self.prev_val = input;
-                let up_ma = self.up_ma_indicator.next(0.0);
-                let down_ma = self.down_ma_indicator.next(0.0);
+                let up_ma = self.up_ma_indicator.next_value(N::zero());
+                let down_ma = self.down_ma_indicator.next_value(N::zero());
                 (up_ma, down_ma)
             }
             false => {
                 let (up_ma, down_ma) = if input > self.prev_val {
-                    let up_ma = self.up_ma_indicator.next(input - self.prev_val);
-                    let down_ma = self.down_ma_indicator.next(0.0);
+                    let up_ma = self.up_ma_indicator.next_value(input - self.prev_val);
+                    let down_ma = self.down_ma_indicator.next_value(N::zero());
                     (up_ma, down_ma)
                 } else {
-                    let up_ma = self.up_ma_indicator.next(0.0);
-                    let down_ma = self.down_ma_indicator.next(self.prev_val - input);
+                    let up_ma = self.up_ma_indicator.next_value(N::zero());
+                    let down_ma = self.down_ma_indicator.next_value(self.prev_val - input);
                     (up_ma, down_ma)
                 };
                 // 2. 状态更新
@@ -127,39 +145,171 @@ impl Next<f64> for RelativeStrengthIndex {
             }
         };
 
-        // 避免除零（极端情况：MA 结果均为 0，返回 50.0 中性值）
-        if up_ma + down_ma < 1e-9 {
-            return 50.0;
+        let output = relative_strength(up_ma, down_ma);
+        self.output = Some(output);
+        output
+    }
+
+    fn peek_value(&self, input: N) -> N {
+        let (up, down) = if self.is_new {
+            (N::zero(), N::zero())
+        } else if input > self.prev_val {
+            (input - self.prev_val, N::zero())
+        } else {
+            (N::zero(), self.prev_val - input)
+        };
+        let up_ma = self.up_ma_indicator.peek_value(up);
+        let down_ma = self.down_ma_indicator.peek_value(down);
+
+        relative_strength(up_ma, down_ma)
+    }
+
+    fn amend_value(&mut self, input: N) -> N {
+        if self.output.is_none() {
+            return self.next_value(input);
         }
+        let (up, down) = match self.saved_prev_val {
+            Some(prev_val) if input > prev_val => (input - prev_val, N::zero()),
+            Some(prev_val) => (N::zero(), prev_val - input),
+            None => (N::zero(), N::zero()),
+        };
+        self.prev_val = input;
+        let up_ma = self.up_ma_indicator.amend_value(up);
+        let down_ma = self.down_ma_indicator.amend_value(down);
+
+        let output = relative_strength(up_ma, down_ma);
+        self.output = Some(output);
+        output
+    }
+}
+
+fn relative_strength<N: Numeric>(up_ma: N, down_ma: N) -> N {
+    // 避免除零（极端情况：MA 结果均为 0，返回 50.0 中性值）
+    if up_ma + down_ma < N::from_param(1e-9) {
+        N::from_count(50)
+    } else {
+        N::from_count(100) * up_ma / (up_ma + down_ma)
+    }
+}
 
-        100.0 * up_ma / (up_ma + down_ma)
+impl<N: Numeric> Period for RelativeStrengthIndex<N> {
+    fn period(&self) -> usize {
+        self.period
     }
 }
 
-impl<T: Close> Next<&T> for RelativeStrengthIndex {
-    type Output = f64;
+impl<N: Numeric> Checkpoint for RelativeStrengthIndex<N> {
+    type State = RelativeStrengthIndexState<N>;
+
+    fn state(&self) -> Self::State {
+        RelativeStrengthIndexState {
+            period: self.period,
+            up: self.up_ma_indicator.state(),
+            down: self.down_ma_indicator.state(),
+            last: (!self.is_new).then_some(self.prev_val),
+            previous: self.saved_prev_val,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.up.period != state.period
+            || state.down.period != state.period
+            || state.up.count != state.down.count
+            || state.last.is_some() != (state.up.count > 0)
+            || state.previous.is_some() != (state.up.count > 1)
+        {
+            return Err(TaError::InvalidState);
+        }
+        let up_ma_indicator = Ssma::from_state(state.up)?;
+        let down_ma_indicator = Ssma::from_state(state.down)?;
+        let output = up_ma_indicator
+            .current()
+            .zip(down_ma_indicator.current())
+            .map(|(up_ma, down_ma)| relative_strength(up_ma, down_ma));
+
+        Ok(Self {
+            period: state.period,
+            up_ma_indicator,
+            down_ma_indicator,
+            prev_val: state.last.unwrap_or(N::zero()),
+            is_new: state.last.is_none(),
+            output,
+            saved_prev_val: state.previous,
+        })
+    }
+}
+
+impl_versioned_serde!(RelativeStrengthIndex<N>, 1);
+
+impl<N: Numeric> SetPeriod for RelativeStrengthIndex<N> {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        self.up_ma_indicator.set_period(period)?;
+        self.down_ma_indicator.set_period(period)?;
+        self.period = period;
+        Ok(())
+    }
+}
+
+impl_numeric_inputs!(RelativeStrengthIndex);
+
+impl<N: Numeric, T: Close<N>> Next<&T> for RelativeStrengthIndex<N> {
+    type Output = N;
 
     fn next(&mut self, input: &T) -> Self::Output {
-        self.next(input.close())
+        self.next_value(input.close())
+    }
+}
+
+impl<N: Numeric, T: Close<N>> Peek<&T> for RelativeStrengthIndex<N> {
+    fn peek(&self, input: &T) -> N {
+        self.peek_value(input.close())
     }
 }
 
-impl Reset for RelativeStrengthIndex {
+impl<N: Numeric, T: Close<N>> Amend<&T> for RelativeStrengthIndex<N> {
+    fn amend(&mut self, input: &T) -> N {
+        self.amend_value(input.close())
+    }
+}
+
+impl<N: Numeric> Reset for RelativeStrengthIndex<N> {
     fn reset(&mut self) {
         self.is_new = true;
-        self.prev_val = 0.0;
+        self.prev_val = N::zero();
         self.up_ma_indicator.reset();
         self.down_ma_indicator.reset();
+        self.output = None;
+        self.saved_prev_val = None;
+    }
+}
+
+impl<N: Numeric> Current for RelativeStrengthIndex<N> {
+    type Output = N;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<N: Numeric> IsReady for RelativeStrengthIndex<N> {
+    fn is_ready(&self) -> bool {
+        self.up_ma_indicator.is_ready()
     }
 }
 
-impl Default for RelativeStrengthIndex {
+impl<N: Numeric> Lookback for RelativeStrengthIndex<N> {
+    fn lookback(&self) -> usize {
+        self.up_ma_indicator.lookback()
+    }
+}
+
+impl<N: Numeric> Default for RelativeStrengthIndex<N> {
     fn default() -> Self {
         Self::new(14).unwrap()
     }
 }
 
-impl fmt::Display for RelativeStrengthIndex {
+impl<N: Numeric> fmt::Display for RelativeStrengthIndex<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "RSI({})", self.period)
     }
@@ -168,6 +318,7 @@ impl fmt::Display for RelativeStrengthIndex {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::RelativeStrengthIndex;
     use crate::test_helper::*;
 
     test_indicator!(RelativeStrengthIndex);
@@ -187,6 +338,18 @@ mod tests {
         assert_eq!(rsi.next(9.5).round(), 16.0);
     }
 
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_next_decimal() {
+        use crate::indicators::generic::RelativeStrengthIndex;
+        use rust_decimal::Decimal;
+
+        let mut rsi = RelativeStrengthIndex::<Decimal>::new(2).unwrap();
+        assert_eq!(rsi.next(Decimal::new(10, 1)), Decimal::new(50, 0));
+        assert_eq!(rsi.next(Decimal::new(11, 1)), Decimal::new(100, 0));
+        assert_eq!(rsi.next(Decimal::new(105, 2)), Decimal::new(50, 0));
+    }
+
     #[test]
     fn test_reset() {
         let mut rsi = RelativeStrengthIndex::new(3).unwrap();
@@ -0,0 +1,494 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::indicators::{SimpleMovingAverage as Sma, SimpleMovingAverageState};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Open, Peek, Period,
+    Reset,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Relative Vigor Index (RVI).
+///
+/// Measures the conviction of a price move by comparing the close-open change to the
+/// high-low range, both smoothed with a symmetric weighted average.
+///
+/// # Formula
+///
+/// Numerator = SWMA(Close - Open)
+///
+/// Denominator = SWMA(High - Low)
+///
+/// RVI = SMA(Numerator, _period_) / SMA(Denominator, _period_)
+///
+/// Signal = TWMA(RVI, _signal_period_)
+///
+/// Where:
+///
+/// * _SWMA_ - symmetric weighted average of the last 4 bars with weights 1, 2, 2, 1
+/// * _TWMA_ - triangular weighted average, a generalization of SWMA to any number of bars
+///   (for 4 bars it is the same as SWMA)
+///
+/// Until enough bars are available, the first value is repeated to fill the weighted windows.
+/// When the denominator is 0, RVI is 0.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods for the SMA (integer greater than 0). Default is 10.
+/// * _signal_period_ - number of RVI values in the signal average (integer greater than 0). Default is 4.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RelativeVigorIndex;
+/// use ta::{Next, DataItem};
+///
+/// let mut rvi = RelativeVigorIndex::new(2, 4).unwrap();
+///
+/// let di = DataItem::builder()
+///             .high(12.0).low(8.0).close(11.0).open(9.0)
+///             .volume(1000.0)
+///             .build().unwrap();
+///
+/// let out = rvi.next(&di);
+/// assert_eq!(out.rvi, 0.5);
+/// assert_eq!(out.signal, 0.5);
+/// ```
+///
+/// # Links
+///
+/// * [Relative Vigor Index, Investopedia](https://www.investopedia.com/terms/r/relative_vigor_index.asp)
+///
+#[doc(alias = "RVI")]
+#[derive(Debug, Clone)]
+pub struct RelativeVigorIndex {
+    signal_period: usize,
+    index: usize,
+    is_new: bool,
+    close_open: [f64; 4],
+    high_low: [f64; 4],
+    numerator: Sma,
+    denominator: Sma,
+    rvi: Box<[f64]>,
+    count: usize,
+    output: Option<RelativeVigorIndexOutput>,
+    // State before the last input, restored by `amend`
+    saved_count: usize,
+    saved_close_open: [f64; 4],
+    saved_high_low: [f64; 4],
+    saved_rvi: f64,
+}
+
+/// State of [RelativeVigorIndex](struct.RelativeVigorIndex.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelativeVigorIndexState {
+    pub signal_period: usize,
+    /// Number of inputs, up to the lookback.
+    pub count: usize,
+    /// Close minus open of the last 4 bars, the oldest first.
+    pub close_open: [f64; 4],
+    /// High minus low of the last 4 bars, the oldest first.
+    pub high_low: [f64; 4],
+    pub numerator: SimpleMovingAverageState,
+    pub denominator: SimpleMovingAverageState,
+    /// RVI values in the window of the signal, the oldest first.
+    pub rvi: Vec<f64>,
+    pub output: Option<RelativeVigorIndexOutput>,
+    /// Count and differences of the last 4 bars before the last input, to amend it.
+    pub previous_count: usize,
+    pub previous_close_open: [f64; 4],
+    pub previous_high_low: [f64; 4],
+    /// RVI value which the last input pushed out of the window of the signal, to amend it.
+    pub evicted: Option<f64>,
+}
+
+/// Output of [RelativeVigorIndex](struct.RelativeVigorIndex.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelativeVigorIndexOutput {
+    pub rvi: f64,
+    pub signal: f64,
+}
+
+impl_output!(RelativeVigorIndexOutput, rvi, signal);
+
+impl From<RelativeVigorIndexOutput> for (f64, f64) {
+    fn from(o: RelativeVigorIndexOutput) -> Self {
+        (o.rvi, o.signal)
+    }
+}
+
+// Triangular weighted average of a window of `n` values. Weights are symmetric, so the order of
+// the values in a ring buffer does not matter as long as `value` walks the window contiguously.
+fn triangular_average(n: usize, value: impl Fn(usize) -> f64) -> f64 {
+    let mut sum = 0.0;
+    let mut weights = 0.0;
+
+    for age in 0..n {
+        let weight = (age + 1).min(n - age) as f64;
+        sum += weight * value(age);
+        weights += weight;
+    }
+
+    sum / weights
+}
+
+impl RelativeVigorIndex {
+    pub fn new(period: usize, signal_period: usize) -> Result<Self> {
+        if signal_period == 0 {
+            return Err(TaError::invalid_parameter("signal_period", 0.0, ">= 1"));
+        }
+
+        Ok(Self {
+            signal_period,
+            index: 0,
+            is_new: true,
+            close_open: [0.0; 4],
+            high_low: [0.0; 4],
+            numerator: Sma::new(period)?,
+            denominator: Sma::new(period)?,
+            rvi: vec![0.0; signal_period].into_boxed_slice(),
+            count: 0,
+            output: None,
+            saved_count: 0,
+            saved_close_open: [0.0; 4],
+            saved_high_low: [0.0; 4],
+            saved_rvi: 0.0,
+        })
+    }
+
+    // Triangular averages of the differences of the last 4 bars, once the bar is in
+    fn push<T: Open + High + Low + Close>(&mut self, input: &T) -> (f64, f64) {
+        if !self.is_ready() {
+            self.count += 1;
+        }
+        let close_open = input.close() - input.open();
+        let high_low = input.high() - input.low();
+
+        if self.is_new {
+            self.close_open = [close_open; 4];
+            self.high_low = [high_low; 4];
+        } else {
+            self.close_open.rotate_left(1);
+            self.high_low.rotate_left(1);
+            self.close_open[3] = close_open;
+            self.high_low[3] = high_low;
+        }
+
+        (
+            triangular_average(4, |age| self.close_open[age]),
+            triangular_average(4, |age| self.high_low[age]),
+        )
+    }
+
+    fn update(&mut self, numerator: f64, denominator: f64) -> RelativeVigorIndexOutput {
+        let rvi = if denominator != 0.0 {
+            numerator / denominator
+        } else {
+            0.0
+        };
+
+        if self.is_new {
+            self.is_new = false;
+            for value in self.rvi.iter_mut() {
+                *value = rvi;
+            }
+        } else {
+            self.rvi[self.index] = rvi;
+        }
+        self.index = if self.index + 1 < self.signal_period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        let output = RelativeVigorIndexOutput {
+            rvi,
+            signal: triangular_average(self.signal_period, |age| {
+                self.rvi[(self.index + age) % self.signal_period]
+            }),
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Period for RelativeVigorIndex {
+    fn period(&self) -> usize {
+        self.numerator.period()
+    }
+}
+
+impl Checkpoint for RelativeVigorIndex {
+    type State = RelativeVigorIndexState;
+
+    fn state(&self) -> Self::State {
+        RelativeVigorIndexState {
+            signal_period: self.signal_period,
+            count: self.count,
+            close_open: self.close_open,
+            high_low: self.high_low,
+            numerator: self.numerator.state(),
+            denominator: self.denominator.state(),
+            rvi: if self.is_new {
+                Vec::new()
+            } else {
+                ring_values(&self.rvi, self.index, self.signal_period)
+            },
+            output: self.output.clone(),
+            previous_count: self.saved_count,
+            previous_close_open: self.saved_close_open,
+            previous_high_low: self.saved_high_low,
+            evicted: (self.saved_count > 0).then_some(self.saved_rvi),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(state.numerator.period, state.signal_period)
+            .map_err(|_| TaError::InvalidState)?;
+        let is_new = state.count == 0;
+        if state.count > indicator.lookback()
+            || state.denominator.period != state.numerator.period
+            || state.rvi.len() != if is_new { 0 } else { state.signal_period }
+            || state.output.is_some() == is_new
+            || state.previous_count > state.count
+            || state.evicted.is_some() != (state.previous_count > 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        if !is_new {
+            let (rvi, index) = ring_from_values(state.signal_period, &state.rvi)?;
+            indicator.rvi = rvi;
+            indicator.index = index;
+        }
+        indicator.is_new = is_new;
+        indicator.count = state.count;
+        indicator.close_open = state.close_open;
+        indicator.high_low = state.high_low;
+        indicator.numerator = Sma::from_state(state.numerator)?;
+        indicator.denominator = Sma::from_state(state.denominator)?;
+        indicator.output = state.output;
+        indicator.saved_count = state.previous_count;
+        indicator.saved_close_open = state.previous_close_open;
+        indicator.saved_high_low = state.previous_high_low;
+        indicator.saved_rvi = state.evicted.unwrap_or(0.0);
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(RelativeVigorIndex, 2);
+
+impl<T: Open + High + Low + Close> Next<&T> for RelativeVigorIndex {
+    type Output = RelativeVigorIndexOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_count = self.count;
+        self.saved_close_open = self.close_open;
+        self.saved_high_low = self.high_low;
+        self.saved_rvi = self.rvi[self.index];
+        let (close_open, high_low) = self.push(input);
+        let numerator = self.numerator.next(close_open);
+        let denominator = self.denominator.next(high_low);
+        self.update(numerator, denominator)
+    }
+}
+
+impl<T: Open + High + Low + Close> Peek<&T> for RelativeVigorIndex {
+    fn peek(&self, input: &T) -> RelativeVigorIndexOutput {
+        let close_open = input.close() - input.open();
+        let high_low = input.high() - input.low();
+        // The last 4 bars with the input in place of the oldest one
+        let window = |values: [f64; 4], input: f64| {
+            move |age: usize| match age {
+                3 => input,
+                _ if self.is_new => input,
+                _ => values[age + 1],
+            }
+        };
+
+        let numerator = self
+            .numerator
+            .peek(triangular_average(4, window(self.close_open, close_open)));
+        let denominator = self
+            .denominator
+            .peek(triangular_average(4, window(self.high_low, high_low)));
+
+        let rvi = if denominator != 0.0 {
+            numerator / denominator
+        } else {
+            0.0
+        };
+
+        let n = self.signal_period;
+        let start = self.index + 1;
+        RelativeVigorIndexOutput {
+            rvi,
+            signal: triangular_average(n, |age| {
+                let slot = (start + age) % n;
+                if self.is_new || slot == self.index {
+                    rvi
+                } else {
+                    self.rvi[slot]
+                }
+            }),
+        }
+    }
+}
+
+impl<T: Open + High + Low + Close> Amend<&T> for RelativeVigorIndex {
+    fn amend(&mut self, input: &T) -> RelativeVigorIndexOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.signal_period - 1) % self.signal_period;
+        self.rvi[self.index] = self.saved_rvi;
+        self.is_new = self.saved_count == 0;
+        self.count = self.saved_count;
+        self.close_open = self.saved_close_open;
+        self.high_low = self.saved_high_low;
+        let (close_open, high_low) = self.push(input);
+        let numerator = self.numerator.amend(close_open);
+        let denominator = self.denominator.amend(high_low);
+        self.update(numerator, denominator)
+    }
+}
+
+impl Reset for RelativeVigorIndex {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.is_new = true;
+        self.close_open = [0.0; 4];
+        self.high_low = [0.0; 4];
+        self.numerator.reset();
+        self.denominator.reset();
+        for i in 0..self.signal_period {
+            self.rvi[i] = 0.0;
+        }
+        self.count = 0;
+        self.output = None;
+        self.saved_count = 0;
+        self.saved_close_open = [0.0; 4];
+        self.saved_high_low = [0.0; 4];
+        self.saved_rvi = 0.0;
+    }
+}
+
+impl Current for RelativeVigorIndex {
+    type Output = RelativeVigorIndexOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for RelativeVigorIndex {
+    fn is_ready(&self) -> bool {
+        self.count >= self.lookback()
+    }
+}
+
+impl Lookback for RelativeVigorIndex {
+    fn lookback(&self) -> usize {
+        // the first full average of 4 bars needs 3 more bars
+        3 + self.numerator.lookback() + self.signal_period - 1
+    }
+}
+
+impl Default for RelativeVigorIndex {
+    fn default() -> Self {
+        Self::new(10, 4).unwrap()
+    }
+}
+
+impl fmt::Display for RelativeVigorIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "RVI({}, {})",
+            self.numerator.period(),
+            self.signal_period
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn round(nums: (f64, f64)) -> (f64, f64) {
+        let n0 = (nums.0 * 1000.0).round() / 1000.0;
+        let n1 = (nums.1 * 1000.0).round() / 1000.0;
+        (n0, n1)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(RelativeVigorIndex::new(0, 4).is_err());
+        assert!(RelativeVigorIndex::new(10, 0).is_err());
+        assert!(RelativeVigorIndex::new(1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut rvi = RelativeVigorIndex::new(2, 4).unwrap();
+
+        let bars = [
+            Bar::new().open(9).high(12).low(8).close(11),
+            Bar::new().open(11).high(11).low(9).close(9),
+            Bar::new().open(9).high(13).low(9).close(12),
+            Bar::new().open(12).high(14).low(10).close(11),
+            Bar::new().open(11).high(12).low(10).close(12),
+        ];
+        let expected = [
+            (0.5, 0.5),
+            (0.435, 0.489),
+            (0.31, 0.447),
+            (0.2, 0.365),
+            (0.15, 0.267),
+        ];
+
+        for (bar, expected) in bars.iter().zip(expected.iter()) {
+            assert_eq!(round(rvi.next(bar).into()), *expected);
+        }
+    }
+
+    #[test]
+    fn test_next_flat_bars() {
+        let mut rvi = RelativeVigorIndex::new(2, 4).unwrap();
+
+        let bar = Bar::new().open(5).high(5).low(5).close(5);
+        assert_eq!(round(rvi.next(&bar).into()), (0.0, 0.0));
+        assert_eq!(round(rvi.next(&bar).into()), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rvi = RelativeVigorIndex::new(2, 4).unwrap();
+
+        let bar1 = Bar::new().open(9).high(12).low(8).close(11);
+        let bar2 = Bar::new().open(11).high(11).low(9).close(9);
+
+        rvi.next(&bar1);
+        rvi.next(&bar2);
+
+        rvi.reset();
+        assert_eq!(round(rvi.next(&bar1).into()), (0.5, 0.5));
+        assert_eq!(round(rvi.next(&bar2).into()), (0.435, 0.489));
+    }
+
+    #[test]
+    fn test_default() {
+        RelativeVigorIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let rvi = RelativeVigorIndex::new(10, 4).unwrap();
+        assert_eq!(format!("{}", rvi), "RVI(10, 4)");
+    }
+}
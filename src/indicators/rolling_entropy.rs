@@ -0,0 +1,480 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling Shannon entropy of returns (ENTROPY).
+///
+/// Sorts the returns of the last _period_ bars into a histogram of _bins_ equally wide bins
+/// covering -_range_..._range_, and measures how evenly they are spread. Returns outside the
+/// range go into the first or the last bin. A low entropy means that most returns look alike,
+/// e.g. in a steady trend, a high entropy means that the market is noisy.
+///
+/// The histogram is updated incrementally as returns enter and leave the window, so every
+/// update takes constant time regardless of the period.
+///
+/// The entropy is normalized by its maximum, so the indicator returns output in the range of
+/// 0..1. It is 0 until the second input, as there is no return yet.
+///
+/// # Formula
+///
+/// ENTROPY = -Σ p<sub>i</sub> * log(p<sub>i</sub>) / log(_bins_)
+///
+/// Where:
+///
+/// * _p<sub>i</sub>_ - share of the returns in the window that fall into bin _i_
+/// * _return_ - Close / Close<sub>t-1</sub> - 1
+///
+/// # Parameters
+///
+/// * _period_ - number of returns in the window (integer greater than 0). Default is 20.
+/// * _bins_ - number of bins (integer greater than 1). Default is 10.
+/// * _range_ - largest absolute return covered by the bins (number greater than 0). Default
+///   is 0.05.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RollingEntropy;
+/// use ta::Next;
+///
+/// let mut entropy = RollingEntropy::new(4, 2, 0.1).unwrap();
+///
+/// assert_eq!(entropy.next(100.0), 0.0);
+/// assert_eq!(entropy.next(105.0), 0.0);
+/// // One return up, one return down
+/// assert_eq!(entropy.next(100.0), 1.0);
+/// ```
+///
+/// # Links
+///
+/// * [Entropy (information theory), Wikipedia](https://en.wikipedia.org/wiki/Entropy_(information_theory))
+///
+#[doc(alias = "ENTROPY")]
+#[derive(Debug, Clone)]
+pub struct RollingEntropy {
+    period: usize,
+    range: f64,
+    index: usize,
+    count: usize,
+    prev: Option<f64>,
+    /// Σ c * ln(c) over the bin counts
+    sum_c_ln_c: f64,
+    counts: Box<[usize]>,
+    deque: Box<[usize]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: usize,
+    saved_count: usize,
+    saved_prev: Option<f64>,
+    saved_sum_c_ln_c: f64,
+}
+
+/// State of [RollingEntropy](struct.RollingEntropy.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RollingEntropyState {
+    pub period: usize,
+    pub bins: usize,
+    pub range: f64,
+    /// Previous input.
+    pub previous: Option<f64>,
+    /// Bins of the returns in the window, the oldest first.
+    pub window: Vec<usize>,
+    /// Running sum of c ln(c) over the counts c of the bins.
+    pub sum_c_ln_c: f64,
+    pub output: Option<f64>,
+    /// Bin which the last input pushed out of the window, to amend it.
+    pub evicted: Option<usize>,
+    /// Input before the last one and sum before the last input, to amend the last one.
+    pub before_last: Option<f64>,
+    pub previous_sum_c_ln_c: f64,
+}
+
+impl RollingEntropy {
+    pub fn new(period: usize, bins: usize, range: f64) -> Result<Self> {
+        if period == 0 {
+            return Err(TaError::invalid_parameter("period", 0.0, ">= 1"));
+        }
+        if bins < 2 {
+            return Err(TaError::invalid_parameter("bins", bins as f64, ">= 2"));
+        }
+        if !range.is_finite() || range <= 0.0 {
+            return Err(TaError::invalid_parameter("range", range, "> 0"));
+        }
+        Ok(Self {
+            period,
+            range,
+            index: 0,
+            count: 0,
+            prev: None,
+            sum_c_ln_c: 0.0,
+            counts: vec![0; bins].into_boxed_slice(),
+            deque: vec![0; period].into_boxed_slice(),
+            output: None,
+            saved_value: 0,
+            saved_count: 0,
+            saved_prev: None,
+            saved_sum_c_ln_c: 0.0,
+        })
+    }
+
+    pub fn bins(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn range(&self) -> f64 {
+        self.range
+    }
+
+    fn bin(&self, ret: f64) -> usize {
+        let bins = self.counts.len();
+        let position = (ret.clamp(-self.range, self.range) + self.range) / (2.0 * self.range);
+        ((position * bins as f64) as usize).min(bins - 1)
+    }
+
+    /// Entropy of a window with `count` returns whose bin counts sum up to `sum_c_ln_c`.
+    fn entropy(&self, count: usize, sum_c_ln_c: f64) -> f64 {
+        let n = count as f64;
+        let entropy = n.ln() - sum_c_ln_c / n;
+        (entropy / (self.counts.len() as f64).ln()).clamp(0.0, 1.0)
+    }
+
+    /// Changes the count of a bin and keeps Σ c * ln(c) up to date.
+    fn update_bin(&mut self, bin: usize, add: bool) {
+        let c = self.counts[bin];
+        self.sum_c_ln_c -= c_ln_c(c);
+        self.counts[bin] = if add { c + 1 } else { c - 1 };
+        self.sum_c_ln_c += c_ln_c(self.counts[bin]);
+    }
+}
+
+fn c_ln_c(c: usize) -> f64 {
+    if c > 1 {
+        c as f64 * (c as f64).ln()
+    } else {
+        0.0
+    }
+}
+
+impl Period for RollingEntropy {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for RollingEntropy {
+    type State = RollingEntropyState;
+
+    fn state(&self) -> Self::State {
+        RollingEntropyState {
+            period: self.period,
+            bins: self.counts.len(),
+            range: self.range,
+            previous: self.prev,
+            window: ring_values(&self.deque, self.index, self.count),
+            sum_c_ln_c: self.sum_c_ln_c,
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            before_last: self.saved_prev,
+            previous_sum_c_ln_c: self.saved_sum_c_ln_c,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() != state.previous.is_some()
+            || (state.previous.is_none() && !state.window.is_empty())
+            || state
+                .window
+                .iter()
+                .chain(&state.evicted)
+                .any(|&bin| bin >= state.bins)
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator =
+            Self::new(state.period, state.bins, state.range).map_err(|_| TaError::InvalidState)?;
+        let (deque, index) = ring_from_values(state.period, &state.window)?;
+        for &bin in &state.window {
+            indicator.counts[bin] += 1;
+        }
+        indicator.deque = deque;
+        indicator.index = index;
+        indicator.count = state.window.len();
+        indicator.prev = state.previous;
+        indicator.sum_c_ln_c = state.sum_c_ln_c;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or(0);
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_prev = state.before_last;
+        indicator.saved_sum_c_ln_c = state.previous_sum_c_ln_c;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(RollingEntropy, 2);
+
+impl SetPeriod for RollingEntropy {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period, self.bins(), self.range)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for RollingEntropy {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
+        self.saved_prev = self.prev;
+        self.saved_sum_c_ln_c = self.sum_c_ln_c;
+        let prev = self.prev.replace(input);
+        let ret = match prev {
+            Some(prev) if prev != 0.0 => input / prev - 1.0,
+            Some(_) => 0.0,
+            None => {
+                self.output = Some(0.0);
+                return 0.0;
+            }
+        };
+
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            self.update_bin(self.deque[self.index], false);
+        }
+        let bin = self.bin(ret);
+        self.update_bin(bin, true);
+        self.deque[self.index] = bin;
+
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        let output = self.entropy(self.count, self.sum_c_ln_c);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for RollingEntropy {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for RollingEntropy {
+    fn peek(&self, input: f64) -> f64 {
+        let ret = match self.prev {
+            Some(prev) if prev != 0.0 => input / prev - 1.0,
+            Some(_) => 0.0,
+            None => return 0.0,
+        };
+
+        // Same updates of Σ c * ln(c) as `next`
+        let mut sum_c_ln_c = self.sum_c_ln_c;
+        let mut evicted = None;
+        let count = if self.count < self.period {
+            self.count + 1
+        } else {
+            let bin = self.deque[self.index];
+            let c = self.counts[bin];
+            sum_c_ln_c -= c_ln_c(c);
+            sum_c_ln_c += c_ln_c(c - 1);
+            evicted = Some(bin);
+            self.count
+        };
+        let bin = self.bin(ret);
+        let c = self.counts[bin] - usize::from(evicted == Some(bin));
+        sum_c_ln_c -= c_ln_c(c);
+        sum_c_ln_c += c_ln_c(c + 1);
+
+        self.entropy(count, sum_c_ln_c)
+    }
+}
+
+impl<T: Close> Peek<&T> for RollingEntropy {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for RollingEntropy {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        // The first input doesn't enter the window
+        if self.saved_prev.is_some() {
+            self.index = (self.index + self.period - 1) % self.period;
+            self.update_bin(self.deque[self.index], false);
+            if self.saved_count == self.period {
+                self.update_bin(self.saved_value, true);
+            }
+            self.deque[self.index] = self.saved_value;
+            self.count = self.saved_count;
+            self.sum_c_ln_c = self.saved_sum_c_ln_c;
+        }
+        self.prev = self.saved_prev;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for RollingEntropy {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for RollingEntropy {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.prev = None;
+        self.sum_c_ln_c = 0.0;
+        for count in self.counts.iter_mut() {
+            *count = 0;
+        }
+        self.output = None;
+        self.saved_value = 0;
+        self.saved_count = 0;
+        self.saved_prev = None;
+        self.saved_sum_c_ln_c = 0.0;
+    }
+}
+
+impl Current for RollingEntropy {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for RollingEntropy {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for RollingEntropy {
+    fn lookback(&self) -> usize {
+        // the first return needs two prices
+        self.period + 1
+    }
+}
+
+impl Default for RollingEntropy {
+    fn default() -> Self {
+        Self::new(20, 10, 0.05).unwrap()
+    }
+}
+
+impl fmt::Display for RollingEntropy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ENTROPY({}, {}, {})",
+            self.period,
+            self.counts.len(),
+            self.range
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RollingEntropy);
+
+    #[test]
+    fn test_new() {
+        assert!(RollingEntropy::new(0, 10, 0.05).is_err());
+        assert!(RollingEntropy::new(20, 1, 0.05).is_err());
+        assert!(RollingEntropy::new(20, 10, 0.0).is_err());
+        assert!(RollingEntropy::new(20, 10, f64::NAN).is_err());
+        assert!(RollingEntropy::new(1, 2, 0.05).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut entropy = RollingEntropy::new(3, 2, 0.1).unwrap();
+
+        assert_eq!(entropy.next(100.0), 0.0);
+        assert_eq!(entropy.next(110.0), 0.0);
+        assert_eq!(round(entropy.next(99.0)), 1.0);
+        assert_eq!(round(entropy.next(108.9)), 0.918);
+        assert_eq!(round(entropy.next(130.68)), 0.918);
+        // Returns of 10%, 20% and 5% all fall into the upper bin
+        assert_eq!(round(entropy.next(137.214)), 0.0);
+    }
+
+    #[test]
+    fn test_bins() {
+        let entropy = RollingEntropy::new(3, 4, 0.1).unwrap();
+
+        assert_eq!(entropy.bin(-0.5), 0);
+        assert_eq!(entropy.bin(-0.06), 0);
+        assert_eq!(entropy.bin(-0.04), 1);
+        assert_eq!(entropy.bin(0.0), 2);
+        assert_eq!(entropy.bin(0.06), 3);
+        assert_eq!(entropy.bin(0.1), 3);
+        assert_eq!(entropy.bin(0.5), 3);
+    }
+
+    #[test]
+    fn test_next_uniform() {
+        let mut entropy = RollingEntropy::new(4, 4, 0.1).unwrap();
+
+        entropy.next(100.0);
+        entropy.next(91.0);
+        entropy.next(88.27);
+        entropy.next(88.27);
+        assert_eq!(round(entropy.next(93.5662)), 1.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut entropy = RollingEntropy::new(3, 2, 0.1).unwrap();
+
+        entropy.next(100.0);
+        entropy.next(110.0);
+        entropy.next(99.0);
+
+        entropy.reset();
+        assert_eq!(entropy.next(100.0), 0.0);
+        assert_eq!(entropy.next(110.0), 0.0);
+        assert_eq!(entropy.next(121.0), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingEntropy::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let entropy = RollingEntropy::new(20, 10, 0.05).unwrap();
+        assert_eq!(format!("{}", entropy), "ENTROPY(20, 10, 0.05)");
+    }
+}
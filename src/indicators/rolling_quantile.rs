@@ -0,0 +1,593 @@
+use core::cmp::{Ordering, Reverse};
+use core::fmt;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling quantile (QUANTILE).
+///
+/// Returns the requested quantile of the last _period_ values, e.g. the median for 0.5.
+///
+/// The window is split into two heaps: the lower one holds the values up to the quantile and
+/// the upper one the values above it, so the quantile is read from their tops. A value leaving
+/// the window is only marked as removed and dropped once it reaches the top of its heap, so an
+/// update takes O(log n) time. The heaps are rebuilt from the window when the removed values
+/// they still hold outnumber the period.
+///
+/// # Formula
+///
+/// QUANTILE = s<sub>⌊h⌋</sub> + (h - ⌊h⌋) * (s<sub>⌊h⌋+1</sub> - s<sub>⌊h⌋</sub>)
+///
+/// Where:
+///
+/// * _s_ - values of the window sorted in ascending order, indexed from 0
+/// * _h_ = (n - 1) * _quantile_
+/// * _n_ - number of values in the window, which is less than _period_ during the first bars
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+/// * _quantile_ - number in range 0..1. Default is 0.5 (median).
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RollingQuantile;
+/// use ta::Next;
+///
+/// let mut median = RollingQuantile::median(3).unwrap();
+/// assert_eq!(median.next(2.0), 2.0);
+/// assert_eq!(median.next(5.0), 3.5);
+/// assert_eq!(median.next(1.0), 2.0);
+/// assert_eq!(median.next(6.0), 5.0);
+///
+/// let mut q = RollingQuantile::new(5, 0.25).unwrap();
+/// for x in &[4.0, 1.0, 3.0, 2.0] {
+///     q.next(*x);
+/// }
+/// assert_eq!(q.next(5.0), 2.0);
+/// ```
+///
+/// # Links
+///
+/// * [Quantile, Wikipedia](https://en.wikipedia.org/wiki/Quantile)
+///
+#[doc(alias = "QUANTILE")]
+#[doc(alias = "MEDIAN")]
+#[derive(Debug, Clone)]
+pub struct RollingQuantile {
+    period: usize,
+    quantile: f64,
+    index: usize,
+    count: usize,
+    deque: Box<[f64]>,
+    lower: Heap<Value>,
+    upper: Heap<Reverse<Value>>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+}
+
+/// Input ordered with `total_cmp`, so it can be kept in a heap.
+#[derive(Debug, Clone, Copy)]
+struct Value(f64);
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Value {}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Heap with lazy deletion: removed values stay in the heap until they reach its top.
+#[derive(Debug, Clone)]
+struct Heap<T> {
+    heap: BinaryHeap<T>,
+    /// Number of removed values still in the heap, by their bits.
+    removed: HashMap<u64, usize>,
+    /// Number of values in the heap that are not removed.
+    len: usize,
+}
+
+trait HeapValue: Ord + Copy {
+    fn new(value: f64) -> Self;
+    fn value(self) -> f64;
+}
+
+impl HeapValue for Value {
+    fn new(value: f64) -> Self {
+        Value(value)
+    }
+
+    fn value(self) -> f64 {
+        self.0
+    }
+}
+
+impl HeapValue for Reverse<Value> {
+    fn new(value: f64) -> Self {
+        Reverse(Value(value))
+    }
+
+    fn value(self) -> f64 {
+        self.0 .0
+    }
+}
+
+impl<T: HeapValue> Heap<T> {
+    fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            removed: HashMap::new(),
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, value: f64) {
+        self.heap.push(T::new(value));
+        self.len += 1;
+    }
+
+    /// Top value that is not removed.
+    fn peek(&mut self) -> Option<f64> {
+        while let Some(&top) = self.heap.peek() {
+            let bits = top.value().to_bits();
+            match self.removed.get_mut(&bits) {
+                Some(count) => {
+                    *count -= 1;
+                    if *count == 0 {
+                        self.removed.remove(&bits);
+                    }
+                    self.heap.pop();
+                }
+                None => return Some(top.value()),
+            }
+        }
+        None
+    }
+
+    fn pop(&mut self) -> Option<f64> {
+        let top = self.peek()?;
+        self.heap.pop();
+        self.len -= 1;
+        Some(top)
+    }
+
+    /// Marks a value of the heap as removed.
+    fn remove(&mut self, value: f64) {
+        *self.removed.entry(value.to_bits()).or_insert(0) += 1;
+        self.len -= 1;
+    }
+
+    /// Number of removed values still in the heap.
+    fn stale(&self) -> usize {
+        self.heap.len() - self.len
+    }
+
+    fn clear(&mut self) {
+        self.heap.clear();
+        self.removed.clear();
+        self.len = 0;
+    }
+}
+
+/// State of [RollingQuantile](struct.RollingQuantile.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RollingQuantileState {
+    pub period: usize,
+    pub quantile: f64,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<f64>,
+    pub output: Option<f64>,
+    /// Input which the last one pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
+}
+
+impl RollingQuantile {
+    pub fn new(period: usize, quantile: f64) -> Result<Self> {
+        if period == 0 {
+            return Err(TaError::invalid_parameter("period", 0.0, ">= 1"));
+        }
+        if !(0.0..=1.0).contains(&quantile) {
+            return Err(TaError::invalid_parameter("quantile", quantile, "0..=1"));
+        }
+
+        Ok(Self {
+            period,
+            quantile,
+            index: 0,
+            count: 0,
+            deque: vec![0.0; period].into_boxed_slice(),
+            lower: Heap::new(),
+            upper: Heap::new(),
+            output: None,
+            saved_value: 0.0,
+            saved_count: 0,
+        })
+    }
+
+    /// Rolling median, the same as the 0.5 quantile.
+    pub fn median(period: usize) -> Result<Self> {
+        Self::new(period, 0.5)
+    }
+
+    pub fn quantile(&self) -> f64 {
+        self.quantile
+    }
+
+    /// Values of the current window.
+    pub(super) fn window(&self) -> &[f64] {
+        &self.deque[..self.count]
+    }
+
+    /// Inputs in the window if `input` was the next input, in the order of [window](#method.window).
+    pub(super) fn window_with(&self, input: f64) -> Vec<f64> {
+        let mut window = self.window().to_vec();
+        if self.count == self.period {
+            window[self.index] = input;
+        } else {
+            window.push(input);
+        }
+        window
+    }
+
+    fn insert(&mut self, value: f64) {
+        // Goes through the lower heap, so the lower values stay below the upper ones
+        self.lower.push(value);
+        let top = self.lower.pop().unwrap();
+        self.upper.push(top);
+    }
+
+    fn remove(&mut self, value: f64) {
+        // The lower heap holds every value below its top, and a value equal to it
+        match self.lower.peek() {
+            Some(top) if value.total_cmp(&top) != Ordering::Greater => self.lower.remove(value),
+            _ => self.upper.remove(value),
+        }
+    }
+
+    /// Moves values between the heaps, so the lower one holds the values up to the quantile.
+    fn balance(&mut self, rank: usize) {
+        while self.lower.len > rank + 1 {
+            let value = self.lower.pop().unwrap();
+            self.upper.push(value);
+        }
+        while self.lower.len < rank + 1 {
+            let value = self.upper.pop().unwrap();
+            self.lower.push(value);
+        }
+    }
+
+    /// Refills the heaps with the values of the window only.
+    fn rebuild(&mut self) {
+        self.lower.clear();
+        self.upper.clear();
+        for i in 0..self.count {
+            self.lower.push(self.deque[i]);
+        }
+    }
+}
+
+impl Period for RollingQuantile {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for RollingQuantile {
+    type State = RollingQuantileState;
+
+    fn state(&self) -> Self::State {
+        RollingQuantileState {
+            period: self.period,
+            quantile: self.quantile,
+            inputs: ring_values(&self.deque, self.index, self.count),
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.inputs.is_empty() {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator =
+            Self::new(state.period, state.quantile).map_err(|_| TaError::InvalidState)?;
+        let (deque, index) = ring_from_values(state.period, &state.inputs)?;
+        indicator.deque = deque;
+        indicator.index = index;
+        indicator.count = state.inputs.len();
+        indicator.rebuild();
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(RollingQuantile, 2);
+
+impl SetPeriod for RollingQuantile {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period, self.quantile)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for RollingQuantile {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
+        if self.count == self.period {
+            self.remove(self.deque[self.index]);
+        } else {
+            self.count += 1;
+        }
+        self.insert(input);
+
+        self.deque[self.index] = input;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        if self.lower.stale() + self.upper.stale() > self.period {
+            self.rebuild();
+        }
+
+        let h = (self.count - 1) as f64 * self.quantile;
+        let lower = h.floor() as usize;
+        let upper = h.ceil() as usize;
+        self.balance(lower);
+
+        let low = self.lower.peek().unwrap();
+        let high = if upper == lower {
+            low
+        } else {
+            self.upper.peek().unwrap()
+        };
+        let output = low + (h - lower as f64) * (high - low);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Peek<f64> for RollingQuantile {
+    fn peek(&self, input: f64) -> f64 {
+        let mut window = self.window_with(input);
+
+        let h = (window.len() - 1) as f64 * self.quantile;
+        let lower = h.floor() as usize;
+        let upper = h.ceil() as usize;
+
+        let (_, &mut low, above) = window.select_nth_unstable_by(lower, f64::total_cmp);
+        let high = if upper == lower {
+            low
+        } else {
+            above.iter().copied().min_by(f64::total_cmp).unwrap()
+        };
+        low + (h - lower as f64) * (high - low)
+    }
+}
+
+impl<T: Close> Peek<&T> for RollingQuantile {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl<T: Close> Next<&T> for RollingQuantile {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Amend<f64> for RollingQuantile {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        self.remove(self.deque[self.index]);
+        if self.saved_count == self.period {
+            self.insert(self.saved_value);
+        }
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for RollingQuantile {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for RollingQuantile {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.lower.clear();
+        self.upper.clear();
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+    }
+}
+
+impl Current for RollingQuantile {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for RollingQuantile {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for RollingQuantile {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for RollingQuantile {
+    fn default() -> Self {
+        Self::new(20, 0.5).unwrap()
+    }
+}
+
+impl fmt::Display for RollingQuantile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "QUANTILE({}, {})", self.period, self.quantile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RollingQuantile);
+
+    #[test]
+    fn test_new() {
+        assert!(RollingQuantile::new(0, 0.5).is_err());
+        assert!(RollingQuantile::new(20, -0.1).is_err());
+        assert!(RollingQuantile::new(20, 1.1).is_err());
+        assert!(RollingQuantile::new(20, f64::NAN).is_err());
+        assert!(RollingQuantile::new(1, 0.0).is_ok());
+        assert!(RollingQuantile::new(1, 1.0).is_ok());
+        assert!(RollingQuantile::median(0).is_err());
+    }
+
+    #[test]
+    fn test_next_median() {
+        let mut median = RollingQuantile::median(3).unwrap();
+
+        assert_eq!(median.next(2.0), 2.0);
+        assert_eq!(median.next(5.0), 3.5);
+        assert_eq!(median.next(1.0), 2.0);
+        assert_eq!(median.next(6.0), 5.0);
+        assert_eq!(median.next(6.0), 6.0);
+        assert_eq!(median.next(0.0), 6.0);
+        assert_eq!(median.next(3.0), 3.0);
+    }
+
+    #[test]
+    fn test_next_quantiles() {
+        let mut min = RollingQuantile::new(3, 0.0).unwrap();
+        let mut max = RollingQuantile::new(3, 1.0).unwrap();
+        let mut q = RollingQuantile::new(3, 0.75).unwrap();
+
+        for x in &[4.0, 8.0, 2.0, 6.0] {
+            min.next(*x);
+            max.next(*x);
+            q.next(*x);
+        }
+
+        // window is 2, 6, 6
+        assert_eq!(min.next(6.0), 2.0);
+        assert_eq!(max.next(6.0), 6.0);
+        assert_eq!(q.next(6.0), 6.0);
+    }
+
+    #[test]
+    fn test_next_large_window() {
+        // Pseudo-random inputs with repeated values, against sorting the window
+        let inputs: Vec<f64> = (0..3000u64)
+            .map(|i| {
+                i.wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407)
+            })
+            .map(|x| ((x >> 40) % 1000) as f64 / 10.0)
+            .collect();
+
+        for &quantile in &[0.0, 0.1, 0.5, 0.77, 1.0] {
+            let mut indicator = RollingQuantile::new(500, quantile).unwrap();
+            for (i, &input) in inputs.iter().enumerate() {
+                let mut window = inputs[(i + 1).saturating_sub(500)..=i].to_vec();
+                window.sort_by(f64::total_cmp);
+                let h = (window.len() - 1) as f64 * quantile;
+                let (lower, upper) = (h.floor() as usize, h.ceil() as usize);
+                let expected = window[lower] + (h - lower as f64) * (window[upper] - window[lower]);
+
+                assert_eq!(indicator.next(input), expected);
+                assert!(indicator.lower.heap.len() + indicator.upper.heap.len() <= 2 * 500 + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut median = RollingQuantile::median(3).unwrap();
+
+        assert_eq!(median.next(&Bar::new().close(2)), 2.0);
+        assert_eq!(median.next(&Bar::new().close(5)), 3.5);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut median = RollingQuantile::median(3).unwrap();
+
+        median.next(2.0);
+        median.next(5.0);
+
+        median.reset();
+        assert_eq!(median.next(1.0), 1.0);
+        assert_eq!(median.next(6.0), 3.5);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingQuantile::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let q = RollingQuantile::new(20, 0.25).unwrap();
+        assert_eq!(format!("{}", q), "QUANTILE(20, 0.25)");
+    }
+}
@@ -0,0 +1,295 @@
+use core::f64::consts::PI;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{SuperSmoother, SuperSmootherState};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Ehlers Roofing Filter.
+///
+/// A two-pole high-pass filter followed by a [Super Smoother](struct.SuperSmoother.html). The
+/// high-pass filter removes the trend (cycles longer than _high_pass_period_) and the Super
+/// Smoother removes the noise (cycles shorter than _smoother_period_), so only the cycles in
+/// between are left. The output oscillates around zero.
+///
+/// # Formula
+///
+/// HP<sub>t</sub> = (1 - α / 2)<sup>2</sup> × (Price<sub>t</sub> - 2 × Price<sub>t-1</sub> +
+/// Price<sub>t-2</sub>) + 2 × (1 - α) × HP<sub>t-1</sub> - (1 - α)<sup>2</sup> ×
+/// HP<sub>t-2</sub>
+///
+/// Roofing<sub>t</sub> = SuperSmoother(HP, _smoother_period_)<sub>t</sub>
+///
+/// Where:
+///
+/// * ω = 0.707 × 2π / _high_pass_period_
+/// * α = (cos(ω) + sin(ω) - 1) / cos(ω)
+///
+/// Previous prices start at the first price and previous HP values at 0.
+///
+/// # Parameters
+///
+/// * _high_pass_period_ - longest cycle to keep (integer greater than 0). Default is 48.
+/// * _smoother_period_ - shortest cycle to keep (integer greater than 0). Default is 10.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RoofingFilter;
+/// use ta::Next;
+///
+/// let mut roofing = RoofingFilter::new(48, 10).unwrap();
+///
+/// // A constant price has no cycles
+/// for _ in 0..10 {
+///     assert_eq!(roofing.next(20.0), 0.0);
+/// }
+/// ```
+#[doc(alias = "ROOF")]
+#[derive(Debug, Clone)]
+pub struct RoofingFilter {
+    high_pass_period: usize,
+    alpha: f64,
+    smoother: SuperSmoother,
+    prices: Option<(f64, f64)>,
+    high_pass: (f64, f64),
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_prices: Option<(f64, f64)>,
+    saved_high_pass: (f64, f64),
+}
+
+/// State of [RoofingFilter](struct.RoofingFilter.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoofingFilterState {
+    pub high_pass_period: usize,
+    pub smoother: SuperSmootherState,
+    /// Last two inputs, the newest first.
+    pub prices: Option<(f64, f64)>,
+    /// Last two values of the high-pass filter, the newest first.
+    pub high_pass: (f64, f64),
+    pub output: Option<f64>,
+    /// Prices and high-pass values before the last input, to amend it.
+    pub previous_prices: Option<(f64, f64)>,
+    pub previous_high_pass: (f64, f64),
+}
+
+impl RoofingFilter {
+    pub fn new(high_pass_period: usize, smoother_period: usize) -> Result<Self> {
+        if high_pass_period == 0 {
+            return Err(TaError::invalid_parameter("high_pass_period", 0.0, ">= 1"));
+        }
+        let omega = 0.707 * 2.0 * PI / high_pass_period as f64;
+        Ok(Self {
+            high_pass_period,
+            alpha: (omega.cos() + omega.sin() - 1.0) / omega.cos(),
+            smoother: SuperSmoother::new(smoother_period)?,
+            prices: None,
+            high_pass: (0.0, 0.0),
+            output: None,
+            saved_prices: None,
+            saved_high_pass: (0.0, 0.0),
+        })
+    }
+
+    // High-pass filter of the price, before the smoother
+    fn high_pass(&self, input: f64) -> f64 {
+        let (price1, price2) = self.prices.unwrap_or((input, input));
+        let (hp1, hp2) = self.high_pass;
+        let a = self.alpha;
+
+        (1.0 - a / 2.0).powi(2) * (input - 2.0 * price1 + price2) + 2.0 * (1.0 - a) * hp1
+            - (1.0 - a).powi(2) * hp2
+    }
+}
+
+impl Checkpoint for RoofingFilter {
+    type State = RoofingFilterState;
+
+    fn state(&self) -> Self::State {
+        RoofingFilterState {
+            high_pass_period: self.high_pass_period,
+            smoother: self.smoother.state(),
+            prices: self.prices,
+            high_pass: self.high_pass,
+            output: self.output,
+            previous_prices: self.saved_prices,
+            previous_high_pass: self.saved_high_pass,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(state.high_pass_period, state.smoother.period)
+            .map_err(|_| TaError::InvalidState)?;
+        if state.prices.is_none() != state.output.is_none() {
+            return Err(TaError::InvalidState);
+        }
+        indicator.smoother = SuperSmoother::from_state(state.smoother)?;
+        indicator.prices = state.prices;
+        indicator.high_pass = state.high_pass;
+        indicator.output = state.output;
+        indicator.saved_prices = state.previous_prices;
+        indicator.saved_high_pass = state.previous_high_pass;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(RoofingFilter, 2);
+
+impl Next<f64> for RoofingFilter {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_prices = self.prices;
+        self.saved_high_pass = self.high_pass;
+        let hp = self.high_pass(input);
+        self.prices = Some((input, self.prices.map_or(input, |(price1, _)| price1)));
+        self.high_pass = (hp, self.high_pass.0);
+        let output = self.smoother.next(hp);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for RoofingFilter {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for RoofingFilter {
+    fn peek(&self, input: f64) -> f64 {
+        self.smoother.peek(self.high_pass(input))
+    }
+}
+
+impl<T: Close> Peek<&T> for RoofingFilter {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for RoofingFilter {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.prices = self.saved_prices;
+        self.high_pass = self.saved_high_pass;
+        let hp = self.high_pass(input);
+        self.prices = Some((input, self.prices.map_or(input, |(price1, _)| price1)));
+        self.high_pass = (hp, self.high_pass.0);
+        let output = self.smoother.amend(hp);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for RoofingFilter {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for RoofingFilter {
+    fn reset(&mut self) {
+        self.smoother.reset();
+        self.prices = None;
+        self.high_pass = (0.0, 0.0);
+        self.output = None;
+        self.saved_prices = None;
+        self.saved_high_pass = (0.0, 0.0);
+    }
+}
+
+impl Current for RoofingFilter {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for RoofingFilter {
+    fn is_ready(&self) -> bool {
+        self.smoother.is_ready()
+    }
+}
+
+impl Lookback for RoofingFilter {
+    fn lookback(&self) -> usize {
+        self.smoother.lookback()
+    }
+}
+
+impl Default for RoofingFilter {
+    fn default() -> Self {
+        Self::new(48, 10).unwrap()
+    }
+}
+
+impl fmt::Display for RoofingFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ROOF({}, {})",
+            self.high_pass_period,
+            self.smoother.period()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RoofingFilter);
+
+    #[test]
+    fn test_new() {
+        assert!(RoofingFilter::new(0, 10).is_err());
+        assert!(RoofingFilter::new(48, 0).is_err());
+        assert!(RoofingFilter::new(48, 10).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut roofing = RoofingFilter::new(8, 4).unwrap();
+
+        assert_eq!(round(roofing.next(10.0)), 0.0);
+        assert_eq!(round(roofing.next(12.0)), 0.494);
+        assert_eq!(round(roofing.next(11.0)), 0.447);
+        assert_eq!(round(roofing.next(14.0)), 0.508);
+        assert_eq!(round(roofing.next(&Bar::new().close(13))), 0.486);
+        assert_eq!(round(roofing.next(&Bar::new().close(15))), 0.136);
+        assert_eq!(round(roofing.next(&Bar::new().close(14))), -0.078);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut roofing = RoofingFilter::new(8, 4).unwrap();
+
+        roofing.next(10.0);
+        roofing.next(12.0);
+        roofing.reset();
+        assert_eq!(roofing.next(20.0), 0.0);
+        assert_eq!(round(roofing.next(22.0)), 0.494);
+    }
+
+    #[test]
+    fn test_default() {
+        RoofingFilter::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let roofing = RoofingFilter::new(40, 8).unwrap();
+        assert_eq!(format!("{}", roofing), "ROOF(40, 8)");
+    }
+}
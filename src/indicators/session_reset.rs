@@ -0,0 +1,465 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Checkpoint, Current, IsReady, Lookback, Next, Peek, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Day of the week.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+/// Trading session schedule.
+///
+/// A session starts every trading day at the same local time and lasts until the start of the
+/// next trading day's session. Weekends and holidays are not trading days, so e.g. the Friday
+/// session lasts until the Monday session starts.
+///
+/// The timezone is a fixed offset from UTC, daylight saving time is not taken into account.
+///
+/// # Parameters
+///
+/// * _utc_offset_ - offset of the local time from UTC in minutes (integer in range
+///   [-1080, 1080]).
+/// * _hour_, _minute_ - local start time of the session.
+///
+/// Saturday and Sunday are weekend days by default, see [weekends](#method.weekends).
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{Session, Weekday};
+///
+/// // New York session opening at 09:30 EST
+/// let session = Session::new(-5 * 60, 9, 30).unwrap()
+///     .weekends(&[Weekday::Saturday, Weekday::Sunday])
+///     .holiday(2024, 12, 25).unwrap();
+///
+/// // 2024-12-24 14:30 UTC and 2024-12-26 14:30 UTC, the 25th is a holiday
+/// assert_eq!(session.session_day(1735050600), session.session_day(1735223399));
+/// assert_ne!(session.session_day(1735050600), session.session_day(1735223400));
+/// ```
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "SessionFields")
+)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    utc_offset: i64,
+    start: i64,
+    weekends: [bool; 7],
+    holidays: Vec<i64>,
+}
+
+/// Serialized fields of a [Session], checked by its constructor when deserialized.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct SessionFields {
+    utc_offset: i64,
+    start: i64,
+    weekends: [bool; 7],
+    holidays: Vec<i64>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SessionFields> for Session {
+    type Error = TaError;
+
+    fn try_from(fields: SessionFields) -> Result<Self> {
+        if fields.utc_offset % 60 != 0
+            || fields.start % 60 != 0
+            || !(0..SECONDS_PER_DAY).contains(&fields.start)
+        {
+            return Err(TaError::InvalidState);
+        }
+        let utc_offset =
+            i32::try_from(fields.utc_offset / 60).map_err(|_| TaError::InvalidState)?;
+        let start = fields.start / 60;
+        let mut session = Self::new(utc_offset, (start / 60) as u32, (start % 60) as u32)?;
+        session.weekends = fields.weekends;
+        session.holidays = fields.holidays;
+        Ok(session)
+    }
+}
+
+impl Session {
+    pub fn new(utc_offset: i32, hour: u32, minute: u32) -> Result<Self> {
+        if !(-18 * 60..=18 * 60).contains(&utc_offset) {
+            return Err(TaError::invalid_parameter(
+                "utc_offset",
+                utc_offset as f64,
+                "-1080..=1080",
+            ));
+        }
+        if hour > 23 {
+            return Err(TaError::invalid_parameter("hour", hour as f64, "0..=23"));
+        }
+        if minute > 59 {
+            return Err(TaError::invalid_parameter(
+                "minute",
+                minute as f64,
+                "0..=59",
+            ));
+        }
+        let mut weekends = [false; 7];
+        weekends[Weekday::Saturday as usize] = true;
+        weekends[Weekday::Sunday as usize] = true;
+        Ok(Self {
+            utc_offset: utc_offset as i64 * 60,
+            start: (hour as i64 * 60 + minute as i64) * 60,
+            weekends,
+            holidays: Vec::new(),
+        })
+    }
+
+    /// Replaces the weekend days.
+    pub fn weekends(mut self, days: &[Weekday]) -> Self {
+        self.weekends = [false; 7];
+        for &day in days {
+            self.weekends[day as usize] = true;
+        }
+        self
+    }
+
+    /// Adds a holiday, given as a local date. Fails when the date does not exist.
+    pub fn holiday(mut self, year: i32, month: u32, day: u32) -> Result<Self> {
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days_in_month = match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            1..=12 => 31,
+            _ => 0,
+        };
+        if days_in_month == 0 {
+            return Err(TaError::invalid_parameter("month", month as f64, "1..=12"));
+        }
+        if day == 0 || day > days_in_month {
+            return Err(TaError::invalid_parameter(
+                "day",
+                day as f64,
+                "a day of the month",
+            ));
+        }
+        self.holidays.push(days_from_civil(year, month, day));
+        Ok(self)
+    }
+
+    /// Trading day of the session that contains `timestamp` (seconds since the Unix epoch),
+    /// as the number of days since 1970-01-01.
+    pub fn session_day(&self, timestamp: i64) -> i64 {
+        let day = (timestamp + self.utc_offset - self.start).div_euclid(SECONDS_PER_DAY);
+        // Look back a week plus all holidays at most
+        (0..=7 + self.holidays.len() as i64)
+            .map(|i| day - i)
+            .find(|&d| self.is_trading_day(d))
+            .unwrap_or(day)
+    }
+
+    fn is_trading_day(&self, day: i64) -> bool {
+        // 1970-01-01 was a Thursday
+        let weekday = (day + 3).rem_euclid(7) as usize;
+        !self.weekends[weekday] && !self.holidays.contains(&day)
+    }
+}
+
+impl fmt::Display for Session {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.utc_offset < 0 { '-' } else { '+' };
+        let offset = self.utc_offset.abs() / 60;
+        write!(
+            f,
+            "{}{:02}:{:02} {:02}:{:02}",
+            sign,
+            offset / 60,
+            offset % 60,
+            self.start / 3600,
+            self.start / 60 % 60
+        )
+    }
+}
+
+/// Number of days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = year as i64 - if month <= 2 { 1 } else { 0 };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let month = month as i64;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Resets an indicator at the start of every trading session.
+///
+/// Wraps an indicator and calls [reset](crate::Reset) on it whenever an input belongs to a
+/// later [session](struct.Session.html) than the previous input. This turns cumulative
+/// indicators into intraday ones, e.g. an [AnchoredVwap](struct.AnchoredVwap.html) into a
+/// daily VWAP.
+///
+/// Inputs are `(timestamp, input)` pairs, where the timestamp is in seconds since the Unix
+/// epoch and the input is anything the wrapped indicator accepts.
+///
+/// # Parameters
+///
+/// * _indicator_ - indicator to reset.
+/// * _session_ - session schedule.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{AnchoredVwap, Session, SessionReset};
+/// use ta::{DataItem, Next};
+///
+/// fn bar(price: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(price).high(price).low(price).close(price)
+///         .volume(100.0)
+///         .build().unwrap()
+/// }
+///
+/// // Sessions start at midnight UTC
+/// let session = Session::new(0, 0, 0).unwrap();
+/// let mut vwap = SessionReset::new(AnchoredVwap::new(), session);
+///
+/// // Monday 2024-01-01
+/// assert_eq!(vwap.next((1704103200, &bar(10.0))), 10.0);
+/// assert_eq!(vwap.next((1704106800, &bar(20.0))), 15.0);
+/// // Tuesday
+/// assert_eq!(vwap.next((1704189600, &bar(30.0))), 30.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SessionReset<I> {
+    indicator: I,
+    session: Session,
+    current: Option<i64>,
+}
+
+/// State of [SessionReset](struct.SessionReset.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionResetState<S> {
+    pub indicator: S,
+    pub session: Session,
+    /// Session day of the last input, counted in days since 1970-01-01.
+    pub current: Option<i64>,
+}
+
+impl<I> SessionReset<I> {
+    pub fn new(indicator: I, session: Session) -> Self {
+        Self {
+            indicator,
+            session,
+            current: None,
+        }
+    }
+
+    /// The wrapped indicator.
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+}
+
+impl<I: Checkpoint> Checkpoint for SessionReset<I> {
+    type State = SessionResetState<I::State>;
+
+    fn state(&self) -> Self::State {
+        SessionResetState {
+            indicator: self.indicator.state(),
+            session: self.session.clone(),
+            current: self.current,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(Self {
+            indicator: I::from_state(state.indicator)?,
+            session: state.session,
+            current: state.current,
+        })
+    }
+}
+
+impl_versioned_serde!(SessionReset<I>, 1);
+
+impl<I: Next<T> + Reset, T> Next<(i64, T)> for SessionReset<I> {
+    type Output = I::Output;
+
+    fn next(&mut self, (timestamp, input): (i64, T)) -> Self::Output {
+        let day = self.session.session_day(timestamp);
+        match self.current {
+            Some(current) if day > current => {
+                self.indicator.reset();
+                self.current = Some(day);
+            }
+            None => self.current = Some(day),
+            _ => {}
+        }
+        self.indicator.next(input)
+    }
+}
+
+impl<I: Peek<T> + Reset + Clone, T> Peek<(i64, T)> for SessionReset<I> {
+    fn peek(&self, (timestamp, input): (i64, T)) -> Self::Output {
+        let day = self.session.session_day(timestamp);
+        match self.current {
+            Some(current) if day > current => {
+                let mut indicator = self.indicator.clone();
+                indicator.reset();
+                indicator.peek(input)
+            }
+            _ => self.indicator.peek(input),
+        }
+    }
+}
+
+impl<I: Reset> Reset for SessionReset<I> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.current = None;
+    }
+}
+
+impl<I: Current> Current for SessionReset<I> {
+    type Output = I::Output;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.indicator.current()
+    }
+}
+
+impl<I: IsReady> IsReady for SessionReset<I> {
+    fn is_ready(&self) -> bool {
+        self.indicator.is_ready()
+    }
+}
+
+impl<I: Lookback> Lookback for SessionReset<I> {
+    fn lookback(&self) -> usize {
+        self.indicator.lookback()
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for SessionReset<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SESSION({}, {})", self.indicator, self.session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::SimpleMovingAverage as Sma;
+
+    // Monday 2024-01-01 00:00 UTC
+    const MONDAY: i64 = 1704067200;
+    const HOUR: i64 = 3600;
+    const DAY: i64 = 24 * HOUR;
+
+    #[test]
+    fn test_session_new() {
+        assert!(Session::new(0, 24, 0).is_err());
+        assert!(Session::new(0, 9, 60).is_err());
+        assert!(Session::new(19 * 60, 9, 30).is_err());
+        assert!(Session::new(-18 * 60, 23, 59).is_ok());
+    }
+
+    #[test]
+    fn test_holiday() {
+        let session = Session::new(0, 0, 0).unwrap();
+        assert!(session.clone().holiday(2023, 2, 29).is_err());
+        assert!(session.clone().holiday(2024, 2, 29).is_ok());
+        assert!(session.clone().holiday(2024, 13, 1).is_err());
+        assert!(session.clone().holiday(2024, 4, 31).is_err());
+        assert!(session.holiday(2024, 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_days_from_civil() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(days_from_civil(2024, 1, 1) * DAY, MONDAY);
+    }
+
+    #[test]
+    fn test_session_day() {
+        // 09:30 at UTC+1, which is 08:30 UTC
+        let session = Session::new(60, 9, 30).unwrap();
+        let monday = MONDAY / DAY;
+
+        assert_eq!(session.session_day(MONDAY + 8 * HOUR), monday - 3);
+        assert_eq!(session.session_day(MONDAY + 8 * HOUR + 1800), monday);
+        assert_eq!(session.session_day(MONDAY + DAY + 8 * HOUR), monday);
+        assert_eq!(session.session_day(MONDAY + DAY + 9 * HOUR), monday + 1);
+
+        // Friday session runs through the weekend
+        let friday = MONDAY + 4 * DAY + 9 * HOUR;
+        assert_eq!(session.session_day(friday), monday + 4);
+        assert_eq!(session.session_day(friday + DAY), monday + 4);
+        assert_eq!(session.session_day(friday + 2 * DAY), monday + 4);
+        assert_eq!(session.session_day(friday + 3 * DAY), monday + 7);
+    }
+
+    #[test]
+    fn test_weekends_and_holidays() {
+        let session = Session::new(0, 0, 0)
+            .unwrap()
+            .weekends(&[Weekday::Friday])
+            .holiday(2024, 1, 2)
+            .unwrap();
+        let monday = MONDAY / DAY;
+
+        assert_eq!(session.session_day(MONDAY + DAY), monday);
+        assert_eq!(session.session_day(MONDAY + 4 * DAY), monday + 3);
+        assert_eq!(session.session_day(MONDAY + 5 * DAY), monday + 5);
+    }
+
+    #[test]
+    fn test_next() {
+        let session = Session::new(0, 0, 0).unwrap();
+        let mut sma = SessionReset::new(Sma::new(3).unwrap(), session);
+
+        assert_eq!(sma.next((MONDAY + HOUR, 2.0)), 2.0);
+        assert_eq!(sma.next((MONDAY + 2 * HOUR, 4.0)), 3.0);
+        assert_eq!(sma.next((MONDAY + DAY, 10.0)), 10.0);
+
+        // Inputs from an earlier session do not reset
+        assert_eq!(sma.next((MONDAY + HOUR, 20.0)), 15.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let session = Session::new(0, 0, 0).unwrap();
+        let mut sma = SessionReset::new(Sma::new(3).unwrap(), session);
+
+        sma.next((MONDAY, 2.0));
+        sma.reset();
+        assert_eq!(sma.next((MONDAY, 4.0)), 4.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let session = Session::new(-5 * 60 - 30, 9, 5).unwrap();
+        assert_eq!(format!("{}", session), "-05:30 09:05");
+
+        let sma = SessionReset::new(Sma::new(9).unwrap(), session);
+        assert_eq!(format!("{}", sma), "SESSION(SMA(9), -05:30 09:05)");
+    }
+}
@@ -1,7 +1,10 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Numeric, Peek, Period, Reset,
+    SetPeriod,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -39,43 +42,63 @@ use serde::{Deserialize, Serialize};
 /// * [Simple Moving Average, Wikipedia](https://en.wikipedia.org/wiki/Moving_average#Simple_moving_average)
 ///
 #[doc(alias = "SMA")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct SimpleMovingAverage {
+pub struct SimpleMovingAverage<N = f64> {
     period: usize,
     index: usize,
     count: usize,
-    sum: f64,
-    deque: Box<[f64]>,
+    sum: N,
+    deque: Box<[N]>,
+    output: Option<N>,
+    // State before the last input, restored by `amend`
+    saved_sum: N,
+    saved_count: usize,
+    saved_value: N,
+}
+
+/// State of [SimpleMovingAverage](generic/struct.SimpleMovingAverage.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimpleMovingAverageState<N = f64> {
+    pub period: usize,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<N>,
+    /// Input that the last input pushed out of the window, if the window was full.
+    pub evicted: Option<N>,
+    /// Running sum of the window before the last input.
+    pub previous_sum: N,
 }
 
-impl SimpleMovingAverage {
+impl<N: Numeric> SimpleMovingAverage<N> {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
             _ => Ok(Self {
                 period,
                 index: 0,
                 count: 0,
-                sum: 0.0,
-                deque: vec![0.0; period].into_boxed_slice(),
+                sum: N::zero(),
+                deque: vec![N::zero(); period].into_boxed_slice(),
+                output: None,
+                saved_sum: N::zero(),
+                saved_count: 0,
+                saved_value: N::zero(),
             }),
         }
     }
-}
 
-impl Period for SimpleMovingAverage {
-    fn period(&self) -> usize {
-        self.period
+    /// Inputs in the window, the oldest first.
+    fn inputs(&self) -> impl Iterator<Item = N> + '_ {
+        let oldest = self.index + self.period - self.count;
+        (0..self.count).map(move |i| self.deque[(oldest + i) % self.period])
     }
-}
 
-impl Next<f64> for SimpleMovingAverage {
-    type Output = f64;
-
-    fn next(&mut self, input: f64) -> Self::Output {
+    fn next_value(&mut self, input: N) -> N {
         let old_val = self.deque[self.index];
         self.deque[self.index] = input;
+        self.saved_sum = self.sum;
+        self.saved_count = self.count;
+        self.saved_value = old_val;
 
         self.index = (self.index + 1) % self.period;
 
@@ -89,36 +112,151 @@ impl Next<f64> for SimpleMovingAverage {
         //     res
         // };
         self.sum = self.sum - old_val + input;
-        self.sum / (self.count as f64)
+        let output = self.sum / N::from_count(self.count);
+        self.output = Some(output);
+        output
+    }
+
+    fn peek_value(&self, input: N) -> N {
+        let count = (self.count + 1).min(self.period);
+        (self.sum - self.deque[self.index] + input) / N::from_count(count)
+    }
+
+    fn amend_value(&mut self, input: N) -> N {
+        if self.output.is_none() {
+            return self.next_value(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        self.deque[self.index] = self.saved_value;
+        self.sum = self.saved_sum;
+        self.count = self.saved_count;
+        self.next_value(input)
     }
 }
 
-impl<T: Close> Next<&T> for SimpleMovingAverage {
-    type Output = f64;
+impl<N> Period for SimpleMovingAverage<N> {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl<N: Numeric> Checkpoint for SimpleMovingAverage<N> {
+    type State = SimpleMovingAverageState<N>;
+
+    fn state(&self) -> Self::State {
+        SimpleMovingAverageState {
+            period: self.period,
+            inputs: self.inputs().collect(),
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous_sum: self.saved_sum,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let count = state.inputs.len();
+        if state.period == 0
+            || count > state.period
+            || (state.evicted.is_some() && count != state.period)
+            || (count == 0 && state.previous_sum != N::zero())
+        {
+            return Err(TaError::InvalidState);
+        }
+        // Rebuild the window before the last input and feed the last input again, so the
+        // running sum continues exactly and the last input can be amended
+        let mut indicator = Self::new(state.period)?;
+        if let Some((&last, previous)) = state.inputs.split_last() {
+            let window = state.evicted.iter().chain(previous);
+            for (slot, &input) in indicator.deque.iter_mut().zip(window) {
+                *slot = input;
+            }
+            indicator.count = previous.len() + state.evicted.iter().count();
+            indicator.index = indicator.count % indicator.period;
+            indicator.sum = state.previous_sum;
+            indicator.next_value(last);
+        }
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(SimpleMovingAverage<N>, 1);
+
+impl<N: Numeric> SetPeriod for SimpleMovingAverage<N> {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        // Replay the latest inputs that fit in the new window
+        let mut resized = Self::new(period)?;
+        for input in self.inputs().skip(self.count.saturating_sub(period)) {
+            resized.next_value(input);
+        }
+        *self = resized;
+        Ok(())
+    }
+}
+
+impl_numeric_inputs!(SimpleMovingAverage);
+
+impl<N: Numeric, T: Close<N>> Next<&T> for SimpleMovingAverage<N> {
+    type Output = N;
 
     fn next(&mut self, input: &T) -> Self::Output {
-        self.next(input.close())
+        self.next_value(input.close())
+    }
+}
+
+impl<N: Numeric, T: Close<N>> Peek<&T> for SimpleMovingAverage<N> {
+    fn peek(&self, input: &T) -> N {
+        self.peek_value(input.close())
     }
 }
 
-impl Reset for SimpleMovingAverage {
+impl<N: Numeric, T: Close<N>> Amend<&T> for SimpleMovingAverage<N> {
+    fn amend(&mut self, input: &T) -> N {
+        self.amend_value(input.close())
+    }
+}
+
+impl<N: Numeric> Reset for SimpleMovingAverage<N> {
     fn reset(&mut self) {
         self.index = 0;
         self.count = 0;
-        self.sum = 0.0;
+        self.sum = N::zero();
         for i in 0..self.period {
-            self.deque[i] = 0.0;
+            self.deque[i] = N::zero();
         }
+        self.output = None;
+        self.saved_sum = N::zero();
+        self.saved_count = 0;
+        self.saved_value = N::zero();
+    }
+}
+
+impl<N: Numeric> Current for SimpleMovingAverage<N> {
+    type Output = N;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<N> IsReady for SimpleMovingAverage<N> {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl<N> Lookback for SimpleMovingAverage<N> {
+    fn lookback(&self) -> usize {
+        self.period
     }
 }
 
-impl Default for SimpleMovingAverage {
+impl<N: Numeric> Default for SimpleMovingAverage<N> {
     fn default() -> Self {
         Self::new(9).unwrap()
     }
 }
 
-impl fmt::Display for SimpleMovingAverage {
+impl<N> fmt::Display for SimpleMovingAverage<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "SMA({})", self.period)
     }
@@ -127,6 +265,7 @@ impl fmt::Display for SimpleMovingAverage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::SimpleMovingAverage;
     use crate::test_helper::*;
 
     test_indicator!(SimpleMovingAverage);
@@ -149,6 +288,19 @@ mod tests {
         assert_eq!(sma.next(2.0), 5.0);
     }
 
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_next_decimal() {
+        use crate::indicators::generic::SimpleMovingAverage;
+        use rust_decimal::Decimal;
+
+        let mut sma = SimpleMovingAverage::<Decimal>::new(3).unwrap();
+        assert_eq!(sma.next(Decimal::new(1, 1)), Decimal::new(1, 1));
+        assert_eq!(sma.next(Decimal::new(2, 1)), Decimal::new(15, 2));
+        assert_eq!(sma.next(Decimal::new(3, 1)), Decimal::new(2, 1));
+        assert_eq!(sma.next(Decimal::new(4, 1)), Decimal::new(3, 1));
+    }
+
     #[test]
     fn test_next_with_bars() {
         fn bar(close: f64) -> Bar {
@@ -173,6 +325,22 @@ mod tests {
         assert_eq!(sma.next(99.0), 99.0);
     }
 
+    #[test]
+    fn test_is_ready() {
+        let mut sma = SimpleMovingAverage::new(3).unwrap();
+        assert!(!sma.is_ready());
+        sma.next(1.0);
+        sma.next(2.0);
+        assert!(!sma.is_ready());
+        sma.next(3.0);
+        assert!(sma.is_ready());
+        sma.next(4.0);
+        assert!(sma.is_ready());
+
+        sma.reset();
+        assert!(!sma.is_ready());
+    }
+
     #[test]
     fn test_default() {
         SimpleMovingAverage::default();
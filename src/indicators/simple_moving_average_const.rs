@@ -0,0 +1,308 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset};
+
+/// Simple moving average (SMA) with a period known at compile time.
+///
+/// Same as [SimpleMovingAverage](crate::indicators::SimpleMovingAverage), but the inputs are kept
+/// in an array of _N_ values instead of a heap allocated buffer, so it needs no allocation and
+/// is available without the `std` feature. Unlike the other indicators it can't be serialized.
+///
+/// # Parameters
+///
+/// * _N_ - number of periods (integer greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverageConst;
+/// use ta::Next;
+///
+/// let mut sma = SimpleMovingAverageConst::<3>::new().unwrap();
+/// assert_eq!(sma.next(10.0), 10.0);
+/// assert_eq!(sma.next(11.0), 10.5);
+/// assert_eq!(sma.next(12.0), 11.0);
+/// assert_eq!(sma.next(13.0), 12.0);
+/// ```
+#[doc(alias = "SMA")]
+#[derive(Debug, Clone)]
+pub struct SimpleMovingAverageConst<const N: usize> {
+    index: usize,
+    count: usize,
+    sum: f64,
+    deque: [f64; N],
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_sum: f64,
+    saved_count: usize,
+    saved_value: f64,
+}
+
+/// State of [SimpleMovingAverageConst](struct.SimpleMovingAverageConst.html), see
+/// [Checkpoint](../trait.Checkpoint.html). Kept in an array as well, so it needs no allocation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimpleMovingAverageConstState<const N: usize> {
+    /// Number of inputs in the window, up to _N_.
+    pub count: usize,
+    /// Inputs in the window, the oldest first, followed by zeros.
+    pub inputs: [f64; N],
+    /// Input that the last input pushed out of the window, if the window was full.
+    pub evicted: Option<f64>,
+    /// Running sum of the window before the last input.
+    pub previous_sum: f64,
+}
+
+impl<const N: usize> SimpleMovingAverageConst<N> {
+    pub fn new() -> Result<Self> {
+        match N {
+            0 => Err(TaError::invalid_parameter("N", 0.0, ">= 1")),
+            _ => Ok(Self {
+                index: 0,
+                count: 0,
+                sum: 0.0,
+                deque: [0.0; N],
+                output: None,
+                saved_sum: 0.0,
+                saved_count: 0,
+                saved_value: 0.0,
+            }),
+        }
+    }
+}
+
+impl<const N: usize> Period for SimpleMovingAverageConst<N> {
+    fn period(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Checkpoint for SimpleMovingAverageConst<N> {
+    type State = SimpleMovingAverageConstState<N>;
+
+    fn state(&self) -> Self::State {
+        let mut inputs = [0.0; N];
+        let oldest = self.index + N - self.count;
+        for (i, input) in inputs.iter_mut().take(self.count).enumerate() {
+            *input = self.deque[(oldest + i) % N];
+        }
+        SimpleMovingAverageConstState {
+            count: self.count,
+            inputs,
+            evicted: (self.count > 0 && self.saved_count == N).then_some(self.saved_value),
+            previous_sum: self.saved_sum,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let count = state.count;
+        if count > N
+            || (state.evicted.is_some() && count != N)
+            || (count == 0 && state.previous_sum != 0.0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        // Rebuild the window before the last input and feed the last input again, so the
+        // running sum continues exactly and the last input can be amended
+        let mut indicator = Self::new()?;
+        if let Some((&last, previous)) = state.inputs[..count].split_last() {
+            let window = state.evicted.iter().chain(previous);
+            for (slot, &input) in indicator.deque.iter_mut().zip(window) {
+                *slot = input;
+            }
+            indicator.count = previous.len() + state.evicted.iter().count();
+            indicator.index = indicator.count % N;
+            indicator.sum = state.previous_sum;
+            indicator.next(last);
+        }
+        Ok(indicator)
+    }
+}
+
+impl<const N: usize> Next<f64> for SimpleMovingAverageConst<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let old_val = self.deque[self.index];
+        self.deque[self.index] = input;
+        self.saved_sum = self.sum;
+        self.saved_count = self.count;
+        self.saved_value = old_val;
+
+        self.index = if self.index + 1 < N {
+            self.index + 1
+        } else {
+            0
+        };
+        self.count = (self.count + 1).min(N);
+
+        self.sum = self.sum - old_val + input;
+        let output = self.sum / (self.count as f64);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<const N: usize, T: Close> Next<&T> for SimpleMovingAverageConst<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl<const N: usize> Peek<f64> for SimpleMovingAverageConst<N> {
+    fn peek(&self, input: f64) -> f64 {
+        let count = (self.count + 1).min(N);
+        (self.sum - self.deque[self.index] + input) / (count as f64)
+    }
+}
+
+impl<const N: usize, T: Close> Peek<&T> for SimpleMovingAverageConst<N> {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl<const N: usize> Amend<f64> for SimpleMovingAverageConst<N> {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + N - 1) % N;
+        self.deque[self.index] = self.saved_value;
+        self.sum = self.saved_sum;
+        self.count = self.saved_count;
+        self.next(input)
+    }
+}
+
+impl<const N: usize, T: Close> Amend<&T> for SimpleMovingAverageConst<N> {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl<const N: usize> Reset for SimpleMovingAverageConst<N> {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.sum = 0.0;
+        self.deque = [0.0; N];
+        self.output = None;
+        self.saved_sum = 0.0;
+        self.saved_count = 0;
+        self.saved_value = 0.0;
+    }
+}
+
+impl<const N: usize> Current for SimpleMovingAverageConst<N> {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<const N: usize> IsReady for SimpleMovingAverageConst<N> {
+    fn is_ready(&self) -> bool {
+        self.count == N
+    }
+}
+
+impl<const N: usize> Lookback for SimpleMovingAverageConst<N> {
+    fn lookback(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Default for SimpleMovingAverageConst<N> {
+    fn default() -> Self {
+        Self::new().unwrap()
+    }
+}
+
+impl<const N: usize> fmt::Display for SimpleMovingAverageConst<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SMA({})", N)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    type SimpleMovingAverageConst9 = SimpleMovingAverageConst<9>;
+
+    test_indicator!(SimpleMovingAverageConst9);
+
+    #[test]
+    fn test_new() {
+        assert!(SimpleMovingAverageConst::<0>::new().is_err());
+        assert!(SimpleMovingAverageConst::<1>::new().is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut sma = SimpleMovingAverageConst::<4>::new().unwrap();
+        assert_eq!(sma.next(4.0), 4.0);
+        assert_eq!(sma.next(5.0), 4.5);
+        assert_eq!(sma.next(6.0), 5.0);
+        assert_eq!(sma.next(6.0), 5.25);
+        assert_eq!(sma.next(6.0), 5.75);
+        assert_eq!(sma.next(6.0), 6.0);
+        assert_eq!(sma.next(2.0), 5.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_same_as_dynamic() {
+        use crate::indicators::SimpleMovingAverage;
+
+        let mut sma = SimpleMovingAverageConst::<5>::new().unwrap();
+        let mut dynamic = SimpleMovingAverage::new(5).unwrap();
+
+        for i in 0..20 {
+            let input = (i * 7 % 11) as f64;
+            assert_eq!(sma.next(input), dynamic.next(input));
+            assert_eq!(sma.is_ready(), dynamic.is_ready());
+        }
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        fn bar(close: f64) -> Bar {
+            Bar::new().close(close)
+        }
+
+        let mut sma = SimpleMovingAverageConst::<3>::new().unwrap();
+        assert_eq!(sma.next(&bar(4.0)), 4.0);
+        assert_eq!(sma.next(&bar(4.0)), 4.0);
+        assert_eq!(sma.next(&bar(7.0)), 5.0);
+        assert_eq!(sma.next(&bar(1.0)), 4.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sma = SimpleMovingAverageConst::<4>::new().unwrap();
+        assert_eq!(sma.next(4.0), 4.0);
+        assert_eq!(sma.next(5.0), 4.5);
+        assert_eq!(sma.next(6.0), 5.0);
+
+        sma.reset();
+        assert!(!sma.is_ready());
+        assert_eq!(sma.next(99.0), 99.0);
+    }
+
+    #[test]
+    fn test_default() {
+        SimpleMovingAverageConst::<9>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let sma = SimpleMovingAverageConst::<5>::new().unwrap();
+        assert_eq!(format!("{}", sma), "SMA(5)");
+    }
+}
@@ -0,0 +1,320 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Simple return.
+///
+/// Relative change of the value compared to _lag_ bars ago, as a fraction
+/// (0.01 means +1%). See also [LogReturn](struct.LogReturn.html).
+///
+/// # Formula
+///
+/// R = p<sub>t</sub> / p<sub>t-n</sub> - 1
+///
+/// Where _n_ is the _lag_.
+///
+/// The return is 0 until _lag_ previous values are available, and when p<sub>t-n</sub> is 0.
+///
+/// # Parameters
+///
+/// * _lag_ - number of bars (integer greater than 0). Default is 1.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleReturn;
+/// use ta::Next;
+///
+/// let mut ret = SimpleReturn::new(1).unwrap();
+/// assert_eq!(ret.next(10.0), 0.0);
+/// assert_eq!(ret.next(12.5), 0.25);
+/// assert_eq!((ret.next(10.0) * 100.0).round(), -20.0);
+/// ```
+///
+/// # Links
+///
+/// * [Rate of return, Wikipedia](https://en.wikipedia.org/wiki/Rate_of_return)
+///
+#[derive(Debug, Clone)]
+pub struct SimpleReturn {
+    lag: usize,
+    index: usize,
+    count: usize,
+    deque: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+}
+
+/// State of [SimpleReturn](struct.SimpleReturn.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimpleReturnState {
+    pub lag: usize,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<f64>,
+    /// Last output, `None` before the first input.
+    pub output: Option<f64>,
+    /// Input which the last one pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
+}
+
+impl SimpleReturn {
+    pub fn new(lag: usize) -> Result<Self> {
+        match lag {
+            0 => Err(TaError::invalid_parameter("lag", 0.0, ">= 1")),
+            _ => Ok(Self {
+                lag,
+                index: 0,
+                count: 0,
+                deque: vec![0.0; lag].into_boxed_slice(),
+                output: None,
+                saved_value: 0.0,
+                saved_count: 0,
+            }),
+        }
+    }
+}
+
+impl Period for SimpleReturn {
+    fn period(&self) -> usize {
+        self.lag
+    }
+}
+
+impl Checkpoint for SimpleReturn {
+    type State = SimpleReturnState;
+
+    fn state(&self) -> Self::State {
+        SimpleReturnState {
+            lag: self.lag,
+            inputs: ring_values(&self.deque, self.index, self.count),
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count >= self.lag).then_some(self.saved_value),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.inputs.is_empty()
+            || (state.evicted.is_some() && state.inputs.len() != state.lag)
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.lag).map_err(|_| TaError::InvalidState)?;
+        let (deque, index) = ring_from_values(state.lag, &state.inputs)?;
+        indicator.deque = deque;
+        indicator.index = index;
+        indicator.count = state.inputs.len();
+        indicator.output = state.output;
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(SimpleReturn, 2);
+
+impl SetPeriod for SimpleReturn {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for SimpleReturn {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
+        let previous = self.deque[self.index];
+        self.deque[self.index] = input;
+
+        self.index = if self.index + 1 < self.lag {
+            self.index + 1
+        } else {
+            0
+        };
+
+        self.count = (self.count + 1).min(self.lag + 1);
+        let output = if self.count > self.lag && previous != 0.0 {
+            input / previous - 1.0
+        } else {
+            0.0
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for SimpleReturn {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for SimpleReturn {
+    fn peek(&self, input: f64) -> f64 {
+        let previous = self.deque[self.index];
+        if self.count + 1 > self.lag && previous != 0.0 {
+            input / previous - 1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for SimpleReturn {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for SimpleReturn {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.lag - 1) % self.lag;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for SimpleReturn {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for SimpleReturn {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        for i in 0..self.lag {
+            self.deque[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+    }
+}
+
+impl Current for SimpleReturn {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for SimpleReturn {
+    fn is_ready(&self) -> bool {
+        self.count > self.lag
+    }
+}
+
+impl Lookback for SimpleReturn {
+    fn lookback(&self) -> usize {
+        self.lag + 1
+    }
+}
+
+impl Default for SimpleReturn {
+    fn default() -> Self {
+        Self::new(1).unwrap()
+    }
+}
+
+impl fmt::Display for SimpleReturn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RET({})", self.lag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(SimpleReturn);
+
+    #[test]
+    fn test_new() {
+        assert!(SimpleReturn::new(0).is_err());
+        assert!(SimpleReturn::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut ret = SimpleReturn::new(1).unwrap();
+
+        assert_eq!(ret.next(10.0), 0.0);
+        assert_eq!(ret.next(12.5), 0.25);
+        assert_eq!(round(ret.next(10.0)), -0.2);
+        assert_eq!(ret.next(10.0), 0.0);
+    }
+
+    #[test]
+    fn test_next_with_lag() {
+        let mut ret = SimpleReturn::new(2).unwrap();
+
+        assert_eq!(ret.next(10.0), 0.0);
+        assert_eq!(ret.next(11.0), 0.0);
+        assert_eq!(round(ret.next(12.0)), 0.2);
+        assert_eq!(round(ret.next(8.8)), -0.2);
+        assert_eq!(round(ret.next(6.0)), -0.5);
+    }
+
+    #[test]
+    fn test_next_after_zero() {
+        let mut ret = SimpleReturn::new(1).unwrap();
+
+        ret.next(0.0);
+        assert_eq!(ret.next(5.0), 0.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut ret = SimpleReturn::new(1).unwrap();
+
+        assert_eq!(ret.next(&Bar::new().close(10)), 0.0);
+        assert_eq!(ret.next(&Bar::new().close(12.5)), 0.25);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ret = SimpleReturn::new(1).unwrap();
+
+        ret.next(10.0);
+        ret.next(12.5);
+
+        ret.reset();
+        assert_eq!(ret.next(12.5), 0.0);
+        assert_eq!(round(ret.next(10.0)), -0.2);
+    }
+
+    #[test]
+    fn test_default() {
+        SimpleReturn::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let ret = SimpleReturn::new(5).unwrap();
+        assert_eq!(format!("{}", ret), "RET(5)");
+    }
+}
@@ -1,8 +1,12 @@
-use std::fmt;
+use core::fmt;
 
-use crate::errors::Result;
-use crate::indicators::{ExponentialMovingAverage, FastStochastic};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::errors::{Result, TaError};
+use crate::indicators::{
+    ExponentialMovingAverage, ExponentialMovingAverageState, FastStochastic, FastStochasticState,
+};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -28,11 +32,23 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(stoch.next(30.0).round(), 31.0);
 /// assert_eq!(stoch.next(55.0).round(), 77.0);
 /// ```
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub struct SlowStochastic {
     fast_stochastic: FastStochastic,
     ema: ExponentialMovingAverage,
+    count: usize,
+    output: Option<f64>,
+}
+
+/// State of [SlowStochastic](struct.SlowStochastic.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlowStochasticState {
+    pub fast_stochastic: FastStochasticState,
+    pub ema: ExponentialMovingAverageState,
+    /// Number of inputs since the fast stochastic is ready, up to the EMA period.
+    pub count: usize,
 }
 
 impl SlowStochastic {
@@ -40,15 +56,56 @@ impl SlowStochastic {
         Ok(Self {
             fast_stochastic: FastStochastic::new(stochastic_period)?,
             ema: ExponentialMovingAverage::new(ema_period)?,
+            count: 0,
+            output: None,
         })
     }
+
+    fn smooth(&mut self, k: f64) -> f64 {
+        if self.fast_stochastic.is_ready() && !self.is_ready() {
+            self.count += 1;
+        }
+        self.ema.next(k)
+    }
 }
 
+impl Checkpoint for SlowStochastic {
+    type State = SlowStochasticState;
+
+    fn state(&self) -> Self::State {
+        SlowStochasticState {
+            fast_stochastic: self.fast_stochastic.state(),
+            ema: self.ema.state(),
+            count: self.count,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.fast_stochastic.value.is_some() != (state.ema.count > 0)
+            || state.count > state.ema.count
+        {
+            return Err(TaError::InvalidState);
+        }
+        let ema = ExponentialMovingAverage::from_state(state.ema)?;
+        Ok(Self {
+            fast_stochastic: FastStochastic::from_state(state.fast_stochastic)?,
+            output: ema.current(),
+            ema,
+            count: state.count,
+        })
+    }
+}
+
+impl_versioned_serde!(SlowStochastic, 1);
+
 impl Next<f64> for SlowStochastic {
     type Output = f64;
 
     fn next(&mut self, input: f64) -> Self::Output {
-        self.ema.next(self.fast_stochastic.next(input))
+        let k = self.fast_stochastic.next(input);
+        let output = self.smooth(k);
+        self.output = Some(output);
+        output
     }
 }
 
@@ -56,7 +113,44 @@ impl<T: High + Low + Close> Next<&T> for SlowStochastic {
     type Output = f64;
 
     fn next(&mut self, input: &T) -> Self::Output {
-        self.ema.next(self.fast_stochastic.next(input))
+        let k = self.fast_stochastic.next(input);
+        let output = self.smooth(k);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Peek<f64> for SlowStochastic {
+    fn peek(&self, input: f64) -> f64 {
+        self.ema.peek(self.fast_stochastic.peek(input))
+    }
+}
+
+impl<T: High + Low + Close> Peek<&T> for SlowStochastic {
+    fn peek(&self, input: &T) -> f64 {
+        self.ema.peek(self.fast_stochastic.peek(input))
+    }
+}
+
+impl Amend<f64> for SlowStochastic {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let output = self.ema.amend(self.fast_stochastic.amend(input));
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low + Close> Amend<&T> for SlowStochastic {
+    fn amend(&mut self, input: &T) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let output = self.ema.amend(self.fast_stochastic.amend(input));
+        self.output = Some(output);
+        output
     }
 }
 
@@ -64,6 +158,28 @@ impl Reset for SlowStochastic {
     fn reset(&mut self) {
         self.fast_stochastic.reset();
         self.ema.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl Current for SlowStochastic {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for SlowStochastic {
+    fn is_ready(&self) -> bool {
+        self.count == self.ema.period()
+    }
+}
+
+impl Lookback for SlowStochastic {
+    fn lookback(&self) -> usize {
+        self.fast_stochastic.lookback() + self.ema.period() - 1
     }
 }
 
@@ -139,6 +255,20 @@ mod tests {
         assert_eq!(stoch.next(10.0), 50.0);
     }
 
+    #[test]
+    fn test_is_ready() {
+        let mut stoch = SlowStochastic::new(3, 2).unwrap();
+        for input in [1.0, 2.0, 3.0] {
+            stoch.next(input);
+            assert!(!stoch.is_ready());
+        }
+        stoch.next(&Bar::new().high(4).low(1).close(4));
+        assert!(stoch.is_ready());
+
+        stoch.reset();
+        assert!(!stoch.is_ready());
+    }
+
     #[test]
     fn test_default() {
         SlowStochastic::default();
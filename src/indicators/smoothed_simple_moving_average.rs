@@ -1,7 +1,10 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Numeric, Peek, Period, Reset,
+    SetPeriod,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -41,79 +44,224 @@ use serde::{Deserialize, Serialize};
 /// * [Smoothed Moving Average, Investopedia](https://www.investopedia.com/terms/s/smoothed-moving-average-sma.asp)
 ///
 #[doc(alias = "SSMA")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct SmoothedSimpleMovingAverage {
-    period: usize,       // 计算周期
-    current_val: f64,    // 上一期的 SSMA 值（用于递推）
-    count: usize,        // 已接收的输入数据量
-    sum: f64,            // 初始阶段累加和（count ≤ period 时使用）
+pub struct SmoothedSimpleMovingAverage<N = f64> {
+    period: usize,  // 计算周期
+    current_val: N, // 上一期的 SSMA 值（用于递推）
+    count: usize,   // 初始阶段已接收的输入数据量，最多为 period
+    sum: N,         // 初始阶段累加和（count ≤ period 时使用）
+    output: Option<N>,
+    // State before the last input, restored by `amend`
+    saved_current_val: N,
+    saved_sum: N,
+    saved_count: usize,
+}
+
+/// State of [SmoothedSimpleMovingAverage](generic/struct.SmoothedSimpleMovingAverage.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmoothedSimpleMovingAverageState<N = f64> {
+    pub period: usize,
+    /// Number of inputs, up to the period.
+    pub count: usize,
+    /// Sum of the inputs, used until there are as many inputs as the period.
+    pub sum: N,
+    pub current: N,
+    /// Number of inputs before the last one, to amend it.
+    pub previous_count: usize,
+    /// Sum before the last input, to amend it.
+    pub previous_sum: N,
+    /// Value before the last input, to amend it.
+    pub previous: N,
 }
 
-impl SmoothedSimpleMovingAverage {
+impl<N: Numeric> SmoothedSimpleMovingAverage<N> {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter), // 周期不能为0，和SMA保持一致
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")), // 周期不能为0，和SMA保持一致
             _ => Ok(Self {
                 period,
-                current_val: 0.0, // 初始值设为0.0
-                count: 0,         // 初始无数据
-                sum: 0.0,         // 初始累加和为0.0
+                current_val: N::zero(), // 初始值设为0.0
+                count: 0,               // 初始无数据
+                sum: N::zero(),         // 初始累加和为0.0
+                output: None,
+                saved_current_val: N::zero(),
+                saved_sum: N::zero(),
+                saved_count: 0,
             }),
         }
     }
+
+    pub(super) fn next_value(&mut self, input: N) -> N {
+        self.saved_current_val = self.current_val;
+        self.saved_sum = self.sum;
+        self.saved_count = self.count;
+
+        // 核心逻辑：分阶段计算
+        if self.count < self.period {
+            // 阶段1：前N期，复用SMA逻辑（算术平均），保证初始平滑
+            self.count += 1;
+            self.sum += input; // 累加输入值（初始阶段用）
+            self.current_val = self.sum / N::from_count(self.count);
+        } else {
+            // 阶段2：第N+1期及以后，递推公式平滑更新
+            self.current_val = (self.current_val * N::from_count(self.period - 1) + input)
+                / N::from_count(self.period);
+        }
+
+        let output = self.current_val;
+        self.output = Some(output);
+        output
+    }
+
+    pub(super) fn peek_value(&self, input: N) -> N {
+        if self.count < self.period {
+            (self.sum + input) / N::from_count(self.count + 1)
+        } else {
+            (self.current_val * N::from_count(self.period - 1) + input) / N::from_count(self.period)
+        }
+    }
+
+    pub(super) fn amend_value(&mut self, input: N) -> N {
+        if self.output.is_none() {
+            return self.next_value(input);
+        }
+        self.current_val = self.saved_current_val;
+        self.sum = self.saved_sum;
+        self.count = self.saved_count;
+        self.next_value(input)
+    }
 }
 
-impl Period for SmoothedSimpleMovingAverage {
+impl<N: Numeric> Period for SmoothedSimpleMovingAverage<N> {
     fn period(&self) -> usize {
         self.period
     }
 }
 
-impl Next<f64> for SmoothedSimpleMovingAverage {
-    type Output = f64;
+impl<N: Numeric> Checkpoint for SmoothedSimpleMovingAverage<N> {
+    type State = SmoothedSimpleMovingAverageState<N>;
 
-    fn next(&mut self, input: f64) -> Self::Output {
-        self.count += 1;
-        self.sum += input; // 累加输入值（初始阶段用）
+    fn state(&self) -> Self::State {
+        SmoothedSimpleMovingAverageState {
+            period: self.period,
+            count: self.count,
+            sum: self.sum,
+            current: self.current_val,
+            previous_count: self.saved_count,
+            previous_sum: self.saved_sum,
+            previous: self.saved_current_val,
+        }
+    }
 
-        // 核心逻辑：分阶段计算
-        if self.count <= self.period {
-            // 阶段1：前N期，复用SMA逻辑（算术平均），保证初始平滑
-            self.current_val = self.sum / self.count as f64;
-        } else {
-            // 阶段2：第N+1期及以后，递推公式平滑更新
-            self.current_val = (self.current_val * (self.period - 1) as f64 + input) / self.period as f64;
+    fn from_state(state: Self::State) -> Result<Self> {
+        // The last input is either averaged, or smoothed once all of the period is averaged
+        let averaged = state.previous_count + 1 == state.count;
+        let smoothed = state.previous_count == state.period && state.count == state.period;
+        if state.period == 0
+            || state.count > state.period
+            || (state.count > 0 && !averaged && !smoothed)
+            || (state.count == 0
+                && (state.previous_count != 0
+                    || state.sum != N::zero()
+                    || state.current != N::zero()
+                    || state.previous_sum != N::zero()
+                    || state.previous != N::zero()))
+        {
+            return Err(TaError::InvalidState);
         }
+        let mut ssma = Self::new(state.period)?;
+        ssma.count = state.count;
+        ssma.sum = state.sum;
+        ssma.current_val = state.current;
+        ssma.output = (state.count > 0).then_some(state.current);
+        ssma.saved_count = state.previous_count;
+        ssma.saved_sum = state.previous_sum;
+        ssma.saved_current_val = state.previous;
+        Ok(ssma)
+    }
+}
 
-        self.current_val
+impl_versioned_serde!(SmoothedSimpleMovingAverage<N>, 2);
+
+impl<N: Numeric> SetPeriod for SmoothedSimpleMovingAverage<N> {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        match period {
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
+            _ => {
+                // 周期变短时初始阶段随之结束，之后从当前值递推
+                self.period = period;
+                self.count = self.count.min(period);
+                self.saved_count = self.saved_count.min(period);
+                Ok(())
+            }
+        }
     }
 }
 
+impl_numeric_inputs!(SmoothedSimpleMovingAverage);
+
 // 支持 Close 类型输入（和SMA保持API兼容）
-impl<T: Close> Next<&T> for SmoothedSimpleMovingAverage {
-    type Output = f64;
+impl<N: Numeric, T: Close<N>> Next<&T> for SmoothedSimpleMovingAverage<N> {
+    type Output = N;
 
     fn next(&mut self, input: &T) -> Self::Output {
-        self.next(input.close())
+        self.next_value(input.close())
+    }
+}
+
+impl<N: Numeric, T: Close<N>> Peek<&T> for SmoothedSimpleMovingAverage<N> {
+    fn peek(&self, input: &T) -> N {
+        self.peek_value(input.close())
     }
 }
 
-impl Reset for SmoothedSimpleMovingAverage {
+impl<N: Numeric, T: Close<N>> Amend<&T> for SmoothedSimpleMovingAverage<N> {
+    fn amend(&mut self, input: &T) -> N {
+        self.amend_value(input.close())
+    }
+}
+
+impl<N: Numeric> Reset for SmoothedSimpleMovingAverage<N> {
     fn reset(&mut self) {
-        self.current_val = 0.0; // 重置当前值
-        self.count = 0;         // 重置计数
-        self.sum = 0.0;         // 重置累加和
+        self.current_val = N::zero(); // 重置当前值
+        self.count = 0; // 重置计数
+        self.sum = N::zero(); // 重置累加和
+        self.output = None;
+        self.saved_current_val = N::zero();
+        self.saved_sum = N::zero();
+        self.saved_count = 0;
+    }
+}
+
+impl<N: Numeric> Current for SmoothedSimpleMovingAverage<N> {
+    type Output = N;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<N: Numeric> IsReady for SmoothedSimpleMovingAverage<N> {
+    fn is_ready(&self) -> bool {
+        self.count >= self.period
+    }
+}
+
+impl<N: Numeric> Lookback for SmoothedSimpleMovingAverage<N> {
+    fn lookback(&self) -> usize {
+        self.period
     }
 }
 
-impl Default for SmoothedSimpleMovingAverage {
+impl<N: Numeric> Default for SmoothedSimpleMovingAverage<N> {
     fn default() -> Self {
         Self::new(9).unwrap() // 默认周期9，和SMA保持一致
     }
 }
 
-impl fmt::Display for SmoothedSimpleMovingAverage {
+impl<N: Numeric> fmt::Display for SmoothedSimpleMovingAverage<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "SSMA({})", self.period) // 显示格式：SSMA(周期)
     }
@@ -122,6 +270,7 @@ impl fmt::Display for SmoothedSimpleMovingAverage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::SmoothedSimpleMovingAverage;
     use crate::test_helper::*;
 
     test_indicator!(SmoothedSimpleMovingAverage);
@@ -139,9 +288,9 @@ mod tests {
         let mut ssma = SmoothedSimpleMovingAverage::new(3).unwrap();
 
         // 阶段1：前3期（count ≤ period），和SMA结果一致
-        assert_eq!(ssma.next(10.0), 10.0);    // count=1: 10/1
-        assert_eq!(ssma.next(11.0), 10.5);   // count=2: (10+11)/2
-        assert_eq!(ssma.next(12.0), 11.0);   // count=3: (10+11+12)/3
+        assert_eq!(ssma.next(10.0), 10.0); // count=1: 10/1
+        assert_eq!(ssma.next(11.0), 10.5); // count=2: (10+11)/2
+        assert_eq!(ssma.next(12.0), 11.0); // count=3: (10+11+12)/3
 
         // 阶段2：第4期及以后（递推公式）
         assert!((ssma.next(13.0) - 11.666666666666666).abs() < 1e-9); // (11×2 +13)/3 ≈11.666...
@@ -149,6 +298,19 @@ mod tests {
         assert!((ssma.next(15.0) - 13.296296296296296).abs() < 1e-9); // (12.444...×2 +15)/3 ≈13.296...
     }
 
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_next_decimal() {
+        use crate::indicators::generic::SmoothedSimpleMovingAverage;
+        use rust_decimal::Decimal;
+
+        let mut ssma = SmoothedSimpleMovingAverage::<Decimal>::new(3).unwrap();
+        assert_eq!(ssma.next(Decimal::new(3, 1)), Decimal::new(3, 1));
+        assert_eq!(ssma.next(Decimal::new(6, 1)), Decimal::new(45, 2));
+        assert_eq!(ssma.next(Decimal::new(9, 1)), Decimal::new(6, 1));
+        assert_eq!(ssma.next(Decimal::new(15, 1)), Decimal::new(9, 1));
+    }
+
     #[test]
     fn test_next_with_bars() {
         // 测试支持 Close 类型输入（和SMA测试风格一致）
@@ -157,11 +319,11 @@ mod tests {
         }
 
         let mut ssma = SmoothedSimpleMovingAverage::new(4).unwrap();
-        assert_eq!(ssma.next(&bar(4.0)), 4.0);    // count=1
-        assert_eq!(ssma.next(&bar(5.0)), 4.5);   // count=2
-        assert_eq!(ssma.next(&bar(6.0)), 5.0);   // count=3
-        assert_eq!(ssma.next(&bar(6.0)), 5.25);  // count=4（初始阶段结束）
-        assert_eq!(ssma.next(&bar(6.0)), 5.75);  // count=5: (5.25×3 +6)/4=5.75
+        assert_eq!(ssma.next(&bar(4.0)), 4.0); // count=1
+        assert_eq!(ssma.next(&bar(5.0)), 4.5); // count=2
+        assert_eq!(ssma.next(&bar(6.0)), 5.0); // count=3
+        assert_eq!(ssma.next(&bar(6.0)), 5.25); // count=4（初始阶段结束）
+        assert_eq!(ssma.next(&bar(6.0)), 5.75); // count=5: (5.25×3 +6)/4=5.75
         assert_eq!(ssma.next(&bar(2.0)), 4.8125); // count=6: (5.75×3 +2)/4=4.8125
     }
 
@@ -178,6 +340,26 @@ mod tests {
         assert_eq!(ssma.next(99.0), 99.0); // 重置后第一期：99/1
     }
 
+    #[test]
+    fn test_set_period() {
+        let mut ssma = SmoothedSimpleMovingAverage::new(2).unwrap();
+        ssma.next(10.0);
+        assert_eq!(ssma.next(20.0), 15.0);
+
+        // 周期变长时继续累加平均
+        ssma.set_period(4).unwrap();
+        assert!(!ssma.is_ready());
+        assert_eq!(ssma.next(30.0), 20.0);
+
+        // 周期变短时从当前值递推
+        ssma.set_period(2).unwrap();
+        assert!(ssma.is_ready());
+        assert_eq!(ssma.next(40.0), 30.0);
+
+        assert!(ssma.set_period(0).is_err());
+        assert_eq!(ssma.period(), 2);
+    }
+
     #[test]
     fn test_default() {
         // 默认周期应为9，且初始化不报错
@@ -199,4 +381,4 @@ mod tests {
         assert_eq!(ssma.next(200.0), 200.0);
         assert_eq!(ssma.next(300.0), 300.0);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,363 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{Amend, Checkpoint, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Sortino ratio.
+///
+/// Risk adjusted return over the last _period_ bars. Unlike the Sharpe ratio it only
+/// penalizes returns below the target, so upside volatility does not lower the ratio.
+///
+/// The input is a stream of per-bar returns (e.g. 0.01 for +1%), not prices.
+///
+/// # Formula
+///
+/// Sortino = (mean(r) - _target_) / DD
+///
+/// DD = √(Σ min(0, r<sub>i</sub> - _target_)<sup>2</sup> / n)
+///
+/// Where:
+///
+/// * _r<sub>i</sub>_ - return of the i-th bar in the window
+/// * _n_ - number of returns in the window
+/// * _DD_ - downside deviation
+///
+/// When there are no returns below the target (DD is 0), the ratio is 0.
+///
+/// # Parameters
+///
+/// * _period_ - number of returns (integer greater than 0). Default is 20.
+/// * _target_ - minimal acceptable return per bar. Default is 0.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SortinoRatio;
+/// use ta::Next;
+///
+/// let mut sortino = SortinoRatio::new(3, 0.0).unwrap();
+/// assert_eq!(sortino.next(0.02), 0.0);
+/// assert_eq!((sortino.next(-0.01) * 1000.0).round(), 707.0);
+/// ```
+///
+/// # Links
+///
+/// * [Sortino ratio, Wikipedia](https://en.wikipedia.org/wiki/Sortino_ratio)
+///
+#[derive(Debug, Clone)]
+pub struct SortinoRatio {
+    period: usize,
+    target: f64,
+    index: usize,
+    count: usize,
+    sum: f64,
+    sum_downside_sq: f64,
+    deque: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+    saved_sum: f64,
+    saved_sum_downside_sq: f64,
+}
+
+/// State of [SortinoRatio](struct.SortinoRatio.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortinoRatioState {
+    pub period: usize,
+    pub target: f64,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<f64>,
+    pub sum: f64,
+    /// Running sum of the squared shortfalls below the target.
+    pub sum_downside_sq: f64,
+    pub output: Option<f64>,
+    /// Input which the last one pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
+    /// Running sums before the last input, to amend it.
+    pub previous_sum: f64,
+    pub previous_sum_downside_sq: f64,
+}
+
+impl SortinoRatio {
+    pub fn new(period: usize, target: f64) -> Result<Self> {
+        if period == 0 {
+            return Err(TaError::invalid_parameter("period", 0.0, ">= 1"));
+        }
+        if !target.is_finite() {
+            return Err(TaError::invalid_parameter("target", target, "finite"));
+        }
+        Ok(Self {
+            period,
+            target,
+            index: 0,
+            count: 0,
+            sum: 0.0,
+            sum_downside_sq: 0.0,
+            deque: vec![0.0; period].into_boxed_slice(),
+            output: None,
+            saved_value: 0.0,
+            saved_count: 0,
+            saved_sum: 0.0,
+            saved_sum_downside_sq: 0.0,
+        })
+    }
+
+    pub fn target(&self) -> f64 {
+        self.target
+    }
+
+    fn downside_sq(&self, value: f64) -> f64 {
+        let shortfall = (value - self.target).min(0.0);
+        shortfall * shortfall
+    }
+}
+
+impl Period for SortinoRatio {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for SortinoRatio {
+    type State = SortinoRatioState;
+
+    fn state(&self) -> Self::State {
+        SortinoRatioState {
+            period: self.period,
+            target: self.target,
+            inputs: ring_values(&self.deque, self.index, self.count),
+            sum: self.sum,
+            sum_downside_sq: self.sum_downside_sq,
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous_sum: self.saved_sum,
+            previous_sum_downside_sq: self.saved_sum_downside_sq,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.inputs.is_empty() {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator =
+            Self::new(state.period, state.target).map_err(|_| TaError::InvalidState)?;
+        let (deque, index) = ring_from_values(state.period, &state.inputs)?;
+        indicator.deque = deque;
+        indicator.index = index;
+        indicator.count = state.inputs.len();
+        indicator.sum = state.sum;
+        indicator.sum_downside_sq = state.sum_downside_sq;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_sum = state.previous_sum;
+        indicator.saved_sum_downside_sq = state.previous_sum_downside_sq;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(SortinoRatio, 2);
+
+impl SetPeriod for SortinoRatio {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period, self.target)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for SortinoRatio {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
+        self.saved_sum = self.sum;
+        self.saved_sum_downside_sq = self.sum_downside_sq;
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            let old_val = self.deque[self.index];
+            self.sum -= old_val;
+            self.sum_downside_sq -= self.downside_sq(old_val);
+        }
+        self.sum += input;
+        self.sum_downside_sq += self.downside_sq(input);
+        self.deque[self.index] = input;
+
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        let n = self.count as f64;
+        let downside_deviation = (self.sum_downside_sq.max(0.0) / n).sqrt();
+
+        let output = if downside_deviation > 0.0 {
+            (self.sum / n - self.target) / downside_deviation
+        } else {
+            0.0
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Peek<f64> for SortinoRatio {
+    fn peek(&self, input: f64) -> f64 {
+        let (count, mut sum, mut sum_downside_sq) = if self.count < self.period {
+            (self.count + 1, self.sum, self.sum_downside_sq)
+        } else {
+            let old_val = self.deque[self.index];
+            (
+                self.count,
+                self.sum - old_val,
+                self.sum_downside_sq - self.downside_sq(old_val),
+            )
+        };
+        sum += input;
+        sum_downside_sq += self.downside_sq(input);
+
+        let n = count as f64;
+        let downside_deviation = (sum_downside_sq.max(0.0) / n).sqrt();
+        if downside_deviation > 0.0 {
+            (sum / n - self.target) / downside_deviation
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Amend<f64> for SortinoRatio {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.sum = self.saved_sum;
+        self.sum_downside_sq = self.saved_sum_downside_sq;
+        self.next(input)
+    }
+}
+
+impl Reset for SortinoRatio {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.sum = 0.0;
+        self.sum_downside_sq = 0.0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+        self.saved_sum = 0.0;
+        self.saved_sum_downside_sq = 0.0;
+    }
+}
+
+impl Current for SortinoRatio {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for SortinoRatio {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for SortinoRatio {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for SortinoRatio {
+    fn default() -> Self {
+        Self::new(20, 0.0).unwrap()
+    }
+}
+
+impl fmt::Display for SortinoRatio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SORTINO({}, {})", self.period, self.target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(SortinoRatio::new(0, 0.0).is_err());
+        assert!(SortinoRatio::new(3, f64::NAN).is_err());
+        assert!(SortinoRatio::new(1, 0.0).is_ok());
+        assert!(SortinoRatio::new(1, -0.01).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut sortino = SortinoRatio::new(3, 0.0).unwrap();
+
+        assert_eq!(sortino.next(0.02), 0.0);
+        assert_eq!(round(sortino.next(-0.01)), 0.707);
+        assert_eq!(round(sortino.next(0.03)), 2.309);
+        assert_eq!(round(sortino.next(-0.02)), 0.0);
+        assert_eq!(round(sortino.next(0.01)), 0.577);
+    }
+
+    #[test]
+    fn test_next_with_target() {
+        let mut sortino = SortinoRatio::new(3, 0.01).unwrap();
+
+        assert_eq!(sortino.next(0.02), 0.0);
+        assert_eq!(round(sortino.next(-0.01)), -0.354);
+        assert_eq!(round(sortino.next(0.03)), 0.289);
+        assert_eq!(round(sortino.next(-0.02)), -0.48);
+        assert_eq!(round(sortino.next(0.01)), -0.192);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sortino = SortinoRatio::new(3, 0.0).unwrap();
+
+        sortino.next(-0.05);
+        sortino.next(0.02);
+
+        sortino.reset();
+        assert_eq!(sortino.next(0.02), 0.0);
+        assert_eq!(round(sortino.next(-0.01)), 0.707);
+    }
+
+    #[test]
+    fn test_default() {
+        SortinoRatio::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let sortino = SortinoRatio::new(20, 0.01).unwrap();
+        assert_eq!(format!("{}", sortino), "SORTINO(20, 0.01)");
+    }
+}
@@ -0,0 +1,469 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Z-score of the spread between two series (SPREADZ).
+///
+/// The core of pairs trading. Regresses the first series on the second one over the last
+/// _period_ pairs, which gives the hedge ratio, and measures how far the current pair is from
+/// the regression line in standard deviations of the residuals. A high z-score means that the
+/// first series is expensive relative to the second one.
+///
+/// Like [Correlation](struct.Correlation.html), it consumes pairs of values: `(f64, f64)` or
+/// `(&T, &U)`, where both `T` and `U` implement [Close](crate::Close). The inputs are usually
+/// prices or log prices.
+///
+/// # Formula
+///
+/// Hedge ratio = Cov(a, b) / Var(b)
+///
+/// Intercept = Mean(a) - Hedge ratio * Mean(b)
+///
+/// Spread = a - Hedge ratio * b - Intercept
+///
+/// Z-score = Spread / √(Var(a) - Cov(a, b)<sup>2</sup> / Var(b))
+///
+/// Where the population covariance and variances are calculated over the window, which
+/// contains fewer than _period_ pairs during the first bars. The denominator is the standard
+/// deviation of the residuals in the window.
+///
+/// When _b_ did not change within the window, the hedge ratio is 0. When the pairs lie on a
+/// line, the z-score is 0.
+///
+/// # Parameters
+///
+/// * _period_ - number of pairs in the window (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SpreadZScore;
+/// use ta::Next;
+///
+/// let mut spread = SpreadZScore::new(3).unwrap();
+///
+/// spread.next((2.0, 1.0));
+/// let out = spread.next((4.0, 2.0));
+/// assert_eq!(out.hedge_ratio, 2.0);
+/// assert_eq!(out.zscore, 0.0);
+///
+/// let out = spread.next((5.0, 2.0));
+/// assert_eq!((out.zscore * 1000.0).round(), 1225.0);
+/// ```
+///
+/// # Links
+///
+/// * [Pairs trade, Wikipedia](https://en.wikipedia.org/wiki/Pairs_trade)
+///
+#[doc(alias = "SPREADZ")]
+#[derive(Debug, Clone)]
+pub struct SpreadZScore {
+    period: usize,
+    index: usize,
+    count: usize,
+    sum_a: f64,
+    sum_b: f64,
+    sum_ab: f64,
+    sum_aa: f64,
+    sum_bb: f64,
+    a: Box<[f64]>,
+    b: Box<[f64]>,
+    output: Option<SpreadZScoreOutput>,
+    // State before the last input, restored by `amend`
+    saved_value: (f64, f64),
+    saved_count: usize,
+    saved_sums: [f64; 5],
+}
+
+/// State of [SpreadZScore](struct.SpreadZScore.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpreadZScoreState {
+    pub period: usize,
+    /// First inputs of the pairs in the window, the oldest first.
+    pub a: Vec<f64>,
+    /// Second inputs of the pairs in the window, the oldest first.
+    pub b: Vec<f64>,
+    pub sum_a: f64,
+    pub sum_b: f64,
+    pub sum_ab: f64,
+    pub sum_aa: f64,
+    pub sum_bb: f64,
+    pub output: Option<SpreadZScoreOutput>,
+    /// Pair which the last input pushed out of the window, to amend it.
+    pub evicted: Option<(f64, f64)>,
+    /// Running sums before the last input, to amend it.
+    pub previous_sums: [f64; 5],
+}
+
+/// Output of [SpreadZScore](struct.SpreadZScore.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpreadZScoreOutput {
+    pub hedge_ratio: f64,
+    pub intercept: f64,
+    /// Residual of the current pair.
+    pub spread: f64,
+    pub zscore: f64,
+}
+
+impl_output!(SpreadZScoreOutput, hedge_ratio, intercept, spread, zscore);
+
+impl SpreadZScore {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                sum_a: 0.0,
+                sum_b: 0.0,
+                sum_ab: 0.0,
+                sum_aa: 0.0,
+                sum_bb: 0.0,
+                a: vec![0.0; period].into_boxed_slice(),
+                b: vec![0.0; period].into_boxed_slice(),
+                output: None,
+                saved_value: (0.0, 0.0),
+                saved_count: 0,
+                saved_sums: [0.0; 5],
+            }),
+        }
+    }
+
+    // Σa, Σb, Σab, Σa² and Σb² of the window after the pair entered it. The old values are
+    // zeros until the window is full.
+    fn sums_with(&self, (a, b): (f64, f64)) -> [f64; 5] {
+        let old_a = self.a[self.index];
+        let old_b = self.b[self.index];
+        [
+            self.sum_a + (a - old_a),
+            self.sum_b + (b - old_b),
+            self.sum_ab + (a * b - old_a * old_b),
+            self.sum_aa + (a * a - old_a * old_a),
+            self.sum_bb + (b * b - old_b * old_b),
+        ]
+    }
+
+    // Regression of a on b over the window with the given sums, and the spread of the newest pair
+    fn spread(
+        count: usize,
+        [sum_a, sum_b, sum_ab, sum_aa, sum_bb]: [f64; 5],
+        (a, b): (f64, f64),
+    ) -> SpreadZScoreOutput {
+        let n = count as f64;
+        let mean_a = sum_a / n;
+        let mean_b = sum_b / n;
+        let covariance = sum_ab / n - mean_a * mean_b;
+        let variance_a = (sum_aa / n - mean_a * mean_a).max(0.0);
+        let variance_b = (sum_bb / n - mean_b * mean_b).max(0.0);
+
+        let (hedge_ratio, residual_variance) = if variance_b > 0.0 {
+            let hedge_ratio = covariance / variance_b;
+            (hedge_ratio, variance_a - covariance * hedge_ratio)
+        } else {
+            (0.0, variance_a)
+        };
+        let intercept = mean_a - hedge_ratio * mean_b;
+        let spread = a - hedge_ratio * b - intercept;
+
+        // guard against rounding errors when the pairs lie on a line
+        let zscore = if residual_variance > 1e-12 * variance_a {
+            spread / residual_variance.sqrt()
+        } else {
+            0.0
+        };
+
+        SpreadZScoreOutput {
+            hedge_ratio,
+            intercept,
+            spread,
+            zscore,
+        }
+    }
+}
+
+impl Period for SpreadZScore {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for SpreadZScore {
+    type State = SpreadZScoreState;
+
+    fn state(&self) -> Self::State {
+        SpreadZScoreState {
+            period: self.period,
+            a: ring_values(&self.a, self.index, self.count),
+            b: ring_values(&self.b, self.index, self.count),
+            sum_a: self.sum_a,
+            sum_b: self.sum_b,
+            sum_ab: self.sum_ab,
+            sum_aa: self.sum_aa,
+            sum_bb: self.sum_bb,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous_sums: self.saved_sums,
+            output: self.output.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.a.is_empty() || state.b.len() != state.a.len() {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let (a, index) = ring_from_values(state.period, &state.a)?;
+        let (b, _) = ring_from_values(state.period, &state.b)?;
+        indicator.a = a;
+        indicator.b = b;
+        indicator.index = index;
+        indicator.count = state.a.len();
+        indicator.sum_a = state.sum_a;
+        indicator.sum_b = state.sum_b;
+        indicator.sum_ab = state.sum_ab;
+        indicator.sum_aa = state.sum_aa;
+        indicator.sum_bb = state.sum_bb;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or((0.0, 0.0));
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_sums = state.previous_sums;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(SpreadZScore, 2);
+
+impl SetPeriod for SpreadZScore {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<(f64, f64)> for SpreadZScore {
+    type Output = SpreadZScoreOutput;
+
+    fn next(&mut self, (a, b): (f64, f64)) -> Self::Output {
+        self.saved_value = (self.a[self.index], self.b[self.index]);
+        self.saved_count = self.count;
+        self.saved_sums = [
+            self.sum_a,
+            self.sum_b,
+            self.sum_ab,
+            self.sum_aa,
+            self.sum_bb,
+        ];
+        let sums = self.sums_with((a, b));
+        [
+            self.sum_a,
+            self.sum_b,
+            self.sum_ab,
+            self.sum_aa,
+            self.sum_bb,
+        ] = sums;
+        self.a[self.index] = a;
+        self.b[self.index] = b;
+
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < self.period {
+            self.count += 1;
+        }
+
+        let output = Self::spread(self.count, sums, (a, b));
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Close, U: Close> Next<(&T, &U)> for SpreadZScore {
+    type Output = SpreadZScoreOutput;
+
+    fn next(&mut self, (a, b): (&T, &U)) -> Self::Output {
+        self.next((a.close(), b.close()))
+    }
+}
+
+impl Peek<(f64, f64)> for SpreadZScore {
+    fn peek(&self, input: (f64, f64)) -> SpreadZScoreOutput {
+        let count = (self.count + 1).min(self.period);
+        Self::spread(count, self.sums_with(input), input)
+    }
+}
+
+impl<T: Close, U: Close> Peek<(&T, &U)> for SpreadZScore {
+    fn peek(&self, (a, b): (&T, &U)) -> SpreadZScoreOutput {
+        self.peek((a.close(), b.close()))
+    }
+}
+
+impl Amend<(f64, f64)> for SpreadZScore {
+    fn amend(&mut self, input: (f64, f64)) -> SpreadZScoreOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        (self.a[self.index], self.b[self.index]) = self.saved_value;
+        self.count = self.saved_count;
+        [
+            self.sum_a,
+            self.sum_b,
+            self.sum_ab,
+            self.sum_aa,
+            self.sum_bb,
+        ] = self.saved_sums;
+        self.next(input)
+    }
+}
+
+impl<T: Close, U: Close> Amend<(&T, &U)> for SpreadZScore {
+    fn amend(&mut self, (a, b): (&T, &U)) -> SpreadZScoreOutput {
+        self.amend((a.close(), b.close()))
+    }
+}
+
+impl Reset for SpreadZScore {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.sum_a = 0.0;
+        self.sum_b = 0.0;
+        self.sum_ab = 0.0;
+        self.sum_aa = 0.0;
+        self.sum_bb = 0.0;
+        for i in 0..self.period {
+            self.a[i] = 0.0;
+            self.b[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = (0.0, 0.0);
+        self.saved_count = 0;
+        self.saved_sums = [0.0; 5];
+    }
+}
+
+impl Current for SpreadZScore {
+    type Output = SpreadZScoreOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for SpreadZScore {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for SpreadZScore {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for SpreadZScore {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for SpreadZScore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SPREADZ({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn output(out: SpreadZScoreOutput) -> (f64, f64, f64, f64) {
+        (
+            round(out.hedge_ratio),
+            round(out.intercept),
+            round(out.spread),
+            round(out.zscore),
+        )
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(SpreadZScore::new(0).is_err());
+        assert!(SpreadZScore::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut spread = SpreadZScore::new(3).unwrap();
+
+        assert_eq!(output(spread.next((2.0, 1.0))), (0.0, 2.0, 0.0, 0.0));
+        assert_eq!(output(spread.next((4.0, 2.0))), (2.0, 0.0, 0.0, 0.0));
+        assert_eq!(output(spread.next((5.0, 2.0))), (2.5, -0.5, 0.5, 1.225));
+        assert_eq!(output(spread.next((5.0, 3.0))), (0.5, 3.5, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_next_flat_b() {
+        let mut spread = SpreadZScore::new(3).unwrap();
+
+        spread.next((1.0, 5.0));
+        spread.next((2.0, 5.0));
+        // residuals are the deviations of a from its mean
+        assert_eq!(output(spread.next((3.0, 5.0))), (0.0, 2.0, 1.0, 1.225));
+    }
+
+    #[test]
+    fn test_next_bars() {
+        let mut spread = SpreadZScore::new(3).unwrap();
+
+        let a1 = Bar::new().close(2);
+        let a2 = Bar::new().close(4);
+        let b1 = Bar::new().close(1);
+        let b2 = Bar::new().close(2);
+
+        spread.next((&a1, &b1));
+        assert_eq!(spread.next((&a2, &b2)).hedge_ratio, 2.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut spread = SpreadZScore::new(3).unwrap();
+
+        spread.next((2.0, 1.0));
+        spread.next((4.0, 2.0));
+
+        spread.reset();
+        assert_eq!(output(spread.next((3.0, 1.0))), (0.0, 3.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_default() {
+        SpreadZScore::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let spread = SpreadZScore::new(20).unwrap();
+        assert_eq!(format!("{}", spread), "SPREADZ(20)");
+    }
+}
@@ -0,0 +1,443 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{
+    BollingerBands, BollingerBandsState, KeltnerChannel, KeltnerChannelState, LinearRegression,
+    LinearRegressionState, Maximum, MaximumState, Minimum, MinimumState,
+    SimpleMovingAverage as Sma, SimpleMovingAverageState,
+};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Squeeze Momentum (TTM Squeeze).
+///
+/// Detects periods of low volatility, when the [Bollinger Bands](type.BollingerBands.html) are
+/// inside the [Keltner Channel](struct.KeltnerChannel.html), and measures the momentum which is
+/// expected to drive the breakout once the squeeze is released.
+///
+/// # Formula
+///
+/// Squeeze is on when BB<sub>Lower</sub> > KC<sub>Lower</sub> and BB<sub>Upper</sub> < KC<sub>Upper</sub>
+///
+/// Delta = Close - ((max(High) + min(Low)) / 2 + SMA(Close)) / 2
+///
+/// Momentum = [linear regression](struct.LinearRegression.html) of Delta, evaluated at the current bar
+///
+/// Where the highest high, the lowest low, the SMA and the linear regression are calculated
+/// over the last _kc_period_ bars.
+///
+/// # Parameters
+///
+/// * _bb_period_ - period of the Bollinger Bands (integer greater than 0). Default is 20.
+/// * _bb_multiplier_ - standard deviation multiplier of the Bollinger Bands. Default is 2.0.
+/// * _kc_period_ - period of the Keltner Channel and the momentum (integer greater than 0). Default is 20.
+/// * _kc_multiplier_ - ATR multiplier of the Keltner Channel. Default is 1.5.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SqueezeMomentum;
+/// use ta::{Next, DataItem};
+///
+/// fn bar(high: f64, low: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .high(high).low(low).close(close).open(close)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut sqz = SqueezeMomentum::new(3, 2.0, 3, 1.5).unwrap();
+///
+/// sqz.next(&bar(10.5, 9.5, 10.0));
+/// sqz.next(&bar(12.5, 11.5, 12.0));
+/// let out = sqz.next(&bar(14.5, 13.5, 14.0));
+///
+/// assert!(!out.squeeze_on);
+/// assert_eq!(out.momentum, 2.0);
+/// ```
+///
+/// # Links
+///
+/// * [TTM Squeeze, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:ttm_squeeze)
+///
+#[doc(alias = "TTM Squeeze")]
+#[doc(alias = "SQZMOM")]
+#[derive(Debug, Clone)]
+pub struct SqueezeMomentum {
+    bb: BollingerBands,
+    kc: KeltnerChannel,
+    maximum: Maximum,
+    minimum: Minimum,
+    sma: Sma,
+    linreg: LinearRegression,
+    count: usize,
+    output: Option<SqueezeMomentumOutput>,
+}
+
+/// State of [SqueezeMomentum](struct.SqueezeMomentum.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqueezeMomentumState {
+    pub bb: BollingerBandsState,
+    pub kc: KeltnerChannelState,
+    pub maximum: MaximumState,
+    pub minimum: MinimumState,
+    pub sma: SimpleMovingAverageState,
+    pub linreg: LinearRegressionState,
+    /// Number of inputs, up to the lookback.
+    pub count: usize,
+    pub output: Option<SqueezeMomentumOutput>,
+}
+
+/// Output of [SqueezeMomentum](struct.SqueezeMomentum.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqueezeMomentumOutput {
+    pub squeeze_on: bool,
+    pub momentum: f64,
+}
+
+impl_output!(SqueezeMomentumOutput, squeeze_on, momentum);
+
+impl SqueezeMomentum {
+    pub fn new(
+        bb_period: usize,
+        bb_multiplier: f64,
+        kc_period: usize,
+        kc_multiplier: f64,
+    ) -> Result<Self> {
+        Ok(Self {
+            bb: BollingerBands::new(bb_period, bb_multiplier)?,
+            kc: KeltnerChannel::new(kc_period, kc_multiplier)?,
+            maximum: Maximum::new(kc_period)?,
+            minimum: Minimum::new(kc_period)?,
+            sma: Sma::new(kc_period)?,
+            linreg: LinearRegression::new(kc_period)?,
+            count: 0,
+            output: None,
+        })
+    }
+
+    fn calc(&mut self, high: f64, low: f64, close: f64, squeeze_on: bool) -> SqueezeMomentumOutput {
+        if !self.is_ready() {
+            self.count += 1;
+        }
+        let middle = (self.maximum.next(high) + self.minimum.next(low)) / 2.0;
+        let delta = close - (middle + self.sma.next(close)) / 2.0;
+
+        // value of the regression line at the current bar
+        let linreg = self.linreg.next(delta);
+
+        SqueezeMomentumOutput {
+            squeeze_on,
+            momentum: linreg.forecast - linreg.slope,
+        }
+    }
+
+    // Same as `calc`, replacing the last input instead of adding one
+    fn amend_calc(
+        &mut self,
+        high: f64,
+        low: f64,
+        close: f64,
+        squeeze_on: bool,
+    ) -> SqueezeMomentumOutput {
+        let middle = (self.maximum.amend(high) + self.minimum.amend(low)) / 2.0;
+        let delta = close - (middle + self.sma.amend(close)) / 2.0;
+        let linreg = self.linreg.amend(delta);
+
+        SqueezeMomentumOutput {
+            squeeze_on,
+            momentum: linreg.forecast - linreg.slope,
+        }
+    }
+
+    fn peek_calc(
+        &self,
+        high: f64,
+        low: f64,
+        close: f64,
+        squeeze_on: bool,
+    ) -> SqueezeMomentumOutput {
+        let middle = (self.maximum.peek(high) + self.minimum.peek(low)) / 2.0;
+        let delta = close - (middle + self.sma.peek(close)) / 2.0;
+        let linreg = self.linreg.peek(delta);
+
+        SqueezeMomentumOutput {
+            squeeze_on,
+            momentum: linreg.forecast - linreg.slope,
+        }
+    }
+}
+
+impl Period for SqueezeMomentum {
+    fn period(&self) -> usize {
+        self.kc.period()
+    }
+}
+
+impl Checkpoint for SqueezeMomentum {
+    type State = SqueezeMomentumState;
+
+    fn state(&self) -> Self::State {
+        SqueezeMomentumState {
+            bb: self.bb.state(),
+            kc: self.kc.state(),
+            maximum: self.maximum.state(),
+            minimum: self.minimum.state(),
+            sma: self.sma.state(),
+            linreg: self.linreg.state(),
+            count: self.count,
+            output: self.output.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(
+            state.bb.period,
+            state.bb.multiplier,
+            state.kc.ema.period,
+            state.kc.multiplier,
+        )
+        .map_err(|_| TaError::InvalidState)?;
+        let period = state.kc.ema.period;
+        if [
+            state.maximum.period,
+            state.minimum.period,
+            state.sma.period,
+            state.linreg.period,
+        ]
+        .iter()
+        .any(|&p| p != period)
+            || state.count > indicator.lookback()
+            || state.output.is_none() != (state.count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        indicator.bb = BollingerBands::from_state(state.bb)?;
+        indicator.kc = KeltnerChannel::from_state(state.kc)?;
+        indicator.maximum = Maximum::from_state(state.maximum)?;
+        indicator.minimum = Minimum::from_state(state.minimum)?;
+        indicator.sma = Sma::from_state(state.sma)?;
+        indicator.linreg = LinearRegression::from_state(state.linreg)?;
+        indicator.count = state.count;
+        indicator.output = state.output;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(SqueezeMomentum, 1);
+
+impl Next<f64> for SqueezeMomentum {
+    type Output = SqueezeMomentumOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let bb = self.bb.next(input);
+        let kc = self.kc.next(input);
+        let squeeze_on = bb.lower > kc.lower && bb.upper < kc.upper;
+
+        let output = self.calc(input, input, input, squeeze_on);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for SqueezeMomentum {
+    type Output = SqueezeMomentumOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let bb = self.bb.next(input);
+        let kc = self.kc.next(input);
+        let squeeze_on = bb.lower > kc.lower && bb.upper < kc.upper;
+
+        let output = self.calc(input.high(), input.low(), input.close(), squeeze_on);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Peek<f64> for SqueezeMomentum {
+    fn peek(&self, input: f64) -> SqueezeMomentumOutput {
+        let bb = self.bb.peek(input);
+        let kc = self.kc.peek(input);
+        let squeeze_on = bb.lower > kc.lower && bb.upper < kc.upper;
+
+        self.peek_calc(input, input, input, squeeze_on)
+    }
+}
+
+impl<T: High + Low + Close> Peek<&T> for SqueezeMomentum {
+    fn peek(&self, input: &T) -> SqueezeMomentumOutput {
+        let bb = self.bb.peek(input);
+        let kc = self.kc.peek(input);
+        let squeeze_on = bb.lower > kc.lower && bb.upper < kc.upper;
+
+        self.peek_calc(input.high(), input.low(), input.close(), squeeze_on)
+    }
+}
+
+impl Amend<f64> for SqueezeMomentum {
+    fn amend(&mut self, input: f64) -> SqueezeMomentumOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let bb = self.bb.amend(input);
+        let kc = self.kc.amend(input);
+        let squeeze_on = bb.lower > kc.lower && bb.upper < kc.upper;
+
+        let output = self.amend_calc(input, input, input, squeeze_on);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low + Close> Amend<&T> for SqueezeMomentum {
+    fn amend(&mut self, input: &T) -> SqueezeMomentumOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let bb = self.bb.amend(input);
+        let kc = self.kc.amend(input);
+        let squeeze_on = bb.lower > kc.lower && bb.upper < kc.upper;
+
+        let output = self.amend_calc(input.high(), input.low(), input.close(), squeeze_on);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Reset for SqueezeMomentum {
+    fn reset(&mut self) {
+        self.bb.reset();
+        self.kc.reset();
+        self.maximum.reset();
+        self.minimum.reset();
+        self.sma.reset();
+        self.linreg.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl Current for SqueezeMomentum {
+    type Output = SqueezeMomentumOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for SqueezeMomentum {
+    fn is_ready(&self) -> bool {
+        self.count >= self.lookback()
+    }
+}
+
+impl Lookback for SqueezeMomentum {
+    fn lookback(&self) -> usize {
+        // the regression is fed with values from the full KC period
+        let momentum = self.kc.period() + self.linreg.period() - 1;
+        momentum.max(self.bb.lookback())
+    }
+}
+
+impl Default for SqueezeMomentum {
+    fn default() -> Self {
+        Self::new(20, 2.0, 20, 1.5).unwrap()
+    }
+}
+
+impl fmt::Display for SqueezeMomentum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SQZMOM({}, {}, {}, {})",
+            self.bb.period(),
+            self.bb.multiplier(),
+            self.kc.period(),
+            self.kc.multiplier()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(SqueezeMomentum);
+
+    fn output(out: SqueezeMomentumOutput) -> (bool, f64) {
+        (out.squeeze_on, round(out.momentum))
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(SqueezeMomentum::new(0, 2.0, 20, 1.5).is_err());
+        assert!(SqueezeMomentum::new(20, 2.0, 0, 1.5).is_err());
+        assert!(SqueezeMomentum::new(1, 2.0, 1, 1.5).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut sqz = SqueezeMomentum::new(3, 2.0, 3, 1.5).unwrap();
+
+        let bar1 = Bar::new().high(10.5).low(9.5).close(10);
+        let bar2 = Bar::new().high(12.5).low(11.5).close(12);
+        let bar3 = Bar::new().high(14.5).low(13.5).close(14);
+        let bar4 = Bar::new().high(16.5).low(15.5).close(16);
+
+        assert_eq!(output(sqz.next(&bar1)), (true, 0.0));
+        assert_eq!(output(sqz.next(&bar2)), (true, 1.0));
+        assert_eq!(output(sqz.next(&bar3)), (false, 2.0));
+        assert_eq!(output(sqz.next(&bar4)), (false, 2.167));
+    }
+
+    #[test]
+    fn test_next_bar_squeeze() {
+        let mut sqz = SqueezeMomentum::new(3, 2.0, 3, 1.5).unwrap();
+
+        let bars = [
+            Bar::new().high(11).low(9).close(10),
+            Bar::new().high(13).low(10).close(12),
+            Bar::new().high(12).low(10).close(11),
+            Bar::new().high(12).low(11).close(11.5),
+            Bar::new().high(12).low(11).close(11.5),
+        ];
+        let expected = [0.0, 1.0, 0.333, -0.167, 0.278];
+
+        for (bar, momentum) in bars.iter().zip(expected.iter()) {
+            assert_eq!(output(sqz.next(bar)), (true, *momentum));
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sqz = SqueezeMomentum::new(3, 2.0, 3, 1.5).unwrap();
+
+        let bar1 = Bar::new().high(10.5).low(9.5).close(10);
+        let bar2 = Bar::new().high(12.5).low(11.5).close(12);
+
+        sqz.next(&bar1);
+        sqz.next(&bar2);
+
+        sqz.reset();
+        assert_eq!(output(sqz.next(&bar1)), (true, 0.0));
+        assert_eq!(output(sqz.next(&bar2)), (true, 1.0));
+    }
+
+    #[test]
+    fn test_default() {
+        SqueezeMomentum::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let sqz = SqueezeMomentum::new(20, 2.0, 20, 1.5).unwrap();
+        assert_eq!(format!("{}", sqz), "SQZMOM(20, 2, 20, 1.5)");
+    }
+}
@@ -1,7 +1,10 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Numeric, Peek, Period, Reset,
+    SetPeriod,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -39,49 +42,95 @@ use serde::{Deserialize, Serialize};
 /// * [Standard Deviation, Wikipedia](https://en.wikipedia.org/wiki/Standard_deviation)
 ///
 #[doc(alias = "SD")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct StandardDeviation {
+pub struct StandardDeviation<N = f64> {
     period: usize,
     index: usize,
     count: usize,
-    m: f64,
-    m2: f64,
-    deque: Box<[f64]>,
+    m: N,
+    m2: N,
+    deque: Box<[N]>,
+    output: Option<N>,
+    // State before the last input, restored by `amend`
+    saved_count: usize,
+    saved_m: N,
+    saved_m2: N,
+    saved_value: N,
+}
+
+/// State of [StandardDeviation](generic/struct.StandardDeviation.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StandardDeviationState<N = f64> {
+    pub period: usize,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<N>,
+    /// Input that the last input pushed out of the window, if the window was full.
+    pub evicted: Option<N>,
+    /// Running mean of the window before the last input.
+    pub previous_mean: N,
+    /// Running sum of squared deviations from the mean of the window before the last input.
+    pub previous_m2: N,
 }
 
-impl StandardDeviation {
+impl<N: Numeric> StandardDeviation<N> {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
             _ => Ok(Self {
                 period,
                 index: 0,
                 count: 0,
-                m: 0.0,
-                m2: 0.0,
-                deque: vec![0.0; period].into_boxed_slice(),
+                m: N::zero(),
+                m2: N::zero(),
+                deque: vec![N::zero(); period].into_boxed_slice(),
+                output: None,
+                saved_count: 0,
+                saved_m: N::zero(),
+                saved_m2: N::zero(),
+                saved_value: N::zero(),
             }),
         }
     }
 
-    pub(super) fn mean(&self) -> f64 {
-        self.m
+    /// Inputs in the window, the oldest first.
+    fn inputs(&self) -> impl Iterator<Item = N> + '_ {
+        let oldest = self.index + self.period - self.count;
+        (0..self.count).map(move |i| self.deque[(oldest + i) % self.period])
     }
-}
 
-impl Period for StandardDeviation {
-    fn period(&self) -> usize {
-        self.period
+    pub(super) fn mean(&self) -> N {
+        self.m
     }
-}
 
-impl Next<f64> for StandardDeviation {
-    type Output = f64;
+    /// Standard deviation and mean if `input` was the next input.
+    pub(super) fn peek_with_mean(&self, input: N) -> (N, N) {
+        let old_val = self.deque[self.index];
+        let (count, m, m2) = if self.count < self.period {
+            let count = self.count + 1;
+            let delta = input - self.m;
+            let m = self.m + delta / N::from_count(count);
+            (count, m, self.m2 + delta * (input - m))
+        } else {
+            let delta = input - old_val;
+            let m = self.m + delta / N::from_count(self.period);
+            (
+                self.count,
+                m,
+                self.m2 + delta * (input - m + old_val - self.m),
+            )
+        };
+
+        ((m2.max(N::zero()) / N::from_count(count)).sqrt(), m)
+    }
 
-    fn next(&mut self, input: f64) -> Self::Output {
+    pub(super) fn next_value(&mut self, input: N) -> N {
         let old_val = self.deque[self.index];
         self.deque[self.index] = input;
+        self.saved_count = self.count;
+        self.saved_m = self.m;
+        self.saved_m2 = self.m2;
+        self.saved_value = old_val;
 
         self.index = if self.index + 1 < self.period {
             self.index + 1
@@ -92,51 +141,170 @@ impl Next<f64> for StandardDeviation {
         if self.count < self.period {
             self.count += 1;
             let delta = input - self.m;
-            self.m += delta / self.count as f64;
+            self.m += delta / N::from_count(self.count);
             let delta2 = input - self.m;
             self.m2 += delta * delta2;
         } else {
             let delta = input - old_val;
             let old_m = self.m;
-            self.m += delta / self.period as f64;
+            self.m += delta / N::from_count(self.period);
             let delta2 = input - self.m + old_val - old_m;
             self.m2 += delta * delta2;
         }
-        if self.m2 < 0.0 {
-            self.m2 = 0.0;
+        if self.m2 < N::zero() {
+            self.m2 = N::zero();
+        }
+
+        let output = (self.m2 / N::from_count(self.count)).sqrt();
+        self.output = Some(output);
+        output
+    }
+
+    fn peek_value(&self, input: N) -> N {
+        self.peek_with_mean(input).0
+    }
+
+    pub(super) fn amend_value(&mut self, input: N) -> N {
+        if self.output.is_none() {
+            return self.next_value(input);
         }
+        self.index = (self.index + self.period - 1) % self.period;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.m = self.saved_m;
+        self.m2 = self.saved_m2;
+        self.next_value(input)
+    }
+}
 
-        (self.m2 / self.count as f64).sqrt()
+impl<N: Numeric> Period for StandardDeviation<N> {
+    fn period(&self) -> usize {
+        self.period
     }
 }
 
-impl<T: Close> Next<&T> for StandardDeviation {
-    type Output = f64;
+impl<N: Numeric> Checkpoint for StandardDeviation<N> {
+    type State = StandardDeviationState<N>;
+
+    fn state(&self) -> Self::State {
+        StandardDeviationState {
+            period: self.period,
+            inputs: self.inputs().collect(),
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous_mean: self.saved_m,
+            previous_m2: self.saved_m2,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let count = state.inputs.len();
+        if state.period == 0
+            || count > state.period
+            || (state.evicted.is_some() && count != state.period)
+            || (count == 0 && (state.previous_mean != N::zero() || state.previous_m2 != N::zero()))
+            || state.previous_m2 < N::zero()
+        {
+            return Err(TaError::InvalidState);
+        }
+        // Rebuild the window before the last input and feed the last input again, so the
+        // running mean and deviations continue exactly and the last input can be amended
+        let mut indicator = Self::new(state.period)?;
+        if let Some((&last, previous)) = state.inputs.split_last() {
+            let window = state.evicted.iter().chain(previous);
+            for (slot, &input) in indicator.deque.iter_mut().zip(window) {
+                *slot = input;
+            }
+            indicator.count = previous.len() + state.evicted.iter().count();
+            indicator.index = indicator.count % indicator.period;
+            indicator.m = state.previous_mean;
+            indicator.m2 = state.previous_m2;
+            indicator.next_value(last);
+        }
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(StandardDeviation<N>, 1);
+
+impl<N: Numeric> SetPeriod for StandardDeviation<N> {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        // Replay the latest inputs that fit in the new window
+        let mut resized = Self::new(period)?;
+        for input in self.inputs().skip(self.count.saturating_sub(period)) {
+            resized.next_value(input);
+        }
+        *self = resized;
+        Ok(())
+    }
+}
+
+impl_numeric_inputs!(StandardDeviation);
+
+impl<N: Numeric, T: Close<N>> Next<&T> for StandardDeviation<N> {
+    type Output = N;
 
     fn next(&mut self, input: &T) -> Self::Output {
-        self.next(input.close())
+        self.next_value(input.close())
+    }
+}
+
+impl<N: Numeric, T: Close<N>> Peek<&T> for StandardDeviation<N> {
+    fn peek(&self, input: &T) -> N {
+        self.peek_value(input.close())
+    }
+}
+
+impl<N: Numeric, T: Close<N>> Amend<&T> for StandardDeviation<N> {
+    fn amend(&mut self, input: &T) -> N {
+        self.amend_value(input.close())
     }
 }
 
-impl Reset for StandardDeviation {
+impl<N: Numeric> Reset for StandardDeviation<N> {
     fn reset(&mut self) {
         self.index = 0;
         self.count = 0;
-        self.m = 0.0;
-        self.m2 = 0.0;
+        self.m = N::zero();
+        self.m2 = N::zero();
         for i in 0..self.period {
-            self.deque[i] = 0.0;
+            self.deque[i] = N::zero();
         }
+        self.output = None;
+        self.saved_count = 0;
+        self.saved_m = N::zero();
+        self.saved_m2 = N::zero();
+        self.saved_value = N::zero();
     }
 }
 
-impl Default for StandardDeviation {
+impl<N: Numeric> Current for StandardDeviation<N> {
+    type Output = N;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<N: Numeric> IsReady for StandardDeviation<N> {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl<N: Numeric> Lookback for StandardDeviation<N> {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl<N: Numeric> Default for StandardDeviation<N> {
     fn default() -> Self {
         Self::new(9).unwrap()
     }
 }
 
-impl fmt::Display for StandardDeviation {
+impl<N: Numeric> fmt::Display for StandardDeviation<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "SD({})", self.period)
     }
@@ -145,6 +313,7 @@ impl fmt::Display for StandardDeviation {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::StandardDeviation;
     use crate::test_helper::*;
 
     test_indicator!(StandardDeviation);
@@ -166,6 +335,18 @@ mod tests {
         assert_eq!(round(sd.next(100.0)), 35.355);
     }
 
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_next_decimal() {
+        use crate::indicators::generic::StandardDeviation;
+        use rust_decimal::Decimal;
+
+        let mut sd = StandardDeviation::<Decimal>::new(2).unwrap();
+        assert_eq!(sd.next(Decimal::new(1, 1)), Decimal::ZERO);
+        assert_eq!(sd.next(Decimal::new(3, 1)), Decimal::new(1, 1));
+        assert_eq!(sd.next(Decimal::new(3, 1)), Decimal::ZERO);
+    }
+
     #[test]
     fn test_next_floating_point_error() {
         let mut sd = StandardDeviation::new(6).unwrap();
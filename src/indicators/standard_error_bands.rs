@@ -0,0 +1,304 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{LinearRegression, LinearRegressionOutput, LinearRegressionState};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Standard Error Bands (SEB).
+///
+/// A regression based alternative to [Bollinger Bands](type.BollingerBands.html), introduced
+/// by Jon Andersen. The middle line is the value of a [linear regression](struct.LinearRegression.html)
+/// line at the current bar, and the bands are a multiple of the standard error of the regression
+/// away from it. The bands are narrow while prices follow a straight line, regardless of how
+/// steep it is, and widen when prices scatter around it.
+///
+/// # Formula
+///
+/// Middle = Forecast - Slope
+///
+/// Upper = Middle + _multiplier_ * SE
+///
+/// Lower = Middle - _multiplier_ * SE
+///
+/// Where:
+///
+/// * _Forecast_, _Slope_ - [LinearRegression](struct.LinearRegression.html) over _period_ values
+/// * _SE_ - [standard error](struct.LinearRegression.html#method.standard_error) of the
+///   regression, 0 while there are 2 values or less
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 21.
+/// * _multiplier_ - number of standard errors (number greater than 0). Default is 2.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::StandardErrorBands;
+/// use ta::Next;
+///
+/// let mut seb = StandardErrorBands::new(3, 2.0).unwrap();
+///
+/// seb.next(2.0);
+/// seb.next(4.0);
+/// let out = seb.next(6.0);
+///
+/// assert_eq!(out.middle, 6.0);
+/// assert_eq!(out.upper, 6.0);
+/// assert_eq!(out.lower, 6.0);
+/// ```
+#[doc(alias = "SEB")]
+#[derive(Debug, Clone)]
+pub struct StandardErrorBands {
+    multiplier: f64,
+    lr: LinearRegression,
+    output: Option<StandardErrorBandsOutput>,
+}
+
+/// State of [StandardErrorBands](struct.StandardErrorBands.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StandardErrorBandsState {
+    pub multiplier: f64,
+    pub lr: LinearRegressionState,
+    pub output: Option<StandardErrorBandsOutput>,
+}
+
+/// Output of [StandardErrorBands](struct.StandardErrorBands.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StandardErrorBandsOutput {
+    pub upper: f64,
+    pub middle: f64,
+    pub lower: f64,
+}
+
+impl_output!(StandardErrorBandsOutput, upper, middle, lower);
+
+impl StandardErrorBands {
+    pub fn new(period: usize, multiplier: f64) -> Result<Self> {
+        if !multiplier.is_finite() || multiplier <= 0.0 {
+            return Err(TaError::invalid_parameter("multiplier", multiplier, "> 0"));
+        }
+        Ok(Self {
+            multiplier,
+            lr: LinearRegression::new(period)?,
+            output: None,
+        })
+    }
+
+    pub fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    // Bands around the last regression
+    fn bands(&mut self, lr: LinearRegressionOutput) -> StandardErrorBandsOutput {
+        let middle = lr.forecast - lr.slope;
+        let width = self.lr.standard_error() * self.multiplier;
+
+        let output = StandardErrorBandsOutput {
+            upper: middle + width,
+            middle,
+            lower: middle - width,
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Period for StandardErrorBands {
+    fn period(&self) -> usize {
+        self.lr.period()
+    }
+}
+
+impl Checkpoint for StandardErrorBands {
+    type State = StandardErrorBandsState;
+
+    fn state(&self) -> Self::State {
+        StandardErrorBandsState {
+            multiplier: self.multiplier,
+            lr: self.lr.state(),
+            output: self.output.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator =
+            Self::new(state.lr.period, state.multiplier).map_err(|_| TaError::InvalidState)?;
+        if state.output.is_none() != state.lr.output.is_none() {
+            return Err(TaError::InvalidState);
+        }
+        indicator.lr = LinearRegression::from_state(state.lr)?;
+        indicator.output = state.output;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(StandardErrorBands, 1);
+
+impl SetPeriod for StandardErrorBands {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period, self.multiplier)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for StandardErrorBands {
+    type Output = StandardErrorBandsOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let lr = self.lr.next(input);
+        self.bands(lr)
+    }
+}
+
+impl<T: Close> Next<&T> for StandardErrorBands {
+    type Output = StandardErrorBandsOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for StandardErrorBands {
+    fn peek(&self, input: f64) -> StandardErrorBandsOutput {
+        let lr = self.lr.peek(input);
+        let middle = lr.forecast - lr.slope;
+        let width = self.lr.peek_standard_error(input) * self.multiplier;
+
+        StandardErrorBandsOutput {
+            upper: middle + width,
+            middle,
+            lower: middle - width,
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for StandardErrorBands {
+    fn peek(&self, input: &T) -> StandardErrorBandsOutput {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for StandardErrorBands {
+    fn amend(&mut self, input: f64) -> StandardErrorBandsOutput {
+        let lr = self.lr.amend(input);
+        self.bands(lr)
+    }
+}
+
+impl<T: Close> Amend<&T> for StandardErrorBands {
+    fn amend(&mut self, input: &T) -> StandardErrorBandsOutput {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for StandardErrorBands {
+    fn reset(&mut self) {
+        self.lr.reset();
+        self.output = None;
+    }
+}
+
+impl Current for StandardErrorBands {
+    type Output = StandardErrorBandsOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for StandardErrorBands {
+    fn is_ready(&self) -> bool {
+        self.lr.is_ready()
+    }
+}
+
+impl Lookback for StandardErrorBands {
+    fn lookback(&self) -> usize {
+        self.lr.lookback()
+    }
+}
+
+impl Default for StandardErrorBands {
+    fn default() -> Self {
+        Self::new(21, 2.0).unwrap()
+    }
+}
+
+impl fmt::Display for StandardErrorBands {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SEB({}, {})", self.lr.period(), self.multiplier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(StandardErrorBands);
+
+    fn output(out: StandardErrorBandsOutput) -> (f64, f64, f64) {
+        (round(out.upper), round(out.middle), round(out.lower))
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(StandardErrorBands::new(0, 2.0).is_err());
+        assert!(StandardErrorBands::new(3, 0.0).is_err());
+        assert!(StandardErrorBands::new(3, f64::NAN).is_err());
+        assert!(StandardErrorBands::new(3, 2.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut seb = StandardErrorBands::new(3, 2.0).unwrap();
+
+        assert_eq!(output(seb.next(2.0)), (2.0, 2.0, 2.0));
+        assert_eq!(output(seb.next(5.0)), (5.0, 5.0, 5.0));
+        assert_eq!(output(seb.next(1.0)), (7.882, 2.167, -3.549));
+        assert_eq!(output(seb.next(2.0)), (5.249, 1.167, -2.916));
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut seb = StandardErrorBands::new(3, 2.0).unwrap();
+
+        seb.next(&Bar::new().close(2));
+        seb.next(&Bar::new().close(5));
+        assert_eq!(
+            output(seb.next(&Bar::new().close(1))),
+            (7.882, 2.167, -3.549)
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut seb = StandardErrorBands::new(3, 2.0).unwrap();
+
+        seb.next(2.0);
+        seb.next(5.0);
+        seb.next(1.0);
+
+        seb.reset();
+        assert_eq!(output(seb.next(4.0)), (4.0, 4.0, 4.0));
+    }
+
+    #[test]
+    fn test_default() {
+        StandardErrorBands::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let seb = StandardErrorBands::new(21, 2.5).unwrap();
+        assert_eq!(format!("{}", seb), "SEB(21, 2.5)");
+    }
+}
@@ -0,0 +1,442 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{
+    ExponentialMovingAverage as Ema, ExponentialMovingAverageState, Maximum, MaximumState, Minimum,
+    MinimumState,
+};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Stochastic Momentum Index (SMI).
+///
+/// Developed by William Blau, SMI shows where the close is relative to the midpoint of the
+/// recent high/low range, rather than relative to the low as the
+/// [stochastic oscillator](struct.FastStochastic.html) does. The oscillator returns output in
+/// the range of -100..100.
+///
+/// # Formula
+///
+/// D = Close - (HH + LL) / 2
+///
+/// SMI = 100 * EMA(EMA(D)) / (EMA(EMA(HH - LL)) / 2)
+///
+/// Signal = EMA(SMI, _signal_period_)
+///
+/// Where:
+///
+/// * _HH_, _LL_ - highest high and lowest low over the last _k_period_ bars
+/// * the double EMAs use _smoothing_period_
+///
+/// When the smoothed range is 0, SMI is 0.
+///
+/// # Parameters
+///
+/// * _k_period_ - number of periods for the high/low range (integer greater than 0). Default is 10.
+/// * _smoothing_period_ - period of the double smoothing (integer greater than 0). Default is 3.
+/// * _signal_period_ - period of the signal EMA (integer greater than 0). Default is 3.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::StochasticMomentumIndex;
+/// use ta::{Next, DataItem};
+///
+/// fn bar(high: f64, low: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .high(high).low(low).close(close).open(close)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut smi = StochasticMomentumIndex::new(3, 2, 2).unwrap();
+///
+/// let out = smi.next(&bar(11.0, 9.0, 10.0));
+/// assert_eq!(out.smi, 0.0);
+///
+/// let out = smi.next(&bar(13.0, 10.0, 12.0));
+/// assert_eq!(out.smi.round(), 31.0);
+/// ```
+///
+/// # Links
+///
+/// * [Stochastic Momentum Index, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:stochastic_momentum_index_smi)
+///
+#[doc(alias = "SMI")]
+#[derive(Debug, Clone)]
+pub struct StochasticMomentumIndex {
+    k_period: usize,
+    maximum: Maximum,
+    minimum: Minimum,
+    distance_ema1: Ema,
+    distance_ema2: Ema,
+    range_ema1: Ema,
+    range_ema2: Ema,
+    signal_ema: Ema,
+    count: usize,
+    output: Option<StochasticMomentumIndexOutput>,
+}
+
+/// State of [StochasticMomentumIndex](struct.StochasticMomentumIndex.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StochasticMomentumIndexState {
+    pub maximum: MaximumState,
+    pub minimum: MinimumState,
+    pub distance_ema1: ExponentialMovingAverageState,
+    pub distance_ema2: ExponentialMovingAverageState,
+    pub range_ema1: ExponentialMovingAverageState,
+    pub range_ema2: ExponentialMovingAverageState,
+    pub signal_ema: ExponentialMovingAverageState,
+    /// Number of inputs, up to the lookback.
+    pub count: usize,
+    pub output: Option<StochasticMomentumIndexOutput>,
+}
+
+/// Output of [StochasticMomentumIndex](struct.StochasticMomentumIndex.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StochasticMomentumIndexOutput {
+    pub smi: f64,
+    pub signal: f64,
+}
+
+impl_output!(StochasticMomentumIndexOutput, smi, signal);
+
+impl From<StochasticMomentumIndexOutput> for (f64, f64) {
+    fn from(o: StochasticMomentumIndexOutput) -> Self {
+        (o.smi, o.signal)
+    }
+}
+
+impl StochasticMomentumIndex {
+    pub fn new(k_period: usize, smoothing_period: usize, signal_period: usize) -> Result<Self> {
+        Ok(Self {
+            k_period,
+            maximum: Maximum::new(k_period)?,
+            minimum: Minimum::new(k_period)?,
+            distance_ema1: Ema::new(smoothing_period)?,
+            distance_ema2: Ema::new(smoothing_period)?,
+            range_ema1: Ema::new(smoothing_period)?,
+            range_ema2: Ema::new(smoothing_period)?,
+            signal_ema: Ema::new(signal_period)?,
+            count: 0,
+            output: None,
+        })
+    }
+
+    fn calc(&mut self, high: f64, low: f64, close: f64) -> StochasticMomentumIndexOutput {
+        if !self.is_ready() {
+            self.count += 1;
+        }
+        let highest = self.maximum.next(high);
+        let lowest = self.minimum.next(low);
+
+        let distance = close - (highest + lowest) / 2.0;
+        let distance = self.distance_ema2.next(self.distance_ema1.next(distance));
+        let range = self.range_ema2.next(self.range_ema1.next(highest - lowest));
+
+        let smi = if range > 0.0 {
+            100.0 * distance / (range / 2.0)
+        } else {
+            0.0
+        };
+
+        StochasticMomentumIndexOutput {
+            smi,
+            signal: self.signal_ema.next(smi),
+        }
+    }
+
+    // Same as `calc`, replacing the last input instead of adding one
+    fn amend_calc(&mut self, high: f64, low: f64, close: f64) -> StochasticMomentumIndexOutput {
+        let highest = self.maximum.amend(high);
+        let lowest = self.minimum.amend(low);
+
+        let distance = close - (highest + lowest) / 2.0;
+        let distance = self.distance_ema2.amend(self.distance_ema1.amend(distance));
+        let range = self
+            .range_ema2
+            .amend(self.range_ema1.amend(highest - lowest));
+
+        let smi = if range > 0.0 {
+            100.0 * distance / (range / 2.0)
+        } else {
+            0.0
+        };
+
+        StochasticMomentumIndexOutput {
+            smi,
+            signal: self.signal_ema.amend(smi),
+        }
+    }
+
+    // Same as `calc`, without updating the indicator
+    fn peek_calc(&self, high: f64, low: f64, close: f64) -> StochasticMomentumIndexOutput {
+        let highest = self.maximum.peek(high);
+        let lowest = self.minimum.peek(low);
+
+        let distance = close - (highest + lowest) / 2.0;
+        let distance = self.distance_ema2.peek(self.distance_ema1.peek(distance));
+        let range = self.range_ema2.peek(self.range_ema1.peek(highest - lowest));
+
+        let smi = if range > 0.0 {
+            100.0 * distance / (range / 2.0)
+        } else {
+            0.0
+        };
+
+        StochasticMomentumIndexOutput {
+            smi,
+            signal: self.signal_ema.peek(smi),
+        }
+    }
+}
+
+impl Period for StochasticMomentumIndex {
+    fn period(&self) -> usize {
+        self.k_period
+    }
+}
+
+impl Checkpoint for StochasticMomentumIndex {
+    type State = StochasticMomentumIndexState;
+
+    fn state(&self) -> Self::State {
+        StochasticMomentumIndexState {
+            maximum: self.maximum.state(),
+            minimum: self.minimum.state(),
+            distance_ema1: self.distance_ema1.state(),
+            distance_ema2: self.distance_ema2.state(),
+            range_ema1: self.range_ema1.state(),
+            range_ema2: self.range_ema2.state(),
+            signal_ema: self.signal_ema.state(),
+            count: self.count,
+            output: self.output.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(
+            state.maximum.period,
+            state.distance_ema1.period,
+            state.signal_ema.period,
+        )
+        .map_err(|_| TaError::InvalidState)?;
+        if state.minimum.period != state.maximum.period
+            || [&state.distance_ema2, &state.range_ema1, &state.range_ema2]
+                .iter()
+                .any(|ema| ema.period != state.distance_ema1.period)
+            || state.count > indicator.lookback()
+            || state.output.is_none() != (state.count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        indicator.maximum = Maximum::from_state(state.maximum)?;
+        indicator.minimum = Minimum::from_state(state.minimum)?;
+        indicator.distance_ema1 = Ema::from_state(state.distance_ema1)?;
+        indicator.distance_ema2 = Ema::from_state(state.distance_ema2)?;
+        indicator.range_ema1 = Ema::from_state(state.range_ema1)?;
+        indicator.range_ema2 = Ema::from_state(state.range_ema2)?;
+        indicator.signal_ema = Ema::from_state(state.signal_ema)?;
+        indicator.count = state.count;
+        indicator.output = state.output;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(StochasticMomentumIndex, 1);
+
+impl Next<f64> for StochasticMomentumIndex {
+    type Output = StochasticMomentumIndexOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let output = self.calc(input, input, input);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for StochasticMomentumIndex {
+    type Output = StochasticMomentumIndexOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let output = self.calc(input.high(), input.low(), input.close());
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Peek<f64> for StochasticMomentumIndex {
+    fn peek(&self, input: f64) -> StochasticMomentumIndexOutput {
+        self.peek_calc(input, input, input)
+    }
+}
+
+impl<T: High + Low + Close> Peek<&T> for StochasticMomentumIndex {
+    fn peek(&self, input: &T) -> StochasticMomentumIndexOutput {
+        self.peek_calc(input.high(), input.low(), input.close())
+    }
+}
+
+impl Amend<f64> for StochasticMomentumIndex {
+    fn amend(&mut self, input: f64) -> StochasticMomentumIndexOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let output = self.amend_calc(input, input, input);
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low + Close> Amend<&T> for StochasticMomentumIndex {
+    fn amend(&mut self, input: &T) -> StochasticMomentumIndexOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let output = self.amend_calc(input.high(), input.low(), input.close());
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Reset for StochasticMomentumIndex {
+    fn reset(&mut self) {
+        self.maximum.reset();
+        self.minimum.reset();
+        self.distance_ema1.reset();
+        self.distance_ema2.reset();
+        self.range_ema1.reset();
+        self.range_ema2.reset();
+        self.signal_ema.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl Current for StochasticMomentumIndex {
+    type Output = StochasticMomentumIndexOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for StochasticMomentumIndex {
+    fn is_ready(&self) -> bool {
+        self.count >= self.lookback()
+    }
+}
+
+impl Lookback for StochasticMomentumIndex {
+    fn lookback(&self) -> usize {
+        let periods = self.k_period
+            + self.distance_ema1.period()
+            + self.distance_ema2.period()
+            + self.signal_ema.period();
+        periods - 3
+    }
+}
+
+impl Default for StochasticMomentumIndex {
+    fn default() -> Self {
+        Self::new(10, 3, 3).unwrap()
+    }
+}
+
+impl fmt::Display for StochasticMomentumIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SMI({}, {}, {})",
+            self.k_period,
+            self.distance_ema1.period(),
+            self.signal_ema.period()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(StochasticMomentumIndex);
+
+    fn round(nums: (f64, f64)) -> (f64, f64) {
+        let n0 = (nums.0 * 1000.0).round() / 1000.0;
+        let n1 = (nums.1 * 1000.0).round() / 1000.0;
+        (n0, n1)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(StochasticMomentumIndex::new(0, 3, 3).is_err());
+        assert!(StochasticMomentumIndex::new(10, 0, 3).is_err());
+        assert!(StochasticMomentumIndex::new(10, 3, 0).is_err());
+        assert!(StochasticMomentumIndex::new(1, 1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut smi = StochasticMomentumIndex::new(3, 2, 2).unwrap();
+
+        let bars = [
+            Bar::new().high(11).low(9).close(10),
+            Bar::new().high(13).low(10).close(12),
+            Bar::new().high(12).low(10).close(11),
+            Bar::new().high(15).low(12).close(14),
+            Bar::new().high(10).low(8).close(9),
+        ];
+        let expected = [
+            (0.0, 0.0),
+            (30.769, 20.513),
+            (17.021, 18.185),
+            (38.596, 31.793),
+            (-21.694, -3.865),
+        ];
+
+        for (bar, expected) in bars.iter().zip(expected.iter()) {
+            assert_eq!(round(smi.next(bar).into()), *expected);
+        }
+    }
+
+    #[test]
+    fn test_next_flat() {
+        let mut smi = StochasticMomentumIndex::new(3, 2, 2).unwrap();
+
+        assert_eq!(round(smi.next(5.0).into()), (0.0, 0.0));
+        assert_eq!(round(smi.next(5.0).into()), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut smi = StochasticMomentumIndex::new(3, 2, 2).unwrap();
+
+        let bar1 = Bar::new().high(11).low(9).close(10);
+        let bar2 = Bar::new().high(13).low(10).close(12);
+
+        smi.next(&bar1);
+        smi.next(&bar2);
+
+        smi.reset();
+        assert_eq!(round(smi.next(&bar1).into()), (0.0, 0.0));
+        assert_eq!(round(smi.next(&bar2).into()), (30.769, 20.513));
+    }
+
+    #[test]
+    fn test_default() {
+        StochasticMomentumIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let smi = StochasticMomentumIndex::new(10, 3, 3).unwrap();
+        assert_eq!(format!("{}", smi), "SMI(10, 3, 3)");
+    }
+}
@@ -0,0 +1,266 @@
+use core::f64::consts::PI;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Ehlers Super Smoother.
+///
+/// A two-pole Butterworth low-pass filter by John Ehlers. It removes fluctuations shorter than
+/// _period_ bars with much less lag than a moving average of the same length, which makes it a
+/// good preprocessing stage for cycle indicators.
+///
+/// # Formula
+///
+/// Filter<sub>t</sub> = c<sub>1</sub> × (Price<sub>t</sub> + Price<sub>t-1</sub>) / 2 +
+/// c<sub>2</sub> × Filter<sub>t-1</sub> + c<sub>3</sub> × Filter<sub>t-2</sub>
+///
+/// Where:
+///
+/// * a = e<sup>-√2 × π / period</sup>
+/// * c<sub>2</sub> = 2 × a × cos(√2 × π / period)
+/// * c<sub>3</sub> = -a<sup>2</sup>
+/// * c<sub>1</sub> = 1 - c<sub>2</sub> - c<sub>3</sub>
+///
+/// Previous prices and filter values start at the first price.
+///
+/// # Parameters
+///
+/// * _period_ - critical period in bars (integer greater than 0). Default is 10.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SuperSmoother;
+/// use ta::Next;
+///
+/// let mut ss = SuperSmoother::new(4).unwrap();
+/// assert_eq!(ss.next(10.0).round(), 10.0);
+/// assert_eq!((ss.next(12.0) * 1000.0).round() / 1000.0, 10.816);
+/// ```
+#[doc(alias = "SSF")]
+#[derive(Debug, Clone)]
+pub struct SuperSmoother {
+    period: usize,
+    c1: f64,
+    c2: f64,
+    c3: f64,
+    state: Option<(f64, f64, f64)>,
+    output: Option<f64>,
+    // Filter before the last input, restored by `amend`
+    saved_state: Option<(f64, f64, f64)>,
+}
+
+/// State of [SuperSmoother](struct.SuperSmoother.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuperSmootherState {
+    pub period: usize,
+    /// Previous input and the last two outputs, `None` before the first input.
+    pub filter: Option<(f64, f64, f64)>,
+    /// Filter before the last input, to amend it.
+    pub previous: Option<(f64, f64, f64)>,
+}
+
+impl SuperSmoother {
+    pub fn new(period: usize) -> Result<Self> {
+        if period == 0 {
+            return Err(TaError::invalid_parameter("period", 0.0, ">= 1"));
+        }
+        let arg = 2f64.sqrt() * PI / period as f64;
+        let a = (-arg).exp();
+        let c2 = 2.0 * a * arg.cos();
+        let c3 = -a * a;
+        Ok(Self {
+            period,
+            c1: 1.0 - c2 - c3,
+            c2,
+            c3,
+            state: None,
+            output: None,
+            saved_state: None,
+        })
+    }
+}
+
+impl Period for SuperSmoother {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for SuperSmoother {
+    type State = SuperSmootherState;
+
+    fn state(&self) -> Self::State {
+        SuperSmootherState {
+            period: self.period,
+            filter: self.state,
+            previous: self.saved_state,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        indicator.state = state.filter;
+        indicator.saved_state = state.previous;
+        indicator.output = state.filter.map(|(_, filter, _)| filter);
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(SuperSmoother, 2);
+
+impl SetPeriod for SuperSmoother {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for SuperSmoother {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_state = self.state;
+        let (prev_input, filter1, filter2) = self.state.unwrap_or((input, input, input));
+        let filter = self.c1 * (input + prev_input) / 2.0 + self.c2 * filter1 + self.c3 * filter2;
+        self.state = Some((input, filter, filter1));
+        self.output = Some(filter);
+        filter
+    }
+}
+
+impl<T: Close> Next<&T> for SuperSmoother {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for SuperSmoother {
+    fn peek(&self, input: f64) -> f64 {
+        let (prev_input, filter1, filter2) = self.state.unwrap_or((input, input, input));
+        self.c1 * (input + prev_input) / 2.0 + self.c2 * filter1 + self.c3 * filter2
+    }
+}
+
+impl<T: Close> Peek<&T> for SuperSmoother {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for SuperSmoother {
+    fn amend(&mut self, input: f64) -> f64 {
+        self.state = self.saved_state;
+        self.next(input)
+    }
+}
+
+impl<T: Close> Amend<&T> for SuperSmoother {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for SuperSmoother {
+    fn reset(&mut self) {
+        self.state = None;
+        self.output = None;
+        self.saved_state = None;
+    }
+}
+
+impl Current for SuperSmoother {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for SuperSmoother {
+    fn is_ready(&self) -> bool {
+        self.state.is_some()
+    }
+}
+
+impl Lookback for SuperSmoother {
+    fn lookback(&self) -> usize {
+        1
+    }
+}
+
+impl Default for SuperSmoother {
+    fn default() -> Self {
+        Self::new(10).unwrap()
+    }
+}
+
+impl fmt::Display for SuperSmoother {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SSF({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(SuperSmoother);
+
+    #[test]
+    fn test_new() {
+        assert!(SuperSmoother::new(0).is_err());
+        assert!(SuperSmoother::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut ss = SuperSmoother::new(4).unwrap();
+
+        assert_eq!(round(ss.next(10.0)), 10.0);
+        assert_eq!(round(ss.next(12.0)), 10.816);
+        assert_eq!(round(ss.next(11.0)), 11.463);
+        assert_eq!(round(ss.next(14.0)), 12.379);
+        assert_eq!(round(ss.next(&Bar::new().close(13))), 13.393);
+        assert_eq!(round(ss.next(&Bar::new().close(15))), 13.998);
+    }
+
+    #[test]
+    fn test_constant() {
+        let mut ss = SuperSmoother::new(10).unwrap();
+
+        for _ in 0..10 {
+            assert_eq!(round(ss.next(5.0)), 5.0);
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ss = SuperSmoother::new(4).unwrap();
+
+        ss.next(10.0);
+        ss.next(12.0);
+        ss.reset();
+        assert_eq!(round(ss.next(20.0)), 20.0);
+    }
+
+    #[test]
+    fn test_default() {
+        SuperSmoother::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let ss = SuperSmoother::new(12).unwrap();
+        assert_eq!(format!("{}", ss), "SSF(12)");
+    }
+}
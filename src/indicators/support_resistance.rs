@@ -0,0 +1,587 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{
+    SimpleMovingAverage as Sma, SimpleMovingAverageState, SwingPoints, SwingPointsOutput,
+    SwingPointsState,
+};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, OutputValue, Peek,
+    Period, Reset, Volume,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Number of bars used to average the volume when looking for high-volume prices.
+const VOLUME_PERIOD: usize = 20;
+
+/// A bar whose volume is at least this many times the average volume is a high-volume bar.
+const VOLUME_FACTOR: f64 = 2.0;
+
+/// Support and resistance zones.
+///
+/// Collects price levels from [swing points](struct.SwingPoints.html) and from high-volume bars
+/// and clusters them into horizontal zones. A new level joins an existing zone when it lies
+/// within _width_ (relative to the level) of the zone, otherwise it starts a new zone. Every
+/// level that joins a zone counts as a touch.
+///
+/// On each bar the analyzer checks the current zones and reports an event when price:
+///
+/// * tests a zone: the bar reaches into the zone but does not close through it, and the
+///   previous bar did not reach into it. A test is also a touch;
+/// * breaks a zone: the close crosses from below the top of the zone to above it
+///   ([BreakUp](enum.ZoneEventKind.html)) or from above the bottom of the zone to below it
+///   ([BreakDown](enum.ZoneEventKind.html)).
+///
+/// A broken zone is kept, since a broken support often acts as resistance and vice versa. When
+/// there are more than _max_zones_ zones, the one touched the longest time ago is dropped.
+///
+/// High-volume bars are bars with a volume at least twice the average volume of the last 20
+/// bars. They contribute their typical price (high + low + close) / 3 as a level.
+///
+/// # Parameters
+///
+/// * _strength_ - number of bars on each side of a swing point (integer greater than 0).
+///   Default is 5.
+/// * _width_ - relative distance within which levels are clustered together (number in
+///   range (0, 1)). Default is 0.005 (0.5%).
+/// * _max_zones_ - maximum number of zones to keep (integer greater than 0). Default is 10.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{SupportResistance, ZoneEventKind};
+/// use ta::{Next, DataItem};
+///
+/// fn bar(high: f64, low: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .high(high).low(low).close(close).open(close)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut sr = SupportResistance::new(1, 0.01, 10).unwrap();
+///
+/// sr.next(&bar(10.0, 9.0, 9.5));
+/// sr.next(&bar(11.0, 10.0, 10.5));
+/// sr.next(&bar(10.5, 9.5, 10.0));
+/// // The swing high at 11 is now a resistance zone
+/// assert_eq!(sr.zones()[0].high, 11.0);
+///
+/// let events = sr.next(&bar(11.5, 10.9, 11.4));
+/// assert_eq!(events[0].kind, ZoneEventKind::BreakUp);
+/// ```
+#[doc(alias = "SR")]
+#[derive(Debug, Clone)]
+pub struct SupportResistance {
+    width: f64,
+    max_zones: usize,
+    swings: SwingPoints,
+    volume: Sma,
+    zones: Vec<Zone>,
+    bar: usize,
+    prev: Option<(f64, f64, f64)>,
+    output: Option<Vec<ZoneEvent>>,
+    // State before the last input, restored by `amend`
+    saved_zones: Vec<Zone>,
+    saved_prev: Option<(f64, f64, f64)>,
+}
+
+/// State of [SupportResistance](struct.SupportResistance.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SupportResistanceState {
+    pub width: f64,
+    pub max_zones: usize,
+    pub swings: SwingPointsState,
+    /// Average of the volumes.
+    pub volume: SimpleMovingAverageState,
+    pub zones: Vec<Zone>,
+    /// Number of bars so far.
+    pub bar: usize,
+    /// High, low and close of the last input.
+    pub previous: Option<(f64, f64, f64)>,
+    pub output: Option<Vec<ZoneEvent>>,
+    /// Zones before the last input, to amend it.
+    pub previous_zones: Vec<Zone>,
+    /// High, low and close of the input before the last one.
+    pub before_last: Option<(f64, f64, f64)>,
+}
+
+/// Horizontal price zone.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Zone {
+    /// Bottom of the zone.
+    pub low: f64,
+    /// Top of the zone.
+    pub high: f64,
+    /// Number of levels and tests that hit the zone.
+    pub touches: usize,
+    /// Index of the bar of the last touch, counting from 0.
+    pub last_touch: usize,
+}
+
+/// What price did with a zone.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoneEventKind {
+    Test,
+    BreakUp,
+    BreakDown,
+}
+
+/// Output of [SupportResistance](struct.SupportResistance.html), one for every zone that price
+/// interacted with.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneEvent {
+    pub kind: ZoneEventKind,
+    /// The zone after the event.
+    pub zone: Zone,
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..{}", self.low, self.high)
+    }
+}
+
+impl fmt::Display for ZoneEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ZoneEventKind::Test => write!(f, "test"),
+            ZoneEventKind::BreakUp => write!(f, "break up"),
+            ZoneEventKind::BreakDown => write!(f, "break down"),
+        }
+    }
+}
+
+impl fmt::Display for ZoneEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.kind, self.zone)
+    }
+}
+
+impl From<ZoneEvent> for OutputValue {
+    fn from(event: ZoneEvent) -> Self {
+        OutputValue::Named(vec![
+            ("kind", OutputValue::Label(event.kind.to_string())),
+            ("low", event.zone.low.into()),
+            ("high", event.zone.high.into()),
+            ("touches", event.zone.touches.into()),
+            ("last_touch", event.zone.last_touch.into()),
+        ])
+    }
+}
+
+impl SupportResistance {
+    pub fn new(strength: usize, width: f64, max_zones: usize) -> Result<Self> {
+        if !(width > 0.0 && width < 1.0) {
+            return Err(TaError::invalid_parameter("width", width, "0 < width < 1"));
+        }
+        if max_zones == 0 {
+            return Err(TaError::invalid_parameter("max_zones", 0.0, ">= 1"));
+        }
+        Ok(Self {
+            width,
+            max_zones,
+            swings: SwingPoints::new(strength, strength)?,
+            volume: Sma::new(VOLUME_PERIOD)?,
+            zones: Vec::new(),
+            bar: 0,
+            prev: None,
+            output: None,
+            saved_zones: Vec::new(),
+            saved_prev: None,
+        })
+    }
+
+    /// Current zones, in the order they were created.
+    pub fn zones(&self) -> &[Zone] {
+        &self.zones
+    }
+
+    fn add_level(&mut self, price: f64, index: usize) {
+        let margin = price * self.width;
+        let zone = self
+            .zones
+            .iter_mut()
+            .find(|z| price >= z.low - margin && price <= z.high + margin);
+
+        match zone {
+            Some(zone) => {
+                zone.low = zone.low.min(price);
+                zone.high = zone.high.max(price);
+                zone.touches += 1;
+                zone.last_touch = zone.last_touch.max(index);
+            }
+            None => {
+                self.zones.push(Zone {
+                    low: price,
+                    high: price,
+                    touches: 1,
+                    last_touch: index,
+                });
+                if self.zones.len() > self.max_zones {
+                    let oldest = self
+                        .zones
+                        .iter()
+                        .enumerate()
+                        .min_by_key(|(_, z)| z.last_touch)
+                        .map(|(i, _)| i)
+                        .unwrap();
+                    self.zones.remove(oldest);
+                }
+            }
+        }
+    }
+
+    // What the bar does with a zone, a test also touches the zone
+    fn event(
+        zone: &mut Zone,
+        bar: usize,
+        (prev_high, prev_low, prev_close): (f64, f64, f64),
+        (high, low, close): (f64, f64, f64),
+    ) -> Option<ZoneEventKind> {
+        if prev_close <= zone.high && close > zone.high {
+            Some(ZoneEventKind::BreakUp)
+        } else if prev_close >= zone.low && close < zone.low {
+            Some(ZoneEventKind::BreakDown)
+        } else if low <= zone.high
+            && high >= zone.low
+            && !(prev_low <= zone.high && prev_high >= zone.low)
+        {
+            zone.touches += 1;
+            zone.last_touch = bar;
+            Some(ZoneEventKind::Test)
+        } else {
+            None
+        }
+    }
+
+    fn update<T: High + Low + Close + Volume>(
+        &mut self,
+        input: &T,
+        swing: SwingPointsOutput,
+        average: f64,
+    ) -> Vec<ZoneEvent> {
+        let (high, low, close) = (input.high(), input.low(), input.close());
+        let bar = self.bar;
+        let mut events = Vec::new();
+
+        if let Some(prev) = self.prev {
+            for zone in self.zones.iter_mut() {
+                if let Some(kind) = Self::event(zone, bar, prev, (high, low, close)) {
+                    events.push(ZoneEvent { kind, zone: *zone });
+                }
+            }
+        }
+
+        if let Some(point) = swing.high {
+            self.add_level(point.price, point.index);
+        }
+        if let Some(point) = swing.low {
+            self.add_level(point.price, point.index);
+        }
+
+        let volume = input.volume();
+        if bar >= VOLUME_PERIOD && volume >= VOLUME_FACTOR * average {
+            self.add_level((high + low + close) / 3.0, bar);
+        }
+
+        self.prev = Some((high, low, close));
+        self.bar += 1;
+        self.output = Some(events.clone());
+        events
+    }
+}
+
+impl Period for SupportResistance {
+    fn period(&self) -> usize {
+        self.swings.period()
+    }
+}
+
+impl Checkpoint for SupportResistance {
+    type State = SupportResistanceState;
+
+    fn state(&self) -> Self::State {
+        SupportResistanceState {
+            width: self.width,
+            max_zones: self.max_zones,
+            swings: self.swings.state(),
+            volume: self.volume.state(),
+            zones: self.zones.clone(),
+            bar: self.bar,
+            previous: self.prev,
+            output: self.output.clone(),
+            previous_zones: self.saved_zones.clone(),
+            before_last: self.saved_prev,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(state.swings.left, state.width, state.max_zones)
+            .map_err(|_| TaError::InvalidState)?;
+        if state.swings.right != state.swings.left
+            || state.volume.period != VOLUME_PERIOD
+            || state.zones.len() > state.max_zones
+            || state.swings.bar != state.bar
+            || state.previous.is_none() != (state.bar == 0)
+            || state.output.is_none() != (state.bar == 0)
+            || state.previous_zones.len() > state.max_zones
+            || state.before_last.is_none() != (state.bar < 2)
+        {
+            return Err(TaError::InvalidState);
+        }
+        indicator.swings = SwingPoints::from_state(state.swings)?;
+        indicator.volume = Sma::from_state(state.volume)?;
+        indicator.zones = state.zones;
+        indicator.bar = state.bar;
+        indicator.prev = state.previous;
+        indicator.output = state.output;
+        indicator.saved_zones = state.previous_zones;
+        indicator.saved_prev = state.before_last;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(SupportResistance, 2);
+
+impl<T: High + Low + Close + Volume> Next<&T> for SupportResistance {
+    type Output = Vec<ZoneEvent>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_zones.clone_from(&self.zones);
+        self.saved_prev = self.prev;
+        let swing = self.swings.update(input.high(), input.low());
+        let average = self.volume.next(input.volume());
+        self.update(input, swing, average)
+    }
+}
+
+impl<T: High + Low + Close + Volume> Amend<&T> for SupportResistance {
+    fn amend(&mut self, input: &T) -> Vec<ZoneEvent> {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.zones.clone_from(&self.saved_zones);
+        self.prev = self.saved_prev;
+        self.bar -= 1;
+        let swing = self.swings.amend_update(input.high(), input.low());
+        let average = self.volume.amend(input.volume());
+        self.update(input, swing, average)
+    }
+}
+
+impl<T: High + Low + Close + Volume> Peek<&T> for SupportResistance {
+    fn peek(&self, input: &T) -> Vec<ZoneEvent> {
+        let bar = (input.high(), input.low(), input.close());
+        let prev = match self.prev {
+            Some(prev) => prev,
+            None => return Vec::new(),
+        };
+
+        self.zones
+            .iter()
+            .filter_map(|&zone| {
+                let mut zone = zone;
+                Self::event(&mut zone, self.bar, prev, bar).map(|kind| ZoneEvent { kind, zone })
+            })
+            .collect()
+    }
+}
+
+impl Reset for SupportResistance {
+    fn reset(&mut self) {
+        self.swings.reset();
+        self.volume.reset();
+        self.zones.clear();
+        self.bar = 0;
+        self.prev = None;
+        self.output = None;
+        self.saved_zones.clear();
+        self.saved_prev = None;
+    }
+}
+
+impl Current for SupportResistance {
+    type Output = Vec<ZoneEvent>;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for SupportResistance {
+    fn is_ready(&self) -> bool {
+        self.swings.is_ready() && self.volume.is_ready()
+    }
+}
+
+impl Lookback for SupportResistance {
+    fn lookback(&self) -> usize {
+        self.swings.lookback().max(self.volume.lookback())
+    }
+}
+
+impl Default for SupportResistance {
+    fn default() -> Self {
+        Self::new(5, 0.005, 10).unwrap()
+    }
+}
+
+impl fmt::Display for SupportResistance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SR({}, {}, {})",
+            self.swings.left(),
+            self.width,
+            self.max_zones
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn bar(high: f64, low: f64, close: f64) -> Bar {
+        Bar::new().high(high).low(low).close(close).volume(1000.0)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(SupportResistance::new(0, 0.01, 10).is_err());
+        assert!(SupportResistance::new(2, 0.0, 10).is_err());
+        assert!(SupportResistance::new(2, 1.0, 10).is_err());
+        assert!(SupportResistance::new(2, f64::NAN, 10).is_err());
+        assert!(SupportResistance::new(2, 0.01, 0).is_err());
+        assert!(SupportResistance::new(2, 0.01, 10).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut sr = SupportResistance::new(1, 0.01, 10).unwrap();
+
+        assert!(sr.next(&bar(10.0, 9.0, 9.5)).is_empty());
+        assert!(sr.next(&bar(11.0, 10.0, 10.5)).is_empty());
+        assert!(sr.next(&bar(10.5, 9.5, 10.0)).is_empty());
+        assert_eq!(
+            sr.zones(),
+            &[Zone {
+                low: 11.0,
+                high: 11.0,
+                touches: 1,
+                last_touch: 1
+            }]
+        );
+
+        // Reaches into the zone without closing above it
+        let events = sr.next(&bar(11.05, 10.2, 10.8));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, ZoneEventKind::Test);
+        assert_eq!(events[0].zone.touches, 2);
+        assert_eq!(events[0].zone.last_touch, 3);
+
+        // The swing low at 9.5 is now a support zone
+        assert_eq!(sr.zones().len(), 2);
+        assert_eq!(sr.zones()[1].low, 9.5);
+
+        let events = sr.next(&bar(11.5, 10.9, 11.4));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, ZoneEventKind::BreakUp);
+
+        let events = sr.next(&bar(11.3, 9.0, 9.2));
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, ZoneEventKind::BreakDown);
+        assert_eq!(events[1].kind, ZoneEventKind::BreakDown);
+    }
+
+    #[test]
+    fn test_clustering() {
+        let mut sr = SupportResistance::new(1, 0.01, 10).unwrap();
+
+        sr.next(&bar(10.0, 9.0, 9.5));
+        sr.next(&bar(11.0, 10.0, 10.5));
+        sr.next(&bar(10.5, 9.5, 10.0));
+        sr.next(&bar(11.05, 10.0, 10.8));
+        sr.next(&bar(10.8, 10.0, 10.5));
+
+        // Swing high at 11.05 is within 1% of 11
+        let zone = sr.zones()[0];
+        assert_eq!(zone.low, 11.0);
+        assert_eq!(zone.high, 11.05);
+        assert_eq!(zone.touches, 3);
+        assert_eq!(zone.last_touch, 3);
+    }
+
+    #[test]
+    fn test_max_zones() {
+        let mut sr = SupportResistance::new(1, 0.01, 1).unwrap();
+
+        sr.next(&bar(10.0, 9.0, 9.5));
+        sr.next(&bar(11.0, 10.0, 10.5));
+        sr.next(&bar(10.5, 9.5, 10.0));
+        sr.next(&bar(10.8, 10.2, 10.5));
+
+        // The swing low at 9.5 replaced the older swing high at 11
+        assert_eq!(sr.zones().len(), 1);
+        assert_eq!(sr.zones()[0].low, 9.5);
+    }
+
+    #[test]
+    fn test_high_volume() {
+        let mut sr = SupportResistance::new(5, 0.01, 10).unwrap();
+
+        for _ in 0..VOLUME_PERIOD {
+            sr.next(&bar(10.0, 9.0, 9.5));
+        }
+        assert!(sr.zones().is_empty());
+
+        sr.next(&bar(21.0, 18.0, 21.0).volume(5000.0));
+        assert_eq!(sr.zones().len(), 1);
+        assert_eq!(sr.zones()[0].low, 20.0);
+        assert_eq!(sr.zones()[0].last_touch, VOLUME_PERIOD);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sr = SupportResistance::new(1, 0.01, 10).unwrap();
+
+        sr.next(&bar(10.0, 9.0, 9.5));
+        sr.next(&bar(11.0, 10.0, 10.5));
+        sr.next(&bar(10.5, 9.5, 10.0));
+        assert_eq!(sr.zones().len(), 1);
+
+        sr.reset();
+        assert!(sr.zones().is_empty());
+        assert!(sr.next(&bar(11.5, 10.9, 11.4)).is_empty());
+    }
+
+    #[test]
+    fn test_default() {
+        SupportResistance::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let sr = SupportResistance::new(3, 0.01, 8).unwrap();
+        assert_eq!(format!("{}", sr), "SR(3, 0.01, 8)");
+
+        let event = ZoneEvent {
+            kind: ZoneEventKind::BreakUp,
+            zone: Zone {
+                low: 10.0,
+                high: 10.5,
+                touches: 3,
+                last_touch: 7,
+            },
+        };
+        assert_eq!(format!("{}", event), "break up 10..10.5");
+    }
+}
@@ -0,0 +1,489 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Current, High, IsReady, Lookback, Low, Next, OutputValue, Peek, Period,
+    Reset,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Swing highs and swing lows (pivots).
+///
+/// A swing high is a bar whose high is higher than the highs of _left_ bars before it and
+/// _right_ bars after it. A swing low is a bar whose low is lower than the lows of the bars
+/// around it.
+///
+/// A swing can only be confirmed once _right_ bars after it are known, so the output refers to
+/// the bar _bars_ago_ bars before the current one, which is always equal to _right_. Every swing
+/// also carries the index of its bar, counting from 0 at the first bar after creation or
+/// [reset](crate::Reset), which makes it easy to measure the distance between two swings.
+///
+/// This is the building block of [Williams Fractals](struct.WilliamsFractals.html), which are
+/// swings with the same strength on both sides, and of the
+/// [DivergenceDetector](struct.DivergenceDetector.html).
+///
+/// # Formula
+///
+/// Swing high at bar _t_: High<sub>t</sub> > High<sub>t-i</sub> for every _i_ in 1.._left_ and
+/// High<sub>t</sub> > High<sub>t+i</sub> for every _i_ in 1.._right_
+///
+/// Swing low at bar _t_: Low<sub>t</sub> < Low<sub>t-i</sub> for every _i_ in 1.._left_ and
+/// Low<sub>t</sub> < Low<sub>t+i</sub> for every _i_ in 1.._right_
+///
+/// # Parameters
+///
+/// * _left_ - number of bars before the swing (integer greater than 0). Default is 5.
+/// * _right_ - number of bars after the swing (integer greater than 0). Default is 5.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SwingPoints;
+/// use ta::{Next, DataItem};
+///
+/// fn bar(high: f64, low: f64) -> DataItem {
+///     DataItem::builder()
+///         .high(high).low(low).close(low).open(low)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut swings = SwingPoints::new(2, 1).unwrap();
+///
+/// swings.next(&bar(10.0, 8.0));
+/// swings.next(&bar(11.0, 9.0));
+/// swings.next(&bar(12.0, 10.0));
+/// let out = swings.next(&bar(11.0, 9.5));
+///
+/// let high = out.high.unwrap();
+/// assert_eq!(high.price, 12.0);
+/// assert_eq!(high.index, 2);
+/// assert_eq!(out.bars_ago, 1);
+/// ```
+#[doc(alias = "PIVOT")]
+#[derive(Debug, Clone)]
+pub struct SwingPoints {
+    left: usize,
+    right: usize,
+    index: usize,
+    count: usize,
+    bar: usize,
+    highs: Box<[f64]>,
+    lows: Box<[f64]>,
+    output: Option<SwingPointsOutput>,
+    // State before the last bar, restored by `amend`
+    saved_value: (f64, f64),
+    saved_count: usize,
+}
+
+/// State of [SwingPoints](struct.SwingPoints.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwingPointsState {
+    pub left: usize,
+    pub right: usize,
+    /// Number of bars so far.
+    pub bar: usize,
+    /// Highs of the last _left_ + _right_ + 1 bars, the oldest first.
+    pub highs: Vec<f64>,
+    /// Lows of the last _left_ + _right_ + 1 bars, the oldest first.
+    pub lows: Vec<f64>,
+    pub output: Option<SwingPointsOutput>,
+    /// High and low which the last bar pushed out of the window, to amend it.
+    pub evicted: Option<(f64, f64)>,
+}
+
+/// Confirmed swing high or swing low.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwingPoint {
+    /// High of a swing high, low of a swing low.
+    pub price: f64,
+    /// Index of the swing bar, counting from 0.
+    pub index: usize,
+}
+
+impl fmt::Display for SwingPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (bar {})", self.price, self.index)
+    }
+}
+
+impl From<SwingPoint> for OutputValue {
+    fn from(point: SwingPoint) -> Self {
+        OutputValue::Named(vec![
+            ("price", point.price.into()),
+            ("index", point.index.into()),
+        ])
+    }
+}
+
+/// Output of [SwingPoints](struct.SwingPoints.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwingPointsOutput {
+    /// Swing high, if one was confirmed.
+    pub high: Option<SwingPoint>,
+    /// Swing low, if one was confirmed.
+    pub low: Option<SwingPoint>,
+    /// Number of bars between the swing bar and the current bar.
+    pub bars_ago: usize,
+}
+
+impl_output!(SwingPointsOutput, high, low, bars_ago);
+
+impl SwingPoints {
+    pub fn new(left: usize, right: usize) -> Result<Self> {
+        if left == 0 {
+            return Err(TaError::invalid_parameter("left", 0.0, ">= 1"));
+        }
+        if right == 0 {
+            return Err(TaError::invalid_parameter("right", 0.0, ">= 1"));
+        }
+        let size = left + right + 1;
+        Ok(Self {
+            left,
+            right,
+            index: 0,
+            count: 0,
+            bar: 0,
+            highs: vec![0.0; size].into_boxed_slice(),
+            lows: vec![0.0; size].into_boxed_slice(),
+            output: None,
+            saved_value: (0.0, 0.0),
+            saved_count: 0,
+        })
+    }
+
+    pub fn left(&self) -> usize {
+        self.left
+    }
+
+    pub fn right(&self) -> usize {
+        self.right
+    }
+
+    pub(super) fn update(&mut self, high: f64, low: f64) -> SwingPointsOutput {
+        let output = self.detect(high, low);
+
+        let size = self.highs.len();
+        self.saved_value = (self.highs[self.index], self.lows[self.index]);
+        self.saved_count = self.count;
+        self.highs[self.index] = high;
+        self.lows[self.index] = low;
+        self.index = if self.index + 1 < size {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < size {
+            self.count += 1;
+        }
+        self.bar += 1;
+
+        self.output = Some(output.clone());
+        output
+    }
+
+    /// Replaces the last bar, see [Amend](../trait.Amend.html).
+    pub(super) fn amend_update(&mut self, high: f64, low: f64) -> SwingPointsOutput {
+        if self.output.is_none() {
+            return self.update(high, low);
+        }
+        self.undo();
+        self.update(high, low)
+    }
+
+    /// Removes the last bar, which must have been added by `update`.
+    pub(super) fn undo(&mut self) {
+        let size = self.highs.len();
+        self.index = (self.index + size - 1) % size;
+        (self.highs[self.index], self.lows[self.index]) = self.saved_value;
+        self.count = self.saved_count;
+        self.bar -= 1;
+    }
+
+    /// Swing points confirmed by a new bar, without adding the bar.
+    pub(super) fn detect(&self, high: f64, low: f64) -> SwingPointsOutput {
+        let size = self.highs.len();
+
+        let mut output = SwingPointsOutput {
+            high: None,
+            low: None,
+            bars_ago: self.right,
+        };
+        if self.count + 1 < size {
+            return output;
+        }
+
+        // the new bar replaces the one at `self.index`, the oldest bar is the one after it and
+        // the swing candidate is `left` bars after the oldest
+        let with_new = |values: &[f64], new: f64, i: usize| {
+            if i == self.index {
+                new
+            } else {
+                values[i]
+            }
+        };
+        let middle = (self.index + 1 + self.left) % size;
+        let (candidate_high, candidate_low) = (
+            with_new(&self.highs, high, middle),
+            with_new(&self.lows, low, middle),
+        );
+
+        let mut is_high = true;
+        let mut is_low = true;
+        for i in (0..size).filter(|&i| i != middle) {
+            is_high &= candidate_high > with_new(&self.highs, high, i);
+            is_low &= candidate_low < with_new(&self.lows, low, i);
+        }
+
+        let index = self.bar - self.right;
+        if is_high {
+            output.high = Some(SwingPoint {
+                price: candidate_high,
+                index,
+            });
+        }
+        if is_low {
+            output.low = Some(SwingPoint {
+                price: candidate_low,
+                index,
+            });
+        }
+        output
+    }
+}
+
+impl Period for SwingPoints {
+    fn period(&self) -> usize {
+        self.highs.len()
+    }
+}
+
+impl Checkpoint for SwingPoints {
+    type State = SwingPointsState;
+
+    fn state(&self) -> Self::State {
+        SwingPointsState {
+            left: self.left,
+            right: self.right,
+            bar: self.bar,
+            highs: ring_values(&self.highs, self.index, self.count),
+            lows: ring_values(&self.lows, self.index, self.count),
+            output: self.output.clone(),
+            evicted: (self.count > 0 && self.saved_count == self.highs.len())
+                .then_some(self.saved_value),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator =
+            Self::new(state.left, state.right).map_err(|_| TaError::InvalidState)?;
+        let size = indicator.highs.len();
+        if state.highs.len() != state.bar.min(size)
+            || state.lows.len() != state.highs.len()
+            || state.output.is_some() != (state.bar > 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        let (highs, index) = ring_from_values(size, &state.highs)?;
+        let (lows, _) = ring_from_values(size, &state.lows)?;
+        indicator.highs = highs;
+        indicator.lows = lows;
+        indicator.index = index;
+        indicator.count = state.highs.len();
+        indicator.bar = state.bar;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != size {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or((0.0, 0.0));
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(SwingPoints, 2);
+
+impl<T: High + Low> Next<&T> for SwingPoints {
+    type Output = SwingPointsOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.update(input.high(), input.low())
+    }
+}
+
+impl<T: High + Low> Peek<&T> for SwingPoints {
+    fn peek(&self, input: &T) -> SwingPointsOutput {
+        self.detect(input.high(), input.low())
+    }
+}
+
+impl<T: High + Low> Amend<&T> for SwingPoints {
+    fn amend(&mut self, input: &T) -> SwingPointsOutput {
+        self.amend_update(input.high(), input.low())
+    }
+}
+
+impl Reset for SwingPoints {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.bar = 0;
+        for i in 0..self.highs.len() {
+            self.highs[i] = 0.0;
+            self.lows[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = (0.0, 0.0);
+        self.saved_count = 0;
+    }
+}
+
+impl Current for SwingPoints {
+    type Output = SwingPointsOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for SwingPoints {
+    fn is_ready(&self) -> bool {
+        self.count == self.highs.len()
+    }
+}
+
+impl Lookback for SwingPoints {
+    fn lookback(&self) -> usize {
+        self.highs.len()
+    }
+}
+
+impl Default for SwingPoints {
+    fn default() -> Self {
+        Self::new(5, 5).unwrap()
+    }
+}
+
+impl fmt::Display for SwingPoints {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SWING({}, {})", self.left, self.right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    type Swing = Option<(f64, usize)>;
+
+    fn swings(out: SwingPointsOutput) -> (Swing, Swing) {
+        (
+            out.high.map(|s| (s.price, s.index)),
+            out.low.map(|s| (s.price, s.index)),
+        )
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(SwingPoints::new(0, 1).is_err());
+        assert!(SwingPoints::new(1, 0).is_err());
+        assert!(SwingPoints::new(1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut sp = SwingPoints::new(2, 1).unwrap();
+
+        let bars = [
+            Bar::new().high(10).low(8),
+            Bar::new().high(11).low(9),
+            Bar::new().high(12).low(10),
+            Bar::new().high(11).low(9.5),
+            Bar::new().high(11.5).low(7),
+            Bar::new().high(11).low(8),
+            Bar::new().high(10).low(9),
+        ];
+
+        for bar in &bars[..3] {
+            assert_eq!(swings(sp.next(bar)), (None, None));
+        }
+        assert_eq!(swings(sp.next(&bars[3])), (Some((12.0, 2)), None));
+        assert_eq!(swings(sp.next(&bars[4])), (None, None));
+        assert_eq!(swings(sp.next(&bars[5])), (None, Some((7.0, 4))));
+
+        let out = sp.next(&bars[6]);
+        assert_eq!(swings(out.clone()), (None, None));
+        assert_eq!(out.bars_ago, 1);
+    }
+
+    #[test]
+    fn test_next_asymmetric() {
+        // only one bar on the left
+        let mut sp = SwingPoints::new(1, 2).unwrap();
+
+        sp.next(&Bar::new().high(11).low(9));
+        sp.next(&Bar::new().high(12).low(10));
+        sp.next(&Bar::new().high(11).low(10));
+        let out = sp.next(&Bar::new().high(10).low(10.5));
+
+        assert_eq!(swings(out), (Some((12.0, 1)), None));
+    }
+
+    #[test]
+    fn test_next_equal_highs() {
+        let mut sp = SwingPoints::new(1, 1).unwrap();
+
+        sp.next(&Bar::new().high(12).low(9));
+        sp.next(&Bar::new().high(12).low(9));
+        assert_eq!(swings(sp.next(&Bar::new().high(11).low(10))), (None, None));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sp = SwingPoints::new(1, 1).unwrap();
+
+        sp.next(&Bar::new().high(10).low(8));
+        sp.next(&Bar::new().high(12).low(9));
+
+        sp.reset();
+        assert_eq!(swings(sp.next(&Bar::new().high(11).low(7))), (None, None));
+        assert_eq!(swings(sp.next(&Bar::new().high(12).low(9))), (None, None));
+        assert_eq!(
+            swings(sp.next(&Bar::new().high(10).low(8))),
+            (Some((12.0, 1)), None)
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        SwingPoints::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let sp = SwingPoints::new(3, 2).unwrap();
+        assert_eq!(format!("{}", sp), "SWING(3, 2)");
+
+        let output = SwingPointsOutput {
+            high: Some(SwingPoint {
+                price: 12.5,
+                index: 4,
+            }),
+            low: None,
+            bars_ago: 2,
+        };
+        assert_eq!(
+            format!("{}", output),
+            "high: 12.5 (bar 4), low: none, bars_ago: 2"
+        );
+    }
+}
@@ -0,0 +1,345 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{ExponentialMovingAverage as Ema, ExponentialMovingAverageState};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Tillson T3 moving average (T3).
+///
+/// Developed by Tim Tillson, T3 is a six-stage cascade of EMAs combined with coefficients derived
+/// from the _volume factor_. It is smoother than an EMA of the same period while lagging less.
+///
+/// # Formula
+///
+/// T3 = c<sub>1</sub> * e<sub>6</sub> + c<sub>2</sub> * e<sub>5</sub> + c<sub>3</sub> * e<sub>4</sub> + c<sub>4</sub> * e<sub>3</sub>
+///
+/// Where:
+///
+/// * _e<sub>1</sub>_ = EMA(price), _e<sub>i</sub>_ = EMA(_e<sub>i-1</sub>_), all with the same _period_
+/// * _c<sub>1</sub>_ = -v<sup>3</sup>
+/// * _c<sub>2</sub>_ = 3v<sup>2</sup> + 3v<sup>3</sup>
+/// * _c<sub>3</sub>_ = -6v<sup>2</sup> - 3v - 3v<sup>3</sup>
+/// * _c<sub>4</sub>_ = 1 + 3v + v<sup>3</sup> + 3v<sup>2</sup>
+/// * _v_ - volume factor
+///
+/// # Parameters
+///
+/// * _period_ - period of the EMAs (integer greater than 0). Default is 5.
+/// * _volume_factor_ - number in range 0..1. Default is 0.7.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::TillsonT3;
+/// use ta::Next;
+///
+/// let mut t3 = TillsonT3::new(2, 0.7).unwrap();
+/// assert_eq!(t3.next(10.0), 10.0);
+/// assert_eq!((t3.next(12.0) * 1000.0).round() / 1000.0, 11.112);
+/// ```
+///
+#[doc(alias = "T3")]
+#[derive(Debug, Clone)]
+pub struct TillsonT3 {
+    period: usize,
+    volume_factor: f64,
+    coefficients: [f64; 4],
+    emas: [Ema; 6],
+    count: usize,
+    output: Option<f64>,
+}
+
+/// State of [TillsonT3](struct.TillsonT3.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TillsonT3State {
+    pub volume_factor: f64,
+    /// The six averages in a row, the first one of the inputs.
+    pub emas: Vec<ExponentialMovingAverageState>,
+    /// Number of inputs, up to the lookback.
+    pub count: usize,
+    pub output: Option<f64>,
+}
+
+impl TillsonT3 {
+    pub fn new(period: usize, volume_factor: f64) -> Result<Self> {
+        if !(0.0..=1.0).contains(&volume_factor) {
+            return Err(TaError::invalid_parameter(
+                "volume_factor",
+                volume_factor,
+                "0..=1",
+            ));
+        }
+
+        let v = volume_factor;
+        let coefficients = [
+            -v.powi(3),
+            3.0 * v.powi(2) + 3.0 * v.powi(3),
+            -6.0 * v.powi(2) - 3.0 * v - 3.0 * v.powi(3),
+            1.0 + 3.0 * v + v.powi(3) + 3.0 * v.powi(2),
+        ];
+
+        Ok(Self {
+            period,
+            volume_factor,
+            coefficients,
+            emas: [
+                Ema::new(period)?,
+                Ema::new(period)?,
+                Ema::new(period)?,
+                Ema::new(period)?,
+                Ema::new(period)?,
+                Ema::new(period)?,
+            ],
+            count: 0,
+            output: None,
+        })
+    }
+
+    pub fn volume_factor(&self) -> f64 {
+        self.volume_factor
+    }
+}
+
+impl Period for TillsonT3 {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for TillsonT3 {
+    type State = TillsonT3State;
+
+    fn state(&self) -> Self::State {
+        TillsonT3State {
+            volume_factor: self.volume_factor,
+            emas: self.emas.iter().map(Ema::state).collect(),
+            count: self.count,
+            output: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let period = state.emas.first().map_or(0, |ema| ema.period);
+        let mut indicator =
+            Self::new(period, state.volume_factor).map_err(|_| TaError::InvalidState)?;
+        if state.emas.len() != indicator.emas.len()
+            || state.emas.iter().any(|ema| ema.period != period)
+            || state.count > indicator.lookback()
+            || state.output.is_none() != (state.count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        for (ema, state) in indicator.emas.iter_mut().zip(state.emas) {
+            *ema = Ema::from_state(state)?;
+        }
+        indicator.count = state.count;
+        indicator.output = state.output;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(TillsonT3, 1);
+
+impl SetPeriod for TillsonT3 {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period, self.volume_factor)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for TillsonT3 {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if !self.is_ready() {
+            self.count += 1;
+        }
+        let mut e = [0.0; 6];
+        let mut value = input;
+        for (ema, out) in self.emas.iter_mut().zip(e.iter_mut()) {
+            value = ema.next(value);
+            *out = value;
+        }
+
+        let [c1, c2, c3, c4] = self.coefficients;
+        let output = c1 * e[5] + c2 * e[4] + c3 * e[3] + c4 * e[2];
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for TillsonT3 {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for TillsonT3 {
+    fn peek(&self, input: f64) -> f64 {
+        let mut e = [0.0; 6];
+        let mut value = input;
+        for (ema, out) in self.emas.iter().zip(e.iter_mut()) {
+            value = ema.peek(value);
+            *out = value;
+        }
+
+        let [c1, c2, c3, c4] = self.coefficients;
+        c1 * e[5] + c2 * e[4] + c3 * e[3] + c4 * e[2]
+    }
+}
+
+impl<T: Close> Peek<&T> for TillsonT3 {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for TillsonT3 {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let mut e = [0.0; 6];
+        let mut value = input;
+        for (ema, out) in self.emas.iter_mut().zip(e.iter_mut()) {
+            value = ema.amend(value);
+            *out = value;
+        }
+
+        let [c1, c2, c3, c4] = self.coefficients;
+        let output = c1 * e[5] + c2 * e[4] + c3 * e[3] + c4 * e[2];
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for TillsonT3 {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for TillsonT3 {
+    fn reset(&mut self) {
+        for ema in self.emas.iter_mut() {
+            ema.reset();
+        }
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl Current for TillsonT3 {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for TillsonT3 {
+    fn is_ready(&self) -> bool {
+        self.count >= self.lookback()
+    }
+}
+
+impl Lookback for TillsonT3 {
+    fn lookback(&self) -> usize {
+        // six EMAs in a row
+        6 * (self.period - 1) + 1
+    }
+}
+
+impl Default for TillsonT3 {
+    fn default() -> Self {
+        Self::new(5, 0.7).unwrap()
+    }
+}
+
+impl fmt::Display for TillsonT3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "T3({}, {})", self.period, self.volume_factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(TillsonT3);
+
+    #[test]
+    fn test_new() {
+        assert!(TillsonT3::new(0, 0.7).is_err());
+        assert!(TillsonT3::new(5, -0.1).is_err());
+        assert!(TillsonT3::new(5, 1.1).is_err());
+        assert!(TillsonT3::new(5, f64::NAN).is_err());
+        assert!(TillsonT3::new(1, 0.0).is_ok());
+        assert!(TillsonT3::new(1, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut t3 = TillsonT3::new(2, 0.7).unwrap();
+
+        assert_eq!(round(t3.next(10.0)), 10.0);
+        assert_eq!(round(t3.next(12.0)), 11.112);
+        assert_eq!(round(t3.next(11.0)), 11.247);
+        assert_eq!(round(t3.next(14.0)), 12.802);
+    }
+
+    #[test]
+    fn test_next_zero_volume_factor() {
+        // with v = 0 T3 is a triple EMA cascade
+        let mut t3 = TillsonT3::new(3, 0.0).unwrap();
+        let mut emas = [
+            Ema::new(3).unwrap(),
+            Ema::new(3).unwrap(),
+            Ema::new(3).unwrap(),
+        ];
+
+        for x in &[2.0, 5.0, 1.0, 6.25] {
+            let expected = emas.iter_mut().fold(*x, |value, ema| ema.next(value));
+            assert_eq!(round(t3.next(*x)), round(expected));
+        }
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut t3 = TillsonT3::new(2, 0.7).unwrap();
+
+        assert_eq!(round(t3.next(&Bar::new().close(10))), 10.0);
+        assert_eq!(round(t3.next(&Bar::new().close(12))), 11.112);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut t3 = TillsonT3::new(2, 0.7).unwrap();
+
+        t3.next(10.0);
+        t3.next(12.0);
+
+        t3.reset();
+        assert_eq!(round(t3.next(10.0)), 10.0);
+        assert_eq!(round(t3.next(12.0)), 11.112);
+    }
+
+    #[test]
+    fn test_default() {
+        TillsonT3::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let t3 = TillsonT3::new(5, 0.7).unwrap();
+        assert_eq!(format!("{}", t3), "T3(5, 0.7)");
+    }
+}
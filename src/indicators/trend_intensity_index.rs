@@ -0,0 +1,322 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::indicators::{SimpleMovingAverage as Sma, SimpleMovingAverageState};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Trend Intensity Index (TII).
+///
+/// Share of the last _window_ closes that are above their simple moving average, as a value
+/// from 0 to 100. Values above 80 point to a strong uptrend, values below 20 to a strong
+/// downtrend, and values around 50 to the absence of a trend.
+///
+/// # Formula
+///
+/// TII = 100 × Count(Close > SMA) / _n_
+///
+/// Where:
+///
+/// * _SMA_ - simple moving average of _period_ closes
+/// * _Count_ - number of the last _n_ bars where the close was above the SMA
+/// * _n_ - _window_, or the number of bars seen so far if it is smaller
+///
+/// # Parameters
+///
+/// * _period_ - period of the SMA (integer greater than 0). Default is 60.
+/// * _window_ - number of bars to count (integer greater than 0). Default is 30.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::TrendIntensityIndex;
+/// use ta::Next;
+///
+/// let mut tii = TrendIntensityIndex::new(2, 4).unwrap();
+/// assert_eq!(tii.next(10.0), 0.0);
+/// assert_eq!(tii.next(12.0), 50.0);
+/// assert_eq!(tii.next(14.0), 100.0 * 2.0 / 3.0);
+/// assert_eq!(tii.next(13.0), 50.0);
+/// ```
+#[doc(alias = "TII")]
+#[derive(Debug, Clone)]
+pub struct TrendIntensityIndex {
+    sma: Sma,
+    window: usize,
+    index: usize,
+    count: usize,
+    above: usize,
+    deque: Box<[bool]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: bool,
+    saved_count: usize,
+    saved_above: usize,
+}
+
+/// State of [TrendIntensityIndex](struct.TrendIntensityIndex.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrendIntensityIndexState {
+    pub sma: SimpleMovingAverageState,
+    pub window: usize,
+    /// Whether the inputs in the window were above the SMA, the oldest first.
+    pub above: Vec<bool>,
+    /// Whether the input pushed out by the last one was above the SMA, to amend the last one.
+    pub evicted: Option<bool>,
+}
+
+impl TrendIntensityIndex {
+    pub fn new(period: usize, window: usize) -> Result<Self> {
+        if window == 0 {
+            return Err(TaError::invalid_parameter("window", 0.0, ">= 1"));
+        }
+        Ok(Self {
+            sma: Sma::new(period)?,
+            window,
+            index: 0,
+            count: 0,
+            above: 0,
+            deque: vec![false; window].into_boxed_slice(),
+            output: None,
+            saved_value: false,
+            saved_count: 0,
+            saved_above: 0,
+        })
+    }
+
+    fn update(&mut self, is_above: bool) -> f64 {
+        if self.count < self.window {
+            self.count += 1;
+        } else if self.deque[self.index] {
+            self.above -= 1;
+        }
+        if is_above {
+            self.above += 1;
+        }
+        self.deque[self.index] = is_above;
+
+        self.index = if self.index + 1 < self.window {
+            self.index + 1
+        } else {
+            0
+        };
+
+        let output = 100.0 * self.above as f64 / self.count as f64;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Period for TrendIntensityIndex {
+    fn period(&self) -> usize {
+        self.sma.period()
+    }
+}
+
+impl Checkpoint for TrendIntensityIndex {
+    type State = TrendIntensityIndexState;
+
+    fn state(&self) -> Self::State {
+        TrendIntensityIndexState {
+            sma: self.sma.state(),
+            window: self.window,
+            above: ring_values(&self.deque, self.index, self.count),
+            evicted: (self.count > 0 && self.saved_count == self.window)
+                .then_some(self.saved_value),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator =
+            Self::new(state.sma.period, state.window).map_err(|_| TaError::InvalidState)?;
+        let (deque, index) = ring_from_values(state.window, &state.above)?;
+        let count = state.above.len();
+        let above = state.above.iter().filter(|&&above| above).count();
+        indicator.sma = Sma::from_state(state.sma)?;
+        indicator.deque = deque;
+        indicator.index = index;
+        indicator.count = count;
+        indicator.above = above;
+        indicator.output = (count > 0).then(|| 100.0 * above as f64 / count as f64);
+        if state.evicted.is_some() && count != indicator.window {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or(false);
+        indicator.saved_count = match state.evicted {
+            Some(_) => count,
+            None => count.saturating_sub(1),
+        };
+        // The count before the last input, from the input it pushed out
+        indicator.saved_above = above + usize::from(state.evicted == Some(true))
+            - usize::from(state.above.last() == Some(&true));
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(TrendIntensityIndex, 2);
+
+impl Next<f64> for TrendIntensityIndex {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
+        self.saved_above = self.above;
+        let is_above = input > self.sma.next(input);
+        self.update(is_above)
+    }
+}
+
+impl<T: Close> Next<&T> for TrendIntensityIndex {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for TrendIntensityIndex {
+    fn peek(&self, input: f64) -> f64 {
+        let is_above = input > self.sma.peek(input);
+
+        let (count, mut above) = if self.count < self.window {
+            (self.count + 1, self.above)
+        } else if self.deque[self.index] {
+            (self.count, self.above - 1)
+        } else {
+            (self.count, self.above)
+        };
+        if is_above {
+            above += 1;
+        }
+
+        100.0 * above as f64 / count as f64
+    }
+}
+
+impl<T: Close> Peek<&T> for TrendIntensityIndex {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for TrendIntensityIndex {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.window - 1) % self.window;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.above = self.saved_above;
+        let is_above = input > self.sma.amend(input);
+        self.update(is_above)
+    }
+}
+
+impl<T: Close> Amend<&T> for TrendIntensityIndex {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for TrendIntensityIndex {
+    fn reset(&mut self) {
+        self.sma.reset();
+        self.index = 0;
+        self.count = 0;
+        self.above = 0;
+        for i in 0..self.window {
+            self.deque[i] = false;
+        }
+        self.output = None;
+        self.saved_value = false;
+        self.saved_count = 0;
+        self.saved_above = 0;
+    }
+}
+
+impl Current for TrendIntensityIndex {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for TrendIntensityIndex {
+    fn is_ready(&self) -> bool {
+        self.sma.is_ready() && self.count == self.window
+    }
+}
+
+impl Lookback for TrendIntensityIndex {
+    fn lookback(&self) -> usize {
+        self.sma.lookback().max(self.window)
+    }
+}
+
+impl Default for TrendIntensityIndex {
+    fn default() -> Self {
+        Self::new(60, 30).unwrap()
+    }
+}
+
+impl fmt::Display for TrendIntensityIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TII({}, {})", self.sma.period(), self.window)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(TrendIntensityIndex);
+
+    #[test]
+    fn test_new() {
+        assert!(TrendIntensityIndex::new(0, 30).is_err());
+        assert!(TrendIntensityIndex::new(60, 0).is_err());
+        assert!(TrendIntensityIndex::new(1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut tii = TrendIntensityIndex::new(2, 2).unwrap();
+
+        assert_eq!(tii.next(10.0), 0.0);
+        assert_eq!(tii.next(12.0), 50.0);
+        assert_eq!(tii.next(14.0), 100.0);
+        assert_eq!(tii.next(13.0), 50.0);
+        assert_eq!(tii.next(&Bar::new().close(11)), 0.0);
+        assert_eq!(tii.next(&Bar::new().close(12)), 50.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut tii = TrendIntensityIndex::new(2, 2).unwrap();
+
+        tii.next(10.0);
+        tii.next(12.0);
+        tii.reset();
+        assert_eq!(tii.next(20.0), 0.0);
+        assert_eq!(tii.next(21.0), 50.0);
+    }
+
+    #[test]
+    fn test_default() {
+        TrendIntensityIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let tii = TrendIntensityIndex::new(60, 30).unwrap();
+        assert_eq!(format!("{}", tii), "TII(60, 30)");
+    }
+}
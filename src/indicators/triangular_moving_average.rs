@@ -0,0 +1,293 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{SimpleMovingAverage as Sma, SimpleMovingAverageState};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Triangular moving average (TMA).
+///
+/// A simple moving average of a simple moving average. The weights of the prices form a
+/// triangle: they grow towards the middle of the window and fall off towards both ends, which
+/// gives a smoother line than a [SMA](type.SimpleMovingAverage.html) of the same period.
+///
+/// # Formula
+///
+/// TMA = SMA(SMA(Price, _n<sub>1</sub>_), _n<sub>2</sub>_)
+///
+/// Where:
+///
+/// * _n<sub>1</sub>_ = _period_ / 2 + 1 (integer division)
+/// * _n<sub>2</sub>_ = _period_ / 2, rounded up
+///
+/// so that together the two averages cover exactly _period_ prices.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::TriangularMovingAverage;
+/// use ta::Next;
+///
+/// let mut tma = TriangularMovingAverage::new(4).unwrap();
+/// assert_eq!(tma.next(1.0), 1.0);
+/// assert_eq!(tma.next(2.0), 1.25);
+/// assert_eq!(tma.next(3.0), 1.75);
+/// assert_eq!(tma.next(4.0), 2.5);
+/// assert_eq!(tma.next(5.0), 3.5);
+/// ```
+///
+/// # Links
+///
+/// * [Moving average, Wikipedia](https://en.wikipedia.org/wiki/Moving_average)
+///
+#[doc(alias = "TMA")]
+#[derive(Debug, Clone)]
+pub struct TriangularMovingAverage {
+    period: usize,
+    inner: Sma,
+    outer: Sma,
+    count: usize,
+    output: Option<f64>,
+}
+
+/// State of [TriangularMovingAverage](struct.TriangularMovingAverage.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriangularMovingAverageState {
+    pub period: usize,
+    /// Average of the inputs.
+    pub inner: SimpleMovingAverageState,
+    /// Average of the inner averages.
+    pub outer: SimpleMovingAverageState,
+    /// Number of inputs since the inner average is ready, up to the period of the outer one.
+    pub count: usize,
+    pub output: Option<f64>,
+}
+
+impl TriangularMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
+            _ => Ok(Self {
+                period,
+                inner: Sma::new(period / 2 + 1)?,
+                outer: Sma::new(period.div_ceil(2))?,
+                count: 0,
+                output: None,
+            }),
+        }
+    }
+}
+
+impl Period for TriangularMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for TriangularMovingAverage {
+    type State = TriangularMovingAverageState;
+
+    fn state(&self) -> Self::State {
+        TriangularMovingAverageState {
+            period: self.period,
+            inner: self.inner.state(),
+            outer: self.outer.state(),
+            count: self.count,
+            output: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        if state.inner.period != indicator.inner.period()
+            || state.outer.period != indicator.outer.period()
+            || state.count > state.outer.period
+            || state.output.is_none() != state.inner.inputs.is_empty()
+        {
+            return Err(TaError::InvalidState);
+        }
+        indicator.inner = Sma::from_state(state.inner)?;
+        indicator.outer = Sma::from_state(state.outer)?;
+        indicator.count = state.count;
+        indicator.output = state.output;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(TriangularMovingAverage, 1);
+
+impl SetPeriod for TriangularMovingAverage {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for TriangularMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let inner = self.inner.next(input);
+        if self.inner.is_ready() && !self.is_ready() {
+            self.count += 1;
+        }
+        let output = self.outer.next(inner);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for TriangularMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for TriangularMovingAverage {
+    fn peek(&self, input: f64) -> f64 {
+        self.outer.peek(self.inner.peek(input))
+    }
+}
+
+impl<T: Close> Peek<&T> for TriangularMovingAverage {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for TriangularMovingAverage {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let inner = self.inner.amend(input);
+        let output = self.outer.amend(inner);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for TriangularMovingAverage {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for TriangularMovingAverage {
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.outer.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl Current for TriangularMovingAverage {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for TriangularMovingAverage {
+    fn is_ready(&self) -> bool {
+        self.count == self.outer.period()
+    }
+}
+
+impl Lookback for TriangularMovingAverage {
+    fn lookback(&self) -> usize {
+        self.inner.lookback() + self.outer.period() - 1
+    }
+}
+
+impl Default for TriangularMovingAverage {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for TriangularMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TMA({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(TriangularMovingAverage);
+
+    #[test]
+    fn test_new() {
+        assert!(TriangularMovingAverage::new(0).is_err());
+        assert!(TriangularMovingAverage::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_periods() {
+        // Both averages together always span the full period
+        for period in 1..20 {
+            let tma = TriangularMovingAverage::new(period).unwrap();
+            assert_eq!(tma.inner.period() + tma.outer.period() - 1, period);
+        }
+    }
+
+    #[test]
+    fn test_next() {
+        // Weights 1, 2, 3, 2, 1
+        let mut tma = TriangularMovingAverage::new(5).unwrap();
+
+        for input in [0.0, 0.0, 0.0, 0.0] {
+            tma.next(input);
+        }
+        assert_eq!(round(tma.next(9.0)), 1.0);
+        assert_eq!(round(tma.next(0.0)), 2.0);
+        assert_eq!(round(tma.next(&Bar::new().close(0))), 3.0);
+        assert_eq!(round(tma.next(&Bar::new().close(0))), 2.0);
+        assert_eq!(round(tma.next(&Bar::new().close(0))), 1.0);
+        assert_eq!(round(tma.next(&Bar::new().close(0))), 0.0);
+    }
+
+    #[test]
+    fn test_period_1() {
+        let mut tma = TriangularMovingAverage::new(1).unwrap();
+        assert_eq!(tma.next(3.0), 3.0);
+        assert_eq!(tma.next(7.0), 7.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut tma = TriangularMovingAverage::new(4).unwrap();
+
+        tma.next(1.0);
+        tma.next(2.0);
+        tma.reset();
+        assert_eq!(tma.next(10.0), 10.0);
+    }
+
+    #[test]
+    fn test_default() {
+        TriangularMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let tma = TriangularMovingAverage::new(10).unwrap();
+        assert_eq!(format!("{}", tma), "TMA(10)");
+    }
+}
@@ -0,0 +1,307 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{ExponentialMovingAverage as Ema, ExponentialMovingAverageState};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Triple exponential moving average (TEMA).
+///
+/// A moving average that reduces the lag of a regular EMA by combining a single, a double and
+/// a triple smoothed EMA of the input. Not to be confused with the TRIX oscillator, which is
+/// the rate of change of a triple smoothed EMA.
+///
+/// # Formula
+///
+/// TEMA = 3 * EMA<sub>1</sub> - 3 * EMA<sub>2</sub> + EMA<sub>3</sub>
+///
+/// Where:
+///
+/// * _EMA<sub>1</sub>_ - EMA of the input value
+/// * _EMA<sub>2</sub>_ - EMA of _EMA<sub>1</sub>_
+/// * _EMA<sub>3</sub>_ - EMA of _EMA<sub>2</sub>_
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::TripleExponentialMovingAverage;
+/// use ta::Next;
+///
+/// let mut tema = TripleExponentialMovingAverage::new(3).unwrap();
+/// assert_eq!(tema.next(2.0), 2.0);
+/// assert_eq!(tema.next(5.0), 4.625);
+/// assert_eq!(tema.next(1.0), 1.6875);
+/// ```
+///
+/// # Links
+///
+/// * [Triple exponential moving average, Wikipedia](https://en.wikipedia.org/wiki/Triple_exponential_moving_average)
+///
+#[doc(alias = "TEMA")]
+#[derive(Debug, Clone)]
+pub struct TripleExponentialMovingAverage {
+    period: usize,
+    ema1: Ema,
+    ema2: Ema,
+    ema3: Ema,
+    count: usize,
+    output: Option<f64>,
+}
+
+/// State of [TripleExponentialMovingAverage](struct.TripleExponentialMovingAverage.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TripleExponentialMovingAverageState {
+    pub ema1: ExponentialMovingAverageState,
+    pub ema2: ExponentialMovingAverageState,
+    pub ema3: ExponentialMovingAverageState,
+    /// Number of inputs, up to the lookback.
+    pub count: usize,
+    pub output: Option<f64>,
+}
+
+impl TripleExponentialMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            period,
+            ema1: Ema::new(period)?,
+            ema2: Ema::new(period)?,
+            ema3: Ema::new(period)?,
+            count: 0,
+            output: None,
+        })
+    }
+}
+
+impl Period for TripleExponentialMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for TripleExponentialMovingAverage {
+    type State = TripleExponentialMovingAverageState;
+
+    fn state(&self) -> Self::State {
+        TripleExponentialMovingAverageState {
+            ema1: self.ema1.state(),
+            ema2: self.ema2.state(),
+            ema3: self.ema3.state(),
+            count: self.count,
+            output: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(state.ema1.period).map_err(|_| TaError::InvalidState)?;
+        if state.ema2.period != state.ema1.period
+            || state.ema3.period != state.ema1.period
+            || state.count > indicator.lookback()
+            || state.output.is_none() != (state.count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        indicator.ema1 = Ema::from_state(state.ema1)?;
+        indicator.ema2 = Ema::from_state(state.ema2)?;
+        indicator.ema3 = Ema::from_state(state.ema3)?;
+        indicator.count = state.count;
+        indicator.output = state.output;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(TripleExponentialMovingAverage, 1);
+
+impl SetPeriod for TripleExponentialMovingAverage {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for TripleExponentialMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if !self.is_ready() {
+            self.count += 1;
+        }
+        let ema1 = self.ema1.next(input);
+        let ema2 = self.ema2.next(ema1);
+        let ema3 = self.ema3.next(ema2);
+
+        let output = 3.0 * ema1 - 3.0 * ema2 + ema3;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for TripleExponentialMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for TripleExponentialMovingAverage {
+    fn peek(&self, input: f64) -> f64 {
+        let ema1 = self.ema1.peek(input);
+        let ema2 = self.ema2.peek(ema1);
+        let ema3 = self.ema3.peek(ema2);
+
+        3.0 * ema1 - 3.0 * ema2 + ema3
+    }
+}
+
+impl<T: Close> Peek<&T> for TripleExponentialMovingAverage {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for TripleExponentialMovingAverage {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let ema1 = self.ema1.amend(input);
+        let ema2 = self.ema2.amend(ema1);
+        let ema3 = self.ema3.amend(ema2);
+
+        let output = 3.0 * ema1 - 3.0 * ema2 + ema3;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for TripleExponentialMovingAverage {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for TripleExponentialMovingAverage {
+    fn reset(&mut self) {
+        self.ema1.reset();
+        self.ema2.reset();
+        self.ema3.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl Current for TripleExponentialMovingAverage {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for TripleExponentialMovingAverage {
+    fn is_ready(&self) -> bool {
+        self.count >= self.lookback()
+    }
+}
+
+impl Lookback for TripleExponentialMovingAverage {
+    fn lookback(&self) -> usize {
+        // three EMAs in a row
+        3 * (self.period - 1) + 1
+    }
+}
+
+impl Default for TripleExponentialMovingAverage {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for TripleExponentialMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TEMA({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use crate::TryNext;
+
+    type Tema = TripleExponentialMovingAverage;
+
+    test_indicator!(Tema);
+
+    #[test]
+    fn test_new() {
+        assert!(Tema::new(0).is_err());
+        assert!(Tema::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut tema = Tema::new(3).unwrap();
+
+        assert_eq!(tema.next(2.0), 2.0);
+        assert_eq!(tema.next(5.0), 4.625);
+        assert_eq!(tema.next(1.0), 1.6875);
+        assert_eq!(tema.next(6.25), 5.53125);
+
+        let mut tema = Tema::new(3).unwrap();
+        let bar1 = Bar::new().close(2);
+        let bar2 = Bar::new().close(5);
+        assert_eq!(tema.next(&bar1), 2.0);
+        assert_eq!(tema.next(&bar2), 4.625);
+    }
+
+    #[test]
+    fn test_next_period_1() {
+        let mut tema = Tema::new(1).unwrap();
+
+        assert_eq!(tema.next(2.0), 2.0);
+        assert_eq!(tema.next(7.5), 7.5);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut tema = Tema::new(5).unwrap();
+
+        assert_eq!(tema.next(4.0), 4.0);
+        tema.next(10.0);
+        tema.next(15.0);
+        assert_ne!(tema.next(4.0), 4.0);
+
+        tema.reset();
+        assert_eq!(tema.next(4.0), 4.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut tema = Tema::new(2).unwrap();
+        for input in [1.0, 2.0, 3.0] {
+            assert_eq!(tema.try_next(input), None);
+        }
+        assert!(tema.try_next(4.0).is_some());
+    }
+
+    #[test]
+    fn test_default() {
+        Tema::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let tema = Tema::new(7).unwrap();
+        assert_eq!(format!("{}", tema), "TEMA(7)");
+    }
+}
@@ -0,0 +1,358 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{ExponentialMovingAverage as Ema, ExponentialMovingAverageState};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Triple exponential average oscillator (TRIX).
+///
+/// The percentage rate of change of a triple exponentially smoothed moving average.
+/// Unlike [TEMA](struct.TripleExponentialMovingAverage.html) it is an oscillator
+/// that fluctuates around zero.
+///
+/// # Formula
+///
+/// TRIX<sub>t</sub> = (EMA3<sub>t</sub> - EMA3<sub>t-1</sub>) / EMA3<sub>t-1</sub> * 100
+///
+/// Signal = EMA(TRIX, _signal_period_)
+///
+/// Where:
+///
+/// * _EMA3_ - EMA of EMA of EMA of the input value, all with the same _period_
+///
+/// The first value of TRIX is 0.
+///
+/// # Parameters
+///
+/// * _period_ - period of the EMAs (integer greater than 0). Default is 15.
+/// * _signal_period_ - period of the signal EMA (integer greater than 0). Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::Trix;
+/// use ta::Next;
+///
+/// let mut trix = Trix::new(3, 2).unwrap();
+///
+/// let out = trix.next(2.0);
+/// assert_eq!(out.trix, 0.0);
+/// assert_eq!(out.signal, 0.0);
+///
+/// let out = trix.next(5.0);
+/// assert_eq!(out.trix, 18.75);
+/// assert_eq!(out.signal, 12.5);
+/// ```
+///
+/// # Links
+///
+/// * [Trix, Wikipedia](https://en.wikipedia.org/wiki/Trix_(technical_analysis))
+///
+#[doc(alias = "TRIX")]
+#[derive(Debug, Clone)]
+pub struct Trix {
+    period: usize,
+    ema1: Ema,
+    ema2: Ema,
+    ema3: Ema,
+    signal_ema: Ema,
+    prev_ema3: Option<f64>,
+    count: usize,
+    output: Option<TrixOutput>,
+    // Triple smoothed value before the last input, restored by `amend`
+    saved_prev_ema3: Option<f64>,
+}
+
+/// State of [Trix](struct.Trix.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrixState {
+    pub ema1: ExponentialMovingAverageState,
+    pub ema2: ExponentialMovingAverageState,
+    pub ema3: ExponentialMovingAverageState,
+    pub signal_ema: ExponentialMovingAverageState,
+    /// Last triple smoothed input.
+    pub previous_ema3: Option<f64>,
+    /// Number of inputs, up to the lookback.
+    pub count: usize,
+    pub output: Option<TrixOutput>,
+    /// Triple smoothed value before the last one, to amend the last input.
+    pub ema3_before_last: Option<f64>,
+}
+
+/// Output of [Trix](struct.Trix.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrixOutput {
+    pub trix: f64,
+    pub signal: f64,
+}
+
+impl_output!(TrixOutput, trix, signal);
+
+impl From<TrixOutput> for (f64, f64) {
+    fn from(o: TrixOutput) -> Self {
+        (o.trix, o.signal)
+    }
+}
+
+impl Trix {
+    pub fn new(period: usize, signal_period: usize) -> Result<Self> {
+        Ok(Self {
+            period,
+            ema1: Ema::new(period)?,
+            ema2: Ema::new(period)?,
+            ema3: Ema::new(period)?,
+            signal_ema: Ema::new(signal_period)?,
+            prev_ema3: None,
+            count: 0,
+            output: None,
+            saved_prev_ema3: None,
+        })
+    }
+}
+
+impl Period for Trix {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for Trix {
+    type State = TrixState;
+
+    fn state(&self) -> Self::State {
+        TrixState {
+            ema1: self.ema1.state(),
+            ema2: self.ema2.state(),
+            ema3: self.ema3.state(),
+            signal_ema: self.signal_ema.state(),
+            previous_ema3: self.prev_ema3,
+            count: self.count,
+            output: self.output.clone(),
+            ema3_before_last: self.saved_prev_ema3,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(state.ema1.period, state.signal_ema.period)
+            .map_err(|_| TaError::InvalidState)?;
+        if state.ema2.period != state.ema1.period
+            || state.ema3.period != state.ema1.period
+            || state.previous_ema3.is_none() != state.output.is_none()
+            || state.count > indicator.lookback()
+            || state.output.is_none() != (state.count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        indicator.ema1 = Ema::from_state(state.ema1)?;
+        indicator.ema2 = Ema::from_state(state.ema2)?;
+        indicator.ema3 = Ema::from_state(state.ema3)?;
+        indicator.signal_ema = Ema::from_state(state.signal_ema)?;
+        indicator.prev_ema3 = state.previous_ema3;
+        indicator.count = state.count;
+        indicator.output = state.output;
+        indicator.saved_prev_ema3 = state.ema3_before_last;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(Trix, 2);
+
+impl Next<f64> for Trix {
+    type Output = TrixOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if !self.is_ready() {
+            self.count += 1;
+        }
+        let ema1 = self.ema1.next(input);
+        let ema2 = self.ema2.next(ema1);
+        let ema3 = self.ema3.next(ema2);
+
+        let trix = match self.prev_ema3 {
+            Some(prev) if prev != 0.0 => (ema3 - prev) / prev * 100.0,
+            _ => 0.0,
+        };
+        self.saved_prev_ema3 = self.prev_ema3;
+        self.prev_ema3 = Some(ema3);
+
+        let output = TrixOutput {
+            trix,
+            signal: self.signal_ema.next(trix),
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for Trix {
+    type Output = TrixOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for Trix {
+    fn peek(&self, input: f64) -> TrixOutput {
+        let ema1 = self.ema1.peek(input);
+        let ema2 = self.ema2.peek(ema1);
+        let ema3 = self.ema3.peek(ema2);
+
+        let trix = match self.prev_ema3 {
+            Some(prev) if prev != 0.0 => (ema3 - prev) / prev * 100.0,
+            _ => 0.0,
+        };
+
+        TrixOutput {
+            trix,
+            signal: self.signal_ema.peek(trix),
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for Trix {
+    fn peek(&self, input: &T) -> TrixOutput {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for Trix {
+    fn amend(&mut self, input: f64) -> TrixOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let ema1 = self.ema1.amend(input);
+        let ema2 = self.ema2.amend(ema1);
+        let ema3 = self.ema3.amend(ema2);
+        let trix = match self.saved_prev_ema3 {
+            Some(prev) if prev != 0.0 => (ema3 - prev) / prev * 100.0,
+            _ => 0.0,
+        };
+        self.prev_ema3 = Some(ema3);
+
+        let output = TrixOutput {
+            trix,
+            signal: self.signal_ema.amend(trix),
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for Trix {
+    fn amend(&mut self, input: &T) -> TrixOutput {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for Trix {
+    fn reset(&mut self) {
+        self.ema1.reset();
+        self.ema2.reset();
+        self.ema3.reset();
+        self.signal_ema.reset();
+        self.prev_ema3 = None;
+        self.count = 0;
+        self.output = None;
+        self.saved_prev_ema3 = None;
+    }
+}
+
+impl Current for Trix {
+    type Output = TrixOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for Trix {
+    fn is_ready(&self) -> bool {
+        self.count >= self.lookback()
+    }
+}
+
+impl Lookback for Trix {
+    fn lookback(&self) -> usize {
+        // three EMAs in a row and one more value for the first change
+        3 * (self.period - 1) + 1 + self.signal_ema.period()
+    }
+}
+
+impl Default for Trix {
+    fn default() -> Self {
+        Self::new(15, 9).unwrap()
+    }
+}
+
+impl fmt::Display for Trix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TRIX({}, {})", self.period, self.signal_ema.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Trix);
+
+    fn round(nums: (f64, f64)) -> (f64, f64) {
+        let n0 = (nums.0 * 1000.0).round() / 1000.0;
+        let n1 = (nums.1 * 1000.0).round() / 1000.0;
+        (n0, n1)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Trix::new(0, 9).is_err());
+        assert!(Trix::new(15, 0).is_err());
+        assert!(Trix::new(1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut trix = Trix::new(3, 2).unwrap();
+
+        assert_eq!(round(trix.next(2.0).into()), (0.0, 0.0));
+        assert_eq!(round(trix.next(5.0).into()), (18.75, 12.5));
+        assert_eq!(round(trix.next(1.0).into()), (2.632, 5.921));
+        assert_eq!(round(trix.next(6.25).into()), (19.231, 14.794));
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut trix = Trix::new(3, 2).unwrap();
+
+        assert_eq!(round(trix.next(&Bar::new().close(2)).into()), (0.0, 0.0));
+        assert_eq!(round(trix.next(&Bar::new().close(5)).into()), (18.75, 12.5));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut trix = Trix::new(3, 2).unwrap();
+
+        trix.next(2.0);
+        trix.next(5.0);
+
+        trix.reset();
+        assert_eq!(round(trix.next(2.0).into()), (0.0, 0.0));
+        assert_eq!(round(trix.next(5.0).into()), (18.75, 12.5));
+    }
+
+    #[test]
+    fn test_default() {
+        Trix::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let trix = Trix::new(15, 9).unwrap();
+        assert_eq!(format!("{}", trix), "TRIX(15, 9)");
+    }
+}
@@ -1,7 +1,10 @@
-use std::fmt;
+use core::fmt;
 
+use crate::errors::{Result, TaError};
 use crate::helpers::max3;
-use crate::{Close, High, Low, Next, Reset};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Numeric, Peek, Reset,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -49,47 +52,108 @@ use serde::{Deserialize, Serialize};
 ///     }
 /// }
 /// ```
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct TrueRange {
-    prev_close: Option<f64>,
+pub struct TrueRange<N = f64> {
+    prev_close: Option<N>,
+    output: Option<N>,
+    // Previous close of the last input, restored by `amend`
+    saved_prev_close: Option<N>,
+}
+
+/// State of [TrueRange](generic/struct.TrueRange.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrueRangeState<N = f64> {
+    /// Close of the last input, `None` before the first one.
+    pub close: Option<N>,
+    /// Close of the input before the last one, to amend the last one.
+    pub previous_close: Option<N>,
+    /// True range of the last input.
+    pub value: Option<N>,
 }
 
-impl TrueRange {
+impl<N: Numeric> TrueRange<N> {
     pub fn new() -> Self {
-        Self { prev_close: None }
+        Self {
+            prev_close: None,
+            output: None,
+            saved_prev_close: None,
+        }
+    }
+
+    pub(super) fn next_value(&mut self, input: N) -> N {
+        self.saved_prev_close = self.prev_close;
+        let distance = match self.prev_close {
+            Some(prev) => (input - prev).abs(),
+            None => N::zero(),
+        };
+        self.prev_close = Some(input);
+        self.output = Some(distance);
+        distance
+    }
+
+    pub(super) fn peek_value(&self, input: N) -> N {
+        match self.prev_close {
+            Some(prev) => (input - prev).abs(),
+            None => N::zero(),
+        }
+    }
+
+    pub(super) fn amend_value(&mut self, input: N) -> N {
+        if self.output.is_none() {
+            return self.next_value(input);
+        }
+        self.prev_close = self.saved_prev_close;
+        self.next_value(input)
     }
 }
 
-impl Default for TrueRange {
+impl<N: Numeric> Checkpoint for TrueRange<N> {
+    type State = TrueRangeState<N>;
+
+    fn state(&self) -> Self::State {
+        TrueRangeState {
+            close: self.prev_close,
+            previous_close: self.saved_prev_close,
+            value: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.value.is_some() != state.close.is_some()
+            || (state.previous_close.is_some() && state.close.is_none())
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            prev_close: state.close,
+            output: state.value,
+            saved_prev_close: state.previous_close,
+        })
+    }
+}
+
+impl_versioned_serde!(TrueRange<N>, 1);
+
+impl<N: Numeric> Default for TrueRange<N> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl fmt::Display for TrueRange {
+impl<N: Numeric> fmt::Display for TrueRange<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "TRUE_RANGE()")
     }
 }
 
-impl Next<f64> for TrueRange {
-    type Output = f64;
-
-    fn next(&mut self, input: f64) -> Self::Output {
-        let distance = match self.prev_close {
-            Some(prev) => (input - prev).abs(),
-            None => 0.0,
-        };
-        self.prev_close = Some(input);
-        distance
-    }
-}
+impl_numeric_inputs!(TrueRange);
 
-impl<T: High + Low + Close> Next<&T> for TrueRange {
-    type Output = f64;
+impl<N: Numeric, T: High<N> + Low<N> + Close<N>> Next<&T> for TrueRange<N> {
+    type Output = N;
 
     fn next(&mut self, bar: &T) -> Self::Output {
+        self.saved_prev_close = self.prev_close;
         let max_dist = match self.prev_close {
             Some(prev_close) => {
                 let dist1 = bar.high() - bar.low();
@@ -100,19 +164,67 @@ impl<T: High + Low + Close> Next<&T> for TrueRange {
             None => bar.high() - bar.low(),
         };
         self.prev_close = Some(bar.close());
+        self.output = Some(max_dist);
         max_dist
     }
 }
 
-impl Reset for TrueRange {
+impl<N: Numeric, T: High<N> + Low<N> + Close<N>> Peek<&T> for TrueRange<N> {
+    fn peek(&self, bar: &T) -> N {
+        match self.prev_close {
+            Some(prev_close) => {
+                let dist1 = bar.high() - bar.low();
+                let dist2 = (bar.high() - prev_close).abs();
+                let dist3 = (bar.low() - prev_close).abs();
+                max3(dist1, dist2, dist3)
+            }
+            None => bar.high() - bar.low(),
+        }
+    }
+}
+
+impl<N: Numeric, T: High<N> + Low<N> + Close<N>> Amend<&T> for TrueRange<N> {
+    fn amend(&mut self, input: &T) -> N {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.prev_close = self.saved_prev_close;
+        self.next(input)
+    }
+}
+
+impl<N: Numeric> Reset for TrueRange<N> {
     fn reset(&mut self) {
         self.prev_close = None;
+        self.output = None;
+        self.saved_prev_close = None;
+    }
+}
+
+impl<N: Numeric> Current for TrueRange<N> {
+    type Output = N;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<N: Numeric> IsReady for TrueRange<N> {
+    fn is_ready(&self) -> bool {
+        self.prev_close.is_some()
+    }
+}
+
+impl<N: Numeric> Lookback for TrueRange<N> {
+    fn lookback(&self) -> usize {
+        1
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::TrueRange;
     use crate::test_helper::*;
 
     test_indicator!(TrueRange);
@@ -0,0 +1,398 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{ExponentialMovingAverage as Ema, ExponentialMovingAverageState};
+use crate::{Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// True Strength Index (TSI).
+///
+/// A momentum oscillator based on a double smoothing of price changes.
+/// The oscillator returns output in the range of -100..100.
+///
+/// # Formula
+///
+/// TSI = 100 * EMA(EMA(m, _long_period_), _short_period_) / EMA(EMA(|m|, _long_period_), _short_period_)
+///
+/// Signal = EMA(TSI, _signal_period_)
+///
+/// Where:
+///
+/// * _m_ - price change, Close<sub>t</sub> - Close<sub>t-1</sub>
+///
+/// The price change of the very first bar is 0, and TSI is 0 while there was no price change.
+///
+/// # Parameters
+///
+/// * _long_period_ - period of the first smoothing (integer greater than 0). Default is 25.
+/// * _short_period_ - period of the second smoothing (integer greater than 0). Default is 13.
+/// * _signal_period_ - period of the signal EMA (integer greater than 0). Default is 13.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::TrueStrengthIndex;
+/// use ta::Next;
+///
+/// let mut tsi = TrueStrengthIndex::new(3, 2, 2).unwrap();
+///
+/// let out = tsi.next(10.0);
+/// assert_eq!(out.tsi, 0.0);
+///
+/// let out = tsi.next(12.0);
+/// assert_eq!(out.tsi.round(), 100.0);
+/// assert_eq!(out.signal.round(), 67.0);
+/// ```
+///
+/// # Links
+///
+/// * [True Strength Index, Wikipedia](https://en.wikipedia.org/wiki/True_strength_index)
+///
+#[doc(alias = "TSI")]
+#[derive(Debug, Clone)]
+pub struct TrueStrengthIndex {
+    long_ema: Ema,
+    short_ema: Ema,
+    abs_long_ema: Ema,
+    abs_short_ema: Ema,
+    signal_ema: Ema,
+    prev_close: Option<f64>,
+    count: usize,
+    output: Option<TrueStrengthIndexOutput>,
+    // Close before the last input, restored by `amend`
+    saved_prev_close: Option<f64>,
+}
+
+/// State of [TrueStrengthIndex](struct.TrueStrengthIndex.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrueStrengthIndexState {
+    /// First smoothing of the momentum.
+    pub long_ema: ExponentialMovingAverageState,
+    /// Second smoothing of the momentum.
+    pub short_ema: ExponentialMovingAverageState,
+    /// First smoothing of the absolute momentum.
+    pub abs_long_ema: ExponentialMovingAverageState,
+    /// Second smoothing of the absolute momentum.
+    pub abs_short_ema: ExponentialMovingAverageState,
+    pub signal_ema: ExponentialMovingAverageState,
+    pub previous_close: Option<f64>,
+    /// Number of inputs, up to the lookback.
+    pub count: usize,
+    pub output: Option<TrueStrengthIndexOutput>,
+    /// Input before the last one, to amend the last one.
+    pub close_before_last: Option<f64>,
+}
+
+/// Output of [TrueStrengthIndex](struct.TrueStrengthIndex.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrueStrengthIndexOutput {
+    pub tsi: f64,
+    pub signal: f64,
+}
+
+impl_output!(TrueStrengthIndexOutput, tsi, signal);
+
+impl From<TrueStrengthIndexOutput> for (f64, f64) {
+    fn from(o: TrueStrengthIndexOutput) -> Self {
+        (o.tsi, o.signal)
+    }
+}
+
+impl TrueStrengthIndex {
+    pub fn new(long_period: usize, short_period: usize, signal_period: usize) -> Result<Self> {
+        Ok(Self {
+            long_ema: Ema::new(long_period)?,
+            short_ema: Ema::new(short_period)?,
+            abs_long_ema: Ema::new(long_period)?,
+            abs_short_ema: Ema::new(short_period)?,
+            signal_ema: Ema::new(signal_period)?,
+            prev_close: None,
+            count: 0,
+            output: None,
+            saved_prev_close: None,
+        })
+    }
+}
+
+impl Checkpoint for TrueStrengthIndex {
+    type State = TrueStrengthIndexState;
+
+    fn state(&self) -> Self::State {
+        TrueStrengthIndexState {
+            long_ema: self.long_ema.state(),
+            short_ema: self.short_ema.state(),
+            abs_long_ema: self.abs_long_ema.state(),
+            abs_short_ema: self.abs_short_ema.state(),
+            signal_ema: self.signal_ema.state(),
+            previous_close: self.prev_close,
+            count: self.count,
+            output: self.output.clone(),
+            close_before_last: self.saved_prev_close,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(
+            state.long_ema.period,
+            state.short_ema.period,
+            state.signal_ema.period,
+        )
+        .map_err(|_| TaError::InvalidState)?;
+        if state.abs_long_ema.period != state.long_ema.period
+            || state.abs_short_ema.period != state.short_ema.period
+            || state.previous_close.is_none() != state.output.is_none()
+            || state.count > indicator.lookback()
+            || state.output.is_none() != (state.count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        indicator.long_ema = Ema::from_state(state.long_ema)?;
+        indicator.short_ema = Ema::from_state(state.short_ema)?;
+        indicator.abs_long_ema = Ema::from_state(state.abs_long_ema)?;
+        indicator.abs_short_ema = Ema::from_state(state.abs_short_ema)?;
+        indicator.signal_ema = Ema::from_state(state.signal_ema)?;
+        indicator.prev_close = state.previous_close;
+        indicator.count = state.count;
+        indicator.output = state.output;
+        indicator.saved_prev_close = state.close_before_last;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(TrueStrengthIndex, 2);
+
+impl Next<f64> for TrueStrengthIndex {
+    type Output = TrueStrengthIndexOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if !self.is_ready() {
+            self.count += 1;
+        }
+        let momentum = match self.prev_close {
+            Some(prev) => input - prev,
+            None => 0.0,
+        };
+        self.saved_prev_close = self.prev_close;
+        self.prev_close = Some(input);
+
+        let smoothed = self.short_ema.next(self.long_ema.next(momentum));
+        let abs_smoothed = self
+            .abs_short_ema
+            .next(self.abs_long_ema.next(momentum.abs()));
+
+        let tsi = if abs_smoothed > 0.0 {
+            100.0 * smoothed / abs_smoothed
+        } else {
+            0.0
+        };
+
+        let output = TrueStrengthIndexOutput {
+            tsi,
+            signal: self.signal_ema.next(tsi),
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for TrueStrengthIndex {
+    type Output = TrueStrengthIndexOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for TrueStrengthIndex {
+    fn peek(&self, input: f64) -> TrueStrengthIndexOutput {
+        let momentum = match self.prev_close {
+            Some(prev) => input - prev,
+            None => 0.0,
+        };
+
+        let smoothed = self.short_ema.peek(self.long_ema.peek(momentum));
+        let abs_smoothed = self
+            .abs_short_ema
+            .peek(self.abs_long_ema.peek(momentum.abs()));
+
+        let tsi = if abs_smoothed > 0.0 {
+            100.0 * smoothed / abs_smoothed
+        } else {
+            0.0
+        };
+
+        TrueStrengthIndexOutput {
+            tsi,
+            signal: self.signal_ema.peek(tsi),
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for TrueStrengthIndex {
+    fn peek(&self, input: &T) -> TrueStrengthIndexOutput {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for TrueStrengthIndex {
+    fn amend(&mut self, input: f64) -> TrueStrengthIndexOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let momentum = match self.saved_prev_close {
+            Some(prev) => input - prev,
+            None => 0.0,
+        };
+        self.prev_close = Some(input);
+
+        let smoothed = self.short_ema.amend(self.long_ema.amend(momentum));
+        let abs_smoothed = self
+            .abs_short_ema
+            .amend(self.abs_long_ema.amend(momentum.abs()));
+
+        let tsi = if abs_smoothed > 0.0 {
+            100.0 * smoothed / abs_smoothed
+        } else {
+            0.0
+        };
+
+        let output = TrueStrengthIndexOutput {
+            tsi,
+            signal: self.signal_ema.amend(tsi),
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for TrueStrengthIndex {
+    fn amend(&mut self, input: &T) -> TrueStrengthIndexOutput {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for TrueStrengthIndex {
+    fn reset(&mut self) {
+        self.long_ema.reset();
+        self.short_ema.reset();
+        self.abs_long_ema.reset();
+        self.abs_short_ema.reset();
+        self.signal_ema.reset();
+        self.prev_close = None;
+        self.count = 0;
+        self.output = None;
+        self.saved_prev_close = None;
+    }
+}
+
+impl Current for TrueStrengthIndex {
+    type Output = TrueStrengthIndexOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for TrueStrengthIndex {
+    fn is_ready(&self) -> bool {
+        self.count >= self.lookback()
+    }
+}
+
+impl Lookback for TrueStrengthIndex {
+    fn lookback(&self) -> usize {
+        // one more value for the first momentum
+        let periods = self.long_ema.period() + self.short_ema.period() + self.signal_ema.period();
+        periods - 1
+    }
+}
+
+impl Default for TrueStrengthIndex {
+    fn default() -> Self {
+        Self::new(25, 13, 13).unwrap()
+    }
+}
+
+impl fmt::Display for TrueStrengthIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "TSI({}, {}, {})",
+            self.long_ema.period(),
+            self.short_ema.period(),
+            self.signal_ema.period()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    type Tsi = TrueStrengthIndex;
+
+    test_indicator!(Tsi);
+
+    fn round(nums: (f64, f64)) -> (f64, f64) {
+        let n0 = (nums.0 * 1000.0).round() / 1000.0;
+        let n1 = (nums.1 * 1000.0).round() / 1000.0;
+        (n0, n1)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Tsi::new(0, 13, 13).is_err());
+        assert!(Tsi::new(25, 0, 13).is_err());
+        assert!(Tsi::new(25, 13, 0).is_err());
+        assert!(Tsi::new(1, 1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut tsi = Tsi::new(3, 2, 2).unwrap();
+
+        assert_eq!(round(tsi.next(10.0).into()), (0.0, 0.0));
+        assert_eq!(round(tsi.next(12.0).into()), (100.0, 66.667));
+        assert_eq!(round(tsi.next(11.0).into()), (25.0, 38.889));
+        assert_eq!(round(tsi.next(14.0).into()), (65.909, 56.902));
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut tsi = Tsi::new(3, 2, 2).unwrap();
+
+        assert_eq!(round(tsi.next(&Bar::new().close(10)).into()), (0.0, 0.0));
+        assert_eq!(
+            round(tsi.next(&Bar::new().close(12)).into()),
+            (100.0, 66.667)
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut tsi = Tsi::new(3, 2, 2).unwrap();
+
+        tsi.next(10.0);
+        tsi.next(12.0);
+
+        tsi.reset();
+        assert_eq!(round(tsi.next(10.0).into()), (0.0, 0.0));
+        assert_eq!(round(tsi.next(12.0).into()), (100.0, 66.667));
+    }
+
+    #[test]
+    fn test_default() {
+        Tsi::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let tsi = Tsi::new(25, 13, 7).unwrap();
+        assert_eq!(format!("{}", tsi), "TSI(25, 13, 7)");
+    }
+}
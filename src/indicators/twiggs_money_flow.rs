@@ -0,0 +1,307 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{ExponentialMovingAverage as Ema, ExponentialMovingAverageState};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset,
+    SetPeriod, Volume,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Twiggs Money Flow (TMF).
+///
+/// Colin Twiggs' variant of the [Chaikin Money Flow](struct.ChaikinMoneyFlow.html). It uses the
+/// true range instead of the high-low range, so gaps between bars are taken into account, and
+/// Wilder smoothing instead of a plain sum, so old bars do not drop out of the window all at
+/// once. Like the CMF, it ranges from -1 to 1.
+///
+/// # Formula
+///
+/// TRH = max(High, Close<sub>t-1</sub>)
+///
+/// TRL = min(Low, Close<sub>t-1</sub>)
+///
+/// AD = Volume × ((Close - TRL) - (TRH - Close)) / (TRH - TRL)
+///
+/// TMF = Wilder(AD, _period_) / Wilder(Volume, _period_)
+///
+/// Where:
+///
+/// * _Wilder_ - Wilder smoothing, which is an [EMA](type.ExponentialMovingAverage.html) with
+///   period 2 × _period_ - 1
+///
+/// AD is 0 for a bar with no true range. TMF is 0 when the smoothed volume is 0.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 21.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::TwiggsMoneyFlow;
+/// use ta::{Next, DataItem};
+///
+/// let mut tmf = TwiggsMoneyFlow::new(2).unwrap();
+/// let di = DataItem::builder()
+///             .high(10.0)
+///             .low(6.0)
+///             .close(9.0)
+///             .open(7.0)
+///             .volume(1000.0)
+///             .build().unwrap();
+///
+/// assert_eq!(tmf.next(&di), 0.5);
+/// ```
+///
+/// # Links
+///
+/// * [Twiggs Money Flow, Incredible Charts](https://www.incrediblecharts.com/indicators/twiggs_money_flow.php)
+///
+#[doc(alias = "TMF")]
+#[derive(Debug, Clone)]
+pub struct TwiggsMoneyFlow {
+    period: usize,
+    ad: Ema,
+    volume: Ema,
+    prev_close: Option<f64>,
+    output: Option<f64>,
+    // Close before the last input, restored by `amend`
+    saved_prev_close: Option<f64>,
+}
+
+/// State of [TwiggsMoneyFlow](struct.TwiggsMoneyFlow.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TwiggsMoneyFlowState {
+    pub period: usize,
+    /// Smoothing of the accumulation/distribution.
+    pub ad: ExponentialMovingAverageState,
+    /// Smoothing of the volume.
+    pub volume: ExponentialMovingAverageState,
+    pub previous_close: Option<f64>,
+    pub output: Option<f64>,
+    /// Close of the input before the last one, to amend the last one.
+    pub close_before_last: Option<f64>,
+}
+
+impl TwiggsMoneyFlow {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
+            _ => Ok(Self {
+                period,
+                ad: Ema::new(2 * period - 1)?,
+                volume: Ema::new(2 * period - 1)?,
+                prev_close: None,
+                output: None,
+                saved_prev_close: None,
+            }),
+        }
+    }
+
+    // Accumulation/distribution of a bar over its true range
+    fn accumulation<T: High + Low + Close + Volume>(&self, input: &T) -> f64 {
+        let close = input.close();
+        let prev_close = self.prev_close.unwrap_or(close);
+        let high = input.high().max(prev_close);
+        let low = input.low().min(prev_close);
+
+        let range = high - low;
+        if range > 0.0 {
+            input.volume() * ((close - low) - (high - close)) / range
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Period for TwiggsMoneyFlow {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for TwiggsMoneyFlow {
+    type State = TwiggsMoneyFlowState;
+
+    fn state(&self) -> Self::State {
+        TwiggsMoneyFlowState {
+            period: self.period,
+            ad: self.ad.state(),
+            volume: self.volume.state(),
+            previous_close: self.prev_close,
+            output: self.output,
+            close_before_last: self.saved_prev_close,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        if state.ad.period != indicator.ad.period()
+            || state.volume.period != indicator.volume.period()
+            || state.previous_close.is_none() != state.output.is_none()
+            || state.output.is_none() != (state.ad.count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        indicator.ad = Ema::from_state(state.ad)?;
+        indicator.volume = Ema::from_state(state.volume)?;
+        indicator.prev_close = state.previous_close;
+        indicator.output = state.output;
+        indicator.saved_prev_close = state.close_before_last;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(TwiggsMoneyFlow, 2);
+
+impl SetPeriod for TwiggsMoneyFlow {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl<T: High + Low + Close + Volume> Next<&T> for TwiggsMoneyFlow {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_prev_close = self.prev_close;
+        let ad = self.ad.next(self.accumulation(input));
+        self.prev_close = Some(input.close());
+        let volume = self.volume.next(input.volume());
+        let output = if volume > 0.0 { ad / volume } else { 0.0 };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low + Close + Volume> Peek<&T> for TwiggsMoneyFlow {
+    fn peek(&self, input: &T) -> f64 {
+        let ad = self.ad.peek(self.accumulation(input));
+        let volume = self.volume.peek(input.volume());
+        if volume > 0.0 {
+            ad / volume
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<T: High + Low + Close + Volume> Amend<&T> for TwiggsMoneyFlow {
+    fn amend(&mut self, input: &T) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.prev_close = self.saved_prev_close;
+        let ad = self.ad.amend(self.accumulation(input));
+        self.prev_close = Some(input.close());
+        let volume = self.volume.amend(input.volume());
+
+        let output = if volume > 0.0 { ad / volume } else { 0.0 };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Reset for TwiggsMoneyFlow {
+    fn reset(&mut self) {
+        self.ad.reset();
+        self.volume.reset();
+        self.prev_close = None;
+        self.output = None;
+        self.saved_prev_close = None;
+    }
+}
+
+impl Current for TwiggsMoneyFlow {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for TwiggsMoneyFlow {
+    fn is_ready(&self) -> bool {
+        self.ad.is_ready()
+    }
+}
+
+impl Lookback for TwiggsMoneyFlow {
+    fn lookback(&self) -> usize {
+        self.ad.lookback()
+    }
+}
+
+impl Default for TwiggsMoneyFlow {
+    fn default() -> Self {
+        Self::new(21).unwrap()
+    }
+}
+
+impl fmt::Display for TwiggsMoneyFlow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TMF({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn bar(high: f64, low: f64, close: f64, volume: f64) -> Bar {
+        Bar::new().high(high).low(low).close(close).volume(volume)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(TwiggsMoneyFlow::new(0).is_err());
+        assert!(TwiggsMoneyFlow::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut tmf = TwiggsMoneyFlow::new(2).unwrap();
+
+        assert_eq!(tmf.next(&bar(10.0, 6.0, 9.0, 1000.0)), 0.5);
+        // True range is 7..9 because of the previous close
+        assert_eq!(tmf.next(&bar(9.0, 7.0, 7.0, 2000.0)), -0.5);
+        // True range is 7..12 because of the gap
+        assert_eq!(tmf.next(&bar(12.0, 10.0, 12.0, 1000.0)), 0.1);
+    }
+
+    #[test]
+    fn test_no_range_or_volume() {
+        let mut tmf = TwiggsMoneyFlow::new(2).unwrap();
+
+        assert_eq!(tmf.next(&bar(10.0, 10.0, 10.0, 1000.0)), 0.0);
+        tmf.reset();
+        assert_eq!(tmf.next(&bar(12.0, 10.0, 11.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut tmf = TwiggsMoneyFlow::new(2).unwrap();
+
+        tmf.next(&bar(10.0, 6.0, 9.0, 1000.0));
+        tmf.next(&bar(9.0, 7.0, 7.0, 2000.0));
+        tmf.reset();
+        assert_eq!(tmf.next(&bar(10.0, 6.0, 9.0, 1000.0)), 0.5);
+    }
+
+    #[test]
+    fn test_default() {
+        TwiggsMoneyFlow::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let tmf = TwiggsMoneyFlow::new(21).unwrap();
+        assert_eq!(format!("{}", tmf), "TMF(21)");
+    }
+}
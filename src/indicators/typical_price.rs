@@ -0,0 +1,152 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::{Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Typical price (TP).
+///
+/// Turns a bar into a single price: the average of the high, the low and the close. It can be used
+/// as the input of indicators that take a single price, instead of the close.
+///
+/// # Formula
+///
+/// TP = (High + Low + Close) / 3
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::TypicalPrice;
+/// use ta::{DataItem, Next};
+///
+/// let mut typical = TypicalPrice::new();
+///
+/// let bar = DataItem::builder()
+///     .open(9.0).high(12.0).low(6.0).close(9.0)
+///     .volume(1000.0)
+///     .build().unwrap();
+///
+/// assert_eq!(typical.next(&bar), 9.0);
+/// ```
+///
+/// # Links
+///
+/// * [Typical price, Wikipedia](https://en.wikipedia.org/wiki/Typical_price)
+#[doc(alias = "TP")]
+#[derive(Debug, Clone, Default)]
+pub struct TypicalPrice {
+    output: Option<f64>,
+}
+
+/// State of [TypicalPrice](struct.TypicalPrice.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypicalPriceState {
+    /// Last output, `None` before the first input.
+    pub output: Option<f64>,
+}
+
+impl TypicalPrice {
+    pub fn new() -> Self {
+        Self { output: None }
+    }
+}
+
+impl Checkpoint for TypicalPrice {
+    type State = TypicalPriceState;
+
+    fn state(&self) -> Self::State {
+        TypicalPriceState {
+            output: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(Self {
+            output: state.output,
+        })
+    }
+}
+
+impl_versioned_serde!(TypicalPrice, 1);
+
+impl<T: High + Low + Close> Next<&T> for TypicalPrice {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let output = (input.high() + input.low() + input.close()) / 3.0;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low + Close> Peek<&T> for TypicalPrice {
+    fn peek(&self, input: &T) -> f64 {
+        (input.high() + input.low() + input.close()) / 3.0
+    }
+}
+
+impl<T: High + Low + Close> Amend<&T> for TypicalPrice {
+    fn amend(&mut self, input: &T) -> f64 {
+        // The output only depends on the input
+        self.next(input)
+    }
+}
+
+impl Reset for TypicalPrice {
+    fn reset(&mut self) {
+        self.output = None;
+    }
+}
+
+impl Current for TypicalPrice {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for TypicalPrice {
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+impl Lookback for TypicalPrice {
+    fn lookback(&self) -> usize {
+        1
+    }
+}
+
+impl fmt::Display for TypicalPrice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TP")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_next() {
+        let mut tp = TypicalPrice::new();
+
+        assert_eq!(tp.next(&Bar::new().high(10).low(4).close(7)), 7.0);
+        assert_eq!(tp.next(&Bar::new().high(12).low(6).close(12)), 10.0);
+    }
+
+    #[test]
+    fn test_default() {
+        TypicalPrice::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let tp = TypicalPrice::new();
+        assert_eq!(format!("{}", tp), "TP");
+    }
+}
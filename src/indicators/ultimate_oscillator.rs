@@ -0,0 +1,409 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Ultimate Oscillator (UO).
+///
+/// A momentum oscillator developed by Larry Williams that combines buying pressure averaged over
+/// three different time frames, which reduces the volatility and false signals of single time
+/// frame oscillators.
+///
+/// The oscillator returns output in the range of 0..100.
+///
+/// # Formula
+///
+/// BP = Close - min(Low, Close<sub>prev</sub>)
+///
+/// TR = max(High, Close<sub>prev</sub>) - min(Low, Close<sub>prev</sub>)
+///
+/// Average<sub>n</sub> = Σ(BP) / Σ(TR) over the last _n_ bars
+///
+/// UO = 100 * (4 * Average<sub>short</sub> + 2 * Average<sub>medium</sub> + Average<sub>long</sub>) / 7
+///
+/// When there was no price range at all during a time frame, its average is 0.5.
+///
+/// # Parameters
+///
+/// * _short_period_ - integer greater than 0. Default is 7.
+/// * _medium_period_ - integer greater than 0. Default is 14.
+/// * _long_period_ - integer greater than 0. Default is 28.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::UltimateOscillator;
+/// use ta::{Next, DataItem};
+///
+/// let mut uo = UltimateOscillator::new(1, 2, 3).unwrap();
+///
+/// let di = DataItem::builder()
+///             .high(10.0).low(8.0).close(9.5).open(9.0)
+///             .volume(1000.0)
+///             .build().unwrap();
+///
+/// assert_eq!(uo.next(&di), 75.0);
+/// ```
+///
+/// # Links
+///
+/// * [Ultimate oscillator, Wikipedia](https://en.wikipedia.org/wiki/Ultimate_oscillator)
+///
+#[doc(alias = "UO")]
+#[derive(Debug, Clone)]
+pub struct UltimateOscillator {
+    periods: [usize; 3],
+    index: usize,
+    count: usize,
+    prev_close: Option<f64>,
+    sums_bp: [f64; 3],
+    sums_tr: [f64; 3],
+    bp: Box<[f64]>,
+    tr: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: (f64, f64),
+    saved_count: usize,
+    saved_prev_close: Option<f64>,
+    saved_sums_bp: [f64; 3],
+    saved_sums_tr: [f64; 3],
+}
+
+/// State of [UltimateOscillator](struct.UltimateOscillator.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct UltimateOscillatorState {
+    /// Short, medium and long period.
+    pub periods: [usize; 3],
+    pub previous_close: Option<f64>,
+    /// Buying pressures in the window of the longest period, the oldest first.
+    pub bp: Vec<f64>,
+    /// True ranges in the window of the longest period, the oldest first.
+    pub tr: Vec<f64>,
+    /// Running sums of the buying pressures for each period.
+    pub sums_bp: [f64; 3],
+    /// Running sums of the true ranges for each period.
+    pub sums_tr: [f64; 3],
+    pub output: Option<f64>,
+    /// Buying pressure and true range which the last input pushed out of the window, to amend it.
+    pub evicted: Option<(f64, f64)>,
+    /// Close of the input before the last one and sums before the last input, to amend it.
+    pub close_before_last: Option<f64>,
+    pub previous_sums_bp: [f64; 3],
+    pub previous_sums_tr: [f64; 3],
+}
+
+impl UltimateOscillator {
+    pub fn new(short_period: usize, medium_period: usize, long_period: usize) -> Result<Self> {
+        let periods = [short_period, medium_period, long_period];
+        for (name, period) in ["short_period", "medium_period", "long_period"]
+            .into_iter()
+            .zip(periods)
+        {
+            if period == 0 {
+                return Err(TaError::invalid_parameter(name, 0.0, ">= 1"));
+            }
+        }
+        let capacity = short_period.max(medium_period).max(long_period);
+
+        Ok(Self {
+            periods,
+            index: 0,
+            count: 0,
+            prev_close: None,
+            sums_bp: [0.0; 3],
+            sums_tr: [0.0; 3],
+            bp: vec![0.0; capacity].into_boxed_slice(),
+            tr: vec![0.0; capacity].into_boxed_slice(),
+            output: None,
+            saved_value: (0.0, 0.0),
+            saved_count: 0,
+            saved_prev_close: None,
+            saved_sums_bp: [0.0; 3],
+            saved_sums_tr: [0.0; 3],
+        })
+    }
+
+    // Weighted average of the buying pressure over the true range of the three periods
+    fn oscillator(sums_bp: [f64; 3], sums_tr: [f64; 3]) -> f64 {
+        let mut averages = [0.5; 3];
+        for (average, (sum_bp, sum_tr)) in averages.iter_mut().zip(sums_bp.iter().zip(&sums_tr)) {
+            if *sum_tr > 0.0 {
+                *average = sum_bp / sum_tr;
+            }
+        }
+
+        100.0 * (4.0 * averages[0] + 2.0 * averages[1] + averages[2]) / 7.0
+    }
+}
+
+impl Checkpoint for UltimateOscillator {
+    type State = UltimateOscillatorState;
+
+    fn state(&self) -> Self::State {
+        UltimateOscillatorState {
+            periods: self.periods,
+            previous_close: self.prev_close,
+            bp: ring_values(&self.bp, self.index, self.count),
+            tr: ring_values(&self.tr, self.index, self.count),
+            sums_bp: self.sums_bp,
+            sums_tr: self.sums_tr,
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count == self.bp.len())
+                .then_some(self.saved_value),
+            close_before_last: self.saved_prev_close,
+            previous_sums_bp: self.saved_sums_bp,
+            previous_sums_tr: self.saved_sums_tr,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let [short, medium, long] = state.periods;
+        if state.output.is_some() == state.bp.is_empty()
+            || state.previous_close.is_none() != state.bp.is_empty()
+            || state.tr.len() != state.bp.len()
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(short, medium, long).map_err(|_| TaError::InvalidState)?;
+        let capacity = indicator.bp.len();
+        let (bp, index) = ring_from_values(capacity, &state.bp)?;
+        let (tr, _) = ring_from_values(capacity, &state.tr)?;
+        indicator.bp = bp;
+        indicator.tr = tr;
+        indicator.index = index;
+        indicator.count = state.bp.len();
+        indicator.prev_close = state.previous_close;
+        indicator.sums_bp = state.sums_bp;
+        indicator.sums_tr = state.sums_tr;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.bp.len() {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or((0.0, 0.0));
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_prev_close = state.close_before_last;
+        indicator.saved_sums_bp = state.previous_sums_bp;
+        indicator.saved_sums_tr = state.previous_sums_tr;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(UltimateOscillator, 2);
+
+impl<T: High + Low + Close> Next<&T> for UltimateOscillator {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_value = (self.bp[self.index], self.tr[self.index]);
+        self.saved_count = self.count;
+        self.saved_prev_close = self.prev_close;
+        self.saved_sums_bp = self.sums_bp;
+        self.saved_sums_tr = self.sums_tr;
+        let prev_close = self.prev_close.unwrap_or_else(|| input.close());
+        let low = input.low().min(prev_close);
+        let high = input.high().max(prev_close);
+        let bp = input.close() - low;
+        let tr = high - low;
+        self.prev_close = Some(input.close());
+
+        let capacity = self.bp.len();
+
+        for (i, &period) in self.periods.iter().enumerate() {
+            // drop the value that leaves the window of this period
+            if self.count >= period {
+                let old = (self.index + capacity - period) % capacity;
+                self.sums_bp[i] -= self.bp[old];
+                self.sums_tr[i] -= self.tr[old];
+            }
+            self.sums_bp[i] += bp;
+            self.sums_tr[i] += tr;
+        }
+
+        self.bp[self.index] = bp;
+        self.tr[self.index] = tr;
+        self.count = (self.count + 1).min(capacity);
+        self.index = if self.index + 1 < capacity {
+            self.index + 1
+        } else {
+            0
+        };
+
+        let output = Self::oscillator(self.sums_bp, self.sums_tr);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low + Close> Peek<&T> for UltimateOscillator {
+    fn peek(&self, input: &T) -> f64 {
+        let prev_close = self.prev_close.unwrap_or_else(|| input.close());
+        let low = input.low().min(prev_close);
+        let high = input.high().max(prev_close);
+        let bp = input.close() - low;
+        let tr = high - low;
+
+        let capacity = self.bp.len();
+        let mut sums_bp = self.sums_bp;
+        let mut sums_tr = self.sums_tr;
+        for (i, &period) in self.periods.iter().enumerate() {
+            if self.count >= period {
+                let old = (self.index + capacity - period) % capacity;
+                sums_bp[i] -= self.bp[old];
+                sums_tr[i] -= self.tr[old];
+            }
+            sums_bp[i] += bp;
+            sums_tr[i] += tr;
+        }
+
+        Self::oscillator(sums_bp, sums_tr)
+    }
+}
+
+impl<T: High + Low + Close> Amend<&T> for UltimateOscillator {
+    fn amend(&mut self, input: &T) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        let capacity = self.bp.len();
+        self.index = (self.index + capacity - 1) % capacity;
+        (self.bp[self.index], self.tr[self.index]) = self.saved_value;
+        self.count = self.saved_count;
+        self.prev_close = self.saved_prev_close;
+        self.sums_bp = self.saved_sums_bp;
+        self.sums_tr = self.saved_sums_tr;
+        self.next(input)
+    }
+}
+
+impl Reset for UltimateOscillator {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.prev_close = None;
+        self.sums_bp = [0.0; 3];
+        self.sums_tr = [0.0; 3];
+        for i in 0..self.bp.len() {
+            self.bp[i] = 0.0;
+            self.tr[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = (0.0, 0.0);
+        self.saved_count = 0;
+        self.saved_prev_close = None;
+        self.saved_sums_bp = [0.0; 3];
+        self.saved_sums_tr = [0.0; 3];
+    }
+}
+
+impl Current for UltimateOscillator {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for UltimateOscillator {
+    fn is_ready(&self) -> bool {
+        self.count == self.bp.len()
+    }
+}
+
+impl Lookback for UltimateOscillator {
+    fn lookback(&self) -> usize {
+        self.bp.len()
+    }
+}
+
+impl Default for UltimateOscillator {
+    fn default() -> Self {
+        Self::new(7, 14, 28).unwrap()
+    }
+}
+
+impl fmt::Display for UltimateOscillator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "UO({}, {}, {})",
+            self.periods[0], self.periods[1], self.periods[2]
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(UltimateOscillator::new(0, 14, 28).is_err());
+        assert!(UltimateOscillator::new(7, 0, 28).is_err());
+        assert!(UltimateOscillator::new(7, 14, 0).is_err());
+        assert!(UltimateOscillator::new(1, 1, 1).is_ok());
+        assert!(UltimateOscillator::new(28, 14, 7).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut uo = UltimateOscillator::new(1, 2, 3).unwrap();
+
+        let bar1 = Bar::new().high(10).low(8).close(9.5);
+        let bar2 = Bar::new().high(11).low(9).close(9);
+        let bar3 = Bar::new().high(10).low(7).close(10);
+        let bar4 = Bar::new().high(12).low(11).close(11.5);
+
+        // BP = 1.5, TR = 2
+        assert_eq!(round(uo.next(&bar1)), 75.0);
+        // BP = 0, TR = 2
+        assert_eq!(round(uo.next(&bar2)), 16.071);
+        // BP = 3, TR = 3
+        assert_eq!(round(uo.next(&bar3)), 83.469);
+        // BP = 1.5, TR = 2
+        assert_eq!(round(uo.next(&bar4)), 77.755);
+    }
+
+    #[test]
+    fn test_next_flat_bars() {
+        let mut uo = UltimateOscillator::new(1, 2, 3).unwrap();
+
+        let bar = Bar::new().high(5).low(5).close(5);
+        assert_eq!(uo.next(&bar), 50.0);
+        assert_eq!(uo.next(&bar), 50.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut uo = UltimateOscillator::new(1, 2, 3).unwrap();
+
+        let bar1 = Bar::new().high(10).low(8).close(9.5);
+        let bar2 = Bar::new().high(11).low(9).close(9);
+
+        uo.next(&bar1);
+        uo.next(&bar2);
+
+        uo.reset();
+        assert_eq!(round(uo.next(&bar1)), 75.0);
+        assert_eq!(round(uo.next(&bar2)), 16.071);
+    }
+
+    #[test]
+    fn test_default() {
+        UltimateOscillator::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let uo = UltimateOscillator::new(7, 14, 28).unwrap();
+        assert_eq!(format!("{}", uo), "UO(7, 14, 28)");
+    }
+}
@@ -0,0 +1,458 @@
+use core::f64::consts::PI;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{
+    RollingQuantile, RollingQuantileState, StandardDeviation as Sd, StandardDeviationState,
+};
+use crate::{Amend, Checkpoint, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Method used by [ValueAtRisk](struct.ValueAtRisk.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarMethod {
+    /// Empirical quantile of the returns in the window.
+    Historical,
+    /// Parametric estimate assuming normally distributed returns.
+    Gaussian,
+}
+
+/// Value at Risk (VaR) and Conditional Value at Risk (CVaR).
+///
+/// Estimates the loss which is not exceeded with the given _confidence_ over the next bar,
+/// based on the last _period_ returns. CVaR (also known as expected shortfall) is the average
+/// loss in the cases when VaR is exceeded.
+///
+/// The input is a stream of per-bar returns (e.g. 0.01 for +1%), not prices.
+/// Both outputs are losses, so they are positive when the expected outcome is a loss.
+///
+/// # Formula
+///
+/// Historical:
+///
+/// * VaR = -Q(1 - _confidence_), the quantile of the window as computed by
+///   [RollingQuantile](struct.RollingQuantile.html)
+/// * CVaR = -mean(r<sub>i</sub> ≤ Q(1 - _confidence_))
+///
+/// Gaussian:
+///
+/// * VaR = -(μ - z * σ)
+/// * CVaR = -(μ - σ * φ(z) / (1 - _confidence_))
+///
+/// Where:
+///
+/// * _μ_, _σ_ - mean and [standard deviation](type.StandardDeviation.html) of the returns in the window
+/// * _z_ - quantile of the standard normal distribution at _confidence_
+/// * _φ_ - density of the standard normal distribution
+///
+/// # Parameters
+///
+/// * _period_ - number of returns (integer greater than 0). Default is 250.
+/// * _confidence_ - confidence level, number in range (0, 1). Default is 0.95.
+/// * _method_ - [VarMethod](enum.VarMethod.html). Default is historical.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{ValueAtRisk, VarMethod};
+/// use ta::Next;
+///
+/// let mut var = ValueAtRisk::new(5, 0.8, VarMethod::Historical).unwrap();
+///
+/// for r in &[0.01, -0.02, 0.015, -0.03] {
+///     var.next(*r);
+/// }
+/// let out = var.next(0.005);
+///
+/// assert_eq!((out.var * 1000.0).round(), 22.0);
+/// assert_eq!((out.cvar * 1000.0).round(), 30.0);
+/// ```
+///
+/// # Links
+///
+/// * [Value at risk, Wikipedia](https://en.wikipedia.org/wiki/Value_at_risk)
+/// * [Expected shortfall, Wikipedia](https://en.wikipedia.org/wiki/Expected_shortfall)
+///
+#[doc(alias = "VaR")]
+#[doc(alias = "CVaR")]
+#[doc(alias = "ES")]
+#[derive(Debug, Clone)]
+pub struct ValueAtRisk {
+    confidence: f64,
+    method: VarMethod,
+    z: f64,
+    quantile: RollingQuantile,
+    sd: Sd,
+    output: Option<ValueAtRiskOutput>,
+}
+
+/// State of [ValueAtRisk](struct.ValueAtRisk.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueAtRiskState {
+    pub confidence: f64,
+    pub method: VarMethod,
+    /// Quantile of the inputs, used by the historical method.
+    pub quantile: RollingQuantileState,
+    /// Standard deviation of the inputs, used by the gaussian method.
+    pub sd: StandardDeviationState,
+    pub output: Option<ValueAtRiskOutput>,
+}
+
+impl ValueAtRisk {
+    pub fn new(period: usize, confidence: f64, method: VarMethod) -> Result<Self> {
+        if confidence <= 0.0 || confidence >= 1.0 || confidence.is_nan() {
+            return Err(TaError::invalid_parameter(
+                "confidence",
+                confidence,
+                "0 < confidence < 1",
+            ));
+        }
+        Ok(Self {
+            confidence,
+            method,
+            z: inverse_normal_cdf(confidence),
+            quantile: RollingQuantile::new(period, 1.0 - confidence)?,
+            sd: Sd::new(period)?,
+            output: None,
+        })
+    }
+
+    pub fn confidence(&self) -> f64 {
+        self.confidence
+    }
+
+    pub fn method(&self) -> VarMethod {
+        self.method
+    }
+
+    // Losses at and beyond the quantile `q` of the returns in the window
+    fn historical(q: f64, window: &[f64]) -> ValueAtRiskOutput {
+        let (sum, n) = window
+            .iter()
+            .filter(|&&r| r <= q)
+            .fold((0.0, 0), |(sum, n), r| (sum + r, n + 1));
+
+        ValueAtRiskOutput {
+            var: -q,
+            cvar: -sum / n as f64,
+        }
+    }
+
+    // Losses of normally distributed returns
+    fn gaussian(&self, sd: f64, mean: f64) -> ValueAtRiskOutput {
+        let density = (-0.5 * self.z * self.z).exp() / (2.0 * PI).sqrt();
+
+        ValueAtRiskOutput {
+            var: self.z * sd - mean,
+            cvar: sd * density / (1.0 - self.confidence) - mean,
+        }
+    }
+}
+
+/// Output of [ValueAtRisk](struct.ValueAtRisk.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueAtRiskOutput {
+    pub var: f64,
+    pub cvar: f64,
+}
+
+impl_output!(ValueAtRiskOutput, var, cvar);
+
+impl From<ValueAtRiskOutput> for (f64, f64) {
+    fn from(output: ValueAtRiskOutput) -> Self {
+        (output.var, output.cvar)
+    }
+}
+
+impl Period for ValueAtRisk {
+    fn period(&self) -> usize {
+        self.sd.period()
+    }
+}
+
+impl Checkpoint for ValueAtRisk {
+    type State = ValueAtRiskState;
+
+    fn state(&self) -> Self::State {
+        ValueAtRiskState {
+            confidence: self.confidence,
+            method: self.method,
+            quantile: self.quantile.state(),
+            sd: self.sd.state(),
+            output: self.output.clone(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = Self::new(state.sd.period, state.confidence, state.method)
+            .map_err(|_| TaError::InvalidState)?;
+        let count = match state.method {
+            VarMethod::Historical => state.quantile.inputs.len(),
+            VarMethod::Gaussian => state.sd.inputs.len(),
+        };
+        if state.quantile.period != state.sd.period
+            || state.quantile.quantile != 1.0 - state.confidence
+            || state.output.is_none() != (count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        indicator.quantile = RollingQuantile::from_state(state.quantile)?;
+        indicator.sd = Sd::from_state(state.sd)?;
+        indicator.output = state.output;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(ValueAtRisk, 1);
+
+impl SetPeriod for ValueAtRisk {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period, self.confidence, self.method)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for ValueAtRisk {
+    type Output = ValueAtRiskOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let output = match self.method {
+            VarMethod::Historical => {
+                let q = self.quantile.next(input);
+                Self::historical(q, self.quantile.window())
+            }
+            VarMethod::Gaussian => {
+                let sd = self.sd.next(input);
+                self.gaussian(sd, self.sd.mean())
+            }
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Peek<f64> for ValueAtRisk {
+    fn peek(&self, input: f64) -> ValueAtRiskOutput {
+        match self.method {
+            VarMethod::Historical => {
+                let q = self.quantile.peek(input);
+                Self::historical(q, &self.quantile.window_with(input))
+            }
+            VarMethod::Gaussian => {
+                let (sd, mean) = self.sd.peek_with_mean(input);
+                self.gaussian(sd, mean)
+            }
+        }
+    }
+}
+
+impl Amend<f64> for ValueAtRisk {
+    fn amend(&mut self, input: f64) -> ValueAtRiskOutput {
+        let output = match self.method {
+            VarMethod::Historical => {
+                let q = self.quantile.amend(input);
+                Self::historical(q, self.quantile.window())
+            }
+            VarMethod::Gaussian => {
+                let sd = self.sd.amend(input);
+                self.gaussian(sd, self.sd.mean())
+            }
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Reset for ValueAtRisk {
+    fn reset(&mut self) {
+        self.quantile.reset();
+        self.sd.reset();
+        self.output = None;
+    }
+}
+
+impl Current for ValueAtRisk {
+    type Output = ValueAtRiskOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for ValueAtRisk {
+    fn is_ready(&self) -> bool {
+        self.quantile.is_ready() && self.sd.is_ready()
+    }
+}
+
+impl Lookback for ValueAtRisk {
+    fn lookback(&self) -> usize {
+        self.quantile.lookback().max(self.sd.lookback())
+    }
+}
+
+impl Default for ValueAtRisk {
+    fn default() -> Self {
+        Self::new(250, 0.95, VarMethod::Historical).unwrap()
+    }
+}
+
+impl fmt::Display for ValueAtRisk {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "VAR({}, {}, {:?})",
+            self.sd.period(),
+            self.confidence,
+            self.method
+        )
+    }
+}
+
+/// Quantile function of the standard normal distribution.
+///
+/// Rational approximation by Peter J. Acklam, relative error is below 1.15e-9.
+fn inverse_normal_cdf(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.38357751867269e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549732539343734e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RETURNS: [f64; 6] = [0.01, -0.02, 0.015, -0.03, 0.005, -0.01];
+
+    fn round(nums: (f64, f64)) -> (f64, f64) {
+        let n0 = (nums.0 * 10000.0).round() / 10000.0;
+        let n1 = (nums.1 * 10000.0).round() / 10000.0;
+        (n0, n1)
+    }
+
+    #[test]
+    fn test_new() {
+        let m = VarMethod::Historical;
+        assert!(ValueAtRisk::new(0, 0.95, m).is_err());
+        assert!(ValueAtRisk::new(5, 0.0, m).is_err());
+        assert!(ValueAtRisk::new(5, 1.0, m).is_err());
+        assert!(ValueAtRisk::new(5, f64::NAN, m).is_err());
+        assert!(ValueAtRisk::new(1, 0.95, m).is_ok());
+    }
+
+    #[test]
+    fn test_inverse_normal_cdf() {
+        assert_eq!(inverse_normal_cdf(0.5), 0.0);
+        assert_eq!(
+            round((inverse_normal_cdf(0.95), inverse_normal_cdf(0.01))),
+            (1.6449, -2.3263)
+        );
+        assert_eq!(
+            round((inverse_normal_cdf(0.99), inverse_normal_cdf(0.8))),
+            (2.3263, 0.8416)
+        );
+    }
+
+    #[test]
+    fn test_next_historical() {
+        let mut var = ValueAtRisk::new(5, 0.8, VarMethod::Historical).unwrap();
+        let expected = [
+            (-0.01, -0.01),
+            (0.014, 0.02),
+            (0.008, 0.02),
+            (0.024, 0.03),
+            (0.022, 0.03),
+            (0.022, 0.03),
+        ];
+
+        for (r, e) in RETURNS.iter().zip(expected.iter()) {
+            assert_eq!(round(var.next(*r).into()), *e);
+        }
+    }
+
+    #[test]
+    fn test_next_gaussian() {
+        let mut var = ValueAtRisk::new(5, 0.8, VarMethod::Gaussian).unwrap();
+        let expected = [
+            (-0.01, -0.01),
+            (0.0176, 0.026),
+            (0.0113, 0.02),
+            (0.0224, 0.0331),
+            (0.0189, 0.0288),
+            (0.0217, 0.0308),
+        ];
+
+        for (r, e) in RETURNS.iter().zip(expected.iter()) {
+            assert_eq!(round(var.next(*r).into()), *e);
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut var = ValueAtRisk::new(5, 0.8, VarMethod::Historical).unwrap();
+
+        var.next(-0.05);
+        var.next(0.02);
+
+        var.reset();
+        assert_eq!(round(var.next(0.01).into()), (-0.01, -0.01));
+    }
+
+    #[test]
+    fn test_default() {
+        ValueAtRisk::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let var = ValueAtRisk::new(250, 0.99, VarMethod::Gaussian).unwrap();
+        assert_eq!(format!("{}", var), "VAR(250, 0.99, Gaussian)");
+    }
+}
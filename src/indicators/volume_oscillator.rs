@@ -0,0 +1,239 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{ExponentialMovingAverage as Ema, ExponentialMovingAverageState};
+use crate::{Amend, Checkpoint, Current, IsReady, Lookback, Next, Peek, Period, Reset, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Volume Oscillator (VO).
+///
+/// The difference between a "fast" (short period) and a "slow" (longer period)
+/// exponential moving average of volume, in volume units. Positive values mean
+/// that recent volume is above its longer term average.
+///
+/// See [PercentageVolumeOscillator](struct.PercentageVolumeOscillator.html) for
+/// a normalized version with a signal line.
+///
+/// # Formula
+///
+/// VO = EMA<sub>fast</sub>(Volume) - EMA<sub>slow</sub>(Volume)
+///
+/// # Parameters
+///
+/// * _fast_period_ - period for the fast EMA. Default is 5.
+/// * _slow_period_ - period for the slow EMA. Default is 10.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::VolumeOscillator;
+/// use ta::Next;
+///
+/// let mut vo = VolumeOscillator::new(2, 4).unwrap();
+///
+/// assert_eq!(vo.next(100.0), 0.0);
+/// assert_eq!((vo.next(300.0) * 100.0).round() / 100.0, 53.33);
+/// assert_eq!((vo.next(200.0) * 100.0).round() / 100.0, 23.11);
+/// ```
+#[doc(alias = "VO")]
+#[derive(Debug, Clone)]
+pub struct VolumeOscillator {
+    fast_ema: Ema,
+    slow_ema: Ema,
+    output: Option<f64>,
+}
+
+/// State of [VolumeOscillator](struct.VolumeOscillator.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeOscillatorState {
+    pub fast_ema: ExponentialMovingAverageState,
+    pub slow_ema: ExponentialMovingAverageState,
+    pub output: Option<f64>,
+}
+
+impl VolumeOscillator {
+    pub fn new(fast_period: usize, slow_period: usize) -> Result<Self> {
+        Ok(VolumeOscillator {
+            fast_ema: Ema::new(fast_period)?,
+            slow_ema: Ema::new(slow_period)?,
+            output: None,
+        })
+    }
+}
+
+impl Checkpoint for VolumeOscillator {
+    type State = VolumeOscillatorState;
+
+    fn state(&self) -> Self::State {
+        VolumeOscillatorState {
+            fast_ema: self.fast_ema.state(),
+            slow_ema: self.slow_ema.state(),
+            output: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_none() != (state.fast_ema.count == 0)
+            || state.output.is_none() != (state.slow_ema.count == 0)
+        {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            fast_ema: Ema::from_state(state.fast_ema)?,
+            slow_ema: Ema::from_state(state.slow_ema)?,
+            output: state.output,
+        })
+    }
+}
+
+impl_versioned_serde!(VolumeOscillator, 1);
+
+impl Next<f64> for VolumeOscillator {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let output = self.fast_ema.next(input) - self.slow_ema.next(input);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Volume> Next<&T> for VolumeOscillator {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.volume())
+    }
+}
+
+impl Peek<f64> for VolumeOscillator {
+    fn peek(&self, input: f64) -> f64 {
+        self.fast_ema.peek(input) - self.slow_ema.peek(input)
+    }
+}
+
+impl<T: Volume> Peek<&T> for VolumeOscillator {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.volume())
+    }
+}
+
+impl Amend<f64> for VolumeOscillator {
+    fn amend(&mut self, input: f64) -> f64 {
+        let output = self.fast_ema.amend(input) - self.slow_ema.amend(input);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Volume> Amend<&T> for VolumeOscillator {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.volume())
+    }
+}
+
+impl Reset for VolumeOscillator {
+    fn reset(&mut self) {
+        self.fast_ema.reset();
+        self.slow_ema.reset();
+        self.output = None;
+    }
+}
+
+impl Current for VolumeOscillator {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for VolumeOscillator {
+    fn is_ready(&self) -> bool {
+        self.fast_ema.is_ready() && self.slow_ema.is_ready()
+    }
+}
+
+impl Lookback for VolumeOscillator {
+    fn lookback(&self) -> usize {
+        self.fast_ema.lookback().max(self.slow_ema.lookback())
+    }
+}
+
+impl Default for VolumeOscillator {
+    fn default() -> Self {
+        Self::new(5, 10).unwrap()
+    }
+}
+
+impl fmt::Display for VolumeOscillator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "VO({}, {})",
+            self.fast_ema.period(),
+            self.slow_ema.period()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(VolumeOscillator);
+
+    #[test]
+    fn test_new() {
+        assert!(VolumeOscillator::new(0, 1).is_err());
+        assert!(VolumeOscillator::new(1, 0).is_err());
+        assert!(VolumeOscillator::new(1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut vo = VolumeOscillator::new(2, 4).unwrap();
+
+        assert_eq!(round(vo.next(100.0)), 0.0);
+        assert_eq!(round(vo.next(300.0)), 53.333);
+        assert_eq!(round(vo.next(200.0)), 23.111);
+        assert_eq!(round(vo.next(500.0)), 90.904);
+        assert_eq!(round(vo.next(400.0)), 53.555);
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut vo = VolumeOscillator::new(2, 4).unwrap();
+
+        assert_eq!(round(vo.next(&Bar::new().volume(100.0))), 0.0);
+        assert_eq!(round(vo.next(&Bar::new().volume(300.0))), 53.333);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vo = VolumeOscillator::new(2, 4).unwrap();
+
+        assert_eq!(round(vo.next(100.0)), 0.0);
+        assert_eq!(round(vo.next(300.0)), 53.333);
+
+        vo.reset();
+
+        assert_eq!(round(vo.next(100.0)), 0.0);
+        assert_eq!(round(vo.next(300.0)), 53.333);
+    }
+
+    #[test]
+    fn test_default() {
+        VolumeOscillator::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let indicator = VolumeOscillator::new(5, 10).unwrap();
+        assert_eq!(format!("{}", indicator), "VO(5, 10)");
+    }
+}
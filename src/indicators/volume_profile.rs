@@ -0,0 +1,499 @@
+use core::fmt;
+use std::collections::VecDeque;
+
+use crate::errors::{Result, TaError};
+use crate::{Amend, Checkpoint, Current, High, IsReady, Lookback, Low, Next, Peek, Reset, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Volume Profile.
+///
+/// Distributes traded volume over price. The price range covered by the bars in the profile is
+/// split into bins and the volume of every bar is spread evenly over its high-low range, so each
+/// bin receives the share of the volume that overlaps with it.
+///
+/// The profile either covers the last _period_ bars ([new](#method.new)) or is anchored
+/// ([anchored](#method.anchored)) and keeps growing until it is [reset](crate::Reset), e.g. at
+/// the start of every session.
+///
+/// From the histogram it derives:
+///
+/// * point of control (POC) - middle of the bin with the most volume;
+/// * value area - the smallest range of bins around the POC holding _value_area_ of the total
+///   volume. It is built by starting at the POC and repeatedly adding the neighbouring bin with
+///   more volume. Value area high (VAH) and value area low (VAL) are its edges.
+///
+/// The full histogram is available through [histogram](#method.histogram).
+///
+/// # Parameters
+///
+/// * _period_ - number of bars in a rolling profile (integer greater than 0). Default is 20.
+/// * _bins_ - either a fixed number of bins between the lowest low and the highest high, or a
+///   fixed bin width, in which case the bin edges are multiples of the width. Default is 24
+///   bins.
+/// * _value_area_ - share of the total volume in the value area (number in range (0, 1]).
+///   Default is 0.7.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{ProfileBins, VolumeProfile};
+/// use ta::{Next, DataItem};
+///
+/// fn bar(high: f64, low: f64, volume: f64) -> DataItem {
+///     DataItem::builder()
+///         .high(high).low(low).close(low).open(low)
+///         .volume(volume)
+///         .build().unwrap()
+/// }
+///
+/// let mut vp = VolumeProfile::anchored(ProfileBins::Count(4), 0.7).unwrap();
+///
+/// vp.next(&bar(14.0, 10.0, 400.0));
+/// let out = vp.next(&bar(12.0, 11.0, 300.0));
+///
+/// assert_eq!(out.poc, 11.5);
+/// assert_eq!(out.value_area_low, 11.0);
+/// assert_eq!(out.value_area_high, 13.0);
+/// assert_eq!(vp.histogram()[1].volume, 400.0);
+/// ```
+#[doc(alias = "VP")]
+#[derive(Debug, Clone)]
+pub struct VolumeProfile {
+    period: Option<usize>,
+    bins: ProfileBins,
+    value_area: f64,
+    bars: VecDeque<(f64, f64, f64)>,
+    histogram: Vec<ProfileBin>,
+    output: Option<VolumeProfileOutput>,
+    // State before the last input, restored by `amend`
+    evicted: Option<(f64, f64, f64)>,
+}
+
+/// State of [VolumeProfile](struct.VolumeProfile.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeProfileState {
+    /// Number of bars in the profile, `None` for an anchored profile.
+    pub period: Option<usize>,
+    pub bins: ProfileBins,
+    pub value_area: f64,
+    /// High, low and volume of the bars in the profile, the oldest first.
+    pub bars: Vec<(f64, f64, f64)>,
+    /// Bar which the last input pushed out of the window, to amend it.
+    pub evicted: Option<(f64, f64, f64)>,
+}
+
+/// How the price range of a [VolumeProfile](struct.VolumeProfile.html) is split into bins.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfileBins {
+    /// Fixed number of bins between the lowest low and the highest high.
+    Count(usize),
+    /// Fixed bin width.
+    Width(f64),
+}
+
+/// Single bin of a volume profile, covering prices from `low` up to `high`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProfileBin {
+    pub low: f64,
+    pub high: f64,
+    pub volume: f64,
+}
+
+/// Output of [VolumeProfile](struct.VolumeProfile.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeProfileOutput {
+    /// Point of control.
+    pub poc: f64,
+    pub value_area_high: f64,
+    pub value_area_low: f64,
+}
+
+impl_output!(VolumeProfileOutput, poc, value_area_high, value_area_low);
+
+impl From<VolumeProfileOutput> for (f64, f64, f64) {
+    fn from(vp: VolumeProfileOutput) -> Self {
+        (vp.poc, vp.value_area_high, vp.value_area_low)
+    }
+}
+
+impl VolumeProfile {
+    /// Rolling profile over the last _period_ bars.
+    pub fn new(period: usize, bins: ProfileBins, value_area: f64) -> Result<Self> {
+        if period == 0 {
+            return Err(TaError::invalid_parameter("period", 0.0, ">= 1"));
+        }
+        Self::build(Some(period), bins, value_area)
+    }
+
+    /// Profile over all bars since creation or the last reset.
+    pub fn anchored(bins: ProfileBins, value_area: f64) -> Result<Self> {
+        Self::build(None, bins, value_area)
+    }
+
+    fn build(period: Option<usize>, bins: ProfileBins, value_area: f64) -> Result<Self> {
+        match bins {
+            ProfileBins::Count(0) => return Err(TaError::invalid_parameter("bins", 0.0, ">= 1")),
+            ProfileBins::Width(width) if !(width.is_finite() && width > 0.0) => {
+                return Err(TaError::invalid_parameter("bins", width, "> 0"))
+            }
+            _ => {}
+        }
+        if !(value_area > 0.0 && value_area <= 1.0) {
+            return Err(TaError::invalid_parameter(
+                "value_area",
+                value_area,
+                "0 < value_area <= 1",
+            ));
+        }
+        Ok(Self {
+            period,
+            bins,
+            value_area,
+            bars: VecDeque::new(),
+            histogram: Vec::new(),
+            output: None,
+            evicted: None,
+        })
+    }
+
+    /// Bins of the current profile, from the lowest price to the highest.
+    pub fn histogram(&self) -> &[ProfileBin] {
+        &self.histogram
+    }
+
+    fn rebuild(&mut self) {
+        let mut histogram = core::mem::take(&mut self.histogram);
+        self.fill(&mut histogram, self.bars.iter().copied());
+        self.histogram = histogram;
+    }
+
+    // Builds the histogram of bars given as high, low and volume
+    fn fill(
+        &self,
+        histogram: &mut Vec<ProfileBin>,
+        bars: impl Iterator<Item = (f64, f64, f64)> + Clone,
+    ) {
+        let min = bars.clone().map(|b| b.1).fold(f64::INFINITY, f64::min);
+        let max = bars.clone().map(|b| b.0).fold(f64::NEG_INFINITY, f64::max);
+
+        let (start, width, count) = match self.bins {
+            ProfileBins::Count(count) => (min, (max - min) / count as f64, count),
+            ProfileBins::Width(width) => {
+                let first = (min / width).floor();
+                let count = ((max / width).ceil() - first).max(1.0) as usize;
+                (first * width, width, count)
+            }
+        };
+
+        histogram.clear();
+        histogram.extend((0..count).map(|i| ProfileBin {
+            low: start + i as f64 * width,
+            high: start + (i + 1) as f64 * width,
+            volume: 0.0,
+        }));
+
+        for (high, low, volume) in bars {
+            if high > low {
+                for bin in histogram.iter_mut() {
+                    let overlap = high.min(bin.high) - low.max(bin.low);
+                    if overlap > 0.0 {
+                        bin.volume += volume * overlap / (high - low);
+                    }
+                }
+            } else {
+                let i = if width > 0.0 {
+                    (((low - start) / width) as usize).min(count - 1)
+                } else {
+                    0
+                };
+                histogram[i].volume += volume;
+            }
+        }
+    }
+
+    fn output(&self) -> VolumeProfileOutput {
+        self.summary(&self.histogram)
+    }
+
+    fn summary(&self, histogram: &[ProfileBin]) -> VolumeProfileOutput {
+        let volumes: Vec<f64> = histogram.iter().map(|b| b.volume).collect();
+
+        let mut poc = 0;
+        for (i, &v) in volumes.iter().enumerate() {
+            if v > volumes[poc] {
+                poc = i;
+            }
+        }
+
+        let target = self.value_area * volumes.iter().sum::<f64>();
+        let (mut lo, mut hi) = (poc, poc);
+        let mut sum = volumes[poc];
+        while sum < target && (lo > 0 || hi + 1 < volumes.len()) {
+            let below = if lo > 0 { volumes[lo - 1] } else { -1.0 };
+            let above = if hi + 1 < volumes.len() {
+                volumes[hi + 1]
+            } else {
+                -1.0
+            };
+            if above >= below {
+                hi += 1;
+                sum += above;
+            } else {
+                lo -= 1;
+                sum += below;
+            }
+        }
+
+        let bin = histogram[poc];
+        VolumeProfileOutput {
+            poc: (bin.low + bin.high) / 2.0,
+            value_area_high: histogram[hi].high,
+            value_area_low: histogram[lo].low,
+        }
+    }
+}
+
+impl Checkpoint for VolumeProfile {
+    type State = VolumeProfileState;
+
+    fn state(&self) -> Self::State {
+        VolumeProfileState {
+            period: self.period,
+            bins: self.bins,
+            value_area: self.value_area,
+            bars: self.bars.iter().copied().collect(),
+            evicted: self.evicted,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut indicator = match state.period {
+            Some(period) => Self::new(period, state.bins, state.value_area),
+            None => Self::anchored(state.bins, state.value_area),
+        }
+        .map_err(|_| TaError::InvalidState)?;
+        if state.period.is_some_and(|period| state.bars.len() > period)
+            || (state.evicted.is_some() && Some(state.bars.len()) != state.period)
+        {
+            return Err(TaError::InvalidState);
+        }
+        if !state.bars.is_empty() {
+            indicator.bars = state.bars.into();
+            indicator.rebuild();
+            indicator.output = Some(indicator.output());
+        }
+        indicator.evicted = state.evicted;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(VolumeProfile, 2);
+
+impl<T: High + Low + Volume> Next<&T> for VolumeProfile {
+    type Output = VolumeProfileOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.evicted = None;
+        if Some(self.bars.len()) == self.period {
+            self.evicted = self.bars.pop_front();
+        }
+        self.bars
+            .push_back((input.high(), input.low(), input.volume()));
+
+        self.rebuild();
+        let output = self.output();
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low + Volume> Peek<&T> for VolumeProfile {
+    fn peek(&self, input: &T) -> VolumeProfileOutput {
+        let evicted = usize::from(Some(self.bars.len()) == self.period);
+        let bars = self.bars.iter().copied().skip(evicted);
+        let bar = (input.high(), input.low(), input.volume());
+
+        let mut histogram = Vec::new();
+        self.fill(&mut histogram, bars.chain(core::iter::once(bar)));
+        self.summary(&histogram)
+    }
+}
+
+impl<T: High + Low + Volume> Amend<&T> for VolumeProfile {
+    fn amend(&mut self, input: &T) -> VolumeProfileOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.bars.pop_back();
+        if let Some(bar) = self.evicted {
+            self.bars.push_front(bar);
+        }
+        self.next(input)
+    }
+}
+
+impl Reset for VolumeProfile {
+    fn reset(&mut self) {
+        self.bars.clear();
+        self.histogram.clear();
+        self.output = None;
+        self.evicted = None;
+    }
+}
+
+impl Current for VolumeProfile {
+    type Output = VolumeProfileOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for VolumeProfile {
+    fn is_ready(&self) -> bool {
+        match self.period {
+            Some(period) => self.bars.len() == period,
+            None => !self.bars.is_empty(),
+        }
+    }
+}
+
+impl Lookback for VolumeProfile {
+    fn lookback(&self) -> usize {
+        self.period.unwrap_or(1)
+    }
+}
+
+impl Default for VolumeProfile {
+    fn default() -> Self {
+        Self::new(20, ProfileBins::Count(24), 0.7).unwrap()
+    }
+}
+
+impl fmt::Display for VolumeProfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bins = match self.bins {
+            ProfileBins::Count(count) => format!("{}", count),
+            ProfileBins::Width(width) => format!("{:?}", width),
+        };
+        match self.period {
+            Some(period) => write!(f, "VP({}, {}, {})", period, bins, self.value_area),
+            None => write!(f, "VP({}, {})", bins, self.value_area),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn bar(high: f64, low: f64, volume: f64) -> Bar {
+        Bar::new().high(high).low(low).volume(volume)
+    }
+
+    fn volumes(vp: &VolumeProfile) -> Vec<f64> {
+        vp.histogram().iter().map(|b| b.volume).collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(VolumeProfile::new(0, ProfileBins::Count(10), 0.7).is_err());
+        assert!(VolumeProfile::new(10, ProfileBins::Count(0), 0.7).is_err());
+        assert!(VolumeProfile::new(10, ProfileBins::Width(0.0), 0.7).is_err());
+        assert!(VolumeProfile::new(10, ProfileBins::Width(f64::NAN), 0.7).is_err());
+        assert!(VolumeProfile::new(10, ProfileBins::Count(10), 0.0).is_err());
+        assert!(VolumeProfile::new(10, ProfileBins::Count(10), 1.1).is_err());
+        assert!(VolumeProfile::new(10, ProfileBins::Count(10), 1.0).is_ok());
+        assert!(VolumeProfile::anchored(ProfileBins::Width(0.5), 0.7).is_ok());
+    }
+
+    #[test]
+    fn test_rolling() {
+        let mut vp = VolumeProfile::new(2, ProfileBins::Count(4), 0.7).unwrap();
+
+        let out = vp.next(&bar(14.0, 10.0, 400.0));
+        assert_eq!(volumes(&vp), vec![100.0, 100.0, 100.0, 100.0]);
+        assert_eq!(out.poc, 10.5);
+
+        let out = vp.next(&bar(12.0, 11.0, 300.0));
+        assert_eq!(volumes(&vp), vec![100.0, 400.0, 100.0, 100.0]);
+        assert_eq!(out.poc, 11.5);
+        assert_eq!(out.value_area_low, 11.0);
+        assert_eq!(out.value_area_high, 13.0);
+
+        // The first bar leaves the window
+        let out = vp.next(&bar(14.0, 13.0, 50.0));
+        assert_eq!(volumes(&vp), vec![225.0, 75.0, 12.5, 37.5]);
+        assert_eq!(vp.histogram()[0].low, 11.0);
+        assert_eq!(vp.histogram()[3].high, 14.0);
+        assert_eq!(out.poc, 11.375);
+        assert_eq!(out.value_area_low, 11.0);
+        assert_eq!(out.value_area_high, 12.5);
+    }
+
+    #[test]
+    fn test_anchored_width() {
+        let mut vp = VolumeProfile::anchored(ProfileBins::Width(2.0), 0.7).unwrap();
+
+        let out = vp.next(&bar(14.0, 10.0, 400.0));
+        assert_eq!(volumes(&vp), vec![200.0, 200.0]);
+        assert_eq!(out.poc, 11.0);
+
+        let out = vp.next(&bar(13.0, 12.0, 100.0));
+        assert_eq!(volumes(&vp), vec![200.0, 300.0]);
+        assert_eq!(out.poc, 13.0);
+        assert_eq!(out.value_area_low, 10.0);
+        assert_eq!(out.value_area_high, 14.0);
+
+        let out = vp.next(&bar(15.0, 15.0, 1000.0));
+        assert_eq!(volumes(&vp), vec![200.0, 300.0, 1000.0]);
+        assert_eq!(out.poc, 15.0);
+        assert_eq!(out.value_area_low, 12.0);
+        assert_eq!(out.value_area_high, 16.0);
+    }
+
+    #[test]
+    fn test_flat_range() {
+        let mut vp = VolumeProfile::anchored(ProfileBins::Count(3), 0.7).unwrap();
+
+        let out = vp.next(&bar(10.0, 10.0, 100.0));
+        assert_eq!(out.poc, 10.0);
+        assert_eq!(out.value_area_low, 10.0);
+        assert_eq!(out.value_area_high, 10.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vp = VolumeProfile::anchored(ProfileBins::Count(4), 0.7).unwrap();
+
+        vp.next(&bar(14.0, 10.0, 400.0));
+        vp.next(&bar(12.0, 11.0, 300.0));
+
+        vp.reset();
+        assert!(vp.histogram().is_empty());
+
+        let out = vp.next(&bar(14.0, 10.0, 400.0));
+        assert_eq!(volumes(&vp), vec![100.0, 100.0, 100.0, 100.0]);
+        assert_eq!(out.poc, 10.5);
+    }
+
+    #[test]
+    fn test_default() {
+        VolumeProfile::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let vp = VolumeProfile::new(20, ProfileBins::Count(24), 0.7).unwrap();
+        assert_eq!(format!("{}", vp), "VP(20, 24, 0.7)");
+
+        let vp = VolumeProfile::anchored(ProfileBins::Width(0.5), 0.68).unwrap();
+        assert_eq!(format!("{}", vp), "VP(0.5, 0.68)");
+    }
+}
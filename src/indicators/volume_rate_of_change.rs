@@ -0,0 +1,339 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::traits::{
+    Amend, Checkpoint, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod, Volume,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Volume Rate of Change (VROC)
+///
+/// The percentage change of volume compared to the volume _n_ periods ago.
+/// Same as [RateOfChange](struct.RateOfChange.html), but consumes volume instead of close price.
+///
+/// # Formula
+///
+/// VROC = (Volume<sub>t</sub> - Volume<sub>t-n</sub>) / Volume<sub>t-n</sub> * 100
+///
+/// Where:
+///
+/// * VROC - current value of Volume Rate of Change indicator
+/// * Volume<sub>t</sub> - volume at the moment
+/// * Volume<sub>t-n</sub> - volume _n_ periods ago
+///
+/// If Volume<sub>t-n</sub> is zero, VROC is 0.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods integer greater than 0. Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::VolumeRateOfChange;
+/// use ta::Next;
+///
+/// let mut vroc = VolumeRateOfChange::new(2).unwrap();
+/// assert_eq!(vroc.next(1000.0), 0.0);     //  0
+/// assert_eq!(vroc.next(1200.0), 20.0);    //  (1200 - 1000) / 1000 * 100 = 20
+/// assert_eq!(vroc.next(500.0), -50.0);    //  (500 - 1000)  / 1000 * 100 = -50
+/// assert_eq!(vroc.next(1800.0), 50.0);    //  (1800 - 1200) / 1200 * 100 = 50
+/// ```
+#[doc(alias = "VROC")]
+#[derive(Debug, Clone)]
+pub struct VolumeRateOfChange {
+    period: usize,
+    index: usize,
+    count: usize,
+    deque: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: f64,
+    saved_count: usize,
+}
+
+/// State of [VolumeRateOfChange](struct.VolumeRateOfChange.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeRateOfChangeState {
+    pub period: usize,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<f64>,
+    /// Last output, `None` before the first input.
+    pub output: Option<f64>,
+    /// Input which the last one pushed out of the window, to amend it.
+    pub evicted: Option<f64>,
+}
+
+impl VolumeRateOfChange {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                deque: vec![0.0; period].into_boxed_slice(),
+                output: None,
+                saved_value: 0.0,
+                saved_count: 0,
+            }),
+        }
+    }
+}
+
+impl Period for VolumeRateOfChange {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for VolumeRateOfChange {
+    type State = VolumeRateOfChangeState;
+
+    fn state(&self) -> Self::State {
+        VolumeRateOfChangeState {
+            period: self.period,
+            inputs: ring_values(&self.deque, self.index, self.count),
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count >= self.period)
+                .then_some(self.saved_value),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.inputs.is_empty()
+            || (state.evicted.is_some() && state.inputs.len() != state.period)
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let (deque, index) = ring_from_values(state.period, &state.inputs)?;
+        indicator.deque = deque;
+        indicator.index = index;
+        indicator.count = state.inputs.len();
+        indicator.output = state.output;
+        indicator.saved_value = state.evicted.unwrap_or(0.0);
+        indicator.saved_count = match state.evicted {
+            // A full window takes the oldest input from the next slot
+            Some(_) => indicator.count + 1,
+            None => indicator.count.saturating_sub(1),
+        };
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(VolumeRateOfChange, 2);
+
+impl SetPeriod for VolumeRateOfChange {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for VolumeRateOfChange {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> f64 {
+        self.saved_value = self.deque[self.index];
+        self.saved_count = self.count;
+        let previous = if self.count > self.period {
+            self.deque[self.index]
+        } else {
+            self.count += 1;
+            if self.count == 1 {
+                input
+            } else {
+                self.deque[0]
+            }
+        };
+        self.deque[self.index] = input;
+
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        let output = if previous != 0.0 {
+            (input - previous) / previous * 100.0
+        } else {
+            0.0
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Volume> Next<&T> for VolumeRateOfChange {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> f64 {
+        self.next(input.volume())
+    }
+}
+
+impl Peek<f64> for VolumeRateOfChange {
+    fn peek(&self, input: f64) -> f64 {
+        let previous = if self.count > self.period {
+            self.deque[self.index]
+        } else if self.count == 0 {
+            input
+        } else {
+            self.deque[0]
+        };
+
+        if previous != 0.0 {
+            (input - previous) / previous * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<T: Volume> Peek<&T> for VolumeRateOfChange {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.volume())
+    }
+}
+
+impl Default for VolumeRateOfChange {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for VolumeRateOfChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VROC({})", self.period)
+    }
+}
+
+impl Amend<f64> for VolumeRateOfChange {
+    fn amend(&mut self, input: f64) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        self.deque[self.index] = self.saved_value;
+        self.count = self.saved_count;
+        self.next(input)
+    }
+}
+
+impl<T: Volume> Amend<&T> for VolumeRateOfChange {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.volume())
+    }
+}
+
+impl Reset for VolumeRateOfChange {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = 0.0;
+        self.saved_count = 0;
+    }
+}
+
+impl Current for VolumeRateOfChange {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for VolumeRateOfChange {
+    fn is_ready(&self) -> bool {
+        self.count > self.period
+    }
+}
+
+impl Lookback for VolumeRateOfChange {
+    fn lookback(&self) -> usize {
+        self.period + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(VolumeRateOfChange);
+
+    #[test]
+    fn test_new() {
+        assert!(VolumeRateOfChange::new(0).is_err());
+        assert!(VolumeRateOfChange::new(1).is_ok());
+        assert!(VolumeRateOfChange::new(100_000).is_ok());
+    }
+
+    #[test]
+    fn test_next_f64() {
+        let mut vroc = VolumeRateOfChange::new(3).unwrap();
+
+        assert_eq!(round(vroc.next(1000.0)), 0.0);
+        assert_eq!(round(vroc.next(1040.0)), 4.0);
+        assert_eq!(round(vroc.next(1057.0)), 5.7);
+        assert_eq!(round(vroc.next(1080.0)), 8.0);
+        assert_eq!(round(vroc.next(1090.0)), 4.808);
+        assert_eq!(round(vroc.next(1000.0)), -5.393);
+    }
+
+    #[test]
+    fn test_next_zero_volume() {
+        let mut vroc = VolumeRateOfChange::new(1).unwrap();
+
+        assert_eq!(vroc.next(0.0), 0.0);
+        assert_eq!(vroc.next(100.0), 0.0);
+        assert_eq!(vroc.next(150.0), 50.0);
+    }
+
+    #[test]
+    fn test_next_bar() {
+        fn bar(volume: f64) -> Bar {
+            Bar::new().volume(volume)
+        }
+
+        let mut vroc = VolumeRateOfChange::new(3).unwrap();
+
+        assert_eq!(round(vroc.next(&bar(1000.0))), 0.0);
+        assert_eq!(round(vroc.next(&bar(1040.0))), 4.0);
+        assert_eq!(round(vroc.next(&bar(1057.0))), 5.7);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vroc = VolumeRateOfChange::new(3).unwrap();
+
+        vroc.next(1230.0);
+        vroc.next(1500.0);
+
+        vroc.reset();
+
+        assert_eq!(round(vroc.next(1000.0)), 0.0);
+        assert_eq!(round(vroc.next(1040.0)), 4.0);
+        assert_eq!(round(vroc.next(1057.0)), 5.7);
+    }
+
+    #[test]
+    fn test_default() {
+        VolumeRateOfChange::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let indicator = VolumeRateOfChange::new(14).unwrap();
+        assert_eq!(format!("{}", indicator), "VROC(14)");
+    }
+}
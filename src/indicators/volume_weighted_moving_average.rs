@@ -0,0 +1,353 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+    Volume,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Volume weighted moving average (VWMA).
+///
+/// The average of the close price weighted by traded volume over the last _period_ bars.
+/// Unlike the [VWAP](struct.VolumeWeightedAveragePrice.html) it is based on the close price,
+/// so it can replace a [SMA](type.SimpleMovingAverage.html) of the close.
+///
+/// # Formula
+///
+/// VWMA = Σ(Close * Volume) / Σ(Volume)
+///
+/// Where sums are calculated over the last _period_ bars.
+///
+/// If there was no traded volume in the window, the close price of the current bar is returned.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::VolumeWeightedMovingAverage;
+/// use ta::{Next, DataItem};
+///
+/// let mut vwma = VolumeWeightedMovingAverage::new(2).unwrap();
+///
+/// let di1 = DataItem::builder()
+///             .high(11.0).low(9.0).close(10.0).open(10.0)
+///             .volume(100.0)
+///             .build().unwrap();
+/// let di2 = DataItem::builder()
+///             .high(14.0).low(12.0).close(13.0).open(13.0)
+///             .volume(200.0)
+///             .build().unwrap();
+///
+/// assert_eq!(vwma.next(&di1), 10.0);
+/// assert_eq!(vwma.next(&di2), 12.0);
+/// ```
+///
+/// # Links
+///
+/// * [Volume Weighted Moving Average, Investopedia](https://www.investopedia.com/articles/trading/11/trading-with-vwap-mvwap.asp)
+///
+#[doc(alias = "VWMA")]
+#[derive(Debug, Clone)]
+pub struct VolumeWeightedMovingAverage {
+    period: usize,
+    index: usize,
+    count: usize,
+    sum_price_volume: f64,
+    sum_volume: f64,
+    price_volumes: Box<[f64]>,
+    volumes: Box<[f64]>,
+    output: Option<f64>,
+    // State before the last input, restored by `amend`
+    saved_value: (f64, f64),
+    saved_count: usize,
+    saved_sum_price_volume: f64,
+    saved_sum_volume: f64,
+}
+
+/// State of [VolumeWeightedMovingAverage](struct.VolumeWeightedMovingAverage.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeWeightedMovingAverageState {
+    pub period: usize,
+    /// Closes times volumes in the window, the oldest first.
+    pub price_volumes: Vec<f64>,
+    /// Volumes in the window, the oldest first.
+    pub volumes: Vec<f64>,
+    pub sum_price_volume: f64,
+    pub sum_volume: f64,
+    pub output: Option<f64>,
+    /// Close volume and volume which the last input pushed out of the window, to amend it.
+    pub evicted: Option<(f64, f64)>,
+    /// Running sums before the last input, to amend it.
+    pub previous_sum_price_volume: f64,
+    pub previous_sum_volume: f64,
+}
+
+impl VolumeWeightedMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                sum_price_volume: 0.0,
+                sum_volume: 0.0,
+                price_volumes: vec![0.0; period].into_boxed_slice(),
+                volumes: vec![0.0; period].into_boxed_slice(),
+                output: None,
+                saved_value: (0.0, 0.0),
+                saved_count: 0,
+                saved_sum_price_volume: 0.0,
+                saved_sum_volume: 0.0,
+            }),
+        }
+    }
+}
+
+impl Period for VolumeWeightedMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for VolumeWeightedMovingAverage {
+    type State = VolumeWeightedMovingAverageState;
+
+    fn state(&self) -> Self::State {
+        VolumeWeightedMovingAverageState {
+            period: self.period,
+            price_volumes: ring_values(&self.price_volumes, self.index, self.count),
+            volumes: ring_values(&self.volumes, self.index, self.count),
+            sum_price_volume: self.sum_price_volume,
+            sum_volume: self.sum_volume,
+            output: self.output,
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous_sum_price_volume: self.saved_sum_price_volume,
+            previous_sum_volume: self.saved_sum_volume,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() == state.price_volumes.is_empty()
+            || state.volumes.len() != state.price_volumes.len()
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let (price_volumes, index) = ring_from_values(state.period, &state.price_volumes)?;
+        let (volumes, _) = ring_from_values(state.period, &state.volumes)?;
+        indicator.price_volumes = price_volumes;
+        indicator.volumes = volumes;
+        indicator.index = index;
+        indicator.count = state.price_volumes.len();
+        indicator.sum_price_volume = state.sum_price_volume;
+        indicator.sum_volume = state.sum_volume;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or((0.0, 0.0));
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_sum_price_volume = state.previous_sum_price_volume;
+        indicator.saved_sum_volume = state.previous_sum_volume;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(VolumeWeightedMovingAverage, 2);
+
+impl SetPeriod for VolumeWeightedMovingAverage {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl<T: Close + Volume> Next<&T> for VolumeWeightedMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_value = (self.price_volumes[self.index], self.volumes[self.index]);
+        self.saved_count = self.count;
+        self.saved_sum_price_volume = self.sum_price_volume;
+        self.saved_sum_volume = self.sum_volume;
+        let close = input.close();
+        let volume = input.volume();
+        let price_volume = close * volume;
+
+        self.sum_price_volume += price_volume - self.price_volumes[self.index];
+        self.sum_volume += volume - self.volumes[self.index];
+        self.price_volumes[self.index] = price_volume;
+        self.volumes[self.index] = volume;
+
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        self.count = (self.count + 1).min(self.period);
+
+        let output = if self.sum_volume > 0.0 {
+            self.sum_price_volume / self.sum_volume
+        } else {
+            close
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close + Volume> Peek<&T> for VolumeWeightedMovingAverage {
+    fn peek(&self, input: &T) -> f64 {
+        let close = input.close();
+        let volume = input.volume();
+        let sum_price_volume =
+            self.sum_price_volume + (close * volume - self.price_volumes[self.index]);
+        let sum_volume = self.sum_volume + (volume - self.volumes[self.index]);
+
+        if sum_volume > 0.0 {
+            sum_price_volume / sum_volume
+        } else {
+            close
+        }
+    }
+}
+
+impl<T: Close + Volume> Amend<&T> for VolumeWeightedMovingAverage {
+    fn amend(&mut self, input: &T) -> f64 {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        (self.price_volumes[self.index], self.volumes[self.index]) = self.saved_value;
+        self.count = self.saved_count;
+        self.sum_price_volume = self.saved_sum_price_volume;
+        self.sum_volume = self.saved_sum_volume;
+        self.next(input)
+    }
+}
+
+impl Reset for VolumeWeightedMovingAverage {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.sum_price_volume = 0.0;
+        self.sum_volume = 0.0;
+        for i in 0..self.period {
+            self.price_volumes[i] = 0.0;
+            self.volumes[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = (0.0, 0.0);
+        self.saved_count = 0;
+        self.saved_sum_price_volume = 0.0;
+        self.saved_sum_volume = 0.0;
+    }
+}
+
+impl Current for VolumeWeightedMovingAverage {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for VolumeWeightedMovingAverage {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for VolumeWeightedMovingAverage {
+    fn lookback(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for VolumeWeightedMovingAverage {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for VolumeWeightedMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VWMA({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    type Vwma = VolumeWeightedMovingAverage;
+
+    #[test]
+    fn test_new() {
+        assert!(Vwma::new(0).is_err());
+        assert!(Vwma::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut vwma = Vwma::new(2).unwrap();
+
+        let bar1 = Bar::new().close(10).volume(100.0);
+        let bar2 = Bar::new().close(13).volume(200.0);
+        let bar3 = Bar::new().close(7).volume(200.0);
+        let bar4 = Bar::new().close(7).volume(0.0);
+
+        assert_eq!(vwma.next(&bar1), 10.0);
+        assert_eq!(vwma.next(&bar2), 12.0);
+        // bar1 left the window
+        assert_eq!(vwma.next(&bar3), 10.0);
+        assert_eq!(vwma.next(&bar4), 7.0);
+    }
+
+    #[test]
+    fn test_next_without_volume() {
+        let mut vwma = Vwma::new(3).unwrap();
+
+        assert_eq!(vwma.next(&Bar::new().close(10).volume(0.0)), 10.0);
+        assert_eq!(vwma.next(&Bar::new().close(13).volume(0.0)), 13.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vwma = Vwma::new(5).unwrap();
+
+        let bar1 = Bar::new().close(10).volume(100.0);
+        let bar2 = Bar::new().close(13).volume(200.0);
+
+        vwma.next(&bar1);
+        vwma.next(&bar2);
+
+        vwma.reset();
+        assert_eq!(vwma.next(&bar2), 13.0);
+    }
+
+    #[test]
+    fn test_default() {
+        Vwma::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let vwma = Vwma::new(20).unwrap();
+        assert_eq!(format!("{}", vwma), "VWMA(20)");
+    }
+}
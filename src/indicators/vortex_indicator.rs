@@ -0,0 +1,504 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring_from_values, ring_values};
+use crate::indicators::{TrueRange, TrueRangeState};
+use crate::{
+    Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset,
+    SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Vortex Indicator (VI).
+///
+/// Consists of two oscillators that capture positive and negative trend movement.
+/// A bullish signal triggers when the positive line crosses above the negative line.
+///
+/// # Formula
+///
+/// +VM<sub>t</sub> = |High<sub>t</sub> - Low<sub>t-1</sub>|
+///
+/// -VM<sub>t</sub> = |Low<sub>t</sub> - High<sub>t-1</sub>|
+///
+/// VI+ = Σ(+VM) / Σ(TR)
+///
+/// VI- = Σ(-VM) / Σ(TR)
+///
+/// Where:
+///
+/// * _TR_ - [true range](type.TrueRange.html)
+/// * sums are calculated over the last _period_ bars
+///
+/// The very first bar has no previous bar, so it has no movements and its true range is left
+/// out of the sums as well; its output is 0. The indicator is ready after _period + 1_ bars.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::VortexIndicator;
+/// use ta::{Next, DataItem};
+///
+/// fn bar(high: f64, low: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .high(high).low(low).close(close).open(close)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut vi = VortexIndicator::new(2).unwrap();
+///
+/// vi.next(&bar(10.0, 8.0, 9.0));
+/// vi.next(&bar(12.0, 9.0, 11.0));
+/// let out = vi.next(&bar(11.0, 7.0, 8.0));
+/// assert_eq!(out.plus, 6.0 / 7.0);
+/// assert_eq!(out.minus, 6.0 / 7.0);
+/// ```
+///
+/// # Links
+///
+/// * [Vortex indicator, Wikipedia](https://en.wikipedia.org/wiki/Vortex_indicator)
+///
+#[doc(alias = "VI")]
+#[derive(Debug, Clone)]
+pub struct VortexIndicator {
+    period: usize,
+    index: usize,
+    count: usize,
+    prev_high: Option<f64>,
+    prev_low: f64,
+    true_range: TrueRange,
+    sum_plus_vm: f64,
+    sum_minus_vm: f64,
+    sum_tr: f64,
+    plus_vm: Box<[f64]>,
+    minus_vm: Box<[f64]>,
+    tr: Box<[f64]>,
+    output: Option<VortexIndicatorOutput>,
+    // State before the last input, restored by `amend`
+    saved_value: (f64, f64, f64),
+    saved_count: usize,
+    saved_prev_high: Option<f64>,
+    saved_prev_low: f64,
+    saved_sums: (f64, f64, f64),
+}
+
+/// State of [VortexIndicator](struct.VortexIndicator.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VortexIndicatorState {
+    pub period: usize,
+    pub true_range: TrueRangeState,
+    /// High and low of the previous bar.
+    pub previous: Option<(f64, f64)>,
+    /// Positive vortex movements in the window, the oldest first.
+    pub plus_vm: Vec<f64>,
+    /// Negative vortex movements in the window, the oldest first.
+    pub minus_vm: Vec<f64>,
+    /// True ranges in the window, the oldest first.
+    pub tr: Vec<f64>,
+    pub sum_plus_vm: f64,
+    pub sum_minus_vm: f64,
+    pub sum_tr: f64,
+    pub output: Option<VortexIndicatorOutput>,
+    /// Movements and true range which the last input pushed out of the window, to amend it.
+    pub evicted: Option<(f64, f64, f64)>,
+    /// High and low of the bar before the last one, to amend the last one.
+    pub before_last: Option<(f64, f64)>,
+    /// Sums of the movements and true ranges before the last input, to amend it.
+    pub previous_sums: (f64, f64, f64),
+}
+
+/// Output of [VortexIndicator](struct.VortexIndicator.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VortexIndicatorOutput {
+    pub plus: f64,
+    pub minus: f64,
+}
+
+impl_output!(VortexIndicatorOutput, plus, minus);
+
+impl From<VortexIndicatorOutput> for (f64, f64) {
+    fn from(vi: VortexIndicatorOutput) -> Self {
+        (vi.plus, vi.minus)
+    }
+}
+
+impl VortexIndicator {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                prev_high: None,
+                prev_low: 0.0,
+                true_range: TrueRange::new(),
+                sum_plus_vm: 0.0,
+                sum_minus_vm: 0.0,
+                sum_tr: 0.0,
+                plus_vm: vec![0.0; period].into_boxed_slice(),
+                minus_vm: vec![0.0; period].into_boxed_slice(),
+                tr: vec![0.0; period].into_boxed_slice(),
+                output: None,
+                saved_value: (0.0, 0.0, 0.0),
+                saved_count: 0,
+                saved_prev_high: None,
+                saved_prev_low: 0.0,
+                saved_sums: (0.0, 0.0, 0.0),
+            }),
+        }
+    }
+
+    fn update<T: High + Low>(&mut self, input: &T, tr: f64) -> VortexIndicatorOutput {
+        let prev_high = self.prev_high.replace(input.high());
+        let prev_low = core::mem::replace(&mut self.prev_low, input.low());
+        let prev_high = match prev_high {
+            Some(prev_high) => prev_high,
+            None => {
+                let output = VortexIndicatorOutput {
+                    plus: 0.0,
+                    minus: 0.0,
+                };
+                self.output = Some(output.clone());
+                return output;
+            }
+        };
+        let plus_vm = (input.high() - prev_low).abs();
+        let minus_vm = (input.low() - prev_high).abs();
+
+        self.sum_plus_vm += plus_vm - self.plus_vm[self.index];
+        self.sum_minus_vm += minus_vm - self.minus_vm[self.index];
+        self.sum_tr += tr - self.tr[self.index];
+        self.plus_vm[self.index] = plus_vm;
+        self.minus_vm[self.index] = minus_vm;
+        self.tr[self.index] = tr;
+
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        self.count = (self.count + 1).min(self.period);
+
+        let output = if self.sum_tr > 0.0 {
+            VortexIndicatorOutput {
+                plus: self.sum_plus_vm / self.sum_tr,
+                minus: self.sum_minus_vm / self.sum_tr,
+            }
+        } else {
+            VortexIndicatorOutput {
+                plus: 0.0,
+                minus: 0.0,
+            }
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Period for VortexIndicator {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Checkpoint for VortexIndicator {
+    type State = VortexIndicatorState;
+
+    fn state(&self) -> Self::State {
+        VortexIndicatorState {
+            period: self.period,
+            true_range: self.true_range.state(),
+            previous: self.prev_high.map(|high| (high, self.prev_low)),
+            plus_vm: ring_values(&self.plus_vm, self.index, self.count),
+            minus_vm: ring_values(&self.minus_vm, self.index, self.count),
+            tr: ring_values(&self.tr, self.index, self.count),
+            sum_plus_vm: self.sum_plus_vm,
+            sum_minus_vm: self.sum_minus_vm,
+            sum_tr: self.sum_tr,
+            output: self.output.clone(),
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            before_last: self.saved_prev_high.map(|high| (high, self.saved_prev_low)),
+            previous_sums: self.saved_sums,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_some() != state.previous.is_some()
+            || (state.previous.is_none() && !state.plus_vm.is_empty())
+            || state.minus_vm.len() != state.plus_vm.len()
+            || state.tr.len() != state.plus_vm.len()
+        {
+            return Err(TaError::InvalidState);
+        }
+        let mut indicator = Self::new(state.period).map_err(|_| TaError::InvalidState)?;
+        let (plus_vm, index) = ring_from_values(state.period, &state.plus_vm)?;
+        let (minus_vm, _) = ring_from_values(state.period, &state.minus_vm)?;
+        let (tr, _) = ring_from_values(state.period, &state.tr)?;
+        indicator.true_range = TrueRange::from_state(state.true_range)?;
+        indicator.prev_high = state.previous.map(|(high, _)| high);
+        indicator.prev_low = state.previous.map_or(0.0, |(_, low)| low);
+        indicator.plus_vm = plus_vm;
+        indicator.minus_vm = minus_vm;
+        indicator.tr = tr;
+        indicator.index = index;
+        indicator.count = state.plus_vm.len();
+        indicator.sum_plus_vm = state.sum_plus_vm;
+        indicator.sum_minus_vm = state.sum_minus_vm;
+        indicator.sum_tr = state.sum_tr;
+        indicator.output = state.output;
+        if state.evicted.is_some() && indicator.count != indicator.period {
+            return Err(TaError::InvalidState);
+        }
+        indicator.saved_value = state.evicted.unwrap_or((0.0, 0.0, 0.0));
+        indicator.saved_count = match state.evicted {
+            Some(_) => indicator.count,
+            None => indicator.count.saturating_sub(1),
+        };
+        indicator.saved_prev_high = state.before_last.map(|(high, _)| high);
+        indicator.saved_prev_low = state.before_last.map_or(0.0, |(_, low)| low);
+        indicator.saved_sums = state.previous_sums;
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(VortexIndicator, 2);
+
+impl SetPeriod for VortexIndicator {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for VortexIndicator {
+    type Output = VortexIndicatorOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.saved_value = (
+            self.plus_vm[self.index],
+            self.minus_vm[self.index],
+            self.tr[self.index],
+        );
+        self.saved_count = self.count;
+        self.saved_prev_high = self.prev_high;
+        self.saved_prev_low = self.prev_low;
+        self.saved_sums = (self.sum_plus_vm, self.sum_minus_vm, self.sum_tr);
+        let tr = self.true_range.next(input);
+        self.update(input, tr)
+    }
+}
+
+impl<T: High + Low + Close> Peek<&T> for VortexIndicator {
+    fn peek(&self, input: &T) -> VortexIndicatorOutput {
+        let prev_high = match self.prev_high {
+            Some(prev_high) => prev_high,
+            None => {
+                return VortexIndicatorOutput {
+                    plus: 0.0,
+                    minus: 0.0,
+                }
+            }
+        };
+        let plus_vm = (input.high() - self.prev_low).abs();
+        let minus_vm = (input.low() - prev_high).abs();
+
+        let sum_plus_vm = self.sum_plus_vm + (plus_vm - self.plus_vm[self.index]);
+        let sum_minus_vm = self.sum_minus_vm + (minus_vm - self.minus_vm[self.index]);
+        let sum_tr = self.sum_tr + (self.true_range.peek(input) - self.tr[self.index]);
+
+        if sum_tr > 0.0 {
+            VortexIndicatorOutput {
+                plus: sum_plus_vm / sum_tr,
+                minus: sum_minus_vm / sum_tr,
+            }
+        } else {
+            VortexIndicatorOutput {
+                plus: 0.0,
+                minus: 0.0,
+            }
+        }
+    }
+}
+
+impl<T: High + Low + Close> Amend<&T> for VortexIndicator {
+    fn amend(&mut self, input: &T) -> VortexIndicatorOutput {
+        if self.output.is_none() {
+            return self.next(input);
+        }
+        // The first bar doesn't enter the window
+        if self.saved_prev_high.is_some() {
+            self.index = (self.index + self.period - 1) % self.period;
+            (
+                self.plus_vm[self.index],
+                self.minus_vm[self.index],
+                self.tr[self.index],
+            ) = self.saved_value;
+            self.count = self.saved_count;
+            (self.sum_plus_vm, self.sum_minus_vm, self.sum_tr) = self.saved_sums;
+        }
+        self.prev_high = self.saved_prev_high;
+        self.prev_low = self.saved_prev_low;
+        let tr = self.true_range.amend(input);
+        self.update(input, tr)
+    }
+}
+
+impl Reset for VortexIndicator {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.prev_high = None;
+        self.prev_low = 0.0;
+        self.true_range.reset();
+        self.sum_plus_vm = 0.0;
+        self.sum_minus_vm = 0.0;
+        self.sum_tr = 0.0;
+        for i in 0..self.period {
+            self.plus_vm[i] = 0.0;
+            self.minus_vm[i] = 0.0;
+            self.tr[i] = 0.0;
+        }
+        self.output = None;
+        self.saved_value = (0.0, 0.0, 0.0);
+        self.saved_count = 0;
+        self.saved_prev_high = None;
+        self.saved_prev_low = 0.0;
+        self.saved_sums = (0.0, 0.0, 0.0);
+    }
+}
+
+impl Current for VortexIndicator {
+    type Output = VortexIndicatorOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for VortexIndicator {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Lookback for VortexIndicator {
+    fn lookback(&self) -> usize {
+        self.period + 1
+    }
+}
+
+impl Default for VortexIndicator {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for VortexIndicator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VI({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn round(nums: (f64, f64)) -> (f64, f64) {
+        let n0 = (nums.0 * 1000.0).round() / 1000.0;
+        let n1 = (nums.1 * 1000.0).round() / 1000.0;
+        (n0, n1)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(VortexIndicator::new(0).is_err());
+        assert!(VortexIndicator::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut vi = VortexIndicator::new(2).unwrap();
+
+        let bar1 = Bar::new().high(10).low(8).close(9);
+        let bar2 = Bar::new().high(12).low(9).close(11);
+        let bar3 = Bar::new().high(11).low(7).close(8);
+        let bar4 = Bar::new().high(11).low(11).close(11);
+
+        // No previous bar
+        assert_eq!(round(vi.next(&bar1).into()), (0.0, 0.0));
+        // +VM = 4, -VM = 1, TR = 3
+        assert_eq!(round(vi.next(&bar2).into()), (1.333, 0.333));
+        // +VM = 2, -VM = 5, TR = 4
+        assert_eq!(round(vi.next(&bar3).into()), (0.857, 0.857));
+        // +VM = 4, -VM = 0, TR = 3
+        assert_eq!(round(vi.next(&bar4).into()), (0.857, 0.714));
+    }
+
+    #[test]
+    fn test_next_flat_bars() {
+        let mut vi = VortexIndicator::new(3).unwrap();
+
+        let bar = Bar::new().high(5).low(5).close(5);
+        assert_eq!(round(vi.next(&bar).into()), (0.0, 0.0));
+        assert_eq!(round(vi.next(&bar).into()), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vi = VortexIndicator::new(2).unwrap();
+
+        let bar1 = Bar::new().high(10).low(8).close(9);
+        let bar2 = Bar::new().high(12).low(9).close(11);
+
+        vi.next(&bar1);
+        vi.next(&bar2);
+
+        vi.reset();
+        assert_eq!(round(vi.next(&bar1).into()), (0.0, 0.0));
+        assert_eq!(round(vi.next(&bar2).into()), (1.333, 0.333));
+    }
+
+    #[test]
+    fn test_first_ready_value() {
+        let mut vi = VortexIndicator::new(3).unwrap();
+        let bars = [
+            Bar::new().high(10).low(8).close(9),
+            Bar::new().high(12).low(9).close(11),
+            Bar::new().high(11).low(7).close(8),
+            Bar::new().high(13).low(10).close(12),
+        ];
+
+        for bar in &bars[..3] {
+            vi.next(bar);
+            assert!(!vi.is_ready());
+        }
+        let out = vi.next(&bars[3]);
+        assert!(vi.is_ready());
+        assert_eq!(vi.lookback(), 4);
+
+        // +VM = 4 + 2 + 6, -VM = 1 + 5 + 1, TR = 3 + 4 + 5, all from the second bar on
+        assert_eq!(round(out.into()), (1.0, 0.583));
+    }
+
+    #[test]
+    fn test_default() {
+        VortexIndicator::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let vi = VortexIndicator::new(14).unwrap();
+        assert_eq!(format!("{}", vi), "VI(14)");
+    }
+}
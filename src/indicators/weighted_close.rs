@@ -0,0 +1,149 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::{Amend, Checkpoint, Close, Current, High, IsReady, Lookback, Low, Next, Peek, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Weighted close (WC).
+///
+/// Turns a bar into a single price: the average of the high, the low and the close, with the close
+/// counted twice. It can be used as the input of indicators that take a single price, instead of
+/// the close.
+///
+/// # Formula
+///
+/// WC = (High + Low + 2 × Close) / 4
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::WeightedClose;
+/// use ta::{DataItem, Next};
+///
+/// let mut weighted = WeightedClose::new();
+///
+/// let bar = DataItem::builder()
+///     .open(9.0).high(12.0).low(6.0).close(9.0)
+///     .volume(1000.0)
+///     .build().unwrap();
+///
+/// assert_eq!(weighted.next(&bar), 9.0);
+/// ```
+#[doc(alias = "WC")]
+#[derive(Debug, Clone, Default)]
+pub struct WeightedClose {
+    output: Option<f64>,
+}
+
+/// State of [WeightedClose](struct.WeightedClose.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedCloseState {
+    /// Last output, `None` before the first input.
+    pub output: Option<f64>,
+}
+
+impl WeightedClose {
+    pub fn new() -> Self {
+        Self { output: None }
+    }
+}
+
+impl Checkpoint for WeightedClose {
+    type State = WeightedCloseState;
+
+    fn state(&self) -> Self::State {
+        WeightedCloseState {
+            output: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(Self {
+            output: state.output,
+        })
+    }
+}
+
+impl_versioned_serde!(WeightedClose, 1);
+
+impl<T: High + Low + Close> Next<&T> for WeightedClose {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let output = (input.high() + input.low() + 2.0 * input.close()) / 4.0;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low + Close> Peek<&T> for WeightedClose {
+    fn peek(&self, input: &T) -> f64 {
+        (input.high() + input.low() + 2.0 * input.close()) / 4.0
+    }
+}
+
+impl<T: High + Low + Close> Amend<&T> for WeightedClose {
+    fn amend(&mut self, input: &T) -> f64 {
+        // The output only depends on the input
+        self.next(input)
+    }
+}
+
+impl Reset for WeightedClose {
+    fn reset(&mut self) {
+        self.output = None;
+    }
+}
+
+impl Current for WeightedClose {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for WeightedClose {
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+impl Lookback for WeightedClose {
+    fn lookback(&self) -> usize {
+        1
+    }
+}
+
+impl fmt::Display for WeightedClose {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WC")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_next() {
+        let mut wc = WeightedClose::new();
+
+        assert_eq!(wc.next(&Bar::new().high(10).low(4).close(7)), 7.0);
+        assert_eq!(wc.next(&Bar::new().high(12).low(6).close(11)), 10.0);
+    }
+
+    #[test]
+    fn test_default() {
+        WeightedClose::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let wc = WeightedClose::new();
+        assert_eq!(format!("{}", wc), "WC");
+    }
+}
@@ -1,7 +1,10 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Numeric, Peek, Period, Reset,
+    SetPeriod,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -39,47 +42,72 @@ use serde::{Deserialize, Serialize};
 ///
 
 #[doc(alias = "WMA")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct WeightedMovingAverage {
+pub struct WeightedMovingAverage<N = f64> {
     period: usize,
     index: usize,
     count: usize,
-    weight: f64,
-    sum: f64,
-    sum_flat: f64,
-    deque: Box<[f64]>,
+    weight: N,
+    sum: N,
+    sum_flat: N,
+    deque: Box<[N]>,
+    output: Option<N>,
+    // State before the last input, restored by `amend`
+    saved_sum: N,
+    saved_sum_flat: N,
+    saved_count: usize,
+    saved_value: N,
+}
+
+/// State of [WeightedMovingAverage](generic/struct.WeightedMovingAverage.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedMovingAverageState<N = f64> {
+    pub period: usize,
+    /// Inputs in the window, the oldest first.
+    pub inputs: Vec<N>,
+    /// Input that the last input pushed out of the window, if the window was full.
+    pub evicted: Option<N>,
+    /// Running weighted sum of the window before the last input.
+    pub previous_weighted_sum: N,
+    /// Running sum of the window before the last input.
+    pub previous_sum: N,
 }
 
-impl WeightedMovingAverage {
+impl<N: Numeric> WeightedMovingAverage<N> {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::invalid_parameter("period", 0.0, ">= 1")),
             _ => Ok(Self {
                 period,
                 index: 0,
                 count: 0,
-                weight: 0.0,
-                sum: 0.0,
-                sum_flat: 0.0,
-                deque: vec![0.0; period].into_boxed_slice(),
+                weight: N::zero(),
+                sum: N::zero(),
+                sum_flat: N::zero(),
+                deque: vec![N::zero(); period].into_boxed_slice(),
+                output: None,
+                saved_sum: N::zero(),
+                saved_sum_flat: N::zero(),
+                saved_count: 0,
+                saved_value: N::zero(),
             }),
         }
     }
-}
 
-impl Period for WeightedMovingAverage {
-    fn period(&self) -> usize {
-        self.period
+    /// Inputs in the window, the oldest first.
+    fn inputs(&self) -> impl Iterator<Item = N> + '_ {
+        let oldest = self.index + self.period - self.count;
+        (0..self.count).map(move |i| self.deque[(oldest + i) % self.period])
     }
-}
 
-impl Next<f64> for WeightedMovingAverage {
-    type Output = f64;
-
-    fn next(&mut self, input: f64) -> Self::Output {
-        let old_val: f64 = self.deque[self.index];
+    fn next_value(&mut self, input: N) -> N {
+        let old_val = self.deque[self.index];
         self.deque[self.index] = input;
+        self.saved_sum = self.sum;
+        self.saved_sum_flat = self.sum_flat;
+        self.saved_count = self.count;
+        self.saved_value = old_val;
 
         self.index = if self.index + 1 < self.period {
             self.index + 1
@@ -89,44 +117,172 @@ impl Next<f64> for WeightedMovingAverage {
 
         if self.count < self.period {
             self.count += 1;
-            self.weight = self.count as f64;
+            self.weight = N::from_count(self.count);
             self.sum += input * self.weight
         } else {
             self.sum = self.sum - self.sum_flat + (input * self.weight);
         }
         self.sum_flat = self.sum_flat - old_val + input;
-        self.sum / (self.weight * (self.weight + 1.0) / 2.0)
+        let output = self.sum / (self.weight * (self.weight + N::one()) / N::from_count(2));
+        self.output = Some(output);
+        output
+    }
+
+    fn peek_value(&self, input: N) -> N {
+        let (sum, weight) = if self.count < self.period {
+            let weight = N::from_count(self.count + 1);
+            (self.sum + input * weight, weight)
+        } else {
+            (self.sum - self.sum_flat + input * self.weight, self.weight)
+        };
+        sum / (weight * (weight + N::one()) / N::from_count(2))
+    }
+
+    fn amend_value(&mut self, input: N) -> N {
+        if self.output.is_none() {
+            return self.next_value(input);
+        }
+        self.index = (self.index + self.period - 1) % self.period;
+        self.deque[self.index] = self.saved_value;
+        self.sum = self.saved_sum;
+        self.sum_flat = self.saved_sum_flat;
+        self.count = self.saved_count;
+        // The weight of the last input is always the number of inputs in the window
+        self.weight = N::from_count(self.count);
+        self.next_value(input)
     }
 }
 
-impl<T: Close> Next<&T> for WeightedMovingAverage {
-    type Output = f64;
+impl<N: Numeric> Period for WeightedMovingAverage<N> {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl<N: Numeric> Checkpoint for WeightedMovingAverage<N> {
+    type State = WeightedMovingAverageState<N>;
+
+    fn state(&self) -> Self::State {
+        WeightedMovingAverageState {
+            period: self.period,
+            inputs: self.inputs().collect(),
+            evicted: (self.count > 0 && self.saved_count == self.period)
+                .then_some(self.saved_value),
+            previous_weighted_sum: self.saved_sum,
+            previous_sum: self.saved_sum_flat,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let count = state.inputs.len();
+        if state.period == 0
+            || count > state.period
+            || (state.evicted.is_some() && count != state.period)
+            || (count == 0
+                && (state.previous_weighted_sum != N::zero() || state.previous_sum != N::zero()))
+        {
+            return Err(TaError::InvalidState);
+        }
+        // Rebuild the window before the last input and feed the last input again, so the
+        // running sums continue exactly and the last input can be amended
+        let mut indicator = Self::new(state.period)?;
+        if let Some((&last, previous)) = state.inputs.split_last() {
+            let window = state.evicted.iter().chain(previous);
+            for (slot, &input) in indicator.deque.iter_mut().zip(window) {
+                *slot = input;
+            }
+            indicator.count = previous.len() + state.evicted.iter().count();
+            indicator.index = indicator.count % indicator.period;
+            indicator.weight = N::from_count(indicator.count);
+            indicator.sum = state.previous_weighted_sum;
+            indicator.sum_flat = state.previous_sum;
+            indicator.next_value(last);
+        }
+        Ok(indicator)
+    }
+}
+
+impl_versioned_serde!(WeightedMovingAverage<N>, 1);
+
+impl<N: Numeric> SetPeriod for WeightedMovingAverage<N> {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        // Replay the latest inputs that fit in the new window
+        let mut resized = Self::new(period)?;
+        for input in self.inputs().skip(self.count.saturating_sub(period)) {
+            resized.next_value(input);
+        }
+        *self = resized;
+        Ok(())
+    }
+}
+
+impl_numeric_inputs!(WeightedMovingAverage);
+
+impl<N: Numeric, T: Close<N>> Next<&T> for WeightedMovingAverage<N> {
+    type Output = N;
 
     fn next(&mut self, input: &T) -> Self::Output {
-        self.next(input.close())
+        self.next_value(input.close())
     }
 }
 
-impl Reset for WeightedMovingAverage {
+impl<N: Numeric, T: Close<N>> Peek<&T> for WeightedMovingAverage<N> {
+    fn peek(&self, input: &T) -> N {
+        self.peek_value(input.close())
+    }
+}
+
+impl<N: Numeric, T: Close<N>> Amend<&T> for WeightedMovingAverage<N> {
+    fn amend(&mut self, input: &T) -> N {
+        self.amend_value(input.close())
+    }
+}
+
+impl<N: Numeric> Reset for WeightedMovingAverage<N> {
     fn reset(&mut self) {
         self.index = 0;
         self.count = 0;
-        self.weight = 0.0;
-        self.sum = 0.0;
-        self.sum_flat = 0.0;
+        self.weight = N::zero();
+        self.sum = N::zero();
+        self.sum_flat = N::zero();
         for i in 0..self.period {
-            self.deque[i] = 0.0;
+            self.deque[i] = N::zero();
         }
+        self.output = None;
+        self.saved_sum = N::zero();
+        self.saved_sum_flat = N::zero();
+        self.saved_count = 0;
+        self.saved_value = N::zero();
+    }
+}
+
+impl<N: Numeric> Current for WeightedMovingAverage<N> {
+    type Output = N;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<N: Numeric> IsReady for WeightedMovingAverage<N> {
+    fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl<N: Numeric> Lookback for WeightedMovingAverage<N> {
+    fn lookback(&self) -> usize {
+        self.period
     }
 }
 
-impl Default for WeightedMovingAverage {
+impl<N: Numeric> Default for WeightedMovingAverage<N> {
     fn default() -> Self {
         Self::new(9).unwrap()
     }
 }
 
-impl fmt::Display for WeightedMovingAverage {
+impl<N: Numeric> fmt::Display for WeightedMovingAverage<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "WMA({})", self.period)
     }
@@ -135,6 +291,7 @@ impl fmt::Display for WeightedMovingAverage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::WeightedMovingAverage;
     use crate::test_helper::*;
 
     test_indicator!(WeightedMovingAverage);
@@ -161,6 +318,18 @@ mod tests {
         assert_eq!(wma.next(&bar2), 4.0);
     }
 
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_next_decimal() {
+        use crate::indicators::generic::WeightedMovingAverage;
+        use rust_decimal::Decimal;
+
+        let mut wma = WeightedMovingAverage::<Decimal>::new(3).unwrap();
+        assert_eq!(wma.next(Decimal::new(3, 1)), Decimal::new(3, 1));
+        assert_eq!(wma.next(Decimal::new(6, 1)), Decimal::new(5, 1));
+        assert_eq!(wma.next(Decimal::new(9, 1)), Decimal::new(7, 1));
+    }
+
     #[test]
     fn test_reset() {
         let mut wma = WeightedMovingAverage::new(5).unwrap();
@@ -0,0 +1,287 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{SwingPoints, SwingPointsOutput, SwingPointsState};
+use crate::{
+    Amend, Checkpoint, Current, High, IsReady, Lookback, Low, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Williams Fractals.
+///
+/// Developed by Bill Williams, a fractal marks a local turning point: a bar whose high is
+/// higher (bearish fractal) or whose low is lower (bullish fractal) than the highs or lows of
+/// _period_ bars on each side. These are [swing points](struct.SwingPoints.html) with the
+/// same number of bars on both sides.
+///
+/// A fractal can only be confirmed once _period_ bars after it are known, so the output refers
+/// to the bar _bars_ago_ bars before the current one, which is always equal to _period_.
+///
+/// # Formula
+///
+/// Bearish fractal at bar _t_: High<sub>t</sub> > High<sub>t±i</sub> for every _i_ in 1.._period_
+///
+/// Bullish fractal at bar _t_: Low<sub>t</sub> < Low<sub>t±i</sub> for every _i_ in 1.._period_
+///
+/// # Parameters
+///
+/// * _period_ - number of bars on each side of the fractal (integer greater than 0). Default is 2,
+///   which gives the classic 5 bar pattern.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::WilliamsFractals;
+/// use ta::{Next, DataItem};
+///
+/// fn bar(high: f64, low: f64) -> DataItem {
+///     DataItem::builder()
+///         .high(high).low(low).close(low).open(low)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut fractals = WilliamsFractals::new(1).unwrap();
+///
+/// fractals.next(&bar(10.0, 8.0));
+/// fractals.next(&bar(12.0, 9.0));
+/// let out = fractals.next(&bar(11.0, 7.0));
+///
+/// // the high of the previous bar is a bearish fractal
+/// assert_eq!(out.bearish, Some(12.0));
+/// assert_eq!(out.bullish, None);
+/// assert_eq!(out.bars_ago, 1);
+/// ```
+///
+/// # Links
+///
+/// * [Fractal, Investopedia](https://www.investopedia.com/terms/f/fractal.asp)
+///
+#[doc(alias = "FRACTALS")]
+#[derive(Debug, Clone)]
+pub struct WilliamsFractals {
+    swings: SwingPoints,
+    output: Option<WilliamsFractalsOutput>,
+}
+
+/// State of [WilliamsFractals](struct.WilliamsFractals.html), see
+/// [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct WilliamsFractalsState {
+    pub swings: SwingPointsState,
+}
+
+/// Output of [WilliamsFractals](struct.WilliamsFractals.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct WilliamsFractalsOutput {
+    /// Low of the bullish fractal, if one was confirmed.
+    pub bullish: Option<f64>,
+    /// High of the bearish fractal, if one was confirmed.
+    pub bearish: Option<f64>,
+    /// Number of bars between the fractal bar and the current bar.
+    pub bars_ago: usize,
+}
+
+impl_output!(WilliamsFractalsOutput, bullish, bearish, bars_ago);
+
+impl WilliamsFractals {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            swings: SwingPoints::new(period, period)?,
+            output: None,
+        })
+    }
+
+    fn output(swings: SwingPointsOutput) -> WilliamsFractalsOutput {
+        WilliamsFractalsOutput {
+            bullish: swings.low.map(|low| low.price),
+            bearish: swings.high.map(|high| high.price),
+            bars_ago: swings.bars_ago,
+        }
+    }
+}
+
+impl Period for WilliamsFractals {
+    fn period(&self) -> usize {
+        self.swings.right()
+    }
+}
+
+impl Checkpoint for WilliamsFractals {
+    type State = WilliamsFractalsState;
+
+    fn state(&self) -> Self::State {
+        WilliamsFractalsState {
+            swings: self.swings.state(),
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.swings.left != state.swings.right {
+            return Err(TaError::InvalidState);
+        }
+        let swings = SwingPoints::from_state(state.swings)?;
+        Ok(Self {
+            output: swings.current().map(Self::output),
+            swings,
+        })
+    }
+}
+
+impl_versioned_serde!(WilliamsFractals, 2);
+
+impl SetPeriod for WilliamsFractals {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl<T: High + Low> Next<&T> for WilliamsFractals {
+    type Output = WilliamsFractalsOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let output = Self::output(self.swings.next(input));
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl<T: High + Low> Peek<&T> for WilliamsFractals {
+    fn peek(&self, input: &T) -> WilliamsFractalsOutput {
+        Self::output(self.swings.peek(input))
+    }
+}
+
+impl<T: High + Low> Amend<&T> for WilliamsFractals {
+    fn amend(&mut self, input: &T) -> WilliamsFractalsOutput {
+        let output = Self::output(self.swings.amend(input));
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Reset for WilliamsFractals {
+    fn reset(&mut self) {
+        self.swings.reset();
+        self.output = None;
+    }
+}
+
+impl Current for WilliamsFractals {
+    type Output = WilliamsFractalsOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl IsReady for WilliamsFractals {
+    fn is_ready(&self) -> bool {
+        self.swings.is_ready()
+    }
+}
+
+impl Lookback for WilliamsFractals {
+    fn lookback(&self) -> usize {
+        self.swings.lookback()
+    }
+}
+
+impl Default for WilliamsFractals {
+    fn default() -> Self {
+        Self::new(2).unwrap()
+    }
+}
+
+impl fmt::Display for WilliamsFractals {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FRACTALS({})", self.swings.right())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn fractals(out: WilliamsFractalsOutput) -> (Option<f64>, Option<f64>) {
+        (out.bullish, out.bearish)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(WilliamsFractals::new(0).is_err());
+        assert!(WilliamsFractals::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut wf = WilliamsFractals::new(2).unwrap();
+
+        let bars = [
+            Bar::new().high(10).low(8),
+            Bar::new().high(11).low(9),
+            Bar::new().high(13).low(10),
+            Bar::new().high(12).low(9),
+            Bar::new().high(11).low(7),
+            Bar::new().high(10).low(8),
+            Bar::new().high(12).low(9),
+        ];
+
+        // not enough bars yet
+        for bar in &bars[..4] {
+            assert_eq!(fractals(wf.next(bar)), (None, None));
+        }
+        // bearish fractal at the high of 13, two bars ago
+        let out = wf.next(&bars[4]);
+        assert_eq!(fractals(out.clone()), (None, Some(13.0)));
+        assert_eq!(out.bars_ago, 2);
+
+        assert_eq!(fractals(wf.next(&bars[5])), (None, None));
+        // bullish fractal at the low of 7
+        assert_eq!(fractals(wf.next(&bars[6])), (Some(7.0), None));
+    }
+
+    #[test]
+    fn test_next_equal_highs() {
+        let mut wf = WilliamsFractals::new(1).unwrap();
+
+        wf.next(&Bar::new().high(12).low(9));
+        wf.next(&Bar::new().high(12).low(9));
+        assert_eq!(
+            fractals(wf.next(&Bar::new().high(11).low(10))),
+            (None, None)
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut wf = WilliamsFractals::new(1).unwrap();
+
+        wf.next(&Bar::new().high(10).low(8));
+        wf.next(&Bar::new().high(12).low(9));
+
+        wf.reset();
+        assert_eq!(fractals(wf.next(&Bar::new().high(11).low(7))), (None, None));
+        assert_eq!(fractals(wf.next(&Bar::new().high(12).low(9))), (None, None));
+        assert_eq!(
+            fractals(wf.next(&Bar::new().high(10).low(8))),
+            (None, Some(12.0))
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        WilliamsFractals::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let wf = WilliamsFractals::new(2).unwrap();
+        assert_eq!(format!("{}", wf), "FRACTALS(2)");
+    }
+}
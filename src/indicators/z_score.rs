@@ -0,0 +1,267 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{StandardDeviation as Sd, StandardDeviationState};
+use crate::{
+    Amend, Checkpoint, Close, Current, IsReady, Lookback, Next, Peek, Period, Reset, SetPeriod,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Z-Score (ZS).
+///
+/// Number of standard deviations the current value is away from its moving average.
+/// It is a common mean reversion signal, closely related to
+/// [Bollinger Bands](type.BollingerBands.html).
+///
+/// # Formula
+///
+/// ZS = (p - SMA(p, _period_)) / SD(p, _period_)
+///
+/// Where:
+///
+/// * _p_ - input value
+/// * _SD_ - [standard deviation](type.StandardDeviation.html)
+///
+/// When the standard deviation is 0, the Z-Score is 0.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::ZScore;
+/// use ta::Next;
+///
+/// let mut zs = ZScore::new(3).unwrap();
+/// assert_eq!(zs.next(2.0), 0.0);
+/// assert_eq!(zs.next(5.0), 1.0);
+/// ```
+///
+/// # Links
+///
+/// * [Standard score, Wikipedia](https://en.wikipedia.org/wiki/Standard_score)
+///
+#[doc(alias = "ZS")]
+#[derive(Debug, Clone)]
+pub struct ZScore {
+    sd: Sd,
+    output: Option<f64>,
+}
+
+/// State of [ZScore](struct.ZScore.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZScoreState {
+    pub sd: StandardDeviationState,
+    pub output: Option<f64>,
+}
+
+impl ZScore {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            sd: Sd::new(period)?,
+            output: None,
+        })
+    }
+}
+
+impl Period for ZScore {
+    fn period(&self) -> usize {
+        self.sd.period()
+    }
+}
+
+impl Checkpoint for ZScore {
+    type State = ZScoreState;
+
+    fn state(&self) -> Self::State {
+        ZScoreState {
+            sd: self.sd.state(),
+            output: self.output,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.output.is_none() != state.sd.inputs.is_empty() {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            sd: Sd::from_state(state.sd)?,
+            output: state.output,
+        })
+    }
+}
+
+impl_versioned_serde!(ZScore, 1);
+
+impl SetPeriod for ZScore {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        *self = Self::new(period)?;
+        Ok(())
+    }
+}
+
+impl Next<f64> for ZScore {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let sd = self.sd.next(input);
+
+        let output = if sd > 0.0 {
+            (input - self.sd.mean()) / sd
+        } else {
+            0.0
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for ZScore {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Peek<f64> for ZScore {
+    fn peek(&self, input: f64) -> f64 {
+        let (sd, mean) = self.sd.peek_with_mean(input);
+        if sd > 0.0 {
+            (input - mean) / sd
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<T: Close> Peek<&T> for ZScore {
+    fn peek(&self, input: &T) -> f64 {
+        self.peek(input.close())
+    }
+}
+
+impl Amend<f64> for ZScore {
+    fn amend(&mut self, input: f64) -> f64 {
+        let sd = self.sd.amend(input);
+        let output = if sd > 0.0 {
+            (input - self.sd.mean()) / sd
+        } else {
+            0.0
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Amend<&T> for ZScore {
+    fn amend(&mut self, input: &T) -> f64 {
+        self.amend(input.close())
+    }
+}
+
+impl Reset for ZScore {
+    fn reset(&mut self) {
+        self.sd.reset();
+        self.output = None;
+    }
+}
+
+impl Current for ZScore {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl IsReady for ZScore {
+    fn is_ready(&self) -> bool {
+        self.sd.is_ready()
+    }
+}
+
+impl Lookback for ZScore {
+    fn lookback(&self) -> usize {
+        self.sd.lookback()
+    }
+}
+
+impl Default for ZScore {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for ZScore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ZS({})", self.sd.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(ZScore);
+
+    #[test]
+    fn test_new() {
+        assert!(ZScore::new(0).is_err());
+        assert!(ZScore::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut zs = ZScore::new(3).unwrap();
+
+        assert_eq!(zs.next(2.0), 0.0);
+        assert_eq!(round(zs.next(5.0)), 1.0);
+        assert_eq!(round(zs.next(1.0)), -0.981);
+        assert_eq!(round(zs.next(6.25)), 0.968);
+    }
+
+    #[test]
+    fn test_next_flat() {
+        let mut zs = ZScore::new(3).unwrap();
+
+        assert_eq!(zs.next(4.0), 0.0);
+        assert_eq!(zs.next(4.0), 0.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut zs = ZScore::new(3).unwrap();
+
+        assert_eq!(zs.next(&Bar::new().close(2)), 0.0);
+        assert_eq!(round(zs.next(&Bar::new().close(5))), 1.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut zs = ZScore::new(3).unwrap();
+
+        zs.next(2.0);
+        zs.next(5.0);
+
+        zs.reset();
+        assert_eq!(zs.next(5.0), 0.0);
+        assert_eq!(round(zs.next(2.0)), -1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        ZScore::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let zs = ZScore::new(20).unwrap();
+        assert_eq!(format!("{}", zs), "ZS(20)");
+    }
+}
@@ -5,7 +5,14 @@
 //! state.
 //!
 //! Every indicator implements [Next<T>](trait.Next.html) and [Reset](trait.Reset.html) traits,
-//! which are the core concept of the library.
+//! which are the core concept of the library. Indicators also implement
+//! [IsReady](trait.IsReady.html), so [TryNext<T>](trait.TryNext.html) can tell warm-up values
+//! apart from fully formed ones, and [Lookback](trait.Lookback.html), which tells how many
+//! inputs that takes. [Current](trait.Current.html) returns the last output, e.g. to read an
+//! indicator fed by another part of a program, and many implement [Peek](trait.Peek.html),
+//! which calculates the output for an input without feeding it, and [Amend](trait.Amend.html),
+//! which replaces the last input.
+//! [Compose](trait.Compose.html) composes them into new indicators, e.g. an EMA of an RSI.
 //!
 //! Since `Next<T>` is a generic trait, most of the indicators can work with both input types: `f64` and more complex
 //! structures like [DataItem](struct.DataItem.html).
@@ -24,45 +31,216 @@
 //! assert_eq!(ema.next(6.25), 4.25);
 //! ```
 //!
+//! # Outputs
+//!
+//! Indicators with more than one value return a named output struct, e.g.
+//! [BollingerBandsOutput](crate::indicators::BollingerBandsOutput) for
+//! [BollingerBands](crate::indicators::BollingerBands). Output structs implement `Debug`, `Clone`,
+//! `PartialEq` and `Display`, and `Serialize`/`Deserialize` with the `serde` feature. Their fields
+//! follow the same naming conventions:
+//!
+//! * Bands and channels have `upper`, `middle` and `lower` fields. The center line of
+//!   Bollinger Bands and Keltner Channel is named `average` instead.
+//! * An indicator line is named after the indicator (e.g. `macd`), its moving average is
+//!   `signal` and the difference of both is `histogram`.
+//! * Events that may or may not happen on a bar are `Option` fields.
+//!
+//! Every output also converts into an [OutputValue](enum.OutputValue.html), which lets
+//! indicators with different outputs be used through the [DynIndicator](trait.DynIndicator.html)
+//! trait object, e.g. in a `Vec<Box<dyn DynIndicator>>` configured at runtime. The
+//! [factory](factory/index.html) creates them from names and parameters, and with the `dsl`
+//! feature [dsl](dsl/index.html) composes them from expressions like `ema(rsi(close, 14), 9)`.
+//!
+//! # Value types
+//!
+//! The basic moving averages, [Maximum](crate::indicators::Maximum),
+//! [Minimum](crate::indicators::Minimum), [StandardDeviation](crate::indicators::StandardDeviation),
+//! [BollingerBands](crate::indicators::BollingerBands), RSI, TrueRange and ATR are generic over
+//! their [value type](trait.Numeric.html) in [indicators::generic](indicators/generic/index.html).
+//! Next to `f64` they run on `f32` and, with the `decimal` feature, on `rust_decimal::Decimal`,
+//! which keeps decimal prices exact, e.g. when a sum is divided by the period.
+//!
+//! # no_std
+//!
+//! With `default-features = false` the library does not depend on `std` and does not allocate,
+//! e.g. to run on a microcontroller. Only indicators that need neither heap allocated buffers
+//! nor floating point functions like `sqrt` or `ln` are available then, such as
+//! [EMA](crate::indicators::ExponentialMovingAverage),
+//! [RSI](crate::indicators::RelativeStrengthIndex),
+//! [ATR](crate::indicators::AverageTrueRange) and
+//! [MACD](crate::indicators::MovingAverageConvergenceDivergence). Indicators over a window of
+//! inputs, like [SMA](crate::indicators::SimpleMovingAverage), as well as
+//! [NextBatch](trait.NextBatch.html), require the `std` feature, but some of them have variants
+//! with a period known at compile time that keep their inputs on the stack, e.g.
+//! [SimpleMovingAverageConst](crate::indicators::SimpleMovingAverageConst).
+//!
 //! # List of indicators
 //!
 //! * Trend
 //!   * [Exponential Moving Average (EMA)](crate::indicators::ExponentialMovingAverage)
 //!   * [Simple Moving Average (SMA)](crate::indicators::SimpleMovingAverage)
 //!   * [Weighted Moving Average (WMA)](crate::indicators::WeightedMovingAverage)
+//!   * [Triple Exponential Moving Average (TEMA)](crate::indicators::TripleExponentialMovingAverage)
+//!   * [Kaufman's Adaptive Moving Average (KAMA)](crate::indicators::KaufmanAdaptiveMovingAverage)
+//!   * [Volume Weighted Average Price (VWAP)](crate::indicators::VolumeWeightedAveragePrice)
+//!   * [Anchored Volume Weighted Average Price (AVWAP)](crate::indicators::AnchoredVwap)
+//!   * [McGinley Dynamic (MD)](crate::indicators::McGinleyDynamic)
+//!   * [Fractal Adaptive Moving Average (FRAMA)](crate::indicators::FractalAdaptiveMovingAverage)
+//!   * [Tillson T3 Moving Average (T3)](crate::indicators::TillsonT3)
+//!   * [MESA Adaptive Moving Average (MAMA)](crate::indicators::MesaAdaptiveMovingAverage)
+//!   * [Volume Weighted Moving Average (VWMA)](crate::indicators::VolumeWeightedMovingAverage)
+//!   * [Moving Average Ribbon](crate::indicators::MovingAverageRibbon)
+//!   * [Williams Alligator (ALLIGATOR)](crate::indicators::Alligator)
+//!   * [Laguerre Filter (LAGUERRE)](crate::indicators::LaguerreFilter)
+//!   * [Ehlers Super Smoother (SSF)](crate::indicators::SuperSmoother)
+//!   * [Moving Average with selectable type (MA)](crate::indicators::MovingAverage)
+//!   * [Triangular Moving Average (TMA)](crate::indicators::TriangularMovingAverage)
+//!   * [Geometric Moving Average (GMA)](crate::indicators::GeometricMovingAverage)
+//!   * [Harmonic Moving Average](crate::indicators::HarmonicMovingAverage)
+//!   * [Simple Moving Average with a period known at compile time](crate::indicators::SimpleMovingAverageConst)
 //! * Oscillators
-//!   * [Relative Strength Index (RSI)](indicators/struct.RelativeStrengthIndex.html)
+//!   * [Relative Strength Index (RSI)](indicators/type.RelativeStrengthIndex.html)
 //!   * [Fast Stochastic](indicators/struct.FastStochastic.html)
 //!   * [Slow Stochastic](indicators/struct.SlowStochastic.html)
 //!   * [Moving Average Convergence Divergence (MACD)](indicators/struct.MovingAverageConvergenceDivergence.html)
 //!   * [Percentage Price Oscillator (PPO)](indicators/struct.PercentagePriceOscillator.html)
 //!   * [Commodity Channel Index (CCI)](indicators/struct.CommodityChannelIndex.html)
 //!   * [Money Flow Index (MFI)](indicators/struct.MoneyFlowIndex.html)
+//!   * [Vortex Indicator (VI)](crate::indicators::VortexIndicator)
+//!   * [Triple Exponential Average (TRIX)](crate::indicators::Trix)
+//!   * [Ultimate Oscillator (UO)](crate::indicators::UltimateOscillator)
+//!   * [Awesome Oscillator (AO)](crate::indicators::AwesomeOscillator)
+//!   * [Accelerator Oscillator (AC)](crate::indicators::AcceleratorOscillator)
+//!   * [Chaikin Money Flow (CMF)](crate::indicators::ChaikinMoneyFlow)
+//!   * [Force Index (FI)](crate::indicators::ForceIndex)
+//!   * [Elder Ray Index (ERI)](crate::indicators::ElderRay)
+//!   * [Detrended Price Oscillator (DPO)](crate::indicators::DetrendedPriceOscillator)
+//!   * [Connors RSI (CRSI)](crate::indicators::ConnorsRsi)
+//!   * [True Strength Index (TSI)](crate::indicators::TrueStrengthIndex)
+//!   * [Fisher Transform (FT)](crate::indicators::FisherTransform)
+//!   * [Ease of Movement (EOM)](crate::indicators::EaseOfMovement)
+//!   * [Balance of Power (BOP)](crate::indicators::BalanceOfPower)
+//!   * [Relative Vigor Index (RVI)](crate::indicators::RelativeVigorIndex)
+//!   * [Quantitative Qualitative Estimation (QQE)](crate::indicators::Qqe)
+//!   * [Squeeze Momentum (TTM Squeeze)](crate::indicators::SqueezeMomentum)
+//!   * [Chande Momentum Oscillator (CMO)](crate::indicators::ChandeMomentumOscillator)
+//!   * [Stochastic Momentum Index (SMI)](crate::indicators::StochasticMomentumIndex)
+//!   * [Percentage Volume Oscillator (PVO)](crate::indicators::PercentageVolumeOscillator)
+//!   * [Volume Oscillator (VO)](crate::indicators::VolumeOscillator)
+//!   * [Volume Rate of Change (VROC)](crate::indicators::VolumeRateOfChange)
+//!   * [Gator Oscillator (GATOR)](crate::indicators::GatorOscillator)
+//!   * [Laguerre RSI (LRSI)](crate::indicators::LaguerreRsi)
+//!   * [Ehlers Roofing Filter (ROOF)](crate::indicators::RoofingFilter)
+//!   * [Center of Gravity (CG)](crate::indicators::CenterOfGravity)
+//!   * [Disparity Index (DISPARITY)](crate::indicators::DisparityIndex)
+//!   * [Trend Intensity Index (TII)](crate::indicators::TrendIntensityIndex)
+//!   * [Twiggs Money Flow (TMF)](crate::indicators::TwiggsMoneyFlow)
 //! * Other
-//!   * [Standard Deviation (SD)](indicators/struct.StandardDeviation.html)
+//!   * [Standard Deviation (SD)](indicators/type.StandardDeviation.html)
 //!   * [Mean Absolute Deviation (MAD)](indicators/struct.MeanAbsoluteDeviation.html)
-//!   * [Bollinger Bands (BB)](indicators/struct.BollingerBands.html)
+//!   * [Bollinger Bands (BB)](indicators/type.BollingerBands.html)
 //!   * [Chandelier Exit (CE)](indicators/struct.ChandelierExit.html)
 //!   * [Keltner Channel (KC)](indicators/struct.KeltnerChannel.html)
-//!   * [Maximum](indicators/struct.Maximum.html)
-//!   * [Minimum](indicators/struct.Minimum.html)
-//!   * [True Range](indicators/struct.TrueRange.html)
-//!   * [Average True Range (ATR)](indicators/struct.AverageTrueRange.html)
+//!   * [Maximum](indicators/type.Maximum.html)
+//!   * [Minimum](indicators/type.Minimum.html)
+//!   * [True Range](indicators/type.TrueRange.html)
+//!   * [Average True Range (ATR)](indicators/type.AverageTrueRange.html)
 //!   * [Efficiency Ratio (ER)](indicators/struct.EfficiencyRatio.html)
 //!   * [Rate of Change (ROC)](indicators/struct.RateOfChange.html)
 //!   * [On Balance Volume (OBV)](indicators/struct.OnBalanceVolume.html)
+//!   * [Donchian Channel (DC)](crate::indicators::DonchianChannel)
+//!   * [Accumulation/Distribution Line (ADL)](crate::indicators::AccumulationDistributionLine)
+//!   * [Choppiness Index (CHOP)](crate::indicators::ChoppinessIndex)
+//!   * [Price Volume Trend (PVT)](crate::indicators::PriceVolumeTrend)
+//!   * [Linear Regression (LINREG)](crate::indicators::LinearRegression)
+//!   * [Correlation (CORR)](crate::indicators::Correlation)
+//!   * [Beta (BETA)](crate::indicators::Beta)
+//!   * [Z-Score (ZS)](crate::indicators::ZScore)
+//!   * [Rolling Quantile (QUANTILE)](crate::indicators::RollingQuantile)
+//!   * [Dominant Cycle Period (DCPERIOD)](crate::indicators::DominantCyclePeriod)
+//!   * [Williams Fractals](crate::indicators::WilliamsFractals)
+//!   * [ATR Trailing Stop](crate::indicators::AtrTrailingStop)
+//!   * [Negative Volume Index (NVI)](crate::indicators::NegativeVolumeIndex)
+//!   * [Positive Volume Index (PVI)](crate::indicators::PositiveVolumeIndex)
+//!   * [Historical Volatility (HV)](crate::indicators::HistoricalVolatility)
+//!   * [Range Volatility (Parkinson, Garman-Klass, Rogers-Satchell, Yang-Zhang)](crate::indicators::RangeVolatility)
+//!   * [Sortino Ratio](crate::indicators::SortinoRatio)
+//!   * [Drawdown Statistics and Calmar Ratio](crate::indicators::DrawdownStats)
+//!   * [Value at Risk (VaR) and Conditional Value at Risk (CVaR)](crate::indicators::ValueAtRisk)
+//!   * [Simple Return](crate::indicators::SimpleReturn)
+//!   * [Log Return](crate::indicators::LogReturn)
+//!   * [Cumulative Return](crate::indicators::CumulativeReturn)
+//!   * [Divergence Detector](crate::indicators::DivergenceDetector)
+//!   * [Inside/Outside Bar (IOB)](crate::indicators::InsideOutsideBar)
+//!   * [Swing Points](crate::indicators::SwingPoints)
+//!   * [Support and Resistance zones (SR)](crate::indicators::SupportResistance)
+//!   * [Volume Profile (VP)](crate::indicators::VolumeProfile)
+//!   * [Multi-timeframe wrapper (MTF)](crate::indicators::MultiTimeframe)
+//!   * [Session-aware reset](crate::indicators::SessionReset)
+//!   * [Applied price source (SOURCED)](crate::indicators::Sourced)
+//!   * [Typical Price (TP)](crate::indicators::TypicalPrice)
+//!   * [Median Price (MP)](crate::indicators::MedianPrice)
+//!   * [Weighted Close (WC)](crate::indicators::WeightedClose)
+//!   * [Market Facilitation Index (BW MFI)](crate::indicators::MarketFacilitationIndex)
+//!   * [Standard Error Bands (SEB)](crate::indicators::StandardErrorBands)
+//!   * [Acceleration Bands (ABANDS)](crate::indicators::AccelerationBands)
+//!   * [Autocorrelation (AUTOCORR)](crate::indicators::Autocorrelation)
+//!   * [Rolling Entropy (ENTROPY)](crate::indicators::RollingEntropy)
+//!   * [Spread Z-Score (SPREADZ)](crate::indicators::SpreadZScore)
+//!   * [Fibonacci Levels (FIB)](crate::indicators::FibonacciLevels)
+//!   * [Fractal Dimension Index (FDI)](crate::indicators::FractalDimensionIndex)
+//!   * [Maximum with a period known at compile time](crate::indicators::MaximumConst)
+//!   * [Minimum with a period known at compile time](crate::indicators::MinimumConst)
+//! * Transforms
+//!   * [Heikin-Ashi (HA)](crate::transforms::HeikinAshi)
+//!   * [Renko bricks (RENKO)](crate::transforms::RenkoBuilder)
+//! * Bars
+//!   * [Tick bars](crate::bars::TickBars)
+//!   * [Volume bars](crate::bars::VolumeBars)
+//!   * [Dollar bars](crate::bars::DollarBars)
+//!   * [Range bars](crate::bars::RangeBars)
+//!   * [Time-based resampler](crate::bars::Resampler)
+//! * Patterns
+//!   * [Doji](crate::patterns::Doji)
+//!   * [Hammer](crate::patterns::Hammer)
+//!   * [Shooting Star](crate::patterns::ShootingStar)
+//!   * [Bullish/Bearish Engulfing](crate::patterns::BullishEngulfing)
+//!   * [Bullish/Bearish Harami](crate::patterns::BullishHarami)
+//!   * [Morning/Evening Star](crate::patterns::MorningStar)
+//!   * [Three White Soldiers/Three Black Crows](crate::patterns::ThreeWhiteSoldiers)
 //!
+#![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
+
 #[cfg(test)]
 #[macro_use]
 mod test_helper;
 
+#[macro_use]
 mod helpers;
 
+pub mod bars;
+pub mod combinators;
+#[cfg(feature = "dsl")]
+pub mod dsl;
 pub mod errors;
+#[cfg(feature = "std")]
+pub mod factory;
 pub mod indicators;
+pub mod patterns;
+pub mod transforms;
 
 mod traits;
 pub use crate::traits::*;
 
+mod numeric;
+pub use crate::numeric::Numeric;
+
+pub use crate::combinators::Compose;
+
 mod data_item;
 pub use crate::data_item::DataItem;
+
+#[cfg(feature = "std")]
+mod dynamic;
+#[cfg(feature = "std")]
+pub use crate::dynamic::{DynIndicator, OutputValue};
@@ -0,0 +1,196 @@
+use core::fmt;
+use core::ops::Neg;
+
+use num_traits::{FromPrimitive, Num, NumAssignOps, ToPrimitive};
+
+/// Value type of the indicators which are generic over it.
+///
+/// The moving averages [SMA](indicators/generic/struct.SimpleMovingAverage.html),
+/// [EMA](indicators/generic/struct.ExponentialMovingAverage.html),
+/// [WMA](indicators/generic/struct.WeightedMovingAverage.html) and
+/// [SSMA](indicators/generic/struct.SmoothedSimpleMovingAverage.html), as well as
+/// [Maximum](indicators/generic/struct.Maximum.html),
+/// [Minimum](indicators/generic/struct.Minimum.html),
+/// [StandardDeviation](indicators/generic/struct.StandardDeviation.html),
+/// [BollingerBands](indicators/generic/struct.BollingerBands.html),
+/// [RSI](indicators/generic/struct.RelativeStrengthIndex.html),
+/// [TrueRange](indicators/generic/struct.TrueRange.html) and
+/// [ATR](indicators/generic/struct.AverageTrueRange.html) take, keep and return values of any
+/// `Numeric` type, e.g. `f32` to run on data that is already in single precision without
+/// converting it. Their bar inputs implement [Close](trait.Close.html) and the other price traits
+/// for that type. [indicators](indicators/index.html) exports them for `f64` and
+/// [indicators::generic](indicators/generic/index.html) for any value type. The other indicators
+/// work with `f64`.
+///
+/// Implemented for `f64`, `f32` and, with the `decimal` feature, `rust_decimal::Decimal`, whose
+/// arithmetic is exact for decimal prices. Its square root is rounded to 28 digits.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::generic::ExponentialMovingAverage;
+/// use ta::Next;
+///
+/// let mut ema = ExponentialMovingAverage::<f32>::new(3).unwrap();
+/// assert_eq!(ema.next(10.0), 10.0);
+/// assert_eq!(ema.next(11.0), 10.5);
+/// ```
+pub trait Numeric:
+    Num
+    + NumAssignOps
+    + FromPrimitive
+    + ToPrimitive
+    + Neg<Output = Self>
+    + Copy
+    + PartialOrd
+    + fmt::Debug
+    + fmt::Display
+    + sealed::Sealed
+{
+    /// Converts a number of inputs, e.g. to divide a sum by it.
+    fn from_count(count: usize) -> Self;
+
+    /// Converts a parameter of an indicator, e.g. a multiplier.
+    fn from_param(value: f64) -> Self;
+
+    /// Value below any input, e.g. negative infinity.
+    fn lowest() -> Self;
+
+    /// Value above any input, e.g. infinity.
+    fn highest() -> Self;
+
+    fn abs(self) -> Self;
+
+    fn max(self, other: Self) -> Self;
+
+    fn min(self, other: Self) -> Self;
+
+    #[cfg(feature = "std")]
+    fn sqrt(self) -> Self;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_numeric_float {
+    ($($float:ty),*) => {
+        $(
+            impl sealed::Sealed for $float {}
+
+            impl Numeric for $float {
+                fn from_count(count: usize) -> Self {
+                    count as $float
+                }
+
+                fn from_param(value: f64) -> Self {
+                    value as $float
+                }
+
+                fn lowest() -> Self {
+                    <$float>::NEG_INFINITY
+                }
+
+                fn highest() -> Self {
+                    <$float>::INFINITY
+                }
+
+                fn abs(self) -> Self {
+                    <$float>::abs(self)
+                }
+
+                fn max(self, other: Self) -> Self {
+                    <$float>::max(self, other)
+                }
+
+                fn min(self, other: Self) -> Self {
+                    <$float>::min(self, other)
+                }
+
+                #[cfg(feature = "std")]
+                fn sqrt(self) -> Self {
+                    <$float>::sqrt(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_float!(f64, f32);
+
+#[cfg(feature = "decimal")]
+mod decimal {
+    use num_traits::FromPrimitive;
+    use rust_decimal::{Decimal, MathematicalOps};
+
+    use super::{sealed, Numeric};
+
+    impl sealed::Sealed for Decimal {}
+
+    impl Numeric for Decimal {
+        fn from_count(count: usize) -> Self {
+            Decimal::from(count)
+        }
+
+        // Saturates outside of the range of `Decimal`
+        fn from_param(value: f64) -> Self {
+            Decimal::from_f64(value).unwrap_or(if value < 0.0 {
+                Decimal::MIN
+            } else {
+                Decimal::MAX
+            })
+        }
+
+        fn lowest() -> Self {
+            Decimal::MIN
+        }
+
+        fn highest() -> Self {
+            Decimal::MAX
+        }
+
+        fn abs(self) -> Self {
+            Decimal::abs(&self)
+        }
+
+        fn max(self, other: Self) -> Self {
+            Ord::max(self, other)
+        }
+
+        fn min(self, other: Self) -> Self {
+            Ord::min(self, other)
+        }
+
+        // A negative value, e.g. a variance below zero by rounding, has no square root
+        #[cfg(feature = "std")]
+        fn sqrt(self) -> Self {
+            MathematicalOps::sqrt(&self).unwrap_or(Decimal::ZERO)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(f32::from_count(3), 3.0);
+        assert_eq!(f64::from_param(0.5), 0.5);
+        assert_eq!(Numeric::abs(-2.5_f32), 2.5);
+        assert_eq!(Numeric::max(1.0_f64, f64::NAN), 1.0);
+        assert_eq!(Numeric::min(1.0_f32, 2.0), 1.0);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal_conversions() {
+        use rust_decimal::Decimal;
+
+        assert_eq!(Decimal::from_count(3), Decimal::new(3, 0));
+        assert_eq!(Decimal::from_param(0.1), Decimal::new(1, 1));
+        assert_eq!(Decimal::from_param(f64::INFINITY), Decimal::MAX);
+        assert_eq!(Decimal::from_param(f64::MIN), Decimal::MIN);
+        assert_eq!(Numeric::max(Decimal::ONE, Decimal::TWO), Decimal::TWO);
+    }
+}
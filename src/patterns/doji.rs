@@ -0,0 +1,159 @@
+use core::fmt;
+
+use super::{Candle, Direction, Pattern};
+use crate::errors::{Result, TaError};
+use crate::{Checkpoint, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Doji.
+///
+/// A candle whose open and close are (almost) equal, a sign of indecision.
+///
+/// # Formula
+///
+/// Body ≤ _max_body_ratio_ * Range, where Range is greater than 0.
+///
+/// # Parameters
+///
+/// * _max_body_ratio_ - maximal size of the body relative to the range, number in range 0..1.
+///   Default is 0.1.
+///
+/// # Example
+///
+/// ```
+/// use ta::patterns::Doji;
+/// use ta::{DataItem, Next};
+///
+/// let mut doji = Doji::new(0.1).unwrap();
+///
+/// let bar = DataItem::builder()
+///     .open(10.0).high(12.0).low(8.0).close(10.2)
+///     .volume(1000.0)
+///     .build().unwrap();
+///
+/// assert!(doji.next(&bar));
+/// ```
+///
+/// # Links
+///
+/// * [Doji, Wikipedia](https://en.wikipedia.org/wiki/Doji)
+///
+#[derive(Debug, Clone)]
+pub struct Doji {
+    max_body_ratio: f64,
+}
+
+/// State of [Doji](struct.Doji.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DojiState {
+    pub max_body_ratio: f64,
+}
+
+impl Doji {
+    pub fn new(max_body_ratio: f64) -> Result<Self> {
+        if !(0.0..=1.0).contains(&max_body_ratio) {
+            return Err(TaError::invalid_parameter(
+                "max_body_ratio",
+                max_body_ratio,
+                "0..=1",
+            ));
+        }
+        Ok(Self { max_body_ratio })
+    }
+}
+
+impl Checkpoint for Doji {
+    type State = DojiState;
+
+    fn state(&self) -> Self::State {
+        DojiState {
+            max_body_ratio: self.max_body_ratio,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Self::new(state.max_body_ratio).map_err(|_| TaError::InvalidState)
+    }
+}
+
+impl_versioned_serde!(Doji, 1);
+
+impl Pattern for Doji {
+    fn name(&self) -> &'static str {
+        "DOJI"
+    }
+
+    fn direction(&self) -> Direction {
+        Direction::Neutral
+    }
+
+    fn next_candle(&mut self, candle: &Candle) -> bool {
+        candle.range() > 0.0 && candle.body() <= self.max_body_ratio * candle.range()
+    }
+}
+
+impl Reset for Doji {
+    fn reset(&mut self) {}
+}
+
+impl Default for Doji {
+    fn default() -> Self {
+        Self::new(0.1).unwrap()
+    }
+}
+
+impl fmt::Display for Doji {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DOJI({})", self.max_body_ratio)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use crate::Next;
+
+    #[test]
+    fn test_new() {
+        assert!(Doji::new(-0.1).is_err());
+        assert!(Doji::new(1.1).is_err());
+        assert!(Doji::new(f64::NAN).is_err());
+        assert!(Doji::new(0.0).is_ok());
+        assert!(Doji::new(1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut doji = Doji::new(0.1).unwrap();
+
+        assert!(doji.next(&Bar::new().open(10).high(12).low(8).close(10)));
+        assert!(doji.next(&Bar::new().open(10).high(12).low(8).close(9.7)));
+        assert!(!doji.next(&Bar::new().open(10).high(12).low(8).close(9.5)));
+        // no range at all
+        assert!(!doji.next(&Bar::new().open(10).high(10).low(10).close(10)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut doji = Doji::default();
+        let bar = Bar::new().open(10).high(12).low(8).close(10);
+
+        assert!(doji.next(&bar));
+        doji.reset();
+        assert!(doji.next(&bar));
+    }
+
+    #[test]
+    fn test_default() {
+        Doji::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let doji = Doji::new(0.05).unwrap();
+        assert_eq!(format!("{}", doji), "DOJI(0.05)");
+    }
+}
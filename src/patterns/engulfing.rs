@@ -0,0 +1,303 @@
+use core::fmt;
+
+use super::{Candle, Direction, Pattern};
+use crate::errors::Result;
+use crate::{Checkpoint, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Bullish engulfing.
+///
+/// A bearish candle followed by a bullish candle whose body completely covers the body of the
+/// previous one.
+///
+/// # Formula
+///
+/// * Previous candle is bearish, current candle is bullish
+/// * Open ≤ Close<sub>t-1</sub> and Close ≥ Open<sub>t-1</sub>
+/// * Body > Body<sub>t-1</sub>
+///
+/// # Example
+///
+/// ```
+/// use ta::patterns::BullishEngulfing;
+/// use ta::{DataItem, Next};
+///
+/// fn bar(open: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(open).high(open.max(close)).low(open.min(close)).close(close)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut engulfing = BullishEngulfing::new();
+///
+/// assert!(!engulfing.next(&bar(10.0, 9.0)));
+/// assert!(engulfing.next(&bar(8.8, 10.5)));
+/// ```
+///
+/// # Links
+///
+/// * [Candlestick pattern, Wikipedia](https://en.wikipedia.org/wiki/Candlestick_pattern)
+///
+#[derive(Debug, Clone)]
+pub struct BullishEngulfing {
+    prev: Option<Candle>,
+}
+
+/// State of [BullishEngulfing](struct.BullishEngulfing.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BullishEngulfingState {
+    /// Previous candle.
+    pub previous: Option<Candle>,
+}
+
+impl BullishEngulfing {
+    pub fn new() -> Self {
+        Self { prev: None }
+    }
+}
+
+impl Checkpoint for BullishEngulfing {
+    type State = BullishEngulfingState;
+
+    fn state(&self) -> Self::State {
+        BullishEngulfingState {
+            previous: self.prev,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(Self {
+            prev: state.previous,
+        })
+    }
+}
+
+impl_versioned_serde!(BullishEngulfing, 1);
+
+impl Pattern for BullishEngulfing {
+    fn name(&self) -> &'static str {
+        "BULLENGULFING"
+    }
+
+    fn direction(&self) -> Direction {
+        Direction::Bullish
+    }
+
+    fn next_candle(&mut self, candle: &Candle) -> bool {
+        let found = match self.prev {
+            Some(prev) => {
+                prev.is_bearish()
+                    && candle.is_bullish()
+                    && candle.open <= prev.close
+                    && candle.close >= prev.open
+                    && candle.body() > prev.body()
+            }
+            None => false,
+        };
+        self.prev = Some(*candle);
+        found
+    }
+}
+
+impl Reset for BullishEngulfing {
+    fn reset(&mut self) {
+        self.prev = None;
+    }
+}
+
+impl Default for BullishEngulfing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for BullishEngulfing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BULLENGULFING")
+    }
+}
+
+/// Bearish engulfing.
+///
+/// A bullish candle followed by a bearish candle whose body completely covers the body of the
+/// previous one.
+///
+/// # Formula
+///
+/// * Previous candle is bullish, current candle is bearish
+/// * Open ≥ Close<sub>t-1</sub> and Close ≤ Open<sub>t-1</sub>
+/// * Body > Body<sub>t-1</sub>
+///
+/// # Example
+///
+/// ```
+/// use ta::patterns::BearishEngulfing;
+/// use ta::{DataItem, Next};
+///
+/// fn bar(open: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(open).high(open.max(close)).low(open.min(close)).close(close)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut engulfing = BearishEngulfing::new();
+///
+/// assert!(!engulfing.next(&bar(9.0, 10.0)));
+/// assert!(engulfing.next(&bar(10.2, 8.5)));
+/// ```
+///
+/// # Links
+///
+/// * [Candlestick pattern, Wikipedia](https://en.wikipedia.org/wiki/Candlestick_pattern)
+///
+#[derive(Debug, Clone)]
+pub struct BearishEngulfing {
+    prev: Option<Candle>,
+}
+
+/// State of [BearishEngulfing](struct.BearishEngulfing.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BearishEngulfingState {
+    /// Previous candle.
+    pub previous: Option<Candle>,
+}
+
+impl BearishEngulfing {
+    pub fn new() -> Self {
+        Self { prev: None }
+    }
+}
+
+impl Checkpoint for BearishEngulfing {
+    type State = BearishEngulfingState;
+
+    fn state(&self) -> Self::State {
+        BearishEngulfingState {
+            previous: self.prev,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(Self {
+            prev: state.previous,
+        })
+    }
+}
+
+impl_versioned_serde!(BearishEngulfing, 1);
+
+impl Pattern for BearishEngulfing {
+    fn name(&self) -> &'static str {
+        "BEARENGULFING"
+    }
+
+    fn direction(&self) -> Direction {
+        Direction::Bearish
+    }
+
+    fn next_candle(&mut self, candle: &Candle) -> bool {
+        let found = match self.prev {
+            Some(prev) => {
+                prev.is_bullish()
+                    && candle.is_bearish()
+                    && candle.open >= prev.close
+                    && candle.close <= prev.open
+                    && candle.body() > prev.body()
+            }
+            None => false,
+        };
+        self.prev = Some(*candle);
+        found
+    }
+}
+
+impl Reset for BearishEngulfing {
+    fn reset(&mut self) {
+        self.prev = None;
+    }
+}
+
+impl Default for BearishEngulfing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for BearishEngulfing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BEARENGULFING")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use crate::Next;
+
+    fn bar(open: f64, close: f64) -> Bar {
+        Bar::new()
+            .open(open)
+            .high(open.max(close))
+            .low(open.min(close))
+            .close(close)
+    }
+
+    #[test]
+    fn test_next_bullish() {
+        let mut engulfing = BullishEngulfing::new();
+
+        assert!(!engulfing.next(&bar(10.0, 9.0)));
+        assert!(engulfing.next(&bar(8.8, 10.5)));
+        // previous candle is bullish
+        assert!(!engulfing.next(&bar(9.0, 11.0)));
+        // body does not reach the previous open
+        assert!(!engulfing.next(&bar(10.0, 9.0)));
+        assert!(!engulfing.next(&bar(9.0, 9.8)));
+    }
+
+    #[test]
+    fn test_next_bearish() {
+        let mut engulfing = BearishEngulfing::new();
+
+        assert!(!engulfing.next(&bar(9.0, 10.0)));
+        assert!(engulfing.next(&bar(10.2, 8.5)));
+        // previous candle is bearish
+        assert!(!engulfing.next(&bar(9.0, 8.0)));
+        // same body size does not engulf
+        assert!(!engulfing.next(&bar(8.0, 9.0)));
+        assert!(!engulfing.next(&bar(9.0, 8.0)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bullish = BullishEngulfing::new();
+        let mut bearish = BearishEngulfing::new();
+
+        bullish.next(&bar(10.0, 9.0));
+        bearish.next(&bar(9.0, 10.0));
+
+        bullish.reset();
+        bearish.reset();
+
+        assert!(!bullish.next(&bar(8.8, 10.5)));
+        assert!(!bearish.next(&bar(10.2, 8.5)));
+    }
+
+    #[test]
+    fn test_default() {
+        BullishEngulfing::default();
+        BearishEngulfing::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", BullishEngulfing::new()), "BULLENGULFING");
+        assert_eq!(format!("{}", BearishEngulfing::new()), "BEARENGULFING");
+    }
+}
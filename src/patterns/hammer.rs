@@ -0,0 +1,141 @@
+use core::fmt;
+
+use super::{Candle, Direction, Pattern};
+use crate::errors::Result;
+use crate::{Checkpoint, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Hammer.
+///
+/// A candle with a small body near the high and a long lower shadow: sellers pushed the price
+/// down, but buyers brought it back. It is considered bullish when it appears after a decline.
+/// See also [ShootingStar](struct.ShootingStar.html).
+///
+/// # Formula
+///
+/// * Lower shadow ≥ 2 * Body
+/// * Upper shadow ≤ 0.1 * Range
+///
+/// Where Range is greater than 0.
+///
+/// # Example
+///
+/// ```
+/// use ta::patterns::Hammer;
+/// use ta::{DataItem, Next};
+///
+/// let mut hammer = Hammer::new();
+///
+/// let bar = DataItem::builder()
+///     .open(9.5).high(10.0).low(7.0).close(10.0)
+///     .volume(1000.0)
+///     .build().unwrap();
+///
+/// assert!(hammer.next(&bar));
+/// ```
+///
+/// # Links
+///
+/// * [Candlestick pattern, Wikipedia](https://en.wikipedia.org/wiki/Candlestick_pattern)
+///
+#[derive(Debug, Clone)]
+pub struct Hammer {}
+
+impl Hammer {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// State of [Hammer](struct.Hammer.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HammerState {}
+
+impl Checkpoint for Hammer {
+    type State = HammerState;
+
+    fn state(&self) -> Self::State {
+        HammerState {}
+    }
+
+    fn from_state(_state: Self::State) -> Result<Self> {
+        Ok(Self::new())
+    }
+}
+
+impl_versioned_serde!(Hammer, 1);
+
+impl Pattern for Hammer {
+    fn name(&self) -> &'static str {
+        "HAMMER"
+    }
+
+    fn direction(&self) -> Direction {
+        Direction::Bullish
+    }
+
+    fn next_candle(&mut self, candle: &Candle) -> bool {
+        candle.range() > 0.0
+            && candle.lower_shadow() >= 2.0 * candle.body()
+            && candle.upper_shadow() <= 0.1 * candle.range()
+    }
+}
+
+impl Reset for Hammer {
+    fn reset(&mut self) {}
+}
+
+impl Default for Hammer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Hammer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HAMMER")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use crate::Next;
+
+    #[test]
+    fn test_next() {
+        let mut hammer = Hammer::new();
+
+        assert!(hammer.next(&Bar::new().open(9.5).high(10).low(7).close(10)));
+        // bearish body is fine as well
+        assert!(hammer.next(&Bar::new().open(10).high(10).low(7).close(9.5)));
+        // lower shadow is too short
+        assert!(!hammer.next(&Bar::new().open(8).high(10).low(7).close(10)));
+        // upper shadow is too long
+        assert!(!hammer.next(&Bar::new().open(9).high(10).low(7).close(9.5)));
+        assert!(!hammer.next(&Bar::new().open(10).high(10).low(10).close(10)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut hammer = Hammer::new();
+        let bar = Bar::new().open(9.5).high(10).low(7).close(10);
+
+        assert!(hammer.next(&bar));
+        hammer.reset();
+        assert!(hammer.next(&bar));
+    }
+
+    #[test]
+    fn test_default() {
+        Hammer::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Hammer::new()), "HAMMER");
+    }
+}
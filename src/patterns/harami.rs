@@ -0,0 +1,303 @@
+use core::fmt;
+
+use super::{Candle, Direction, Pattern};
+use crate::errors::Result;
+use crate::{Checkpoint, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Bullish harami.
+///
+/// A bearish candle followed by a smaller bullish candle whose body lies within the body of
+/// the previous one.
+///
+/// # Formula
+///
+/// * Previous candle is bearish, current candle is bullish
+/// * Open ≥ Close<sub>t-1</sub> and Close ≤ Open<sub>t-1</sub>
+/// * Body < Body<sub>t-1</sub>
+///
+/// # Example
+///
+/// ```
+/// use ta::patterns::BullishHarami;
+/// use ta::{DataItem, Next};
+///
+/// fn bar(open: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(open).high(open.max(close)).low(open.min(close)).close(close)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut harami = BullishHarami::new();
+///
+/// assert!(!harami.next(&bar(10.0, 8.0)));
+/// assert!(harami.next(&bar(8.5, 9.5)));
+/// ```
+///
+/// # Links
+///
+/// * [Candlestick pattern, Wikipedia](https://en.wikipedia.org/wiki/Candlestick_pattern)
+///
+#[derive(Debug, Clone)]
+pub struct BullishHarami {
+    prev: Option<Candle>,
+}
+
+/// State of [BullishHarami](struct.BullishHarami.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BullishHaramiState {
+    /// Previous candle.
+    pub previous: Option<Candle>,
+}
+
+impl BullishHarami {
+    pub fn new() -> Self {
+        Self { prev: None }
+    }
+}
+
+impl Checkpoint for BullishHarami {
+    type State = BullishHaramiState;
+
+    fn state(&self) -> Self::State {
+        BullishHaramiState {
+            previous: self.prev,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(Self {
+            prev: state.previous,
+        })
+    }
+}
+
+impl_versioned_serde!(BullishHarami, 1);
+
+impl Pattern for BullishHarami {
+    fn name(&self) -> &'static str {
+        "BULLHARAMI"
+    }
+
+    fn direction(&self) -> Direction {
+        Direction::Bullish
+    }
+
+    fn next_candle(&mut self, candle: &Candle) -> bool {
+        let found = match self.prev {
+            Some(prev) => {
+                prev.is_bearish()
+                    && candle.is_bullish()
+                    && candle.open >= prev.close
+                    && candle.close <= prev.open
+                    && candle.body() < prev.body()
+            }
+            None => false,
+        };
+        self.prev = Some(*candle);
+        found
+    }
+}
+
+impl Reset for BullishHarami {
+    fn reset(&mut self) {
+        self.prev = None;
+    }
+}
+
+impl Default for BullishHarami {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for BullishHarami {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BULLHARAMI")
+    }
+}
+
+/// Bearish harami.
+///
+/// A bullish candle followed by a smaller bearish candle whose body lies within the body of
+/// the previous one.
+///
+/// # Formula
+///
+/// * Previous candle is bullish, current candle is bearish
+/// * Open ≤ Close<sub>t-1</sub> and Close ≥ Open<sub>t-1</sub>
+/// * Body < Body<sub>t-1</sub>
+///
+/// # Example
+///
+/// ```
+/// use ta::patterns::BearishHarami;
+/// use ta::{DataItem, Next};
+///
+/// fn bar(open: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(open).high(open.max(close)).low(open.min(close)).close(close)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut harami = BearishHarami::new();
+///
+/// assert!(!harami.next(&bar(8.0, 10.0)));
+/// assert!(harami.next(&bar(9.5, 8.5)));
+/// ```
+///
+/// # Links
+///
+/// * [Candlestick pattern, Wikipedia](https://en.wikipedia.org/wiki/Candlestick_pattern)
+///
+#[derive(Debug, Clone)]
+pub struct BearishHarami {
+    prev: Option<Candle>,
+}
+
+/// State of [BearishHarami](struct.BearishHarami.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BearishHaramiState {
+    /// Previous candle.
+    pub previous: Option<Candle>,
+}
+
+impl BearishHarami {
+    pub fn new() -> Self {
+        Self { prev: None }
+    }
+}
+
+impl Checkpoint for BearishHarami {
+    type State = BearishHaramiState;
+
+    fn state(&self) -> Self::State {
+        BearishHaramiState {
+            previous: self.prev,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(Self {
+            prev: state.previous,
+        })
+    }
+}
+
+impl_versioned_serde!(BearishHarami, 1);
+
+impl Pattern for BearishHarami {
+    fn name(&self) -> &'static str {
+        "BEARHARAMI"
+    }
+
+    fn direction(&self) -> Direction {
+        Direction::Bearish
+    }
+
+    fn next_candle(&mut self, candle: &Candle) -> bool {
+        let found = match self.prev {
+            Some(prev) => {
+                prev.is_bullish()
+                    && candle.is_bearish()
+                    && candle.open <= prev.close
+                    && candle.close >= prev.open
+                    && candle.body() < prev.body()
+            }
+            None => false,
+        };
+        self.prev = Some(*candle);
+        found
+    }
+}
+
+impl Reset for BearishHarami {
+    fn reset(&mut self) {
+        self.prev = None;
+    }
+}
+
+impl Default for BearishHarami {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for BearishHarami {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BEARHARAMI")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use crate::Next;
+
+    fn bar(open: f64, close: f64) -> Bar {
+        Bar::new()
+            .open(open)
+            .high(open.max(close))
+            .low(open.min(close))
+            .close(close)
+    }
+
+    #[test]
+    fn test_next_bullish() {
+        let mut harami = BullishHarami::new();
+
+        assert!(!harami.next(&bar(10.0, 8.0)));
+        assert!(harami.next(&bar(8.5, 9.5)));
+        // previous candle is bullish
+        assert!(!harami.next(&bar(9.0, 9.2)));
+        // body goes above the previous open
+        assert!(!harami.next(&bar(10.0, 8.0)));
+        assert!(!harami.next(&bar(9.0, 10.5)));
+    }
+
+    #[test]
+    fn test_next_bearish() {
+        let mut harami = BearishHarami::new();
+
+        assert!(!harami.next(&bar(8.0, 10.0)));
+        assert!(harami.next(&bar(9.5, 8.5)));
+        // previous candle is bearish
+        assert!(!harami.next(&bar(9.0, 8.8)));
+        // body goes below the previous open
+        assert!(!harami.next(&bar(8.0, 10.0)));
+        assert!(!harami.next(&bar(9.0, 7.5)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bullish = BullishHarami::new();
+        let mut bearish = BearishHarami::new();
+
+        bullish.next(&bar(10.0, 8.0));
+        bearish.next(&bar(8.0, 10.0));
+
+        bullish.reset();
+        bearish.reset();
+
+        assert!(!bullish.next(&bar(8.5, 9.5)));
+        assert!(!bearish.next(&bar(9.5, 8.5)));
+    }
+
+    #[test]
+    fn test_default() {
+        BullishHarami::default();
+        BearishHarami::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", BullishHarami::new()), "BULLHARAMI");
+        assert_eq!(format!("{}", BearishHarami::new()), "BEARHARAMI");
+    }
+}
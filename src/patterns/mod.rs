@@ -0,0 +1,215 @@
+//! Candlestick patterns.
+//!
+//! Every recognizer consumes OHLC bars and returns `true` for the bar which completes the
+//! pattern. Recognizers only look at the shape of the candles, checking the trend in which
+//! a pattern appears is up to the caller.
+//!
+//! All recognizers implement the [Pattern] trait, so many of them can be run at once
+//! with a [PatternScanner].
+//!
+//! # Example
+//!
+//! ```
+//! use ta::patterns::BullishEngulfing;
+//! use ta::{DataItem, Next};
+//!
+//! fn bar(open: f64, close: f64) -> DataItem {
+//!     DataItem::builder()
+//!         .open(open).high(open.max(close)).low(open.min(close)).close(close)
+//!         .volume(1000.0)
+//!         .build().unwrap()
+//! }
+//!
+//! let mut engulfing = BullishEngulfing::new();
+//!
+//! assert!(!engulfing.next(&bar(10.0, 9.0)));
+//! assert!(engulfing.next(&bar(8.8, 10.5)));
+//! ```
+
+use core::fmt;
+
+use crate::{Close, High, Low, Open, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod doji;
+pub use self::doji::{Doji, DojiState};
+
+mod hammer;
+pub use self::hammer::{Hammer, HammerState};
+
+mod shooting_star;
+pub use self::shooting_star::{ShootingStar, ShootingStarState};
+
+mod engulfing;
+pub use self::engulfing::{
+    BearishEngulfing, BearishEngulfingState, BullishEngulfing, BullishEngulfingState,
+};
+
+mod harami;
+pub use self::harami::{BearishHarami, BearishHaramiState, BullishHarami, BullishHaramiState};
+
+mod star;
+pub use self::star::{EveningStar, EveningStarState, MorningStar, MorningStarState};
+
+mod three_candles;
+pub use self::three_candles::{
+    ThreeBlackCrows, ThreeBlackCrowsState, ThreeWhiteSoldiers, ThreeWhiteSoldiersState,
+};
+
+#[cfg(feature = "std")]
+mod scanner;
+#[cfg(feature = "std")]
+pub use self::scanner::{PatternMatch, PatternScanner};
+
+/// Direction suggested by a pattern.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Bullish,
+    Bearish,
+    /// Indecision, e.g. a doji.
+    Neutral,
+}
+
+/// Common interface of candlestick pattern recognizers.
+///
+/// Unlike [Next](crate::Next), which is generic over the input type, this trait takes a
+/// [Candle], so recognizers can be used as trait objects, e.g. by a [PatternScanner].
+pub trait Pattern: Reset + fmt::Debug {
+    /// Short name of the pattern, e.g. "DOJI".
+    fn name(&self) -> &'static str;
+
+    /// Direction suggested by the pattern.
+    fn direction(&self) -> Direction;
+
+    /// Consumes the next candle, returns `true` if it completes the pattern.
+    fn next_candle(&mut self, candle: &Candle) -> bool;
+}
+
+/// OHLC prices of a single bar.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candle {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl Candle {
+    pub fn from_bar<T: Open + High + Low + Close>(bar: &T) -> Self {
+        Self {
+            open: bar.open(),
+            high: bar.high(),
+            low: bar.low(),
+            close: bar.close(),
+        }
+    }
+
+    /// Distance between open and close.
+    pub fn body(&self) -> f64 {
+        (self.close - self.open).abs()
+    }
+
+    /// Distance between high and low.
+    pub fn range(&self) -> f64 {
+        self.high - self.low
+    }
+
+    /// Distance between the top of the body and the high.
+    pub fn upper_shadow(&self) -> f64 {
+        self.high - self.open.max(self.close)
+    }
+
+    /// Distance between the bottom of the body and the low.
+    pub fn lower_shadow(&self) -> f64 {
+        self.open.min(self.close) - self.low
+    }
+
+    /// Middle of the body.
+    pub fn midpoint(&self) -> f64 {
+        (self.open + self.close) / 2.0
+    }
+
+    pub fn is_bullish(&self) -> bool {
+        self.close > self.open
+    }
+
+    pub fn is_bearish(&self) -> bool {
+        self.close < self.open
+    }
+}
+
+impl Open for Candle {
+    fn open(&self) -> f64 {
+        self.open
+    }
+}
+
+impl High for Candle {
+    fn high(&self) -> f64 {
+        self.high
+    }
+}
+
+impl Low for Candle {
+    fn low(&self) -> f64 {
+        self.low
+    }
+}
+
+impl Close for Candle {
+    fn close(&self) -> f64 {
+        self.close
+    }
+}
+
+/// Implements `Next<&T>` on top of [Pattern::next_candle] for bars with OHLC prices.
+macro_rules! impl_next {
+    ($($pattern:ty),*) => {
+        $(
+            impl<T: Open + High + Low + Close> crate::Next<&T> for $pattern {
+                type Output = bool;
+
+                fn next(&mut self, input: &T) -> Self::Output {
+                    self.next_candle(&Candle::from_bar(input))
+                }
+            }
+        )*
+    };
+}
+
+impl_next!(
+    Doji,
+    Hammer,
+    ShootingStar,
+    BullishEngulfing,
+    BearishEngulfing,
+    BullishHarami,
+    BearishHarami,
+    MorningStar,
+    EveningStar,
+    ThreeWhiteSoldiers,
+    ThreeBlackCrows
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_candle() {
+        let bar = Bar::new().open(10).high(15).low(4).close(12);
+        let candle = Candle::from_bar(&bar);
+
+        assert_eq!(candle.body(), 2.0);
+        assert_eq!(candle.range(), 11.0);
+        assert_eq!(candle.upper_shadow(), 3.0);
+        assert_eq!(candle.lower_shadow(), 6.0);
+        assert_eq!(candle.midpoint(), 11.0);
+        assert!(candle.is_bullish());
+        assert!(!candle.is_bearish());
+    }
+}
@@ -0,0 +1,181 @@
+use core::fmt;
+
+use super::*;
+use crate::{Close, High, Low, Next, Open, Reset};
+
+/// Pattern found by a [PatternScanner](struct.PatternScanner.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternMatch {
+    /// Short name of the pattern, as returned by [Pattern::name].
+    pub name: &'static str,
+    pub direction: Direction,
+}
+
+/// Runs many pattern recognizers at once.
+///
+/// Every bar is passed to all recognizers and the patterns completed by the bar are returned.
+/// [Default](#impl-Default) creates a scanner with all patterns of this module using their
+/// default parameters.
+///
+/// # Example
+///
+/// ```
+/// use ta::patterns::{Direction, Doji, Hammer, PatternScanner};
+/// use ta::{DataItem, Next};
+///
+/// let mut scanner = PatternScanner::new()
+///     .with(Doji::default())
+///     .with(Hammer::new());
+///
+/// let bar = DataItem::builder()
+///     .open(10.0).high(10.0).low(7.0).close(10.0)
+///     .volume(1000.0)
+///     .build().unwrap();
+///
+/// let matches = scanner.next(&bar);
+///
+/// assert_eq!(matches.len(), 2);
+/// assert_eq!(matches[0].name, "DOJI");
+/// assert_eq!(matches[1].direction, Direction::Bullish);
+/// ```
+#[derive(Debug)]
+pub struct PatternScanner {
+    patterns: Vec<Box<dyn Pattern>>,
+}
+
+impl PatternScanner {
+    /// Creates a scanner without any patterns.
+    pub fn new() -> Self {
+        Self {
+            patterns: Vec::new(),
+        }
+    }
+
+    /// Adds a pattern to the scanner.
+    pub fn with<P: Pattern + 'static>(mut self, pattern: P) -> Self {
+        self.patterns.push(Box::new(pattern));
+        self
+    }
+
+    /// Names of the patterns in the scanner.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.patterns.iter().map(|p| p.name()).collect()
+    }
+
+    /// Consumes the next candle, returns the patterns it completes.
+    pub fn next_candle(&mut self, candle: &Candle) -> Vec<PatternMatch> {
+        self.patterns
+            .iter_mut()
+            .filter_map(|pattern| {
+                if pattern.next_candle(candle) {
+                    Some(PatternMatch {
+                        name: pattern.name(),
+                        direction: pattern.direction(),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+impl<T: Open + High + Low + Close> Next<&T> for PatternScanner {
+    type Output = Vec<PatternMatch>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next_candle(&Candle::from_bar(input))
+    }
+}
+
+impl Reset for PatternScanner {
+    fn reset(&mut self) {
+        for pattern in self.patterns.iter_mut() {
+            pattern.reset();
+        }
+    }
+}
+
+impl Default for PatternScanner {
+    fn default() -> Self {
+        Self::new()
+            .with(Doji::default())
+            .with(Hammer::new())
+            .with(ShootingStar::new())
+            .with(BullishEngulfing::new())
+            .with(BearishEngulfing::new())
+            .with(BullishHarami::new())
+            .with(BearishHarami::new())
+            .with(MorningStar::new())
+            .with(EveningStar::new())
+            .with(ThreeWhiteSoldiers::new())
+            .with(ThreeBlackCrows::new())
+    }
+}
+
+impl fmt::Display for PatternScanner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PATTERNS({})", self.names().join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn bar(open: f64, close: f64) -> Bar {
+        Bar::new()
+            .open(open)
+            .high(open.max(close))
+            .low(open.min(close))
+            .close(close)
+    }
+
+    fn names(matches: Vec<PatternMatch>) -> Vec<&'static str> {
+        matches.into_iter().map(|m| m.name).collect()
+    }
+
+    #[test]
+    fn test_next() {
+        let mut scanner = PatternScanner::default();
+
+        assert!(scanner.next(&bar(10.0, 9.0)).is_empty());
+        assert_eq!(names(scanner.next(&bar(8.8, 10.5))), vec!["BULLENGULFING"]);
+        assert_eq!(
+            scanner.next(&bar(10.4, 10.0)),
+            vec![PatternMatch {
+                name: "BEARHARAMI",
+                direction: Direction::Bearish
+            }]
+        );
+    }
+
+    #[test]
+    fn test_next_empty() {
+        let mut scanner = PatternScanner::new();
+        assert!(scanner.next(&bar(10.0, 10.0)).is_empty());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut scanner = PatternScanner::default();
+
+        scanner.next(&bar(10.0, 9.0));
+        scanner.reset();
+        assert!(scanner.next(&bar(8.8, 10.5)).is_empty());
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(PatternScanner::default().names().len(), 11);
+    }
+
+    #[test]
+    fn test_display() {
+        let scanner = PatternScanner::new()
+            .with(Doji::default())
+            .with(Hammer::new());
+        assert_eq!(format!("{}", scanner), "PATTERNS(DOJI, HAMMER)");
+    }
+}
@@ -0,0 +1,140 @@
+use core::fmt;
+
+use super::{Candle, Direction, Pattern};
+use crate::errors::Result;
+use crate::{Checkpoint, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Shooting star.
+///
+/// A candle with a small body near the low and a long upper shadow: buyers pushed the price up,
+/// but sellers brought it back. It is considered bearish when it appears after a rally.
+/// See also [Hammer](struct.Hammer.html).
+///
+/// # Formula
+///
+/// * Upper shadow ≥ 2 * Body
+/// * Lower shadow ≤ 0.1 * Range
+///
+/// Where Range is greater than 0.
+///
+/// # Example
+///
+/// ```
+/// use ta::patterns::ShootingStar;
+/// use ta::{DataItem, Next};
+///
+/// let mut star = ShootingStar::new();
+///
+/// let bar = DataItem::builder()
+///     .open(7.5).high(10.0).low(7.0).close(7.0)
+///     .volume(1000.0)
+///     .build().unwrap();
+///
+/// assert!(star.next(&bar));
+/// ```
+///
+/// # Links
+///
+/// * [Candlestick pattern, Wikipedia](https://en.wikipedia.org/wiki/Candlestick_pattern)
+///
+#[derive(Debug, Clone)]
+pub struct ShootingStar {}
+
+impl ShootingStar {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// State of [ShootingStar](struct.ShootingStar.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShootingStarState {}
+
+impl Checkpoint for ShootingStar {
+    type State = ShootingStarState;
+
+    fn state(&self) -> Self::State {
+        ShootingStarState {}
+    }
+
+    fn from_state(_state: Self::State) -> Result<Self> {
+        Ok(Self::new())
+    }
+}
+
+impl_versioned_serde!(ShootingStar, 1);
+
+impl Pattern for ShootingStar {
+    fn name(&self) -> &'static str {
+        "SHOOTINGSTAR"
+    }
+
+    fn direction(&self) -> Direction {
+        Direction::Bearish
+    }
+
+    fn next_candle(&mut self, candle: &Candle) -> bool {
+        candle.range() > 0.0
+            && candle.upper_shadow() >= 2.0 * candle.body()
+            && candle.lower_shadow() <= 0.1 * candle.range()
+    }
+}
+
+impl Reset for ShootingStar {
+    fn reset(&mut self) {}
+}
+
+impl Default for ShootingStar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for ShootingStar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SHOOTINGSTAR")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use crate::Next;
+
+    #[test]
+    fn test_next() {
+        let mut star = ShootingStar::new();
+
+        assert!(star.next(&Bar::new().open(7.5).high(10).low(7).close(7)));
+        assert!(star.next(&Bar::new().open(7).high(10).low(7).close(7.5)));
+        // upper shadow is too short
+        assert!(!star.next(&Bar::new().open(7).high(10).low(7).close(9)));
+        // lower shadow is too long
+        assert!(!star.next(&Bar::new().open(8).high(10).low(7).close(7.5)));
+        assert!(!star.next(&Bar::new().open(10).high(10).low(10).close(10)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut star = ShootingStar::new();
+        let bar = Bar::new().open(7.5).high(10).low(7).close(7);
+
+        assert!(star.next(&bar));
+        star.reset();
+        assert!(star.next(&bar));
+    }
+
+    #[test]
+    fn test_default() {
+        ShootingStar::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", ShootingStar::new()), "SHOOTINGSTAR");
+    }
+}
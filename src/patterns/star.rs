@@ -0,0 +1,345 @@
+use core::fmt;
+
+use super::{Candle, Direction, Pattern};
+use crate::errors::{Result, TaError};
+use crate::{Checkpoint, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Morning star.
+///
+/// A three candle bottom reversal: a long bearish candle, a small candle (the star) which
+/// stays below the first close, and a bullish candle which closes above the middle of the
+/// first body.
+///
+/// # Formula
+///
+/// * First candle is bearish
+/// * Body<sub>2</sub> ≤ 0.3 * Body<sub>1</sub> and max(Open<sub>2</sub>, Close<sub>2</sub>) ≤ Close<sub>1</sub>
+/// * Third candle is bullish and Close<sub>3</sub> > (Open<sub>1</sub> + Close<sub>1</sub>) / 2
+///
+/// # Example
+///
+/// ```
+/// use ta::patterns::MorningStar;
+/// use ta::{DataItem, Next};
+///
+/// fn bar(open: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(open).high(open.max(close)).low(open.min(close)).close(close)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut star = MorningStar::new();
+///
+/// assert!(!star.next(&bar(10.0, 8.0)));
+/// assert!(!star.next(&bar(7.8, 7.6)));
+/// assert!(star.next(&bar(7.8, 9.5)));
+/// ```
+///
+/// # Links
+///
+/// * [Candlestick pattern, Wikipedia](https://en.wikipedia.org/wiki/Candlestick_pattern)
+///
+#[derive(Debug, Clone)]
+pub struct MorningStar {
+    first: Option<Candle>,
+    second: Option<Candle>,
+}
+
+/// State of [MorningStar](struct.MorningStar.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MorningStarState {
+    /// Candle before the previous one.
+    pub first: Option<Candle>,
+    /// Previous candle.
+    pub second: Option<Candle>,
+}
+
+impl MorningStar {
+    pub fn new() -> Self {
+        Self {
+            first: None,
+            second: None,
+        }
+    }
+}
+
+impl Checkpoint for MorningStar {
+    type State = MorningStarState;
+
+    fn state(&self) -> Self::State {
+        MorningStarState {
+            first: self.first,
+            second: self.second,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.first.is_some() && state.second.is_none() {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            first: state.first,
+            second: state.second,
+        })
+    }
+}
+
+impl_versioned_serde!(MorningStar, 1);
+
+impl Pattern for MorningStar {
+    fn name(&self) -> &'static str {
+        "MORNINGSTAR"
+    }
+
+    fn direction(&self) -> Direction {
+        Direction::Bullish
+    }
+
+    fn next_candle(&mut self, candle: &Candle) -> bool {
+        let found = match (self.first, self.second) {
+            (Some(first), Some(star)) => {
+                first.is_bearish()
+                    && star.body() <= 0.3 * first.body()
+                    && star.open.max(star.close) <= first.close
+                    && candle.is_bullish()
+                    && candle.close > first.midpoint()
+            }
+            _ => false,
+        };
+        self.first = self.second;
+        self.second = Some(*candle);
+        found
+    }
+}
+
+impl Reset for MorningStar {
+    fn reset(&mut self) {
+        self.first = None;
+        self.second = None;
+    }
+}
+
+impl Default for MorningStar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for MorningStar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MORNINGSTAR")
+    }
+}
+
+/// Evening star.
+///
+/// A three candle top reversal: a long bullish candle, a small candle (the star) which
+/// stays above the first close, and a bearish candle which closes below the middle of the
+/// first body.
+///
+/// # Formula
+///
+/// * First candle is bullish
+/// * Body<sub>2</sub> ≤ 0.3 * Body<sub>1</sub> and min(Open<sub>2</sub>, Close<sub>2</sub>) ≥ Close<sub>1</sub>
+/// * Third candle is bearish and Close<sub>3</sub> < (Open<sub>1</sub> + Close<sub>1</sub>) / 2
+///
+/// # Example
+///
+/// ```
+/// use ta::patterns::EveningStar;
+/// use ta::{DataItem, Next};
+///
+/// fn bar(open: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(open).high(open.max(close)).low(open.min(close)).close(close)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut star = EveningStar::new();
+///
+/// assert!(!star.next(&bar(8.0, 10.0)));
+/// assert!(!star.next(&bar(10.2, 10.4)));
+/// assert!(star.next(&bar(10.2, 8.5)));
+/// ```
+///
+/// # Links
+///
+/// * [Candlestick pattern, Wikipedia](https://en.wikipedia.org/wiki/Candlestick_pattern)
+///
+#[derive(Debug, Clone)]
+pub struct EveningStar {
+    first: Option<Candle>,
+    second: Option<Candle>,
+}
+
+/// State of [EveningStar](struct.EveningStar.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EveningStarState {
+    /// Candle before the previous one.
+    pub first: Option<Candle>,
+    /// Previous candle.
+    pub second: Option<Candle>,
+}
+
+impl EveningStar {
+    pub fn new() -> Self {
+        Self {
+            first: None,
+            second: None,
+        }
+    }
+}
+
+impl Checkpoint for EveningStar {
+    type State = EveningStarState;
+
+    fn state(&self) -> Self::State {
+        EveningStarState {
+            first: self.first,
+            second: self.second,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.first.is_some() && state.second.is_none() {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            first: state.first,
+            second: state.second,
+        })
+    }
+}
+
+impl_versioned_serde!(EveningStar, 1);
+
+impl Pattern for EveningStar {
+    fn name(&self) -> &'static str {
+        "EVENINGSTAR"
+    }
+
+    fn direction(&self) -> Direction {
+        Direction::Bearish
+    }
+
+    fn next_candle(&mut self, candle: &Candle) -> bool {
+        let found = match (self.first, self.second) {
+            (Some(first), Some(star)) => {
+                first.is_bullish()
+                    && star.body() <= 0.3 * first.body()
+                    && star.open.min(star.close) >= first.close
+                    && candle.is_bearish()
+                    && candle.close < first.midpoint()
+            }
+            _ => false,
+        };
+        self.first = self.second;
+        self.second = Some(*candle);
+        found
+    }
+}
+
+impl Reset for EveningStar {
+    fn reset(&mut self) {
+        self.first = None;
+        self.second = None;
+    }
+}
+
+impl Default for EveningStar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for EveningStar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "EVENINGSTAR")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use crate::Next;
+
+    fn bar(open: f64, close: f64) -> Bar {
+        Bar::new()
+            .open(open)
+            .high(open.max(close))
+            .low(open.min(close))
+            .close(close)
+    }
+
+    #[test]
+    fn test_next_morning() {
+        let mut star = MorningStar::new();
+
+        assert!(!star.next(&bar(10.0, 8.0)));
+        assert!(!star.next(&bar(7.8, 7.6)));
+        assert!(star.next(&bar(7.8, 9.5)));
+
+        // the third candle closes below the middle of the first body
+        star.reset();
+        star.next(&bar(10.0, 8.0));
+        star.next(&bar(7.8, 7.6));
+        assert!(!star.next(&bar(7.8, 8.9)));
+
+        // the star body is too large
+        star.reset();
+        star.next(&bar(10.0, 8.0));
+        star.next(&bar(7.9, 7.0));
+        assert!(!star.next(&bar(7.0, 9.5)));
+    }
+
+    #[test]
+    fn test_next_evening() {
+        let mut star = EveningStar::new();
+
+        assert!(!star.next(&bar(8.0, 10.0)));
+        assert!(!star.next(&bar(10.2, 10.4)));
+        assert!(star.next(&bar(10.2, 8.5)));
+
+        // the star opens inside the first body
+        star.reset();
+        star.next(&bar(8.0, 10.0));
+        star.next(&bar(9.8, 10.2));
+        assert!(!star.next(&bar(10.0, 8.5)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut morning = MorningStar::new();
+        let mut evening = EveningStar::new();
+
+        morning.next(&bar(10.0, 8.0));
+        morning.next(&bar(7.8, 7.6));
+        evening.next(&bar(8.0, 10.0));
+        evening.next(&bar(10.2, 10.4));
+
+        morning.reset();
+        evening.reset();
+
+        assert!(!morning.next(&bar(7.8, 9.5)));
+        assert!(!evening.next(&bar(10.2, 8.5)));
+    }
+
+    #[test]
+    fn test_default() {
+        MorningStar::default();
+        EveningStar::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", MorningStar::new()), "MORNINGSTAR");
+        assert_eq!(format!("{}", EveningStar::new()), "EVENINGSTAR");
+    }
+}
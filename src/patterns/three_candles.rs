@@ -0,0 +1,343 @@
+use core::fmt;
+
+use super::{Candle, Direction, Pattern};
+use crate::errors::{Result, TaError};
+use crate::{Checkpoint, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Three white soldiers.
+///
+/// Three consecutive bullish candles, each opening within the body of the previous one and
+/// closing higher.
+///
+/// # Formula
+///
+/// For the second and the third candle:
+///
+/// * Candle is bullish and Close > Close<sub>t-1</sub>
+/// * Open<sub>t-1</sub> < Open ≤ Close<sub>t-1</sub>
+///
+/// The first candle must be bullish as well.
+///
+/// # Example
+///
+/// ```
+/// use ta::patterns::ThreeWhiteSoldiers;
+/// use ta::{DataItem, Next};
+///
+/// fn bar(open: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(open).high(open.max(close)).low(open.min(close)).close(close)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut soldiers = ThreeWhiteSoldiers::new();
+///
+/// assert!(!soldiers.next(&bar(10.0, 11.0)));
+/// assert!(!soldiers.next(&bar(10.8, 12.0)));
+/// assert!(soldiers.next(&bar(11.5, 13.0)));
+/// ```
+///
+/// # Links
+///
+/// * [Candlestick pattern, Wikipedia](https://en.wikipedia.org/wiki/Candlestick_pattern)
+///
+#[derive(Debug, Clone)]
+pub struct ThreeWhiteSoldiers {
+    first: Option<Candle>,
+    second: Option<Candle>,
+}
+
+/// State of [ThreeWhiteSoldiers](struct.ThreeWhiteSoldiers.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreeWhiteSoldiersState {
+    /// Candle before the previous one.
+    pub first: Option<Candle>,
+    /// Previous candle.
+    pub second: Option<Candle>,
+}
+
+impl ThreeWhiteSoldiers {
+    pub fn new() -> Self {
+        Self {
+            first: None,
+            second: None,
+        }
+    }
+}
+
+fn advances(prev: &Candle, candle: &Candle) -> bool {
+    candle.is_bullish()
+        && candle.close > prev.close
+        && candle.open > prev.open
+        && candle.open <= prev.close
+}
+
+fn declines(prev: &Candle, candle: &Candle) -> bool {
+    candle.is_bearish()
+        && candle.close < prev.close
+        && candle.open < prev.open
+        && candle.open >= prev.close
+}
+
+impl Checkpoint for ThreeWhiteSoldiers {
+    type State = ThreeWhiteSoldiersState;
+
+    fn state(&self) -> Self::State {
+        ThreeWhiteSoldiersState {
+            first: self.first,
+            second: self.second,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.first.is_some() && state.second.is_none() {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            first: state.first,
+            second: state.second,
+        })
+    }
+}
+
+impl_versioned_serde!(ThreeWhiteSoldiers, 1);
+
+impl Pattern for ThreeWhiteSoldiers {
+    fn name(&self) -> &'static str {
+        "3WHITESOLDIERS"
+    }
+
+    fn direction(&self) -> Direction {
+        Direction::Bullish
+    }
+
+    fn next_candle(&mut self, candle: &Candle) -> bool {
+        let found = match (self.first, self.second) {
+            (Some(first), Some(second)) => {
+                first.is_bullish() && advances(&first, &second) && advances(&second, candle)
+            }
+            _ => false,
+        };
+        self.first = self.second;
+        self.second = Some(*candle);
+        found
+    }
+}
+
+impl Reset for ThreeWhiteSoldiers {
+    fn reset(&mut self) {
+        self.first = None;
+        self.second = None;
+    }
+}
+
+impl Default for ThreeWhiteSoldiers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for ThreeWhiteSoldiers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "3WHITESOLDIERS")
+    }
+}
+
+/// Three black crows.
+///
+/// Three consecutive bearish candles, each opening within the body of the previous one and
+/// closing lower.
+///
+/// # Formula
+///
+/// For the second and the third candle:
+///
+/// * Candle is bearish and Close < Close<sub>t-1</sub>
+/// * Close<sub>t-1</sub> ≤ Open < Open<sub>t-1</sub>
+///
+/// The first candle must be bearish as well.
+///
+/// # Example
+///
+/// ```
+/// use ta::patterns::ThreeBlackCrows;
+/// use ta::{DataItem, Next};
+///
+/// fn bar(open: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(open).high(open.max(close)).low(open.min(close)).close(close)
+///         .volume(1000.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut crows = ThreeBlackCrows::new();
+///
+/// assert!(!crows.next(&bar(13.0, 12.0)));
+/// assert!(!crows.next(&bar(12.2, 11.0)));
+/// assert!(crows.next(&bar(11.5, 10.0)));
+/// ```
+///
+/// # Links
+///
+/// * [Candlestick pattern, Wikipedia](https://en.wikipedia.org/wiki/Candlestick_pattern)
+///
+#[derive(Debug, Clone)]
+pub struct ThreeBlackCrows {
+    first: Option<Candle>,
+    second: Option<Candle>,
+}
+
+/// State of [ThreeBlackCrows](struct.ThreeBlackCrows.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreeBlackCrowsState {
+    /// Candle before the previous one.
+    pub first: Option<Candle>,
+    /// Previous candle.
+    pub second: Option<Candle>,
+}
+
+impl ThreeBlackCrows {
+    pub fn new() -> Self {
+        Self {
+            first: None,
+            second: None,
+        }
+    }
+}
+
+impl Checkpoint for ThreeBlackCrows {
+    type State = ThreeBlackCrowsState;
+
+    fn state(&self) -> Self::State {
+        ThreeBlackCrowsState {
+            first: self.first,
+            second: self.second,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        if state.first.is_some() && state.second.is_none() {
+            return Err(TaError::InvalidState);
+        }
+        Ok(Self {
+            first: state.first,
+            second: state.second,
+        })
+    }
+}
+
+impl_versioned_serde!(ThreeBlackCrows, 1);
+
+impl Pattern for ThreeBlackCrows {
+    fn name(&self) -> &'static str {
+        "3BLACKCROWS"
+    }
+
+    fn direction(&self) -> Direction {
+        Direction::Bearish
+    }
+
+    fn next_candle(&mut self, candle: &Candle) -> bool {
+        let found = match (self.first, self.second) {
+            (Some(first), Some(second)) => {
+                first.is_bearish() && declines(&first, &second) && declines(&second, candle)
+            }
+            _ => false,
+        };
+        self.first = self.second;
+        self.second = Some(*candle);
+        found
+    }
+}
+
+impl Reset for ThreeBlackCrows {
+    fn reset(&mut self) {
+        self.first = None;
+        self.second = None;
+    }
+}
+
+impl Default for ThreeBlackCrows {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for ThreeBlackCrows {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "3BLACKCROWS")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use crate::Next;
+
+    fn bar(open: f64, close: f64) -> Bar {
+        Bar::new()
+            .open(open)
+            .high(open.max(close))
+            .low(open.min(close))
+            .close(close)
+    }
+
+    #[test]
+    fn test_next_soldiers() {
+        let mut soldiers = ThreeWhiteSoldiers::new();
+
+        assert!(!soldiers.next(&bar(10.0, 11.0)));
+        assert!(!soldiers.next(&bar(10.8, 12.0)));
+        assert!(soldiers.next(&bar(11.5, 13.0)));
+        // a fourth soldier completes another pattern
+        assert!(soldiers.next(&bar(12.5, 14.0)));
+        // opens above the previous close
+        assert!(!soldiers.next(&bar(14.5, 15.0)));
+    }
+
+    #[test]
+    fn test_next_crows() {
+        let mut crows = ThreeBlackCrows::new();
+
+        assert!(!crows.next(&bar(13.0, 12.0)));
+        assert!(!crows.next(&bar(12.2, 11.0)));
+        assert!(crows.next(&bar(11.5, 10.0)));
+        // closes higher
+        assert!(!crows.next(&bar(10.5, 10.2)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut soldiers = ThreeWhiteSoldiers::new();
+        let mut crows = ThreeBlackCrows::new();
+
+        soldiers.next(&bar(10.0, 11.0));
+        soldiers.next(&bar(10.8, 12.0));
+        crows.next(&bar(13.0, 12.0));
+        crows.next(&bar(12.2, 11.0));
+
+        soldiers.reset();
+        crows.reset();
+
+        assert!(!soldiers.next(&bar(11.5, 13.0)));
+        assert!(!crows.next(&bar(11.5, 10.0)));
+    }
+
+    #[test]
+    fn test_default() {
+        ThreeWhiteSoldiers::default();
+        ThreeBlackCrows::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", ThreeWhiteSoldiers::new()), "3WHITESOLDIERS");
+        assert_eq!(format!("{}", ThreeBlackCrows::new()), "3BLACKCROWS");
+    }
+}
@@ -20,10 +20,10 @@ impl Bar {
         }
     }
 
-    //pub fn open<T: Into<f64>>(mut self, val :T ) -> Self {
-    //    self.open = val.into();
-    //    self
-    //}
+    pub fn open<T: Into<f64>>(mut self, val: T) -> Self {
+        self.open = val.into();
+        self
+    }
 
     pub fn high<T: Into<f64>>(mut self, val: T) -> Self {
         self.high = val.into();
@@ -93,10 +93,14 @@ macro_rules! test_indicator {
             let first_output = indicator.next(12.3);
 
             // ensure next accepts &DataItem as well
-            indicator.next(&bar);
+            let output = indicator.next(&bar);
+
+            // ensure Current returns the last output
+            assert_eq!(crate::Current::current(&indicator), Some(output));
 
             // ensure Reset is implemented and works correctly
             indicator.reset();
+            assert_eq!(crate::Current::current(&indicator), None);
             assert_eq!(indicator.next(12.3), first_output);
 
             // ensure Display is implemented
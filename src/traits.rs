@@ -1,6 +1,8 @@
 // Indicator traits
 //
 
+use crate::errors::Result;
+
 /// Resets an indicator to the initial state.
 pub trait Reset {
     fn reset(&mut self);
@@ -11,6 +13,354 @@ pub trait Period {
     fn period(&self) -> usize;
 }
 
+/// Changes the period of an indicator without building and warming up a new one, e.g. to try
+/// out periods in a parameter optimization loop.
+///
+/// Implemented for every indicator with a single period. An invalid period returns an error and
+/// leaves the indicator as it was. These keep their warm state:
+/// [SMA](indicators/type.SimpleMovingAverage.html),
+/// [WMA](indicators/type.WeightedMovingAverage.html),
+/// [EMA](indicators/type.ExponentialMovingAverage.html),
+/// [SSMA](indicators/type.SmoothedSimpleMovingAverage.html),
+/// [MovingAverage](indicators/struct.MovingAverage.html),
+/// [Maximum](indicators/type.Maximum.html),
+/// [Minimum](indicators/type.Minimum.html),
+/// [StandardDeviation](indicators/type.StandardDeviation.html),
+/// [BollingerBands](indicators/type.BollingerBands.html),
+/// [RSI](indicators/type.RelativeStrengthIndex.html),
+/// [ATR](indicators/type.AverageTrueRange.html) and [Sourced](indicators/struct.Sourced.html)
+/// around one of them. Those over a window of inputs keep the latest inputs that fit in the new
+/// window, the smoothing ones keep their current value and derive their smoothing factor from the
+/// new period. Every other indicator starts over with the new period, as after a
+/// [reset](trait.Reset.html).
+///
+/// Not implemented for indicators with several periods, for
+/// [SimpleMovingAverageConst](indicators/struct.SimpleMovingAverageConst.html),
+/// [MaximumConst](indicators/struct.MaximumConst.html) and
+/// [MinimumConst](indicators/struct.MinimumConst.html), whose period is a const parameter, and
+/// for [FibonacciLevels](indicators/struct.FibonacciLevels.html) and
+/// [SupportResistance](indicators/struct.SupportResistance.html), whose period is the window
+/// around a swing point rather than a parameter.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{Next, Period, SetPeriod};
+///
+/// let mut sma = SimpleMovingAverage::new(4).unwrap();
+/// for input in [2.0, 4.0, 6.0, 8.0] {
+///     sma.next(input);
+/// }
+///
+/// sma.set_period(2).unwrap();
+/// assert_eq!(sma.period(), 2);
+/// assert_eq!(sma.next(10.0), 9.0);
+/// assert!(sma.set_period(0).is_err());
+/// # }
+/// ```
+pub trait SetPeriod: Period {
+    fn set_period(&mut self, period: usize) -> Result<()>;
+}
+
+/// Exports the state of an indicator as a plain struct and restores an indicator from it.
+///
+/// Lets a program checkpoint indicators to a storage format of its own, without the `serde`
+/// feature. The state holds the parameters of the indicator along with everything it needs to
+/// continue where it left off, including [amending](trait.Amend.html) its last input.
+/// [from_state](#tymethod.from_state) returns [TaError::InvalidState](errors/enum.TaError.html)
+/// for a state that is not consistent, e.g. with more inputs than its period.
+///
+/// Implemented for every indicator, bar aggregator, transform and pattern, and for the wrappers
+/// and combinators when the indicators they wrap implement it. Not implemented for
+/// [Map](combinators/struct.Map.html), as its function can't be restored from a state, nor for
+/// [PatternScanner](patterns/struct.PatternScanner.html) and the pipelines of the `dsl` feature,
+/// which hold boxed trait objects.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{Checkpoint, Next};
+///
+/// let mut sma = SimpleMovingAverage::new(3).unwrap();
+/// sma.next(4.0);
+/// sma.next(6.0);
+///
+/// let mut state = sma.state();
+/// assert_eq!(state.inputs, vec![4.0, 6.0]);
+///
+/// let mut restored = SimpleMovingAverage::from_state(state.clone()).unwrap();
+/// assert_eq!(restored.next(8.0), sma.next(8.0));
+///
+/// state.inputs = vec![1.0; 4];
+/// assert!(SimpleMovingAverage::from_state(state).is_err());
+/// # }
+/// ```
+pub trait Checkpoint: Sized {
+    type State;
+    fn state(&self) -> Self::State;
+    fn from_state(state: Self::State) -> Result<Self>;
+}
+
+/// Tells whether an indicator is warmed up.
+///
+/// Most indicators return values from the very first input, but the first values are
+/// calculated over fewer inputs than the indicator needs (e.g. an
+/// [SMA](indicators/type.SimpleMovingAverage.html) averages the inputs seen so far until it
+/// has seen _period_ of them). `is_ready` returns `true` once the indicator has seen enough
+/// inputs for its output to be fully formed. Indicators without a warm-up phase are always
+/// ready.
+pub trait IsReady {
+    fn is_ready(&self) -> bool;
+}
+
+/// Number of inputs an indicator needs before its output is fully formed.
+///
+/// The first `lookback()` - 1 outputs are warm-up values, so the first fully formed output
+/// belongs to the input at index `lookback() - 1`. For composed indicators it covers all of
+/// their stages, e.g. for [MACD](indicators/struct.MovingAverageConvergenceDivergence.html) it
+/// is the slow period plus the signal period minus 1. Indicators without a warm-up phase
+/// return 1.
+///
+/// Once an indicator has seen `lookback()` inputs, it [is ready](trait.IsReady.html).
+///
+/// Implemented for every indicator apart from
+/// [MultiTimeframe](indicators/struct.MultiTimeframe.html): the number of lower timeframe bars
+/// that make up a higher timeframe bar depends on their timestamps, e.g. on gaps in the data,
+/// and can't be known from its configuration.
+pub trait Lookback {
+    fn lookback(&self) -> usize;
+}
+
+/// Returns the last output of an indicator without feeding it.
+///
+/// Useful when one part of a program feeds an indicator and other parts read its value. Returns
+/// `None` before the first input and after a [reset](trait.Reset.html).
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{Current, Next};
+///
+/// let mut sma = SimpleMovingAverage::new(2).unwrap();
+/// assert_eq!(sma.current(), None);
+///
+/// sma.next(4.0);
+/// sma.next(6.0);
+/// assert_eq!(sma.current(), Some(5.0));
+/// # }
+/// ```
+pub trait Current {
+    type Output;
+    fn current(&self) -> Option<Self::Output>;
+}
+
+/// Calculates what [next](trait.Next.html#tymethod.next) would return for an input, without
+/// feeding it to the indicator.
+///
+/// Useful in live trading to evaluate the bar that is still forming, e.g. "what would the RSI
+/// be if the candle closed right now", on every tick without cloning the indicator. Implemented
+/// for every indicator in [indicators](indicators/index.html), for the same inputs as `Next`,
+/// and for the [combinators](combinators/index.html) of indicators that implement it. The
+/// wrappers [SessionReset](indicators/struct.SessionReset.html),
+/// [MultiTimeframe](indicators/struct.MultiTimeframe.html) and
+/// [DivergenceDetector](indicators/struct.DivergenceDetector.html) implement it when the wrapped
+/// indicator does. [SessionReset](indicators/struct.SessionReset.html) also needs it to be
+/// `Clone`, for an input that starts a new session.
+///
+/// Most indicators peek in constant time. [RollingQuantile](indicators/struct.RollingQuantile.html),
+/// [ValueAtRisk](indicators/struct.ValueAtRisk.html) and
+/// [VolumeProfile](indicators/struct.VolumeProfile.html) copy their window instead.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{Next, Peek};
+///
+/// let mut sma = SimpleMovingAverage::new(2).unwrap();
+/// sma.next(4.0);
+///
+/// assert_eq!(sma.peek(6.0), 5.0);
+/// assert_eq!(sma.peek(8.0), 6.0);
+/// assert_eq!(sma.next(8.0), 6.0);
+/// # }
+/// ```
+pub trait Peek<T>: Next<T> {
+    fn peek(&self, input: T) -> Self::Output;
+}
+
+/// Replaces the last input of an indicator with another one and returns the new output.
+///
+/// Useful in live trading to follow the bar that is still forming: feed its first tick with
+/// [next](trait.Next.html#tymethod.next), amend the indicator on every following tick and feed
+/// the next bar with `next` again once the bar is closed. The indicator ends up the same as if it
+/// had been fed the new input instead of the last one. An indicator without inputs is fed the
+/// input, as with `next`.
+///
+/// Implemented for every indicator in [indicators](indicators/index.html), for the same inputs
+/// as `Next`, and for the [combinators](combinators/index.html) of indicators that implement it,
+/// except:
+///
+/// * [SessionReset](indicators/struct.SessionReset.html) - a session boundary discards the
+///   state from before it, which the last input can't restore;
+/// * [MultiTimeframe](indicators/struct.MultiTimeframe.html) - the resampler folds every input
+///   into the bar that is forming and can't take it back out.
+///
+/// [DivergenceDetector](indicators/struct.DivergenceDetector.html) implements it when the wrapped
+/// indicator does.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{Amend, Next};
+///
+/// let mut sma = SimpleMovingAverage::new(2).unwrap();
+/// sma.next(4.0);
+///
+/// assert_eq!(sma.next(5.0), 4.5);
+/// assert_eq!(sma.amend(7.0), 5.5);
+/// assert_eq!(sma.amend(8.0), 6.0);
+/// assert_eq!(sma.next(10.0), 9.0);
+/// # }
+/// ```
+pub trait Amend<T>: Next<T> {
+    fn amend(&mut self, input: T) -> Self::Output;
+}
+
+/// Like [Next](trait.Next.html), but returns `None` while the indicator is warming up.
+///
+/// Implemented for every indicator that implements both `Next<T>` and
+/// [IsReady](trait.IsReady.html). The indicator is updated either way.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::TryNext;
+///
+/// let mut sma = SimpleMovingAverage::new(2).unwrap();
+/// assert_eq!(sma.try_next(4.0), None);
+/// assert_eq!(sma.try_next(6.0), Some(5.0));
+/// assert_eq!(sma.try_next(8.0), Some(7.0));
+/// # }
+/// ```
+pub trait TryNext<T> {
+    type Output;
+    fn try_next(&mut self, input: T) -> Option<Self::Output>;
+}
+
+impl<I: Next<T> + IsReady, T> TryNext<T> for I {
+    type Output = I::Output;
+
+    fn try_next(&mut self, input: T) -> Option<Self::Output> {
+        let output = self.next(input);
+        if self.is_ready() {
+            Some(output)
+        } else {
+            None
+        }
+    }
+}
+
+/// Feeds a slice of inputs to an indicator and collects the outputs.
+///
+/// Useful to backfill an indicator with historical data. The indicator keeps its state, so it
+/// can be fed with [next](trait.Next.html#tymethod.next) afterwards. It works on slices of:
+///
+/// * `f64` for indicators that implement `Next<f64>`
+/// * bars that implement [Close](trait.Close.html) (e.g. [DataItem](struct.DataItem.html)) for
+///   indicators that implement `Next<&T>`
+/// * pairs `(f64, f64)` for indicators that implement `Next<(f64, f64)>`, e.g.
+///   [Correlation](indicators/struct.Correlation.html) or the bar aggregators
+/// * pairs of bars `(T, U)` for indicators that implement `Next<(&T, &U)>`
+///
+/// Other inputs, like the timestamped ones of
+/// [SessionReset](indicators/struct.SessionReset.html) or
+/// [MultiTimeframe](indicators/struct.MultiTimeframe.html), are not covered, since a blanket
+/// implementation for any `Next<&T>` would overlap with the one for `f64`.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{AverageTrueRange, SimpleMovingAverage};
+/// use ta::{DataItem, NextBatch};
+///
+/// let mut sma = SimpleMovingAverage::new(2).unwrap();
+/// assert_eq!(sma.next_batch(&[4.0, 6.0, 8.0]), vec![4.0, 5.0, 7.0]);
+///
+/// let bars: Vec<DataItem> = [(10.0, 8.0, 9.0), (12.0, 9.0, 11.0)]
+///     .iter()
+///     .map(|&(high, low, close)| {
+///         DataItem::builder()
+///             .open(close).high(high).low(low).close(close)
+///             .volume(100.0)
+///             .build().unwrap()
+///     })
+///     .collect();
+/// let mut atr = AverageTrueRange::new(3).unwrap();
+/// assert_eq!(atr.next_batch(&bars), vec![2.0, 2.5]);
+/// ```
+#[cfg(feature = "std")]
+pub trait NextBatch<T> {
+    type Output;
+    fn next_batch(&mut self, input: &[T]) -> Vec<Self::Output>;
+}
+
+#[cfg(feature = "std")]
+impl<I: Next<f64>> NextBatch<f64> for I {
+    type Output = I::Output;
+
+    fn next_batch(&mut self, input: &[f64]) -> Vec<Self::Output> {
+        input.iter().map(|&value| self.next(value)).collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I, T, O> NextBatch<T> for I
+where
+    I: for<'a> Next<&'a T, Output = O>,
+    T: Close,
+{
+    type Output = O;
+
+    fn next_batch(&mut self, input: &[T]) -> Vec<Self::Output> {
+        input.iter().map(|item| self.next(item)).collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I: Next<(f64, f64)>> NextBatch<(f64, f64)> for I {
+    type Output = I::Output;
+
+    fn next_batch(&mut self, input: &[(f64, f64)]) -> Vec<Self::Output> {
+        input.iter().map(|&pair| self.next(pair)).collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I, T, U, O> NextBatch<(T, U)> for I
+where
+    I: for<'a> Next<(&'a T, &'a U), Output = O>,
+    T: Close,
+    U: Close,
+{
+    type Output = O;
+
+    fn next_batch(&mut self, input: &[(T, U)]) -> Vec<Self::Output> {
+        input.iter().map(|(a, b)| self.next((a, b))).collect()
+    }
+}
+
 /// Consumes a data item of type `T` and returns `Output`.
 ///
 /// Typically `T` can be `f64` or a struct similar to [DataItem](struct.DataItem.html), that implements
@@ -20,32 +370,39 @@ pub trait Period {
 /// [MACD](indicators/struct.MovingAverageConvergenceDivergence.html) it is `(f64, f64, f64)` since
 /// MACD returns 3 values.
 ///
+/// Indicators that compare two series, like [Correlation](indicators/struct.Correlation.html),
+/// consume pairs: `(f64, f64)` or `(&T, &U)`. The first element is the primary series
+/// (e.g. an asset), the second one is the reference series (e.g. a benchmark).
+///
 pub trait Next<T> {
     type Output;
     fn next(&mut self, input: T) -> Self::Output;
 }
 
 /// Open price of a particular period.
-pub trait Open {
-    fn open(&self) -> f64;
+pub trait Open<N = f64> {
+    fn open(&self) -> N;
 }
 
 /// Close price of a particular period.
-pub trait Close {
-    fn close(&self) -> f64;
+///
+/// The prices are `f64`, apart from bars fed to an indicator generic over its
+/// [value type](trait.Numeric.html), which implement the price traits for that type.
+pub trait Close<N = f64> {
+    fn close(&self) -> N;
 }
 
 /// Lowest price of a particular period.
-pub trait Low {
-    fn low(&self) -> f64;
+pub trait Low<N = f64> {
+    fn low(&self) -> N;
 }
 
 /// Highest price of a particular period.
-pub trait High {
-    fn high(&self) -> f64;
+pub trait High<N = f64> {
+    fn high(&self) -> N;
 }
 
 /// Trading volume of a particular trading period.
-pub trait Volume {
-    fn volume(&self) -> f64;
+pub trait Volume<N = f64> {
+    fn volume(&self) -> N;
 }
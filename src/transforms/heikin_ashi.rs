@@ -0,0 +1,181 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::{Checkpoint, Close, DataItem, High, Low, Next, Open, Reset, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Heikin-Ashi (HA) candles.
+///
+/// Converts regular OHLC bars into Heikin-Ashi bars, which average out the price action
+/// and make trends easier to see. The resulting [DataItem](crate::DataItem) can be passed
+/// to any indicator in place of the original bar.
+///
+/// # Formula
+///
+/// HA<sub>Close</sub> = (Open + High + Low + Close) / 4
+///
+/// HA<sub>Open</sub> = (HA<sub>Open, t-1</sub> + HA<sub>Close, t-1</sub>) / 2
+///
+/// HA<sub>High</sub> = max(High, HA<sub>Open</sub>, HA<sub>Close</sub>)
+///
+/// HA<sub>Low</sub> = min(Low, HA<sub>Open</sub>, HA<sub>Close</sub>)
+///
+/// The open of the very first bar is (Open + Close) / 2. Volume is passed through unchanged.
+///
+/// # Example
+///
+/// ```
+/// use ta::transforms::HeikinAshi;
+/// use ta::indicators::ExponentialMovingAverage;
+/// use ta::{Close, DataItem, Next, Open};
+///
+/// let mut ha = HeikinAshi::new();
+/// let mut ema = ExponentialMovingAverage::new(3).unwrap();
+///
+/// let bar = DataItem::builder()
+///     .open(10.0).high(14.0).low(8.0).close(12.0)
+///     .volume(1000.0)
+///     .build().unwrap();
+///
+/// let ha_bar = ha.next(&bar);
+/// assert_eq!(ha_bar.open(), 11.0);
+/// assert_eq!(ha_bar.close(), 11.0);
+///
+/// assert_eq!(ema.next(&ha_bar), 11.0);
+/// ```
+///
+/// # Links
+///
+/// * [Heikin-Ashi, Investopedia](https://www.investopedia.com/terms/h/heikinashi.asp)
+///
+#[doc(alias = "HA")]
+#[derive(Debug, Clone)]
+pub struct HeikinAshi {
+    prev: Option<(f64, f64)>,
+}
+
+/// State of [HeikinAshi](struct.HeikinAshi.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeikinAshiState {
+    /// Open and close of the previous Heikin-Ashi bar.
+    pub previous: Option<(f64, f64)>,
+}
+
+impl HeikinAshi {
+    pub fn new() -> Self {
+        Self { prev: None }
+    }
+}
+
+impl Checkpoint for HeikinAshi {
+    type State = HeikinAshiState;
+
+    fn state(&self) -> Self::State {
+        HeikinAshiState {
+            previous: self.prev,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        Ok(Self {
+            prev: state.previous,
+        })
+    }
+}
+
+impl_versioned_serde!(HeikinAshi, 1);
+
+impl<T: Open + High + Low + Close + Volume> Next<&T> for HeikinAshi {
+    type Output = DataItem;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let close = (input.open() + input.high() + input.low() + input.close()) / 4.0;
+        let open = match self.prev {
+            Some((prev_open, prev_close)) => (prev_open + prev_close) / 2.0,
+            None => (input.open() + input.close()) / 2.0,
+        };
+        let high = input.high().max(open).max(close);
+        let low = input.low().min(open).min(close);
+        self.prev = Some((open, close));
+
+        DataItem::new_unchecked(open, high, low, close, input.volume())
+    }
+}
+
+impl Reset for HeikinAshi {
+    fn reset(&mut self) {
+        self.prev = None;
+    }
+}
+
+impl Default for HeikinAshi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for HeikinAshi {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HA")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn ohlc(item: DataItem) -> (f64, f64, f64, f64) {
+        (item.open(), item.high(), item.low(), item.close())
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut ha = HeikinAshi::new();
+
+        let bar1 = Bar::new().open(10).high(14).low(8).close(12).volume(100.0);
+        let bar2 = Bar::new().open(12).high(16).low(11).close(15).volume(200.0);
+        let bar3 = Bar::new().open(15).high(15).low(9).close(10).volume(300.0);
+
+        assert_eq!(ohlc(ha.next(&bar1)), (11.0, 14.0, 8.0, 11.0));
+        assert_eq!(ohlc(ha.next(&bar2)), (11.0, 16.0, 11.0, 13.5));
+        assert_eq!(ohlc(ha.next(&bar3)), (12.25, 15.0, 9.0, 12.25));
+
+        assert_eq!(ha.next(&bar3).volume(), 300.0);
+    }
+
+    #[test]
+    fn test_next_extends_range() {
+        let mut ha = HeikinAshi::new();
+
+        ha.next(&Bar::new().open(20).high(22).low(18).close(21));
+        // HA open (20.375) is above the high of the bar
+        let out = ha.next(&Bar::new().open(11).high(12).low(10).close(11));
+        assert_eq!(ohlc(out), (20.375, 20.375, 10.0, 11.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ha = HeikinAshi::new();
+
+        let bar1 = Bar::new().open(10).high(14).low(8).close(12);
+        let bar2 = Bar::new().open(12).high(16).low(11).close(15);
+
+        ha.next(&bar1);
+        ha.reset();
+        assert_eq!(ohlc(ha.next(&bar2)), (13.5, 16.0, 11.0, 13.5));
+    }
+
+    #[test]
+    fn test_default() {
+        HeikinAshi::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let ha = HeikinAshi::new();
+        assert_eq!(format!("{}", ha), "HA");
+    }
+}
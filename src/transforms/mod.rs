@@ -0,0 +1,10 @@
+//! Transforms convert a stream of bars into another stream of bars,
+//! which can be fed into indicators instead of the original data.
+
+mod heikin_ashi;
+pub use self::heikin_ashi::{HeikinAshi, HeikinAshiState};
+
+#[cfg(feature = "std")]
+mod renko;
+#[cfg(feature = "std")]
+pub use self::renko::{BrickSize, RenkoBrick, RenkoBuilder, RenkoBuilderState};
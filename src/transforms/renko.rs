@@ -0,0 +1,371 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{AverageTrueRange, AverageTrueRangeState};
+use crate::{Checkpoint, Close, High, Low, Next, Open, Period, Reset, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Renko bricks.
+///
+/// Turns a price stream into Renko bricks: bricks of a fixed height that only form when price
+/// moves by a full brick from the last brick. A new brick in the same direction opens at the
+/// close of the last brick, a brick in the opposite direction opens at the open of the last
+/// brick, so a reversal needs a move of two bricks. Time and small fluctuations are ignored.
+///
+/// A single input can complete any number of bricks, including none, so every call to
+/// [next](crate::Next) returns a `Vec` of the completed bricks. Bricks implement
+/// [Open](crate::Open), [High](crate::High), [Low](crate::Low), [Close](crate::Close) and
+/// [Volume](crate::Volume), so they can be fed straight into other indicators. The volume of
+/// the inputs since the last brick goes to the first new brick.
+///
+/// The brick height is either fixed or the current
+/// [Average True Range](crate::indicators::AverageTrueRange) of the inputs. No bricks are formed while
+/// the brick height is 0.
+///
+/// # Parameters
+///
+/// * _size_ - [BrickSize::Fixed](enum.BrickSize.html) with a positive height, or
+///   [BrickSize::Atr](enum.BrickSize.html) with the ATR period (integer greater than 0).
+///   Default is ATR(14).
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::ExponentialMovingAverage;
+/// use ta::transforms::{BrickSize, RenkoBuilder};
+/// use ta::Next;
+///
+/// let mut renko = RenkoBuilder::new(BrickSize::Fixed(1.0)).unwrap();
+/// let mut ema = ExponentialMovingAverage::new(3).unwrap();
+///
+/// assert!(renko.next(10.0).is_empty());
+/// assert!(renko.next(10.5).is_empty());
+///
+/// let bricks = renko.next(12.2);
+/// assert_eq!(bricks.len(), 2);
+/// assert_eq!((bricks[1].open, bricks[1].close), (11.0, 12.0));
+///
+/// for brick in bricks.iter() {
+///     ema.next(brick);
+/// }
+/// ```
+#[doc(alias = "RENKO")]
+#[derive(Debug, Clone)]
+pub struct RenkoBuilder {
+    size: BrickSize,
+    atr: Option<AverageTrueRange>,
+    last: Option<(f64, f64)>,
+    volume: f64,
+}
+
+/// State of [RenkoBuilder](struct.RenkoBuilder.html), see [Checkpoint](../trait.Checkpoint.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenkoBuilderState {
+    pub size: BrickSize,
+    /// State of the ATR for a brick size of [BrickSize::Atr].
+    pub atr: Option<AverageTrueRangeState>,
+    /// Open and close of the last brick, or the first price before the first brick.
+    pub last: Option<(f64, f64)>,
+    /// Volume since the last brick.
+    pub volume: f64,
+}
+
+/// Height of the bricks of a [RenkoBuilder](struct.RenkoBuilder.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BrickSize {
+    /// Fixed height.
+    Fixed(f64),
+    /// Average True Range over the given number of inputs.
+    Atr(usize),
+}
+
+/// Completed Renko brick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenkoBrick {
+    pub open: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+impl RenkoBrick {
+    pub fn is_up(&self) -> bool {
+        self.close > self.open
+    }
+}
+
+impl Open for RenkoBrick {
+    fn open(&self) -> f64 {
+        self.open
+    }
+}
+
+impl High for RenkoBrick {
+    fn high(&self) -> f64 {
+        self.open.max(self.close)
+    }
+}
+
+impl Low for RenkoBrick {
+    fn low(&self) -> f64 {
+        self.open.min(self.close)
+    }
+}
+
+impl Close for RenkoBrick {
+    fn close(&self) -> f64 {
+        self.close
+    }
+}
+
+impl Volume for RenkoBrick {
+    fn volume(&self) -> f64 {
+        self.volume
+    }
+}
+
+impl RenkoBuilder {
+    pub fn new(size: BrickSize) -> Result<Self> {
+        let atr = match size {
+            BrickSize::Fixed(height) => {
+                if !height.is_finite() || height <= 0.0 {
+                    return Err(TaError::invalid_parameter("size", height, "> 0"));
+                }
+                None
+            }
+            BrickSize::Atr(period) => Some(AverageTrueRange::new(period)?),
+        };
+        Ok(Self {
+            size,
+            atr,
+            last: None,
+            volume: 0.0,
+        })
+    }
+
+    fn bricks(&mut self, price: f64, size: f64, volume: f64) -> Vec<RenkoBrick> {
+        self.volume += volume;
+        let mut bricks = Vec::new();
+
+        let (mut open, mut close) = match self.last {
+            Some(last) => last,
+            None => {
+                self.last = Some((price, price));
+                return bricks;
+            }
+        };
+        if size <= 0.0 {
+            return bricks;
+        }
+
+        loop {
+            let (top, bottom) = (open.max(close), open.min(close));
+            if price >= top + size {
+                open = top;
+                close = top + size;
+            } else if price <= bottom - size {
+                open = bottom;
+                close = bottom - size;
+            } else {
+                break;
+            }
+            bricks.push(RenkoBrick {
+                open,
+                close,
+                volume: self.volume,
+            });
+            self.volume = 0.0;
+        }
+
+        self.last = Some((open, close));
+        bricks
+    }
+}
+
+impl Checkpoint for RenkoBuilder {
+    type State = RenkoBuilderState;
+
+    fn state(&self) -> Self::State {
+        RenkoBuilderState {
+            size: self.size,
+            atr: self.atr.as_ref().map(AverageTrueRange::state),
+            last: self.last,
+            volume: self.volume,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Result<Self> {
+        let mut builder = Self::new(state.size).map_err(|_| TaError::InvalidState)?;
+        builder.atr = match (state.size, state.atr) {
+            (BrickSize::Atr(period), Some(atr)) => {
+                let atr = AverageTrueRange::from_state(atr)?;
+                if atr.period() != period {
+                    return Err(TaError::InvalidState);
+                }
+                Some(atr)
+            }
+            (BrickSize::Fixed(_), None) => None,
+            _ => return Err(TaError::InvalidState),
+        };
+        builder.last = state.last;
+        builder.volume = state.volume;
+        Ok(builder)
+    }
+}
+
+impl_versioned_serde!(RenkoBuilder, 1);
+
+impl Next<f64> for RenkoBuilder {
+    type Output = Vec<RenkoBrick>;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let size = match (self.size, self.atr.as_mut()) {
+            (_, Some(atr)) => atr.next(input),
+            (BrickSize::Fixed(height), None) => height,
+            _ => unreachable!(),
+        };
+        self.bricks(input, size, 0.0)
+    }
+}
+
+impl<T: High + Low + Close + Volume> Next<&T> for RenkoBuilder {
+    type Output = Vec<RenkoBrick>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let size = match (self.size, self.atr.as_mut()) {
+            (_, Some(atr)) => atr.next(input),
+            (BrickSize::Fixed(height), None) => height,
+            _ => unreachable!(),
+        };
+        self.bricks(input.close(), size, input.volume())
+    }
+}
+
+impl Reset for RenkoBuilder {
+    fn reset(&mut self) {
+        if let Some(atr) = self.atr.as_mut() {
+            atr.reset();
+        }
+        self.last = None;
+        self.volume = 0.0;
+    }
+}
+
+impl Default for RenkoBuilder {
+    fn default() -> Self {
+        Self::new(BrickSize::Atr(14)).unwrap()
+    }
+}
+
+impl fmt::Display for RenkoBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.size {
+            BrickSize::Fixed(height) => write!(f, "RENKO({})", height),
+            BrickSize::Atr(period) => write!(f, "RENKO(ATR({}))", period),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn brick(open: f64, close: f64) -> (f64, f64) {
+        (open, close)
+    }
+
+    fn bricks(out: Vec<RenkoBrick>) -> Vec<(f64, f64)> {
+        out.iter().map(|b| (b.open, b.close)).collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(RenkoBuilder::new(BrickSize::Fixed(0.0)).is_err());
+        assert!(RenkoBuilder::new(BrickSize::Fixed(-1.0)).is_err());
+        assert!(RenkoBuilder::new(BrickSize::Fixed(f64::INFINITY)).is_err());
+        assert!(RenkoBuilder::new(BrickSize::Atr(0)).is_err());
+        assert!(RenkoBuilder::new(BrickSize::Fixed(0.5)).is_ok());
+        assert!(RenkoBuilder::new(BrickSize::Atr(14)).is_ok());
+    }
+
+    #[test]
+    fn test_next_fixed() {
+        let mut renko = RenkoBuilder::new(BrickSize::Fixed(1.0)).unwrap();
+
+        assert!(renko.next(10.0).is_empty());
+        assert!(renko.next(9.5).is_empty());
+        assert_eq!(bricks(renko.next(11.0)), vec![brick(10.0, 11.0)]);
+        assert_eq!(
+            bricks(renko.next(13.5)),
+            vec![brick(11.0, 12.0), brick(12.0, 13.0)]
+        );
+
+        // A reversal needs two bricks
+        assert!(renko.next(12.1).is_empty());
+        assert_eq!(bricks(renko.next(11.0)), vec![brick(12.0, 11.0)]);
+        assert_eq!(bricks(renko.next(10.0)), vec![brick(11.0, 10.0)]);
+        assert_eq!(bricks(renko.next(12.0)), vec![brick(11.0, 12.0)]);
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut renko = RenkoBuilder::new(BrickSize::Fixed(2.0)).unwrap();
+
+        renko.next(&Bar::new().high(11).low(9).close(10).volume(100.0));
+        assert!(renko
+            .next(&Bar::new().high(12).low(10).close(11).volume(200.0))
+            .is_empty());
+
+        let out = renko.next(&Bar::new().high(15).low(11).close(14).volume(300.0));
+        assert_eq!(out.len(), 2);
+        assert!(out[0].is_up());
+        assert_eq!(out[0].volume, 600.0);
+        assert_eq!(out[1].volume, 0.0);
+        assert_eq!(out[1].high(), 14.0);
+        assert_eq!(out[1].low(), 12.0);
+    }
+
+    #[test]
+    fn test_next_atr() {
+        let mut renko = RenkoBuilder::new(BrickSize::Atr(3)).unwrap();
+
+        let bar = |high: f64, low: f64, close: f64| Bar::new().high(high).low(low).close(close);
+
+        renko.next(&bar(10.5, 9.5, 10.0));
+        // ATR is 1.25
+        assert!(renko.next(&bar(11.5, 10.5, 11.0)).is_empty());
+        assert_eq!(
+            bricks(renko.next(&bar(12.0, 11.0, 12.0))),
+            vec![brick(10.0, 11.125)]
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut renko = RenkoBuilder::new(BrickSize::Fixed(1.0)).unwrap();
+
+        renko.next(10.0);
+        renko.next(11.0);
+
+        renko.reset();
+        assert!(renko.next(20.0).is_empty());
+        assert_eq!(bricks(renko.next(19.0)), vec![brick(20.0, 19.0)]);
+    }
+
+    #[test]
+    fn test_default() {
+        RenkoBuilder::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let renko = RenkoBuilder::new(BrickSize::Fixed(0.5)).unwrap();
+        assert_eq!(format!("{}", renko), "RENKO(0.5)");
+
+        let renko = RenkoBuilder::new(BrickSize::Atr(10)).unwrap();
+        assert_eq!(format!("{}", renko), "RENKO(ATR(10))");
+    }
+}
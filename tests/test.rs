@@ -5,10 +5,27 @@ extern crate ta;
 
 #[cfg(test)]
 mod test {
-    #[cfg(feature = "serde")]
+    #[cfg(all(feature = "std", feature = "serde"))]
     mod serde {
-        use ta::indicators::SimpleMovingAverage;
-        use ta::Next;
+        use serde::de::DeserializeOwned;
+        use serde::Serialize;
+        use ta::bars::{PartialBar, TickBars, TickBarsState};
+        use ta::indicators::{
+            BollingerBands, BollingerBandsOutput, DetrendedPriceOscillator, Maximum, MaximumState,
+            MovingAverage, MovingAverageType, PriceSource, Session, SessionReset,
+            SimpleMovingAverage, SimpleMovingAverageState, Sourced, SourcedState, TillsonT3,
+            VolumeWeightedAveragePrice,
+        };
+        use ta::patterns::{Doji, DojiState};
+        use ta::transforms::{BrickSize, RenkoBuilder, RenkoBuilderState};
+        use ta::{Amend, Checkpoint, DataItem, Next};
+
+        // Same layout as the serialized form of an indicator
+        #[derive(Serialize)]
+        struct Versioned<S> {
+            version: u32,
+            state: S,
+        }
 
         // Simple smoke test that serde works (not sure if this is really necessary)
         #[test]
@@ -19,5 +36,1399 @@ mod test {
 
             assert_eq!(deserialized.next(2.0), macd.next(2.0));
         }
+
+        #[test]
+        fn test_serde_output() {
+            let mut bb = BollingerBands::new(3, 2.0).unwrap();
+            bb.next(2.0);
+            let output = bb.next(5.0);
+            let bytes = bincode::serialize(&output).unwrap();
+            let deserialized: BollingerBandsOutput = bincode::deserialize(&bytes).unwrap();
+
+            assert_eq!(deserialized, output);
+        }
+
+        #[test]
+        fn test_serde_state() {
+            let mut ma = MovingAverage::new(MovingAverageType::Smoothed, 3).unwrap();
+            ma.next(2.0);
+            ma.next(5.0);
+            let bytes = bincode::serialize(&ma).unwrap();
+            let mut deserialized: MovingAverage = bincode::deserialize(&bytes).unwrap();
+
+            assert_eq!(deserialized.state(), ma.state());
+            assert_eq!(deserialized.amend(4.0), ma.amend(4.0));
+            assert_eq!(deserialized.next(7.0), ma.next(7.0));
+        }
+
+        #[test]
+        fn test_serde_invalid_state() {
+            let state = SimpleMovingAverageState {
+                period: 2,
+                inputs: vec![1.0, 2.0, 3.0],
+                evicted: None,
+                previous_sum: 3.0,
+            };
+            let bytes = bincode::serialize(&Versioned { version: 1, state }).unwrap();
+            let error = bincode::deserialize::<SimpleMovingAverage>(&bytes).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "invalid indicator state of SimpleMovingAverage"
+            );
+
+            let state = SimpleMovingAverage::new(2).unwrap().state();
+            let bytes = bincode::serialize(&Versioned { version: 2, state }).unwrap();
+            let error = bincode::deserialize::<SimpleMovingAverage>(&bytes).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "unsupported version 2 of SimpleMovingAverage state, expected 1"
+            );
+        }
+
+        fn bar(close: f64, volume: f64) -> DataItem {
+            DataItem::builder()
+                .open(close)
+                .high(close + 1.0)
+                .low(close - 1.0)
+                .close(close)
+                .volume(volume)
+                .build()
+                .unwrap()
+        }
+
+        fn deserialize_error<T: DeserializeOwned, S: Serialize>(version: u32, state: S) -> String {
+            let bytes = bincode::serialize(&Versioned { version, state }).unwrap();
+            match bincode::deserialize::<T>(&bytes) {
+                Ok(_) => panic!("tampered state deserialized"),
+                Err(error) => error.to_string(),
+            }
+        }
+
+        #[test]
+        fn test_serde_tampered_period() {
+            let mut vwap = VolumeWeightedAveragePrice::new(1).unwrap();
+            vwap.next(&bar(10.0, 100.0));
+            let mut bytes = bincode::serialize(&vwap).unwrap();
+
+            // The period follows the version
+            bytes[4..12].copy_from_slice(&5u64.to_le_bytes());
+            let mut deserialized: VolumeWeightedAveragePrice =
+                bincode::deserialize(&bytes).unwrap();
+            let mut expected = VolumeWeightedAveragePrice::new(5).unwrap();
+            expected.next(&bar(10.0, 100.0));
+            assert_eq!(
+                deserialized.next(&bar(12.0, 50.0)),
+                expected.next(&bar(12.0, 50.0))
+            );
+
+            bytes[4..12].copy_from_slice(&0u64.to_le_bytes());
+            let error = bincode::deserialize::<VolumeWeightedAveragePrice>(&bytes).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "invalid indicator state of VolumeWeightedAveragePrice"
+            );
+        }
+
+        #[test]
+        fn test_serde_tampered_buffers() {
+            // Ring buffer with more values than the period
+            let mut vwap = VolumeWeightedAveragePrice::new(2).unwrap();
+            vwap.next(&bar(10.0, 100.0));
+            let mut state = vwap.state();
+            state.price_volumes = vec![1.0, 2.0, 3.0];
+            state.volumes = vec![1.0, 1.0, 1.0];
+            assert_eq!(
+                deserialize_error::<VolumeWeightedAveragePrice, _>(2, state),
+                "invalid indicator state of VolumeWeightedAveragePrice"
+            );
+
+            // Window of the extremes
+            let state = MaximumState {
+                period: 2,
+                inputs: vec![1.0, 2.0, 3.0],
+            };
+            assert_eq!(
+                deserialize_error::<Maximum, _>(1, state),
+                "invalid indicator state of Maximum"
+            );
+
+            // Delayed values
+            let mut dpo = DetrendedPriceOscillator::new(4).unwrap();
+            for x in 0..6 {
+                dpo.next(x as f64);
+            }
+            let mut state = dpo.state();
+            state.averages.push(1.0);
+            assert_eq!(
+                deserialize_error::<DetrendedPriceOscillator, _>(2, state),
+                "invalid indicator state of DetrendedPriceOscillator"
+            );
+
+            // Nested indicators
+            let mut state = TillsonT3::new(3, 0.7).unwrap().state();
+            state.emas.pop();
+            assert_eq!(
+                deserialize_error::<TillsonT3, _>(1, state),
+                "invalid indicator state of TillsonT3"
+            );
+
+            // Wrapped indicator
+            let state = SourcedState {
+                indicator: SimpleMovingAverageState {
+                    period: 0,
+                    inputs: vec![],
+                    evicted: None,
+                    previous_sum: 0.0,
+                },
+                source: PriceSource::Close,
+            };
+            assert_eq!(
+                deserialize_error::<Sourced<SimpleMovingAverage>, _>(1, state),
+                "invalid indicator state of Sourced"
+            );
+
+            // Bar that is forming
+            let state = TickBarsState {
+                ticks: 2,
+                partial: Some(PartialBar {
+                    open: 1.0,
+                    high: 1.0,
+                    low: 1.0,
+                    close: 1.0,
+                    volume: 1.0,
+                    value: 1.0,
+                    ticks: 5,
+                }),
+            };
+            assert_eq!(
+                deserialize_error::<TickBars, _>(1, state),
+                "invalid indicator state of TickBars"
+            );
+
+            // Brick size without its average
+            let state = RenkoBuilderState {
+                size: BrickSize::Atr(3),
+                atr: None,
+                last: None,
+                volume: 0.0,
+            };
+            assert_eq!(
+                deserialize_error::<RenkoBuilder, _>(1, state),
+                "invalid indicator state of RenkoBuilder"
+            );
+
+            // Parameter out of range
+            let state = DojiState {
+                max_body_ratio: -1.0,
+            };
+            assert_eq!(
+                deserialize_error::<Doji, _>(1, state),
+                "invalid indicator state of Doji"
+            );
+        }
+
+        // Same layout as the serialized form of a session
+        #[derive(Serialize)]
+        struct SessionFields {
+            utc_offset: i64,
+            start: i64,
+            weekends: [bool; 7],
+            holidays: Vec<i64>,
+        }
+
+        #[test]
+        fn test_serde_tampered_session() {
+            let session = Session::new(-5 * 60, 9, 30).unwrap();
+            let mut reset = SessionReset::new(SimpleMovingAverage::new(2).unwrap(), session);
+            reset.next((1704103200, 1.0));
+            let bytes = bincode::serialize(&reset).unwrap();
+            let deserialized: SessionReset<SimpleMovingAverage> =
+                bincode::deserialize(&bytes).unwrap();
+            assert_eq!(deserialized.state(), reset.state());
+
+            let session = SessionFields {
+                utc_offset: 19 * 60 * 60,
+                start: 0,
+                weekends: [false; 7],
+                holidays: vec![],
+            };
+            let state = (
+                SimpleMovingAverage::new(2).unwrap().state(),
+                session,
+                None::<i64>,
+            );
+            assert_eq!(
+                deserialize_error::<SessionReset<SimpleMovingAverage>, _>(1, state),
+                "invalid parameter utc_offset = 1140, expected -1080..=1080"
+            );
+
+            let session = SessionFields {
+                utc_offset: 0,
+                start: 24 * 60 * 60,
+                weekends: [false; 7],
+                holidays: vec![],
+            };
+            let state = (
+                SimpleMovingAverage::new(2).unwrap().state(),
+                session,
+                None::<i64>,
+            );
+            assert_eq!(
+                deserialize_error::<SessionReset<SimpleMovingAverage>, _>(1, state),
+                "invalid indicator state"
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod batch {
+        use ta::indicators::{
+            AverageTrueRange, Correlation, MovingAverageConvergenceDivergence as Macd,
+        };
+        use ta::{Close, DataItem, Next, NextBatch};
+
+        fn bar(i: usize) -> DataItem {
+            let close = 10.0 + (i as f64 * 0.7).sin();
+            DataItem::builder()
+                .open(close)
+                .high(close + 0.5)
+                .low(close - 0.5)
+                .close(close)
+                .volume(100.0)
+                .build()
+                .unwrap()
+        }
+
+        #[test]
+        fn test_next_batch() {
+            let bars: Vec<DataItem> = (0..50).map(bar).collect();
+            let closes: Vec<f64> = bars.iter().map(|bar| bar.close()).collect();
+
+            let mut macd = Macd::new(3, 6, 4).unwrap();
+            let expected: Vec<_> = closes.iter().map(|&close| macd.next(close)).collect();
+            let mut macd = Macd::new(3, 6, 4).unwrap();
+            let mut actual = macd.next_batch(&closes[..20]);
+            actual.extend(macd.next_batch(&closes[20..]));
+            assert_eq!(actual, expected);
+
+            let mut atr = AverageTrueRange::new(5).unwrap();
+            let expected: Vec<_> = bars.iter().map(|bar| atr.next(bar)).collect();
+            let mut atr = AverageTrueRange::new(5).unwrap();
+            assert_eq!(atr.next_batch(&bars), expected);
+        }
+
+        #[test]
+        fn test_next_batch_pairs() {
+            let bars: Vec<DataItem> = (0..30).map(bar).collect();
+            let pairs: Vec<(f64, f64)> = bars
+                .iter()
+                .zip(bars.iter().skip(3))
+                .map(|(a, b)| (a.close(), b.close()))
+                .collect();
+
+            let mut corr = Correlation::new(5).unwrap();
+            let expected: Vec<_> = pairs.iter().map(|&pair| corr.next(pair)).collect();
+            let mut corr = Correlation::new(5).unwrap();
+            assert_eq!(corr.next_batch(&pairs), expected);
+
+            let bar_pairs: Vec<(DataItem, DataItem)> = bars
+                .iter()
+                .cloned()
+                .zip(bars.iter().skip(3).cloned())
+                .collect();
+            let mut corr = Correlation::new(5).unwrap();
+            assert_eq!(corr.next_batch(&bar_pairs), expected);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod lookback {
+        use ta::indicators::*;
+        use ta::{Current, DataItem, DynIndicator, IsReady, Lookback, OutputValue};
+
+        fn bar(i: usize) -> DataItem {
+            let close = 10.0 + (i as f64 * 0.7).sin() + (i % 4) as f64 * 0.3;
+            DataItem::builder()
+                .open(close - 0.2)
+                .high(close + 0.5)
+                .low(close - 0.6)
+                .close(close)
+                .volume(100.0 + (i % 7) as f64 * 10.0)
+                .build()
+                .unwrap()
+        }
+
+        // Every indicator is a DynIndicator, must be ready exactly after `lookback()` inputs and
+        // must keep its last output as the current one
+        macro_rules! test_lookback {
+            ($($indicator:ident),* $(,)?) => {
+                $(
+                    let mut indicator = $indicator::default();
+                    assert!(indicator.current().is_none(), "{}", stringify!($indicator));
+                    let lookback = indicator.lookback();
+                    for i in 0..lookback + 10 {
+                        let value = indicator.next_value(&bar(i));
+                        assert_eq!(
+                            OutputValue::from(indicator.current()),
+                            value,
+                            "{}",
+                            stringify!($indicator)
+                        );
+                        assert_eq!(
+                            indicator.is_ready(),
+                            i + 1 >= lookback,
+                            "{} after {} inputs, lookback {}",
+                            stringify!($indicator),
+                            i + 1,
+                            lookback
+                        );
+                    }
+                )*
+            };
+        }
+
+        #[test]
+        fn test_lookback() {
+            test_lookback!(
+                AccelerationBands,
+                AcceleratorOscillator,
+                AccumulationDistributionLine,
+                Alligator,
+                AnchoredVwap,
+                AtrTrailingStop,
+                Autocorrelation,
+                AverageTrueRange,
+                AwesomeOscillator,
+                BalanceOfPower,
+                BollingerBands,
+                CenterOfGravity,
+                ChaikinMoneyFlow,
+                ChandeMomentumOscillator,
+                ChandelierExit,
+                ChoppinessIndex,
+                CommodityChannelIndex,
+                ConnorsRsi,
+                CumulativeReturn,
+                DetrendedPriceOscillator,
+                DisparityIndex,
+                DominantCyclePeriod,
+                DonchianChannel,
+                DrawdownStats,
+                EaseOfMovement,
+                EfficiencyRatio,
+                ElderRay,
+                ExponentialMovingAverage,
+                FastStochastic,
+                FibonacciLevels,
+                FisherTransform,
+                ForceIndex,
+                FractalAdaptiveMovingAverage,
+                FractalDimensionIndex,
+                GatorOscillator,
+                GeometricMovingAverage,
+                HarmonicMovingAverage,
+                HistoricalVolatility,
+                InsideOutsideBar,
+                KaufmanAdaptiveMovingAverage,
+                KeltnerChannel,
+                LaguerreFilter,
+                LaguerreRsi,
+                LinearRegression,
+                LogReturn,
+                MarketFacilitationIndex,
+                Maximum,
+                McGinleyDynamic,
+                MeanAbsoluteDeviation,
+                MedianPrice,
+                MesaAdaptiveMovingAverage,
+                Minimum,
+                MoneyFlowIndex,
+                MovingAverage,
+                MovingAverageConvergenceDivergence,
+                MovingAverageRibbon,
+                NegativeVolumeIndex,
+                OnBalanceVolume,
+                PercentagePriceOscillator,
+                PercentageVolumeOscillator,
+                PositiveVolumeIndex,
+                PriceVolumeTrend,
+                Qqe,
+                RangeVolatility,
+                RateOfChange,
+                RelativeStrengthIndex,
+                RelativeVigorIndex,
+                RollingEntropy,
+                RollingQuantile,
+                RoofingFilter,
+                SimpleMovingAverage,
+                SimpleReturn,
+                SlowStochastic,
+                SqueezeMomentum,
+                StandardDeviation,
+                StandardErrorBands,
+                StochasticMomentumIndex,
+                SuperSmoother,
+                SupportResistance,
+                SwingPoints,
+                TillsonT3,
+                TrendIntensityIndex,
+                TriangularMovingAverage,
+                TripleExponentialMovingAverage,
+                Trix,
+                TrueRange,
+                TrueStrengthIndex,
+                TwiggsMoneyFlow,
+                TypicalPrice,
+                UltimateOscillator,
+                VolumeOscillator,
+                VolumeProfile,
+                VolumeRateOfChange,
+                VolumeWeightedAveragePrice,
+                VolumeWeightedMovingAverage,
+                VortexIndicator,
+                WeightedClose,
+                WeightedMovingAverage,
+                WilliamsFractals,
+                ZScore,
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod peek {
+        use ta::bars::Resampler;
+        use ta::indicators::*;
+        use ta::{Close, Compose, DataItem, High, Next, Peek};
+
+        pub(super) fn bar(i: usize) -> DataItem {
+            let close = 10.0 + (i as f64 * 0.9).sin() * 2.0 + (i % 3) as f64 * 0.5;
+            DataItem::builder()
+                .open(close - 0.3)
+                .high(close + 0.4 + (i % 2) as f64 * 0.3)
+                .low(close - 0.7)
+                .close(close)
+                .volume(100.0 + (i % 5) as f64 * 20.0)
+                .build()
+                .unwrap()
+        }
+
+        // Peeking returns the output of the next input and leaves the indicator as it was
+        macro_rules! test_peek {
+            ($input:expr; $($indicator:expr),* $(,)?) => {
+                $(
+                    let mut indicator = $indicator;
+                    let mut twin = indicator.clone();
+                    for i in 0..40 {
+                        let peeked = indicator.peek($input(i + 100));
+                        assert_eq!(indicator.peek($input(i)), twin.clone().next($input(i)));
+                        assert_eq!(peeked, twin.clone().next($input(i + 100)));
+                        assert_eq!(indicator.next($input(i)), twin.next($input(i)));
+                    }
+                )*
+            };
+        }
+
+        #[test]
+        fn test_peek() {
+            let bars: Vec<DataItem> = (0..140).map(bar).collect();
+
+            test_peek!(
+                |i: usize| bars[i].close();
+                SimpleMovingAverage::new(5).unwrap(),
+                SimpleMovingAverageConst::<5>::new().unwrap(),
+                ExponentialMovingAverage::new(5).unwrap(),
+                WeightedMovingAverage::new(5).unwrap(),
+                SmoothedSimpleMovingAverage::new(5).unwrap(),
+                MovingAverage::new(MovingAverageType::Weighted, 5).unwrap(),
+                TriangularMovingAverage::new(5).unwrap(),
+                TripleExponentialMovingAverage::new(3).unwrap(),
+                TillsonT3::new(3, 0.7).unwrap(),
+                KaufmanAdaptiveMovingAverage::new(5, 2, 10).unwrap(),
+                GeometricMovingAverage::new(5).unwrap(),
+                HarmonicMovingAverage::new(5).unwrap(),
+                McGinleyDynamic::new(5, 0.6).unwrap(),
+                FractalAdaptiveMovingAverage::new(4).unwrap(),
+                SuperSmoother::new(5).unwrap(),
+                LaguerreFilter::new(0.5).unwrap(),
+                LaguerreRsi::new(0.5).unwrap(),
+                MovingAverageRibbon::new(3, 2, 8, RibbonSpacing::Geometric).unwrap(),
+                EfficiencyRatio::new(5).unwrap(),
+                RateOfChange::new(5).unwrap(),
+                VolumeRateOfChange::new(5).unwrap(),
+                LogReturn::new(3).unwrap(),
+                SimpleReturn::new(3).unwrap(),
+                MeanAbsoluteDeviation::new(5).unwrap(),
+                ZScore::new(5).unwrap(),
+                PercentagePriceOscillator::new(3, 6, 4).unwrap(),
+                PercentageVolumeOscillator::new(3, 6, 4).unwrap(),
+                VolumeOscillator::new(3, 6).unwrap(),
+                AwesomeOscillator::new(3, 5).unwrap(),
+                AcceleratorOscillator::new(3, 5, 3).unwrap(),
+                Trix::new(3, 3).unwrap(),
+                TrueStrengthIndex::new(5, 3, 3).unwrap(),
+                DisparityIndex::new(5, MovingAverageType::Simple).unwrap(),
+                KeltnerChannel::new(5, 2.0).unwrap(),
+                DonchianChannel::new(5).unwrap(),
+                HistoricalVolatility::new(5, 252.0).unwrap(),
+                ChandeMomentumOscillator::new(5).unwrap(),
+                FisherTransform::new(5).unwrap(),
+                RoofingFilter::new(10, 5).unwrap(),
+                LinearRegression::new(5).unwrap(),
+                StandardErrorBands::new(5, 2.0).unwrap(),
+                CenterOfGravity::new(5).unwrap(),
+                StochasticMomentumIndex::new(5, 3, 3).unwrap(),
+                DetrendedPriceOscillator::new(4).unwrap(),
+                CumulativeReturn::new(100.0).unwrap(),
+                SortinoRatio::new(5, 10.0).unwrap(),
+                FractalDimensionIndex::new(5).unwrap(),
+                AtrTrailingStop::new(5, 2.0).unwrap(),
+                Alligator::new(5, 3, 3, 2, 2, 1).unwrap(),
+                GatorOscillator::new(5, 3, 3, 2, 2, 1).unwrap(),
+                TrendIntensityIndex::new(4, 5).unwrap(),
+                Autocorrelation::new(5, 2).unwrap(),
+                ConnorsRsi::new(3, 2, 5).unwrap(),
+                RollingEntropy::new(5, 4, 0.05).unwrap(),
+                RollingQuantile::new(5, 0.3).unwrap(),
+                ValueAtRisk::new(5, 0.9, VarMethod::Historical).unwrap(),
+                ValueAtRisk::new(5, 0.9, VarMethod::Gaussian).unwrap(),
+                DivergenceDetector::new(RelativeStrengthIndex::new(3).unwrap(), 2, 10).unwrap(),
+                MesaAdaptiveMovingAverage::new(0.5, 0.05).unwrap(),
+                DominantCyclePeriod::new(),
+                Qqe::new(3, 2, 4.236, 2.0).unwrap(),
+                SqueezeMomentum::new(5, 2.0, 5, 1.5).unwrap(),
+                DrawdownStats::new(5, 252.0).unwrap(),
+                Maximum::new(5).unwrap(),
+                MaximumConst::<5>::new().unwrap(),
+                Minimum::new(5).unwrap(),
+                MinimumConst::<5>::new().unwrap(),
+                StandardDeviation::new(5).unwrap(),
+                BollingerBands::new(5, 2.0).unwrap(),
+                RelativeStrengthIndex::new(5).unwrap(),
+                MovingAverageConvergenceDivergence::new(3, 6, 4).unwrap(),
+                TrueRange::new(),
+                AverageTrueRange::new(5).unwrap(),
+                FastStochastic::new(5).unwrap(),
+                SlowStochastic::new(5, 3).unwrap(),
+                Sourced::new(SimpleMovingAverage::new(3).unwrap(), PriceSource::HLC3),
+                RelativeStrengthIndex::new(5)
+                    .unwrap()
+                    .then(ExponentialMovingAverage::new(3).unwrap()),
+                Minimum::new(3)
+                    .unwrap()
+                    .zip(Maximum::new(4).unwrap())
+                    .map(|(min, max)| max - min),
+            );
+            test_peek!(
+                |i: usize| &bars[i];
+                SimpleMovingAverage::new(5).unwrap(),
+                Maximum::new(5).unwrap(),
+                Minimum::new(5).unwrap(),
+                TrueRange::new(),
+                AverageTrueRange::new(5).unwrap(),
+                FastStochastic::new(5).unwrap(),
+                SlowStochastic::new(5, 3).unwrap(),
+                OnBalanceVolume::new(),
+                FractalAdaptiveMovingAverage::new(4).unwrap(),
+                VolumeWeightedMovingAverage::new(5).unwrap(),
+                VolumeWeightedAveragePrice::new(5).unwrap(),
+                AnchoredVwap::new(),
+                CumulativeReturn::new(100.0).unwrap(),
+                VolumeRateOfChange::new(5).unwrap(),
+                PercentageVolumeOscillator::new(3, 6, 4).unwrap(),
+                VolumeOscillator::new(3, 6).unwrap(),
+                AwesomeOscillator::new(3, 5).unwrap(),
+                AcceleratorOscillator::new(3, 5, 3).unwrap(),
+                CommodityChannelIndex::new(5).unwrap(),
+                ElderRay::new(5).unwrap(),
+                ForceIndex::new(5).unwrap(),
+                BalanceOfPower::new(5).unwrap(),
+                TypicalPrice::new(),
+                MedianPrice::new(),
+                WeightedClose::new(),
+                AccumulationDistributionLine::new(),
+                PriceVolumeTrend::new(),
+                KeltnerChannel::new(5, 2.0).unwrap(),
+                DonchianChannel::new(5).unwrap(),
+                ChandelierExit::new(5, 3.0).unwrap(),
+                AccelerationBands::new(5, 2.0).unwrap(),
+                MoneyFlowIndex::new(5).unwrap(),
+                ChaikinMoneyFlow::new(5).unwrap(),
+                FisherTransform::new(5).unwrap(),
+                EaseOfMovement::new(5, 100.0).unwrap(),
+                TwiggsMoneyFlow::new(3).unwrap(),
+                NegativeVolumeIndex::new(5).unwrap(),
+                PositiveVolumeIndex::new(5).unwrap(),
+                ChoppinessIndex::new(5).unwrap(),
+                VortexIndicator::new(5).unwrap(),
+                UltimateOscillator::new(2, 3, 5).unwrap(),
+                StochasticMomentumIndex::new(5, 3, 3).unwrap(),
+                AtrTrailingStop::new(5, 2.0).unwrap(),
+                Alligator::new(5, 3, 3, 2, 2, 1).unwrap(),
+                DivergenceDetector::new(RelativeStrengthIndex::new(3).unwrap(), 2, 10).unwrap(),
+                SwingPoints::new(2, 2).unwrap(),
+                WilliamsFractals::new(2).unwrap(),
+                FibonacciLevels::new(2).unwrap(),
+                SupportResistance::new(2, 0.01, 3).unwrap(),
+                VolumeProfile::new(5, ProfileBins::Count(4), 0.7).unwrap(),
+                InsideOutsideBar::new(2).unwrap(),
+                MarketFacilitationIndex::new(),
+                RangeVolatility::new(5, VolatilityEstimator::YangZhang, 252.0).unwrap(),
+                RelativeVigorIndex::new(3, 4).unwrap(),
+                SqueezeMomentum::new(5, 2.0, 5, 1.5).unwrap(),
+                Sourced::new(SimpleMovingAverage::new(3).unwrap(), PriceSource::HLC3),
+                AverageTrueRange::new(3)
+                    .unwrap()
+                    .then(SimpleMovingAverage::new(2).unwrap()),
+            );
+            test_peek!(
+                |i: usize| (bars[i].close(), bars[i].high());
+                Correlation::new(5).unwrap(),
+                Beta::new(5).unwrap(),
+                SpreadZScore::new(5).unwrap(),
+            );
+            test_peek!(
+                |i: usize| (i as i64 * 7 * 3600, bars[i].close());
+                SessionReset::new(SimpleMovingAverage::new(3).unwrap(), Session::new(0, 9, 30).unwrap()),
+            );
+            test_peek!(
+                |i: usize| (i as i64, &bars[i]);
+                MultiTimeframe::new(SimpleMovingAverage::new(2).unwrap(), Resampler::new(3, 0).unwrap()),
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod amend {
+        use super::peek::bar;
+        use ta::indicators::*;
+        use ta::{Amend, Close, Compose, Current, DataItem, High, Next};
+
+        // Amending the last input gives the same outputs as feeding the amended input instead
+        macro_rules! test_amend {
+            ($input:expr; $($indicator:expr),* $(,)?) => {
+                $(
+                    let mut indicator = $indicator;
+                    let mut twin = indicator.clone();
+                    assert_eq!(indicator.amend($input(0)), twin.next($input(0)));
+                    for i in 1..40 {
+                        indicator.next($input(i + 100));
+                        indicator.amend($input(i + 50));
+                        assert_eq!(indicator.amend($input(i)), twin.next($input(i)));
+                        assert_eq!(indicator.current(), twin.current());
+                    }
+                )*
+            };
+        }
+
+        #[test]
+        fn test_amend() {
+            let bars: Vec<DataItem> = (0..140).map(bar).collect();
+
+            test_amend!(
+                |i: usize| bars[i].close();
+                SimpleMovingAverage::new(5).unwrap(),
+                SimpleMovingAverageConst::<5>::new().unwrap(),
+                ExponentialMovingAverage::new(5).unwrap(),
+                WeightedMovingAverage::new(5).unwrap(),
+                SmoothedSimpleMovingAverage::new(5).unwrap(),
+                MovingAverage::new(MovingAverageType::Weighted, 5).unwrap(),
+                Maximum::new(5).unwrap(),
+                MaximumConst::<5>::new().unwrap(),
+                Minimum::new(5).unwrap(),
+                MinimumConst::<5>::new().unwrap(),
+                StandardDeviation::new(5).unwrap(),
+                BollingerBands::new(5, 2.0).unwrap(),
+                RelativeStrengthIndex::new(5).unwrap(),
+                MovingAverageConvergenceDivergence::new(3, 6, 4).unwrap(),
+                TrueRange::new(),
+                AverageTrueRange::new(5).unwrap(),
+                FastStochastic::new(5).unwrap(),
+                SlowStochastic::new(5, 3).unwrap(),
+                AwesomeOscillator::new(3, 5).unwrap(),
+                AcceleratorOscillator::new(3, 5, 3).unwrap(),
+                DisparityIndex::new(5, MovingAverageType::Simple).unwrap(),
+                DonchianChannel::new(5).unwrap(),
+                KeltnerChannel::new(5, 2.0).unwrap(),
+                PercentagePriceOscillator::new(3, 6, 4).unwrap(),
+                PercentageVolumeOscillator::new(3, 6, 4).unwrap(),
+                VolumeOscillator::new(3, 6).unwrap(),
+                TriangularMovingAverage::new(5).unwrap(),
+                TripleExponentialMovingAverage::new(3).unwrap(),
+                TillsonT3::new(3, 0.7).unwrap(),
+                Trix::new(3, 4).unwrap(),
+                TrueStrengthIndex::new(6, 3, 4).unwrap(),
+                HistoricalVolatility::new(5, 252.0).unwrap(),
+                AtrTrailingStop::new(5, 3.0).unwrap(),
+                McGinleyDynamic::new(5, 0.6).unwrap(),
+                SuperSmoother::new(5).unwrap(),
+                LaguerreFilter::new(0.5).unwrap(),
+                LaguerreRsi::new(0.5).unwrap(),
+                RoofingFilter::new(8, 5).unwrap(),
+                FisherTransform::new(5).unwrap(),
+                RateOfChange::new(5).unwrap(),
+                LogReturn::new(3).unwrap(),
+                SimpleReturn::new(3).unwrap(),
+                VolumeRateOfChange::new(5).unwrap(),
+                EfficiencyRatio::new(5).unwrap(),
+                FractalDimensionIndex::new(5).unwrap(),
+                GeometricMovingAverage::new(5).unwrap(),
+                HarmonicMovingAverage::new(5).unwrap(),
+                MeanAbsoluteDeviation::new(5).unwrap(),
+                LinearRegression::new(5).unwrap(),
+                CenterOfGravity::new(5).unwrap(),
+                ChandeMomentumOscillator::new(5).unwrap(),
+                KaufmanAdaptiveMovingAverage::new(5, 2, 10).unwrap(),
+                FractalAdaptiveMovingAverage::new(4).unwrap(),
+                StandardErrorBands::new(5, 2.0).unwrap(),
+                ZScore::new(5).unwrap(),
+                SortinoRatio::new(5, 10.0).unwrap(),
+                TrendIntensityIndex::new(4, 5).unwrap(),
+                DrawdownStats::new(5, 252.0).unwrap(),
+                RollingEntropy::new(5, 4, 0.05).unwrap(),
+                RollingQuantile::new(5, 0.3).unwrap(),
+                ConnorsRsi::new(3, 2, 5).unwrap(),
+                Qqe::new(3, 2, 4.236, 2.0).unwrap(),
+                ValueAtRisk::new(5, 0.9, VarMethod::Historical).unwrap(),
+                ValueAtRisk::new(5, 0.9, VarMethod::Gaussian).unwrap(),
+                StochasticMomentumIndex::new(5, 3, 3).unwrap(),
+                SqueezeMomentum::new(5, 2.0, 5, 1.5).unwrap(),
+                MovingAverageRibbon::new(3, 2, 8, RibbonSpacing::Geometric).unwrap(),
+                MesaAdaptiveMovingAverage::new(0.5, 0.05).unwrap(),
+                DominantCyclePeriod::new(),
+                Alligator::new(5, 3, 3, 2, 2, 1).unwrap(),
+                GatorOscillator::new(5, 3, 3, 2, 2, 1).unwrap(),
+                DetrendedPriceOscillator::new(4).unwrap(),
+                Autocorrelation::new(5, 2).unwrap(),
+                DivergenceDetector::new(RelativeStrengthIndex::new(3).unwrap(), 2, 10).unwrap(),
+                Sourced::new(SimpleMovingAverage::new(3).unwrap(), PriceSource::HLC3),
+                RelativeStrengthIndex::new(5)
+                    .unwrap()
+                    .then(ExponentialMovingAverage::new(3).unwrap()),
+                Minimum::new(3)
+                    .unwrap()
+                    .zip(Maximum::new(4).unwrap())
+                    .map(|(min, max)| max - min),
+            );
+            test_amend!(
+                |i: usize| &bars[i];
+                SimpleMovingAverage::new(5).unwrap(),
+                Maximum::new(5).unwrap(),
+                Minimum::new(5).unwrap(),
+                TrueRange::new(),
+                AverageTrueRange::new(5).unwrap(),
+                FastStochastic::new(5).unwrap(),
+                SlowStochastic::new(5, 3).unwrap(),
+                OnBalanceVolume::new(),
+                TypicalPrice::new(),
+                MedianPrice::new(),
+                WeightedClose::new(),
+                AccelerationBands::new(5, 2.0).unwrap(),
+                BalanceOfPower::new(5).unwrap(),
+                ChandelierExit::new(5, 3.0).unwrap(),
+                DonchianChannel::new(5).unwrap(),
+                ElderRay::new(5).unwrap(),
+                KeltnerChannel::new(5, 2.0).unwrap(),
+                AwesomeOscillator::new(3, 5).unwrap(),
+                AcceleratorOscillator::new(3, 5, 3).unwrap(),
+                PercentageVolumeOscillator::new(3, 6, 4).unwrap(),
+                VolumeOscillator::new(3, 6).unwrap(),
+                AccumulationDistributionLine::new(),
+                PriceVolumeTrend::new(),
+                AnchoredVwap::new(),
+                CumulativeReturn::new(100.0).unwrap(),
+                ForceIndex::new(5).unwrap(),
+                EaseOfMovement::new(5, 1000.0).unwrap(),
+                TwiggsMoneyFlow::new(5).unwrap(),
+                NegativeVolumeIndex::new(5).unwrap(),
+                PositiveVolumeIndex::new(5).unwrap(),
+                AtrTrailingStop::new(5, 3.0).unwrap(),
+                InsideOutsideBar::new(2).unwrap(),
+                MarketFacilitationIndex::new(),
+                FisherTransform::new(5).unwrap(),
+                FractalAdaptiveMovingAverage::new(4).unwrap(),
+                CommodityChannelIndex::new(5).unwrap(),
+                ChaikinMoneyFlow::new(5).unwrap(),
+                MoneyFlowIndex::new(5).unwrap(),
+                VolumeWeightedAveragePrice::new(5).unwrap(),
+                VolumeWeightedMovingAverage::new(5).unwrap(),
+                UltimateOscillator::new(2, 3, 5).unwrap(),
+                VortexIndicator::new(5).unwrap(),
+                ChoppinessIndex::new(5).unwrap(),
+                RangeVolatility::new(5, VolatilityEstimator::YangZhang, 252.0).unwrap(),
+                RelativeVigorIndex::new(3, 4).unwrap(),
+                StochasticMomentumIndex::new(5, 3, 3).unwrap(),
+                SqueezeMomentum::new(5, 2.0, 5, 1.5).unwrap(),
+                SwingPoints::new(2, 2).unwrap(),
+                FibonacciLevels::new(2).unwrap(),
+                DivergenceDetector::new(RelativeStrengthIndex::new(3).unwrap(), 2, 10).unwrap(),
+                SupportResistance::new(2, 0.01, 3).unwrap(),
+                VolumeProfile::new(5, ProfileBins::Count(4), 0.7).unwrap(),
+                VolumeProfile::anchored(ProfileBins::Width(0.25), 0.7).unwrap(),
+                WilliamsFractals::new(2).unwrap(),
+                Sourced::new(SimpleMovingAverage::new(3).unwrap(), PriceSource::HLC3),
+                AverageTrueRange::new(3)
+                    .unwrap()
+                    .then(SimpleMovingAverage::new(2).unwrap()),
+            );
+            test_amend!(
+                |i: usize| (bars[i].close(), bars[i].high());
+                Correlation::new(5).unwrap(),
+                Beta::new(5).unwrap(),
+                SpreadZScore::new(5).unwrap(),
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod set_period {
+        use super::peek::bar;
+        use ta::indicators::*;
+        use ta::{Close, DataItem, High, IsReady, Next, Period, SetPeriod};
+
+        // An indicator over a window of inputs continues as if it had the shorter period all along
+        macro_rules! test_set_period {
+            ($input:expr; $($indicator:expr => $resized:expr),* $(,)?) => {
+                $(
+                    let mut indicator = $indicator;
+                    let mut resized = $resized;
+                    for i in 0..30 {
+                        indicator.next($input(i));
+                        resized.next($input(i));
+                    }
+
+                    assert!(indicator.set_period(0).is_err());
+                    indicator.set_period(resized.period()).unwrap();
+                    assert_eq!(indicator.period(), resized.period());
+                    assert_eq!(indicator.is_ready(), resized.is_ready());
+                    for i in 30..40 {
+                        assert_eq!(
+                            format!("{:.9?}", indicator.next($input(i))),
+                            format!("{:.9?}", resized.next($input(i)))
+                        );
+                    }
+                )*
+            };
+        }
+
+        // Any other indicator starts over with the new period
+        macro_rules! test_set_period_restart {
+            ($input:expr; $($indicator:expr => $resized:expr),* $(,)?) => {
+                $(
+                    let mut indicator = $indicator;
+                    let mut resized = $resized;
+                    for i in 0..30 {
+                        indicator.next($input(i));
+                    }
+
+                    let period = indicator.period();
+                    assert!(indicator.set_period(0).is_err());
+                    assert_eq!(indicator.period(), period);
+                    indicator.set_period(resized.period()).unwrap();
+                    assert_eq!(indicator.period(), resized.period());
+                    assert!(!indicator.is_ready());
+                    for i in 30..40 {
+                        assert_eq!(
+                            format!("{:.9?}", indicator.next($input(i))),
+                            format!("{:.9?}", resized.next($input(i)))
+                        );
+                    }
+                )*
+            };
+        }
+
+        #[test]
+        fn test_set_period() {
+            let bars: Vec<DataItem> = (0..40).map(bar).collect();
+
+            test_set_period!(
+                |i: usize| bars[i].close();
+                SimpleMovingAverage::new(6).unwrap() => SimpleMovingAverage::new(3).unwrap(),
+                WeightedMovingAverage::new(6).unwrap() => WeightedMovingAverage::new(3).unwrap(),
+                MovingAverage::new(MovingAverageType::Simple, 5).unwrap()
+                    => MovingAverage::new(MovingAverageType::Simple, 3).unwrap(),
+                StandardDeviation::new(6).unwrap() => StandardDeviation::new(3).unwrap(),
+                BollingerBands::new(6, 2.0).unwrap() => BollingerBands::new(4, 2.0).unwrap(),
+                Maximum::new(6).unwrap() => Maximum::new(3).unwrap(),
+                Minimum::new(6).unwrap() => Minimum::new(3).unwrap(),
+            );
+            test_set_period!(
+                |i: usize| &bars[i];
+                Sourced::new(SimpleMovingAverage::new(6).unwrap(), PriceSource::HL2)
+                    => Sourced::new(SimpleMovingAverage::new(3).unwrap(), PriceSource::HL2),
+                Maximum::new(6).unwrap() => Maximum::new(3).unwrap(),
+            );
+
+            test_set_period_restart!(
+                |i: usize| bars[i].close() / 100.0 - 1.0;
+                SortinoRatio::new(6, 0.0).unwrap() => SortinoRatio::new(3, 0.0).unwrap(),
+                ValueAtRisk::new(6, 0.95, VarMethod::Historical).unwrap()
+                    => ValueAtRisk::new(3, 0.95, VarMethod::Historical).unwrap(),
+            );
+            test_set_period_restart!(
+                |i: usize| &bars[i];
+                AccelerationBands::new(6, 4.0).unwrap() => AccelerationBands::new(3, 4.0).unwrap(),
+                AtrTrailingStop::new(6, 3.0).unwrap() => AtrTrailingStop::new(3, 3.0).unwrap(),
+                BalanceOfPower::new(6).unwrap() => BalanceOfPower::new(3).unwrap(),
+                CenterOfGravity::new(6).unwrap() => CenterOfGravity::new(3).unwrap(),
+                ChaikinMoneyFlow::new(6).unwrap() => ChaikinMoneyFlow::new(3).unwrap(),
+                ChandeMomentumOscillator::new(6).unwrap()
+                    => ChandeMomentumOscillator::new(3).unwrap(),
+                ChandelierExit::new(6, 3.0).unwrap() => ChandelierExit::new(3, 3.0).unwrap(),
+                ChoppinessIndex::new(6).unwrap() => ChoppinessIndex::new(3).unwrap(),
+                CommodityChannelIndex::new(6).unwrap() => CommodityChannelIndex::new(3).unwrap(),
+                DetrendedPriceOscillator::new(6).unwrap()
+                    => DetrendedPriceOscillator::new(3).unwrap(),
+                DisparityIndex::new(6, MovingAverageType::Exponential).unwrap()
+                    => DisparityIndex::new(3, MovingAverageType::Exponential).unwrap(),
+                DonchianChannel::new(6).unwrap() => DonchianChannel::new(3).unwrap(),
+                DrawdownStats::new(6, 252.0).unwrap() => DrawdownStats::new(3, 252.0).unwrap(),
+                EaseOfMovement::new(6, 1e6).unwrap() => EaseOfMovement::new(3, 1e6).unwrap(),
+                EfficiencyRatio::new(6).unwrap() => EfficiencyRatio::new(3).unwrap(),
+                ElderRay::new(6).unwrap() => ElderRay::new(3).unwrap(),
+                FastStochastic::new(6).unwrap() => FastStochastic::new(3).unwrap(),
+                FisherTransform::new(6).unwrap() => FisherTransform::new(3).unwrap(),
+                ForceIndex::new(6).unwrap() => ForceIndex::new(3).unwrap(),
+                FractalAdaptiveMovingAverage::new(6).unwrap()
+                    => FractalAdaptiveMovingAverage::new(4).unwrap(),
+                FractalDimensionIndex::new(6).unwrap() => FractalDimensionIndex::new(3).unwrap(),
+                GeometricMovingAverage::new(6).unwrap() => GeometricMovingAverage::new(3).unwrap(),
+                HarmonicMovingAverage::new(6).unwrap() => HarmonicMovingAverage::new(3).unwrap(),
+                HistoricalVolatility::new(6, 252.0).unwrap()
+                    => HistoricalVolatility::new(3, 252.0).unwrap(),
+                InsideOutsideBar::new(6).unwrap() => InsideOutsideBar::new(3).unwrap(),
+                KeltnerChannel::new(6, 2.0).unwrap() => KeltnerChannel::new(3, 2.0).unwrap(),
+                LinearRegression::new(6).unwrap() => LinearRegression::new(3).unwrap(),
+                LogReturn::new(6).unwrap() => LogReturn::new(3).unwrap(),
+                McGinleyDynamic::new(6, 0.6).unwrap() => McGinleyDynamic::new(3, 0.6).unwrap(),
+                MeanAbsoluteDeviation::new(6).unwrap() => MeanAbsoluteDeviation::new(3).unwrap(),
+                MoneyFlowIndex::new(6).unwrap() => MoneyFlowIndex::new(3).unwrap(),
+                NegativeVolumeIndex::new(6).unwrap() => NegativeVolumeIndex::new(3).unwrap(),
+                PositiveVolumeIndex::new(6).unwrap() => PositiveVolumeIndex::new(3).unwrap(),
+                RangeVolatility::new(6, VolatilityEstimator::Parkinson, 252.0).unwrap()
+                    => RangeVolatility::new(3, VolatilityEstimator::Parkinson, 252.0).unwrap(),
+                RateOfChange::new(6).unwrap() => RateOfChange::new(3).unwrap(),
+                RollingEntropy::new(6, 4, 0.1).unwrap() => RollingEntropy::new(3, 4, 0.1).unwrap(),
+                RollingQuantile::new(6, 0.5).unwrap() => RollingQuantile::new(3, 0.5).unwrap(),
+                SimpleReturn::new(6).unwrap() => SimpleReturn::new(3).unwrap(),
+                StandardErrorBands::new(6, 2.0).unwrap()
+                    => StandardErrorBands::new(3, 2.0).unwrap(),
+                SuperSmoother::new(6).unwrap() => SuperSmoother::new(3).unwrap(),
+                TillsonT3::new(6, 0.7).unwrap() => TillsonT3::new(3, 0.7).unwrap(),
+                TriangularMovingAverage::new(6).unwrap()
+                    => TriangularMovingAverage::new(3).unwrap(),
+                TripleExponentialMovingAverage::new(6).unwrap()
+                    => TripleExponentialMovingAverage::new(3).unwrap(),
+                TwiggsMoneyFlow::new(6).unwrap() => TwiggsMoneyFlow::new(3).unwrap(),
+                VolumeRateOfChange::new(6).unwrap() => VolumeRateOfChange::new(3).unwrap(),
+                VolumeWeightedAveragePrice::new(6).unwrap()
+                    => VolumeWeightedAveragePrice::new(3).unwrap(),
+                VolumeWeightedMovingAverage::new(6).unwrap()
+                    => VolumeWeightedMovingAverage::new(3).unwrap(),
+                VortexIndicator::new(6).unwrap() => VortexIndicator::new(3).unwrap(),
+                WilliamsFractals::new(6).unwrap() => WilliamsFractals::new(3).unwrap(),
+                ZScore::new(6).unwrap() => ZScore::new(3).unwrap(),
+            );
+            test_set_period_restart!(
+                |i: usize| (bars[i].close(), bars[i].high());
+                Correlation::new(6).unwrap() => Correlation::new(3).unwrap(),
+                Beta::new(6).unwrap() => Beta::new(3).unwrap(),
+                SpreadZScore::new(6).unwrap() => SpreadZScore::new(3).unwrap(),
+            );
+
+            // A longer window keeps the inputs there are and warms up again
+            let mut sma = SimpleMovingAverage::new(3).unwrap();
+            let mut resized = SimpleMovingAverage::new(5).unwrap();
+            for bar in &bars[..30] {
+                sma.next(bar);
+            }
+            for bar in &bars[27..30] {
+                resized.next(bar);
+            }
+            sma.set_period(5).unwrap();
+            assert!(!sma.is_ready());
+            for bar in &bars[30..] {
+                assert_eq!(sma.next(bar), resized.next(bar));
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod checkpoint {
+        use super::peek::bar;
+        use std::fmt::Debug;
+        use ta::bars::Resampler;
+        use ta::errors::TaError;
+        use ta::indicators::*;
+        use ta::{Amend, Checkpoint, Close, Compose, Current, DataItem, High, Next, Reset};
+
+        fn restore<I: Checkpoint>(indicator: &I) -> I
+        where
+            I::State: Clone + PartialEq + Debug,
+        {
+            let state = indicator.state();
+            let restored = I::from_state(state.clone())
+                .unwrap_or_else(|e| panic!("{}: {:?}", std::any::type_name::<I>(), e));
+            assert_eq!(restored.state(), state);
+            restored
+        }
+
+        // An indicator restored from its state continues exactly as the indicator itself
+        macro_rules! test_checkpoint {
+            ($input:expr; $($indicator:expr),* $(,)?) => {
+                $(
+                    let mut indicator = $indicator;
+                    for steps in [0, 3, 17] {
+                        indicator.reset();
+                        for i in 0..steps {
+                            indicator.next($input(i));
+                        }
+
+                        let mut restored = restore(&indicator);
+                        assert_eq!(
+                            format!("{:?}", restored.current()),
+                            format!("{:?}", indicator.current())
+                        );
+                        for i in 20..30 {
+                            assert_eq!(
+                                format!("{:?}", restored.amend($input(i))),
+                                format!("{:?}", indicator.amend($input(i)))
+                            );
+                            assert_eq!(
+                                format!("{:?}", restored.next($input(i + 10))),
+                                format!("{:?}", indicator.next($input(i + 10)))
+                            );
+                        }
+                    }
+                )*
+            };
+        }
+
+        #[test]
+        fn test_checkpoint() {
+            let bars: Vec<DataItem> = (0..40).map(bar).collect();
+
+            test_checkpoint!(
+                |i: usize| bars[i].close();
+                SimpleMovingAverage::new(5).unwrap(),
+                ExponentialMovingAverage::new(5).unwrap(),
+                WeightedMovingAverage::new(5).unwrap(),
+                SmoothedSimpleMovingAverage::new(5).unwrap(),
+                MovingAverage::new(MovingAverageType::Exponential, 5).unwrap(),
+                Maximum::new(5).unwrap(),
+                Minimum::new(5).unwrap(),
+                StandardDeviation::new(5).unwrap(),
+                BollingerBands::new(5, 2.0).unwrap(),
+                RelativeStrengthIndex::new(5).unwrap(),
+                MovingAverageConvergenceDivergence::new(3, 6, 4).unwrap(),
+                TrueRange::new(),
+                AverageTrueRange::new(5).unwrap(),
+                FastStochastic::new(5).unwrap(),
+                SlowStochastic::new(5, 3).unwrap(),
+                SimpleMovingAverage::new(3)
+                    .unwrap()
+                    .then(ExponentialMovingAverage::new(2).unwrap()),
+                SimpleMovingAverage::new(3)
+                    .unwrap()
+                    .zip(Maximum::new(4).unwrap()),
+                LaguerreFilter::new(0.5).unwrap(),
+                LaguerreRsi::new(0.5).unwrap(),
+                SuperSmoother::new(5).unwrap(),
+                McGinleyDynamic::new(5, 0.6).unwrap(),
+                CumulativeReturn::new(100.0).unwrap(),
+                AwesomeOscillator::new(3, 5).unwrap(),
+                AcceleratorOscillator::new(3, 5, 3).unwrap(),
+                AtrTrailingStop::new(5, 2.0).unwrap(),
+                DisparityIndex::new(5, MovingAverageType::Simple).unwrap(),
+                DonchianChannel::new(5).unwrap(),
+                FisherTransform::new(5).unwrap(),
+                HistoricalVolatility::new(5, 252.0).unwrap(),
+                KeltnerChannel::new(5, 2.0).unwrap(),
+                PercentagePriceOscillator::new(3, 6, 4).unwrap(),
+                PercentageVolumeOscillator::new(3, 6, 4).unwrap(),
+                RoofingFilter::new(10, 5).unwrap(),
+                TillsonT3::new(3, 0.7).unwrap(),
+                TriangularMovingAverage::new(5).unwrap(),
+                TripleExponentialMovingAverage::new(3).unwrap(),
+                Trix::new(3, 3).unwrap(),
+                TrueStrengthIndex::new(5, 3, 3).unwrap(),
+                VolumeOscillator::new(3, 6).unwrap(),
+                RateOfChange::new(5).unwrap(),
+                LogReturn::new(3).unwrap(),
+                SimpleReturn::new(3).unwrap(),
+                VolumeRateOfChange::new(5).unwrap(),
+                EfficiencyRatio::new(5).unwrap(),
+                FractalDimensionIndex::new(5).unwrap(),
+                MeanAbsoluteDeviation::new(5).unwrap(),
+                GeometricMovingAverage::new(5).unwrap(),
+                HarmonicMovingAverage::new(5).unwrap(),
+                CenterOfGravity::new(5).unwrap(),
+                ChandeMomentumOscillator::new(5).unwrap(),
+                LinearRegression::new(5).unwrap(),
+                FractalAdaptiveMovingAverage::new(4).unwrap(),
+                KaufmanAdaptiveMovingAverage::new(5, 2, 10).unwrap(),
+                StandardErrorBands::new(5, 2.0).unwrap(),
+                ZScore::new(5).unwrap(),
+                SortinoRatio::new(5, 10.0).unwrap(),
+                TrendIntensityIndex::new(4, 5).unwrap(),
+                DrawdownStats::new(5, 252.0).unwrap(),
+                RollingEntropy::new(5, 4, 0.05).unwrap(),
+                RollingQuantile::new(5, 0.3).unwrap(),
+                ConnorsRsi::new(3, 2, 5).unwrap(),
+                Qqe::new(3, 2, 4.236, 2.0).unwrap(),
+                ValueAtRisk::new(5, 0.9, VarMethod::Historical).unwrap(),
+                ValueAtRisk::new(5, 0.9, VarMethod::Gaussian).unwrap(),
+                SqueezeMomentum::new(5, 2.0, 5, 1.5).unwrap(),
+                StochasticMomentumIndex::new(5, 3, 3).unwrap(),
+                DominantCyclePeriod::new(),
+                MesaAdaptiveMovingAverage::new(0.5, 0.05).unwrap(),
+                MovingAverageRibbon::new(3, 2, 8, RibbonSpacing::Geometric).unwrap(),
+                Alligator::new(5, 3, 3, 2, 2, 1).unwrap(),
+                Autocorrelation::new(5, 2).unwrap(),
+                DetrendedPriceOscillator::new(4).unwrap(),
+                GatorOscillator::new(5, 3, 3, 2, 2, 1).unwrap(),
+                DivergenceDetector::new(RelativeStrengthIndex::new(3).unwrap(), 2, 10).unwrap(),
+            );
+            test_checkpoint!(
+                |i: usize| &bars[i];
+                Sourced::new(SimpleMovingAverage::new(5).unwrap(), PriceSource::HLC3),
+                SimpleMovingAverage::new(5).unwrap(),
+                TrueRange::new(),
+                AverageTrueRange::new(5).unwrap(),
+                FastStochastic::new(5).unwrap(),
+                SlowStochastic::new(5, 3).unwrap(),
+                OnBalanceVolume::new(),
+                AccumulationDistributionLine::new(),
+                AnchoredVwap::new(),
+                CumulativeReturn::new(100.0).unwrap(),
+                PriceVolumeTrend::new(),
+                TypicalPrice::new(),
+                MedianPrice::new(),
+                WeightedClose::new(),
+                InsideOutsideBar::new(2).unwrap(),
+                MarketFacilitationIndex::new(),
+                AccelerationBands::new(5, 2.0).unwrap(),
+                AtrTrailingStop::new(5, 2.0).unwrap(),
+                BalanceOfPower::new(5).unwrap(),
+                ChandelierExit::new(5, 3.0).unwrap(),
+                DonchianChannel::new(5).unwrap(),
+                EaseOfMovement::new(5, 100.0).unwrap(),
+                ElderRay::new(5).unwrap(),
+                ForceIndex::new(5).unwrap(),
+                KeltnerChannel::new(5, 2.0).unwrap(),
+                NegativeVolumeIndex::new(5).unwrap(),
+                PositiveVolumeIndex::new(5).unwrap(),
+                TwiggsMoneyFlow::new(3).unwrap(),
+                VolumeOscillator::new(3, 6).unwrap(),
+                FractalAdaptiveMovingAverage::new(4).unwrap(),
+                CommodityChannelIndex::new(5).unwrap(),
+                MoneyFlowIndex::new(5).unwrap(),
+                ChaikinMoneyFlow::new(5).unwrap(),
+                VolumeWeightedAveragePrice::new(5).unwrap(),
+                VolumeWeightedMovingAverage::new(5).unwrap(),
+                UltimateOscillator::new(2, 3, 5).unwrap(),
+                VortexIndicator::new(5).unwrap(),
+                ChoppinessIndex::new(5).unwrap(),
+                RangeVolatility::new(5, VolatilityEstimator::YangZhang, 252.0).unwrap(),
+                RelativeVigorIndex::new(3, 4).unwrap(),
+                SqueezeMomentum::new(5, 2.0, 5, 1.5).unwrap(),
+                StochasticMomentumIndex::new(5, 3, 3).unwrap(),
+                SwingPoints::new(2, 2).unwrap(),
+                FibonacciLevels::new(2).unwrap(),
+                SupportResistance::new(2, 0.01, 3).unwrap(),
+                VolumeProfile::new(5, ProfileBins::Count(4), 0.7).unwrap(),
+                VolumeProfile::anchored(ProfileBins::Width(0.25), 0.7).unwrap(),
+                WilliamsFractals::new(2).unwrap(),
+                DivergenceDetector::new(RelativeStrengthIndex::new(3).unwrap(), 2, 10).unwrap(),
+            );
+            test_checkpoint!(
+                |i: usize| (bars[i].close(), bars[i].high());
+                Correlation::new(5).unwrap(),
+                Beta::new(5).unwrap(),
+                SpreadZScore::new(5).unwrap(),
+            );
+        }
+
+        // Same for indicators which can't amend their last input
+        macro_rules! test_checkpoint_next {
+            ($input:expr; $($indicator:expr),* $(,)?) => {
+                $(
+                    let mut indicator = $indicator;
+                    for steps in [0, 3, 17] {
+                        indicator.reset();
+                        for i in 0..steps {
+                            indicator.next($input(i));
+                        }
+
+                        let mut restored = restore(&indicator);
+                        for i in steps..steps + 20 {
+                            assert_eq!(
+                                format!("{:?}", restored.next($input(i))),
+                                format!("{:?}", indicator.next($input(i)))
+                            );
+                        }
+                    }
+                )*
+            };
+        }
+
+        #[test]
+        fn test_checkpoint_next() {
+            let bars: Vec<DataItem> = (0..40).map(bar).collect();
+
+            test_checkpoint_next!(
+                |i: usize| (i as i64 * 7 * 3600, bars[i].close());
+                SessionReset::new(SimpleMovingAverage::new(3).unwrap(), Session::new(0, 9, 30).unwrap()),
+            );
+            test_checkpoint_next!(
+                |i: usize| (i as i64, &bars[i]);
+                MultiTimeframe::new(SimpleMovingAverage::new(2).unwrap(), Resampler::new(3, 0).unwrap()),
+            );
+        }
+
+        #[test]
+        fn test_invalid_state() {
+            let mut sma = SimpleMovingAverage::new(3).unwrap();
+            let mut ema = ExponentialMovingAverage::new(3).unwrap();
+            let mut rsi = RelativeStrengthIndex::new(3).unwrap();
+            for i in 0..5 {
+                sma.next(i as f64);
+                ema.next(i as f64);
+                rsi.next(i as f64);
+            }
+
+            let mut state = sma.state();
+            state.inputs.push(5.0);
+            assert_eq!(
+                SimpleMovingAverage::from_state(state).unwrap_err(),
+                TaError::InvalidState
+            );
+
+            let mut state = ema.state();
+            state.period = 0;
+            assert!(ExponentialMovingAverage::from_state(state).is_err());
+
+            let mut state = rsi.state();
+            state.last = None;
+            assert!(RelativeStrengthIndex::from_state(state).is_err());
+
+            let mut state = rsi.state();
+            state.down.period = 4;
+            assert!(RelativeStrengthIndex::from_state(state).is_err());
+
+            let mut ssma = SmoothedSimpleMovingAverage::new(3).unwrap();
+            for i in 0..5 {
+                ssma.next(i as f64);
+            }
+            let mut state = ssma.state();
+            state.count = 4;
+            assert!(SmoothedSimpleMovingAverage::from_state(state).is_err());
+
+            let mut state = ssma.state();
+            state.previous_count = 1;
+            assert!(SmoothedSimpleMovingAverage::from_state(state).is_err());
+
+            let mut state = SmoothedSimpleMovingAverage::new(3).unwrap().state();
+            state.sum = f64::NAN;
+            assert!(SmoothedSimpleMovingAverage::from_state(state).is_err());
+
+            let state = TrueRangeState {
+                close: None,
+                previous_close: None,
+                value: Some(1.0),
+            };
+            assert!(TrueRange::from_state(state).is_err());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod numeric {
+        use super::peek::bar;
+        use ta::indicators::{generic, *};
+        use ta::{Amend, Close, DataItem, High, Low, Next, Peek};
+
+        struct Bar32 {
+            high: f32,
+            low: f32,
+            close: f32,
+        }
+
+        impl High<f32> for Bar32 {
+            fn high(&self) -> f32 {
+                self.high
+            }
+        }
+
+        impl Low<f32> for Bar32 {
+            fn low(&self) -> f32 {
+                self.low
+            }
+        }
+
+        impl Close<f32> for Bar32 {
+            fn close(&self) -> f32 {
+                self.close
+            }
+        }
+
+        fn bar32(bar: &DataItem) -> Bar32 {
+            Bar32 {
+                high: bar.high() as f32,
+                low: bar.low() as f32,
+                close: bar.close() as f32,
+            }
+        }
+
+        // An indicator of `f32` values follows the same indicator of `f64` values
+        macro_rules! test_f32 {
+            ($input:expr, $input32:expr; $(($indicator:expr, $indicator32:expr)),* $(,)?) => {
+                $(
+                    let mut indicator = $indicator;
+                    let mut indicator32 = $indicator32;
+                    for i in 0..40 {
+                        let peeked = indicator32.peek($input32(i));
+                        let output = indicator32.next($input32(i));
+                        assert_eq!(output, peeked);
+                        assert!((output - indicator.next($input(i)) as f32).abs() < 1e-3);
+
+                        let output = indicator32.amend($input32(i + 100));
+                        assert!((output - indicator.amend($input(i + 100)) as f32).abs() < 1e-3);
+                        indicator32.amend($input32(i));
+                        indicator.amend($input(i));
+                    }
+                )*
+            };
+        }
+
+        #[test]
+        fn test_f32() {
+            let bars: Vec<DataItem> = (0..140).map(bar).collect();
+            let bars32: Vec<Bar32> = bars.iter().map(bar32).collect();
+
+            test_f32!(
+                |i: usize| bars[i].close(), |i: usize| bars[i].close() as f32;
+                (SimpleMovingAverage::new(5).unwrap(), generic::SimpleMovingAverage::<f32>::new(5).unwrap()),
+                (ExponentialMovingAverage::new(5).unwrap(), generic::ExponentialMovingAverage::<f32>::new(5).unwrap()),
+                (WeightedMovingAverage::new(5).unwrap(), generic::WeightedMovingAverage::<f32>::new(5).unwrap()),
+                (SmoothedSimpleMovingAverage::new(5).unwrap(), generic::SmoothedSimpleMovingAverage::<f32>::new(5).unwrap()),
+                (Maximum::new(5).unwrap(), generic::Maximum::<f32>::new(5).unwrap()),
+                (Minimum::new(5).unwrap(), generic::Minimum::<f32>::new(5).unwrap()),
+                (StandardDeviation::new(5).unwrap(), generic::StandardDeviation::<f32>::new(5).unwrap()),
+                (RelativeStrengthIndex::new(5).unwrap(), generic::RelativeStrengthIndex::<f32>::new(5).unwrap()),
+            );
+            test_f32!(
+                |i: usize| &bars[i], |i: usize| &bars32[i];
+                (TrueRange::new(), generic::TrueRange::<f32>::new()),
+                (AverageTrueRange::new(5).unwrap(), generic::AverageTrueRange::<f32>::new(5).unwrap()),
+            );
+
+            let mut bb = BollingerBands::new(5, 2.0).unwrap();
+            let mut bb32 = generic::BollingerBands::<f32>::new(5, 2.0).unwrap();
+            for bar in &bars[..40] {
+                let output = bb.next(bar.close());
+                let output32 = bb32.next(bar.close() as f32);
+                assert!((output32.upper - output.upper as f32).abs() < 1e-3);
+                assert!((output32.lower - output.lower as f32).abs() < 1e-3);
+            }
+        }
     }
 }